@@ -0,0 +1,461 @@
+//! `ssenrah-cli`: the app's config checks for CI and headless servers.
+
+mod serve;
+
+use std::fs;
+use std::path::Path;
+use std::process::ExitCode;
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use ssenrah_core::bundle::{self, BundleInclude};
+use ssenrah_core::config::{self, read_json_file, read_scope};
+use ssenrah_core::doctor::{self, CheckStatus};
+use ssenrah_core::frontmatter::parse_frontmatter;
+use ssenrah_core::platform::paths::{self, resolve_agents_dir, resolve_mcp_path};
+use ssenrah_core::platform::portable;
+use ssenrah_core::schema::agent;
+use ssenrah_core::schema::diff::{self, ChangeKind};
+use ssenrah_core::validation;
+use ssenrah_core::{ConfigScope, IpcError, ValidationError, ValidationWarning};
+
+const USAGE: &str = "\
+Usage: ssenrah-cli <command> [options]
+
+Commands:
+  effective              Print the settings Claude Code ends up with
+  validate               Check settings, MCP files, and agent definitions
+  diff <left> <right>    Compare two settings sources key by key; each side is
+                         user, project, local, managed, effective, or a file
+  doctor                 Run the configuration health checks
+  export <file>          Write a .tar.gz bundle of the configuration
+  serve                  Serve the config operations as JSON-RPC over a
+                         WebSocket on 127.0.0.1 for remote frontends
+
+Options:
+  --project <dir>        Project to inspect (default: current directory)
+  --no-project           Only look at user and managed configuration
+  --scope <scope>        export: scope to include, repeatable (default: all)
+  --json                 Print machine-readable JSON
+  --port <port>          serve: port to listen on (default: 7421)
+  --read-only            serve: reject methods that write files
+  --portable             Keep ssenrah's own state beside the executable (also
+                         on when a portable.flag file is there)
+
+serve requires every client to present a token, as `Authorization: Bearer`
+or a `token` query parameter. It is read from SSENRAH_TOKEN, or generated
+and printed at startup.
+
+Exit status is 0 when everything passes, 1 when validate or doctor found
+errors or diff found differences, and 2 on usage or IO errors.";
+
+/// Parsed command line.
+#[derive(Debug, Default, PartialEq)]
+struct Args {
+    command: String,
+    operands: Vec<String>,
+    project: Option<String>,
+    no_project: bool,
+    scopes: Vec<String>,
+    json: bool,
+    port: Option<u16>,
+    read_only: bool,
+    portable: bool,
+}
+
+fn parse_args(raw: impl IntoIterator<Item = String>) -> Result<Args, String> {
+    let mut args = Args::default();
+    let mut raw = raw.into_iter();
+    while let Some(arg) = raw.next() {
+        match arg.as_str() {
+            "--json" => args.json = true,
+            "--no-project" => args.no_project = true,
+            "--read-only" => args.read_only = true,
+            "--portable" => args.portable = true,
+            "--project" | "--scope" | "--port" => {
+                let value = raw.next().ok_or_else(|| format!("{} needs a value", arg))?;
+                match arg.as_str() {
+                    "--project" => args.project = Some(value),
+                    "--scope" => args.scopes.push(value),
+                    _ => {
+                        let port = value
+                            .parse()
+                            .map_err(|_| format!("'{}' is not a port", value))?;
+                        args.port = Some(port);
+                    }
+                }
+            }
+            flag if flag.starts_with("--") => return Err(format!("Unknown option {}", flag)),
+            _ if args.command.is_empty() => args.command = arg,
+            _ => args.operands.push(arg),
+        }
+    }
+    let operands = match args.command.as_str() {
+        "effective" | "validate" | "doctor" | "serve" => 0,
+        "export" => 1,
+        "diff" => 2,
+        "" => return Err("Missing command".to_string()),
+        other => return Err(format!("Unknown command '{}'", other)),
+    };
+    if args.operands.len() != operands {
+        return Err(format!(
+            "'{}' takes {} argument(s), got {}",
+            args.command,
+            operands,
+            args.operands.len()
+        ));
+    }
+    Ok(args)
+}
+
+/// The project to inspect, as an absolute path.
+fn project_root(args: &Args) -> Result<Option<String>, IpcError> {
+    if args.no_project {
+        return Ok(None);
+    }
+    let dir = match &args.project {
+        Some(dir) => Path::new(dir).to_path_buf(),
+        None => std::env::current_dir().map_err(|e| IpcError::PlatformError {
+            message: format!("Failed to read the current directory: {}", e),
+        })?,
+    };
+    let dir = dir.canonicalize().map_err(|_| IpcError::NotFound {
+        path: dir.display().to_string(),
+    })?;
+    Ok(Some(dir.to_string_lossy().to_string()))
+}
+
+fn print_json(value: &impl Serialize) {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(value).unwrap_or_default()
+    );
+}
+
+fn effective(args: &Args, root: &Option<String>) -> Result<bool, IpcError> {
+    let effective = config::compute_effective(root)?;
+    if args.json {
+        print_json(&effective);
+    } else {
+        print_json(&effective.settings);
+    }
+    Ok(true)
+}
+
+/// Findings for one file.
+struct FileReport {
+    path: String,
+    errors: Vec<ValidationError>,
+    warnings: Vec<ValidationWarning>,
+}
+
+fn parse_failure(path: &Path, e: IpcError) -> FileReport {
+    FileReport {
+        path: path.display().to_string(),
+        errors: vec![ValidationError {
+            path: String::new(),
+            message: e.to_string(),
+            code: "JSON_PARSE_ERROR".to_string(),
+        }],
+        warnings: Vec::new(),
+    }
+}
+
+fn validate(args: &Args, root: &Option<String>) -> Result<bool, IpcError> {
+    let mut reports = Vec::new();
+
+    for scope in [
+        ConfigScope::User,
+        ConfigScope::Project,
+        ConfigScope::Local,
+        ConfigScope::Managed,
+    ] {
+        let Ok(path) = paths::resolve_settings_path(&scope, root) else {
+            continue;
+        };
+        match read_scope(&scope, root) {
+            Ok(Some(settings)) => {
+                let result = validation::validate_settings(&settings, root);
+                reports.push(FileReport {
+                    path: path.display().to_string(),
+                    errors: result.errors,
+                    warnings: result.warnings,
+                });
+            }
+            Ok(None) => {}
+            Err(e) => reports.push(parse_failure(&path, e)),
+        }
+    }
+
+    for source in ["user", "project", "managed"] {
+        let Ok(path) = resolve_mcp_path(source, root) else {
+            continue;
+        };
+        if let Err(e) = read_json_file(&path) {
+            reports.push(parse_failure(&path, e));
+        }
+    }
+
+    for scope in ["user", "project"] {
+        let Ok(dir) = resolve_agents_dir(scope, root) else {
+            continue;
+        };
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut files: Vec<_> = entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("md"))
+            .collect();
+        files.sort();
+        for path in files {
+            let content = fs::read_to_string(&path).unwrap_or_default();
+            let (frontmatter, body) = parse_frontmatter(&content);
+            let report = agent::validate_agent(&frontmatter, &body);
+            reports.push(FileReport {
+                path: path.display().to_string(),
+                errors: report.errors,
+                warnings: report.warnings,
+            });
+        }
+    }
+
+    let valid = reports.iter().all(|r| r.errors.is_empty());
+    if args.json {
+        let files: Vec<Value> = reports
+            .iter()
+            .map(|r| {
+                json!({
+                    "path": r.path,
+                    "valid": r.errors.is_empty(),
+                    "errors": r.errors,
+                    "warnings": r.warnings,
+                })
+            })
+            .collect();
+        print_json(&json!({ "valid": valid, "files": files }));
+    } else {
+        for report in &reports {
+            let findings = report
+                .errors
+                .iter()
+                .map(|e| {
+                    (
+                        "error",
+                        e.path.as_str(),
+                        e.message.as_str(),
+                        e.code.as_str(),
+                    )
+                })
+                .chain(report.warnings.iter().map(|w| {
+                    (
+                        "warning",
+                        w.path.as_str(),
+                        w.message.as_str(),
+                        w.code.as_str(),
+                    )
+                }));
+            for (level, key, message, code) in findings {
+                let at = if key.is_empty() {
+                    String::new()
+                } else {
+                    format!(" ({})", key)
+                };
+                println!("{}: {}{}: {} [{}]", level, report.path, at, message, code);
+            }
+        }
+        println!(
+            "{} file(s) checked, {}",
+            reports.len(),
+            if valid { "no errors" } else { "errors found" }
+        );
+    }
+    Ok(valid)
+}
+
+/// Reads one side of a diff. Missing files compare as `{}`.
+fn diff_side(side: &str, root: &Option<String>) -> Result<Value, IpcError> {
+    let scope = match side {
+        "effective" => return Ok(config::compute_effective(root)?.settings),
+        "user" => Some(ConfigScope::User),
+        "project" => Some(ConfigScope::Project),
+        "local" => Some(ConfigScope::Local),
+        "managed" => Some(ConfigScope::Managed),
+        _ => None,
+    };
+    let value = match scope {
+        Some(scope) => read_scope(&scope, root)?,
+        None => read_json_file(Path::new(side))?,
+    };
+    Ok(value.unwrap_or_else(|| json!({})))
+}
+
+fn diff(args: &Args, root: &Option<String>) -> Result<bool, IpcError> {
+    let left = diff_side(&args.operands[0], root)?;
+    let right = diff_side(&args.operands[1], root)?;
+    let changes = diff::diff(&left, &right);
+    if args.json {
+        print_json(&changes);
+    } else {
+        let show = |v: &Option<Value>| v.as_ref().map(Value::to_string).unwrap_or_default();
+        for change in &changes {
+            match change.kind {
+                ChangeKind::Added => println!("+ {}: {}", change.path, show(&change.right)),
+                ChangeKind::Removed => println!("- {}: {}", change.path, show(&change.left)),
+                ChangeKind::Changed => println!(
+                    "~ {}: {} -> {}",
+                    change.path,
+                    show(&change.left),
+                    show(&change.right)
+                ),
+            }
+        }
+    }
+    Ok(changes.is_empty())
+}
+
+fn doctor(args: &Args, root: &Option<String>) -> Result<bool, IpcError> {
+    let report = doctor::run(root, false);
+    if args.json {
+        print_json(&report);
+    } else {
+        for check in &report.checks {
+            let level = match check.status {
+                CheckStatus::Ok => "ok",
+                CheckStatus::Warning => "warning",
+                CheckStatus::Error => "error",
+            };
+            match &check.path {
+                Some(path) => println!("{}: {} ({})", level, check.message, path),
+                None => println!("{}: {}", level, check.message),
+            }
+            if check.status != CheckStatus::Ok {
+                if let Some(suggestion) = &check.suggestion {
+                    println!("  {}", suggestion);
+                }
+            }
+        }
+        println!("{} error(s), {} warning(s)", report.errors, report.warnings);
+    }
+    Ok(report.errors == 0)
+}
+
+fn export(args: &Args, root: &Option<String>) -> Result<bool, IpcError> {
+    let scopes = if !args.scopes.is_empty() {
+        args.scopes.clone()
+    } else if root.is_some() {
+        ["user", "project", "project_root", "local"]
+            .map(String::from)
+            .to_vec()
+    } else {
+        vec!["user".to_string()]
+    };
+    let result = bundle::export(
+        &scopes,
+        &BundleInclude::default(),
+        args.operands[0].clone(),
+        root,
+    )?;
+    if args.json {
+        print_json(&result);
+    } else {
+        println!(
+            "Wrote {} file(s) to {}",
+            result.manifest.files.len(),
+            result.path
+        );
+    }
+    Ok(true)
+}
+
+fn serve(args: &Args, root: Option<String>) -> Result<bool, IpcError> {
+    let token = match std::env::var("SSENRAH_TOKEN") {
+        Ok(token) if !token.is_empty() => token,
+        _ => {
+            let token = serve::generate_token()?;
+            eprintln!("ssenrah-cli: token {}", token);
+            token
+        }
+    };
+    let server = serve::Server {
+        project_root: root,
+        token,
+        read_only: args.read_only,
+    };
+    serve::run(server, args.port.unwrap_or(7421))?;
+    Ok(true)
+}
+
+fn main() -> ExitCode {
+    let raw: Vec<String> = std::env::args().skip(1).collect();
+    if raw.iter().any(|a| a == "-h" || a == "--help") {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+    let args = match parse_args(raw) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("ssenrah-cli: {}\n\n{}", message, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    portable::init(args.portable.then(|| portable::FLAG_ARG.to_string()));
+
+    let result = project_root(&args).and_then(|root| match args.command.as_str() {
+        "effective" => effective(&args, &root),
+        "validate" => validate(&args, &root),
+        "diff" => diff(&args, &root),
+        "doctor" => doctor(&args, &root),
+        "serve" => serve(&args, root),
+        _ => export(&args, &root),
+    });
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        Err(e) => {
+            eprintln!("ssenrah-cli: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Args, String> {
+        parse_args(args.iter().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn parses_commands_and_options() {
+        let args = parse(&["diff", "user", "--json", "effective", "--project", "/p"]).unwrap();
+        assert_eq!(args.command, "diff");
+        assert_eq!(args.operands, vec!["user", "effective"]);
+        assert_eq!(args.project.as_deref(), Some("/p"));
+        assert!(args.json);
+
+        let args = parse(&[
+            "export",
+            "out.tar.gz",
+            "--scope",
+            "user",
+            "--scope",
+            "local",
+        ])
+        .unwrap();
+        assert_eq!(args.scopes, vec!["user", "local"]);
+
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["lint"]).is_err());
+        assert!(parse(&["diff", "user"]).is_err());
+        assert!(parse(&["doctor", "--project"]).is_err());
+        assert!(parse(&["doctor", "--verbose"]).is_err());
+        assert_eq!(
+            parse(&["serve", "--port", "9000"]).unwrap().port,
+            Some(9000)
+        );
+        assert!(parse(&["serve", "--port", "http"]).is_err());
+    }
+}
@@ -0,0 +1,408 @@
+//! `ssenrah-cli serve`: the app's config operations as JSON-RPC 2.0 over a
+//! loopback WebSocket, for remote frontends and editor extensions.
+//!
+//! Methods carry the names and camelCase params of the matching IPC
+//! commands, so a client can route `invoke` calls here unchanged. Failures
+//! are JSON-RPC errors with code -32000 and the `IpcError` as `data`.
+
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::path::Path;
+use std::sync::Arc;
+use std::thread;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use ssenrah_core::bundle::{self, BundleInclude, ImportStrategy};
+use ssenrah_core::schema::merge::MergePolicy;
+use ssenrah_core::{config, doctor, validation};
+use ssenrah_core::{ConfigScope, IpcError, WritableScope};
+use tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tungstenite::http::StatusCode;
+use tungstenite::Message;
+
+/// A JSON-RPC error object.
+#[derive(Debug, Serialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        RpcError {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+}
+
+impl From<IpcError> for RpcError {
+    fn from(e: IpcError) -> Self {
+        RpcError {
+            code: -32000,
+            message: e.to_string(),
+            data: serde_json::to_value(&e).ok(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ScopeParams {
+    scope: ConfigScope,
+}
+
+#[derive(Deserialize)]
+struct WriteSettingsParams {
+    scope: WritableScope,
+    settings: Value,
+}
+
+#[derive(Deserialize)]
+struct PreviewParams {
+    scope: ConfigScope,
+    proposed: Value,
+}
+
+#[derive(Deserialize)]
+struct SourceParams {
+    source: String,
+}
+
+#[derive(Deserialize)]
+struct WriteMcpParams {
+    source: String,
+    config: Value,
+}
+
+#[derive(Deserialize)]
+struct SettingsParams {
+    settings: Value,
+}
+
+#[derive(Deserialize)]
+struct ExportParams {
+    scopes: Vec<String>,
+    include: Option<BundleInclude>,
+    destination: String,
+}
+
+#[derive(Deserialize)]
+struct ImportParams {
+    path: String,
+    strategy: ImportStrategy,
+    scopes: Option<Vec<String>>,
+}
+
+fn params<T: DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+    let params = if params.is_null() { json!({}) } else { params };
+    serde_json::from_value(params).map_err(|e| RpcError::new(-32602, e.to_string()))
+}
+
+fn to_result(value: impl Serialize) -> Result<Value, RpcError> {
+    serde_json::to_value(value).map_err(|e| RpcError::new(-32603, e.to_string()))
+}
+
+/// Serves one project to clients holding `token`.
+pub struct Server {
+    pub project_root: Option<String>,
+    pub token: String,
+    pub read_only: bool,
+}
+
+impl Server {
+    fn ensure_writable(&self) -> Result<(), RpcError> {
+        if self.read_only {
+            return Err(IpcError::ReadOnly {
+                message: "The server was started with --read-only.".to_string(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Runs one method.
+    fn call(&self, method: &str, p: Value) -> Result<Value, RpcError> {
+        let root = &self.project_root;
+        match method {
+            "read_settings" => {
+                let p: ScopeParams = params(p)?;
+                to_result(config::read_settings(&p.scope, root)?)
+            }
+            "write_settings" => {
+                self.ensure_writable()?;
+                let p: WriteSettingsParams = params(p)?;
+                to_result(config::write_settings(&p.scope, &p.settings, None, root)?)
+            }
+            "read_mcp_config" => {
+                let p: SourceParams = params(p)?;
+                to_result(config::read_mcp_config(&p.source, root)?)
+            }
+            "write_mcp_config" => {
+                self.ensure_writable()?;
+                let p: WriteMcpParams = params(p)?;
+                to_result(config::write_mcp_config(&p.source, &p.config, root)?)
+            }
+            "compute_effective_config" => to_result(config::compute_effective(root)?),
+            "compute_effective_hooks" => to_result(config::compute_effective_hooks(root)?),
+            "compute_scope_matrix" => to_result(config::compute_scope_matrix(root)?),
+            "preview_effective_with_edit" => {
+                let p: PreviewParams = params(p)?;
+                to_result(config::preview_effective_with_edit(
+                    &p.scope,
+                    &p.proposed,
+                    root,
+                )?)
+            }
+            "get_merge_policy" => to_result(MergePolicy::default()),
+            "list_known_tools" => to_result(config::list_known_tools(root)?),
+            "validate_settings" => {
+                let p: SettingsParams = params(p)?;
+                to_result(validation::validate_settings(&p.settings, root))
+            }
+            "run_doctor" => to_result(doctor::run(root, false)),
+            "export_config_bundle" => {
+                // The bundle is written to a caller-chosen path.
+                self.ensure_writable()?;
+                let p: ExportParams = params(p)?;
+                let include = p.include.unwrap_or_default();
+                to_result(bundle::export(&p.scopes, &include, p.destination, root)?)
+            }
+            "import_config_bundle" => {
+                self.ensure_writable()?;
+                let p: ImportParams = params(p)?;
+                let path = Path::new(&p.path);
+                to_result(bundle::import(path, p.strategy, p.scopes.as_deref(), root)?)
+            }
+            _ => Err(RpcError::new(
+                -32601,
+                format!("Method '{}' not found", method),
+            )),
+        }
+    }
+
+    /// Answers one request object, or `None` for a notification.
+    fn respond(&self, request: Value) -> Option<Value> {
+        let id = request.get("id").cloned();
+        let method = request.get("method").and_then(Value::as_str);
+        let result = match (request.get("jsonrpc").and_then(Value::as_str), method) {
+            (Some("2.0"), Some(method)) => {
+                let p = request.get("params").cloned().unwrap_or(Value::Null);
+                self.call(method, p)
+            }
+            _ => Err(RpcError::new(-32600, "Invalid request")),
+        };
+        // Notifications get no reply, but a malformed request always does.
+        if id.is_none() && method.is_some() {
+            return None;
+        }
+        let id = id.unwrap_or(Value::Null);
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(error) => json!({ "jsonrpc": "2.0", "id": id, "error": error }),
+        })
+    }
+
+    /// Answers a text frame: a single request or a batch.
+    pub fn handle(&self, text: &str) -> Option<String> {
+        let reply = match serde_json::from_str::<Value>(text) {
+            Ok(Value::Array(batch)) if !batch.is_empty() => {
+                let replies: Vec<Value> = batch
+                    .into_iter()
+                    .filter_map(|request| self.respond(request))
+                    .collect();
+                if replies.is_empty() {
+                    return None;
+                }
+                Value::Array(replies)
+            }
+            Ok(request) => self.respond(request)?,
+            Err(e) => json!({
+                "jsonrpc": "2.0",
+                "id": null,
+                "error": RpcError::new(-32700, e.to_string()),
+            }),
+        };
+        Some(reply.to_string())
+    }
+
+    /// Whether the handshake carries the token, as `Authorization: Bearer`
+    /// or, for browsers that cannot set headers, a `token` query parameter.
+    fn authorized(&self, request: &Request) -> bool {
+        let bearer = request
+            .headers()
+            .get("authorization")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "));
+        let query = request.uri().query().unwrap_or_default();
+        let from_query = query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("token="));
+        bearer
+            .into_iter()
+            .chain(from_query)
+            .any(|candidate| constant_time_eq(candidate.as_bytes(), self.token.as_bytes()))
+    }
+
+    // The handshake callback's error type is tungstenite's `ErrorResponse`.
+    #[allow(clippy::result_large_err)]
+    fn serve_connection(&self, stream: TcpStream) {
+        let peer = stream.peer_addr().ok();
+        let check = |request: &Request, response: Response| {
+            if self.authorized(request) {
+                Ok(response)
+            } else {
+                let mut denied = ErrorResponse::new(Some("Missing or wrong token".to_string()));
+                *denied.status_mut() = StatusCode::UNAUTHORIZED;
+                Err(denied)
+            }
+        };
+        let mut socket = match tungstenite::accept_hdr(stream, check) {
+            Ok(socket) => socket,
+            Err(e) => {
+                eprintln!("ssenrah-cli: rejected connection from {:?}: {}", peer, e);
+                return;
+            }
+        };
+        loop {
+            match socket.read() {
+                Ok(Message::Text(text)) => {
+                    if let Some(reply) = self.handle(&text) {
+                        if socket.send(Message::Text(reply)).is_err() {
+                            break;
+                        }
+                    }
+                }
+                Ok(Message::Close(_)) | Err(_) => break,
+                Ok(_) => {}
+            }
+        }
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// A random 128-bit token, hex encoded.
+pub fn generate_token() -> Result<String, IpcError> {
+    let mut bytes = [0u8; 16];
+    getrandom::getrandom(&mut bytes).map_err(|e| IpcError::PlatformError {
+        message: format!("Failed to generate a token: {}", e),
+    })?;
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// Listens on 127.0.0.1:`port` until the process is killed. Only loopback is
+/// bound; reach it from another machine through an SSH tunnel.
+pub fn run(server: Server, port: u16) -> Result<(), IpcError> {
+    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+    let listener = TcpListener::bind(addr).map_err(|e| IpcError::PlatformError {
+        message: format!("Failed to listen on {}: {}", addr, e),
+    })?;
+    eprintln!(
+        "ssenrah-cli: serving {} on ws://{}/",
+        server.project_root.as_deref().unwrap_or("(no project)"),
+        addr
+    );
+    let server = Arc::new(server);
+    for stream in listener.incoming().flatten() {
+        let server = Arc::clone(&server);
+        thread::spawn(move || server.serve_connection(stream));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server() -> Server {
+        Server {
+            project_root: None,
+            token: "secret".to_string(),
+            read_only: true,
+        }
+    }
+
+    fn call(text: &str) -> Value {
+        serde_json::from_str(&server().handle(text).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn answers_requests_with_results_and_errors() {
+        let ok = call(
+            r#"{"jsonrpc":"2.0","id":1,"method":"validate_settings","params":{"settings":{}}}"#,
+        );
+        assert_eq!(ok["id"], 1);
+        assert_eq!(ok["result"]["valid"], true);
+
+        let missing = call(r#"{"jsonrpc":"2.0","id":2,"method":"delete_everything"}"#);
+        assert_eq!(missing["error"]["code"], -32601);
+
+        let bad_params = call(r#"{"jsonrpc":"2.0","id":3,"method":"read_settings"}"#);
+        assert_eq!(bad_params["error"]["code"], -32602);
+
+        let read_only = call(
+            r#"{"jsonrpc":"2.0","id":4,"method":"write_settings","params":{"scope":"user","settings":{}}}"#,
+        );
+        assert_eq!(read_only["error"]["data"]["kind"], "read_only");
+
+        assert_eq!(call("{")["error"]["code"], -32700);
+        assert_eq!(call(r#"{"id":5}"#)["error"]["code"], -32600);
+        assert!(server()
+            .handle(r#"{"jsonrpc":"2.0","method":"run_doctor"}"#)
+            .is_none());
+
+        let batch = call(
+            r#"[{"jsonrpc":"2.0","id":6,"method":"validate_settings","params":{"settings":{}}},
+                {"jsonrpc":"2.0","method":"run_doctor"}]"#,
+        );
+        assert_eq!(batch.as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn read_only_server_rejects_methods_that_write_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let destination = tmp.path().join("bundle.tar.gz").display().to_string();
+        let requests = [
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "export_config_bundle",
+                "params": { "scopes": ["user"], "destination": destination },
+            }),
+            json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "import_config_bundle",
+                "params": { "path": destination, "strategy": "merge" },
+            }),
+        ];
+        for request in requests {
+            let response = call(&request.to_string());
+            assert_eq!(response["error"]["data"]["kind"], "read_only", "{}", request);
+        }
+        assert!(!Path::new(&destination).exists());
+    }
+
+    #[test]
+    fn requires_the_token() {
+        let request = |uri: &str, auth: Option<&str>| {
+            let mut builder = Request::builder().uri(uri);
+            if let Some(auth) = auth {
+                builder = builder.header("Authorization", auth);
+            }
+            builder.body(()).unwrap()
+        };
+        let server = server();
+        assert!(server.authorized(&request("/?token=secret", None)));
+        assert!(server.authorized(&request("/", Some("Bearer secret"))));
+        assert!(!server.authorized(&request("/", None)));
+        assert!(!server.authorized(&request("/?token=secre", None)));
+        assert!(!server.authorized(&request("/", Some("Bearer wrong"))));
+    }
+}
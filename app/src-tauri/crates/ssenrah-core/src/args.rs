@@ -0,0 +1,167 @@
+//! Validation of IPC command arguments.
+//!
+//! Commands check names, paths, enumerated values, and sizes here before
+//! touching the filesystem, so bad input fails with
+//! `IpcError::InvalidArgument` naming the parameter instead of surfacing
+//! later as a confusing filesystem error (or succeeding in the wrong
+//! place, e.g. an empty skill name writing `SKILL.md` into the skills
+//! directory itself).
+
+use std::path::{Component, Path};
+
+use crate::errors::IpcError;
+
+/// Longest file or directory name accepted, the common filesystem limit.
+pub const MAX_NAME_BYTES: usize = 255;
+
+/// Largest content a write command accepts.
+pub const MAX_CONTENT_BYTES: usize = 10 * 1024 * 1024;
+
+/// An `InvalidArgument` error for `argument`.
+pub fn invalid(argument: &str, message: impl Into<String>) -> IpcError {
+    IpcError::InvalidArgument {
+        argument: argument.to_string(),
+        message: message.into(),
+    }
+}
+
+/// Rejects an empty or whitespace-only value.
+pub fn non_empty<'a>(argument: &str, value: &'a str) -> Result<&'a str, IpcError> {
+    if value.trim().is_empty() {
+        return Err(invalid(argument, format!("{} cannot be empty", argument)));
+    }
+    Ok(value)
+}
+
+/// Rejects values not in `allowed`.
+pub fn one_of<'a>(argument: &str, value: &'a str, allowed: &[&str]) -> Result<&'a str, IpcError> {
+    if !allowed.contains(&value) {
+        return Err(invalid(
+            argument,
+            format!(
+                "Unknown {} '{}'. Expected one of: {}",
+                argument,
+                value,
+                allowed.join(", ")
+            ),
+        ));
+    }
+    Ok(value)
+}
+
+/// A single file or directory name: not empty, not `.` or `..`, without
+/// path separators or NUL, and at most `MAX_NAME_BYTES` long.
+pub fn file_name<'a>(argument: &str, value: &'a str) -> Result<&'a str, IpcError> {
+    let mut components = Path::new(value).components();
+    let single =
+        matches!(components.next(), Some(Component::Normal(_))) && components.next().is_none();
+    if !single || value.contains(['/', '\\', '\0']) {
+        return Err(invalid(
+            argument,
+            format!(
+                "Invalid {} '{}': expected a plain file name",
+                argument, value
+            ),
+        ));
+    }
+    if value.len() > MAX_NAME_BYTES {
+        return Err(invalid(
+            argument,
+            format!("{} is longer than {} bytes", argument, MAX_NAME_BYTES),
+        ));
+    }
+    Ok(value)
+}
+
+/// A relative path that stays inside the directory it is joined to: not
+/// empty, no root, `.` or `..` components, and every component a valid
+/// `file_name`. Both `/` and `\` separate components.
+pub fn relative_path<'a>(argument: &str, value: &'a str) -> Result<&'a str, IpcError> {
+    let inside = !value.is_empty()
+        && !value.contains('\0')
+        && Path::new(value)
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+        && value
+            .split(['/', '\\'])
+            .all(|part| !part.is_empty() && part != "." && part != "..");
+    if !inside {
+        return Err(invalid(
+            argument,
+            format!(
+                "Invalid {} '{}': expected a relative path without '..'",
+                argument, value
+            ),
+        ));
+    }
+    for part in value.split(['/', '\\']) {
+        file_name(argument, part)?;
+    }
+    Ok(value)
+}
+
+/// A user-chosen name that becomes a file name (profiles, baselines):
+/// letters, digits, `-`, `_`, and `.`, not starting with `.`.
+pub fn identifier<'a>(argument: &str, value: &'a str) -> Result<&'a str, IpcError> {
+    let valid = !value.is_empty()
+        && !value.starts_with('.')
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if !valid {
+        return Err(invalid(
+            argument,
+            format!(
+                "Invalid {} '{}': use letters, digits, '-', '_', or '.'",
+                argument, value
+            ),
+        ));
+    }
+    file_name(argument, value)
+}
+
+/// Rejects content longer than `limit` bytes.
+pub fn max_bytes(argument: &str, len: usize, limit: usize) -> Result<(), IpcError> {
+    if len > limit {
+        return Err(invalid(
+            argument,
+            format!(
+                "{} is {} bytes, more than the {} byte limit",
+                argument, len, limit
+            ),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argument_of(result: Result<&str, IpcError>) -> String {
+        match result {
+            Err(IpcError::InvalidArgument { argument, .. }) => argument,
+            other => panic!("expected InvalidArgument, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn checks_names_and_paths() {
+        assert_eq!(file_name("filename", "reviewer.md").unwrap(), "reviewer.md");
+        for bad in ["", ".", "..", "a/b", "a\\b", "/etc"] {
+            assert_eq!(argument_of(file_name("filename", bad)), "filename");
+        }
+        assert!(file_name("filename", &"x".repeat(256)).is_err());
+
+        assert!(relative_path("filename", "scripts/run.sh").is_ok());
+        for bad in ["", "../x", "a/../b", "/abs", "a//b", "./a"] {
+            assert!(relative_path("filename", bad).is_err(), "{}", bad);
+        }
+
+        assert!(identifier("name", "team-1.0").is_ok());
+        assert!(identifier("name", ".hidden").is_err());
+        assert_eq!(argument_of(one_of("level", "loud", &["info"])), "level");
+        assert!(non_empty("message", "  ").is_err());
+        assert!(max_bytes("content", 11, 10).is_err());
+    }
+}
@@ -0,0 +1,263 @@
+use std::cell::RefCell;
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::clock;
+use crate::errors::IpcError;
+use crate::platform::paths;
+
+/// Serializes appends so concurrent commands never interleave lines.
+static AUDIT_LOCK: Mutex<()> = Mutex::new(());
+
+thread_local! {
+    /// Name of the IPC command running on this thread, set by
+    /// [`with_command`].
+    static CURRENT_COMMAND: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Path of the audit trail: `{configDir}/ssenrah/audit.jsonl`.
+pub fn audit_path() -> Result<PathBuf, IpcError> {
+    Ok(paths::resolve_app_dir()?.join("audit.jsonl"))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    Write,
+    Delete,
+    Rename,
+}
+
+/// One line of `audit.jsonl`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditRecord {
+    /// Unix time in milliseconds.
+    pub timestamp: u64,
+    /// OS user running the app.
+    pub user: String,
+    /// IPC command that made the change, if known.
+    pub command: Option<String>,
+    pub action: AuditAction,
+    pub path: String,
+    /// Destination of a rename.
+    pub target: Option<String>,
+    /// Bytes written (writes only).
+    pub bytes: Option<u64>,
+    /// SHA-256 of the file before the change; `None` if it did not exist or
+    /// is a directory.
+    pub hash_before: Option<String>,
+    /// SHA-256 of the file after the change; `None` after a delete.
+    pub hash_after: Option<String>,
+}
+
+/// Filter for `read_audit_log`. All fields are optional and combine with AND.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditFilter {
+    pub command: Option<String>,
+    /// Substring matched against `path` and `target`.
+    pub path: Option<String>,
+    pub action: Option<AuditAction>,
+    /// Inclusive lower bound, Unix milliseconds.
+    pub since: Option<u64>,
+    /// Exclusive upper bound, Unix milliseconds.
+    pub until: Option<u64>,
+}
+
+impl AuditFilter {
+    pub fn matches(&self, record: &AuditRecord) -> bool {
+        self.command
+            .as_ref()
+            .is_none_or(|c| record.command.as_ref() == Some(c))
+            && self.path.as_ref().is_none_or(|p| {
+                record.path.contains(p.as_str())
+                    || record
+                        .target
+                        .as_ref()
+                        .is_some_and(|t| t.contains(p.as_str()))
+            })
+            && self.action.is_none_or(|a| record.action == a)
+            && self.since.is_none_or(|s| record.timestamp >= s)
+            && self.until.is_none_or(|u| record.timestamp < u)
+    }
+}
+
+/// Runs `f` with `command` recorded as the source of any audited change.
+pub fn with_command<T>(command: &str, f: impl FnOnce() -> T) -> T {
+    let previous = CURRENT_COMMAND.with(|c| c.replace(Some(command.to_string())));
+    let result = f();
+    CURRENT_COMMAND.with(|c| *c.borrow_mut() = previous);
+    result
+}
+
+/// Hex-encoded SHA-256 of `bytes`.
+pub fn hash(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Hash of a regular file's content, or `None` if it is missing or not a
+/// file.
+pub fn file_hash(path: &Path) -> Option<String> {
+    if !path.is_file() {
+        return None;
+    }
+    fs::read(path).ok().map(|bytes| hash(&bytes))
+}
+
+fn current_user() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+fn new_record(action: AuditAction, path: &Path) -> AuditRecord {
+    AuditRecord {
+        timestamp: clock::now_millis(),
+        user: current_user(),
+        command: CURRENT_COMMAND.with(|c| c.borrow().clone()),
+        action,
+        path: path.display().to_string(),
+        target: None,
+        bytes: None,
+        hash_before: None,
+        hash_after: None,
+    }
+}
+
+/// Appends a record to the audit trail. Auditing never fails the change it
+/// describes; errors are logged instead.
+fn append(record: &AuditRecord) {
+    let path = match audit_path() {
+        Ok(path) => path,
+        Err(e) => {
+            tracing::warn!(error = %e, "failed to write audit record");
+            return;
+        }
+    };
+    let _guard = AUDIT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let result = (|| -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)?
+            .write_all(&line)
+    })();
+    if let Err(e) = result {
+        tracing::warn!(error = %e, path = %path.display(), "failed to write audit record");
+    }
+}
+
+/// Records that `after` was written to `path`, replacing `before`.
+pub fn record_write(path: &Path, before: Option<&[u8]>, after: &[u8]) {
+    let mut record = new_record(AuditAction::Write, path);
+    record.bytes = Some(after.len() as u64);
+    record.hash_before = before.map(hash);
+    record.hash_after = Some(hash(after));
+    append(&record);
+}
+
+/// Records that `path` was replaced by copying or moving content into place,
+/// e.g. when a profile is activated.
+pub fn record_replace(path: &Path, hash_before: Option<String>) {
+    let mut record = new_record(AuditAction::Write, path);
+    record.bytes = fs::metadata(path)
+        .ok()
+        .filter(|m| m.is_file())
+        .map(|m| m.len());
+    record.hash_before = hash_before;
+    record.hash_after = file_hash(path);
+    append(&record);
+}
+
+/// Records the deletion of `path`. `hash_before` comes from [`file_hash`]
+/// taken before the file was removed.
+pub fn record_delete(path: &Path, hash_before: Option<String>) {
+    let mut record = new_record(AuditAction::Delete, path);
+    record.hash_before = hash_before;
+    append(&record);
+}
+
+/// Records that `from` was moved to `to`.
+pub fn record_rename(from: &Path, to: &Path) {
+    let mut record = new_record(AuditAction::Rename, from);
+    record.target = Some(to.display().to_string());
+    record.hash_after = file_hash(to);
+    record.hash_before = record.hash_after.clone();
+    append(&record);
+}
+
+/// Reads up to `limit` of the most recent matching records, oldest first.
+/// Returns an empty list if nothing has been audited yet.
+pub fn read_records(filter: &AuditFilter, limit: usize) -> Result<Vec<AuditRecord>, IpcError> {
+    let path = audit_path()?;
+    let file = match fs::File::open(&path) {
+        Ok(f) => f,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(IpcError::PlatformError {
+                message: format!("Failed to read {}: {}", path.display(), e),
+            })
+        }
+    };
+    let records: Vec<AuditRecord> = BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str::<AuditRecord>(&line).ok())
+        .filter(|r| filter.matches(r))
+        .collect();
+    let skip = records.len().saturating_sub(limit);
+    Ok(records.into_iter().skip(skip).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hashes_with_sha256() {
+        assert_eq!(
+            hash(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn filter_matches_records() {
+        let record = with_command("write_settings", || {
+            new_record(AuditAction::Write, Path::new("/p/.claude/settings.json"))
+        });
+        assert_eq!(record.command.as_deref(), Some("write_settings"));
+        assert!(new_record(AuditAction::Write, Path::new("/x"))
+            .command
+            .is_none());
+
+        assert!(AuditFilter::default().matches(&record));
+        let filter = AuditFilter {
+            command: Some("write_settings".to_string()),
+            path: Some("settings.json".to_string()),
+            action: Some(AuditAction::Write),
+            since: Some(record.timestamp),
+            until: Some(record.timestamp + 1),
+        };
+        assert!(filter.matches(&record));
+        let filter = AuditFilter {
+            action: Some(AuditAction::Delete),
+            ..Default::default()
+        };
+        assert!(!filter.matches(&record));
+    }
+}
@@ -0,0 +1,709 @@
+use std::fs;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::clock;
+use crate::config::read_json_file;
+use crate::errors::IpcError;
+use crate::io::atomic::atomic_write;
+use crate::io::copy::collect_files;
+use crate::io::journal::Transaction;
+use crate::platform::paths;
+use crate::schema::merge;
+
+/// Name of the manifest entry inside every bundle.
+const MANIFEST_NAME: &str = "manifest.json";
+
+/// Bundle format version written to the manifest.
+const BUNDLE_VERSION: u32 = 1;
+
+/// Largest single entry accepted on import, in bytes.
+const MAX_ENTRY_BYTES: u64 = 8 * 1024 * 1024;
+
+/// Largest total of all entries accepted on import, in bytes, so a small
+/// archive cannot decompress into an unbounded amount of memory.
+const MAX_BUNDLE_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Which artifact kinds to include in a bundle. Omitted fields default to
+/// `true`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleInclude {
+    #[serde(default = "default_true")]
+    pub settings: bool,
+    #[serde(default = "default_true")]
+    pub mcp: bool,
+    #[serde(default = "default_true")]
+    pub agents: bool,
+    #[serde(default = "default_true")]
+    pub skills: bool,
+    #[serde(default = "default_true")]
+    pub memory: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for BundleInclude {
+    fn default() -> Self {
+        Self {
+            settings: true,
+            mcp: true,
+            agents: true,
+            skills: true,
+            memory: true,
+        }
+    }
+}
+
+impl BundleInclude {
+    fn includes(&self, kind: &str) -> bool {
+        match kind {
+            "settings" => self.settings,
+            "mcp" => self.mcp,
+            "agents" => self.agents,
+            "skills" => self.skills,
+            "memory" => self.memory,
+            _ => false,
+        }
+    }
+}
+
+/// How `import` treats files that already exist.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportStrategy {
+    /// Replace existing files with the bundled version.
+    Overwrite,
+    /// Merge JSON files (bundle wins per field, per server for MCP files);
+    /// keep differing markdown.
+    Merge,
+    /// Only write files that do not exist yet.
+    SkipExisting,
+}
+
+/// Manifest stored as `manifest.json` at the root of the archive.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleManifest {
+    pub version: u32,
+    pub created_at: u64,
+    pub app_version: String,
+    pub files: Vec<BundleFile>,
+}
+
+/// One file inside a bundle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleFile {
+    /// Path inside the archive, e.g. `project/agents/reviewer.md`.
+    pub bundle_path: String,
+    pub scope: String,
+    pub kind: String,
+    pub size: u64,
+}
+
+/// Result of `export`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportBundleResult {
+    pub path: String,
+    pub manifest: BundleManifest,
+}
+
+/// Per-file outcome of an import.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportFileResult {
+    pub bundle_path: String,
+    pub target: String,
+    /// "written", "merged", "skipped", or "conflict".
+    pub action: String,
+    pub message: Option<String>,
+}
+
+/// Result of `import`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportBundleResult {
+    pub manifest: BundleManifest,
+    pub files: Vec<ImportFileResult>,
+}
+
+/// Base directory a bundle scope prefix maps to.
+///
+/// - "user"         -> {configDir}/
+/// - "project"      -> {projectRoot}/.claude/
+/// - "project_root" -> {projectRoot}/
+/// - "local"        -> {projectRoot}/.claude/
+fn scope_base(scope: &str, project_root: &Option<String>) -> Result<PathBuf, IpcError> {
+    let root = || {
+        project_root.as_ref().map(PathBuf::from).ok_or_else(|| IpcError::NoProject {
+            message: format!("No project is open. Open a project to use {} scope.", scope),
+        })
+    };
+
+    match scope {
+        "user" => paths::resolve_config_dir(),
+        "project" | "local" => Ok(root()?.join(".claude")),
+        "project_root" => root(),
+        _ => Err(IpcError::InvalidArgument {
+            argument: "scopes".to_string(),
+            message: format!("Unknown bundle scope: {}", scope),
+        }),
+    }
+}
+
+/// Lists `(kind, relative path)` items a scope contributes to a bundle.
+/// Directories are expanded recursively at export time.
+fn scope_items(scope: &str) -> &'static [(&'static str, &'static str)] {
+    match scope {
+        "user" => &[
+            ("settings", "settings.json"),
+            ("mcp", "mcp.json"),
+            ("memory", "CLAUDE.md"),
+            ("agents", "agents"),
+            ("skills", "commands"),
+        ],
+        "project" => &[
+            ("settings", "settings.json"),
+            ("mcp", ".mcp.json"),
+            ("memory", "CLAUDE.md"),
+            ("agents", "agents"),
+            ("skills", "commands"),
+        ],
+        "project_root" => &[("memory", "CLAUDE.md")],
+        "local" => &[
+            ("settings", "settings.local.json"),
+            ("memory", "CLAUDE.local.md"),
+        ],
+        _ => &[],
+    }
+}
+
+/// Converts a relative path to a forward-slash archive path.
+fn to_bundle_path(scope: &str, rel: &Path) -> String {
+    let parts: Vec<String> = rel
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+    format!("{}/{}", scope, parts.join("/"))
+}
+
+/// Reads the user-scope MCP servers from ~/.claude.json as a standalone
+/// `{ mcpServers }` document. Other keys (tokens, caches) are never exported.
+fn read_user_mcp() -> Result<Option<Vec<u8>>, IpcError> {
+    let path = paths::home_dir()?.join(".claude.json");
+    let servers = match read_json_file(&path)? {
+        Some(v) => v.get("mcpServers").cloned(),
+        None => None,
+    };
+
+    match servers {
+        Some(servers) => {
+            let doc = serde_json::json!({ "mcpServers": servers });
+            serde_json::to_vec_pretty(&doc)
+                .map(Some)
+                .map_err(|e| IpcError::PlatformError {
+                    message: format!("Failed to serialize MCP servers: {}", e),
+                })
+        }
+        None => Ok(None),
+    }
+}
+
+/// Exports configuration into a single `.tar.gz` bundle.
+///
+/// `scopes` selects from "user", "project", "project_root", and "local";
+/// `include` selects artifact kinds. The archive contains `manifest.json`
+/// plus one entry per file under `{scope}/...`. For the user scope only the
+/// `mcpServers` section of ~/.claude.json is exported (as `user/mcp.json`).
+pub fn export(
+    scopes: &[String],
+    include: &BundleInclude,
+    destination: String,
+    project_root: &Option<String>,
+) -> Result<ExportBundleResult, IpcError> {
+    let mut entries: Vec<(BundleFile, Vec<u8>)> = Vec::new();
+
+    for scope in scopes {
+        let base = scope_base(scope, project_root)?;
+
+        for (kind, item) in scope_items(scope) {
+            if !include.includes(kind) {
+                continue;
+            }
+
+            if scope == "user" && *kind == "mcp" {
+                if let Some(bytes) = read_user_mcp()? {
+                    entries.push((
+                        BundleFile {
+                            bundle_path: format!("user/{}", item),
+                            scope: scope.clone(),
+                            kind: kind.to_string(),
+                            size: bytes.len() as u64,
+                        },
+                        bytes,
+                    ));
+                }
+                continue;
+            }
+
+            let mut files = Vec::new();
+            collect_files(&base, &base.join(item), &mut files);
+            files.sort();
+
+            for rel in files {
+                let abs = base.join(&rel);
+                let bytes = fs::read(&abs).map_err(|e| IpcError::PlatformError {
+                    message: format!("Failed to read {}: {}", abs.display(), e),
+                })?;
+                entries.push((
+                    BundleFile {
+                        bundle_path: to_bundle_path(scope, &rel),
+                        scope: scope.clone(),
+                        kind: kind.to_string(),
+                        size: bytes.len() as u64,
+                    },
+                    bytes,
+                ));
+            }
+        }
+    }
+
+    let manifest = BundleManifest {
+        version: BUNDLE_VERSION,
+        created_at: clock::now_secs(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        files: entries.iter().map(|(f, _)| f.clone()).collect(),
+    };
+
+    let archive = build_archive(&manifest, &entries).map_err(|e| IpcError::WriteFailed {
+        path: destination.clone(),
+        message: format!("Failed to build bundle: {}", e),
+    })?;
+
+    atomic_write(Path::new(&destination), &archive)?;
+
+    Ok(ExportBundleResult {
+        path: destination,
+        manifest,
+    })
+}
+
+/// Builds a gzipped tarball containing the manifest and all entries.
+fn build_archive(
+    manifest: &BundleManifest,
+    entries: &[(BundleFile, Vec<u8>)],
+) -> std::io::Result<Vec<u8>> {
+    let encoder = GzEncoder::new(Vec::new(), Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+
+    let manifest_bytes = serde_json::to_vec_pretty(manifest)?;
+    append_bytes(&mut builder, MANIFEST_NAME, &manifest_bytes)?;
+
+    for (file, bytes) in entries {
+        append_bytes(&mut builder, &file.bundle_path, bytes)?;
+    }
+
+    builder.into_inner()?.finish()
+}
+
+fn append_bytes<W: std::io::Write>(
+    builder: &mut tar::Builder<W>,
+    name: &str,
+    bytes: &[u8],
+) -> std::io::Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(bytes.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(clock::now_secs());
+    header.set_cksum();
+    builder.append_data(&mut header, name, bytes)
+}
+
+/// `(bundle path, bytes)` pairs read from an archive, excluding the manifest.
+type ArchiveEntries = Vec<(String, Vec<u8>)>;
+
+/// Reads a bundle into its manifest and entries.
+fn read_archive(path: &Path) -> Result<(BundleManifest, ArchiveEntries), IpcError> {
+    let path_str = path.display().to_string();
+    let file = fs::File::open(path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => IpcError::NotFound {
+            path: path_str.clone(),
+        },
+        _ => IpcError::PlatformError {
+            message: format!("Failed to open {}: {}", path_str, e),
+        },
+    })?;
+
+    let parse_err = |message: String| IpcError::ParseError {
+        path: path_str.clone(),
+        message,
+    };
+
+    let mut archive = tar::Archive::new(GzDecoder::new(file));
+    let mut manifest: Option<BundleManifest> = None;
+    let mut entries = Vec::new();
+    let mut total = 0;
+
+    for entry in archive.entries().map_err(|e| parse_err(e.to_string()))? {
+        let mut entry = entry.map_err(|e| parse_err(e.to_string()))?;
+        let name = entry
+            .path()
+            .map_err(|e| parse_err(e.to_string()))?
+            .to_string_lossy()
+            .to_string();
+        let mut bytes = Vec::new();
+        (&mut entry)
+            .take(MAX_ENTRY_BYTES + 1)
+            .read_to_end(&mut bytes)
+            .map_err(|e| parse_err(e.to_string()))?;
+        if bytes.len() as u64 > MAX_ENTRY_BYTES {
+            return Err(parse_err(format!(
+                "Entry {} is larger than {} bytes",
+                name, MAX_ENTRY_BYTES
+            )));
+        }
+        total += bytes.len() as u64;
+        if total > MAX_BUNDLE_BYTES {
+            return Err(parse_err(format!(
+                "Bundle contents are larger than {} bytes",
+                MAX_BUNDLE_BYTES
+            )));
+        }
+
+        if name == MANIFEST_NAME {
+            manifest = Some(
+                serde_json::from_slice(&bytes)
+                    .map_err(|e| parse_err(format!("Invalid manifest: {}", e)))?,
+            );
+        } else {
+            entries.push((name, bytes));
+        }
+    }
+
+    let manifest = manifest.ok_or_else(|| parse_err("Bundle has no manifest.json".to_string()))?;
+    if manifest.version > BUNDLE_VERSION {
+        return Err(parse_err(format!(
+            "Bundle version {} is newer than supported version {}",
+            manifest.version, BUNDLE_VERSION
+        )));
+    }
+
+    Ok((manifest, entries))
+}
+
+/// Splits `scope/rest/of/path` and rejects absolute or `..` components so a
+/// crafted bundle cannot write outside the scope directory.
+fn split_bundle_path(bundle_path: &str) -> Option<(&str, PathBuf)> {
+    let (scope, rest) = bundle_path.split_once('/')?;
+    let rel = PathBuf::from(rest);
+    let safe = !rest.is_empty() && rel.components().all(|c| matches!(c, Component::Normal(_)));
+    safe.then_some((scope, rel))
+}
+
+/// Restores a bundle created by `export`.
+///
+/// `strategy` decides what happens to files that already exist:
+/// - `overwrite`: replace them
+/// - `merge`: settings are merged (bundle wins per field, using the same
+///   semantics as the effective-config merge); MCP files keep every existing
+///   server and take bundled servers whole; differing markdown is left alone
+///   and reported as a conflict
+/// - `skip_existing`: leave them untouched
+///
+/// Only scopes listed in `scopes` (default: all in the bundle) are restored.
+/// Every file gets an entry in the per-file report. All files are written in
+/// one journaled transaction, so a failure leaves the configuration as it
+/// was.
+pub fn import(
+    path: &Path,
+    strategy: ImportStrategy,
+    scopes: Option<&[String]>,
+    project_root: &Option<String>,
+) -> Result<ImportBundleResult, IpcError> {
+    let (manifest, entries) = read_archive(path)?;
+    let mut files = Vec::new();
+    let mut txn = Transaction::begin("import-bundle")?;
+
+    for (bundle_path, bytes) in entries {
+        let (scope, rel) = match split_bundle_path(&bundle_path) {
+            Some(parts) => parts,
+            None => {
+                files.push(ImportFileResult {
+                    bundle_path,
+                    target: String::new(),
+                    action: "skipped".to_string(),
+                    message: Some("Unsafe path in bundle".to_string()),
+                });
+                continue;
+            }
+        };
+
+        if let Some(wanted) = scopes {
+            if !wanted.iter().any(|s| s == scope) {
+                files.push(ImportFileResult {
+                    bundle_path,
+                    target: String::new(),
+                    action: "skipped".to_string(),
+                    message: Some("Scope not selected".to_string()),
+                });
+                continue;
+            }
+        }
+
+        let result = if scope == "user" && rel == Path::new("mcp.json") {
+            import_user_mcp(&mut txn, &bundle_path, &bytes, strategy)?
+        } else {
+            let base = scope_base(scope, project_root)?;
+            import_file(&mut txn, &bundle_path, &base.join(&rel), &bytes, strategy)?
+        };
+        files.push(result);
+    }
+    txn.commit()?;
+
+    Ok(ImportBundleResult { manifest, files })
+}
+
+/// Stages one bundled file for `target` according to `strategy`.
+fn import_file(
+    txn: &mut Transaction,
+    bundle_path: &str,
+    target: &Path,
+    bytes: &[u8],
+    strategy: ImportStrategy,
+) -> Result<ImportFileResult, IpcError> {
+    let target_str = target.display().to_string();
+    let result = |action: &str, message: Option<String>| ImportFileResult {
+        bundle_path: bundle_path.to_string(),
+        target: target_str.clone(),
+        action: action.to_string(),
+        message,
+    };
+
+    let mut content = bytes.to_vec();
+    let mut action = "written";
+
+    if target.exists() {
+        match strategy {
+            ImportStrategy::SkipExisting => {
+                return Ok(result("skipped", Some("File already exists".to_string())));
+            }
+            ImportStrategy::Overwrite => {}
+            ImportStrategy::Merge => {
+                let existing = fs::read(target).map_err(|e| IpcError::PlatformError {
+                    message: format!("Failed to read {}: {}", target_str, e),
+                })?;
+                if existing == bytes {
+                    return Ok(result("skipped", Some("Identical content".to_string())));
+                }
+
+                let is_json = target.extension().is_some_and(|e| e == "json");
+                let parsed = (
+                    serde_json::from_slice::<Value>(&existing),
+                    serde_json::from_slice::<Value>(bytes),
+                );
+                match (is_json, parsed) {
+                    (true, (Ok(existing), Ok(incoming))) => {
+                        // The effective-config merge replaces `mcpServers`
+                        // wholesale, which would drop servers the bundle
+                        // does not mention.
+                        let merged = if target.file_name().is_some_and(|n| n == ".mcp.json") {
+                            merge_mcp_servers(existing, incoming)
+                        } else {
+                            merge::compute_effective(Some(&existing), Some(&incoming), None, None)
+                                .settings
+                        };
+                        content = serde_json::to_vec_pretty(&merged).map_err(|e| {
+                            IpcError::WriteFailed {
+                                path: target_str.clone(),
+                                message: format!("Failed to serialize merged file: {}", e),
+                            }
+                        })?;
+                        action = "merged";
+                    }
+                    _ => {
+                        return Ok(result(
+                            "conflict",
+                            Some("Existing file differs and cannot be merged".to_string()),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    txn.write(target, &content)?;
+
+    Ok(result(action, None))
+}
+
+/// `existing` with the keys of `incoming` added; `mcpServers` is merged per
+/// server, the bundled definition winning, as `import_user_mcp` does.
+fn merge_mcp_servers(existing: Value, incoming: Value) -> Value {
+    let mut doc = match existing {
+        Value::Object(m) => m,
+        _ => serde_json::Map::new(),
+    };
+    let Value::Object(incoming) = incoming else {
+        return Value::Object(doc);
+    };
+    for (key, value) in incoming {
+        match (key.as_str(), doc.get_mut(&key), value) {
+            ("mcpServers", Some(Value::Object(servers)), Value::Object(incoming_servers)) => {
+                servers.extend(incoming_servers);
+            }
+            (_, _, value) => {
+                doc.insert(key, value);
+            }
+        }
+    }
+    Value::Object(doc)
+}
+
+/// Restores bundled user MCP servers into ~/.claude.json, preserving every
+/// other key in that file.
+fn import_user_mcp(
+    txn: &mut Transaction,
+    bundle_path: &str,
+    bytes: &[u8],
+    strategy: ImportStrategy,
+) -> Result<ImportFileResult, IpcError> {
+    let target = paths::home_dir()?.join(".claude.json");
+    let target_str = target.display().to_string();
+    txn.lock(&target);
+
+    let incoming: Value = serde_json::from_slice(bytes).map_err(|e| IpcError::ParseError {
+        path: bundle_path.to_string(),
+        message: e.to_string(),
+    })?;
+    let incoming_servers = incoming
+        .get("mcpServers")
+        .and_then(|v| v.as_object())
+        .cloned()
+        .unwrap_or_default();
+
+    let mut doc = match read_json_file(&target)? {
+        Some(Value::Object(m)) => m,
+        _ => serde_json::Map::new(),
+    };
+    let mut servers = doc
+        .get("mcpServers")
+        .and_then(|v| v.as_object())
+        .cloned()
+        .unwrap_or_default();
+
+    let had_servers = !servers.is_empty();
+    let mut skipped = Vec::new();
+    match strategy {
+        ImportStrategy::Overwrite => servers = incoming_servers,
+        ImportStrategy::Merge => servers.extend(incoming_servers),
+        ImportStrategy::SkipExisting => {
+            for (name, def) in incoming_servers {
+                if servers.contains_key(&name) {
+                    skipped.push(name);
+                } else {
+                    servers.insert(name, def);
+                }
+            }
+        }
+    }
+
+    doc.insert("mcpServers".to_string(), Value::Object(servers));
+    let content = serde_json::to_vec_pretty(&Value::Object(doc)).map_err(|e| {
+        IpcError::WriteFailed {
+            path: target_str.clone(),
+            message: format!("Failed to serialize config: {}", e),
+        }
+    })?;
+    txn.write(&target, &content)?;
+
+    let action = match strategy {
+        ImportStrategy::Merge if had_servers => "merged",
+        _ => "written",
+    };
+    Ok(ImportFileResult {
+        bundle_path: bundle_path.to_string(),
+        target: target_str,
+        action: action.to_string(),
+        message: (!skipped.is_empty())
+            .then(|| format!("Kept existing servers: {}", skipped.join(", "))),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn merging_project_mcp_keeps_unbundled_servers() {
+        let existing = json!({
+            "mcpServers": {
+                "local": { "command": "local-server" },
+                "shared": { "command": "old", "env": { "TOKEN": "x" } },
+            }
+        });
+        let incoming = json!({
+            "mcpServers": {
+                "shared": { "command": "new" },
+                "bundled": { "type": "http", "url": "https://example.com/mcp" },
+            }
+        });
+
+        let merged = merge_mcp_servers(existing, incoming);
+        assert_eq!(
+            merged,
+            json!({
+                "mcpServers": {
+                    "local": { "command": "local-server" },
+                    "shared": { "command": "new" },
+                    "bundled": { "type": "http", "url": "https://example.com/mcp" },
+                }
+            })
+        );
+
+        let merged = merge_mcp_servers(json!({}), json!({ "mcpServers": { "a": {} } }));
+        assert_eq!(merged, json!({ "mcpServers": { "a": {} } }));
+    }
+
+    #[test]
+    fn rejects_oversized_entries() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let manifest = BundleManifest {
+            version: BUNDLE_VERSION,
+            created_at: 0,
+            app_version: "test".to_string(),
+            files: Vec::new(),
+        };
+        let file = |size: u64| {
+            let entry = BundleFile {
+                bundle_path: "user/CLAUDE.md".to_string(),
+                scope: "user".to_string(),
+                kind: "memory".to_string(),
+                size,
+            };
+            (entry, vec![b'x'; size as usize])
+        };
+
+        let path = dir.join("ok.tar.gz");
+        fs::write(&path, build_archive(&manifest, &[file(16)]).unwrap()).unwrap();
+        let (_, entries) = read_archive(&path).unwrap();
+        assert_eq!(entries[0].1.len(), 16);
+
+        let path = dir.join("large.tar.gz");
+        let archive = build_archive(&manifest, &[file(MAX_ENTRY_BYTES + 1)]).unwrap();
+        fs::write(&path, archive).unwrap();
+        let err = read_archive(&path).unwrap_err();
+        assert!(matches!(err, IpcError::ParseError { .. }));
+    }
+}
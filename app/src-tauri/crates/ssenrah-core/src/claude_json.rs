@@ -0,0 +1,467 @@
+//! Typed access to `~/.claude.json`, which besides the user MCP servers holds
+//! per-project state (trust, allowed tools, history), onboarding flags, and
+//! the logged-in account. Top-level keys are grouped into sections so that
+//! readers and change events can deal with one concern at a time.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use xxhash_rust::xxh3::xxh3_64;
+
+use crate::config::read_json_file;
+use crate::errors::IpcError;
+use crate::io::lock::lock_path;
+use crate::io::text;
+use crate::platform::paths;
+use crate::types::ValidationError;
+
+/// Sections `read_section` accepts, in display order.
+pub const SECTIONS: &[&str] = &["mcpServers", "projects", "onboarding", "account", "other"];
+
+const ONBOARDING_KEYS: &[&str] = &[
+    "hasCompletedOnboarding",
+    "lastOnboardingVersion",
+    "lastReleaseNotesSeen",
+    "numStartups",
+    "firstStartTime",
+    "tipsHistory",
+];
+
+const ACCOUNT_KEYS: &[&str] = &[
+    "oauthAccount",
+    "userID",
+    "primaryApiKey",
+    "customApiKeyResponses",
+];
+
+/// Per-directory state Claude Code keeps under `projects`, keyed by the
+/// absolute directory path. Unknown keys are kept in `extra`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectEntry {
+    /// The user accepted the "do you trust the files in this folder" dialog.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub has_trust_dialog_accepted: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_tools: Option<Vec<String>>,
+    /// Prompt history; entries are kept as-is.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history: Option<Vec<Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcp_servers: Option<Map<String, Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled_mcpjson_servers: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disabled_mcpjson_servers: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub has_claude_md_external_includes_approved: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_onboarding_seen_count: Option<u64>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// First-run and release-notes state.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Onboarding {
+    pub has_completed_onboarding: Option<bool>,
+    pub last_onboarding_version: Option<String>,
+    pub last_release_notes_seen: Option<String>,
+    pub num_startups: Option<u64>,
+    pub first_start_time: Option<String>,
+    pub tips_history: Option<Map<String, Value>>,
+}
+
+/// The logged-in account. API keys stored next to it are never returned.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Account {
+    pub oauth_account: Option<Map<String, Value>>,
+    #[serde(rename = "userID")]
+    pub user_id: Option<String>,
+}
+
+/// One section of `~/.claude.json`, as returned by `read_section`.
+#[derive(Debug, Serialize)]
+#[serde(tag = "section", content = "value", rename_all = "camelCase")]
+pub enum ClaudeJsonSection {
+    McpServers(Map<String, Value>),
+    Projects(BTreeMap<String, ProjectEntry>),
+    Onboarding(Onboarding),
+    Account(Account),
+    /// Top-level keys not in any other section.
+    Other(Map<String, Value>),
+}
+
+/// Whether Claude Code trusts a directory, per `projects` in ~/.claude.json.
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectTrust {
+    pub path: String,
+    /// The `projects` key recorded for the directory itself, if any.
+    pub key: Option<String>,
+    pub trusted: bool,
+    /// Trusted ancestor the trust comes from. Claude Code does not ask
+    /// again anywhere below a trusted directory.
+    pub inherited_from: Option<String>,
+}
+
+/// Path of `~/.claude.json`.
+pub fn claude_json_path() -> Result<PathBuf, IpcError> {
+    Ok(paths::home_dir()?.join(".claude.json"))
+}
+
+/// The section a top-level key belongs to.
+pub fn section_of(key: &str) -> &'static str {
+    match key {
+        "mcpServers" => "mcpServers",
+        "projects" => "projects",
+        k if ONBOARDING_KEYS.contains(&k) => "onboarding",
+        k if ACCOUNT_KEYS.contains(&k) => "account",
+        _ => "other",
+    }
+}
+
+fn section_keys<'a>(
+    json: &'a Value,
+    section: &'a str,
+) -> impl Iterator<Item = (&'a String, &'a Value)> {
+    json.as_object()
+        .into_iter()
+        .flatten()
+        .filter(move |(key, _)| section_of(key) == section)
+}
+
+fn deserialize<T: serde::de::DeserializeOwned>(value: Value, prefix: &str) -> Result<T, IpcError> {
+    serde_path_to_error::deserialize(value).map_err(|e| {
+        let path = e.path().to_string();
+        IpcError::ValidationError {
+            errors: vec![ValidationError {
+                path: match path.as_str() {
+                    "." => prefix.to_string(),
+                    _ if prefix.is_empty() => path,
+                    _ => format!("{}.{}", prefix, path),
+                },
+                message: e.into_inner().to_string(),
+                code: "INVALID_TYPE".to_string(),
+            }],
+        }
+    })
+}
+
+/// Extracts `section` from the parsed file, type-checking known fields.
+pub fn parse_section(json: &Value, section: &str) -> Result<ClaudeJsonSection, IpcError> {
+    let field = |key: &str| json.get(key).cloned().unwrap_or(Value::Object(Map::new()));
+    let grouped = || -> Value {
+        Value::Object(
+            section_keys(json, section)
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+        )
+    };
+    Ok(match section {
+        "mcpServers" => {
+            ClaudeJsonSection::McpServers(deserialize(field("mcpServers"), "mcpServers")?)
+        }
+        "projects" => ClaudeJsonSection::Projects(deserialize(field("projects"), "projects")?),
+        "onboarding" => ClaudeJsonSection::Onboarding(deserialize(grouped(), "")?),
+        "account" => ClaudeJsonSection::Account(deserialize(grouped(), "")?),
+        "other" => ClaudeJsonSection::Other(deserialize(grouped(), "")?),
+        _ => {
+            return Err(IpcError::InvalidArgument {
+                argument: "section".to_string(),
+                message: format!(
+                    "Unknown ~/.claude.json section '{}'; expected one of: {}",
+                    section,
+                    SECTIONS.join(", ")
+                ),
+            })
+        }
+    })
+}
+
+/// Reads one section of the file at `path`, or `None` if it does not exist.
+pub fn read_section(path: &Path, section: &str) -> Result<Option<ClaudeJsonSection>, IpcError> {
+    read_json_file(path)?
+        .map(|json| parse_section(&json, section))
+        .transpose()
+}
+
+/// A hash of each section present in `json`, for telling which sections
+/// changed between two versions of the file.
+pub fn section_hashes(json: &Value) -> BTreeMap<&'static str, u64> {
+    SECTIONS
+        .iter()
+        .filter_map(|&section| {
+            let mut keys = section_keys(json, section).peekable();
+            keys.peek()?;
+            let text: String = keys.map(|(k, v)| format!("{}={}\n", k, v)).collect();
+            Some((section, xxh3_64(text.as_bytes())))
+        })
+        .collect()
+}
+
+/// Sections added, removed, or changed between two `section_hashes` results.
+pub fn changed_sections(
+    old: &BTreeMap<&'static str, u64>,
+    new: &BTreeMap<&'static str, u64>,
+) -> Vec<&'static str> {
+    SECTIONS
+        .iter()
+        .copied()
+        .filter(|section| old.get(section) != new.get(section))
+        .collect()
+}
+
+/// Reads the file at `path` as a JSON object for editing.
+fn read_object(path: &Path) -> Result<Map<String, Value>, IpcError> {
+    match read_json_file(path)? {
+        Some(Value::Object(obj)) => Ok(obj),
+        Some(_) => Err(IpcError::ParseError {
+            path: path.display().to_string(),
+            message: "expected a JSON object".to_string(),
+        }),
+        None => Err(IpcError::NotFound {
+            path: path.display().to_string(),
+        }),
+    }
+}
+
+fn write_object(path: &Path, obj: Map<String, Value>) -> Result<(), IpcError> {
+    let text =
+        serde_json::to_string_pretty(&Value::Object(obj)).map_err(|e| IpcError::WriteFailed {
+            path: path.display().to_string(),
+            message: e.to_string(),
+        })?;
+    text::write_text(path, &text, None)
+}
+
+fn not_found(dir: &str) -> IpcError {
+    IpcError::NotFound {
+        path: format!("projects.{}", dir),
+    }
+}
+
+/// Applies `patch` to the `projects` entry for `dir` in the file at `path`:
+/// each key is set, or removed when its value is `null`. The patched entry
+/// must still type-check. Every other part of the file is kept.
+pub fn update_project_entry(
+    path: &Path,
+    dir: &str,
+    patch: &Map<String, Value>,
+) -> Result<ProjectEntry, IpcError> {
+    let _lock = lock_path(path);
+    let mut obj = read_object(path)?;
+    let entry = obj
+        .get_mut("projects")
+        .and_then(|p| p.get_mut(dir))
+        .and_then(|e| e.as_object_mut())
+        .ok_or_else(|| not_found(dir))?;
+    for (key, value) in patch {
+        match value {
+            Value::Null => entry.remove(key),
+            _ => entry.insert(key.clone(), value.clone()),
+        };
+    }
+    let updated: ProjectEntry =
+        deserialize(Value::Object(entry.clone()), &format!("projects.{}", dir))?;
+
+    write_object(path, obj)?;
+    Ok(updated)
+}
+
+/// Removes the `projects` entry for `dir` from the file at `path`, so Claude
+/// Code treats the directory as never opened.
+pub fn remove_project_entry(path: &Path, dir: &str) -> Result<(), IpcError> {
+    let _lock = lock_path(path);
+    let mut obj = read_object(path)?;
+    obj.get_mut("projects")
+        .and_then(|p| p.as_object_mut())
+        .and_then(|p| p.remove(dir))
+        .ok_or_else(|| not_found(dir))?;
+    write_object(path, obj)
+}
+
+/// The `projects` key for `dir`: an exact match, or else a key naming the
+/// same directory spelled differently (symlinks, trailing separator).
+fn find_project_key(projects: &Map<String, Value>, dir: &Path) -> Option<String> {
+    let exact = dir.to_string_lossy();
+    if projects.contains_key(exact.as_ref()) {
+        return Some(exact.into_owned());
+    }
+    let wanted = paths::normalize(dir);
+    projects
+        .keys()
+        .find(|key| paths::normalize(Path::new(key)) == wanted)
+        .cloned()
+}
+
+fn is_trusted(projects: &Map<String, Value>, key: &str) -> bool {
+    projects
+        .get(key)
+        .and_then(|e| e.get("hasTrustDialogAccepted"))
+        .and_then(|t| t.as_bool())
+        .unwrap_or(false)
+}
+
+fn trust_in(projects: &Map<String, Value>, dir: &Path) -> ProjectTrust {
+    let key = find_project_key(projects, dir);
+    let trusted = key.as_deref().is_some_and(|k| is_trusted(projects, k));
+    let inherited_from = dir
+        .ancestors()
+        .skip(1)
+        .filter_map(|ancestor| find_project_key(projects, ancestor))
+        .find(|k| is_trusted(projects, k));
+    ProjectTrust {
+        path: dir.display().to_string(),
+        key,
+        trusted: trusted || inherited_from.is_some(),
+        inherited_from,
+    }
+}
+
+/// Reads the trust state of `dir` from the file at `path`. A missing file
+/// means nothing is trusted yet.
+pub fn project_trust(path: &Path, dir: &Path) -> Result<ProjectTrust, IpcError> {
+    let json = read_json_file(path)?.unwrap_or_default();
+    let empty = Map::new();
+    let projects = json
+        .get("projects")
+        .and_then(|p| p.as_object())
+        .unwrap_or(&empty);
+    Ok(trust_in(projects, dir))
+}
+
+/// Records in the file at `path` whether `dir` is trusted, creating its
+/// `projects` entry if needed; nothing is written if the recorded state
+/// already matches. Distrusting a directory does not touch trusted
+/// ancestors, so the result may still be trusted through `inherited_from`.
+pub fn set_project_trust(path: &Path, dir: &Path, trusted: bool) -> Result<ProjectTrust, IpcError> {
+    let _lock = lock_path(path);
+    let mut obj = if path.exists() {
+        read_object(path)?
+    } else {
+        Map::new()
+    };
+    let projects = obj
+        .entry("projects")
+        .or_insert_with(|| Value::Object(Map::new()))
+        .as_object_mut()
+        .ok_or_else(|| IpcError::ParseError {
+            path: path.display().to_string(),
+            message: "projects is not an object".to_string(),
+        })?;
+
+    let key = find_project_key(projects, dir).unwrap_or_else(|| dir.display().to_string());
+    if projects.contains_key(&key) && is_trusted(projects, &key) == trusted {
+        return Ok(trust_in(projects, dir));
+    }
+    let entry = projects
+        .entry(key.clone())
+        .or_insert_with(|| Value::Object(Map::new()))
+        .as_object_mut()
+        .ok_or_else(|| IpcError::ParseError {
+            path: path.display().to_string(),
+            message: format!("projects.{} is not an object", key),
+        })?;
+    entry.insert("hasTrustDialogAccepted".to_string(), Value::Bool(trusted));
+    let trust = trust_in(projects, dir);
+
+    write_object(path, obj)?;
+    Ok(trust)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sample() -> Value {
+        json!({
+            "numStartups": 12,
+            "hasCompletedOnboarding": true,
+            "oauthAccount": { "emailAddress": "a@example.com" },
+            "primaryApiKey": "sk-secret",
+            "mcpServers": {},
+            "projects": {
+                "/work/app": {
+                    "hasTrustDialogAccepted": true,
+                    "allowedTools": ["Bash(npm test)"],
+                    "lastCost": 0.5
+                }
+            },
+            "autoUpdates": false
+        })
+    }
+
+    #[test]
+    fn parses_sections_and_tracks_changes() {
+        let json = sample();
+        let ClaudeJsonSection::Projects(projects) = parse_section(&json, "projects").unwrap()
+        else {
+            panic!("expected projects");
+        };
+        let app = &projects["/work/app"];
+        assert_eq!(app.has_trust_dialog_accepted, Some(true));
+        assert_eq!(app.extra["lastCost"], json!(0.5));
+
+        let account = serde_json::to_value(parse_section(&json, "account").unwrap()).unwrap();
+        assert!(!account.to_string().contains("sk-secret"));
+        assert!(parse_section(&json, "history").is_err());
+
+        let mut bad = json.clone();
+        bad["projects"]["/work/app"]["allowedTools"] = json!("Bash");
+        match parse_section(&bad, "projects") {
+            Err(IpcError::ValidationError { errors }) => {
+                assert_eq!(errors[0].path, "projects./work/app.allowedTools")
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        let mut changed = json.clone();
+        changed["projects"]["/work/app"]["hasTrustDialogAccepted"] = json!(false);
+        changed["numStartups"] = json!(13);
+        changed.as_object_mut().unwrap().remove("autoUpdates");
+        assert_eq!(
+            changed_sections(&section_hashes(&json), &section_hashes(&changed)),
+            ["projects", "onboarding", "other"]
+        );
+    }
+
+    #[test]
+    fn edits_one_project_entry() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let path = dir.join(".claude.json");
+        std::fs::write(&path, sample().to_string()).unwrap();
+
+        let patch = json!({ "hasTrustDialogAccepted": null, "allowedTools": [] });
+        let entry = update_project_entry(&path, "/work/app", patch.as_object().unwrap()).unwrap();
+        assert_eq!(entry.has_trust_dialog_accepted, None);
+        assert_eq!(entry.extra["lastCost"], json!(0.5));
+
+        let bad = json!({ "projectOnboardingSeenCount": "x" });
+        assert!(update_project_entry(&path, "/work/app", bad.as_object().unwrap()).is_err());
+        assert!(update_project_entry(&path, "/elsewhere", &Map::new()).is_err());
+
+        let nested = Path::new("/work/app/packages/api");
+        let trust = set_project_trust(&path, Path::new("/work/app"), true).unwrap();
+        assert!(trust.trusted);
+        let trust = project_trust(&path, nested).unwrap();
+        assert_eq!(trust.key, None);
+        assert_eq!(trust.inherited_from.as_deref(), Some("/work/app"));
+        let trust = set_project_trust(&path, nested, false).unwrap();
+        assert_eq!(trust.key.as_deref(), Some("/work/app/packages/api"));
+        assert!(trust.trusted);
+        assert!(!project_trust(&path, Path::new("/work")).unwrap().trusted);
+
+        remove_project_entry(&path, "/work/app/packages/api").unwrap();
+        remove_project_entry(&path, "/work/app").unwrap();
+        let json = read_json_file(&path).unwrap().unwrap();
+        assert_eq!(json["projects"], json!({}));
+        assert_eq!(json["primaryApiKey"], "sk-secret");
+    }
+}
@@ -0,0 +1,357 @@
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::errors::IpcError;
+use crate::io::lock::lock_path;
+use crate::io::text::{self, strip_bom, TextFormat};
+use crate::platform::paths::{self, resolve_mcp_path};
+use crate::policy;
+use crate::schema::diff::{self, SettingChange};
+use crate::schema::hooks::{self, EffectiveHooks};
+use crate::schema::matrix::{self, MatrixRow};
+use crate::schema::merge::{self, EffectiveConfig, MergePolicy};
+use crate::schema::model;
+use crate::schema::tools::{self, ToolInfo};
+use crate::types::{ConfigScope, WritableScope};
+
+/// Reads a JSON file and returns its contents, or None if it doesn't exist.
+pub fn read_json_file(path: &Path) -> Result<Option<serde_json::Value>, IpcError> {
+    let path_str = path.to_string_lossy().to_string();
+    match fs::read_to_string(path) {
+        Ok(contents) => {
+            let value: serde_json::Value =
+                serde_json::from_str(strip_bom(&contents)).map_err(|e| IpcError::ParseError {
+                    path: path_str.clone(),
+                    message: e.to_string(),
+                })?;
+            Ok(Some(value))
+        }
+        Err(e) => match e.kind() {
+            std::io::ErrorKind::NotFound => Ok(None),
+            std::io::ErrorKind::PermissionDenied => {
+                Err(IpcError::PermissionDenied { path: path_str })
+            }
+            _ => Err(IpcError::PlatformError {
+                message: format!("Failed to read {}: {}", path_str, e),
+            }),
+        },
+    }
+}
+
+/// Reads a settings file for the given scope, returning `None` if the file
+/// does not exist. Parse errors and permission errors are propagated.
+pub fn read_scope(
+    scope: &ConfigScope,
+    project_root: &Option<String>,
+) -> Result<Option<serde_json::Value>, IpcError> {
+    let path = match paths::resolve_settings_path(scope, project_root) {
+        Ok(p) => p,
+        // If there is no project open, project/local scopes simply return None.
+        Err(IpcError::NoProject { .. }) => return Ok(None),
+        // Managed settings dir not supported on this platform -> None.
+        Err(IpcError::Unsupported { .. }) => return Ok(None),
+        Err(e) => return Err(e),
+    };
+
+    let path_str = path.to_string_lossy().to_string();
+
+    match fs::read_to_string(&path) {
+        Ok(contents) => {
+            let value: serde_json::Value =
+                serde_json::from_str(strip_bom(&contents)).map_err(|e| IpcError::ParseError {
+                    path: path_str.clone(),
+                    message: e.to_string(),
+                })?;
+            Ok(Some(value))
+        }
+        Err(e) => match e.kind() {
+            std::io::ErrorKind::NotFound => Ok(None),
+            std::io::ErrorKind::PermissionDenied => {
+                Err(IpcError::PermissionDenied { path: path_str })
+            }
+            _ => Err(IpcError::PlatformError {
+                message: format!("Failed to read {}: {}", path_str, e),
+            }),
+        },
+    }
+}
+
+/// Reads all four settings scopes and merges them.
+pub fn compute_effective(project_root: &Option<String>) -> Result<EffectiveConfig, IpcError> {
+    let user = read_scope(&ConfigScope::User, project_root)?;
+    let project = read_scope(&ConfigScope::Project, project_root)?;
+    let local = read_scope(&ConfigScope::Local, project_root)?;
+    let managed = read_scope(&ConfigScope::Managed, project_root)?;
+
+    Ok(merge::compute_effective(
+        user.as_ref(),
+        project.as_ref(),
+        local.as_ref(),
+        managed.as_ref(),
+    ))
+}
+
+/// Result of `preview_effective_with_edit`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EditPreview {
+    /// The effective config as it would be after saving.
+    pub effective: EffectiveConfig,
+    /// Effective settings keys that would change, against the current result.
+    pub changes: Vec<SettingChange>,
+}
+
+/// Merges the four settings scopes with `proposed` in place of `scope`'s
+/// file, without writing anything, and lists how the effective settings
+/// would change.
+pub fn preview_effective_with_edit(
+    scope: &ConfigScope,
+    proposed: &serde_json::Value,
+    project_root: &Option<String>,
+) -> Result<EditPreview, IpcError> {
+    let mut user = read_scope(&ConfigScope::User, project_root)?;
+    let mut project = read_scope(&ConfigScope::Project, project_root)?;
+    let mut local = read_scope(&ConfigScope::Local, project_root)?;
+    let mut managed = read_scope(&ConfigScope::Managed, project_root)?;
+
+    let current = merge::compute_effective(
+        user.as_ref(),
+        project.as_ref(),
+        local.as_ref(),
+        managed.as_ref(),
+    );
+    let edited = match scope {
+        ConfigScope::User => &mut user,
+        ConfigScope::Project => &mut project,
+        ConfigScope::Local => &mut local,
+        ConfigScope::Managed => &mut managed,
+    };
+    *edited = Some(proposed.clone());
+    let effective = merge::compute_effective(
+        user.as_ref(),
+        project.as_ref(),
+        local.as_ref(),
+        managed.as_ref(),
+    );
+
+    Ok(EditPreview {
+        changes: diff::diff(&current.settings, &effective.settings),
+        effective,
+    })
+}
+
+/// Reads all four settings scopes and lays them out key by key next to the
+/// merged result.
+pub fn compute_scope_matrix(project_root: &Option<String>) -> Result<Vec<MatrixRow>, IpcError> {
+    let user = read_scope(&ConfigScope::User, project_root)?;
+    let project = read_scope(&ConfigScope::Project, project_root)?;
+    let local = read_scope(&ConfigScope::Local, project_root)?;
+    let managed = read_scope(&ConfigScope::Managed, project_root)?;
+
+    let effective = merge::compute_effective(
+        user.as_ref(),
+        project.as_ref(),
+        local.as_ref(),
+        managed.as_ref(),
+    );
+    Ok(matrix::compute_scope_matrix(
+        &MergePolicy::default(),
+        user.as_ref(),
+        project.as_ref(),
+        local.as_ref(),
+        managed.as_ref(),
+        &effective,
+    ))
+}
+
+/// Reads all four settings scopes and composes their hooks the way Claude
+/// Code runs them.
+pub fn compute_effective_hooks(project_root: &Option<String>) -> Result<EffectiveHooks, IpcError> {
+    let user = read_scope(&ConfigScope::User, project_root)?;
+    let project = read_scope(&ConfigScope::Project, project_root)?;
+    let local = read_scope(&ConfigScope::Local, project_root)?;
+    let managed = read_scope(&ConfigScope::Managed, project_root)?;
+
+    Ok(hooks::compose_hooks(&[
+        ("user", user.as_ref()),
+        ("project", project.as_ref()),
+        ("local", local.as_ref()),
+        ("managed", managed.as_ref()),
+    ]))
+}
+
+/// Builds the tool catalog from the built-in tools, the servers of every
+/// readable MCP config, and the MCP tools named in the effective settings.
+pub fn list_known_tools(project_root: &Option<String>) -> Result<Vec<ToolInfo>, IpcError> {
+    let mut configs = Vec::new();
+    for source in ["user", "project", "managed"] {
+        if resolve_mcp_path(source, project_root).is_err() {
+            continue;
+        }
+        if let Some(config) = read_mcp_config(source, project_root)? {
+            configs.push((source, config));
+        }
+    }
+    let effective = compute_effective(project_root)?;
+
+    let configs: Vec<_> = configs.iter().map(|(s, c)| (*s, c)).collect();
+    Ok(tools::catalog(&configs, &effective.settings))
+}
+
+/// Reads the settings file of `scope`, or `None` if it does not exist.
+///
+/// Only invalid JSON fails the read. A known field with the wrong type is
+/// left for `validation::validate_settings` to report and `write_settings`
+/// to reject, so one bad field does not hide the whole file.
+pub fn read_settings(
+    scope: &ConfigScope,
+    project_root: &Option<String>,
+) -> Result<Option<serde_json::Value>, IpcError> {
+    let path = paths::resolve_settings_path(scope, project_root)?;
+    read_json_file(&path)
+}
+
+/// Line endings and BOM of the settings file of `scope`, or `None` if it
+/// does not exist.
+pub fn settings_format(
+    scope: &ConfigScope,
+    project_root: &Option<String>,
+) -> Result<Option<TextFormat>, IpcError> {
+    let path = paths::resolve_settings_path(scope, project_root)?;
+    Ok(path.exists().then(|| TextFormat::of_file(&path)))
+}
+
+/// Creates the parent directory of `path` if it is missing.
+fn ensure_parent(path: &Path) -> Result<(), IpcError> {
+    if let Some(parent) = path.parent() {
+        if !parent.exists() {
+            fs::create_dir_all(parent).map_err(|e| IpcError::WriteFailed {
+                path: path.display().to_string(),
+                message: format!("Failed to create parent directory: {}", e),
+            })?;
+        }
+    }
+    Ok(())
+}
+
+fn to_pretty_json(path: &Path, value: &serde_json::Value) -> Result<String, IpcError> {
+    serde_json::to_string_pretty(value).map_err(|e| IpcError::WriteFailed {
+        path: path.display().to_string(),
+        message: format!("Failed to serialize {}: {}", path.display(), e),
+    })
+}
+
+/// Type-checks `settings` against the settings model and the enforced admin
+/// policy, if any, and atomically writes it to `scope` as pretty JSON. The
+/// value is written as-is so key order and unknown keys are preserved. The
+/// file keeps its line endings and BOM unless `format` overrides them.
+pub fn write_settings(
+    scope: &WritableScope,
+    settings: &serde_json::Value,
+    format: Option<TextFormat>,
+    project_root: &Option<String>,
+) -> Result<(), IpcError> {
+    let config_scope = match scope {
+        WritableScope::User => ConfigScope::User,
+        WritableScope::Project => ConfigScope::Project,
+        WritableScope::Local => ConfigScope::Local,
+    };
+
+    model::parse_settings(settings).map_err(|e| IpcError::ValidationError { errors: vec![e] })?;
+    policy::enforce(|p| policy::check_settings(p, settings))?;
+
+    let path = paths::resolve_settings_path(&config_scope, project_root)?;
+    ensure_parent(&path)?;
+    text::write_text(&path, &to_pretty_json(&path, settings)?, format)
+}
+
+/// Reads MCP config from `source`. For "user" only the `mcpServers` key of
+/// ~/.claude.json is returned, wrapped as `{ mcpServers: ... }`.
+pub fn read_mcp_config(
+    source: &str,
+    project_root: &Option<String>,
+) -> Result<Option<serde_json::Value>, IpcError> {
+    let path = resolve_mcp_path(source, project_root)?;
+    let config = read_json_file(&path)?;
+    if source != "user" {
+        return Ok(config);
+    }
+    Ok(config.map(|full| {
+        let servers = full
+            .get("mcpServers")
+            .cloned()
+            .unwrap_or(serde_json::json!({}));
+        serde_json::json!({ "mcpServers": servers })
+    }))
+}
+
+/// Writes MCP config to "project" or "user". For "user" only the
+/// `mcpServers` key of ~/.claude.json is replaced; every other key is kept.
+/// Servers outside an enforced policy allow list are refused.
+pub fn write_mcp_config(
+    source: &str,
+    config: &serde_json::Value,
+    project_root: &Option<String>,
+) -> Result<(), IpcError> {
+    if source != "project" && source != "user" {
+        return Err(IpcError::InvalidArgument {
+            argument: "source".to_string(),
+            message: format!("MCP source '{}' is not writable", source),
+        });
+    }
+
+    policy::enforce(|p| policy::check_mcp_config(p, config))?;
+
+    let path = resolve_mcp_path(source, project_root)?;
+    let _lock = lock_path(&path);
+    ensure_parent(&path)?;
+
+    let value = if source == "user" {
+        let mut obj = match read_json_file(&path)? {
+            Some(serde_json::Value::Object(m)) => m,
+            _ => serde_json::Map::new(),
+        };
+        let servers = config
+            .get("mcpServers")
+            .cloned()
+            .unwrap_or(serde_json::json!({}));
+        obj.insert("mcpServers".to_string(), servers);
+        serde_json::Value::Object(obj)
+    } else {
+        config.clone()
+    };
+    text::write_text(&path, &to_pretty_json(&path, &value)?, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validation;
+
+    #[test]
+    fn reads_mistyped_settings_and_rejects_writing_them() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        fs::create_dir_all(dir.join(".claude")).unwrap();
+        fs::write(
+            dir.join(".claude").join("settings.json"),
+            r#"{ "model": 5, "permissions": { "allow": ["Read"] } }"#,
+        )
+        .unwrap();
+        let root = Some(dir.to_string_lossy().to_string());
+
+        let settings = read_settings(&ConfigScope::Project, &root)
+            .unwrap()
+            .unwrap();
+        assert_eq!(settings["permissions"]["allow"][0], "Read");
+
+        let result = validation::validate_settings(&settings, &root);
+        assert!(!result.valid);
+        assert_eq!(result.errors[0].path, "model");
+
+        let written = write_settings(&WritableScope::Project, &settings, None, &root);
+        assert!(matches!(written, Err(IpcError::ValidationError { .. })));
+    }
+}
@@ -0,0 +1,505 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use notify::{RecursiveMode, Watcher};
+use serde::Serialize;
+
+use crate::fixes;
+use crate::git::check_local_gitignore;
+use crate::io::text::strip_bom;
+use crate::lockfile;
+use crate::platform::detect;
+use crate::platform::paths::{self, resolve_mcp_path};
+use crate::schema::hooks;
+use crate::schema::migrations;
+use crate::types::ConfigScope;
+
+/// Outcome of a single doctor check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Ok,
+    Warning,
+    Error,
+}
+
+/// One finding in a doctor report.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DoctorCheck {
+    /// "config_dir", "settings", "mcp", "lockfile", "watcher", "managed",
+    /// "hooks", or "git".
+    pub category: &'static str,
+    /// Stable machine-readable code, e.g. `JSON_PARSE_ERROR`.
+    pub code: &'static str,
+    pub status: CheckStatus,
+    pub message: String,
+    pub path: Option<String>,
+    /// What the user can do about it.
+    pub suggestion: Option<String>,
+    /// `apply_fix` id that resolves this finding automatically.
+    pub fix_id: Option<&'static str>,
+    pub fix_target: Option<String>,
+}
+
+/// Result of `run_doctor`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DoctorReport {
+    pub checks: Vec<DoctorCheck>,
+    pub errors: usize,
+    pub warnings: usize,
+}
+
+struct Doctor {
+    checks: Vec<DoctorCheck>,
+}
+
+impl Doctor {
+    fn push(
+        &mut self,
+        category: &'static str,
+        code: &'static str,
+        status: CheckStatus,
+        message: String,
+        path: Option<&Path>,
+        suggestion: Option<&str>,
+    ) {
+        self.checks.push(DoctorCheck {
+            category,
+            code,
+            status,
+            message,
+            path: path.map(|p| p.display().to_string()),
+            suggestion: suggestion.map(str::to_string),
+            fix_id: None,
+            fix_target: None,
+        });
+    }
+
+    /// Attaches an `apply_fix` id to the last finding.
+    fn fix(&mut self, fix_id: &'static str, target: Option<String>) {
+        if let Some(check) = self.checks.last_mut() {
+            check.fix_id = Some(fix_id);
+            check.fix_target = target;
+        }
+    }
+
+    fn ok(
+        &mut self,
+        category: &'static str,
+        code: &'static str,
+        message: String,
+        path: Option<&Path>,
+    ) {
+        self.push(category, code, CheckStatus::Ok, message, path, None);
+    }
+
+    /// Config dir exists and accepts a write.
+    fn check_config_dir(&mut self) {
+        if let Err(e) = paths::home_dir() {
+            self.push(
+                "config_dir",
+                "HOME_DIR_UNAVAILABLE",
+                CheckStatus::Warning,
+                e.to_string(),
+                None,
+                Some("Set HOME or CLAUDE_CONFIG_DIR, or set a home directory override."),
+            );
+        }
+        let dir = match paths::resolve_config_dir() {
+            Ok(dir) => dir,
+            Err(e) => {
+                self.push(
+                    "config_dir",
+                    "CONFIG_DIR_UNAVAILABLE",
+                    CheckStatus::Error,
+                    e.to_string(),
+                    None,
+                    Some("Set CLAUDE_CONFIG_DIR, or set a home directory override."),
+                );
+                return;
+            }
+        };
+        if !dir.is_dir() {
+            self.push(
+                "config_dir",
+                "CONFIG_DIR_MISSING",
+                CheckStatus::Warning,
+                "The Claude Code config directory does not exist yet".to_string(),
+                Some(&dir),
+                Some("Create the directory or run Claude Code once to initialize it."),
+            );
+            self.fix("create_directory", Some(dir.display().to_string()));
+            return;
+        }
+
+        let probe = dir.join(format!(".ssenrah-doctor-{}", std::process::id()));
+        match fs::write(&probe, b"") {
+            Ok(()) => {
+                let _ = fs::remove_file(&probe);
+                self.ok(
+                    "config_dir",
+                    "CONFIG_DIR_WRITABLE",
+                    "Config directory is writable".to_string(),
+                    Some(&dir),
+                );
+            }
+            Err(e) => self.push(
+                "config_dir",
+                "CONFIG_DIR_NOT_WRITABLE",
+                CheckStatus::Error,
+                format!("Cannot write to the config directory: {}", e),
+                Some(&dir),
+                Some("Fix the directory's ownership or permissions."),
+            ),
+        }
+    }
+
+    /// Parses a JSON config file, returning its value when it parses.
+    fn check_json(&mut self, category: &'static str, path: &Path) -> Option<serde_json::Value> {
+        let contents = match fs::read_to_string(path) {
+            Ok(c) => c,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return None,
+            Err(e) => {
+                self.push(
+                    category,
+                    "FILE_UNREADABLE",
+                    CheckStatus::Error,
+                    format!("Cannot read file: {}", e),
+                    Some(path),
+                    Some("Check the file's permissions."),
+                );
+                return None;
+            }
+        };
+
+        let contents = strip_bom(&contents);
+        match serde_json::from_str(contents) {
+            Ok(value) => {
+                self.ok(
+                    category,
+                    "JSON_VALID",
+                    "File parses as JSON".to_string(),
+                    Some(path),
+                );
+                Some(value)
+            }
+            Err(e) => {
+                self.push(
+                    category,
+                    "JSON_PARSE_ERROR",
+                    CheckStatus::Error,
+                    format!("Invalid JSON: {}", e),
+                    Some(path),
+                    Some("Claude Code ignores a file it cannot parse. Fix the syntax at the reported line."),
+                );
+                let repairable = fixes::strip_trailing_commas(contents)
+                    .is_some_and(|fixed| serde_json::from_str::<serde_json::Value>(&fixed).is_ok());
+                let managed =
+                    paths::resolve_managed_settings_dir().is_some_and(|d| path.starts_with(d));
+                if repairable && !managed {
+                    self.fix("remove_trailing_commas", Some(path.display().to_string()));
+                }
+                None
+            }
+        }
+    }
+
+    /// Lockfile exists and names this process.
+    fn check_lockfile(&mut self) {
+        // A missing config directory is reported by `check_config_dir`.
+        let Ok(path) = lockfile::lockfile_path() else {
+            return;
+        };
+        match fs::read_to_string(&path) {
+            Ok(content) if content.trim() == std::process::id().to_string() => {
+                self.ok("lockfile", "LOCKFILE_OK", "Lockfile is held by this instance".to_string(), Some(&path));
+            }
+            Ok(content) => self.push(
+                "lockfile",
+                "LOCKFILE_FOREIGN",
+                CheckStatus::Warning,
+                format!("Lockfile names another process ({})", content.trim()),
+                Some(&path),
+                Some("Close other ssenrah windows; if none are running, restart ssenrah to reclaim the lock."),
+            ),
+            Err(_) => self.push(
+                "lockfile",
+                "LOCKFILE_MISSING",
+                CheckStatus::Warning,
+                "No lockfile; another instance could edit config concurrently".to_string(),
+                Some(&path),
+                Some("Restart ssenrah to recreate the lockfile."),
+            ),
+        }
+    }
+
+    /// A filesystem watcher can be created and attached to the config dir.
+    fn check_watcher(&mut self) {
+        // A missing config directory is reported by `check_config_dir`.
+        let Ok(dir) = paths::resolve_config_dir() else {
+            return;
+        };
+        let result = notify::recommended_watcher(|_: notify::Result<notify::Event>| {})
+            .and_then(|mut w| w.watch(&dir, RecursiveMode::NonRecursive));
+        match result {
+            Ok(()) => self.ok("watcher", "WATCHER_OK", "File watching is available".to_string(), Some(&dir)),
+            Err(e) => self.push(
+                "watcher",
+                "WATCHER_UNAVAILABLE",
+                CheckStatus::Warning,
+                format!("Cannot watch config files: {}", e),
+                Some(&dir),
+                Some("External edits will not refresh automatically. On Linux, raise fs.inotify.max_user_watches."),
+            ),
+        }
+    }
+
+    /// Managed settings directory, if present, is readable.
+    fn check_managed(&mut self) {
+        let Some(dir) = paths::resolve_managed_settings_dir() else {
+            return;
+        };
+        if !dir.exists() {
+            self.ok(
+                "managed",
+                "MANAGED_ABSENT",
+                "No managed settings are installed".to_string(),
+                Some(&dir),
+            );
+            return;
+        }
+        match fs::read_dir(&dir) {
+            Ok(_) => self.ok("managed", "MANAGED_READABLE", "Managed settings are readable".to_string(), Some(&dir)),
+            Err(e) => self.push(
+                "managed",
+                "MANAGED_UNREADABLE",
+                CheckStatus::Warning,
+                format!("Cannot read managed settings: {}", e),
+                Some(&dir),
+                Some("Effective config will not reflect organization policy. Ask your administrator for read access."),
+            ),
+        }
+    }
+
+    /// Every stdio MCP server command resolves to an executable.
+    fn check_mcp_commands(&mut self, path: &Path, config: &serde_json::Value) {
+        let Some(servers) = config.get("mcpServers").and_then(|s| s.as_object()) else {
+            return;
+        };
+        for (name, server) in servers {
+            let Some(command) = server.get("command").and_then(|c| c.as_str()) else {
+                continue;
+            };
+            if detect::find_on_path(command).is_some() {
+                self.ok(
+                    "mcp",
+                    "MCP_COMMAND_FOUND",
+                    format!("MCP server '{}' command '{}' found", name, command),
+                    Some(path),
+                );
+            } else {
+                self.push(
+                    "mcp",
+                    "MCP_COMMAND_NOT_FOUND",
+                    CheckStatus::Warning,
+                    format!("MCP server '{}' runs '{}', which is not on PATH", name, command),
+                    Some(path),
+                    Some("Install the program or use an absolute path. Apps launched from the desktop may see a shorter PATH than your shell."),
+                );
+            }
+        }
+    }
+
+    /// Settings use no deprecated keys.
+    fn check_deprecated_keys(
+        &mut self,
+        scope: &'static str,
+        path: &Path,
+        settings: &serde_json::Value,
+    ) {
+        let deprecated = migrations::pending(settings);
+        if deprecated.is_empty() {
+            return;
+        }
+        let moves: Vec<String> = deprecated
+            .iter()
+            .map(|m| format!("'{}' (now '{}')", m.from, m.to))
+            .collect();
+        self.push(
+            "settings",
+            "DEPRECATED_SETTINGS_KEY",
+            CheckStatus::Warning,
+            format!("Deprecated keys: {}", moves.join(", ")),
+            Some(path),
+            Some("Move the values to their current keys; the CLI no longer reads the old ones."),
+        );
+        if scope != "managed" {
+            self.fix("rename_deprecated_keys", Some(scope.to_string()));
+        }
+    }
+
+    /// Hook commands that name a script point at an executable file.
+    fn check_hooks(
+        &mut self,
+        path: &Path,
+        settings: &serde_json::Value,
+        project_root: Option<&Path>,
+    ) {
+        let Ok(home) = paths::home_dir() else {
+            return;
+        };
+        for (field, command) in hooks::command_hooks(settings) {
+            let Some(script) = hooks::script_path(&command, project_root, &home) else {
+                continue;
+            };
+            if !script.is_file() {
+                self.push(
+                    "hooks",
+                    "HOOK_SCRIPT_MISSING",
+                    CheckStatus::Error,
+                    format!("{} runs {}, which does not exist", field, script.display()),
+                    Some(path),
+                    Some("Create the script or fix the hook command."),
+                );
+            } else if !detect::is_executable(&script) {
+                self.push(
+                    "hooks",
+                    "HOOK_NOT_EXECUTABLE",
+                    CheckStatus::Error,
+                    format!(
+                        "{} runs {}, which is not executable",
+                        field,
+                        script.display()
+                    ),
+                    Some(&script),
+                    Some("Make the script executable (chmod +x)."),
+                );
+                self.fix("chmod_hook_script", Some(script.display().to_string()));
+            } else {
+                self.ok(
+                    "hooks",
+                    "HOOK_SCRIPT_OK",
+                    format!("{} script is executable", field),
+                    Some(&script),
+                );
+            }
+        }
+    }
+
+    /// Local-only config files are gitignored.
+    fn check_gitignore(&mut self, root: &Path) {
+        match check_local_gitignore(root, false) {
+            Ok(check) if check.missing.is_empty() => {
+                self.ok(
+                    "git",
+                    "GITIGNORE_OK",
+                    "Local config files are gitignored".to_string(),
+                    Some(Path::new(&check.path)),
+                );
+            }
+            Ok(check) => {
+                self.push(
+                    "git",
+                    "GITIGNORE_MISSING_ENTRIES",
+                    CheckStatus::Warning,
+                    format!("Not ignored: {}", check.missing.join(", ")),
+                    Some(Path::new(&check.path)),
+                    Some("Add the missing patterns so personal settings and tokens are not committed."),
+                );
+                self.fix("add_gitignore_entries", None);
+            }
+            Err(e) => self.push(
+                "git",
+                "GITIGNORE_CHECK_FAILED",
+                CheckStatus::Warning,
+                format!("Could not check .gitignore: {}", e),
+                Some(&root.join(".gitignore")),
+                None,
+            ),
+        }
+    }
+}
+
+/// Runs every check against the config dir and `project_root`.
+///
+/// Covers config dir writability, JSON syntax of every settings and MCP file,
+/// file watching, managed settings access, MCP server commands on PATH, hook
+/// script permissions, and `.gitignore` coverage of local files. The lockfile
+/// check only makes sense inside the app, which holds the lock, so callers
+/// opt into it with `check_lockfile`.
+pub fn run(project_root: &Option<String>, check_lockfile: bool) -> DoctorReport {
+    let root = project_root.as_ref().map(PathBuf::from);
+
+    let mut doctor = Doctor { checks: Vec::new() };
+    doctor.check_config_dir();
+    if check_lockfile {
+        doctor.check_lockfile();
+    }
+    doctor.check_watcher();
+    doctor.check_managed();
+
+    let scopes = [
+        ("user", ConfigScope::User),
+        ("project", ConfigScope::Project),
+        ("local", ConfigScope::Local),
+        ("managed", ConfigScope::Managed),
+    ];
+    for (name, scope) in scopes {
+        let Ok(path) = paths::resolve_settings_path(&scope, project_root) else {
+            continue;
+        };
+        if let Some(settings) = doctor.check_json("settings", &path) {
+            doctor.check_deprecated_keys(name, &path, &settings);
+            doctor.check_hooks(&path, &settings, root.as_deref());
+        }
+    }
+
+    for source in ["user", "project", "managed"] {
+        let Ok(path) = resolve_mcp_path(source, project_root) else {
+            continue;
+        };
+        if let Some(config) = doctor.check_json("mcp", &path) {
+            doctor.check_mcp_commands(&path, &config);
+        }
+    }
+
+    if let Some(root) = &root {
+        doctor.check_gitignore(root);
+    }
+
+    let count = |status| doctor.checks.iter().filter(|c| c.status == status).count();
+    let errors = count(CheckStatus::Error);
+    let warnings = count(CheckStatus::Warning);
+
+    DoctorReport {
+        checks: doctor.checks,
+        errors,
+        warnings,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_json_with_a_byte_order_mark() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let path = dir.join("settings.json");
+        fs::write(&path, "\u{feff}{\"model\": \"opus\"}").unwrap();
+
+        let mut doctor = Doctor { checks: Vec::new() };
+        let value = doctor.check_json("settings", &path).unwrap();
+        assert_eq!(value["model"], "opus");
+        assert_eq!(doctor.checks[0].code, "JSON_VALID");
+
+        fs::write(&path, "\u{feff}{\"model\": \"opus\",}").unwrap();
+        let mut doctor = Doctor { checks: Vec::new() };
+        assert!(doctor.check_json("settings", &path).is_none());
+        assert_eq!(doctor.checks[0].code, "JSON_PARSE_ERROR");
+        assert_eq!(doctor.checks[0].fix_id, Some("remove_trailing_commas"));
+    }
+}
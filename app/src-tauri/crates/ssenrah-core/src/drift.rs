@@ -0,0 +1,314 @@
+//! Named baselines of the effective config, so a team can notice when
+//! someone's setup drifts from the one they agreed on.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::args;
+use crate::audit;
+use crate::clock;
+use crate::config;
+use crate::errors::IpcError;
+use crate::io::atomic::atomic_write;
+use crate::platform::paths;
+use crate::schema::diff::{self, SettingChange};
+
+/// Directory holding pinned baselines: `{configDir}/ssenrah/baselines/`.
+pub fn baselines_dir() -> Result<PathBuf, IpcError> {
+    Ok(paths::resolve_app_dir()?.join("baselines"))
+}
+
+/// The effective settings as they were when the baseline was pinned.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Baseline {
+    pub name: String,
+    /// Milliseconds since the Unix epoch.
+    pub pinned_at: u64,
+    /// Project whose effective config was pinned, if one was open.
+    pub project_root: Option<String>,
+    /// SHA-256 of the effective settings.
+    pub hash: String,
+    /// Report drift as settings change, not only when checked: the app's
+    /// file watcher emits a `config_drift` event.
+    pub alert: bool,
+    pub settings: Value,
+}
+
+/// Result of comparing the current effective config with a baseline.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DriftReport {
+    pub name: String,
+    pub pinned_at: u64,
+    pub baseline_hash: String,
+    pub current_hash: String,
+    pub drifted: bool,
+    /// Keys changed since the baseline, sorted by path.
+    pub changes: Vec<SettingChange>,
+}
+
+fn settings_hash(settings: &Value) -> String {
+    audit::hash(settings.to_string().as_bytes())
+}
+
+/// Names become file names, so only letters, digits, `-`, `_`, and `.`
+/// (not leading) are allowed.
+fn check_name(name: &str) -> Result<(), IpcError> {
+    args::identifier("name", name).map(|_| ())
+}
+
+fn baseline_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{}.json", name))
+}
+
+/// Pins the current effective config of `project_root` as baseline `name`,
+/// replacing any baseline of that name.
+pub fn pin(name: &str, alert: bool, project_root: &Option<String>) -> Result<Baseline, IpcError> {
+    let effective = config::compute_effective(project_root)?;
+    pin_in(
+        &baselines_dir()?,
+        name,
+        alert,
+        project_root,
+        effective.settings,
+    )
+}
+
+fn pin_in(
+    dir: &Path,
+    name: &str,
+    alert: bool,
+    project_root: &Option<String>,
+    settings: Value,
+) -> Result<Baseline, IpcError> {
+    check_name(name)?;
+    let baseline = Baseline {
+        name: name.to_string(),
+        pinned_at: clock::now_millis(),
+        project_root: project_root.clone(),
+        hash: settings_hash(&settings),
+        alert,
+        settings,
+    };
+    let path = baseline_path(dir, name);
+    let failed = |message: String| IpcError::WriteFailed {
+        path: path.display().to_string(),
+        message,
+    };
+    fs::create_dir_all(dir).map_err(|e| failed(e.to_string()))?;
+    let record = serde_json::to_vec_pretty(&baseline).map_err(|e| failed(e.to_string()))?;
+    atomic_write(&path, &record)?;
+    Ok(baseline)
+}
+
+/// Lists pinned baselines by name.
+pub fn list() -> Result<Vec<Baseline>, IpcError> {
+    Ok(list_in(&baselines_dir()?))
+}
+
+fn list_in(dir: &Path) -> Vec<Baseline> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut baselines: Vec<Baseline> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| fs::read(e.path()).ok())
+        .filter_map(|bytes| serde_json::from_slice(&bytes).ok())
+        .collect();
+    baselines.sort_by(|a, b| a.name.cmp(&b.name));
+    baselines
+}
+
+fn load_in(dir: &Path, name: &str) -> Result<Baseline, IpcError> {
+    check_name(name)?;
+    let path = baseline_path(dir, name);
+    fs::read(&path)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .ok_or_else(|| IpcError::NotFound {
+            path: path.display().to_string(),
+        })
+}
+
+/// Compares a baseline with `settings`, the current effective settings.
+pub fn compare(baseline: &Baseline, settings: &Value) -> DriftReport {
+    let current_hash = settings_hash(settings);
+    DriftReport {
+        name: baseline.name.clone(),
+        pinned_at: baseline.pinned_at,
+        drifted: current_hash != baseline.hash,
+        changes: diff::diff(&baseline.settings, settings),
+        baseline_hash: baseline.hash.clone(),
+        current_hash,
+    }
+}
+
+/// Reports how the current effective config of `project_root` differs from
+/// baseline `name`. Refuses a baseline pinned for a different project, whose
+/// settings would show up as drift.
+pub fn check(name: &str, project_root: &Option<String>) -> Result<DriftReport, IpcError> {
+    let baseline = load_in(&baselines_dir()?, name)?;
+    check_project(&baseline, project_root.as_deref().map(Path::new))?;
+    let effective = config::compute_effective(project_root)?;
+    Ok(compare(&baseline, &effective.settings))
+}
+
+/// Whether `baseline` was pinned for `project_root`, compared as normalized
+/// paths, or without a project when there is none.
+fn pinned_for(baseline: &Baseline, project_root: Option<&Path>) -> bool {
+    baseline
+        .project_root
+        .as_deref()
+        .map(|r| paths::normalize(Path::new(r)))
+        == project_root.map(paths::normalize)
+}
+
+fn check_project(baseline: &Baseline, project_root: Option<&Path>) -> Result<(), IpcError> {
+    if pinned_for(baseline, project_root) {
+        return Ok(());
+    }
+    let describe = |root: Option<&str>| match root {
+        Some(root) => format!("project {}", root),
+        None => "no project".to_string(),
+    };
+    Err(args::invalid(
+        "name",
+        format!(
+            "Baseline '{}' was pinned for {}, but {} is open",
+            baseline.name,
+            describe(baseline.project_root.as_deref()),
+            describe(project_root.and_then(Path::to_str)),
+        ),
+    ))
+}
+
+/// Baselines with `alert` set that were pinned for `project_root` (compared
+/// as normalized paths) or, with no project, pinned without one.
+pub fn alerting_for(project_root: Option<&Path>) -> Result<Vec<Baseline>, IpcError> {
+    Ok(list()?
+        .into_iter()
+        .filter(|b| b.alert && pinned_for(b, project_root))
+        .collect())
+}
+
+/// Checks the alerting baselines of a project (see [`Baseline::alert`]),
+/// remembering what was last reported so the same drift is announced once.
+#[derive(Debug, Default)]
+pub struct DriftAlerts {
+    project_root: Option<PathBuf>,
+    /// Effective-config hash last reported, by baseline name.
+    reported: HashMap<String, String>,
+}
+
+impl DriftAlerts {
+    pub fn new(project_root: Option<PathBuf>) -> Self {
+        Self {
+            project_root,
+            reported: HashMap::new(),
+        }
+    }
+
+    /// Returns one report per alerting baseline the current effective config
+    /// newly drifted from. Call it after the settings changed.
+    pub fn check(&mut self) -> Vec<DriftReport> {
+        let baselines = match alerting_for(self.project_root.as_deref()) {
+            Ok(baselines) if !baselines.is_empty() => baselines,
+            _ => return Vec::new(),
+        };
+        let root = self.project_root.as_ref().map(|p| p.display().to_string());
+        let Ok(effective) = config::compute_effective(&root) else {
+            return Vec::new();
+        };
+        self.newly_drifted(&baselines, &effective.settings)
+    }
+
+    fn newly_drifted(&mut self, baselines: &[Baseline], settings: &Value) -> Vec<DriftReport> {
+        let mut reports = Vec::new();
+        for baseline in baselines {
+            let report = compare(baseline, settings);
+            if !report.drifted {
+                self.reported.remove(&baseline.name);
+                continue;
+            }
+            if self.reported.get(&baseline.name) != Some(&report.current_hash) {
+                self.reported
+                    .insert(baseline.name.clone(), report.current_hash.clone());
+                reports.push(report);
+            }
+        }
+        reports
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn pins_and_reports_drift() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let settings = json!({ "model": "opus", "permissions": { "allow": ["Read"] } });
+
+        let root = Some("/work/app".to_string());
+        pin_in(dir, "team", true, &root, settings.clone()).unwrap();
+        assert!(pin_in(dir, "../escape", false, &None, json!({})).is_err());
+        let baseline = load_in(dir, "team").unwrap();
+        assert_eq!(list_in(dir).len(), 1);
+
+        let same = compare(&baseline, &settings);
+        assert!(!same.drifted && same.changes.is_empty());
+
+        let tweaked = json!({ "model": "sonnet", "permissions": { "allow": ["Read"] } });
+        let report = compare(&baseline, &tweaked);
+        assert!(report.drifted);
+        let paths: Vec<_> = report.changes.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(paths, ["model"]);
+    }
+
+    #[test]
+    fn refuses_a_baseline_pinned_for_another_project() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let root = Some("/work/app".to_string());
+        let baseline = pin_in(dir, "team", false, &root, json!({})).unwrap();
+
+        assert!(check_project(&baseline, Some(Path::new("/work/app"))).is_ok());
+        assert!(check_project(&baseline, Some(Path::new("/work/other"))).is_err());
+        assert!(check_project(&baseline, None).is_err());
+
+        let global = pin_in(dir, "global", false, &None, json!({})).unwrap();
+        assert!(check_project(&global, None).is_ok());
+        assert!(check_project(&global, Some(Path::new("/work/app"))).is_err());
+    }
+
+    #[test]
+    fn alerts_once_per_drift() {
+        let settings = json!({ "model": "opus" });
+        let baseline = Baseline {
+            name: "team".into(),
+            pinned_at: 0,
+            project_root: None,
+            hash: settings_hash(&settings),
+            alert: true,
+            settings: settings.clone(),
+        };
+        let baselines = [baseline];
+        let mut alerts = DriftAlerts::new(None);
+        let drifted = json!({ "model": "sonnet" });
+
+        assert!(alerts.newly_drifted(&baselines, &settings).is_empty());
+        assert_eq!(alerts.newly_drifted(&baselines, &drifted).len(), 1);
+        assert!(alerts.newly_drifted(&baselines, &drifted).is_empty());
+
+        // Returning to the baseline re-arms the alert.
+        assert!(alerts.newly_drifted(&baselines, &settings).is_empty());
+        assert_eq!(alerts.newly_drifted(&baselines, &drifted).len(), 1);
+    }
+}
@@ -0,0 +1,364 @@
+use std::cell::Cell;
+use std::path::Path;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::types::ValidationError;
+
+/// IPC error types matching the TypeScript contract:
+///
+/// ```typescript
+/// type IpcError = IpcErrorDetail & { retryable: boolean; suggestion: string | null };
+/// type IpcErrorDetail =
+///   | { kind: "not_found"; path: string }
+///   | { kind: "permission_denied"; path: string }
+///   | { kind: "parse_error"; path: string; message: string }
+///   | { kind: "write_failed"; path: string; message: string }
+///   | { kind: "validation_error"; errors: ValidationError[] }
+///   | { kind: "no_project"; message: string }
+///   | { kind: "platform_error"; message: string }
+///   | { kind: "read_only"; message: string }
+///   | { kind: "timeout"; operation: string; seconds: number }
+///   | { kind: "conflict"; path: string; message: string }
+///   | { kind: "locked"; path: string; message: string }
+///   | { kind: "invalid_argument"; argument: string; message: string }
+///   | { kind: "confirmation_required"; message: string }
+///   | { kind: "unsupported"; message: string };
+/// ```
+///
+/// Every error is serialized with `retryable` (see [`IpcError::retryable`])
+/// and `suggestion` (see [`IpcError::suggestion`]) so the frontend can
+/// decide how to react from the kind alone instead of matching messages.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(remote = "Self", tag = "kind", rename_all = "snake_case")]
+pub enum IpcError {
+    NotFound {
+        path: String,
+    },
+    PermissionDenied {
+        path: String,
+    },
+    ParseError {
+        path: String,
+        message: String,
+    },
+    WriteFailed {
+        path: String,
+        message: String,
+    },
+    ValidationError {
+        errors: Vec<ValidationError>,
+    },
+    NoProject {
+        message: String,
+    },
+    PlatformError {
+        message: String,
+    },
+    ReadOnly {
+        message: String,
+    },
+    /// A command's file IO did not finish within `seconds`, typically on a
+    /// slow network filesystem.
+    Timeout {
+        operation: String,
+        seconds: u64,
+    },
+    /// The target is not in the state the caller expected: it already
+    /// exists, or changed since it was read or previewed. `path` names the
+    /// file, or the item when there is no file yet.
+    Conflict {
+        path: String,
+        message: String,
+    },
+    /// Another process holds the file open, e.g. an editor on Windows.
+    Locked {
+        path: String,
+        message: String,
+    },
+    /// A command argument was rejected. `argument` names the parameter or
+    /// option, e.g. "scope".
+    InvalidArgument {
+        argument: String,
+        message: String,
+    },
+    /// The change needs the user's explicit agreement; repeat the call with
+    /// `confirmed` set once they gave it.
+    ConfirmationRequired {
+        message: String,
+    },
+    /// The operation is not available on this platform or installation.
+    Unsupported {
+        message: String,
+    },
+}
+
+impl IpcError {
+    /// The `kind` tag this error is serialized with, e.g. "not_found".
+    pub fn kind(&self) -> &'static str {
+        match self {
+            IpcError::NotFound { .. } => "not_found",
+            IpcError::PermissionDenied { .. } => "permission_denied",
+            IpcError::ParseError { .. } => "parse_error",
+            IpcError::WriteFailed { .. } => "write_failed",
+            IpcError::ValidationError { .. } => "validation_error",
+            IpcError::NoProject { .. } => "no_project",
+            IpcError::PlatformError { .. } => "platform_error",
+            IpcError::ReadOnly { .. } => "read_only",
+            IpcError::Timeout { .. } => "timeout",
+            IpcError::Conflict { .. } => "conflict",
+            IpcError::Locked { .. } => "locked",
+            IpcError::InvalidArgument { .. } => "invalid_argument",
+            IpcError::ConfirmationRequired { .. } => "confirmation_required",
+            IpcError::Unsupported { .. } => "unsupported",
+        }
+    }
+
+    /// Whether repeating the same call unchanged may succeed: the failure
+    /// was caused by something transient rather than by the request or the
+    /// files themselves.
+    pub fn retryable(&self) -> bool {
+        matches!(self, IpcError::Timeout { .. } | IpcError::Locked { .. })
+    }
+
+    /// What the user can do about the error, when there is something more
+    /// useful to say than the message.
+    pub fn suggestion(&self) -> Option<String> {
+        let text = match self {
+            IpcError::PermissionDenied { .. } => {
+                "Check the file's owner and permissions, or run ssenrah as a user who can access it"
+            }
+            IpcError::ParseError { .. } => {
+                "Fix the syntax error in the file, or restore it from a backup or snapshot"
+            }
+            IpcError::WriteFailed { .. } => {
+                "Check that the disk has free space and the directory is writable"
+            }
+            IpcError::ValidationError { .. } => "Fix the listed fields and save again",
+            IpcError::NoProject { .. } => "Open a project first",
+            IpcError::ReadOnly { .. } => "Turn off read-only mode to make changes",
+            IpcError::Timeout { .. } => {
+                "Try again; if the config lives on a network drive, check that it is reachable"
+            }
+            IpcError::Conflict { .. } => {
+                "Reload to see the current state, then apply the change again or pick another name"
+            }
+            IpcError::Locked { .. } => "Close other programs using the file and try again",
+            IpcError::ConfirmationRequired { .. } => {
+                "Ask the user to confirm, then repeat the call with confirmed set"
+            }
+            IpcError::NotFound { .. }
+            | IpcError::PlatformError { .. }
+            | IpcError::InvalidArgument { .. }
+            | IpcError::Unsupported { .. } => return None,
+        };
+        Some(text.to_string())
+    }
+
+    /// A failed write of `path`: `Locked` if another process holds the file
+    /// (sharing violations on Windows, `EBUSY` elsewhere), otherwise
+    /// `WriteFailed` with `context` prefixed to the OS message.
+    pub fn write_failed(path: &Path, context: &str, e: &std::io::Error) -> Self {
+        let path = path.display().to_string();
+        let message = format!("{}: {}", context, e);
+        // ERROR_SHARING_VIOLATION and ERROR_LOCK_VIOLATION.
+        let windows_busy = cfg!(windows) && matches!(e.raw_os_error(), Some(32 | 33));
+        if windows_busy || e.kind() == std::io::ErrorKind::ResourceBusy {
+            IpcError::Locked { path, message }
+        } else {
+            IpcError::WriteFailed { path, message }
+        }
+    }
+}
+
+thread_local! {
+    /// Kind of the last error serialized on this thread; see
+    /// [`take_serialized_kind`].
+    static SERIALIZED_KIND: Cell<Option<&'static str>> = const { Cell::new(None) };
+}
+
+/// Kind of the last `IpcError` serialized on this thread since the previous
+/// call, if any. Tauri serializes a failing command's error on the thread
+/// the command ran on, so the IPC wrapper reads this after the handler to
+/// learn the outcome of commands that finish there.
+pub fn take_serialized_kind() -> Option<&'static str> {
+    SERIALIZED_KIND.with(Cell::take)
+}
+
+/// An error as the frontend receives it: the variant's fields tagged with
+/// `kind` (the derived, `remote = "Self"` representation), then `retryable`
+/// and `suggestion`.
+#[derive(Serialize)]
+struct WireError<'a> {
+    #[serde(flatten, serialize_with = "serialize_detail")]
+    detail: &'a IpcError,
+    retryable: bool,
+    suggestion: Option<String>,
+}
+
+fn serialize_detail<S: Serializer>(error: &&IpcError, serializer: S) -> Result<S::Ok, S::Error> {
+    IpcError::serialize(error, serializer)
+}
+
+impl Serialize for IpcError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SERIALIZED_KIND.with(|kind| kind.set(Some(self.kind())));
+        WireError {
+            detail: self,
+            retryable: self.retryable(),
+            suggestion: self.suggestion(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for IpcError {
+    /// Reads an error back from its serialized form; `retryable` and
+    /// `suggestion` follow from the kind and are ignored.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        IpcError::deserialize(deserializer)
+    }
+}
+
+impl std::fmt::Display for IpcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IpcError::NotFound { path } => write!(f, "not found: {}", path),
+            IpcError::PermissionDenied { path } => write!(f, "permission denied: {}", path),
+            IpcError::ParseError { path, message } => {
+                write!(f, "parse error at {}: {}", path, message)
+            }
+            IpcError::WriteFailed { path, message } => {
+                write!(f, "write failed at {}: {}", path, message)
+            }
+            IpcError::ValidationError { errors } => {
+                write!(f, "validation errors: {} issue(s)", errors.len())
+            }
+            IpcError::NoProject { message } => write!(f, "no project: {}", message),
+            IpcError::PlatformError { message } => write!(f, "platform error: {}", message),
+            IpcError::ReadOnly { message } => write!(f, "read-only: {}", message),
+            IpcError::Timeout { operation, seconds } => {
+                write!(f, "{} timed out after {}s", operation, seconds)
+            }
+            IpcError::Conflict { path, message } => write!(f, "conflict at {}: {}", path, message),
+            IpcError::Locked { path, message } => write!(f, "locked: {}: {}", path, message),
+            IpcError::InvalidArgument { argument, message } => {
+                write!(f, "invalid {}: {}", argument, message)
+            }
+            IpcError::ConfirmationRequired { message } => {
+                write!(f, "confirmation required: {}", message)
+            }
+            IpcError::Unsupported { message } => write!(f, "unsupported: {}", message),
+        }
+    }
+}
+
+// IpcError implements Serialize, so Tauri's blanket
+// `impl<T: Serialize> From<T> for InvokeError` handles
+// conversion automatically — no manual From impl needed.
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_retryability_and_suggestion() {
+        let locked = IpcError::Locked {
+            path: "C:/settings.json".to_string(),
+            message: "in use".to_string(),
+        };
+        let json = serde_json::to_value(&locked).unwrap();
+        assert_eq!(json["kind"], "locked");
+        assert_eq!(json["path"], "C:/settings.json");
+        assert_eq!(json["retryable"], true);
+        assert!(json["suggestion"].is_string());
+
+        let invalid = IpcError::InvalidArgument {
+            argument: "scope".to_string(),
+            message: "Unknown scope: team".to_string(),
+        };
+        let json = serde_json::to_value(&invalid).unwrap();
+        assert_eq!(json["argument"], "scope");
+        assert_eq!(json["retryable"], false);
+        assert!(json["suggestion"].is_null());
+
+        assert_eq!(take_serialized_kind(), Some("invalid_argument"));
+        assert_eq!(take_serialized_kind(), None);
+    }
+
+    #[test]
+    fn round_trips_every_variant() {
+        let path = || "/p".to_string();
+        let message = || "m".to_string();
+        let errors = [
+            IpcError::NotFound { path: path() },
+            IpcError::PermissionDenied { path: path() },
+            IpcError::ParseError {
+                path: path(),
+                message: message(),
+            },
+            IpcError::WriteFailed {
+                path: path(),
+                message: message(),
+            },
+            IpcError::ValidationError {
+                errors: vec![ValidationError {
+                    path: "model".to_string(),
+                    message: message(),
+                    code: "INVALID_TYPE".to_string(),
+                }],
+            },
+            IpcError::NoProject { message: message() },
+            IpcError::PlatformError { message: message() },
+            IpcError::ReadOnly { message: message() },
+            IpcError::Timeout {
+                operation: "read_settings".to_string(),
+                seconds: 20,
+            },
+            IpcError::Conflict {
+                path: path(),
+                message: message(),
+            },
+            IpcError::Locked {
+                path: path(),
+                message: message(),
+            },
+            IpcError::InvalidArgument {
+                argument: "scope".to_string(),
+                message: message(),
+            },
+            IpcError::ConfirmationRequired { message: message() },
+            IpcError::Unsupported { message: message() },
+        ];
+
+        let mut kinds = std::collections::HashSet::new();
+        for error in &errors {
+            // Adding a variant breaks this match; add it to `errors` too.
+            match error {
+                IpcError::NotFound { .. }
+                | IpcError::PermissionDenied { .. }
+                | IpcError::ParseError { .. }
+                | IpcError::WriteFailed { .. }
+                | IpcError::ValidationError { .. }
+                | IpcError::NoProject { .. }
+                | IpcError::PlatformError { .. }
+                | IpcError::ReadOnly { .. }
+                | IpcError::Timeout { .. }
+                | IpcError::Conflict { .. }
+                | IpcError::Locked { .. }
+                | IpcError::InvalidArgument { .. }
+                | IpcError::ConfirmationRequired { .. }
+                | IpcError::Unsupported { .. } => {}
+            }
+            kinds.insert(error.kind());
+
+            let json = serde_json::to_value(error).unwrap();
+            assert_eq!(json["kind"], error.kind());
+            assert_eq!(json["retryable"], error.retryable());
+            assert_eq!(json["suggestion"], serde_json::json!(error.suggestion()));
+            let back: IpcError = serde_json::from_value(json).unwrap();
+            assert_eq!(&back, error);
+        }
+        assert_eq!(kinds.len(), errors.len());
+    }
+}
@@ -0,0 +1,55 @@
+/// Fix ids understood by `apply_fix`.
+pub const FIX_IDS: &[&str] = &[
+    "add_gitignore_entries",
+    "rename_deprecated_keys",
+    "chmod_hook_script",
+    "remove_trailing_commas",
+    "create_directory",
+];
+
+/// Removes commas that directly precede a closing `}` or `]`, leaving string
+/// contents untouched. Returns `None` if there was nothing to remove.
+pub fn strip_trailing_commas(text: &str) -> Option<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::with_capacity(text.len());
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut removed = false;
+
+    for (i, &c) in chars.iter().enumerate() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+        } else if c == '"' {
+            in_string = true;
+        } else if c == ',' {
+            let next = chars[i + 1..].iter().find(|c| !c.is_whitespace());
+            if matches!(next, Some('}' | ']')) {
+                removed = true;
+                continue;
+            }
+        }
+        out.push(c);
+    }
+
+    removed.then_some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_trailing_commas_outside_strings() {
+        let text = "{ \"a\": [1, 2, ], \"b\": \"x,}\", }";
+        assert_eq!(
+            strip_trailing_commas(text).unwrap(),
+            "{ \"a\": [1, 2 ], \"b\": \"x,}\" }"
+        );
+        assert_eq!(strip_trailing_commas("{\"a\": 1}"), None);
+    }
+}
@@ -0,0 +1,281 @@
+use serde_json::json;
+
+/// Parse YAML frontmatter from a markdown file.
+///
+/// Splits the content on "---" markers. The text between the first and second
+/// "---" lines is treated as YAML frontmatter (parsed into a JSON value via
+/// simple key: value line parsing). Everything after the second "---" is the body.
+pub fn parse_frontmatter(content: &str) -> (serde_json::Value, String) {
+    let trimmed = content.trim_start();
+    if !trimmed.starts_with("---") {
+        return (json!({}), content.to_string());
+    }
+
+    // Find the closing ---
+    let after_first = &trimmed[3..];
+    let after_first = after_first.trim_start_matches(['\r', '\n']);
+
+    if let Some(end_idx) = after_first.find("\n---") {
+        let yaml_part = &after_first[..end_idx];
+        let body_start = end_idx + 4; // skip "\n---"
+        let body = if body_start < after_first.len() {
+            after_first[body_start..]
+                .trim_start_matches(['\r', '\n'])
+                .to_string()
+        } else {
+            String::new()
+        };
+
+        let frontmatter = parse_yaml_simple(yaml_part);
+        (frontmatter, body)
+    } else {
+        // No closing ---, treat entire content as body
+        (json!({}), content.to_string())
+    }
+}
+
+/// Simple YAML-like parser for frontmatter key: value pairs.
+///
+/// Handles strings, booleans, numbers, and bracket-delimited arrays.
+fn parse_yaml_simple(yaml: &str) -> serde_json::Value {
+    let mut map = serde_json::Map::new();
+
+    for line in yaml.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some((key, value)) = line.split_once(':') {
+            let key = key.trim().to_string();
+            let value = value.trim();
+
+            if value.is_empty() {
+                map.insert(key, json!(null));
+                continue;
+            }
+
+            // Array: [item1, item2, ...]
+            if value.starts_with('[') && value.ends_with(']') {
+                let inner = &value[1..value.len() - 1];
+                let items: Vec<serde_json::Value> = inner
+                    .split(',')
+                    .map(|s| {
+                        let s = s.trim().trim_matches('"').trim_matches('\'');
+                        json!(s)
+                    })
+                    .collect();
+                map.insert(key, json!(items));
+                continue;
+            }
+
+            // Boolean
+            if value == "true" {
+                map.insert(key, json!(true));
+                continue;
+            }
+            if value == "false" {
+                map.insert(key, json!(false));
+                continue;
+            }
+
+            // Number (integer)
+            if let Ok(n) = value.parse::<i64>() {
+                map.insert(key, json!(n));
+                continue;
+            }
+
+            // Number (float)
+            if let Ok(n) = value.parse::<f64>() {
+                map.insert(key, json!(n));
+                continue;
+            }
+
+            // String (strip optional quotes)
+            let s = value.trim_matches('"').trim_matches('\'');
+            map.insert(key, json!(s));
+        }
+    }
+
+    serde_json::Value::Object(map)
+}
+
+/// Serialize one frontmatter key as a YAML-like line, or `None` for a null
+/// value.
+fn serialize_entry(key: &str, value: &serde_json::Value) -> Option<String> {
+    let line = match value {
+        serde_json::Value::String(s) => format!("{}: {}", key, s),
+        serde_json::Value::Bool(b) => format!("{}: {}", key, b),
+        serde_json::Value::Number(n) => format!("{}: {}", key, n),
+        serde_json::Value::Array(arr) => {
+            let items: Vec<String> = arr
+                .iter()
+                .map(|v| match v {
+                    serde_json::Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                })
+                .collect();
+            format!("{}: [{}]", key, items.join(", "))
+        }
+        serde_json::Value::Null => return None,
+        _ => format!(
+            "{}: {}",
+            key,
+            serde_json::to_string(value).unwrap_or_default()
+        ),
+    };
+    Some(line)
+}
+
+/// Serialize a JSON value as YAML-like frontmatter lines.
+pub fn serialize_frontmatter(frontmatter: &serde_json::Value) -> String {
+    let mut lines = Vec::new();
+    if let Some(obj) = frontmatter.as_object() {
+        for (key, value) in obj {
+            lines.extend(serialize_entry(key, value));
+        }
+    }
+    lines.join("\n")
+}
+
+/// Byte offsets of the frontmatter block `parse_frontmatter` finds.
+struct Layout {
+    /// Start of the first YAML line.
+    yaml_start: usize,
+    /// The newline before the closing `---`.
+    yaml_end: usize,
+    /// First byte of the body.
+    body_start: usize,
+}
+
+fn layout(content: &str) -> Option<Layout> {
+    let lead = content.len() - content.trim_start().len();
+    let after_open = content[lead..].strip_prefix("---")?;
+    let yaml_start = content.len() - after_open.trim_start_matches(['\r', '\n']).len();
+    let yaml_end = yaml_start + content[yaml_start..].find("\n---")?;
+    let after_close = &content[yaml_end + 4..];
+    let body_start = content.len() - after_close.trim_start_matches(['\r', '\n']).len();
+    Some(Layout {
+        yaml_start,
+        yaml_end,
+        body_start,
+    })
+}
+
+/// Replaces the body of a markdown file, keeping everything up to it —
+/// frontmatter, comments, key order, and line endings — byte-for-byte.
+pub fn replace_body(content: &str, body: &str) -> String {
+    let Some(layout) = layout(content) else {
+        return body.to_string();
+    };
+    let head = &content[..layout.body_start];
+    if head.ends_with('\n') {
+        format!("{}{}", head, body)
+    } else {
+        let eol = if head.contains("\r\n") { "\r\n" } else { "\n" };
+        format!("{}{}{}{}", head, eol, eol, body)
+    }
+}
+
+/// Applies `patch` to the frontmatter of a markdown file without touching
+/// the keys it does not name or the body.
+///
+/// A key in `patch` replaces the line (and any indented continuation lines)
+/// that defines it, or is appended if the frontmatter lacks it; a `null`
+/// removes it. Comments and the order of untouched keys are kept. A file
+/// without frontmatter gets a new block in front of its content.
+pub fn patch_frontmatter(
+    content: &str,
+    patch: &serde_json::Map<String, serde_json::Value>,
+) -> String {
+    let Some(layout) = layout(content) else {
+        let fm = serialize_frontmatter(&serde_json::Value::Object(patch.clone()));
+        if fm.is_empty() {
+            return content.to_string();
+        }
+        return format!("---\n{}\n---\n\n{}", fm, content);
+    };
+
+    let yaml = &content[layout.yaml_start..layout.yaml_end];
+    let crlf = yaml.ends_with('\r') || yaml.contains("\r\n");
+    let eol = if crlf { "\r\n" } else { "\n" };
+
+    // Top-level entries: the key (None for comments and blank lines) and
+    // its lines, including indented continuations such as block lists.
+    let mut entries: Vec<(Option<String>, Vec<String>)> = Vec::new();
+    let lines = yaml.split('\n').map(|l| l.strip_suffix('\r').unwrap_or(l));
+    for line in lines.filter(|_| !yaml.trim().is_empty()) {
+        let continues = line.starts_with([' ', '\t']) || line.starts_with("- ");
+        match entries.last_mut() {
+            Some((Some(_), body)) if continues => body.push(line.to_string()),
+            _ => {
+                let key = line
+                    .split_once(':')
+                    .filter(|_| !line.starts_with('#') && !continues)
+                    .map(|(k, _)| k.trim().to_string());
+                entries.push((key, vec![line.to_string()]));
+            }
+        }
+    }
+
+    for (key, value) in patch {
+        let position = entries.iter().position(|(k, _)| k.as_deref() == Some(key));
+        match (position, serialize_entry(key, value)) {
+            (Some(i), Some(line)) => entries[i].1 = vec![line],
+            (Some(i), None) => {
+                entries.remove(i);
+            }
+            (None, Some(line)) => entries.push((Some(key.clone()), vec![line])),
+            (None, None) => {}
+        }
+    }
+
+    let yaml: Vec<String> = entries.into_iter().flat_map(|(_, lines)| lines).collect();
+    let mut out = content[..layout.yaml_start].to_string();
+    out.push_str(&yaml.join(eol));
+    if crlf {
+        out.push('\r');
+    }
+    out.push_str(&content[layout.yaml_end..]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn body_edits_keep_frontmatter_bytes() {
+        let content = "---\r\n# reviewer\r\nname: reviewer\r\ndescription: Reviews\r\n---\r\n\r\nOld body\r\n";
+        assert_eq!(
+            replace_body(content, "New body\r\n"),
+            "---\r\n# reviewer\r\nname: reviewer\r\ndescription: Reviews\r\n---\r\n\r\nNew body\r\n"
+        );
+        assert_eq!(
+            replace_body("---\nname: a\n---", "Body"),
+            "---\nname: a\n---\n\nBody"
+        );
+        assert_eq!(replace_body("No frontmatter", "Body"), "Body");
+    }
+
+    #[test]
+    fn patches_only_named_keys() {
+        let content = "---\nname: reviewer\n# keep me\ntools:\n  - Read\n  - Grep\nmodel: sonnet\ncolor: red\n---\n\nBody  \n";
+        let patch = json!({ "tools": ["Read"], "color": null, "description": "Reviews PRs" });
+        let patched = patch_frontmatter(content, patch.as_object().unwrap());
+        assert_eq!(
+            patched,
+            "---\nname: reviewer\n# keep me\ntools: [Read]\nmodel: sonnet\ndescription: Reviews PRs\n---\n\nBody  \n"
+        );
+        let (fm, body) = parse_frontmatter(&patched);
+        assert_eq!(fm["tools"], json!(["Read"]));
+        assert_eq!(body, "Body  \n");
+
+        let patch = json!({ "name": "new" });
+        assert_eq!(
+            patch_frontmatter("Body", patch.as_object().unwrap()),
+            "---\nname: new\n---\n\nBody"
+        );
+    }
+}
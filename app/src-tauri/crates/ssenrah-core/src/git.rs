@@ -0,0 +1,174 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde::Serialize;
+
+use crate::errors::IpcError;
+use crate::io::atomic::atomic_write;
+use crate::io::lock::lock_path;
+
+/// Patterns that keep personal config out of version control, each with a
+/// sample path used to test whether the existing ignore rules already cover
+/// it. MCP servers commonly take tokens from a per-user override or env file.
+const LOCAL_GITIGNORE_PATTERNS: &[(&str, &str)] = &[
+    (".claude/settings.local.json", ".claude/settings.local.json"),
+    (".claude/CLAUDE.local.md", ".claude/CLAUDE.local.md"),
+    (".claude/.mcp.local.json", ".claude/.mcp.local.json"),
+    (".claude/*.env", ".claude/mcp.env"),
+];
+
+/// Comment block written above patterns appended to `.gitignore`.
+const GITIGNORE_COMMENT: &[&str] = &[
+    "# Personal Claude Code config (added by ssenrah).",
+    "# Local settings and memory are per-developer overrides, and MCP files",
+    "# may contain tokens. Keep them out of version control.",
+];
+
+/// Outcome of checking the project's `.gitignore`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitignoreCheck {
+    /// The project-root `.gitignore`.
+    pub path: String,
+    /// Patterns not covered by the current ignore rules.
+    pub missing: Vec<String>,
+    /// Patterns appended by this call.
+    pub added: Vec<String>,
+}
+
+/// Walks up from the given path looking for a `.git` directory.
+/// Returns the path containing `.git`, or None.
+pub fn find_git_root(start: &Path) -> Option<String> {
+    let mut current = start.to_path_buf();
+    loop {
+        if current.join(".git").exists() {
+            return Some(current.to_string_lossy().to_string());
+        }
+        if !current.pop() {
+            return None;
+        }
+    }
+}
+
+/// Runs git in `cwd` and returns stdout. Exit codes other than 0 and those in
+/// `also_ok` are errors (`git check-ignore` and `git diff --no-index` use 1 to
+/// report a result, not a failure).
+pub fn run_git(cwd: &Path, args: &[&str], also_ok: &[i32]) -> Result<String, IpcError> {
+    let output = Command::new("git")
+        .current_dir(cwd)
+        .args(args)
+        .output()
+        .map_err(|e| IpcError::PlatformError {
+            message: format!("Failed to run git: {}", e),
+        })?;
+
+    match output.status.code() {
+        Some(code) if code == 0 || also_ok.contains(&code) => {
+            Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        }
+        _ => Err(IpcError::PlatformError {
+            message: format!(
+                "git {} failed: {}",
+                args.first().unwrap_or(&""),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        }),
+    }
+}
+
+/// Converts a project-relative path into a pathspec relative to the git root,
+/// forward-slash separated.
+pub fn git_spec(root: &Path, git_root: &Path, rel: &str) -> String {
+    let prefix = root
+        .strip_prefix(git_root)
+        .map(|p| {
+            p.components()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+                .join("/")
+        })
+        .unwrap_or_default();
+    if prefix.is_empty() {
+        rel.to_string()
+    } else {
+        format!("{}/{}", prefix, rel)
+    }
+}
+
+/// Checks the project-root `.gitignore` against `LOCAL_GITIGNORE_PATTERNS`,
+/// appending the missing patterns when `apply` is true.
+pub fn check_local_gitignore(root: &Path, apply: bool) -> Result<GitignoreCheck, IpcError> {
+    let gitignore = root.join(".gitignore");
+    let _lock = lock_path(&gitignore);
+    let path_str = gitignore.display().to_string();
+
+    let existing = match std::fs::read_to_string(&gitignore) {
+        Ok(s) => s,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => {
+            return Err(IpcError::PlatformError {
+                message: format!("Failed to read {}: {}", path_str, e),
+            })
+        }
+    };
+
+    let ignored = match find_git_root(root) {
+        Some(git_root) => {
+            let git_root = PathBuf::from(git_root);
+            let specs: Vec<String> = LOCAL_GITIGNORE_PATTERNS
+                .iter()
+                .map(|(_, sample)| git_spec(root, &git_root, sample))
+                .collect();
+            let mut args = vec!["check-ignore", "--no-index", "--"];
+            args.extend(specs.iter().map(String::as_str));
+            let out = run_git(&git_root, &args, &[1])?;
+            let out: HashSet<&str> = out.lines().collect();
+            specs.iter().map(|s| out.contains(s.as_str())).collect()
+        }
+        None => LOCAL_GITIGNORE_PATTERNS
+            .iter()
+            .map(|(pattern, _)| existing.lines().any(|l| l.trim() == *pattern))
+            .collect::<Vec<_>>(),
+    };
+
+    let missing: Vec<String> = LOCAL_GITIGNORE_PATTERNS
+        .iter()
+        .zip(ignored)
+        .filter(|(_, ignored)| !ignored)
+        .map(|((pattern, _), _)| pattern.to_string())
+        .collect();
+
+    let mut added = Vec::new();
+    if apply && !missing.is_empty() {
+        let eol = if existing.contains("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+        let mut content = existing.clone();
+        if !content.is_empty() {
+            if !content.ends_with('\n') {
+                content.push_str(eol);
+            }
+            content.push_str(eol);
+        }
+        for line in GITIGNORE_COMMENT
+            .iter()
+            .copied()
+            .chain(missing.iter().map(String::as_str))
+        {
+            content.push_str(line);
+            content.push_str(eol);
+        }
+
+        atomic_write(&gitignore, content.as_bytes())?;
+        added = missing.clone();
+    }
+
+    Ok(GitignoreCheck {
+        path: path_str,
+        missing,
+        added,
+    })
+}
@@ -0,0 +1,36 @@
+use crate::audit;
+use crate::errors::IpcError;
+use crate::io::lock::lock_path;
+use crate::io::self_write;
+use std::fs;
+use std::path::Path;
+
+pub fn atomic_write(path: &Path, content: &[u8]) -> Result<(), IpcError> {
+    let _lock = lock_path(path);
+    let tmp_path = path.with_extension("ssenrah-tmp");
+    let before = fs::read(path).ok();
+
+    // Write to temp file
+    if let Err(e) = fs::write(&tmp_path, content) {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(IpcError::write_failed(
+            path,
+            "Failed to write temp file",
+            &e,
+        ));
+    }
+
+    // Rename to target
+    if let Err(e) = fs::rename(&tmp_path, path) {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(IpcError::write_failed(
+            path,
+            "Failed to rename temp file",
+            &e,
+        ));
+    }
+
+    self_write::tracker().register(path);
+    audit::record_write(path, before.as_deref(), content);
+    Ok(())
+}
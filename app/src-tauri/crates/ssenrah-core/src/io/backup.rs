@@ -0,0 +1,52 @@
+use std::path::{Path, PathBuf};
+
+use crate::clock;
+use crate::errors::IpcError;
+use crate::io::copy::copy_path;
+use crate::platform::paths;
+
+/// Directory holding ssenrah's backups: `{configDir}/ssenrah/backups/`.
+pub fn backups_dir() -> Result<PathBuf, IpcError> {
+    Ok(paths::resolve_app_dir()?.join("backups"))
+}
+
+/// Copies the given items (files or directories, relative to `root`) into a
+/// new timestamped backup directory and returns its path.
+///
+/// Items that do not exist are skipped. The backup directory is named
+/// `{unixMillis}-{label}` so listings sort chronologically.
+pub fn backup_items(label: &str, root: &Path, items: &[&str]) -> Result<PathBuf, IpcError> {
+    let millis = clock::now_millis();
+    let dir = backups_dir()?.join(format!("{}-{}", millis, label));
+
+    std::fs::create_dir_all(&dir).map_err(|e| IpcError::WriteFailed {
+        path: dir.display().to_string(),
+        message: format!("Failed to create backup directory: {}", e),
+    })?;
+
+    for item in items {
+        let src = root.join(item);
+        if !src.exists() {
+            continue;
+        }
+        copy_path(&src, &dir.join(item)).map_err(|e| IpcError::WriteFailed {
+            path: src.display().to_string(),
+            message: format!("Failed to back up: {}", e),
+        })?;
+    }
+
+    Ok(dir)
+}
+
+/// Backs up a single file before it is modified. Returns `None` if the file
+/// does not exist yet.
+pub fn backup_file(label: &str, path: &Path) -> Result<Option<PathBuf>, IpcError> {
+    let (Some(parent), Some(name)) = (path.parent(), path.file_name()) else {
+        return Ok(None);
+    };
+    if !path.exists() {
+        return Ok(None);
+    }
+    let name = name.to_string_lossy();
+    backup_items(label, parent, &[name.as_ref()]).map(Some)
+}
@@ -0,0 +1,387 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+use crate::audit;
+use crate::clock;
+use crate::errors::IpcError;
+use crate::io::copy::{copy_path, remove_path};
+use crate::io::lock::{lock_path, PathLock};
+use crate::io::self_write;
+use crate::platform::paths;
+
+/// Distinguishes transactions started within the same millisecond.
+static SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+/// Directory holding journals of in-flight transactions:
+/// `{configDir}/ssenrah/journal/`.
+pub fn journal_dir() -> Result<PathBuf, IpcError> {
+    Ok(paths::resolve_app_dir()?.join("journal"))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Phase {
+    /// New content is being staged; no target has been touched.
+    Staging,
+    /// Targets are being swapped; an interruption is rolled back.
+    Committing,
+    /// Every target is in place; only cleanup is left.
+    Committed,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Entry {
+    target: PathBuf,
+    /// New content staged next to the target; `None` removes the target.
+    staged: Option<PathBuf>,
+    /// Where the original target is moved during commit.
+    backup: PathBuf,
+    /// Whether the target existed when the commit started.
+    existed: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Journal {
+    id: String,
+    label: String,
+    phase: Phase,
+    entries: Vec<Entry>,
+}
+
+/// A group of file changes applied all-or-nothing.
+///
+/// New content is staged next to each target (so the final move is a
+/// same-filesystem rename) and the intent is recorded in a journal before
+/// anything is touched. `commit` moves originals aside, renames the staged
+/// content into place, then deletes the originals. If the app dies midway,
+/// [`recover`] rolls back an unfinished commit or completes the cleanup of a
+/// finished one. Dropping an uncommitted transaction discards what it staged.
+pub struct Transaction {
+    dir: PathBuf,
+    journal: Journal,
+    /// Held from the first staged change until the transaction ends, so no
+    /// other command writes a target in between.
+    locks: Vec<PathLock>,
+    done: bool,
+}
+
+impl Transaction {
+    /// Starts a transaction journaled under [`journal_dir`].
+    pub fn begin(label: &str) -> Result<Self, IpcError> {
+        Ok(Self::begin_in(journal_dir()?, label))
+    }
+
+    /// Starts a transaction journaled under `dir`.
+    pub fn begin_in(dir: PathBuf, label: &str) -> Self {
+        let millis = clock::now_millis();
+        let id = format!(
+            "{}-{}-{}",
+            millis,
+            std::process::id(),
+            SEQUENCE.fetch_add(1, Ordering::Relaxed)
+        );
+        Transaction {
+            dir,
+            journal: Journal {
+                id,
+                label: label.to_string(),
+                phase: Phase::Staging,
+                entries: Vec::new(),
+            },
+            locks: Vec::new(),
+            done: false,
+        }
+    }
+
+    fn sibling(&self, target: &Path, kind: &str) -> PathBuf {
+        let name = target
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        target.with_file_name(format!("{}.ssenrah-{}-{}", name, kind, self.journal.id))
+    }
+
+    /// Locks `target` until the transaction ends. Staging a change locks its
+    /// target automatically; call this first when the new content is
+    /// derived from the target's current content.
+    pub fn lock(&mut self, target: &Path) {
+        self.locks.push(lock_path(target));
+    }
+
+    /// Records a new entry for `target` and returns its staging path, after
+    /// creating the target's parent directory.
+    fn add(&mut self, target: &Path, staged: bool) -> Result<Option<PathBuf>, IpcError> {
+        if self.journal.entries.iter().any(|e| e.target == target) {
+            return Err(IpcError::WriteFailed {
+                path: target.display().to_string(),
+                message: "File is already part of this transaction".to_string(),
+            });
+        }
+        self.lock(target);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| IpcError::WriteFailed {
+                path: target.display().to_string(),
+                message: format!("Failed to create parent directory: {}", e),
+            })?;
+        }
+        let staged = staged.then(|| self.sibling(target, "staged"));
+        self.journal.entries.push(Entry {
+            target: target.to_path_buf(),
+            staged: staged.clone(),
+            backup: self.sibling(target, "backup"),
+            existed: false,
+        });
+        // Write the intent before creating the staged file, so recovery can
+        // always find it.
+        self.save()?;
+        Ok(staged)
+    }
+
+    /// Stages `bytes` to be written to `target`.
+    pub fn write(&mut self, target: &Path, bytes: &[u8]) -> Result<(), IpcError> {
+        if let Some(staged) = self.add(target, true)? {
+            fs::write(&staged, bytes).map_err(|e| IpcError::WriteFailed {
+                path: target.display().to_string(),
+                message: format!("Failed to stage file: {}", e),
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Stages a copy of `source` (a file or directory tree) to replace
+    /// `target`.
+    pub fn copy(&mut self, target: &Path, source: &Path) -> Result<(), IpcError> {
+        if let Some(staged) = self.add(target, true)? {
+            copy_path(source, &staged).map_err(|e| IpcError::WriteFailed {
+                path: target.display().to_string(),
+                message: format!("Failed to stage {}: {}", source.display(), e),
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Stages an empty directory to be created at `target`.
+    pub fn create_dir(&mut self, target: &Path) -> Result<(), IpcError> {
+        if let Some(staged) = self.add(target, true)? {
+            fs::create_dir(&staged).map_err(|e| IpcError::WriteFailed {
+                path: target.display().to_string(),
+                message: format!("Failed to stage directory: {}", e),
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Stages the removal of `target` (a file or directory tree).
+    pub fn remove(&mut self, target: &Path) -> Result<(), IpcError> {
+        self.add(target, false).map(|_| ())
+    }
+
+    fn journal_path(&self) -> PathBuf {
+        self.dir.join(format!("{}.json", self.journal.id))
+    }
+
+    fn save(&self) -> Result<(), IpcError> {
+        write_journal(&self.journal_path(), &self.journal)
+    }
+
+    /// Applies every staged change. On failure, targets already swapped are
+    /// restored before the error is returned.
+    pub fn commit(mut self) -> Result<(), IpcError> {
+        let mut hashes = Vec::new();
+        for entry in &mut self.journal.entries {
+            entry.existed = fs::symlink_metadata(&entry.target).is_ok();
+            hashes.push(audit::file_hash(&entry.target));
+        }
+        self.journal.phase = Phase::Committing;
+        self.save()?;
+
+        for entry in &self.journal.entries {
+            if let Err(e) = swap(entry) {
+                rollback(&self.journal);
+                let _ = fs::remove_file(self.journal_path());
+                self.done = true;
+                return Err(IpcError::WriteFailed {
+                    path: entry.target.display().to_string(),
+                    message: format!("Failed to move file into place: {}", e),
+                });
+            }
+        }
+
+        self.journal.phase = Phase::Committed;
+        let _ = self.save();
+        for (entry, hash_before) in self.journal.entries.iter().zip(hashes) {
+            self_write::tracker().register(&entry.target);
+            if entry.staged.is_some() {
+                audit::record_replace(&entry.target, hash_before);
+            } else if entry.existed {
+                audit::record_delete(&entry.target, hash_before);
+            }
+        }
+        finish(&self.journal);
+        let _ = fs::remove_file(self.journal_path());
+        self.done = true;
+        Ok(())
+    }
+}
+
+impl Drop for Transaction {
+    fn drop(&mut self) {
+        if !self.done {
+            for staged in self
+                .journal
+                .entries
+                .iter()
+                .filter_map(|e| e.staged.as_ref())
+            {
+                let _ = remove_path(staged);
+            }
+            let _ = fs::remove_file(self.journal_path());
+        }
+    }
+}
+
+fn write_journal(path: &Path, journal: &Journal) -> Result<(), IpcError> {
+    let write_failed = |e: String| IpcError::WriteFailed {
+        path: path.display().to_string(),
+        message: format!("Failed to write journal: {}", e),
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| write_failed(e.to_string()))?;
+    }
+    let content = serde_json::to_vec_pretty(journal).map_err(|e| write_failed(e.to_string()))?;
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, content)
+        .and_then(|_| fs::rename(&tmp, path))
+        .map_err(|e| write_failed(e.to_string()))
+}
+
+/// Moves the original aside and the staged content into place.
+fn swap(entry: &Entry) -> std::io::Result<()> {
+    if entry.existed {
+        fs::rename(&entry.target, &entry.backup)?;
+    }
+    if let Some(staged) = &entry.staged {
+        fs::rename(staged, &entry.target)?;
+    }
+    Ok(())
+}
+
+/// Puts every original back and discards staged content.
+fn rollback(journal: &Journal) {
+    for entry in &journal.entries {
+        if fs::symlink_metadata(&entry.backup).is_ok() {
+            let _ = remove_path(&entry.target);
+            let _ = fs::rename(&entry.backup, &entry.target);
+        } else if !entry.existed && journal.phase == Phase::Committing {
+            // A target that did not exist was created by this transaction
+            // once its staged content is gone.
+            if entry.staged.as_ref().is_some_and(|s| !s.exists()) {
+                let _ = remove_path(&entry.target);
+            }
+        }
+        if let Some(staged) = &entry.staged {
+            let _ = remove_path(staged);
+        }
+    }
+}
+
+/// Deletes the originals of a completed commit.
+fn finish(journal: &Journal) {
+    for entry in &journal.entries {
+        let _ = remove_path(&entry.backup);
+    }
+}
+
+/// Resolves transactions interrupted by a crash: unfinished ones are rolled
+/// back, committed ones have their leftovers removed. Returns a description
+/// of each transaction that needed attention.
+pub fn recover() -> Result<Vec<String>, IpcError> {
+    Ok(recover_in(&journal_dir()?))
+}
+
+fn recover_in(dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut recovered = Vec::new();
+    for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+        if path.extension().is_none_or(|e| e != "json") {
+            let _ = fs::remove_file(&path);
+            continue;
+        }
+        let journal = fs::read(&path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<Journal>(&bytes).ok());
+        if let Some(journal) = journal {
+            let outcome = match journal.phase {
+                Phase::Staging | Phase::Committing => {
+                    rollback(&journal);
+                    "rolled back"
+                }
+                Phase::Committed => {
+                    finish(&journal);
+                    "completed"
+                }
+            };
+            recovered.push(format!("{} ({}): {}", journal.label, journal.id, outcome));
+        }
+        let _ = fs::remove_file(&path);
+    }
+    recovered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rolls_back_interrupted_commit() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let journals = dir.join("journal");
+        let (a, b) = (dir.join("a.json"), dir.join("new/b.json"));
+        fs::write(&a, "old").unwrap();
+
+        let mut txn = Transaction::begin_in(journals.clone(), "test");
+        txn.write(&a, b"new a").unwrap();
+        txn.write(&b, b"new b").unwrap();
+        for entry in &mut txn.journal.entries {
+            entry.existed = entry.target.exists();
+        }
+        txn.journal.phase = Phase::Committing;
+        txn.save().unwrap();
+        // Crash after both targets were swapped.
+        for entry in &txn.journal.entries {
+            swap(entry).unwrap();
+        }
+        std::mem::forget(txn);
+        assert_eq!(fs::read_to_string(&a).unwrap(), "new a");
+
+        assert_eq!(recover_in(&journals).len(), 1);
+        assert_eq!(fs::read_to_string(&a).unwrap(), "old");
+        assert!(!b.exists());
+        assert_eq!(fs::read_dir(dir).unwrap().count(), 3);
+        assert!(recover_in(&journals).is_empty());
+    }
+
+    #[test]
+    fn dropped_transaction_discards_staged_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let journals = dir.join("journal");
+        let a = dir.join("a.md");
+        {
+            let mut txn = Transaction::begin_in(journals.clone(), "test");
+            txn.write(&a, b"content").unwrap();
+            txn.remove(&dir.join("b.md")).unwrap();
+        }
+        assert!(!a.exists());
+        assert_eq!(fs::read_dir(dir).unwrap().count(), 1);
+        assert_eq!(fs::read_dir(&journals).unwrap().count(), 0);
+    }
+}
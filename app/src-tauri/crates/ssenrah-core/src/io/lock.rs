@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Condvar, Mutex, OnceLock};
+use std::thread::{self, ThreadId};
+
+use crate::platform::paths;
+
+/// Files currently locked, with the owning thread and its nesting depth.
+#[derive(Default)]
+struct Registry {
+    held: Mutex<HashMap<PathBuf, (ThreadId, usize)>>,
+    released: Condvar,
+}
+
+fn registry() -> &'static Registry {
+    static REGISTRY: OnceLock<Registry> = OnceLock::new();
+    REGISTRY.get_or_init(Registry::default)
+}
+
+/// Exclusive hold on one file path, released on drop.
+#[must_use = "the lock is released as soon as the guard is dropped"]
+pub struct PathLock {
+    path: PathBuf,
+}
+
+/// Blocks until no other thread holds `path`, then locks it.
+///
+/// Serializes read-modify-write cycles on the same file across concurrent
+/// IPC commands, e.g. two views editing `mcpServers` in ~/.claude.json at
+/// once. Locks are re-entrant per thread, so a command holding a file's lock
+/// can still call `atomic_write` on it. Paths are normalized first, so
+/// different spellings of one file share a lock.
+pub fn lock_path(path: &Path) -> PathLock {
+    let path = paths::normalize(path);
+    let me = thread::current().id();
+    let registry = registry();
+    let mut held = registry.held.lock().unwrap_or_else(|e| e.into_inner());
+    loop {
+        match held.get_mut(&path) {
+            None => {
+                held.insert(path.clone(), (me, 1));
+                break;
+            }
+            Some((owner, depth)) if *owner == me => {
+                *depth += 1;
+                break;
+            }
+            Some(_) => {
+                held = registry
+                    .released
+                    .wait(held)
+                    .unwrap_or_else(|e| e.into_inner());
+            }
+        }
+    }
+    PathLock { path }
+}
+
+impl Drop for PathLock {
+    fn drop(&mut self) {
+        let registry = registry();
+        let mut held = registry.held.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((_, depth)) = held.get_mut(&self.path) {
+            *depth -= 1;
+            if *depth == 0 {
+                held.remove(&self.path);
+                registry.released.notify_all();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[test]
+    fn serializes_threads_but_reenters_on_one() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("lock.json");
+        let outer = lock_path(&path);
+        let inner = lock_path(&path);
+
+        let acquired = Arc::new(AtomicBool::new(false));
+        let waiter = {
+            let (path, acquired) = (path.clone(), acquired.clone());
+            thread::spawn(move || {
+                let _lock = lock_path(&path);
+                acquired.store(true, Ordering::SeqCst);
+            })
+        };
+
+        thread::sleep(Duration::from_millis(50));
+        drop(inner);
+        thread::sleep(Duration::from_millis(50));
+        assert!(!acquired.load(Ordering::SeqCst));
+        drop(outer);
+        waiter.join().unwrap();
+        assert!(acquired.load(Ordering::SeqCst));
+    }
+}
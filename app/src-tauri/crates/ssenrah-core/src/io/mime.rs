@@ -0,0 +1,101 @@
+use std::path::Path;
+
+/// Leading bytes that identify common binary formats.
+const MAGIC: &[(&[u8], &str)] = &[
+    (b"\x89PNG\r\n\x1a\n", "image/png"),
+    (b"\xff\xd8\xff", "image/jpeg"),
+    (b"GIF87a", "image/gif"),
+    (b"GIF89a", "image/gif"),
+    (b"%PDF-", "application/pdf"),
+    (b"PK\x03\x04", "application/zip"),
+    (b"\x1f\x8b", "application/gzip"),
+    (b"\x7fELF", "application/x-executable"),
+    (b"MZ", "application/vnd.microsoft.portable-executable"),
+    (b"\xcf\xfa\xed\xfe", "application/x-mach-binary"),
+    (b"\xfe\xed\xfa\xcf", "application/x-mach-binary"),
+    (b"\0asm", "application/wasm"),
+];
+
+/// MIME types of text files, by extension.
+const TEXT_EXTENSIONS: &[(&str, &str)] = &[
+    ("md", "text/markdown"),
+    ("txt", "text/plain"),
+    ("json", "application/json"),
+    ("yaml", "application/yaml"),
+    ("yml", "application/yaml"),
+    ("sh", "text/x-shellscript"),
+    ("py", "text/x-python"),
+    ("js", "text/javascript"),
+    ("ts", "text/x-typescript"),
+    ("html", "text/html"),
+    ("css", "text/css"),
+    ("svg", "image/svg+xml"),
+];
+
+/// Guesses a MIME type from a file's leading bytes, falling back to its
+/// extension. `head` should be the first few hundred bytes of the file.
+pub fn sniff(path: &Path, head: &[u8]) -> &'static str {
+    if let Some((_, mime)) = MAGIC.iter().find(|(magic, _)| head.starts_with(magic)) {
+        return mime;
+    }
+    if head.len() >= 12 && &head[..4] == b"RIFF" && &head[8..12] == b"WEBP" {
+        return "image/webp";
+    }
+
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase())
+        .unwrap_or_default();
+    if let Some((_, mime)) = TEXT_EXTENSIONS.iter().find(|(e, _)| *e == ext) {
+        return mime;
+    }
+
+    if looks_like_text(head) {
+        "text/plain"
+    } else {
+        "application/octet-stream"
+    }
+}
+
+/// Returns `true` if `bytes` has no NUL bytes and is valid UTF-8, allowing a
+/// multi-byte character cut off at the end.
+pub fn looks_like_text(bytes: &[u8]) -> bool {
+    if bytes.contains(&0) {
+        return false;
+    }
+    match std::str::from_utf8(bytes) {
+        Ok(_) => true,
+        Err(e) => e.error_len().is_none(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sniffs_magic_bytes_before_extension() {
+        let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+        assert_eq!(sniff(Path::new("icon.md"), png), "image/png");
+        assert_eq!(
+            sniff(Path::new("a.webp"), b"RIFF\0\0\0\0WEBPVP8 "),
+            "image/webp"
+        );
+    }
+
+    #[test]
+    fn falls_back_to_extension_then_content() {
+        assert_eq!(
+            sniff(Path::new("run.sh"), b"#!/bin/sh\n"),
+            "text/x-shellscript"
+        );
+        assert_eq!(sniff(Path::new("NOTES"), b"plain words"), "text/plain");
+        assert_eq!(
+            sniff(Path::new("blob"), b"\x01\x02\0\x03"),
+            "application/octet-stream"
+        );
+        // A UTF-8 sequence truncated by the read window is still text.
+        assert!(looks_like_text(&"héllo".as_bytes()[..2]));
+    }
+}
@@ -0,0 +1,12 @@
+pub mod atomic;
+pub mod backup;
+pub mod copy;
+pub mod journal;
+pub mod lock;
+pub mod mime;
+pub mod self_write;
+pub mod snapshot;
+pub mod stat;
+pub mod text;
+pub mod trash;
+pub mod walk;
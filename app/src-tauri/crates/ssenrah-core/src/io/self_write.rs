@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use xxhash_rust::xxh3::xxh3_64;
+
+use crate::platform::paths;
+
+/// How long after a write its change events are still attributed to
+/// ssenrah. Covers notify latency and one polling interval.
+const SELF_WRITE_TTL: Duration = Duration::from_secs(5);
+
+/// State a path was left in by one of our own writes.
+#[derive(Debug)]
+struct Written {
+    at: Instant,
+    /// xxh3 of the content, or `None` if the write removed the file.
+    hash: Option<u64>,
+}
+
+/// Paths ssenrah itself just wrote, so the watcher can drop the change
+/// events they cause instead of reporting them as external edits.
+///
+/// A change is only suppressed while the file still holds what we wrote:
+/// an external edit landing right after one of our saves is reported.
+#[derive(Debug, Default)]
+pub struct SelfWriteTracker {
+    written: Mutex<HashMap<PathBuf, Written>>,
+}
+
+/// Content hash of the file at `path`, or `None` if it is not a readable
+/// file.
+fn current_hash(path: &Path) -> Option<u64> {
+    fs::read(path).ok().map(|bytes| xxh3_64(&bytes))
+}
+
+impl SelfWriteTracker {
+    /// Records that ssenrah just wrote or removed `path`. Call after the
+    /// change is on disk.
+    pub fn register(&self, path: &Path) {
+        if path.is_dir() {
+            return;
+        }
+        let now = Instant::now();
+        let mut written = self.written.lock().unwrap_or_else(|e| e.into_inner());
+        written.retain(|_, w| now.duration_since(w.at) < SELF_WRITE_TTL);
+        written.insert(
+            paths::normalize(path),
+            Written {
+                at: now,
+                hash: current_hash(path),
+            },
+        );
+    }
+
+    /// Returns `true` if a change event for `path` was caused by a recent
+    /// ssenrah write and the file has not changed since.
+    pub fn is_self_write(&self, path: &Path) -> bool {
+        let written = self.written.lock().unwrap_or_else(|e| e.into_inner());
+        written
+            .get(&paths::normalize(path))
+            .is_some_and(|w| w.at.elapsed() < SELF_WRITE_TTL && w.hash == current_hash(path))
+    }
+}
+
+/// The process-wide tracker. It is shared through a static rather than
+/// Tauri state because writes happen deep inside `io` helpers that have no
+/// handle to the app.
+pub fn tracker() -> &'static SelfWriteTracker {
+    static TRACKER: OnceLock<SelfWriteTracker> = OnceLock::new();
+    TRACKER.get_or_init(SelfWriteTracker::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suppresses_only_unchanged_own_writes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let file = dir.join("settings.json");
+        let tracker = SelfWriteTracker::default();
+
+        fs::write(&file, "{}").unwrap();
+        assert!(!tracker.is_self_write(&file));
+        tracker.register(&file);
+        assert!(tracker.is_self_write(&dir.join(".").join("settings.json")));
+
+        // An external edit after our write is still reported.
+        fs::write(&file, r#"{"model":"opus"}"#).unwrap();
+        assert!(!tracker.is_self_write(&file));
+
+        fs::remove_file(&file).unwrap();
+        tracker.register(&file);
+        assert!(tracker.is_self_write(&file));
+    }
+}
@@ -0,0 +1,259 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::audit;
+use crate::clock::now_millis;
+use crate::errors::IpcError;
+use crate::io::atomic::atomic_write;
+use crate::io::backup::backups_dir;
+use crate::platform::paths;
+use crate::types::ConfigScope;
+
+/// Snapshots of every scope's config files:
+/// `{configDir}/ssenrah/backups/snapshots/`.
+///
+/// Each snapshot is a `{id}.json` manifest. File contents are stored once
+/// under `objects/{sha256}`, so unchanged files cost nothing per snapshot.
+pub fn snapshots_dir() -> Result<PathBuf, IpcError> {
+    Ok(backups_dir()?.join("snapshots"))
+}
+
+/// One file captured by a snapshot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotFile {
+    /// "user", "project", or "local".
+    pub scope: String,
+    pub path: String,
+    /// SHA-256 of the content, naming its object.
+    pub hash: String,
+    pub size: u64,
+}
+
+/// A point-in-time copy of the config files that existed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Snapshot {
+    pub id: String,
+    /// Milliseconds since the Unix epoch.
+    pub created_at: u64,
+    /// "scheduled", "manual", or "pre-restore".
+    pub trigger: String,
+    pub files: Vec<SnapshotFile>,
+}
+
+/// The settings, memory, and MCP files of the user scope and of each
+/// project in `project_roots`, as (scope, path) pairs. Files need not exist.
+pub fn snapshot_targets(
+    project_roots: &[String],
+) -> Result<Vec<(&'static str, PathBuf)>, IpcError> {
+    let config_dir = paths::resolve_config_dir()?;
+    let mut targets = vec![
+        ("user", config_dir.join("settings.json")),
+        ("user", config_dir.join("CLAUDE.md")),
+    ];
+    for root in project_roots {
+        let project_root = Some(root.clone());
+        let root = Path::new(root);
+        for (scope, config_scope) in [
+            ("project", ConfigScope::Project),
+            ("local", ConfigScope::Local),
+        ] {
+            if let Ok(path) = paths::resolve_settings_path(&config_scope, &project_root) {
+                targets.push((scope, path));
+            }
+        }
+        targets.push(("project", root.join(".claude").join("CLAUDE.md")));
+        targets.push(("project", root.join("CLAUDE.md")));
+        targets.push(("local", root.join(".claude").join("CLAUDE.local.md")));
+        if let Ok(path) = paths::resolve_mcp_path("project", &project_root) {
+            targets.push(("project", path));
+        }
+    }
+    Ok(targets)
+}
+
+/// Snapshots the files in `targets` that exist, unless they are identical
+/// to the latest snapshot. Returns the new snapshot, or `None` if nothing
+/// changed.
+pub fn take(trigger: &str, targets: &[(&str, PathBuf)]) -> Result<Option<Snapshot>, IpcError> {
+    take_in(&snapshots_dir()?, trigger, targets)
+}
+
+fn take_in(
+    dir: &Path,
+    trigger: &str,
+    targets: &[(&str, PathBuf)],
+) -> Result<Option<Snapshot>, IpcError> {
+    let objects = dir.join("objects");
+    let failed = |path: &Path, e: std::io::Error| IpcError::WriteFailed {
+        path: path.display().to_string(),
+        message: format!("Failed to snapshot: {}", e),
+    };
+    fs::create_dir_all(&objects).map_err(|e| failed(&objects, e))?;
+
+    let mut files = Vec::new();
+    for (scope, path) in targets {
+        let Ok(bytes) = fs::read(path) else {
+            continue;
+        };
+        let hash = audit::hash(&bytes);
+        let object = objects.join(&hash);
+        if !object.exists() {
+            fs::write(&object, &bytes).map_err(|e| failed(&object, e))?;
+        }
+        files.push(SnapshotFile {
+            scope: scope.to_string(),
+            path: path.display().to_string(),
+            hash,
+            size: bytes.len() as u64,
+        });
+    }
+
+    if list_in(dir)
+        .first()
+        .is_some_and(|latest| latest.files == files)
+    {
+        return Ok(None);
+    }
+    let created_at = now_millis();
+    let snapshot = Snapshot {
+        id: format!("{}-{}", created_at, trigger),
+        created_at,
+        trigger: trigger.to_string(),
+        files,
+    };
+    let manifest = dir.join(format!("{}.json", snapshot.id));
+    let record = serde_json::to_vec_pretty(&snapshot).unwrap_or_default();
+    fs::write(&manifest, record).map_err(|e| failed(&manifest, e))?;
+    Ok(Some(snapshot))
+}
+
+/// Lists snapshots, newest first.
+pub fn list() -> Result<Vec<Snapshot>, IpcError> {
+    Ok(list_in(&snapshots_dir()?))
+}
+
+fn list_in(dir: &Path) -> Vec<Snapshot> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut snapshots: Vec<Snapshot> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|e| e == "json"))
+        .filter_map(|p| fs::read(p).ok())
+        .filter_map(|bytes| serde_json::from_slice(&bytes).ok())
+        .collect();
+    snapshots.sort_by_key(|s| std::cmp::Reverse(s.created_at));
+    snapshots
+}
+
+/// Ids are manifest file stems; rejects anything that could escape the
+/// snapshots directory.
+fn valid_id(id: &str) -> bool {
+    !id.is_empty() && !id.contains(['/', '\\']) && !id.starts_with('.')
+}
+
+/// Writes the files of snapshot `id` back to their paths, or only those in
+/// `only` when given. The current state of those paths is snapshotted first
+/// (trigger "pre-restore") so the restore can itself be undone. Files the
+/// snapshot did not contain are left alone. Returns the restored paths.
+pub fn restore(id: &str, only: Option<&[String]>) -> Result<Vec<String>, IpcError> {
+    restore_in(&snapshots_dir()?, id, only)
+}
+
+fn restore_in(dir: &Path, id: &str, only: Option<&[String]>) -> Result<Vec<String>, IpcError> {
+    let manifest = dir.join(format!("{}.json", id));
+    let not_found = || IpcError::NotFound {
+        path: manifest.display().to_string(),
+    };
+    if !valid_id(id) {
+        return Err(not_found());
+    }
+    let snapshot: Snapshot = fs::read(&manifest)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .ok_or_else(not_found)?;
+
+    let files: Vec<&SnapshotFile> = snapshot
+        .files
+        .iter()
+        .filter(|f| only.is_none_or(|only| only.contains(&f.path)))
+        .collect();
+    let mut contents = BTreeMap::new();
+    for file in &files {
+        let object = dir.join("objects").join(&file.hash);
+        let bytes = fs::read(&object).map_err(|_| IpcError::NotFound {
+            path: object.display().to_string(),
+        })?;
+        contents.insert(file.path.clone(), bytes);
+    }
+
+    let current: Vec<(&str, PathBuf)> = files
+        .iter()
+        .map(|f| (f.scope.as_str(), PathBuf::from(&f.path)))
+        .collect();
+    take_in(dir, "pre-restore", &current)?;
+
+    for (path, bytes) in &contents {
+        let path = Path::new(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| IpcError::WriteFailed {
+                path: path.display().to_string(),
+                message: format!("Failed to create parent directory: {}", e),
+            })?;
+        }
+        atomic_write(path, bytes)?;
+    }
+    Ok(contents.into_keys().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshots_dedup_and_restore() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let dir = root.join("snapshots");
+        let settings = root.join("project/.claude/settings.json");
+        let memory = root.join("project/CLAUDE.md");
+        fs::create_dir_all(settings.parent().unwrap()).unwrap();
+        fs::write(&settings, r#"{"model":"opus"}"#).unwrap();
+        fs::write(&memory, "# Notes").unwrap();
+        let targets = [
+            ("project", settings.clone()),
+            ("project", memory.clone()),
+            ("local", root.join("project/.claude/settings.local.json")),
+        ];
+
+        let first = take_in(&dir, "manual", &targets).unwrap().unwrap();
+        assert_eq!(first.files.len(), 2);
+        assert!(take_in(&dir, "scheduled", &targets).unwrap().is_none());
+
+        fs::write(&settings, r#"{"model":"sonnet"}"#).unwrap();
+        fs::write(&memory, "# Changed").unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        take_in(&dir, "scheduled", &targets).unwrap().unwrap();
+        // Each distinct content is stored once.
+        assert_eq!(fs::read_dir(dir.join("objects")).unwrap().count(), 4);
+
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        let only = [settings.display().to_string()];
+        let restored = restore_in(&dir, &first.id, Some(&only)).unwrap();
+        assert_eq!(restored, only);
+        assert_eq!(
+            fs::read_to_string(&settings).unwrap(),
+            r#"{"model":"opus"}"#
+        );
+        assert_eq!(fs::read_to_string(&memory).unwrap(), "# Changed");
+        assert_eq!(list_in(&dir)[0].trigger, "pre-restore");
+
+        assert!(restore_in(&dir, "../escape", None).is_err());
+    }
+}
@@ -0,0 +1,117 @@
+use std::fs;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+use serde::Serialize;
+
+/// Metadata of a config file or directory, for "changed externally" and
+/// "this is a symlink" banners.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileStat {
+    pub path: String,
+    /// `false` for a missing file or a dangling symlink.
+    pub exists: bool,
+    pub is_dir: bool,
+    pub size_bytes: Option<u64>,
+    /// Milliseconds since the Unix epoch.
+    pub modified_ms: Option<u64>,
+    pub readonly: bool,
+    /// Permission bits in octal, e.g. "644". `None` on Windows.
+    pub mode: Option<String>,
+    /// Owning user's name, or the numeric uid if it has no passwd entry.
+    /// `None` on Windows.
+    pub owner: Option<String>,
+    pub is_symlink: bool,
+    /// Where the symlink points, as stored in the link.
+    pub symlink_target: Option<String>,
+}
+
+/// Looks up the name of `uid` in /etc/passwd.
+#[cfg(unix)]
+fn user_name(uid: u32) -> Option<String> {
+    let passwd = fs::read_to_string("/etc/passwd").ok()?;
+    passwd.lines().find_map(|line| {
+        let mut fields = line.split(':');
+        let name = fields.next()?;
+        let id = fields.nth(1)?.parse::<u32>().ok()?;
+        (id == uid).then(|| name.to_string())
+    })
+}
+
+#[cfg(unix)]
+fn unix_details(meta: &fs::Metadata) -> (Option<String>, Option<String>) {
+    use std::os::unix::fs::MetadataExt;
+    let owner = user_name(meta.uid()).unwrap_or_else(|| meta.uid().to_string());
+    (Some(format!("{:o}", meta.mode() & 0o7777)), Some(owner))
+}
+
+#[cfg(not(unix))]
+fn unix_details(_meta: &fs::Metadata) -> (Option<String>, Option<String>) {
+    (None, None)
+}
+
+/// Stats `path`, following symlinks for everything but `is_symlink` and
+/// `symlink_target`. Never fails: a missing path reports `exists: false`.
+pub fn stat(path: &Path) -> FileStat {
+    let link = fs::symlink_metadata(path).ok();
+    let is_symlink = link.as_ref().is_some_and(|m| m.file_type().is_symlink());
+    let symlink_target = is_symlink
+        .then(|| fs::read_link(path).ok())
+        .flatten()
+        .map(|t| t.display().to_string());
+
+    let meta = fs::metadata(path).ok();
+    let (mode, owner) = meta.as_ref().map(unix_details).unwrap_or((None, None));
+    FileStat {
+        path: path.display().to_string(),
+        exists: meta.is_some(),
+        is_dir: meta.as_ref().is_some_and(|m| m.is_dir()),
+        size_bytes: meta.as_ref().map(|m| m.len()),
+        modified_ms: meta
+            .as_ref()
+            .and_then(|m| m.modified().ok())
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_millis() as u64),
+        readonly: meta.as_ref().is_some_and(|m| m.permissions().readonly()),
+        mode,
+        owner,
+        is_symlink,
+        symlink_target,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_files_and_symlinks() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let file = dir.join("settings.json");
+        fs::write(&file, "{}").unwrap();
+
+        let s = stat(&file);
+        assert!(s.exists && !s.is_dir && !s.is_symlink);
+        assert_eq!(s.size_bytes, Some(2));
+        assert!(s.modified_ms.is_some());
+
+        let missing = stat(&dir.join("missing.json"));
+        assert!(!missing.exists && missing.size_bytes.is_none());
+
+        #[cfg(unix)]
+        {
+            let link = dir.join("link.json");
+            std::os::unix::fs::symlink(&file, &link).unwrap();
+            let s = stat(&link);
+            assert!(s.exists && s.is_symlink);
+            assert_eq!(s.symlink_target, Some(file.display().to_string()));
+            assert!(s.owner.is_some() && s.mode.is_some());
+
+            fs::remove_file(&file).unwrap();
+            let dangling = stat(&link);
+            assert!(!dangling.exists && dangling.is_symlink);
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::IpcError;
+use crate::io::atomic::atomic_write;
+
+const BOM: &str = "\u{feff}";
+
+/// Line terminator of a text file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LineEnding {
+    #[default]
+    Lf,
+    Crlf,
+}
+
+/// How a text file is laid out on disk, so an edit can be written back the
+/// way the file was found instead of producing a whole-file diff.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextFormat {
+    pub line_ending: LineEnding,
+    /// The file starts with a UTF-8 byte order mark.
+    pub bom: bool,
+}
+
+impl TextFormat {
+    /// Detects the format of file contents. Mixed files count as CRLF when
+    /// most of their line breaks are.
+    pub fn detect(text: &str) -> Self {
+        let crlf = text.matches("\r\n").count();
+        let lf = text.matches('\n').count() - crlf;
+        TextFormat {
+            line_ending: if crlf > lf {
+                LineEnding::Crlf
+            } else {
+                LineEnding::Lf
+            },
+            bom: text.starts_with(BOM),
+        }
+    }
+
+    /// Format of the file at `path`, or the default (LF, no BOM) if it does
+    /// not exist or cannot be read.
+    pub fn of_file(path: &Path) -> Self {
+        fs::read(path)
+            .map(|bytes| Self::detect(&String::from_utf8_lossy(&bytes)))
+            .unwrap_or_default()
+    }
+
+    /// `text` with its line breaks and BOM converted to this format.
+    pub fn apply(&self, text: &str) -> String {
+        let text = strip_bom(text).replace("\r\n", "\n");
+        let text = match self.line_ending {
+            LineEnding::Lf => text,
+            LineEnding::Crlf => text.replace('\n', "\r\n"),
+        };
+        if self.bom {
+            format!("{}{}", BOM, text)
+        } else {
+            text
+        }
+    }
+}
+
+/// `text` without a leading byte order mark, which JSON and frontmatter
+/// parsers would otherwise trip over.
+pub fn strip_bom(text: &str) -> &str {
+    text.strip_prefix(BOM).unwrap_or(text)
+}
+
+/// Reads a UTF-8 text file, returning its content without a BOM and the
+/// format it was stored in.
+pub fn read_text(path: &Path) -> std::io::Result<(String, TextFormat)> {
+    let content = fs::read_to_string(path)?;
+    let format = TextFormat::detect(&content);
+    Ok((strip_bom(&content).to_string(), format))
+}
+
+/// Atomically writes `text` in `format`, or, when `None`, in the format of
+/// the file it replaces (LF without a BOM for a new file).
+pub fn write_text(path: &Path, text: &str, format: Option<TextFormat>) -> Result<(), IpcError> {
+    let format = format.unwrap_or_else(|| TextFormat::of_file(path));
+    atomic_write(path, format.apply(text).as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_and_reapplies_the_original_format() {
+        let original = "\u{feff}# Memory\r\n\r\n- one\r\n";
+        let format = TextFormat::detect(original);
+        assert_eq!(
+            format,
+            TextFormat {
+                line_ending: LineEnding::Crlf,
+                bom: true
+            }
+        );
+
+        // The editor hands back LF text without a BOM.
+        assert_eq!(
+            format.apply("# Memory\n\n- one\n- two\n"),
+            "\u{feff}# Memory\r\n\r\n- one\r\n- two\r\n"
+        );
+        assert_eq!(TextFormat::default().apply(original), "# Memory\n\n- one\n");
+        assert_eq!(
+            TextFormat::detect("a\nb\r\nc\n").line_ending,
+            LineEnding::Lf
+        );
+    }
+}
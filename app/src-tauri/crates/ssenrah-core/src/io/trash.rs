@@ -0,0 +1,244 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::clock::now_millis;
+use crate::errors::IpcError;
+use crate::io::copy::{copy_path, remove_path};
+use crate::platform::paths;
+
+/// How long deleted items are kept before they are purged for good.
+pub const RETENTION: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// Directory holding deleted agents and skills: `{configDir}/ssenrah/trash/`.
+pub fn trash_dir() -> Result<PathBuf, IpcError> {
+    Ok(paths::resolve_app_dir()?.join("trash"))
+}
+
+/// A deleted agent or skill that can still be restored.
+///
+/// Each item lives in `{trash}/{id}/` as `item.json` plus the deleted file
+/// or directory under `payload`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashItem {
+    pub id: String,
+    /// "agent", "skill", or "output-style".
+    pub kind: String,
+    pub scope: String,
+    /// File or directory name at the original location.
+    pub name: String,
+    pub original_path: String,
+    /// Milliseconds since the Unix epoch.
+    pub deleted_at: u64,
+    pub is_dir: bool,
+}
+
+/// Moves `path`, falling back to copy-and-remove across filesystems.
+fn move_path(from: &Path, to: &Path) -> std::io::Result<()> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    copy_path(from, to)?;
+    remove_path(from)
+}
+
+/// Moves `path` into the trash instead of deleting it and returns the
+/// record needed to restore it. Expired items are purged first.
+pub fn move_to_trash(kind: &str, scope: &str, path: &Path) -> Result<TrashItem, IpcError> {
+    trash_in(&trash_dir()?, kind, scope, path, false)
+}
+
+/// Like [`move_to_trash`] but leaves `path` in place, for deletions that a
+/// journaled transaction carries out. [`discard`] the item if the
+/// transaction fails.
+pub fn copy_to_trash(kind: &str, scope: &str, path: &Path) -> Result<TrashItem, IpcError> {
+    trash_in(&trash_dir()?, kind, scope, path, true)
+}
+
+/// Permanently removes a trashed item.
+pub fn discard(id: &str) {
+    if let (true, Ok(dir)) = (valid_id(id), trash_dir()) {
+        let _ = remove_path(&dir.join(id));
+    }
+}
+
+/// Ids are single directory names; rejects anything that could escape the
+/// trash directory.
+fn valid_id(id: &str) -> bool {
+    !id.is_empty() && !id.contains(['/', '\\']) && !id.starts_with('.')
+}
+
+fn trash_in(
+    dir: &Path,
+    kind: &str,
+    scope: &str,
+    path: &Path,
+    keep_original: bool,
+) -> Result<TrashItem, IpcError> {
+    purge_expired_in(dir, now_millis());
+
+    let deleted_at = now_millis();
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    static SEQUENCE: AtomicU64 = AtomicU64::new(0);
+    let id = format!(
+        "{}-{}-{}-{}",
+        deleted_at,
+        SEQUENCE.fetch_add(1, Ordering::Relaxed),
+        kind,
+        name
+    );
+    let item_dir = dir.join(&id);
+    let failed = |e: std::io::Error| IpcError::WriteFailed {
+        path: path.display().to_string(),
+        message: format!("Failed to move to trash: {}", e),
+    };
+
+    fs::create_dir_all(&item_dir).map_err(failed)?;
+    let item = TrashItem {
+        id,
+        kind: kind.to_string(),
+        scope: scope.to_string(),
+        name,
+        original_path: path.display().to_string(),
+        deleted_at,
+        is_dir: path.is_dir(),
+    };
+    let record = serde_json::to_vec_pretty(&item).unwrap_or_default();
+    fs::write(item_dir.join("item.json"), record).map_err(failed)?;
+    let payload = item_dir.join("payload");
+    let stored = if keep_original {
+        copy_path(path, &payload)
+    } else {
+        move_path(path, &payload)
+    };
+    if let Err(e) = stored {
+        let _ = remove_path(&item_dir);
+        return Err(failed(e));
+    }
+    Ok(item)
+}
+
+/// Lists restorable items, newest first. Expired items are purged first.
+pub fn list() -> Result<Vec<TrashItem>, IpcError> {
+    Ok(list_in(&trash_dir()?))
+}
+
+fn list_in(dir: &Path) -> Vec<TrashItem> {
+    purge_expired_in(dir, now_millis());
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut items: Vec<TrashItem> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| fs::read(e.path().join("item.json")).ok())
+        .filter_map(|bytes| serde_json::from_slice(&bytes).ok())
+        .collect();
+    items.sort_by_key(|i| std::cmp::Reverse(i.deleted_at));
+    items
+}
+
+/// Moves a trashed item back to its original location. Fails if something
+/// else has been created there since.
+pub fn restore(id: &str) -> Result<TrashItem, IpcError> {
+    restore_in(&trash_dir()?, id)
+}
+
+fn restore_in(dir: &Path, id: &str) -> Result<TrashItem, IpcError> {
+    let item_dir = dir.join(id);
+    let not_found = || IpcError::NotFound {
+        path: item_dir.display().to_string(),
+    };
+    if !valid_id(id) {
+        return Err(not_found());
+    }
+    let item: TrashItem = fs::read(item_dir.join("item.json"))
+        .ok()
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .ok_or_else(not_found)?;
+
+    let original = PathBuf::from(&item.original_path);
+    if original.exists() {
+        return Err(IpcError::Conflict {
+            path: item.original_path.clone(),
+            message: "Cannot restore: a file with this name already exists".to_string(),
+        });
+    }
+    if let Some(parent) = original.parent() {
+        fs::create_dir_all(parent).map_err(|e| IpcError::WriteFailed {
+            path: item.original_path.clone(),
+            message: format!("Failed to create parent directory: {}", e),
+        })?;
+    }
+    move_path(&item_dir.join("payload"), &original).map_err(|e| IpcError::WriteFailed {
+        path: item.original_path.clone(),
+        message: format!("Failed to restore: {}", e),
+    })?;
+    let _ = remove_path(&item_dir);
+    Ok(item)
+}
+
+/// Permanently removes items deleted more than [`RETENTION`] before `now`
+/// (milliseconds since the Unix epoch), and any leftovers without a
+/// readable record.
+fn purge_expired_in(dir: &Path, now: u64) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let cutoff = now.saturating_sub(RETENTION.as_millis() as u64);
+    for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+        let item = fs::read(path.join("item.json"))
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<TrashItem>(&bytes).ok());
+        if item.is_none_or(|i| i.deleted_at < cutoff) {
+            let _ = remove_path(&path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trashes_and_restores_files_and_directories() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let trash = root.join("trash");
+        let agent = root.join("agents/reviewer.md");
+        let skill = root.join("skills/deploy");
+        fs::create_dir_all(agent.parent().unwrap()).unwrap();
+        fs::create_dir_all(&skill).unwrap();
+        fs::write(&agent, "---\nname: reviewer\n---\n").unwrap();
+        fs::write(skill.join("SKILL.md"), "# Deploy").unwrap();
+
+        let a = trash_in(&trash, "agent", "user", &agent, false).unwrap();
+        let s = trash_in(&trash, "skill", "project", &skill, false).unwrap();
+        assert!(!agent.exists() && !skill.exists());
+        assert!(s.is_dir && !a.is_dir);
+        assert_eq!(list_in(&trash).len(), 2);
+
+        restore_in(&trash, &s.id).unwrap();
+        assert_eq!(
+            fs::read_to_string(skill.join("SKILL.md")).unwrap(),
+            "# Deploy"
+        );
+
+        // Restoring over a new file with the same name is refused.
+        fs::write(&agent, "new").unwrap();
+        assert!(restore_in(&trash, &a.id).is_err());
+        assert!(restore_in(&trash, "../agents").is_err());
+
+        let expiry = a.deleted_at + RETENTION.as_millis() as u64;
+        purge_expired_in(&trash, expiry);
+        assert_eq!(list_in(&trash).len(), 1);
+        purge_expired_in(&trash, expiry + 1);
+        assert!(list_in(&trash).is_empty());
+    }
+}
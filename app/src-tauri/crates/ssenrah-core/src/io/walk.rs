@@ -0,0 +1,115 @@
+use std::collections::HashSet;
+use std::fs::{self, FileType};
+use std::path::{Path, PathBuf};
+
+use crate::git::run_git;
+
+/// Directory levels below the project root that are searched by default.
+pub const DEFAULT_MAX_DEPTH: usize = 8;
+
+/// Directories never searched, even outside a git repository.
+const SKIPPED_DIRS: &[&str] = &[
+    ".git",
+    "node_modules",
+    "target",
+    "dist",
+    "build",
+    "vendor",
+    ".venv",
+    "__pycache__",
+];
+
+/// What `walk_project` should do after visiting an entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Visit {
+    Continue,
+    Stop,
+}
+
+/// Directories git ignores under `root`, or none outside a repository.
+fn ignored_dirs(root: &Path) -> HashSet<PathBuf> {
+    let args = [
+        "ls-files",
+        "-z",
+        "--others",
+        "--ignored",
+        "--exclude-standard",
+        "--directory",
+    ];
+    run_git(root, &args, &[])
+        .map(|out| {
+            out.split('\0')
+                .filter_map(|p| p.strip_suffix('/'))
+                .map(|p| root.join(p))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+struct Walk<'a, F> {
+    ignored: HashSet<PathBuf>,
+    max_depth: usize,
+    visit: &'a mut F,
+}
+
+impl<F: FnMut(&Path, &FileType) -> Visit> Walk<'_, F> {
+    fn dir(&mut self, dir: &Path, depth: usize) -> Visit {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return Visit::Continue;
+        };
+        let mut entries: Vec<_> = entries.flatten().collect();
+        entries.sort_by_key(|e| e.file_name());
+
+        for entry in entries {
+            let path = entry.path();
+            // file_type does not follow symlinks, so linked directories
+            // cannot send the walk in circles.
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if (self.visit)(&path, &file_type) == Visit::Stop {
+                return Visit::Stop;
+            }
+
+            let name = entry.file_name().to_string_lossy().to_string();
+            let hidden = name.starts_with('.') && name != ".claude";
+            if file_type.is_dir()
+                && depth < self.max_depth
+                && !hidden
+                && !SKIPPED_DIRS.contains(&name.as_str())
+                && !self.ignored.contains(&path)
+                && self.dir(&path, depth + 1) == Visit::Stop
+            {
+                return Visit::Stop;
+            }
+        }
+        Visit::Continue
+    }
+}
+
+/// Visits every entry under `root` in path order, descending at most
+/// `max_depth` levels. Gitignored directories (when `root` is in a git
+/// repository), hidden directories other than `.claude`, and common build
+/// and dependency directories are visited but not entered.
+pub fn walk_project(
+    root: &Path,
+    max_depth: usize,
+    mut visit: impl FnMut(&Path, &FileType) -> Visit,
+) {
+    let mut walk = Walk {
+        ignored: ignored_dirs(root),
+        max_depth,
+        visit: &mut visit,
+    };
+    walk.dir(root, 0);
+}
+
+/// `path` relative to `root`, forward-slash separated.
+pub fn relative_slash_path(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect::<Vec<_>>()
+        .join("/")
+}
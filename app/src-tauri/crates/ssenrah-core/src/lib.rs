@@ -0,0 +1,34 @@
+//! Config logic shared by the ssenrah app and `ssenrah-cli`: paths, merge,
+//! validation, frontmatter, and atomic IO. Nothing here depends on Tauri.
+
+pub mod args;
+pub mod audit;
+pub mod bundle;
+pub mod claude_json;
+pub mod clock;
+pub mod config;
+pub mod doctor;
+pub mod drift;
+pub mod errors;
+pub mod fixes;
+pub mod frontmatter;
+pub mod git;
+pub mod io;
+pub mod lockfile;
+pub mod memory;
+pub mod packages;
+pub mod platform;
+pub mod policy;
+pub mod query;
+pub mod rule_suggestions;
+pub mod scaffold;
+pub mod schema;
+pub mod sessions;
+pub mod share;
+pub mod similarity;
+pub mod support;
+pub mod types;
+pub mod validation;
+
+pub use errors::IpcError;
+pub use types::*;
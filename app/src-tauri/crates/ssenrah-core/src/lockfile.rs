@@ -0,0 +1,63 @@
+use crate::errors::IpcError;
+use crate::platform::{paths, portable};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// Path of the lockfile this process created. The config dir can be
+/// overridden at runtime, so release must not re-resolve it.
+static ACQUIRED_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+/// `{configDir}/.ssenrah.lock`, or `{exeDir}/ssenrah-data/.ssenrah.lock` in
+/// portable mode so each copy of a portable install locks only itself.
+pub fn lockfile_path() -> Result<PathBuf, IpcError> {
+    match portable::data_dir() {
+        Some(dir) => Ok(dir.join(".ssenrah.lock")),
+        None => Ok(paths::resolve_config_dir()?.join(".ssenrah.lock")),
+    }
+}
+
+/// Creates the lockfile, failing if another running instance holds it.
+///
+/// Without a config directory there is nowhere to put the lock, so the app
+/// runs unlocked; the doctor reports the missing directory.
+pub fn acquire_lock() -> Result<(), String> {
+    let path = match lockfile_path() {
+        Ok(path) => path,
+        Err(e) => {
+            tracing::warn!(error = %e, "running without a lockfile");
+            return Ok(());
+        }
+    };
+    if path.exists() {
+        // Check if the PID in the lockfile is still running
+        if let Ok(content) = fs::read_to_string(&path) {
+            if let Ok(pid) = content.trim().parse::<u32>() {
+                if is_process_running(pid) {
+                    return Err("Another ssenrah instance is already running".to_string());
+                }
+            }
+        }
+        // Stale lockfile, remove it
+        let _ = fs::remove_file(&path);
+    }
+
+    // Create lockfile with our PID
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    let pid = std::process::id();
+    fs::write(&path, pid.to_string()).map_err(|e| format!("Failed to create lockfile: {}", e))?;
+    let _ = ACQUIRED_PATH.set(path);
+    Ok(())
+}
+
+pub fn release_lock() {
+    if let Some(path) = ACQUIRED_PATH.get() {
+        let _ = fs::remove_file(path);
+    }
+}
+
+fn is_process_running(pid: u32) -> bool {
+    std::path::Path::new(&format!("/proc/{}", pid)).exists()
+}
@@ -0,0 +1,98 @@
+use serde::Serialize;
+
+/// Memory files up to this size are returned whole by `read_memory`.
+pub const DEFAULT_MAX_INLINE_BYTES: u64 = 1024 * 1024;
+
+/// Largest page `read_memory_chunk` returns.
+pub const MAX_CHUNK_BYTES: u64 = 4 * 1024 * 1024;
+
+/// One page of a memory file.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryChunk {
+    pub content: String,
+    /// Byte offset the content starts at. May be past the requested offset
+    /// if that fell inside a multi-byte character.
+    pub offset: u64,
+    /// Where the next page starts.
+    pub next_offset: u64,
+    pub size_bytes: u64,
+    pub eof: bool,
+}
+
+/// Length of `bytes` without a multi-byte character cut off at the end.
+fn complete_len(bytes: &[u8]) -> usize {
+    for back in 1..=bytes.len().min(4) {
+        let b = bytes[bytes.len() - back];
+        if b & 0xC0 != 0x80 {
+            let width = match b {
+                0x00..=0x7F => 1,
+                0xC0..=0xDF => 2,
+                0xE0..=0xEF => 3,
+                _ => 4,
+            };
+            return if back < width {
+                bytes.len() - back
+            } else {
+                bytes.len()
+            };
+        }
+    }
+    bytes.len()
+}
+
+/// Decodes `bytes`, read from `offset` of a `size_bytes` file, on UTF-8
+/// character boundaries: leading continuation bytes are skipped and a
+/// character cut off at the end is left for the next page. Invalid UTF-8
+/// inside the page is replaced with U+FFFD.
+pub fn decode(bytes: &[u8], offset: u64, size_bytes: u64) -> MemoryChunk {
+    let at_eof = offset + bytes.len() as u64 >= size_bytes;
+    let skip = bytes
+        .iter()
+        .take(3)
+        .take_while(|b| (**b & 0xC0) == 0x80)
+        .count();
+    let mut body = &bytes[skip..];
+    if !at_eof {
+        body = &body[..complete_len(body)];
+    }
+    let start = offset + skip as u64;
+    let next_offset = start + body.len() as u64;
+    MemoryChunk {
+        content: String::from_utf8_lossy(body).into_owned(),
+        offset: start,
+        next_offset,
+        size_bytes,
+        eof: next_offset >= size_bytes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_on_character_boundaries() {
+        let text = "ab한글cd".as_bytes();
+        let size = text.len() as u64;
+
+        // "한" is bytes 2..5; a page ending at 4 stops before it.
+        let first = decode(&text[..4], 0, size);
+        assert_eq!((first.content.as_str(), first.next_offset), ("ab", 2));
+        assert!(!first.eof);
+
+        // Starting mid-character skips to the next one.
+        let mid = decode(&text[3..], 3, size);
+        assert_eq!((mid.content.as_str(), mid.offset), ("글cd", 5));
+        assert!(mid.eof);
+
+        // The cut-off character is still held back when a whole one
+        // precedes it.
+        let tail = decode(&text[..6], 0, size);
+        assert_eq!(tail.content, "ab한");
+
+        let all = decode(text, 0, size);
+        assert_eq!(all.content, "ab한글cd");
+        assert_eq!(all.next_offset, size);
+    }
+}
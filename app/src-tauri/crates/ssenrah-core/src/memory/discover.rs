@@ -0,0 +1,123 @@
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::io::text::strip_bom;
+use crate::io::walk::{relative_slash_path, walk_project, Visit};
+
+/// Files reported before the walk stops.
+const MAX_FILES: usize = 500;
+
+/// Files larger than this are listed without a preview.
+const MAX_PREVIEW_FILE_BYTES: u64 = 1024 * 1024;
+
+const PREVIEW_CHARS: usize = 200;
+
+/// A CLAUDE.md or CLAUDE.local.md found somewhere in the project tree.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NestedMemory {
+    pub path: String,
+    /// Path relative to the project root, forward-slash separated.
+    pub relative_path: String,
+    /// `true` for CLAUDE.local.md.
+    pub local: bool,
+    pub size_bytes: u64,
+    /// Start of the file; `None` if it is too large or unreadable.
+    pub preview: Option<String>,
+}
+
+/// Result of `discover`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryDiscovery {
+    pub files: Vec<NestedMemory>,
+    /// The walk stopped at the file limit; there may be more.
+    pub truncated: bool,
+}
+
+fn preview(path: &Path, size_bytes: u64) -> Option<String> {
+    if size_bytes > MAX_PREVIEW_FILE_BYTES {
+        return None;
+    }
+    let content = fs::read_to_string(path).ok()?;
+    Some(strip_bom(&content).chars().take(PREVIEW_CHARS).collect())
+}
+
+/// Finds every CLAUDE.md and CLAUDE.local.md under `root`, in path order.
+///
+/// Claude Code loads the ones in subdirectories as it works in them, so in a
+/// monorepo each package can carry its own. The walk descends at most
+/// `max_depth` levels, skipping the directories `io::walk` does not enter,
+/// and stops after 500 files.
+pub fn discover(root: &Path, max_depth: usize) -> MemoryDiscovery {
+    let mut found = MemoryDiscovery {
+        files: vec![],
+        truncated: false,
+    };
+    walk_project(root, max_depth, |path, file_type| {
+        let name = path.file_name().unwrap_or_default();
+        if !file_type.is_file() || (name != "CLAUDE.md" && name != "CLAUDE.local.md") {
+            return Visit::Continue;
+        }
+        if found.files.len() == MAX_FILES {
+            found.truncated = true;
+            return Visit::Stop;
+        }
+        let size_bytes = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        found.files.push(NestedMemory {
+            path: path.display().to_string(),
+            relative_path: relative_slash_path(root, path),
+            local: name == "CLAUDE.local.md",
+            size_bytes,
+            preview: preview(path, size_bytes),
+        });
+        Visit::Continue
+    });
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_nested_memory_within_limits() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        for (file, content) in [
+            ("CLAUDE.md", "# Root"),
+            (".claude/CLAUDE.local.md", "mine"),
+            ("packages/api/CLAUDE.md", "\u{feff}# API"),
+            ("packages/web/CLAUDE.local.md", "web"),
+            ("packages/web/node_modules/dep/CLAUDE.md", "vendored"),
+            (".cache/CLAUDE.md", "hidden"),
+            ("a/b/c/CLAUDE.md", "deep"),
+        ] {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+
+        let found = discover(root, 2);
+        let paths: Vec<_> = found
+            .files
+            .iter()
+            .map(|f| (f.relative_path.as_str(), f.local))
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                (".claude/CLAUDE.local.md", true),
+                ("CLAUDE.md", false),
+                ("packages/api/CLAUDE.md", false),
+                ("packages/web/CLAUDE.local.md", true),
+            ]
+        );
+        assert_eq!(found.files[2].preview.as_deref(), Some("# API"));
+        assert!(!found.truncated);
+
+        assert_eq!(discover(root, 3).files.len(), 5);
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::memory::imports::{self, ImportStatus, MemoryNode};
+
+/// Rough characters-per-token ratio for English prose and code.
+const CHARS_PER_TOKEN: usize = 4;
+
+/// One file contributing to the combined memory.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryFileEstimate {
+    /// Memory scope of the top-level file this one belongs to.
+    pub scope: String,
+    pub path: String,
+    /// The file whose `@` import pulled this one in; `None` for top-level files.
+    pub imported_from: Option<String>,
+    pub bytes: usize,
+    pub estimated_tokens: usize,
+}
+
+/// All memory files concatenated in load order.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectiveMemory {
+    pub content: String,
+    pub files: Vec<MemoryFileEstimate>,
+    pub total_tokens: usize,
+}
+
+/// Estimates the token count of `text` from its character count.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(CHARS_PER_TOKEN)
+}
+
+/// Concatenates `sources` (scope, path) in order, each followed by the files
+/// it imports depth-first. Missing sources are skipped and a file reached
+/// more than once is only included the first time.
+pub fn combine(sources: &[(&str, PathBuf)], home: &Path) -> EffectiveMemory {
+    let mut memory = EffectiveMemory {
+        content: String::new(),
+        files: vec![],
+        total_tokens: 0,
+    };
+    let mut seen = HashSet::new();
+
+    for (scope, path) in sources {
+        if !path.is_file() {
+            continue;
+        }
+        let graph = imports::build_graph(path, home);
+        append(&mut memory, &mut seen, scope, &graph, None);
+    }
+
+    memory
+}
+
+fn append(
+    memory: &mut EffectiveMemory,
+    seen: &mut HashSet<PathBuf>,
+    scope: &str,
+    node: &MemoryNode,
+    parent: Option<&str>,
+) {
+    if node.status != ImportStatus::Ok {
+        return;
+    }
+    let path = Path::new(&node.path);
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    if !seen.insert(canonical) {
+        return;
+    }
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(_) => return,
+    };
+
+    if !memory.content.is_empty() {
+        memory.content.push_str("\n\n");
+    }
+    memory
+        .content
+        .push_str(&format!("<!-- {} -->\n", node.path));
+    memory.content.push_str(text.trim_end());
+
+    let tokens = estimate_tokens(&text);
+    memory.total_tokens += tokens;
+    memory.files.push(MemoryFileEstimate {
+        scope: scope.to_string(),
+        path: node.path.clone(),
+        imported_from: parent.map(String::from),
+        bytes: text.len(),
+        estimated_tokens: tokens,
+    });
+
+    for child in &node.children {
+        append(memory, seen, scope, child, Some(&node.path));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimates_four_chars_per_token() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcd"), 1);
+        assert_eq!(estimate_tokens("abcde"), 2);
+    }
+
+    #[test]
+    fn combines_sources_with_imports_once() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        fs::write(dir.join("user.md"), "user @shared.md").unwrap();
+        fs::write(dir.join("project.md"), "project @shared.md").unwrap();
+        fs::write(dir.join("shared.md"), "shared").unwrap();
+
+        let memory = combine(
+            &[
+                ("user", dir.join("user.md")),
+                ("project_root", dir.join("missing.md")),
+                ("project", dir.join("project.md")),
+            ],
+            dir,
+        );
+
+        let scopes: Vec<_> = memory.files.iter().map(|f| f.scope.as_str()).collect();
+        assert_eq!(scopes, vec!["user", "user", "project"]);
+        assert!(memory.files[1].imported_from.is_some());
+        assert_eq!(memory.content.matches("<!-- ").count(), 3);
+        assert_eq!(
+            memory.total_tokens,
+            memory
+                .files
+                .iter()
+                .map(|f| f.estimated_tokens)
+                .sum::<usize>()
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+/// Maximum nesting of `@path` imports, matching Claude Code's limit.
+pub const MAX_IMPORT_DEPTH: usize = 5;
+
+/// An `@path` reference found in a memory file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportRef {
+    /// 1-based line number of the reference.
+    pub line: usize,
+    /// The path as written, without the leading `@`.
+    pub target: String,
+}
+
+/// Whether an import could be followed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportStatus {
+    Ok,
+    /// The referenced file does not exist.
+    Missing,
+    /// The file exists but could not be read.
+    Unreadable,
+    /// The file is already being included further up the chain.
+    Cycle,
+    /// The import is nested deeper than `MAX_IMPORT_DEPTH`.
+    DepthExceeded,
+}
+
+/// A memory file and everything it imports.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryNode {
+    pub path: String,
+    /// The `@` reference that pulled this file in; `None` for the root.
+    pub target: Option<String>,
+    /// Line of the reference in the parent file.
+    pub line: Option<usize>,
+    pub depth: usize,
+    pub size: Option<u64>,
+    pub status: ImportStatus,
+    pub children: Vec<MemoryNode>,
+}
+
+/// Extracts `@path` imports from markdown.
+///
+/// An import is an `@` at the start of a line or after whitespace, running to
+/// the next whitespace. Fenced code blocks and inline code spans are skipped,
+/// as Claude Code does not evaluate imports there. Trailing punctuation is
+/// dropped so "see @notes.md." imports `notes.md`.
+pub fn extract_imports(text: &str) -> Vec<ImportRef> {
+    let mut imports = Vec::new();
+    let mut fence: Option<&str> = None;
+
+    for (idx, line) in text.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            continue;
+        }
+        if trimmed.starts_with("```") {
+            fence = Some("```");
+            continue;
+        }
+        if trimmed.starts_with("~~~") {
+            fence = Some("~~~");
+            continue;
+        }
+
+        // Odd-numbered backtick segments are inline code.
+        for (seg_idx, segment) in line.split('`').enumerate() {
+            if seg_idx % 2 == 1 {
+                continue;
+            }
+            let mut prev = ' ';
+            for (pos, c) in segment.char_indices() {
+                if c == '@' && prev.is_whitespace() {
+                    let rest = &segment[pos + 1..];
+                    let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                    let target = rest[..end].trim_end_matches(['.', ',', ';', ':', '!', '?', ')']);
+                    if !target.is_empty() {
+                        imports.push(ImportRef {
+                            line: idx + 1,
+                            target: target.to_string(),
+                        });
+                    }
+                }
+                prev = c;
+            }
+        }
+    }
+
+    imports
+}
+
+/// Resolves an import target: `~/` is relative to `home`, relative paths to
+/// the directory of the importing file.
+pub fn resolve_import(base_dir: &Path, target: &str, home: &Path) -> PathBuf {
+    if let Some(rest) = target.strip_prefix("~/") {
+        home.join(rest)
+    } else {
+        base_dir.join(target)
+    }
+}
+
+/// Builds the import tree rooted at `root`.
+pub fn build_graph(root: &Path, home: &Path) -> MemoryNode {
+    let mut chain = Vec::new();
+    visit(root, None, 0, home, &mut chain)
+}
+
+fn visit(
+    path: &Path,
+    reference: Option<&ImportRef>,
+    depth: usize,
+    home: &Path,
+    chain: &mut Vec<PathBuf>,
+) -> MemoryNode {
+    let mut node = MemoryNode {
+        path: path.display().to_string(),
+        target: reference.map(|r| r.target.clone()),
+        line: reference.map(|r| r.line),
+        depth,
+        size: fs::metadata(path)
+            .ok()
+            .filter(|m| m.is_file())
+            .map(|m| m.len()),
+        status: ImportStatus::Ok,
+        children: vec![],
+    };
+
+    let canonical = match path.canonicalize() {
+        Ok(p) => p,
+        Err(_) => {
+            node.status = ImportStatus::Missing;
+            return node;
+        }
+    };
+    if chain.contains(&canonical) {
+        node.status = ImportStatus::Cycle;
+        return node;
+    }
+    if depth > MAX_IMPORT_DEPTH {
+        node.status = ImportStatus::DepthExceeded;
+        return node;
+    }
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(_) => {
+            node.status = ImportStatus::Unreadable;
+            return node;
+        }
+    };
+
+    let base_dir = path.parent().unwrap_or(Path::new(""));
+    chain.push(canonical);
+    for import in extract_imports(&text) {
+        let child = resolve_import(base_dir, &import.target, home);
+        node.children
+            .push(visit(&child, Some(&import), depth + 1, home, chain));
+    }
+    chain.pop();
+
+    node
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn targets(text: &str) -> Vec<String> {
+        extract_imports(text)
+            .into_iter()
+            .map(|i| i.target)
+            .collect()
+    }
+
+    #[test]
+    fn extracts_imports_outside_code() {
+        let text = "See @README.md and @docs/git.md.\n\
+                    Mail me at a@b.com\n\
+                    Inline `@not/this` stays\n\
+                    ```\n@fenced.md\n```\n\
+                    - @~/.claude/personal.md";
+        assert_eq!(
+            targets(text),
+            vec!["README.md", "docs/git.md", "~/.claude/personal.md"]
+        );
+        assert_eq!(extract_imports(text)[2].line, 7);
+    }
+
+    #[test]
+    fn resolves_home_and_relative_targets() {
+        let home = Path::new("/home/u");
+        let base = Path::new("/proj/.claude");
+        assert_eq!(
+            resolve_import(base, "~/notes.md", home),
+            PathBuf::from("/home/u/notes.md")
+        );
+        assert_eq!(
+            resolve_import(base, "rules/a.md", home),
+            PathBuf::from("/proj/.claude/rules/a.md")
+        );
+    }
+
+    #[test]
+    fn flags_cycles_and_missing_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        fs::write(dir.join("CLAUDE.md"), "@a.md @missing.md").unwrap();
+        fs::write(dir.join("a.md"), "@CLAUDE.md").unwrap();
+
+        let graph = build_graph(&dir.join("CLAUDE.md"), dir);
+
+        assert_eq!(graph.status, ImportStatus::Ok);
+        assert_eq!(graph.children.len(), 2);
+        assert_eq!(graph.children[0].children[0].status, ImportStatus::Cycle);
+        assert_eq!(graph.children[1].status, ImportStatus::Missing);
+    }
+}
@@ -0,0 +1,4 @@
+pub mod chunk;
+pub mod discover;
+pub mod effective;
+pub mod imports;
@@ -0,0 +1,108 @@
+//! Per-package `.claude` directories in monorepos, addressed by scopes such
+//! as `project:packages/api` (see `platform::paths::resolve_scope_address`).
+
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::io::walk::{relative_slash_path, walk_project, Visit};
+
+/// A `.claude` directory below the project root.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NestedClaudeDir {
+    /// Directory holding `.claude`, relative to the project root.
+    pub package: String,
+    /// Absolute path of the `.claude` directory.
+    pub path: String,
+    /// Scope address for the agents, skills, and settings commands.
+    pub scope: String,
+    pub agents: usize,
+    pub skills: usize,
+    pub has_settings: bool,
+    pub has_local_settings: bool,
+}
+
+fn count_entries(dir: &Path, keep: impl Fn(&Path) -> bool) -> usize {
+    fs::read_dir(dir)
+        .map(|entries| entries.flatten().filter(|e| keep(&e.path())).count())
+        .unwrap_or(0)
+}
+
+/// Lists the `.claude` directories of packages under `root`, in path order,
+/// searching at most `max_depth` levels deep. The project's own `.claude` is
+/// not included.
+pub fn find_nested_claude_dirs(root: &Path, max_depth: usize) -> Vec<NestedClaudeDir> {
+    let mut found = Vec::new();
+    walk_project(root, max_depth, |path, file_type| {
+        let Some(package) = path.parent() else {
+            return Visit::Continue;
+        };
+        if !file_type.is_dir() || path.file_name() != Some(".claude".as_ref()) || package == root {
+            return Visit::Continue;
+        }
+        let package = relative_slash_path(root, package);
+        found.push(NestedClaudeDir {
+            scope: format!("project:{}", package),
+            path: path.display().to_string(),
+            agents: count_entries(&path.join("agents"), |p| {
+                p.extension().is_some_and(|e| e == "md")
+            }),
+            skills: count_entries(&path.join("commands"), |p| {
+                p.is_dir() || p.extension().is_some_and(|e| e == "md")
+            }),
+            has_settings: path.join("settings.json").is_file(),
+            has_local_settings: path.join("settings.local.json").is_file(),
+            package,
+        });
+        Visit::Continue
+    });
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::paths::{resolve_agents_dir, resolve_scope_address};
+
+    #[test]
+    fn finds_package_dirs_and_resolves_their_scopes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        for file in [
+            ".claude/settings.json",
+            "packages/api/.claude/agents/reviewer.md",
+            "packages/api/.claude/commands/deploy/SKILL.md",
+            "packages/api/.claude/settings.local.json",
+            "packages/web/.claude/settings.json",
+        ] {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "{}").unwrap();
+        }
+
+        let dirs = find_nested_claude_dirs(root, 4);
+        let summary: Vec<_> = dirs
+            .iter()
+            .map(|d| (d.scope.as_str(), d.agents, d.skills, d.has_settings))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("project:packages/api", 1, 1, false),
+                ("project:packages/web", 0, 0, true),
+            ]
+        );
+        assert!(dirs[0].has_local_settings);
+
+        let project_root = Some(root.display().to_string());
+        assert_eq!(
+            resolve_agents_dir("project:packages/api", &project_root).unwrap(),
+            root.join("packages/api/.claude/agents")
+        );
+        assert!(resolve_scope_address("project:../elsewhere", &project_root).is_err());
+        assert!(resolve_scope_address("project:packages/missing", &project_root).is_err());
+        assert!(resolve_scope_address("user:packages/api", &project_root).is_err());
+    }
+}
@@ -0,0 +1,188 @@
+use std::path::Path;
+use std::process::Command;
+
+use serde::Serialize;
+
+/// Credential kinds in the order Claude Code prefers them.
+pub const PRECEDENCE: &[&str] = &[
+    "bedrock",
+    "vertex",
+    "authToken",
+    "apiKey",
+    "apiKeyHelper",
+    "oauth",
+];
+
+/// One place Claude Code could take credentials from.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialSource {
+    /// One of [`PRECEDENCE`].
+    pub kind: &'static str,
+    pub present: bool,
+    /// Where it was found: "process env", "settings env (user)",
+    /// "keychain", a file path, ...
+    pub origin: Option<String>,
+    /// Masked hint of the secret (last four characters), never the value.
+    pub hint: Option<String>,
+}
+
+impl CredentialSource {
+    pub fn absent(kind: &'static str) -> Self {
+        Self {
+            kind,
+            present: false,
+            origin: None,
+            hint: None,
+        }
+    }
+}
+
+/// Masks a secret, keeping only enough to tell keys apart.
+pub fn mask(secret: &str) -> String {
+    let chars: Vec<char> = secret.trim().chars().collect();
+    if chars.len() < 12 {
+        return "set".to_string();
+    }
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("…{}", tail)
+}
+
+/// The kind Claude Code would use: the first present source by precedence.
+pub fn active(sources: &[CredentialSource]) -> Option<&'static str> {
+    PRECEDENCE
+        .iter()
+        .find(|kind| sources.iter().any(|s| s.kind == **kind && s.present))
+        .copied()
+}
+
+/// Whether an env flag such as `CLAUDE_CODE_USE_BEDROCK` is switched on.
+pub fn is_truthy(value: &str) -> bool {
+    matches!(
+        value.trim().to_ascii_lowercase().as_str(),
+        "1" | "true" | "yes" | "on"
+    )
+}
+
+/// Where the `/login` subscription credentials are stored, if they exist:
+/// the macOS keychain, or `.credentials.json` in the config directory.
+/// Only existence is checked; the secret is never read into memory on
+/// macOS, and only the key names are inspected in the file.
+pub fn oauth_credentials(config_dir: &Path) -> Option<String> {
+    if cfg!(target_os = "macos") {
+        let found = Command::new("security")
+            .args(["find-generic-password", "-s", "Claude Code-credentials"])
+            .output()
+            .is_ok_and(|o| o.status.success());
+        if found {
+            return Some("keychain".to_string());
+        }
+    }
+    let file = config_dir.join(".credentials.json");
+    let has_oauth = std::fs::read_to_string(&file)
+        .ok()
+        .and_then(|text| serde_json::from_str::<serde_json::Value>(&text).ok())
+        .is_some_and(|v| v.get("claudeAiOauth").is_some());
+    has_oauth.then(|| file.display().to_string())
+}
+
+/// Problems with an apiKeyHelper script. `mode` is the Unix permission
+/// bits; when it is `None` (Windows) only the content is checked.
+pub fn check_helper_script(content: &str, mode: Option<u32>) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if content.trim().is_empty() {
+        warnings.push("The script is empty".to_string());
+    }
+    let Some(mode) = mode else {
+        return warnings;
+    };
+    match content.lines().next().and_then(|l| l.strip_prefix("#!")) {
+        None => warnings.push("Missing a shebang line such as #!/bin/sh".to_string()),
+        Some(interpreter) => {
+            let program = interpreter.split_whitespace().next().unwrap_or_default();
+            if !Path::new(program).is_absolute() {
+                warnings.push(format!(
+                    "Shebang interpreter '{}' is not an absolute path",
+                    program
+                ));
+            } else if !Path::new(program).exists() {
+                warnings.push(format!("Shebang interpreter {} does not exist", program));
+            }
+        }
+    }
+    if mode & 0o111 == 0 {
+        warnings.push("The script is not executable (run chmod +x)".to_string());
+    }
+    if mode & 0o022 != 0 {
+        warnings.push(
+            "The script is writable by other users, who could change the key it returns"
+                .to_string(),
+        );
+    }
+    warnings
+}
+
+/// Whether apiKeyHelper output looks like a single key: one non-empty line
+/// of at least 20 printable characters without whitespace.
+pub fn looks_like_key(output: &str) -> bool {
+    let key = output.trim();
+    key.len() >= 20 && key.chars().all(|c| c.is_ascii_graphic())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn present(kind: &'static str) -> CredentialSource {
+        CredentialSource {
+            present: true,
+            ..CredentialSource::absent(kind)
+        }
+    }
+
+    #[test]
+    fn picks_the_highest_precedence_source() {
+        let sources = vec![
+            present("oauth"),
+            present("apiKey"),
+            CredentialSource::absent("vertex"),
+        ];
+        assert_eq!(active(&sources), Some("apiKey"));
+        assert_eq!(active(&[present("oauth")]), Some("oauth"));
+        assert_eq!(active(&[]), None);
+    }
+
+    #[test]
+    fn masks_secrets() {
+        assert_eq!(mask("sk-ant-api03-abcdefgh1234"), "…1234");
+        assert_eq!(mask("short"), "set");
+        assert!(is_truthy("1") && is_truthy("TRUE") && !is_truthy("0"));
+    }
+
+    #[test]
+    fn checks_helper_scripts() {
+        assert!(check_helper_script("#!/bin/sh\necho key\n", Some(0o755)).is_empty());
+        assert_eq!(check_helper_script("echo key\n", Some(0o644)).len(), 2);
+        assert_eq!(check_helper_script("#!/bin/sh\n", Some(0o777)).len(), 1);
+        assert!(check_helper_script("@echo key", None).is_empty());
+
+        assert!(looks_like_key("sk-ant-api03-abcdefghijkl\n"));
+        assert!(!looks_like_key("Error: vault locked"));
+        assert!(!looks_like_key("sk-ant-api03-abcdefghijkl\nsecond"));
+    }
+
+    #[test]
+    fn finds_credentials_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        if !cfg!(target_os = "macos") {
+            assert_eq!(oauth_credentials(dir), None);
+        }
+        std::fs::write(
+            dir.join(".credentials.json"),
+            r#"{"claudeAiOauth":{"accessToken":"x"}}"#,
+        )
+        .unwrap();
+        assert!(oauth_credentials(dir).is_some());
+    }
+}
@@ -0,0 +1,269 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::RwLock;
+
+use crate::platform::paths;
+
+/// Returns the target OS as a lowercase string.
+pub fn detect_os() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "macos"
+    } else if cfg!(target_os = "windows") {
+        "windows"
+    } else if cfg!(target_os = "linux") {
+        "linux"
+    } else {
+        "unknown"
+    }
+}
+
+/// Checks whether we are running inside WSL by reading /proc/version.
+pub fn is_wsl() -> bool {
+    if !cfg!(target_os = "linux") {
+        return false;
+    }
+
+    match std::fs::read_to_string("/proc/version") {
+        Ok(contents) => {
+            let lower = contents.to_lowercase();
+            lower.contains("microsoft")
+        }
+        Err(_) => false,
+    }
+}
+
+/// Detects the user's shell.
+///
+/// Priority:
+/// 1. CLAUDE_CODE_SHELL environment variable
+/// 2. SHELL environment variable
+/// 3. Platform fallback (/bin/sh on Unix, cmd.exe on Windows)
+pub fn detect_shell() -> String {
+    if let Ok(shell) = std::env::var("CLAUDE_CODE_SHELL") {
+        if !shell.is_empty() {
+            return shell;
+        }
+    }
+
+    if let Ok(shell) = std::env::var("SHELL") {
+        if !shell.is_empty() {
+            return shell;
+        }
+    }
+
+    if cfg!(target_os = "windows") {
+        "cmd.exe".to_string()
+    } else {
+        "/bin/sh".to_string()
+    }
+}
+
+/// Detects whether Claude Code is installed and returns (installed, path).
+///
+/// On Unix, uses `which claude`. Also checks common installation paths
+/// as a fallback.
+pub fn detect_claude_code() -> (bool, Option<String>) {
+    // Try `which` on Unix, `where` on Windows
+    let which_result = if cfg!(target_os = "windows") {
+        Command::new("where").arg("claude").output()
+    } else {
+        Command::new("which").arg("claude").output()
+    };
+
+    if let Ok(output) = which_result {
+        if output.status.success() {
+            let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            if !path.is_empty() {
+                return (true, Some(path));
+            }
+        }
+    }
+
+    // Fallback: check common installation paths
+    let common_paths: Vec<&str> = if cfg!(target_os = "macos") {
+        vec![
+            "/usr/local/bin/claude",
+            "/opt/homebrew/bin/claude",
+        ]
+    } else if cfg!(target_os = "windows") {
+        vec![
+            // Windows common paths — typically handled by `where` above
+        ]
+    } else {
+        // Linux / WSL
+        vec![
+            "/usr/local/bin/claude",
+            "/usr/bin/claude",
+        ]
+    };
+
+    for candidate in common_paths {
+        if Path::new(candidate).exists() {
+            return (true, Some(candidate.to_string()));
+        }
+    }
+
+    // Also check ~/.npm-global/bin/claude and ~/.local/bin/claude
+    if let Ok(home) = paths::home_dir() {
+        let candidates = [
+            home.join(".npm-global/bin/claude"),
+            home.join(".local/bin/claude"),
+            home.join(".nvm/versions/node").join("*/bin/claude"), // won't glob, but leave for illustration
+        ];
+        for candidate in &candidates[..2] {
+            if candidate.exists() {
+                return (true, Some(candidate.to_string_lossy().to_string()));
+            }
+        }
+    }
+
+    (false, None)
+}
+
+/// Last `claude --version` result, cached so validation can check settings
+/// against the installed CLI without spawning a process each time.
+static CLAUDE_CODE_VERSION: RwLock<Option<String>> = RwLock::new(None);
+
+/// Runs `{binary} --version` and returns its trimmed output, updating the
+/// cached version on success.
+pub fn detect_claude_code_version(binary: &str) -> Option<String> {
+    let output = Command::new(binary).arg("--version").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if version.is_empty() {
+        return None;
+    }
+
+    *CLAUDE_CODE_VERSION
+        .write()
+        .unwrap_or_else(|e| e.into_inner()) = Some(version.clone());
+    Some(version)
+}
+
+/// Returns the version reported by the last successful
+/// `detect_claude_code_version` call, if any.
+pub fn cached_claude_code_version() -> Option<String> {
+    CLAUDE_CODE_VERSION
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// Finds `program` the way a shell would: a path containing a separator is
+/// checked directly, a bare name is searched for on PATH (with PATHEXT
+/// extensions on Windows).
+pub fn find_on_path(program: &str) -> Option<PathBuf> {
+    let direct = Path::new(program);
+    if direct.components().count() > 1 {
+        return direct.is_file().then(|| direct.to_path_buf());
+    }
+
+    let extensions: Vec<String> = if cfg!(target_os = "windows") {
+        let pathext = std::env::var("PATHEXT").unwrap_or_else(|_| ".EXE;.CMD;.BAT".to_string());
+        std::iter::once(String::new())
+            .chain(pathext.split(';').map(str::to_string))
+            .collect()
+    } else {
+        vec![String::new()]
+    };
+
+    std::env::split_paths(&std::env::var_os("PATH")?)
+        .flat_map(|dir| {
+            extensions
+                .iter()
+                .map(move |ext| dir.join(format!("{}{}", program, ext)))
+        })
+        .find(|p| p.is_file())
+}
+
+/// Returns `true` if `path` has an execute permission bit set. Always `true`
+/// on platforms without Unix permissions.
+pub fn is_executable(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::metadata(path).is_ok_and(|m| m.permissions().mode() & 0o111 != 0)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        true
+    }
+}
+
+/// Read/write access to a directory, as probed by [`probe_dir_access`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DirAccess {
+    pub exists: bool,
+    pub readable: bool,
+    pub writable: bool,
+}
+
+/// Probes whether `dir` can be listed and written. Writability is tested by
+/// creating and removing a temporary file; a directory that does not exist
+/// yet is writable if its nearest existing ancestor is.
+pub fn probe_dir_access(dir: &Path) -> DirAccess {
+    let exists = dir.is_dir();
+    let readable = exists && std::fs::read_dir(dir).is_ok();
+    let writable = dir
+        .ancestors()
+        .find(|p| p.is_dir())
+        .is_some_and(|existing| {
+            let probe = existing.join(format!(".ssenrah-probe-{}", std::process::id()));
+            let created = std::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&probe)
+                .is_ok();
+            if created {
+                let _ = std::fs::remove_file(&probe);
+            }
+            created
+        });
+    DirAccess {
+        exists,
+        readable,
+        writable,
+    }
+}
+
+/// Filesystem types whose change notifications are unreliable or missing:
+/// network shares, WSL's Windows drives, and FUSE remote mounts.
+const NETWORK_FS_TYPES: &[&str] = &[
+    "nfs",
+    "nfs4",
+    "cifs",
+    "smb3",
+    "smbfs",
+    "9p",
+    "drvfs",
+    "afpfs",
+    "webdav",
+    "davfs",
+    "fuse.sshfs",
+    "fuse.rclone",
+];
+
+/// Returns `true` if `path` is on a network or WSL-mounted filesystem,
+/// judged from the longest matching mount point in `/proc/mounts`. Always
+/// `false` where `/proc/mounts` does not exist.
+pub fn is_network_fs(path: &Path) -> bool {
+    let Ok(mounts) = std::fs::read_to_string("/proc/mounts") else {
+        return false;
+    };
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let mount_point = fields.next().and(fields.next())?;
+            let fs_type = fields.next()?;
+            // Spaces in mount points are written as \040.
+            Some((mount_point.replace("\\040", " "), fs_type))
+        })
+        .filter(|(mount_point, _)| path.starts_with(mount_point))
+        .max_by_key(|(mount_point, _)| mount_point.len())
+        .is_some_and(|(_, fs_type)| NETWORK_FS_TYPES.contains(&fs_type))
+}
@@ -0,0 +1,160 @@
+use std::path::Path;
+use std::process::Command;
+
+use base64::Engine;
+
+/// Quotes `s` for a POSIX shell: wrapped in single quotes, with embedded
+/// single quotes closed, escaped, and reopened.
+pub fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+/// Quotes `s` as an AppleScript string literal.
+fn applescript_quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', r"\\").replace('"', "\\\""))
+}
+
+/// Quotes `s` as a PowerShell single-quoted string.
+fn powershell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+/// PowerShell script that copies `src` to `dest`, creating the destination
+/// directory first. Any failure ends it with a non-zero exit code.
+fn powershell_install_script(src: &Path, dest: &Path) -> String {
+    let dir = dest.parent().unwrap_or(dest);
+    format!(
+        "$ErrorActionPreference = 'Stop'; \
+         New-Item -ItemType Directory -Force -Path {} | Out-Null; \
+         Copy-Item -LiteralPath {} -Destination {} -Force",
+        powershell_quote(&dir.to_string_lossy()),
+        powershell_quote(&src.to_string_lossy()),
+        powershell_quote(&dest.to_string_lossy()),
+    )
+}
+
+/// Command that runs `script` in an elevated PowerShell, raising the UAC
+/// prompt, and exits with its exit code. The script is passed encoded, so
+/// paths need no further quoting.
+fn uac_command(script: &str) -> String {
+    let utf16: Vec<u8> = script.encode_utf16().flat_map(u16::to_le_bytes).collect();
+    let encoded = base64::engine::general_purpose::STANDARD.encode(utf16);
+    format!(
+        "$p = Start-Process -FilePath powershell -Verb RunAs -Wait -PassThru \
+         -WindowStyle Hidden -ArgumentList '-NoProfile','-EncodedCommand','{}'; \
+         exit $p.ExitCode",
+        encoded
+    )
+}
+
+/// Whether `elevated_copy` can prompt for administrator rights here.
+pub fn is_supported() -> bool {
+    cfg!(any(
+        target_os = "macos",
+        target_os = "linux",
+        target_os = "windows"
+    ))
+}
+
+/// Shell script that installs `src` at `dest` (mode 644), creating the
+/// destination directory first.
+fn install_script(src: &Path, dest: &Path) -> String {
+    let dir = dest.parent().unwrap_or(Path::new("/"));
+    format!(
+        "mkdir -p {} && cp {} {} && chmod 644 {}",
+        shell_quote(&dir.to_string_lossy()),
+        shell_quote(&src.to_string_lossy()),
+        shell_quote(&dest.to_string_lossy()),
+        shell_quote(&dest.to_string_lossy()),
+    )
+}
+
+/// Copies `src` to `dest` with administrator rights, prompting the user
+/// through the platform's elevation dialog: `osascript ... with
+/// administrator privileges` on macOS, `pkexec` (polkit) on Linux, and a
+/// UAC prompt for an elevated PowerShell on Windows.
+///
+/// Returns an error message if elevation is unavailable (see
+/// [`is_supported`]), was cancelled, or the copy failed.
+pub fn elevated_copy(src: &Path, dest: &Path) -> Result<(), String> {
+    let script = install_script(src, dest);
+    let output = if cfg!(target_os = "windows") {
+        Command::new("powershell")
+            .args(["-NoProfile", "-NonInteractive", "-Command"])
+            .arg(uac_command(&powershell_install_script(src, dest)))
+            .output()
+    } else if cfg!(target_os = "macos") {
+        Command::new("osascript")
+            .arg("-e")
+            .arg(format!(
+                "do shell script {} with administrator privileges",
+                applescript_quote(&script)
+            ))
+            .output()
+    } else if cfg!(target_os = "linux") {
+        Command::new("pkexec").args(["sh", "-c", &script]).output()
+    } else {
+        return Err("Elevated writes are not supported on this platform".to_string());
+    };
+
+    let output = output.map_err(|e| format!("Failed to start the elevation helper: {}", e))?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    // pkexec exits 126 when the user dismisses the dialog; osascript reports
+    // error -128 ("User canceled"); Start-Process reports that "the operation
+    // was canceled by the user" when the UAC prompt is declined.
+    if output.status.code() == Some(126)
+        || stderr.contains("-128")
+        || stderr.contains("canceled by the user")
+    {
+        Err("Administrator authentication was cancelled".to_string())
+    } else if stderr.is_empty() {
+        Err(format!("Elevated copy failed ({})", output.status))
+    } else {
+        Err(format!("Elevated copy failed: {}", stderr))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_paths_for_shell_and_applescript() {
+        assert_eq!(shell_quote("/etc/it's here"), r"'/etc/it'\''s here'");
+        let script = install_script(
+            Path::new("/tmp/new.json"),
+            Path::new("/etc/claude-code/managed-settings.json"),
+        );
+        assert_eq!(
+            script,
+            "mkdir -p '/etc/claude-code' && cp '/tmp/new.json' \
+             '/etc/claude-code/managed-settings.json' && chmod 644 \
+             '/etc/claude-code/managed-settings.json'"
+        );
+        assert_eq!(applescript_quote(r#"a "b" \c"#), r#""a \"b\" \\c""#);
+    }
+
+    #[test]
+    fn encodes_the_uac_copy_script() {
+        let script = powershell_install_script(
+            Path::new(r"C:\Users\o'neil\new.json"),
+            Path::new("C:/Program Files/ClaudeCode/managed-settings.json"),
+        );
+        assert!(script.contains(r"-LiteralPath 'C:\Users\o''neil\new.json'"));
+        assert!(script.contains("-Path 'C:/Program Files/ClaudeCode' |"));
+
+        let command = uac_command(&script);
+        let encoded = command.split('\'').nth(5).unwrap();
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .unwrap();
+        let utf16: Vec<u16> = bytes
+            .chunks(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        assert_eq!(String::from_utf16(&utf16).unwrap(), script);
+    }
+}
@@ -0,0 +1,203 @@
+use std::path::Path;
+
+use serde::Serialize;
+
+/// Shell init files (relative to the home directory) scanned for exports.
+pub const SHELL_INIT_FILES: &[&str] = &[
+    ".profile",
+    ".bash_profile",
+    ".bashrc",
+    ".zshenv",
+    ".zprofile",
+    ".zshrc",
+    ".config/fish/config.fish",
+];
+
+/// Variables whose values are credentials or may embed them (proxy URLs can
+/// carry `user:password@`).
+const SENSITIVE_KEYS: &[&str] = &[
+    "ANTHROPIC_API_KEY",
+    "ANTHROPIC_AUTH_TOKEN",
+    "AWS_ACCESS_KEY_ID",
+    "AWS_SECRET_ACCESS_KEY",
+    "AWS_SESSION_TOKEN",
+    "AWS_BEARER_TOKEN_BEDROCK",
+    "HTTP_PROXY",
+    "HTTPS_PROXY",
+    "ALL_PROXY",
+];
+
+/// `_`-separated name segments that mark a variable as sensitive.
+const SENSITIVE_FRAGMENTS: &[&str] = &["API_KEY", "TOKEN", "SECRET", "PASSWORD", "CREDENTIAL"];
+
+/// An `export` of a variable found in a shell init file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShellExport {
+    pub file: String,
+    /// 1-based line number.
+    pub line: usize,
+    pub value: String,
+}
+
+/// Returns `true` if values of `name` should be masked in the UI.
+pub fn is_sensitive(name: &str) -> bool {
+    let upper = name.to_ascii_uppercase();
+    SENSITIVE_KEYS.contains(&upper.as_str())
+        || SENSITIVE_FRAGMENTS
+            .iter()
+            .any(|f| format!("_{}_", upper).contains(&format!("_{}_", f)))
+}
+
+/// Extracts exported variables from a shell init file.
+///
+/// Understands `export NAME=value` (bash/zsh, including several names on one
+/// line), `declare -x NAME=value`, and fish's `set -x`/`set -gx NAME value`.
+/// Plain assignments are ignored because they are not inherited by child
+/// processes. Quotes around values are removed; expansions are left as-is.
+pub fn parse_exports(content: &str) -> Vec<(String, usize, String)> {
+    let mut exports = Vec::new();
+
+    for (idx, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if line.starts_with('#') {
+            continue;
+        }
+        let words: Vec<&str> = line.split_whitespace().collect();
+
+        match words.as_slice() {
+            ["export" | "declare", ..] => {
+                let rest = line
+                    .split_once(char::is_whitespace)
+                    .map_or("", |(_, rest)| rest.trim_start());
+                let rest = match (words[0], rest.strip_prefix("-x ")) {
+                    ("declare", Some(rest)) => rest,
+                    ("declare", None) => continue,
+                    _ => rest,
+                };
+                for assignment in split_assignments(rest) {
+                    if let Some((name, value)) = assignment.split_once('=') {
+                        if is_identifier(name) {
+                            exports.push((name.to_string(), idx + 1, unquote(value)));
+                        }
+                    }
+                }
+            }
+            ["set", flags @ ..] => {
+                let exported = flags
+                    .iter()
+                    .take_while(|w| w.starts_with('-'))
+                    .any(|w| w.contains('x') || *w == "--export");
+                let mut args = flags.iter().skip_while(|w| w.starts_with('-'));
+                if let (true, Some(name)) = (exported, args.next()) {
+                    if is_identifier(name) {
+                        let value = args.copied().collect::<Vec<_>>().join(" ");
+                        exports.push((name.to_string(), idx + 1, unquote(&value)));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    exports
+}
+
+/// Splits `A=1 B="two words"` into assignments, keeping quoted spaces.
+fn split_assignments(s: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+
+    for c in s.chars() {
+        match (c, quote) {
+            ('"' | '\'', None) => {
+                quote = Some(c);
+                current.push(c);
+            }
+            (c, Some(q)) if c == q => {
+                quote = None;
+                current.push(c);
+            }
+            ('#', None) if current.is_empty() => break,
+            (c, None) if c.is_whitespace() => {
+                if !current.is_empty() {
+                    parts.push(std::mem::take(&mut current));
+                }
+            }
+            _ => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        parts.push(current);
+    }
+    parts
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn unquote(value: &str) -> String {
+    let value = value.trim();
+    for q in ['"', '\''] {
+        if value.len() >= 2 && value.starts_with(q) && value.ends_with(q) {
+            return value[1..value.len() - 1].to_string();
+        }
+    }
+    value.to_string()
+}
+
+/// Collects exports of `name` from the shell init files under `home`, in the
+/// order the files are listed in [`SHELL_INIT_FILES`].
+pub fn shell_exports(home: &Path, name: &str) -> Vec<ShellExport> {
+    SHELL_INIT_FILES
+        .iter()
+        .map(|rel| home.join(rel))
+        .filter_map(|path| std::fs::read_to_string(&path).ok().map(|c| (path, c)))
+        .flat_map(|(path, content)| {
+            parse_exports(&content)
+                .into_iter()
+                .filter(|(n, _, _)| n == name)
+                .map(move |(_, line, value)| ShellExport {
+                    file: path.display().to_string(),
+                    line,
+                    value,
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_posix_and_fish_exports() {
+        let content = "# export IGNORED=1\nPLAIN=1\nexport A=1 B=\"two words\"\n\
+                       declare -x C='c'\nset -gx D some value\nset E 5\n";
+        assert_eq!(
+            parse_exports(content),
+            vec![
+                ("A".to_string(), 3, "1".to_string()),
+                ("B".to_string(), 3, "two words".to_string()),
+                ("C".to_string(), 4, "c".to_string()),
+                ("D".to_string(), 5, "some value".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn flags_sensitive_names() {
+        assert!(is_sensitive("ANTHROPIC_API_KEY"));
+        assert!(is_sensitive("https_proxy"));
+        assert!(is_sensitive("GITHUB_TOKEN"));
+        assert!(!is_sensitive("ANTHROPIC_MODEL"));
+        assert!(!is_sensitive("CLAUDE_CODE_MAX_OUTPUT_TOKENS"));
+    }
+}
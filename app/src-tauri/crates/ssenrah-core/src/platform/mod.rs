@@ -0,0 +1,7 @@
+pub mod auth;
+pub mod detect;
+pub mod elevate;
+pub mod env;
+pub mod paths;
+pub mod portable;
+pub mod proxy;
@@ -0,0 +1,272 @@
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use crate::args;
+use crate::errors::IpcError;
+use crate::platform::portable;
+use crate::types::ConfigScope;
+
+/// Runtime override of the home directory, set via the `set_home_override`
+/// IPC command for portable installs and hosts that report no home.
+static HOME_OVERRIDE: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Sets or clears (`None`) the runtime home directory override.
+pub fn set_home_override(dir: Option<PathBuf>) {
+    let mut guard = HOME_OVERRIDE.write().unwrap_or_else(|e| e.into_inner());
+    *guard = dir;
+}
+
+/// Returns the runtime home directory override, if any.
+pub fn home_override() -> Option<PathBuf> {
+    HOME_OVERRIDE
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// Returns the user's home directory: the runtime override if set,
+/// otherwise the one the OS reports via the `dirs` crate.
+///
+/// Some containers and CI runners have no home directory. Commands that
+/// need it fail with a `PlatformError`; callers that only use it to shorten
+/// or expand `~` skip that step instead.
+pub fn home_dir() -> Result<PathBuf, IpcError> {
+    if let Some(dir) = home_override() {
+        return Ok(dir);
+    }
+    dirs::home_dir().ok_or_else(|| IpcError::PlatformError {
+        message: "Could not determine the home directory. Set HOME, CLAUDE_CONFIG_DIR, or a home \
+                  directory override in ssenrah."
+            .to_string(),
+    })
+}
+
+/// Canonical form of `path` that also works for a file that does not exist
+/// (yet or any more), by canonicalizing its parent instead. Used to compare
+/// paths spelled differently, e.g. through a symlinked temp directory.
+pub fn normalize(path: &Path) -> PathBuf {
+    if let Ok(canonical) = path.canonicalize() {
+        return canonical;
+    }
+    match (
+        path.parent().and_then(|p| p.canonicalize().ok()),
+        path.file_name(),
+    ) {
+        (Some(parent), Some(name)) => parent.join(name),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Runtime override of the config directory, set via the
+/// `set_config_dir_override` IPC command.
+///
+/// Kept process-wide (rather than threaded through every caller) because path
+/// resolution is stateless and called from the watcher thread as well as
+/// from commands.
+static CONFIG_DIR_OVERRIDE: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Sets or clears (`None`) the runtime config directory override.
+pub fn set_config_dir_override(dir: Option<PathBuf>) {
+    let mut guard = CONFIG_DIR_OVERRIDE
+        .write()
+        .unwrap_or_else(|e| e.into_inner());
+    *guard = dir;
+}
+
+/// Returns the runtime config directory override, if any.
+pub fn config_dir_override() -> Option<PathBuf> {
+    CONFIG_DIR_OVERRIDE
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// Resolves the Claude Code config directory.
+///
+/// Priority:
+/// 1. Runtime override (`set_config_dir_override`)
+/// 2. CLAUDE_CONFIG_DIR environment variable; in portable mode a relative
+///    path is taken from the executable's directory
+/// 3. ~/.claude/
+///
+/// Fails when none of these is set and there is no home directory; the
+/// doctor reports why.
+pub fn resolve_config_dir() -> Result<PathBuf, IpcError> {
+    if let Some(dir) = config_dir_override() {
+        return Ok(dir);
+    }
+
+    if let Ok(dir) = std::env::var("CLAUDE_CONFIG_DIR") {
+        if !dir.is_empty() {
+            let dir = PathBuf::from(dir);
+            return Ok(match portable::root() {
+                Some(root) if dir.is_relative() => root.join(dir),
+                _ => dir,
+            });
+        }
+    }
+
+    Ok(home_dir()?.join(".claude"))
+}
+
+/// Resolves the directory for ssenrah's own state (backups, profiles
+/// metadata, logs, ...): `{configDir}/ssenrah/`, or
+/// `{exeDir}/ssenrah-data/` in portable mode.
+///
+/// Kept inside the config dir so it follows `CLAUDE_CONFIG_DIR` and the
+/// runtime override, but in its own subdirectory so Claude Code ignores it.
+pub fn resolve_app_dir() -> Result<PathBuf, IpcError> {
+    match portable::data_dir() {
+        Some(dir) => Ok(dir),
+        None => Ok(resolve_config_dir()?.join("ssenrah")),
+    }
+}
+
+/// Resolves the managed-settings directory (machine-wide, admin-controlled).
+///
+/// - macOS:   /Library/Application Support/ClaudeCode/
+/// - Linux:   /etc/claude-code/
+/// - Windows: None (not yet supported)
+pub fn resolve_managed_settings_dir() -> Option<PathBuf> {
+    if cfg!(target_os = "macos") {
+        Some(PathBuf::from("/Library/Application Support/ClaudeCode"))
+    } else if cfg!(target_os = "linux") {
+        Some(PathBuf::from("/etc/claude-code"))
+    } else {
+        // Windows: no managed-settings directory yet
+        None
+    }
+}
+
+/// Resolves the settings file path for a given scope.
+///
+/// - User:    ~/.claude/settings.json
+/// - Project: {projectRoot}/.claude/settings.json
+/// - Local:   {projectRoot}/.claude/settings.local.json
+/// - Managed: {managedSettingsDir}/managed-settings.json
+pub fn resolve_settings_path(
+    scope: &ConfigScope,
+    project_root: &Option<String>,
+) -> Result<PathBuf, IpcError> {
+    match scope {
+        ConfigScope::User => {
+            let config_dir = resolve_config_dir()?;
+            Ok(config_dir.join("settings.json"))
+        }
+        ConfigScope::Project => {
+            let root = project_root.as_ref().ok_or_else(|| IpcError::NoProject {
+                message: "No project is open. Open a project before reading project settings."
+                    .to_string(),
+            })?;
+            Ok(PathBuf::from(root).join(".claude").join("settings.json"))
+        }
+        ConfigScope::Local => {
+            let root = project_root.as_ref().ok_or_else(|| IpcError::NoProject {
+                message: "No project is open. Open a project before reading local settings."
+                    .to_string(),
+            })?;
+            Ok(PathBuf::from(root)
+                .join(".claude")
+                .join("settings.local.json"))
+        }
+        ConfigScope::Managed => {
+            let dir = resolve_managed_settings_dir().ok_or_else(|| IpcError::Unsupported {
+                message: "Managed settings directory is not supported on this platform."
+                    .to_string(),
+            })?;
+            Ok(dir.join("managed-settings.json"))
+        }
+    }
+}
+
+/// Resolves a scope address to its scope and the project root it refers to.
+///
+/// A plain scope (`"project"`) uses the open project. In a monorepo,
+/// `"project:packages/api"` (or `"local:packages/api"`) addresses the
+/// `.claude` directory of that package instead: the package path must be
+/// relative, stay inside the project, and exist. Only project and local
+/// scopes can address a package.
+pub fn resolve_scope_address<'a>(
+    address: &'a str,
+    project_root: &Option<String>,
+) -> Result<(&'a str, Option<String>), IpcError> {
+    let Some((scope, package)) = address.split_once(':') else {
+        return Ok((address, project_root.clone()));
+    };
+    if scope != "project" && scope != "local" {
+        return Err(IpcError::InvalidArgument {
+            argument: "scope".to_string(),
+            message: format!(
+                "Scope '{}' cannot address a package; only project and local scopes can",
+                scope
+            ),
+        });
+    }
+    let root = project_root.as_ref().ok_or_else(|| IpcError::NoProject {
+        message: "No project is open. Open a project before addressing one of its packages."
+            .to_string(),
+    })?;
+    args::relative_path("scope", package)?;
+    let dir = Path::new(root).join(package);
+    if !dir.is_dir() {
+        return Err(IpcError::NotFound {
+            path: dir.display().to_string(),
+        });
+    }
+    Ok((scope, Some(dir.display().to_string())))
+}
+
+/// Resolves the agents directory for a given scope.
+///
+/// - "user"    -> {configDir}/agents/
+/// - "project" -> {projectRoot}/.claude/agents/
+/// - "project:<package>" -> {projectRoot}/<package>/.claude/agents/
+pub fn resolve_agents_dir(scope: &str, project_root: &Option<String>) -> Result<PathBuf, IpcError> {
+    let (scope, project_root) = resolve_scope_address(scope, project_root)?;
+    match scope {
+        "user" => {
+            let config_dir = resolve_config_dir()?;
+            Ok(config_dir.join("agents"))
+        }
+        "project" => {
+            let root = project_root.ok_or_else(|| IpcError::NoProject {
+                message: "No project is open. Open a project before accessing project agents."
+                    .to_string(),
+            })?;
+            Ok(PathBuf::from(root).join(".claude").join("agents"))
+        }
+        _ => Err(IpcError::InvalidArgument {
+            argument: "scope".to_string(),
+            message: format!("Unknown agent scope: {}", scope),
+        }),
+    }
+}
+
+/// Resolves the MCP config file path for a given source.
+///
+/// - project: {projectRoot}/.claude/.mcp.json
+/// - user:    ~/.claude.json (mcpServers section only)
+/// - managed: {managedSettingsDir}/managed-mcp.json
+pub fn resolve_mcp_path(source: &str, project_root: &Option<String>) -> Result<PathBuf, IpcError> {
+    match source {
+        "project" => {
+            let root = project_root.as_ref().ok_or_else(|| IpcError::NoProject {
+                message: "No project is open. Open a project before reading project MCP config."
+                    .to_string(),
+            })?;
+            Ok(PathBuf::from(root).join(".claude").join(".mcp.json"))
+        }
+        "user" => Ok(home_dir()?.join(".claude.json")),
+        "managed" => {
+            let dir = resolve_managed_settings_dir().ok_or_else(|| IpcError::Unsupported {
+                message: "Managed settings directory is not supported on this platform."
+                    .to_string(),
+            })?;
+            Ok(dir.join("managed-mcp.json"))
+        }
+        _ => Err(IpcError::InvalidArgument {
+            argument: "source".to_string(),
+            message: format!("Unknown MCP source: {}", source),
+        }),
+    }
+}
@@ -0,0 +1,67 @@
+//! Portable mode: ssenrah's own state (preferences, logs, backups, the
+//! lockfile, ...) lives next to the executable instead of inside the config
+//! dir, so the app can run from a USB stick or be shared on a kiosk without
+//! touching the user profile.
+//!
+//! Turned on by a `portable.flag` file beside the executable or by starting
+//! the app with `--portable`. It is decided once at startup.
+
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// File that turns portable mode on when it sits beside the executable.
+pub const FLAG_FILE: &str = "portable.flag";
+
+/// Command-line flag that turns portable mode on.
+pub const FLAG_ARG: &str = "--portable";
+
+/// Directory beside the executable that holds ssenrah's state.
+const DATA_DIR: &str = "ssenrah-data";
+
+/// Directory of the executable when running portable, unset otherwise.
+static ROOT: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+/// Whether `args` or a flag file in `exe_dir` ask for portable mode.
+pub fn requested(args: &[String], exe_dir: &Path) -> bool {
+    args.iter().any(|a| a == FLAG_ARG) || exe_dir.join(FLAG_FILE).is_file()
+}
+
+/// Decides portable mode for this process from the command line and the
+/// executable's directory. Only the first call has an effect; returns the
+/// portable root, if any.
+pub fn init(args: impl IntoIterator<Item = String>) -> Option<PathBuf> {
+    ROOT.get_or_init(|| {
+        let args: Vec<String> = args.into_iter().collect();
+        let exe_dir = std::env::current_exe().ok()?.parent()?.to_path_buf();
+        requested(&args, &exe_dir).then_some(exe_dir)
+    })
+    .clone()
+}
+
+/// Directory of the executable when running portable.
+pub fn root() -> Option<PathBuf> {
+    ROOT.get().cloned().flatten()
+}
+
+/// Where ssenrah keeps its state when running portable:
+/// `{exeDir}/ssenrah-data/`.
+pub fn data_dir() -> Option<PathBuf> {
+    root().map(|root| root.join(DATA_DIR))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_flag_file_and_argument() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+
+        assert!(!requested(&[], dir));
+        assert!(requested(&["--portable".to_string()], dir));
+
+        std::fs::write(dir.join(FLAG_FILE), "").unwrap();
+        assert!(requested(&[], dir));
+    }
+}
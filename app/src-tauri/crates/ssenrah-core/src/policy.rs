@@ -0,0 +1,257 @@
+//! Admin-authored constraints on what users may put in their settings.
+//!
+//! Unlike managed settings, which Claude Code applies on top of every
+//! scope (see `schema::policy` for how they defeat local values), a policy
+//! file only tells ssenrah what to allow: it is checked when settings are
+//! validated and, if `enforce` is on, before they are written.
+
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::errors::IpcError;
+use crate::platform::paths;
+use crate::schema::occurrences::lookup;
+use crate::types::ValidationError;
+
+/// File name of the policy, next to managed-settings.json.
+pub const POLICY_FILE: &str = "ssenrah-policy.json";
+
+/// A single constraint on a settings key.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub enum Constraint {
+    /// If set, `key` must be `value`. A scope that leaves it out is fine:
+    /// another scope, or managed settings, may provide it.
+    Equals { key: String, value: Value },
+    /// If set, `key` must be one of `values`.
+    OneOf { key: String, values: Vec<Value> },
+    /// `key` must not be set.
+    Unset { key: String },
+    /// Only these MCP servers may be configured or enabled.
+    McpAllowList { servers: Vec<String> },
+}
+
+/// A constraint with the message shown when it is violated.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PolicyRule {
+    #[serde(flatten)]
+    pub constraint: Constraint,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Contents of `ssenrah-policy.json`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyFile {
+    /// Refuse writes that violate a rule instead of only warning.
+    #[serde(default)]
+    pub enforce: bool,
+    #[serde(default)]
+    pub rules: Vec<PolicyRule>,
+}
+
+/// Where the policy file lives, or `None` where managed settings are not
+/// supported.
+pub fn policy_path() -> Option<PathBuf> {
+    paths::resolve_managed_settings_dir().map(|dir| dir.join(POLICY_FILE))
+}
+
+/// Reads the policy file, or `None` if there is none.
+pub fn load() -> Result<Option<PolicyFile>, IpcError> {
+    let Some(path) = policy_path() else {
+        return Ok(None);
+    };
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => {
+            return Err(IpcError::PlatformError {
+                message: format!("Failed to read {}: {}", path.display(), e),
+            })
+        }
+    };
+    serde_json::from_str(&contents)
+        .map(Some)
+        .map_err(|e| IpcError::ParseError {
+            path: path.display().to_string(),
+            message: e.to_string(),
+        })
+}
+
+fn violation(rule: &PolicyRule, path: &str, default: String) -> ValidationError {
+    ValidationError {
+        path: path.to_string(),
+        message: rule.message.clone().unwrap_or(default),
+        code: "POLICY_VIOLATION".to_string(),
+    }
+}
+
+/// Server names outside `allowed`, each with the path that names it.
+fn unlisted_servers<'a>(
+    named: impl Iterator<Item = (String, &'a str)>,
+    allowed: &[String],
+) -> Vec<(String, String)> {
+    named
+        .filter(|(_, name)| !allowed.iter().any(|a| a == name))
+        .map(|(path, name)| (path, name.to_string()))
+        .collect()
+}
+
+/// Checks a settings object against every rule of `policy`.
+pub fn check_settings(policy: &PolicyFile, settings: &Value) -> Vec<ValidationError> {
+    let mut violations = Vec::new();
+    for rule in &policy.rules {
+        match &rule.constraint {
+            Constraint::Equals { key, value } => {
+                if lookup(settings, key).is_some_and(|actual| actual != value) {
+                    violations.push(violation(
+                        rule,
+                        key,
+                        format!("Policy requires {} to be {}", key, value),
+                    ));
+                }
+            }
+            Constraint::OneOf { key, values } => {
+                if let Some(actual) = lookup(settings, key) {
+                    if !values.contains(actual) {
+                        let allowed: Vec<String> = values.iter().map(Value::to_string).collect();
+                        violations.push(violation(
+                            rule,
+                            key,
+                            format!(
+                                "Policy allows {} to be one of {}, not {}",
+                                key,
+                                allowed.join(", "),
+                                actual
+                            ),
+                        ));
+                    }
+                }
+            }
+            Constraint::Unset { key } => {
+                if lookup(settings, key).is_some() {
+                    violations.push(violation(
+                        rule,
+                        key,
+                        format!("Policy does not allow {} to be set", key),
+                    ));
+                }
+            }
+            Constraint::McpAllowList { servers } => {
+                let enabled = settings
+                    .get("enabledMcpjsonServers")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .enumerate()
+                    .filter_map(|(i, v)| {
+                        v.as_str()
+                            .map(|name| (format!("enabledMcpjsonServers[{}]", i), name))
+                    });
+                for (path, name) in unlisted_servers(enabled, servers) {
+                    violations.push(violation(
+                        rule,
+                        &path,
+                        format!("Policy does not allow the MCP server '{}'", name),
+                    ));
+                }
+            }
+        }
+    }
+    violations
+}
+
+/// Checks an MCP config (`{ mcpServers: {...} }`) against the policy's MCP
+/// allow lists.
+pub fn check_mcp_config(policy: &PolicyFile, config: &Value) -> Vec<ValidationError> {
+    let servers = config.get("mcpServers").and_then(Value::as_object);
+    let mut violations = Vec::new();
+    for rule in &policy.rules {
+        let Constraint::McpAllowList { servers: allowed } = &rule.constraint else {
+            continue;
+        };
+        let named = servers
+            .into_iter()
+            .flatten()
+            .map(|(name, _)| (format!("mcpServers.{}", name), name.as_str()));
+        for (path, name) in unlisted_servers(named, allowed) {
+            violations.push(violation(
+                rule,
+                &path,
+                format!("Policy does not allow the MCP server '{}'", name),
+            ));
+        }
+    }
+    violations
+}
+
+/// Fails with the violations if the policy is enforced and `check` finds
+/// any. Without a policy file, or with `enforce` off, this always passes.
+pub fn enforce(check: impl Fn(&PolicyFile) -> Vec<ValidationError>) -> Result<(), IpcError> {
+    let Some(policy) = load()? else {
+        return Ok(());
+    };
+    if !policy.enforce {
+        return Ok(());
+    }
+    let errors = check(&policy);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(IpcError::ValidationError { errors })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn reports_each_kind_of_violation() {
+        let policy: PolicyFile = serde_json::from_value(json!({
+            "enforce": true,
+            "rules": [
+                { "rule": "equals", "key": "permissions.disableBypassPermissionsMode", "value": "disable" },
+                { "rule": "one_of", "key": "model", "values": ["sonnet", "opus"] },
+                { "rule": "unset", "key": "env.HTTPS_PROXY", "message": "Use the system proxy" },
+                { "rule": "mcp_allow_list", "servers": ["github"] }
+            ]
+        }))
+        .unwrap();
+
+        let compliant = json!({
+            "permissions": { "disableBypassPermissionsMode": "disable" },
+            "enabledMcpjsonServers": ["github"]
+        });
+        assert!(check_settings(&policy, &compliant).is_empty());
+
+        let settings = json!({
+            "permissions": { "disableBypassPermissionsMode": false },
+            "model": "haiku",
+            "env": { "HTTPS_PROXY": "http://proxy" },
+            "enabledMcpjsonServers": ["github", "scraper"]
+        });
+        let violations = check_settings(&policy, &settings);
+        let paths: Vec<_> = violations.iter().map(|v| v.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "permissions.disableBypassPermissionsMode",
+                "model",
+                "env.HTTPS_PROXY",
+                "enabledMcpjsonServers[1]"
+            ]
+        );
+        assert_eq!(violations[2].message, "Use the system proxy");
+
+        let mcp = json!({ "mcpServers": { "github": {}, "scraper": {} } });
+        let violations = check_mcp_config(&policy, &mcp);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].path, "mcpServers.scraper");
+    }
+}
@@ -0,0 +1,219 @@
+//! Frontmatter filters for `query_artifacts`, so the frontend can ask for
+//! "agents where model is haiku" without listing every file.
+
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::args;
+use crate::errors::IpcError;
+use crate::frontmatter::parse_frontmatter;
+
+/// How a condition compares a frontmatter field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryOp {
+    /// The field equals the value; for a list, one item does.
+    Eq,
+    /// The opposite of `eq`; a missing field matches.
+    Ne,
+    /// The field contains the value, ignoring case; for a list, one item
+    /// does.
+    Contains,
+    /// The field is set to something other than null or an empty string.
+    Exists,
+    /// The opposite of `exists`.
+    Missing,
+}
+
+/// One condition on a frontmatter field.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldCondition {
+    pub field: String,
+    pub op: QueryOp,
+    /// Compared as text, so `2` matches both `2` and `"2"`. Required by
+    /// `eq`, `ne`, and `contains`.
+    pub value: Option<Value>,
+}
+
+/// `value` as the text a frontmatter scalar is compared by.
+fn text(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(s) => Some(s.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+/// The scalars of a field: its items for a list, or the field itself.
+fn scalars(value: &Value) -> Vec<String> {
+    match value {
+        Value::Array(items) => items.iter().filter_map(text).collect(),
+        other => text(other).into_iter().collect(),
+    }
+}
+
+impl FieldCondition {
+    fn matches(&self, frontmatter: &Value) -> bool {
+        let field = &frontmatter[&self.field];
+        let value = self.value.as_ref().and_then(text).unwrap_or_default();
+        match self.op {
+            QueryOp::Eq => scalars(field).contains(&value),
+            QueryOp::Ne => !scalars(field).contains(&value),
+            QueryOp::Contains => {
+                let value = value.to_lowercase();
+                scalars(field)
+                    .iter()
+                    .any(|s| s.to_lowercase().contains(&value))
+            }
+            QueryOp::Exists | QueryOp::Missing => {
+                let set = match field {
+                    Value::Null => false,
+                    Value::String(s) => !s.trim().is_empty(),
+                    _ => true,
+                };
+                set == (self.op == QueryOp::Exists)
+            }
+        }
+    }
+}
+
+/// Checks that every condition names a field and has the value its
+/// operator needs.
+pub fn validate(filter: &[FieldCondition]) -> Result<(), IpcError> {
+    for (i, condition) in filter.iter().enumerate() {
+        args::non_empty(&format!("filter[{}].field", i), &condition.field)?;
+        let needs_value = matches!(condition.op, QueryOp::Eq | QueryOp::Ne | QueryOp::Contains);
+        if needs_value && condition.value.as_ref().and_then(text).is_none() {
+            return Err(args::invalid(
+                &format!("filter[{}].value", i),
+                "eq, ne, and contains need a value",
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Whether `frontmatter` meets every condition of `filter`; an empty
+/// filter matches everything.
+pub fn matches(filter: &[FieldCondition], frontmatter: &Value) -> bool {
+    filter.iter().all(|c| c.matches(frontmatter))
+}
+
+/// An agent or skill returned by `query_artifacts`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactMatch {
+    pub scope: String,
+    /// The agent filename or skill directory, as `list_agents` and
+    /// `list_skills` report it.
+    pub name: String,
+    pub path: String,
+    pub frontmatter: Value,
+    pub body_preview: String,
+}
+
+/// Adds the agents or skills (`kind`) of `dir` whose frontmatter meets
+/// `filter` to `found`.
+pub fn query_dir(
+    kind: &str,
+    scope: &str,
+    dir: &Path,
+    filter: &[FieldCondition],
+    found: &mut Vec<ArtifactMatch>,
+) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let path = entry.path();
+        let file = if kind == "skill" && path.is_dir() {
+            path.join("SKILL.md")
+        } else if name.ends_with(".md") {
+            path
+        } else {
+            continue;
+        };
+        let Ok(content) = fs::read_to_string(&file) else {
+            continue;
+        };
+        let (frontmatter, body) = parse_frontmatter(&content);
+        if matches(filter, &frontmatter) {
+            found.push(ArtifactMatch {
+                scope: scope.to_string(),
+                name,
+                path: file.display().to_string(),
+                frontmatter,
+                body_preview: body.chars().take(200).collect(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn condition(field: &str, op: QueryOp, value: Option<Value>) -> FieldCondition {
+        FieldCondition {
+            field: field.to_string(),
+            op,
+            value,
+        }
+    }
+
+    #[test]
+    fn filters_frontmatter() {
+        let agent = json!({
+            "name": "reviewer",
+            "model": "haiku",
+            "tools": ["Read", "Grep"],
+            "description": "",
+            "priority": 2,
+        });
+
+        let haiku = [condition("model", QueryOp::Eq, Some(json!("haiku")))];
+        assert!(matches(&haiku, &agent));
+        assert!(!matches(&haiku, &json!({ "model": "opus" })));
+        assert!(matches(&[], &agent));
+
+        assert!(matches(
+            &[condition("tools", QueryOp::Eq, Some(json!("Grep")))],
+            &agent
+        ));
+        assert!(matches(
+            &[condition("name", QueryOp::Contains, Some(json!("REVIEW")))],
+            &agent
+        ));
+        assert!(matches(
+            &[condition("priority", QueryOp::Eq, Some(json!("2")))],
+            &agent
+        ));
+        assert!(matches(
+            &[condition("model", QueryOp::Ne, Some(json!("opus")))],
+            &json!({})
+        ));
+
+        assert!(matches(
+            &[condition("description", QueryOp::Missing, None)],
+            &agent
+        ));
+        assert!(!matches(
+            &[condition("model", QueryOp::Missing, None)],
+            &agent
+        ));
+        assert!(matches(
+            &[condition("tools", QueryOp::Exists, None)],
+            &agent
+        ));
+
+        assert!(validate(&haiku).is_ok());
+        assert!(validate(&[condition("model", QueryOp::Eq, None)]).is_err());
+        assert!(validate(&[condition("", QueryOp::Exists, None)]).is_err());
+    }
+}
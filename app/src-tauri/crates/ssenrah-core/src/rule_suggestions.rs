@@ -0,0 +1,238 @@
+use std::collections::{BTreeMap, HashSet};
+
+use serde::Serialize;
+
+use crate::sessions::{ToolCall, ToolOutcome};
+
+/// Tools that never ask for permission, so rules for them change nothing.
+const PROMPTLESS_TOOLS: &[&str] = &[
+    "BashOutput",
+    "ExitPlanMode",
+    "Glob",
+    "Grep",
+    "KillShell",
+    "LS",
+    "NotebookRead",
+    "Read",
+    "Task",
+    "TodoWrite",
+];
+
+/// Tools covered by `Edit` rules.
+const EDIT_TOOLS: &[&str] = &["Edit", "MultiEdit", "NotebookEdit", "Write"];
+
+/// Commands whose second word is a subcommand worth keeping in a prefix
+/// rule: `Bash(git push:*)` rather than `Bash(git:*)`.
+const SUBCOMMAND_PROGRAMS: &[&str] = &[
+    "bun", "cargo", "docker", "gh", "git", "go", "kubectl", "make", "npm", "npx", "pnpm", "poetry",
+    "uv", "yarn",
+];
+
+/// Shell syntax that makes a command too complex for a prefix rule.
+const SHELL_OPERATORS: &[&str] = &["&&", "||", ";", "|", "`", "$(", ">", "<", "\n"];
+
+/// Approvals without a denial needed before a rule is proposed for `allow`.
+pub const MIN_APPROVALS: usize = 3;
+
+/// Sample inputs kept per suggestion.
+const MAX_EXAMPLES: usize = 3;
+
+/// A permission rule proposed from past tool calls.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleSuggestion {
+    pub rule: String,
+    /// "allow", "ask", or "deny".
+    pub list: &'static str,
+    pub approved: usize,
+    pub denied: usize,
+    /// Sessions the rule would have matched calls in.
+    pub sessions: usize,
+    /// Commands, paths, or URLs of matching calls.
+    pub examples: Vec<String>,
+}
+
+/// `Bash(prefix:*)` for a simple command: its program, plus the subcommand
+/// for tools like git and npm. Compound commands and leading variable
+/// assignments get no rule.
+fn bash_rule(command: &str) -> Option<String> {
+    if SHELL_OPERATORS.iter().any(|op| command.contains(op)) {
+        return None;
+    }
+    let mut words = command.split_whitespace();
+    let program = words.next().filter(|w| !w.contains('='))?;
+    let prefix = match words.next() {
+        Some(sub) if SUBCOMMAND_PROGRAMS.contains(&program) && !sub.starts_with('-') => {
+            format!("{} {}", program, sub)
+        }
+        _ => program.to_string(),
+    };
+    Some(format!("Bash({}:*)", prefix))
+}
+
+/// Host of an `http(s)` URL.
+fn url_host(url: &str) -> Option<&str> {
+    let rest = url.split_once("://")?.1;
+    let host = rest.split(['/', '?', '#']).next()?;
+    let host = host.rsplit('@').next()?.split(':').next()?;
+    (!host.is_empty()).then_some(host)
+}
+
+/// The rule that would match `call` and a short example of its input, or
+/// `None` for tools that never prompt.
+pub fn rule_for(call: &ToolCall) -> Option<(String, String)> {
+    let field = |key: &str| {
+        call.input
+            .get(key)
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string()
+    };
+    let name = call.name.as_str();
+    if PROMPTLESS_TOOLS.contains(&name) {
+        return None;
+    }
+    if name == "Bash" {
+        let command = field("command");
+        return Some((bash_rule(&command)?, command));
+    }
+    if EDIT_TOOLS.contains(&name) {
+        let path = field("file_path");
+        let path = if path.is_empty() {
+            field("notebook_path")
+        } else {
+            path
+        };
+        return Some(("Edit".to_string(), path));
+    }
+    if name == "WebFetch" {
+        let url = field("url");
+        let host = url_host(&url)?;
+        return Some((format!("WebFetch(domain:{})", host), url));
+    }
+    Some((name.to_string(), String::new()))
+}
+
+#[derive(Default)]
+struct Tally {
+    approved: usize,
+    denied: usize,
+    sessions: HashSet<usize>,
+    examples: Vec<String>,
+}
+
+/// Proposes rules from the tool calls of several sessions, most frequent
+/// first. A rule whose calls were only ever denied goes to `deny`, one that
+/// was both approved and denied to `ask`, and one approved at least
+/// `MIN_APPROVALS` times to `allow`. Rules already in `existing` are left
+/// out.
+pub fn suggest(sessions: &[Vec<ToolCall>], existing: &[String]) -> Vec<RuleSuggestion> {
+    let mut tallies: BTreeMap<String, Tally> = BTreeMap::new();
+    for (index, calls) in sessions.iter().enumerate() {
+        for call in calls {
+            let Some((rule, example)) = rule_for(call) else {
+                continue;
+            };
+            let tally = tallies.entry(rule).or_default();
+            match call.outcome {
+                ToolOutcome::Approved => tally.approved += 1,
+                ToolOutcome::Denied => tally.denied += 1,
+            }
+            tally.sessions.insert(index);
+            if !example.is_empty()
+                && tally.examples.len() < MAX_EXAMPLES
+                && !tally.examples.contains(&example)
+            {
+                tally.examples.push(example);
+            }
+        }
+    }
+
+    let mut suggestions: Vec<RuleSuggestion> = tallies
+        .into_iter()
+        .filter(|(rule, _)| !existing.contains(rule))
+        .filter_map(|(rule, tally)| {
+            let list = match (tally.approved, tally.denied) {
+                (0, _) => "deny",
+                (_, 1..) => "ask",
+                (n, 0) if n >= MIN_APPROVALS => "allow",
+                _ => return None,
+            };
+            Some(RuleSuggestion {
+                rule,
+                list,
+                approved: tally.approved,
+                denied: tally.denied,
+                sessions: tally.sessions.len(),
+                examples: tally.examples,
+            })
+        })
+        .collect();
+    // Stable, so equally frequent rules stay in name order.
+    suggestions.sort_by_key(|s| std::cmp::Reverse(s.approved + s.denied));
+    suggestions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn call(name: &str, input: serde_json::Value, outcome: ToolOutcome) -> ToolCall {
+        ToolCall {
+            name: name.to_string(),
+            input,
+            outcome,
+        }
+    }
+
+    #[test]
+    fn derives_rules_from_calls() {
+        let approved = ToolOutcome::Approved;
+        let bash = |c: &str| call("Bash", json!({ "command": c }), approved);
+        assert_eq!(
+            bash_rule("git push origin main").unwrap(),
+            "Bash(git push:*)"
+        );
+        assert_eq!(bash_rule("ls -la").unwrap(), "Bash(ls:*)");
+        assert!(bash_rule("cat a | grep b").is_none());
+        assert_eq!(
+            rule_for(&call(
+                "WebFetch",
+                json!({ "url": "https://docs.rs/serde" }),
+                approved
+            ))
+            .unwrap()
+            .0,
+            "WebFetch(domain:docs.rs)"
+        );
+        assert!(rule_for(&call("Read", json!({}), approved)).is_none());
+
+        let sessions = vec![
+            vec![
+                bash("npm test"),
+                bash("npm test -- --watch"),
+                bash("rm -rf dist"),
+            ],
+            vec![
+                bash("npm test"),
+                call(
+                    "Bash",
+                    json!({ "command": "rm -rf /" }),
+                    ToolOutcome::Denied,
+                ),
+                call("Write", json!({ "file_path": "a.md" }), ToolOutcome::Denied),
+            ],
+        ];
+        let suggestions = suggest(&sessions, &["Edit".to_string()]);
+        let rules: Vec<(&str, &str)> = suggestions
+            .iter()
+            .map(|s| (s.rule.as_str(), s.list))
+            .collect();
+        assert_eq!(
+            rules,
+            vec![("Bash(npm test:*)", "allow"), ("Bash(rm:*)", "ask")]
+        );
+        assert_eq!(suggestions[0].sessions, 2);
+    }
+}
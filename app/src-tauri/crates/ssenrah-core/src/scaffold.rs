@@ -0,0 +1,132 @@
+//! Starter content for new Claude Code config directories, and staging it
+//! without touching anything that already exists.
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::args;
+use crate::errors::IpcError;
+use crate::io::journal::Transaction;
+
+/// JSON schema Claude Code publishes for settings files.
+pub const SETTINGS_SCHEMA: &str = "https://json.schemastore.org/claude-code-settings.json";
+
+/// Starter settings templates, in the order the wizard offers them.
+pub const SETTINGS_TEMPLATES: &[&str] = &["minimal", "cautious", "accept_edits"];
+
+/// Skeleton for the user-scope CLAUDE.md.
+pub const USER_MEMORY_SKELETON: &str = "\
+# Personal instructions
+
+Preferences Claude Code follows in every project.
+
+## Style
+
+-
+
+## Workflow
+
+-
+";
+
+/// Reads of environment and secret files, denied by the stricter templates.
+const SECRET_READS: &[&str] = &["Read(./.env)", "Read(./.env.*)", "Read(./secrets/**)"];
+
+/// Starter `settings.json` for `template` (see `SETTINGS_TEMPLATES`):
+///
+/// - `minimal`: only the `$schema` reference
+/// - `cautious`: denies reading `.env` and `secrets/`, asks before `git push`
+/// - `accept_edits`: accepts file edits without asking, still denies
+///   reading secrets
+pub fn starter_settings(template: &str) -> Result<Value, IpcError> {
+    let settings = match args::one_of("template", template, SETTINGS_TEMPLATES)? {
+        "minimal" => json!({ "$schema": SETTINGS_SCHEMA }),
+        "cautious" => json!({
+            "$schema": SETTINGS_SCHEMA,
+            "permissions": {
+                "ask": ["Bash(git push:*)"],
+                "deny": SECRET_READS,
+            },
+        }),
+        _ => json!({
+            "$schema": SETTINGS_SCHEMA,
+            "permissions": {
+                "defaultMode": "acceptEdits",
+                "deny": SECRET_READS,
+            },
+        }),
+    };
+    Ok(settings)
+}
+
+/// Something a scaffold puts in place.
+pub enum Item {
+    Dir(PathBuf),
+    File(PathBuf, Vec<u8>),
+}
+
+impl Item {
+    /// A pretty-printed JSON file.
+    pub fn json(path: PathBuf, value: &Value) -> Self {
+        let mut text = serde_json::to_string_pretty(value).unwrap_or_default();
+        text.push('\n');
+        Item::File(path, text.into_bytes())
+    }
+
+    fn path(&self) -> &Path {
+        match self {
+            Item::Dir(path) | Item::File(path, _) => path,
+        }
+    }
+}
+
+/// What a scaffold created and what it found already in place.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScaffoldReport {
+    pub created: Vec<String>,
+    /// Left untouched, in whatever state they were.
+    pub existing: Vec<String>,
+}
+
+/// Stages every item that does not exist yet in `txn` and reports which
+/// were staged and which already existed. Nothing is written until the
+/// caller commits.
+pub fn stage(txn: &mut Transaction, items: &[Item]) -> Result<ScaffoldReport, IpcError> {
+    let mut report = ScaffoldReport::default();
+    for item in items {
+        let path = item.path();
+        let display = path.display().to_string();
+        if path.exists() {
+            report.existing.push(display);
+            continue;
+        }
+        match item {
+            Item::Dir(path) => txn.create_dir(path)?,
+            Item::File(path, bytes) => txn.write(path, bytes)?,
+        }
+        report.created.push(display);
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validation::validate_settings;
+
+    #[test]
+    fn starter_settings_are_valid() {
+        for template in SETTINGS_TEMPLATES {
+            let settings = starter_settings(template).unwrap();
+            let result = validate_settings(&settings, &None);
+            assert!(result.valid, "{}: {:?}", template, result.errors);
+        }
+        assert!(matches!(
+            starter_settings("yolo"),
+            Err(IpcError::InvalidArgument { .. })
+        ));
+    }
+}
@@ -0,0 +1,184 @@
+use regex::Regex;
+use serde_json::Value;
+
+use crate::schema::markdown::LintReport;
+use crate::schema::tools::{is_valid_tool_name, tool_list};
+
+/// Model aliases an agent may select.
+pub const MODEL_ALIASES: &[&str] = &["sonnet", "opus", "haiku", "inherit"];
+
+pub const PERMISSION_MODES: &[&str] = &[
+    "default",
+    "acceptEdits",
+    "dontAsk",
+    "bypassPermissions",
+    "plan",
+];
+
+const MEMORY_SCOPES: &[&str] = &["user", "project", "local"];
+
+/// Checks an agent's frontmatter and body against the agent file contract.
+///
+/// Mirrors the frontend's `AgentFrontmatterSchema`: `name` (lowercase with
+/// hyphens) and `description` are required, `tools` / `disallowedTools` must
+/// name known tools or `mcp__` identifiers, and `model`, `permissionMode`,
+/// `maxTurns`, and `memory` must hold recognized values.
+pub fn validate_agent(frontmatter: &Value, body: &str) -> LintReport {
+    let mut report = LintReport::default();
+
+    if !frontmatter.is_object() {
+        report.error(
+            "frontmatter".to_string(),
+            "Frontmatter must be an object".to_string(),
+            "INVALID_TYPE",
+        );
+        return report;
+    }
+
+    match frontmatter.get("name").and_then(Value::as_str) {
+        None | Some("") => report.error(
+            "frontmatter.name".to_string(),
+            "Missing required frontmatter key 'name'".to_string(),
+            "MISSING_FIELD",
+        ),
+        Some(name) => {
+            let valid = Regex::new(r"^[a-z][a-z0-9-]*$").is_ok_and(|re| re.is_match(name));
+            if !valid {
+                report.error(
+                    "frontmatter.name".to_string(),
+                    format!(
+                        "Agent name '{}' must start with a lowercase letter and contain only lowercase letters, digits, and hyphens",
+                        name
+                    ),
+                    "INVALID_FORMAT",
+                );
+            }
+        }
+    }
+
+    if frontmatter
+        .get("description")
+        .and_then(Value::as_str)
+        .is_none_or(|s| s.trim().is_empty())
+    {
+        report.error(
+            "frontmatter.description".to_string(),
+            "Missing required frontmatter key 'description'".to_string(),
+            "MISSING_FIELD",
+        );
+    }
+
+    for key in ["tools", "disallowedTools"] {
+        let Some(value) = frontmatter.get(key).filter(|v| !v.is_null()) else {
+            continue;
+        };
+        if !value.is_string() && !value.is_array() {
+            report.error(
+                format!("frontmatter.{}", key),
+                "Expected a comma-separated string or a list of tool names".to_string(),
+                "INVALID_TYPE",
+            );
+            continue;
+        }
+        for tool in tool_list(value) {
+            if !is_valid_tool_name(&tool) {
+                report.error(
+                    format!("frontmatter.{}", key),
+                    format!("Unknown tool '{}'", tool),
+                    "UNKNOWN_TOOL",
+                );
+            }
+        }
+    }
+
+    let enums = [
+        ("model", MODEL_ALIASES),
+        ("permissionMode", PERMISSION_MODES),
+        ("memory", MEMORY_SCOPES),
+    ];
+    for (key, allowed) in enums {
+        let Some(value) = frontmatter.get(key).filter(|v| !v.is_null()) else {
+            continue;
+        };
+        if !value.as_str().is_some_and(|s| allowed.contains(&s)) {
+            report.error(
+                format!("frontmatter.{}", key),
+                format!("'{}' must be one of: {}", key, allowed.join(", ")),
+                "INVALID_VALUE",
+            );
+        }
+    }
+
+    if let Some(value) = frontmatter.get("maxTurns").filter(|v| !v.is_null()) {
+        if !value.as_i64().is_some_and(|n| (1..=100).contains(&n)) {
+            report.error(
+                "frontmatter.maxTurns".to_string(),
+                "'maxTurns' must be an integer from 1 to 100".to_string(),
+                "INVALID_VALUE",
+            );
+        }
+    }
+
+    if body.trim().is_empty() {
+        report.warning(
+            "body".to_string(),
+            "Agent has no system prompt".to_string(),
+            "EMPTY_BODY",
+        );
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn codes(report: &LintReport) -> Vec<(&str, &str)> {
+        report
+            .errors
+            .iter()
+            .map(|e| (e.path.as_str(), e.code.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn accepts_valid_agent() {
+        let report = validate_agent(
+            &json!({
+                "name": "code-reviewer",
+                "description": "Reviews diffs",
+                "tools": "Read, Grep, mcp__github__get_pr",
+                "model": "sonnet",
+            }),
+            "You review code.",
+        );
+        assert!(report.errors.is_empty());
+        assert!(report.warnings.is_empty());
+    }
+
+    #[test]
+    fn reports_contract_violations() {
+        let report = validate_agent(
+            &json!({
+                "name": "Code Reviewer",
+                "tools": ["Read", "Teleport"],
+                "model": "gpt-4",
+                "maxTurns": 0,
+            }),
+            "",
+        );
+        assert_eq!(
+            codes(&report),
+            vec![
+                ("frontmatter.name", "INVALID_FORMAT"),
+                ("frontmatter.description", "MISSING_FIELD"),
+                ("frontmatter.tools", "UNKNOWN_TOOL"),
+                ("frontmatter.model", "INVALID_VALUE"),
+                ("frontmatter.maxTurns", "INVALID_VALUE"),
+            ]
+        );
+        assert_eq!(report.warnings[0].code, "EMPTY_BODY");
+    }
+}
@@ -0,0 +1,129 @@
+use std::fmt;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::schema::occurrences::lookup;
+
+/// Settings keys that older Claude Code releases silently ignore, with the
+/// first release that reads them, taken from the CLI changelog. Keys that
+/// predate 1.0 are not listed.
+pub const KEY_MIN_VERSIONS: &[(&str, &str)] = &[
+    ("statusLine", "1.0.71"),
+    ("outputStyle", "1.0.81"),
+    ("spinnerTipsEnabled", "1.0.88"),
+    ("alwaysThinkingEnabled", "2.0.0"),
+    ("sandbox", "2.0.24"),
+    ("companyAnnouncements", "2.0.32"),
+    ("attribution", "2.0.62"),
+    ("fileSuggestion", "2.0.65"),
+    ("spinnerVerbs", "2.1.0"),
+    ("spinnerTipsOverride", "2.1.0"),
+    ("showTurnDuration", "2.1.0"),
+    ("language", "2.1.0"),
+];
+
+/// A `major.minor.patch` release number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl Version {
+    /// Parses the first `x.y.z` token in `text`, so raw `claude --version`
+    /// output such as `2.0.14 (Claude Code)` is accepted. Pre-release and
+    /// build suffixes are ignored.
+    pub fn parse(text: &str) -> Option<Version> {
+        text.split_whitespace().find_map(|token| {
+            let core = token.trim_start_matches('v').split(['-', '+']).next()?;
+            let mut parts = core.split('.').map(|p| p.parse::<u32>().ok());
+            let version = Version {
+                major: parts.next()??,
+                minor: parts.next()??,
+                patch: parts.next()??,
+            };
+            parts.next().is_none().then_some(version)
+        })
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Whether a versioned settings key works with the installed CLI.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeatureSupport {
+    pub key: String,
+    pub min_version: String,
+    pub supported: bool,
+}
+
+fn min_version(key: &str) -> Option<Version> {
+    KEY_MIN_VERSIONS
+        .iter()
+        .find(|(k, _)| *k == key)
+        .and_then(|(_, v)| Version::parse(v))
+}
+
+/// Reports every versioned key and whether `installed` supports it.
+pub fn feature_matrix(installed: Version) -> Vec<FeatureSupport> {
+    KEY_MIN_VERSIONS
+        .iter()
+        .filter_map(|(key, min)| {
+            let min = Version::parse(min)?;
+            Some(FeatureSupport {
+                key: key.to_string(),
+                min_version: min.to_string(),
+                supported: installed >= min,
+            })
+        })
+        .collect()
+}
+
+/// Returns the keys in `settings` that `installed` is too old to read, with
+/// the release that introduced each.
+pub fn unsupported_keys(settings: &Value, installed: Version) -> Vec<(String, Version)> {
+    KEY_MIN_VERSIONS
+        .iter()
+        .filter(|(key, _)| lookup(settings, key).is_some())
+        .filter_map(|(key, _)| {
+            let min = min_version(key)?;
+            (installed < min).then(|| (key.to_string(), min))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_cli_version_output() {
+        let v = Version::parse("2.0.14 (Claude Code)").unwrap();
+        assert_eq!(v.to_string(), "2.0.14");
+        assert_eq!(Version::parse("claude v1.0.3-beta.1").unwrap().patch, 3);
+        assert!(Version::parse("Claude Code").is_none());
+        assert!(Version::parse("1.0.2 (x)").unwrap() < Version::parse("1.0.10").unwrap());
+    }
+
+    #[test]
+    fn reports_keys_newer_than_the_cli() {
+        let settings = json!({ "model": "opus", "statusLine": {}, "sandbox": {} });
+        let old = Version::parse("1.0.80").unwrap();
+        let keys: Vec<_> = unsupported_keys(&settings, old)
+            .into_iter()
+            .map(|(k, _)| k)
+            .collect();
+        assert_eq!(keys, vec!["sandbox"]);
+        assert!(feature_matrix(old)
+            .iter()
+            .any(|f| f.key == "statusLine" && f.supported));
+    }
+}
@@ -0,0 +1,107 @@
+use serde::Serialize;
+use serde_json::Value;
+
+/// How a key differs between two settings objects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// One key that differs, by dot-separated path.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingChange {
+    pub path: String,
+    pub kind: ChangeKind,
+    pub left: Option<Value>,
+    pub right: Option<Value>,
+}
+
+/// Lists the keys that differ between `left` and `right`, sorted by path.
+///
+/// Objects are compared key by key; arrays and scalars are compared whole,
+/// matching how the effective merge replaces them.
+pub fn diff(left: &Value, right: &Value) -> Vec<SettingChange> {
+    let mut changes = Vec::new();
+    diff_at("", left, right, &mut changes);
+    changes.sort_by(|a, b| a.path.cmp(&b.path));
+    changes
+}
+
+fn diff_at(path: &str, left: &Value, right: &Value, changes: &mut Vec<SettingChange>) {
+    let (Value::Object(l), Value::Object(r)) = (left, right) else {
+        if left != right {
+            changes.push(SettingChange {
+                path: path.to_string(),
+                kind: ChangeKind::Changed,
+                left: Some(left.clone()),
+                right: Some(right.clone()),
+            });
+        }
+        return;
+    };
+    let child = |key: &str| {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", path, key)
+        }
+    };
+    for (key, lv) in l {
+        match r.get(key) {
+            Some(rv) => diff_at(&child(key), lv, rv, changes),
+            None => changes.push(SettingChange {
+                path: child(key),
+                kind: ChangeKind::Removed,
+                left: Some(lv.clone()),
+                right: None,
+            }),
+        }
+    }
+    for (key, rv) in r {
+        if !l.contains_key(key) {
+            changes.push(SettingChange {
+                path: child(key),
+                kind: ChangeKind::Added,
+                left: None,
+                right: Some(rv.clone()),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn reports_nested_changes_by_path() {
+        let left = json!({
+            "model": "opus",
+            "env": { "A": "1", "B": "2" },
+            "permissions": { "allow": ["Read"] }
+        });
+        let right = json!({
+            "model": "opus",
+            "env": { "A": "1", "C": "3" },
+            "permissions": { "allow": ["Read", "Edit"] }
+        });
+        let changes: Vec<(String, ChangeKind)> = diff(&left, &right)
+            .into_iter()
+            .map(|c| (c.path, c.kind))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                ("env.B".to_string(), ChangeKind::Removed),
+                ("env.C".to_string(), ChangeKind::Added),
+                ("permissions.allow".to_string(), ChangeKind::Changed),
+            ]
+        );
+        assert!(diff(&left, &left).is_empty());
+    }
+}
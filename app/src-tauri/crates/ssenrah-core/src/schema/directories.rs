@@ -0,0 +1,200 @@
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::git::find_git_root;
+use crate::platform::paths::normalize;
+use crate::schema::markdown::LintReport;
+use crate::schema::occurrences::lookup;
+
+/// System directories (and everything under them) that should never be
+/// granted as a working directory.
+const SYSTEM_DIRS: &[&str] = &[
+    "/bin",
+    "/boot",
+    "/dev",
+    "/etc",
+    "/lib",
+    "/proc",
+    "/sbin",
+    "/sys",
+    "/usr",
+    "/System",
+    "/Library",
+    "/private/etc",
+    "C:\\Windows",
+    "C:\\Program Files",
+    "C:\\Program Files (x86)",
+];
+
+/// Resolves an `additionalDirectories` entry: `~` is the home directory and a
+/// relative entry is relative to the project root. `None` for a relative
+/// entry when no project is open.
+fn resolve(entry: &str, project_root: Option<&Path>, home: &Path) -> Option<PathBuf> {
+    if entry == "~" {
+        return Some(home.to_path_buf());
+    }
+    if let Some(rest) = entry.strip_prefix("~/") {
+        return Some(home.join(rest));
+    }
+    let path = Path::new(entry);
+    if path.is_absolute() {
+        Some(path.to_path_buf())
+    } else {
+        project_root.map(|root| root.join(path))
+    }
+}
+
+/// Why `dir` is too broad to grant, if it is.
+fn danger(dir: &Path, home: &Path) -> Option<&'static str> {
+    if dir.parent().is_none() {
+        Some("the filesystem root")
+    } else if dir == home {
+        Some("the home directory")
+    } else if SYSTEM_DIRS
+        .iter()
+        .any(|s| dir.starts_with(s) || dir.starts_with(normalize(Path::new(s))))
+    {
+        Some("a system directory")
+    } else {
+        None
+    }
+}
+
+/// Checks each `permissions.additionalDirectories` entry of `settings`.
+///
+/// Granting the filesystem root, the home directory, or a system directory
+/// is an error, as is an entry that names a file. A directory that does not
+/// exist, or one outside the project's git repository, is a warning: a typo
+/// there silently grants the wrong access or none. Relative entries resolve
+/// against `project_root` and are skipped without one.
+pub fn check_additional_directories(
+    settings: &Value,
+    project_root: Option<&Path>,
+    home: &Path,
+) -> LintReport {
+    let mut report = LintReport::default();
+    let Some(entries) =
+        lookup(settings, "permissions.additionalDirectories").and_then(Value::as_array)
+    else {
+        return report;
+    };
+
+    let home = normalize(home);
+    let repo = project_root
+        .and_then(find_git_root)
+        .map(|root| normalize(Path::new(&root)));
+
+    for (i, entry) in entries.iter().enumerate() {
+        let path = format!("permissions.additionalDirectories[{}]", i);
+        let Some(raw) = entry.as_str() else {
+            continue;
+        };
+        let Some(dir) = resolve(raw, project_root, &home) else {
+            continue;
+        };
+        let dir = normalize(&dir);
+        let shown = dir.display();
+
+        if let Some(what) = danger(&dir, &home) {
+            report.error(
+                path,
+                format!("'{}' resolves to {}, {}", raw, shown, what),
+                "DANGEROUS_DIRECTORY",
+            );
+        } else if !dir.exists() {
+            report.warning(
+                path,
+                format!("'{}' resolves to {}, which does not exist", raw, shown),
+                "DIRECTORY_NOT_FOUND",
+            );
+        } else if !dir.is_dir() {
+            report.error(
+                path,
+                format!("'{}' resolves to {}, which is not a directory", raw, shown),
+                "NOT_A_DIRECTORY",
+            );
+        } else if repo.as_ref().is_some_and(|repo| !dir.starts_with(repo)) {
+            report.warning(
+                path,
+                format!(
+                    "'{}' resolves to {}, outside the project's git repository",
+                    raw, shown
+                ),
+                "OUTSIDE_REPOSITORY",
+            );
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::fs;
+
+    #[test]
+    fn flags_each_kind_of_bad_entry() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let home = root.join("home");
+        let project = home.join("repo");
+        fs::create_dir_all(project.join(".git")).unwrap();
+        fs::create_dir_all(project.join("docs")).unwrap();
+        fs::create_dir_all(home.join("shared")).unwrap();
+        fs::write(project.join("notes.txt"), "").unwrap();
+
+        let settings = json!({
+            "permissions": {
+                "additionalDirectories": [
+                    "docs", "/", "~", "/etc/ssh", "dcos", "notes.txt", "~/shared", 3
+                ]
+            }
+        });
+        let report = check_additional_directories(&settings, Some(&project), &home);
+        let errors: Vec<_> = report
+            .errors
+            .iter()
+            .map(|e| (e.path.as_str(), e.code.as_str()))
+            .collect();
+        let warnings: Vec<_> = report
+            .warnings
+            .iter()
+            .map(|w| (w.path.as_str(), w.code.as_str()))
+            .collect();
+        assert_eq!(
+            errors,
+            [
+                (
+                    "permissions.additionalDirectories[1]",
+                    "DANGEROUS_DIRECTORY"
+                ),
+                (
+                    "permissions.additionalDirectories[2]",
+                    "DANGEROUS_DIRECTORY"
+                ),
+                (
+                    "permissions.additionalDirectories[3]",
+                    "DANGEROUS_DIRECTORY"
+                ),
+                ("permissions.additionalDirectories[5]", "NOT_A_DIRECTORY"),
+            ]
+        );
+        assert_eq!(
+            warnings,
+            [
+                (
+                    "permissions.additionalDirectories[4]",
+                    "DIRECTORY_NOT_FOUND"
+                ),
+                ("permissions.additionalDirectories[6]", "OUTSIDE_REPOSITORY"),
+            ]
+        );
+
+        // Without a project, relative entries cannot be resolved.
+        let relative = json!({ "permissions": { "additionalDirectories": ["dcos"] } });
+        let report = check_additional_directories(&relative, None, &home);
+        assert!(report.errors.is_empty() && report.warnings.is_empty());
+    }
+}
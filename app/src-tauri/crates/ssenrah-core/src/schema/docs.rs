@@ -0,0 +1,475 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::schema::compat::KEY_MIN_VERSIONS;
+
+/// Embedded documentation for one settings key.
+struct Doc {
+    key: &'static str,
+    value_type: &'static str,
+    description: &'static str,
+    /// JSON text of the default, if the CLI has one.
+    default: Option<&'static str>,
+    allowed: &'static [&'static str],
+    /// JSON text of an example value.
+    example: &'static str,
+}
+
+/// Documentation for the settings keys the typed model knows, keyed by
+/// dot-path. Keep in sync with `schema::model::Settings`.
+const DOCS: &[Doc] = &[
+    Doc {
+        key: "permissions",
+        value_type: "object",
+        description: "Rules controlling which tools Claude Code may use without asking.",
+        default: None,
+        allowed: &[],
+        example: r#"{ "allow": ["Read"], "deny": ["Bash(rm:*)"] }"#,
+    },
+    Doc {
+        key: "permissions.allow",
+        value_type: "string[]",
+        description: "Permission rules that are approved without prompting.",
+        default: Some("[]"),
+        allowed: &[],
+        example: r#"["Bash(npm run test:*)", "Read(~/.zshrc)"]"#,
+    },
+    Doc {
+        key: "permissions.ask",
+        value_type: "string[]",
+        description: "Permission rules that always prompt for confirmation.",
+        default: Some("[]"),
+        allowed: &[],
+        example: r#"["Bash(git push:*)"]"#,
+    },
+    Doc {
+        key: "permissions.deny",
+        value_type: "string[]",
+        description: "Permission rules that are always refused. Deny wins over allow and ask.",
+        default: Some("[]"),
+        allowed: &[],
+        example: r#"["Read(./.env)", "WebFetch"]"#,
+    },
+    Doc {
+        key: "permissions.additionalDirectories",
+        value_type: "string[]",
+        description: "Extra working directories Claude Code may access.",
+        default: Some("[]"),
+        allowed: &[],
+        example: r#"["../docs/"]"#,
+    },
+    Doc {
+        key: "permissions.defaultMode",
+        value_type: "string",
+        description: "Permission mode used when a session starts.",
+        default: Some(r#""default""#),
+        allowed: &[
+            "default",
+            "acceptEdits",
+            "dontAsk",
+            "bypassPermissions",
+            "plan",
+        ],
+        example: r#""acceptEdits""#,
+    },
+    Doc {
+        key: "permissions.disableBypassPermissionsMode",
+        value_type: "string",
+        description: "Set to \"disable\" to prevent bypassPermissions mode.",
+        default: None,
+        allowed: &["disable"],
+        example: r#""disable""#,
+    },
+    Doc {
+        key: "hooks",
+        value_type: "object",
+        description: "Commands or prompts run on tool and session events, keyed by event name.",
+        default: None,
+        allowed: &[],
+        example: r#"{ "PostToolUse": [{ "matcher": "Edit", "hooks": [{ "type": "command", "command": "npm run fmt" }] }] }"#,
+    },
+    Doc {
+        key: "disableAllHooks",
+        value_type: "boolean",
+        description: "Turns off every hook.",
+        default: Some("false"),
+        allowed: &[],
+        example: "true",
+    },
+    Doc {
+        key: "allowManagedHooksOnly",
+        value_type: "boolean",
+        description: "Managed settings only: ignore hooks defined outside managed settings.",
+        default: Some("false"),
+        allowed: &[],
+        example: "true",
+    },
+    Doc {
+        key: "enableAllProjectMcpServers",
+        value_type: "boolean",
+        description: "Approve every MCP server in the project's .mcp.json automatically.",
+        default: Some("false"),
+        allowed: &[],
+        example: "true",
+    },
+    Doc {
+        key: "enabledMcpjsonServers",
+        value_type: "string[]",
+        description: "Project .mcp.json servers to approve.",
+        default: None,
+        allowed: &[],
+        example: r#"["memory", "github"]"#,
+    },
+    Doc {
+        key: "disabledMcpjsonServers",
+        value_type: "string[]",
+        description: "Project .mcp.json servers to reject.",
+        default: None,
+        allowed: &[],
+        example: r#"["filesystem"]"#,
+    },
+    Doc {
+        key: "allowedMcpServers",
+        value_type: "object[]",
+        description: "Managed settings only: allowlist of MCP servers users may configure.",
+        default: None,
+        allowed: &[],
+        example: r#"[{ "serverName": "github" }]"#,
+    },
+    Doc {
+        key: "deniedMcpServers",
+        value_type: "object[]",
+        description: "Managed settings only: MCP servers users may not configure.",
+        default: None,
+        allowed: &[],
+        example: r#"[{ "serverName": "filesystem" }]"#,
+    },
+    Doc {
+        key: "sandbox",
+        value_type: "object",
+        description: "Bash sandboxing for filesystem and network isolation.",
+        default: None,
+        allowed: &[],
+        example: r#"{ "enabled": true, "excludedCommands": ["docker"] }"#,
+    },
+    Doc {
+        key: "sandbox.enabled",
+        value_type: "boolean",
+        description: "Run Bash commands in the sandbox.",
+        default: Some("false"),
+        allowed: &[],
+        example: "true",
+    },
+    Doc {
+        key: "sandbox.autoAllowBashIfSandboxed",
+        value_type: "boolean",
+        description: "Approve sandboxed Bash commands without prompting.",
+        default: Some("true"),
+        allowed: &[],
+        example: "false",
+    },
+    Doc {
+        key: "sandbox.excludedCommands",
+        value_type: "string[]",
+        description: "Commands that run outside the sandbox.",
+        default: None,
+        allowed: &[],
+        example: r#"["git", "docker"]"#,
+    },
+    Doc {
+        key: "sandbox.network.allowedDomains",
+        value_type: "string[]",
+        description: "Domains sandboxed commands may reach.",
+        default: None,
+        allowed: &[],
+        example: r#"["github.com", "*.npmjs.org"]"#,
+    },
+    Doc {
+        key: "env",
+        value_type: "object",
+        description: "Environment variables applied to every session.",
+        default: None,
+        allowed: &[],
+        example: r#"{ "ANTHROPIC_MODEL": "sonnet" }"#,
+    },
+    Doc {
+        key: "apiKeyHelper",
+        value_type: "string",
+        description: "Script whose output is used as the API key or auth token.",
+        default: None,
+        allowed: &[],
+        example: r#""~/bin/get-anthropic-key.sh""#,
+    },
+    Doc {
+        key: "otelHeadersHelper",
+        value_type: "string",
+        description: "Script that prints dynamic OpenTelemetry headers as JSON.",
+        default: None,
+        allowed: &[],
+        example: r#""~/bin/otel-headers.sh""#,
+    },
+    Doc {
+        key: "awsAuthRefresh",
+        value_type: "string",
+        description: "Script that refreshes AWS credentials for Bedrock.",
+        default: None,
+        allowed: &[],
+        example: r#""aws sso login --profile dev""#,
+    },
+    Doc {
+        key: "awsCredentialExport",
+        value_type: "string",
+        description: "Script that prints AWS credentials as JSON for Bedrock.",
+        default: None,
+        allowed: &[],
+        example: r#""~/bin/export-aws-creds.sh""#,
+    },
+    Doc {
+        key: "model",
+        value_type: "string",
+        description: "Model used by default, as an alias or full model name.",
+        default: None,
+        allowed: &[],
+        example: r#""opus""#,
+    },
+    Doc {
+        key: "availableModels",
+        value_type: "string[]",
+        description: "Restricts the models users can select.",
+        default: None,
+        allowed: &[],
+        example: r#"["sonnet", "haiku"]"#,
+    },
+    Doc {
+        key: "outputStyle",
+        value_type: "string",
+        description: "Output style that adjusts the system prompt.",
+        default: Some(r#""default""#),
+        allowed: &[],
+        example: r#""Explanatory""#,
+    },
+    Doc {
+        key: "language",
+        value_type: "string",
+        description: "Language Claude responds in.",
+        default: None,
+        allowed: &[],
+        example: r#""japanese""#,
+    },
+    Doc {
+        key: "statusLine",
+        value_type: "object",
+        description: "Custom status line produced by a command.",
+        default: None,
+        allowed: &[],
+        example: r#"{ "type": "command", "command": "~/.claude/statusline.sh" }"#,
+    },
+    Doc {
+        key: "statusLine.type",
+        value_type: "string",
+        description: "Status line provider type.",
+        default: None,
+        allowed: &["command"],
+        example: r#""command""#,
+    },
+    Doc {
+        key: "fileSuggestion",
+        value_type: "object",
+        description: "Custom command that supplies @-mention file suggestions.",
+        default: None,
+        allowed: &[],
+        example: r#"{ "type": "command", "command": "~/.claude/files.sh" }"#,
+    },
+    Doc {
+        key: "respectGitignore",
+        value_type: "boolean",
+        description: "Hide gitignored files from the @ file picker.",
+        default: Some("true"),
+        allowed: &[],
+        example: "false",
+    },
+    Doc {
+        key: "spinnerTipsEnabled",
+        value_type: "boolean",
+        description: "Show tips next to the spinner while Claude works.",
+        default: Some("true"),
+        allowed: &[],
+        example: "false",
+    },
+    Doc {
+        key: "spinnerVerbs",
+        value_type: "object",
+        description: "Custom verbs shown in the spinner.",
+        default: None,
+        allowed: &[],
+        example: r#"{ "mode": "append", "verbs": ["Pondering"] }"#,
+    },
+    Doc {
+        key: "spinnerVerbs.mode",
+        value_type: "string",
+        description: "Whether custom verbs extend or replace the built-in list.",
+        default: Some(r#""append""#),
+        allowed: &["append", "replace"],
+        example: r#""replace""#,
+    },
+    Doc {
+        key: "spinnerTipsOverride",
+        value_type: "object",
+        description: "Custom spinner tips, optionally replacing the built-in ones.",
+        default: None,
+        allowed: &[],
+        example: r#"{ "excludeDefault": true, "tips": ["Run the linter before pushing."] }"#,
+    },
+    Doc {
+        key: "showTurnDuration",
+        value_type: "boolean",
+        description: "Show how long each turn took.",
+        default: Some("false"),
+        allowed: &[],
+        example: "true",
+    },
+    Doc {
+        key: "alwaysThinkingEnabled",
+        value_type: "boolean",
+        description: "Enable extended thinking by default.",
+        default: Some("false"),
+        allowed: &[],
+        example: "true",
+    },
+    Doc {
+        key: "attribution",
+        value_type: "object",
+        description:
+            "Attribution text appended to commits and pull requests. Empty strings hide it.",
+        default: None,
+        allowed: &[],
+        example: r#"{ "commit": "", "pr": "" }"#,
+    },
+    Doc {
+        key: "companyAnnouncements",
+        value_type: "string[]",
+        description: "Announcements shown at startup; one is picked at random.",
+        default: None,
+        allowed: &[],
+        example: r#"["Read the contributing guide before opening PRs."]"#,
+    },
+    Doc {
+        key: "enabledPlugins",
+        value_type: "object",
+        description: "Plugins to enable or disable, keyed by \"plugin@marketplace\".",
+        default: None,
+        allowed: &[],
+        example: r#"{ "formatter@acme-tools": true }"#,
+    },
+    Doc {
+        key: "cleanupPeriodDays",
+        value_type: "number",
+        description: "Days to keep session transcripts before they are deleted.",
+        default: Some("30"),
+        allowed: &[],
+        example: "90",
+    },
+    Doc {
+        key: "forceLoginMethod",
+        value_type: "string",
+        description: "Restrict login to Claude.ai accounts or Console accounts.",
+        default: None,
+        allowed: &["claudeai", "console"],
+        example: r#""console""#,
+    },
+    Doc {
+        key: "forceLoginOrgUUID",
+        value_type: "string",
+        description: "Organization selected automatically during login.",
+        default: None,
+        allowed: &[],
+        example: r#""xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx""#,
+    },
+];
+
+/// Documentation for one settings key, as returned to the frontend.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingDoc {
+    pub key: String,
+    #[serde(rename = "type")]
+    pub value_type: String,
+    pub description: String,
+    pub default: Option<Value>,
+    pub allowed_values: Vec<String>,
+    pub example: Value,
+    /// First Claude Code release that reads the key, if it is recent.
+    pub min_version: Option<String>,
+}
+
+impl From<&Doc> for SettingDoc {
+    fn from(doc: &Doc) -> Self {
+        let parse = |s: &str| serde_json::from_str(s).unwrap_or(Value::Null);
+        SettingDoc {
+            key: doc.key.to_string(),
+            value_type: doc.value_type.to_string(),
+            description: doc.description.to_string(),
+            default: doc.default.map(parse),
+            allowed_values: doc.allowed.iter().map(|s| s.to_string()).collect(),
+            example: parse(doc.example),
+            min_version: KEY_MIN_VERSIONS
+                .iter()
+                .find(|(k, _)| *k == doc.key)
+                .map(|(_, v)| v.to_string()),
+        }
+    }
+}
+
+/// Returns the docs for `key_path` and every key nested under it, or all
+/// documented keys when `key_path` is empty.
+pub fn lookup(key_path: &str) -> Vec<SettingDoc> {
+    DOCS.iter()
+        .filter(|doc| {
+            key_path.is_empty()
+                || doc.key == key_path
+                || doc
+                    .key
+                    .strip_prefix(key_path)
+                    .is_some_and(|rest| rest.starts_with('.'))
+        })
+        .map(SettingDoc::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::model;
+    use serde_json::{Map, Value};
+
+    /// Wraps `value` in objects so it sits at dot-path `key`.
+    fn nest(key: &str, value: Value) -> Value {
+        key.rsplit('.').fold(value, |inner, k| {
+            Value::Object(Map::from_iter([(k.to_string(), inner)]))
+        })
+    }
+
+    #[test]
+    fn examples_and_defaults_pass_the_validator() {
+        for doc in DOCS {
+            let entry = SettingDoc::from(doc);
+            assert!(!entry.example.is_null(), "{} example is not JSON", doc.key);
+            for value in std::iter::once(entry.example).chain(entry.default) {
+                assert!(
+                    model::parse_settings(&nest(doc.key, value)).is_ok(),
+                    "{} doc does not match the model",
+                    doc.key
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn versioned_keys_are_documented() {
+        for (key, _) in KEY_MIN_VERSIONS {
+            assert!(!lookup(key).is_empty(), "{} is undocumented", key);
+        }
+        let perms = lookup("permissions");
+        assert!(perms.len() > 1 && perms.iter().all(|d| d.key.starts_with("permissions")));
+    }
+}
@@ -0,0 +1,74 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use serde_json::Value;
+
+/// Modification time and size of a settings file, or `None` if it is
+/// missing or unreadable.
+pub type Stamp = Option<(SystemTime, u64)>;
+
+/// Identifies the inputs of one effective-config computation: each
+/// settings path with its stamp.
+pub type CacheKey = Vec<(PathBuf, Stamp)>;
+
+/// The last computed effective config and the inputs it came from.
+static CACHE: Mutex<Option<(CacheKey, Value)>> = Mutex::new(None);
+
+fn stamp(path: &Path) -> Stamp {
+    let meta = fs::metadata(path).ok()?;
+    Some((meta.modified().ok()?, meta.len()))
+}
+
+/// Builds the cache key for the given settings paths.
+pub fn key(paths: &[PathBuf]) -> CacheKey {
+    paths.iter().map(|p| (p.clone(), stamp(p))).collect()
+}
+
+/// Returns the cached effective config if it was computed from exactly the
+/// inputs `key` describes.
+pub fn get(key: &CacheKey) -> Option<Value> {
+    let cache = CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    cache
+        .as_ref()
+        .filter(|(cached, _)| cached == key)
+        .map(|(_, value)| value.clone())
+}
+
+pub fn put(key: CacheKey, value: Value) {
+    *CACHE.lock().unwrap_or_else(|e| e.into_inner()) = Some((key, value));
+}
+
+/// Drops the cached result. Called by the watcher on any settings change,
+/// which catches rewrites the stamps miss (same size within the mtime
+/// resolution).
+pub fn invalidate() {
+    *CACHE.lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn key_changes_with_the_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let paths = [dir.join("settings.json"), dir.join("settings.local.json")];
+        fs::write(&paths[0], "{}").unwrap();
+
+        let before = key(&paths);
+        assert!(before[0].1.is_some() && before[1].1.is_none());
+        put(before.clone(), json!({ "settings": {} }));
+        assert_eq!(get(&before), Some(json!({ "settings": {} })));
+
+        fs::write(&paths[1], r#"{"model":"opus"}"#).unwrap();
+        assert_ne!(key(&paths), before);
+        assert_eq!(get(&key(&paths)), None);
+
+        invalidate();
+        assert_eq!(get(&before), None);
+    }
+}
@@ -0,0 +1,375 @@
+//! Lints for the shell commands of `command` hooks, covering the mistakes
+//! behind most "my hook does nothing" reports.
+
+use serde::Serialize;
+
+/// Events whose hooks can block with exit code 2.
+const BLOCKING_EVENTS: &[&str] = &[
+    "PreToolUse",
+    "PermissionRequest",
+    "UserPromptSubmit",
+    "Stop",
+    "SubagentStop",
+    "TeammateIdle",
+    "TaskCompleted",
+];
+
+/// Events whose input describes a tool call.
+const TOOL_EVENTS: &[&str] = &[
+    "PreToolUse",
+    "PostToolUse",
+    "PostToolUseFailure",
+    "PermissionRequest",
+];
+
+/// Input fields only tool events provide.
+const TOOL_FIELDS: &[&str] = &[".tool_input", ".tool_name", ".tool_response"];
+
+/// One problem found in a hook command.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HookCommandLint {
+    pub code: &'static str,
+    pub message: String,
+}
+
+/// How a simple command is joined to the one before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Join {
+    Start,
+    Pipe,
+    Other,
+}
+
+/// A simple command: its words with quotes removed.
+#[derive(Debug)]
+struct Simple {
+    join: Join,
+    words: Vec<String>,
+}
+
+/// What the scanner learns about a command line.
+#[derive(Debug, Default)]
+struct Parsed {
+    commands: Vec<Simple>,
+    /// `CLAUDE_*` variables expanded outside double quotes.
+    unquoted: Vec<String>,
+    /// Redirects to stderr (`>&2`).
+    writes_stderr: bool,
+}
+
+impl Parsed {
+    fn word(&mut self, word: &mut String, join: &mut Join) {
+        if word.is_empty() {
+            return;
+        }
+        let text = std::mem::take(word);
+        match self.commands.last_mut() {
+            Some(simple) if *join == Join::Other && !simple.words.is_empty() => {
+                simple.words.push(text)
+            }
+            _ => {
+                self.commands.push(Simple {
+                    join: if *join == Join::Other {
+                        Join::Start
+                    } else {
+                        *join
+                    },
+                    words: vec![text],
+                });
+                *join = Join::Other;
+            }
+        }
+    }
+
+    fn separator(&mut self, word: &mut String, join: &mut Join, next: Join) {
+        self.word(word, join);
+        *join = next;
+    }
+}
+
+/// Name of the variable `$` starts at `chars[i]`, if it is a `CLAUDE_*` one.
+fn claude_variable(chars: &[char], i: usize) -> Option<String> {
+    let start = if chars.get(i + 1) == Some(&'{') {
+        i + 2
+    } else {
+        i + 1
+    };
+    let name: String = chars[start.min(chars.len())..]
+        .iter()
+        .take_while(|c| c.is_ascii_alphanumeric() || **c == '_')
+        .collect();
+    name.starts_with("CLAUDE_").then_some(name)
+}
+
+/// Splits a command line into simple commands the way a POSIX shell would,
+/// closely enough for linting: quotes, `|`, `||`, `&&`, `;`, `&`, newlines,
+/// and `$(...)` are understood; heredocs and functions are not.
+fn parse(command: &str) -> Parsed {
+    let chars: Vec<char> = command.chars().collect();
+    let mut parsed = Parsed::default();
+    let mut word = String::new();
+    let mut join = Join::Start;
+    let mut quote: Option<char> = None;
+    // Quote state outside each open `$(`.
+    let mut outer: Vec<Option<char>> = Vec::new();
+
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        match (quote, c) {
+            (Some('\''), '\'') | (Some('"'), '"') => quote = None,
+            (Some('\''), _) => word.push(c),
+            (_, '\\') => {
+                if let Some(n) = next {
+                    word.push(n);
+                    i += 1;
+                }
+            }
+            (_, '$') if next == Some('(') => {
+                outer.push(quote);
+                quote = None;
+                parsed.separator(&mut word, &mut join, Join::Start);
+                i += 1;
+            }
+            (None, ')') if !outer.is_empty() => {
+                parsed.separator(&mut word, &mut join, Join::Other);
+                quote = outer.pop().flatten();
+            }
+            (_, '$') => {
+                if let Some(name) = claude_variable(&chars, i) {
+                    if quote.is_none() && !parsed.unquoted.contains(&name) {
+                        parsed.unquoted.push(name);
+                    }
+                }
+                word.push(c);
+            }
+            (Some('"'), _) => word.push(c),
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '>') if next == Some('&') && chars.get(i + 2) == Some(&'2') => {
+                parsed.writes_stderr = true;
+                parsed.word(&mut word, &mut join);
+                i += 2;
+            }
+            (None, '|') if next == Some('|') => {
+                parsed.separator(&mut word, &mut join, Join::Start);
+                i += 1;
+            }
+            (None, '|') => parsed.separator(&mut word, &mut join, Join::Pipe),
+            (None, ';' | '&' | '\n' | '(' | ')') => {
+                parsed.separator(&mut word, &mut join, Join::Start);
+                if c == '&' && next == Some('&') {
+                    i += 1;
+                }
+            }
+            (None, c) if c.is_whitespace() => parsed.word(&mut word, &mut join),
+            _ => word.push(c),
+        }
+        i += 1;
+    }
+    parsed.word(&mut word, &mut join);
+    parsed
+}
+
+/// Reserved words that can precede a command.
+const RESERVED: &[&str] = &[
+    "!", "{", "}", "if", "then", "else", "elif", "fi", "do", "done", "while", "until",
+];
+
+impl Simple {
+    /// The words other than redirections and their targets, and whether
+    /// stdin is redirected.
+    fn split_redirects(&self) -> (Vec<&str>, bool) {
+        let mut words = Vec::new();
+        let mut stdin_redirected = false;
+        let mut iter = self.words.iter();
+        while let Some(word) = iter.next() {
+            let end = word
+                .find(|c: char| !(c.is_ascii_digit() || matches!(c, '<' | '>' | '&')))
+                .unwrap_or(word.len());
+            let op = &word[..end];
+            if !op.contains(['<', '>']) {
+                words.push(word.as_str());
+                continue;
+            }
+            stdin_redirected |= op
+                .trim_start_matches(|c: char| c.is_ascii_digit())
+                .starts_with('<');
+            if end == word.len() && op.ends_with(['<', '>']) {
+                // The target is the next word.
+                iter.next();
+            }
+        }
+        (words, stdin_redirected)
+    }
+
+    /// The program run and its arguments, after any leading `NAME=value`
+    /// assignments and reserved words.
+    fn program(&self) -> Option<(&str, Vec<&str>)> {
+        let (words, _) = self.split_redirects();
+        let start = words
+            .iter()
+            .position(|w| !RESERVED.contains(w) && (!w.contains('=') || w.starts_with('=')))?;
+        Some((words[start], words[start + 1..].to_vec()))
+    }
+
+    /// Whether this command reads the hook input from stdin rather than a
+    /// pipe or a file.
+    fn reads_stdin(&self) -> bool {
+        if self.join == Join::Pipe || self.split_redirects().1 {
+            return false;
+        }
+        let Some((program, args)) = self.program() else {
+            return false;
+        };
+        let operands = args.iter().filter(|a| !a.starts_with('-')).count();
+        match program {
+            "read" => true,
+            "cat" => operands == 0 || args.contains(&"-"),
+            // The first operand is the filter.
+            "jq" => operands <= 1,
+            _ => false,
+        }
+    }
+}
+
+/// Lints a hook command. `event` is the hook event it is registered for,
+/// when known; `has_program` reports whether a program is installed.
+///
+/// Flags `$CLAUDE_*` variables outside double quotes (paths with spaces
+/// split into several words), `jq` when it is not installed, exit codes
+/// other than 0 and 2 and stderr messages without `exit 2` for events that
+/// can block, hook input read from stdin twice (the second reader gets
+/// nothing), and tool fields read in events that do not provide them.
+pub fn lint_command(
+    command: &str,
+    event: Option<&str>,
+    has_program: impl Fn(&str) -> bool,
+) -> Vec<HookCommandLint> {
+    let parsed = parse(command);
+    let mut lints = Vec::new();
+    let mut push = |code, message| lints.push(HookCommandLint { code, message });
+
+    for name in &parsed.unquoted {
+        push(
+            "UNQUOTED_VARIABLE",
+            format!(
+                "${} is not in double quotes, so a path with spaces splits into several words; write \"${}\"",
+                name, name
+            ),
+        );
+    }
+
+    let programs: Vec<(&str, Vec<&str>)> =
+        parsed.commands.iter().filter_map(Simple::program).collect();
+    if programs.iter().any(|(p, _)| *p == "jq") && !has_program("jq") {
+        push(
+            "JQ_NOT_INSTALLED",
+            "The command uses jq, which is not installed, so the hook input cannot be parsed"
+                .to_string(),
+        );
+    }
+
+    if let Some(event) = event {
+        if BLOCKING_EVENTS.contains(&event) {
+            let exits: Vec<&str> = programs
+                .iter()
+                .filter(|(p, _)| *p == "exit")
+                .filter_map(|(_, args)| args.first().copied())
+                .collect();
+            for code in exits.iter().filter(|c| !matches!(**c, "0" | "2")) {
+                push(
+                    "NON_BLOCKING_EXIT",
+                    format!(
+                        "exit {} is a non-blocking error that only shows stderr to the user; use exit 2 to block {}",
+                        code, event
+                    ),
+                );
+            }
+            if parsed.writes_stderr && !exits.contains(&"2") {
+                push(
+                    "MISSING_EXIT_CODE",
+                    format!(
+                        "The command writes to stderr but never exits 2, so {} is not blocked and Claude does not see the message",
+                        event
+                    ),
+                );
+            }
+        }
+
+        if !TOOL_EVENTS.contains(&event) {
+            for field in TOOL_FIELDS.iter().filter(|f| command.contains(**f)) {
+                push(
+                    "FIELD_NOT_PROVIDED",
+                    format!(
+                        "{} hooks receive no {} in their input; it is only set for tool events",
+                        event,
+                        field.trim_start_matches('.')
+                    ),
+                );
+            }
+        }
+    }
+
+    if parsed.commands.iter().filter(|c| c.reads_stdin()).count() > 1 {
+        push(
+            "STDIN_READ_TWICE",
+            "The hook input is read from stdin more than once; only the first reader gets it. Save it first, e.g. input=$(cat)"
+                .to_string(),
+        );
+    }
+
+    lints
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes(command: &str, event: Option<&str>) -> Vec<&'static str> {
+        lint_command(command, event, |_| true)
+            .into_iter()
+            .map(|l| l.code)
+            .collect()
+    }
+
+    #[test]
+    fn lints_hook_commands() {
+        assert!(codes(
+            r#""$CLAUDE_PROJECT_DIR"/.claude/hooks/check.sh"#,
+            Some("PreToolUse")
+        )
+        .is_empty());
+        assert_eq!(
+            codes("$CLAUDE_PROJECT_DIR/.claude/hooks/check.sh", None),
+            vec!["UNQUOTED_VARIABLE"]
+        );
+        assert_eq!(
+            codes("echo '$CLAUDE_PROJECT_DIR'", None),
+            Vec::<&str>::new()
+        );
+        assert_eq!(
+            lint_command("jq -r .prompt", None, |_| false)[0].code,
+            "JQ_NOT_INSTALLED"
+        );
+
+        let guard =
+            r#"jq -e '.tool_input.command | test("rm -rf")' && { echo "blocked" >&2; exit 1; }"#;
+        assert_eq!(
+            codes(guard, Some("PreToolUse")),
+            vec!["NON_BLOCKING_EXIT", "MISSING_EXIT_CODE"]
+        );
+        assert!(codes(&guard.replace("exit 1", "exit 2"), Some("PreToolUse")).is_empty());
+        assert_eq!(
+            codes("jq -r .tool_name >> log.txt", Some("Stop")),
+            vec!["FIELD_NOT_PROVIDED"]
+        );
+
+        assert_eq!(
+            codes("jq -r .session_id; jq -r .cwd", None),
+            vec!["STDIN_READ_TWICE"]
+        );
+        assert!(codes(r#"input=$(cat); echo "$input" | jq -r .cwd"#, None).is_empty());
+    }
+}
@@ -0,0 +1,354 @@
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// Returns `(path, command)` for every `command` hook in a settings object,
+/// where `path` points at the command string, e.g.
+/// `hooks.PreToolUse[0].hooks[1].command`.
+pub fn command_hooks(settings: &Value) -> Vec<(String, String)> {
+    let mut found = Vec::new();
+    let Some(events) = settings.get("hooks").and_then(|h| h.as_object()) else {
+        return found;
+    };
+
+    for (event, groups) in events {
+        for (g, group) in groups.as_array().into_iter().flatten().enumerate() {
+            let hooks = group.get("hooks").and_then(|h| h.as_array());
+            for (h, hook) in hooks.into_iter().flatten().enumerate() {
+                if let Some(command) = hook.get("command").and_then(|c| c.as_str()) {
+                    found.push((
+                        format!("hooks.{}[{}].hooks[{}].command", event, g, h),
+                        command.to_string(),
+                    ));
+                }
+            }
+        }
+    }
+
+    found
+}
+
+/// Returns the first shell word of `command` with quotes removed.
+fn first_word(command: &str) -> String {
+    let mut word = String::new();
+    let mut quote: Option<char> = None;
+
+    for c in command.trim_start().chars() {
+        match (c, quote) {
+            ('"' | '\'', None) => quote = Some(c),
+            (c, Some(q)) if c == q => quote = None,
+            (c, None) if c.is_whitespace() => break,
+            _ => word.push(c),
+        }
+    }
+    word
+}
+
+/// Resolves the script file a hook command runs, if its first word is a
+/// path rather than a program looked up on PATH.
+///
+/// `$CLAUDE_PROJECT_DIR` (or `${CLAUDE_PROJECT_DIR}`) and a leading `~` are
+/// expanded; relative paths are taken from the project root, which is the
+/// working directory hooks run in.
+pub fn script_path(command: &str, project_root: Option<&Path>, home: &Path) -> Option<PathBuf> {
+    let word = first_word(command);
+    if !word.contains('/') && !word.contains('\\') {
+        return None;
+    }
+
+    let mut expanded = word;
+    for var in ["${CLAUDE_PROJECT_DIR}", "$CLAUDE_PROJECT_DIR"] {
+        if expanded.contains(var) {
+            let root = project_root?.to_string_lossy().to_string();
+            expanded = expanded.replace(var, &root);
+        }
+    }
+    if expanded.contains('$') {
+        // Other variables depend on the hook's runtime environment.
+        return None;
+    }
+
+    let path = match expanded.strip_prefix("~/") {
+        Some(rest) => home.join(rest),
+        None => PathBuf::from(&expanded),
+    };
+    if path.is_absolute() {
+        Some(path)
+    } else {
+        project_root.map(|root| root.join(path))
+    }
+}
+
+/// Problems with a hook script. `mode` is the Unix permission bits; when it
+/// is `None` (Windows) only the content is checked.
+pub fn check_script(content: &str, mode: Option<u32>) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if content.trim().is_empty() {
+        warnings.push("The script is empty".to_string());
+    }
+    let Some(mode) = mode else {
+        return warnings;
+    };
+    if !content.starts_with("#!") {
+        warnings.push("Missing a shebang line such as #!/bin/sh".to_string());
+    }
+    if mode & 0o111 == 0 {
+        warnings.push("The script is not executable, so the hook fails to start".to_string());
+    }
+    warnings
+}
+
+/// One hook as Claude Code runs it. Unlike the settings merge, which lets a
+/// higher scope's hook list for an event replace a lower one's, Claude Code
+/// runs the hook groups of every scope.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComposedHook {
+    pub scope: String,
+    pub event: String,
+    /// The group's matcher; `None` matches every tool.
+    pub matcher: Option<String>,
+    /// Location in the scope's settings, e.g. `hooks.Stop[0].hooks[1]`.
+    pub path: String,
+    /// The hook object (`type`, `command` or `prompt`, `timeout`, ...).
+    pub hook: Value,
+    /// False when `disableAllHooks` or managed `allowManagedHooksOnly`
+    /// keeps the hook from running.
+    pub runs: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HookFindingKind {
+    /// The same hook is registered more than once for a matcher and runs
+    /// each time.
+    Duplicate,
+    /// Several scopes register different hooks for the same matcher. All of
+    /// them run, which is easy to mistake for one overriding the other.
+    Conflict,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HookFinding {
+    pub kind: HookFindingKind,
+    pub event: String,
+    pub matcher: Option<String>,
+    /// Locations involved, as `scope:path`.
+    pub locations: Vec<String>,
+    pub message: String,
+}
+
+/// Every scope's hooks composed the way Claude Code runs them.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectiveHooks {
+    /// Hooks in the order they were registered, lowest precedence first.
+    pub hooks: Vec<ComposedHook>,
+    pub findings: Vec<HookFinding>,
+    /// Why no hook or only managed hooks run, if so.
+    pub restriction: Option<String>,
+}
+
+/// Matchers that match every tool are compared as one.
+fn normalize_matcher(matcher: Option<&str>) -> Option<String> {
+    match matcher {
+        None | Some("") | Some("*") => None,
+        Some(m) => Some(m.to_string()),
+    }
+}
+
+/// Composes the hooks of `scopes`, given as `(name, settings)` pairs in
+/// precedence order (user, project, local, managed).
+pub fn compose_hooks(scopes: &[(&str, Option<&Value>)]) -> EffectiveHooks {
+    // `disableAllHooks` follows normal precedence: the highest scope that
+    // sets it decides.
+    let disabled = scopes
+        .iter()
+        .rev()
+        .find_map(|(_, s)| s.and_then(|s| s.get("disableAllHooks")))
+        .and_then(Value::as_bool)
+        == Some(true);
+    let managed_only = scopes
+        .iter()
+        .find(|(name, _)| *name == "managed")
+        .and_then(|(_, s)| s.and_then(|s| s.get("allowManagedHooksOnly")))
+        .and_then(Value::as_bool)
+        == Some(true);
+    let restriction = if disabled {
+        Some("disableAllHooks is set, so no hook runs".to_string())
+    } else if managed_only {
+        Some("Managed settings set allowManagedHooksOnly, so only managed hooks run".to_string())
+    } else {
+        None
+    };
+
+    let mut hooks = Vec::new();
+    for (scope, settings) in scopes {
+        let events = settings
+            .and_then(|s| s.get("hooks"))
+            .and_then(Value::as_object);
+        for (event, groups) in events.into_iter().flatten() {
+            for (g, group) in groups.as_array().into_iter().flatten().enumerate() {
+                let matcher = group.get("matcher").and_then(Value::as_str);
+                let entries = group.get("hooks").and_then(Value::as_array);
+                for (h, hook) in entries.into_iter().flatten().enumerate() {
+                    hooks.push(ComposedHook {
+                        scope: scope.to_string(),
+                        event: event.clone(),
+                        matcher: normalize_matcher(matcher),
+                        path: format!("hooks.{}[{}].hooks[{}]", event, g, h),
+                        hook: hook.clone(),
+                        runs: !disabled && (!managed_only || *scope == "managed"),
+                    });
+                }
+            }
+        }
+    }
+
+    // Group by event and matcher, in first-seen order.
+    let mut groups: Vec<Vec<&ComposedHook>> = Vec::new();
+    for hook in &hooks {
+        let group = groups
+            .iter_mut()
+            .find(|g| g[0].event == hook.event && g[0].matcher == hook.matcher);
+        match group {
+            Some(members) => members.push(hook),
+            None => groups.push(vec![hook]),
+        }
+    }
+
+    let location = |h: &ComposedHook| format!("{}:{}", h.scope, h.path);
+    let describe = |matcher: &Option<String>| match matcher {
+        Some(m) => format!("matcher '{}'", m),
+        None => "all tools".to_string(),
+    };
+    let mut findings = Vec::new();
+    for members in &groups {
+        let (event, matcher) = (&members[0].event, &members[0].matcher);
+        let mut seen: Vec<&Value> = Vec::new();
+        for (i, hook) in members.iter().enumerate() {
+            if seen.contains(&&hook.hook) {
+                continue;
+            }
+            seen.push(&hook.hook);
+            let copies: Vec<_> = members[i..]
+                .iter()
+                .filter(|other| other.hook == hook.hook)
+                .collect();
+            if copies.len() > 1 {
+                findings.push(HookFinding {
+                    kind: HookFindingKind::Duplicate,
+                    event: event.to_string(),
+                    matcher: matcher.clone(),
+                    locations: copies.iter().map(|h| location(h)).collect(),
+                    message: format!(
+                        "The same {} hook for {} is registered {} times and runs each time",
+                        event,
+                        describe(matcher),
+                        copies.len()
+                    ),
+                });
+            }
+        }
+
+        let mut scopes_seen: Vec<&str> = members.iter().map(|h| h.scope.as_str()).collect();
+        scopes_seen.dedup();
+        if scopes_seen.len() > 1 && seen.len() > 1 {
+            findings.push(HookFinding {
+                kind: HookFindingKind::Conflict,
+                event: event.to_string(),
+                matcher: matcher.clone(),
+                locations: members.iter().map(|h| location(h)).collect(),
+                message: format!(
+                    "{} define different {} hooks for {}; all of them run",
+                    scopes_seen.join(", "),
+                    event,
+                    describe(matcher)
+                ),
+            });
+        }
+    }
+
+    EffectiveHooks {
+        hooks,
+        findings,
+        restriction,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn finds_hook_scripts() {
+        let settings = json!({ "hooks": { "Stop": [{ "hooks": [
+            { "type": "command", "command": "\"$CLAUDE_PROJECT_DIR\"/.claude/hooks/fmt.sh --all" },
+            { "type": "command", "command": "npx prettier --write" },
+            { "type": "prompt", "prompt": "check" }
+        ] }] } });
+        let hooks = command_hooks(&settings);
+        assert_eq!(hooks.len(), 2);
+        assert_eq!(hooks[0].0, "hooks.Stop[0].hooks[0].command");
+
+        let root = Path::new("/work/app");
+        let home = Path::new("/home/me");
+        assert_eq!(
+            script_path(&hooks[0].1, Some(root), home),
+            Some(PathBuf::from("/work/app/.claude/hooks/fmt.sh"))
+        );
+        assert_eq!(script_path(&hooks[1].1, Some(root), home), None);
+        assert_eq!(
+            script_path("~/bin/notify.sh", None, home),
+            Some(PathBuf::from("/home/me/bin/notify.sh"))
+        );
+    }
+
+    #[test]
+    fn checks_hook_scripts() {
+        assert!(check_script("#!/bin/sh\nexit 0\n", Some(0o755)).is_empty());
+        assert_eq!(check_script("exit 0\n", Some(0o644)).len(), 2);
+        assert!(check_script("exit 0\n", None).is_empty());
+    }
+
+    #[test]
+    fn composes_hooks_from_every_scope() {
+        let fmt = json!({ "type": "command", "command": "fmt.sh" });
+        let user = json!({ "hooks": {
+            "PostToolUse": [{ "matcher": "Edit", "hooks": [fmt, { "type": "command", "command": "lint.sh" }] }],
+            "Stop": [{ "hooks": [{ "type": "command", "command": "notify" }] }]
+        } });
+        let project = json!({ "hooks": {
+            "PostToolUse": [{ "matcher": "Edit", "hooks": [fmt] }],
+            "Stop": [{ "matcher": "*", "hooks": [{ "type": "command", "command": "notify" }] }]
+        } });
+
+        let composed = compose_hooks(&[("user", Some(&user)), ("project", Some(&project))]);
+        assert_eq!(composed.hooks.len(), 5);
+        assert!(composed.hooks.iter().all(|h| h.runs));
+        assert_eq!(composed.hooks[3].scope, "project");
+        assert_eq!(composed.hooks[3].path, "hooks.PostToolUse[0].hooks[0]");
+
+        let kinds: Vec<_> = composed
+            .findings
+            .iter()
+            .map(|f| (f.kind, f.event.as_str(), f.locations.len()))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                (HookFindingKind::Duplicate, "PostToolUse", 2),
+                (HookFindingKind::Conflict, "PostToolUse", 3),
+                (HookFindingKind::Duplicate, "Stop", 2),
+            ]
+        );
+
+        let managed = json!({ "allowManagedHooksOnly": true });
+        let composed = compose_hooks(&[("user", Some(&user)), ("managed", Some(&managed))]);
+        assert!(composed.restriction.is_some());
+        assert!(composed.hooks.iter().all(|h| !h.runs));
+    }
+}
@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use regex::Regex;
+
+use crate::types::{ValidationError, ValidationWarning};
+
+/// Files above this many characters get a size warning. Claude Code warns
+/// about large memory files at the same threshold.
+pub const MAX_MARKDOWN_CHARS: usize = 40_000;
+
+/// Which kind of markdown file is being linted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkdownKind {
+    Memory,
+    Agent,
+    Skill,
+}
+
+impl MarkdownKind {
+    pub fn parse(kind: &str) -> Option<Self> {
+        match kind {
+            "memory" => Some(Self::Memory),
+            "agent" => Some(Self::Agent),
+            "skill" => Some(Self::Skill),
+            _ => None,
+        }
+    }
+
+    /// Frontmatter keys the file must declare.
+    fn required_keys(self) -> &'static [&'static str] {
+        match self {
+            Self::Memory => &[],
+            Self::Agent => &["name", "description"],
+            Self::Skill => &["description", "allowed-tools"],
+        }
+    }
+}
+
+/// Diagnostics from linting one markdown file.
+#[derive(Debug, Default)]
+pub struct LintReport {
+    pub errors: Vec<ValidationError>,
+    pub warnings: Vec<ValidationWarning>,
+}
+
+impl LintReport {
+    pub fn error(&mut self, path: String, message: String, code: &str) {
+        self.errors.push(ValidationError {
+            path,
+            message,
+            code: code.to_string(),
+        });
+    }
+
+    pub fn warning(&mut self, path: String, message: String, code: &str) {
+        self.warnings.push(ValidationWarning {
+            path,
+            message,
+            code: code.to_string(),
+        });
+    }
+}
+
+/// Lints a markdown file.
+///
+/// `frontmatter` is the already-parsed frontmatter object and `body` the text
+/// after it. Relative links are only checked when `base_dir` (the directory
+/// the file lives in) is known.
+pub fn lint(
+    kind: MarkdownKind,
+    content: &str,
+    frontmatter: &serde_json::Value,
+    body: &str,
+    base_dir: Option<&Path>,
+) -> LintReport {
+    let mut report = LintReport::default();
+
+    if let Some(pos) = content
+        .chars()
+        .position(|c| c.is_control() && !matches!(c, '\n' | '\r' | '\t'))
+    {
+        report.error(
+            String::new(),
+            format!(
+                "Content contains binary or control characters (first at character {})",
+                pos
+            ),
+            "BINARY_CONTENT",
+        );
+    }
+
+    let chars = content.chars().count();
+    if chars > MAX_MARKDOWN_CHARS {
+        report.warning(
+            String::new(),
+            format!(
+                "File is {} characters; files over {} use a large share of the context window",
+                chars, MAX_MARKDOWN_CHARS
+            ),
+            "FILE_TOO_LONG",
+        );
+    }
+
+    for key in kind.required_keys() {
+        let present = frontmatter
+            .get(key)
+            .is_some_and(|v| !v.is_null() && v.as_str().is_none_or(|s| !s.trim().is_empty()));
+        if !present {
+            report.error(
+                format!("frontmatter.{}", key),
+                format!("Missing required frontmatter key '{}'", key),
+                "MISSING_FIELD",
+            );
+        }
+    }
+
+    // Line numbers in the body are offset by the frontmatter block.
+    let offset = content
+        .strip_suffix(body)
+        .map(|head| head.lines().count())
+        .unwrap_or(0);
+    lint_body(&mut report, body, offset, base_dir);
+
+    report
+}
+
+fn lint_body(report: &mut LintReport, body: &str, offset: usize, base_dir: Option<&Path>) {
+    let link_re = Regex::new(r#"\[[^\]]*\]\(\s*<?([^)\s>]+)>?(?:\s+"[^"]*")?\s*\)"#).unwrap();
+    let mut headings: HashMap<(usize, String), usize> = HashMap::new();
+    let mut in_fence = false;
+
+    for (idx, line) in body.lines().enumerate() {
+        let line_no = offset + idx + 1;
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+
+        let level = trimmed.chars().take_while(|c| *c == '#').count();
+        if (1..=6).contains(&level) && trimmed[level..].starts_with(' ') {
+            let text = trimmed[level..]
+                .trim()
+                .trim_end_matches('#')
+                .trim()
+                .to_lowercase();
+            if let Some(first) = headings.insert((level, text.clone()), line_no) {
+                report.warning(
+                    format!("line {}", line_no),
+                    format!("Duplicate heading '{}' (first on line {})", text, first),
+                    "DUPLICATE_HEADING",
+                );
+            }
+        }
+
+        let Some(base_dir) = base_dir else {
+            continue;
+        };
+        for caps in link_re.captures_iter(line) {
+            let target = &caps[1];
+            if target.starts_with('#') || target.contains("://") || target.starts_with("mailto:") {
+                continue;
+            }
+            let file = target.split('#').next().unwrap_or(target);
+            if !base_dir.join(file).exists() {
+                report.warning(
+                    format!("line {}", line_no),
+                    format!("Broken relative link '{}'", target),
+                    "BROKEN_LINK",
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn reports_missing_keys_and_binary_content() {
+        let report = lint(
+            MarkdownKind::Agent,
+            "hello\u{0}",
+            &json!({ "name": "reviewer", "description": "  " }),
+            "hello\u{0}",
+            None,
+        );
+        let codes: Vec<_> = report.errors.iter().map(|e| e.code.as_str()).collect();
+        assert_eq!(codes, vec!["BINARY_CONTENT", "MISSING_FIELD"]);
+        assert_eq!(report.errors[1].path, "frontmatter.description");
+    }
+
+    #[test]
+    fn warns_on_duplicate_headings_and_broken_links() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let body = "# Setup\n[ok](.)\n```\n# Setup\n```\n## Setup\n# setup\n[gone](no-such-file.md#x)\n[web](https://x.y)";
+        let content = format!("---\nname: a\n---\n{}", body);
+        let report = lint(MarkdownKind::Memory, &content, &json!({}), body, Some(dir));
+
+        assert!(report.errors.is_empty());
+        let warnings: Vec<_> = report
+            .warnings
+            .iter()
+            .map(|w| (w.code.as_str(), w.path.as_str()))
+            .collect();
+        assert_eq!(
+            warnings,
+            vec![("DUPLICATE_HEADING", "line 10"), ("BROKEN_LINK", "line 11")]
+        );
+    }
+}
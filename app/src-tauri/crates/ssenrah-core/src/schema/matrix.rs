@@ -0,0 +1,141 @@
+use std::collections::BTreeSet;
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::schema::merge::{EffectiveConfig, MergePolicy, MergeRule};
+use crate::schema::occurrences::{lookup, source_of};
+
+/// One settings key across every scope. A scope that does not set the key
+/// leaves its column out; a `null` column is a scope deleting the key.
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatrixRow {
+    /// Dot-separated path of the key.
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub local: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub managed: Option<Value>,
+    /// Value in the merged result; absent if the key was deleted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effective: Option<Value>,
+    /// Scope that provides the effective value.
+    pub source: Option<String>,
+    /// How the scopes' values for this key were combined.
+    pub rule: MergeRule,
+}
+
+/// Collects the row paths of `obj`. Objects the policy deep-merges are
+/// descended into, since their keys merge one by one; any other value is a
+/// single row.
+fn collect_paths(
+    obj: &Map<String, Value>,
+    prefix: &str,
+    policy: &MergePolicy,
+    out: &mut BTreeSet<String>,
+) {
+    for (key, value) in obj {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match value.as_object() {
+            Some(child) if policy.rule(&path) == MergeRule::DeepMerge => {
+                collect_paths(child, &path, policy, out)
+            }
+            _ => {
+                out.insert(path);
+            }
+        }
+    }
+}
+
+/// Lays the four scopes and their merge result side by side, one row per key
+/// set in any scope, sorted by path.
+pub fn compute_scope_matrix(
+    policy: &MergePolicy,
+    user: Option<&Value>,
+    project: Option<&Value>,
+    local: Option<&Value>,
+    managed: Option<&Value>,
+    effective: &EffectiveConfig,
+) -> Vec<MatrixRow> {
+    let mut paths = BTreeSet::new();
+    for scope in [user, project, local, managed].into_iter().flatten() {
+        if let Some(obj) = scope.as_object() {
+            collect_paths(obj, "", policy, &mut paths);
+        }
+    }
+
+    let at = |scope: Option<&Value>, path: &str| scope.and_then(|s| lookup(s, path)).cloned();
+    paths
+        .into_iter()
+        .map(|path| MatrixRow {
+            user: at(user, &path),
+            project: at(project, &path),
+            local: at(local, &path),
+            managed: at(managed, &path),
+            effective: at(Some(&effective.settings), &path),
+            source: source_of(effective, &path),
+            rule: policy.rule(&path),
+            path,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::merge::compute_effective;
+    use serde_json::json;
+
+    #[test]
+    fn rows_cover_every_scope_and_rule() {
+        let user = json!({
+            "model": "a",
+            "env": { "A": "1" },
+            "permissions": { "allow": ["Read"] }
+        });
+        let project = json!({ "model": "b", "env": { "B": "2" } });
+        let local = json!({ "env": { "A": null } });
+        let managed = json!({ "permissions": { "allow": ["Bash"] } });
+
+        let policy = MergePolicy::default();
+        let effective =
+            compute_effective(Some(&user), Some(&project), Some(&local), Some(&managed));
+        let rows = compute_scope_matrix(
+            &policy,
+            Some(&user),
+            Some(&project),
+            Some(&local),
+            Some(&managed),
+            &effective,
+        );
+
+        let paths: Vec<_> = rows.iter().map(|r| r.path.as_str()).collect();
+        assert_eq!(paths, ["env.A", "env.B", "model", "permissions.allow"]);
+
+        let env_a = &rows[0];
+        assert_eq!(env_a.user, Some(json!("1")));
+        assert_eq!(env_a.local, Some(Value::Null));
+        assert_eq!(env_a.effective, None);
+        assert_eq!(env_a.source.as_deref(), Some("local"));
+        assert_eq!(env_a.rule, MergeRule::Replace);
+
+        let model = &rows[2];
+        assert_eq!(model.project, Some(json!("b")));
+        assert_eq!(model.local, None);
+        assert_eq!(model.effective, Some(json!("b")));
+        assert_eq!(model.source.as_deref(), Some("project"));
+
+        let allow = &rows[3];
+        assert_eq!(allow.effective, Some(json!(["Read", "Bash"])));
+        assert_eq!(allow.rule, MergeRule::ArrayUnion);
+    }
+}
@@ -0,0 +1,556 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use super::model::{MergeFields, Settings};
+
+/// Scopes from lowest to highest precedence. Managed settings come last so
+/// no user, project, or local file can override them.
+const PRECEDENCE: &[&str] = &["user", "project", "local", "managed"];
+
+/// How a field's values from several scopes combine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeRule {
+    /// The highest scope's value wins.
+    Replace,
+    /// Like `Replace`, listed separately because the field is an array.
+    ArrayReplace,
+    /// Array entries from every scope are combined.
+    ArrayUnion,
+    /// Object keys are merged recursively.
+    DeepMerge,
+}
+
+/// The rule for one dot-separated settings path.
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldPolicy {
+    pub path: String,
+    pub rule: MergeRule,
+}
+
+/// Scope precedence and per-field merge rules used by `compute_effective`.
+/// Fields without an entry use `MergeRule::Replace`. The default rules come
+/// from the typed model (see [`MergeFields`]).
+#[derive(Debug, Clone, Serialize)]
+pub struct MergePolicy {
+    /// Scope names from lowest to highest precedence.
+    pub precedence: Vec<String>,
+    pub fields: Vec<FieldPolicy>,
+}
+
+/// Per-field rules declared by `Settings`, built once.
+fn default_fields() -> &'static [FieldPolicy] {
+    static FIELDS: OnceLock<Vec<FieldPolicy>> = OnceLock::new();
+    FIELDS.get_or_init(|| {
+        let mut fields = Vec::new();
+        Settings::merge_fields("", &mut fields);
+        fields
+    })
+}
+
+/// The default rule for `path`, as declared by the typed model.
+fn default_rule(path: &str) -> MergeRule {
+    default_fields()
+        .iter()
+        .find(|f| f.path == path)
+        .map_or(MergeRule::Replace, |f| f.rule)
+}
+
+impl Default for MergePolicy {
+    fn default() -> Self {
+        MergePolicy {
+            precedence: PRECEDENCE.iter().map(|s| s.to_string()).collect(),
+            fields: default_fields().to_vec(),
+        }
+    }
+}
+
+impl MergePolicy {
+    /// The rule for `path`.
+    pub fn rule(&self, path: &str) -> MergeRule {
+        self.fields
+            .iter()
+            .find(|f| f.path == path)
+            .map_or(MergeRule::Replace, |f| f.rule)
+    }
+}
+
+/// The merged effective configuration along with source attribution and
+/// override information.
+#[derive(Debug, Serialize)]
+pub struct EffectiveConfig {
+    /// The final merged settings object.
+    pub settings: Value,
+    /// Maps each JSON path (dot-separated) to the scope name that provides it.
+    pub sources: HashMap<String, String>,
+    /// List of fields where a higher scope overrode a lower scope's value.
+    pub overrides: Vec<Override>,
+    /// Arrays set by more than one scope whose entries were combined, such
+    /// as `permissions.allow`.
+    pub combined: Vec<CombinedArray>,
+}
+
+/// Describes a single override: a field whose value was set by multiple scopes,
+/// with the highest-precedence scope winning.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Override {
+    /// Dot-separated JSON path of the field.
+    pub path: String,
+    /// The scope that ultimately provides the value.
+    pub effective_scope: String,
+    /// The scopes whose values were overridden (lower precedence).
+    pub overridden_scopes: Vec<String>,
+    /// The winning value.
+    pub effective_value: Value,
+    /// For arrays, entries of the winning value the overridden value lacked.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub added_elements: Option<Vec<Value>>,
+    /// For arrays, entries of the overridden value the winning value dropped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub removed_elements: Option<Vec<Value>>,
+}
+
+/// An array field whose entries several scopes contributed to.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CombinedArray {
+    /// Dot-separated JSON path of the field.
+    pub path: String,
+    /// One step per scope that set the field, lowest precedence first.
+    pub steps: Vec<ArrayStep>,
+    /// The combined array.
+    pub effective_value: Value,
+}
+
+/// What one scope changed in a combined array.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArrayStep {
+    pub scope: String,
+    /// Entries not already contributed by lower scopes.
+    pub added_elements: Vec<Value>,
+    /// Entries cleared by a `null` (or non-array) value in this scope.
+    pub removed_elements: Vec<Value>,
+}
+
+/// Entries of `a` that are not in `b`, in `a`'s order.
+fn elements_missing_from(a: &[Value], b: &[Value]) -> Vec<Value> {
+    a.iter().filter(|v| !b.contains(v)).cloned().collect()
+}
+
+/// Replays each scope's value of a combined array, recording what it added
+/// or cleared.
+fn combine_steps(path: &str, entries: &[(String, Value)]) -> CombinedArray {
+    let mut acc: Vec<Value> = Vec::new();
+    let mut steps = Vec::new();
+    for (scope, value) in entries {
+        let (added, removed) = match value.as_array() {
+            Some(items) => {
+                let mut added = Vec::new();
+                for item in items {
+                    if !acc.contains(item) && !added.contains(item) {
+                        added.push(item.clone());
+                    }
+                }
+                acc.extend(added.iter().cloned());
+                (added, Vec::new())
+            }
+            None => (Vec::new(), std::mem::take(&mut acc)),
+        };
+        steps.push(ArrayStep {
+            scope: scope.clone(),
+            added_elements: added,
+            removed_elements: removed,
+        });
+    }
+    CombinedArray {
+        path: path.to_string(),
+        steps,
+        effective_value: Value::Array(acc),
+    }
+}
+
+/// Returns `true` if the given dot-path should use deep-merge semantics.
+pub fn is_deep_merge_field(path: &str) -> bool {
+    default_rule(path) == MergeRule::DeepMerge
+}
+
+/// Returns `true` if the given dot-path holds an array whose entries are
+/// combined across scopes.
+pub fn is_array_union_field(path: &str) -> bool {
+    default_rule(path) == MergeRule::ArrayUnion
+}
+
+/// Computes the effective (merged) configuration from up to four scopes
+/// using the default `MergePolicy`.
+pub fn compute_effective(
+    user: Option<&Value>,
+    project: Option<&Value>,
+    local: Option<&Value>,
+    managed: Option<&Value>,
+) -> EffectiveConfig {
+    compute_effective_with(
+        &MergePolicy::default(),
+        &[
+            ("user", user),
+            ("project", project),
+            ("local", local),
+            ("managed", managed),
+        ],
+    )
+}
+
+/// Computes the effective configuration from named scopes.
+///
+/// Scopes are applied in `policy.precedence` order, lowest first; scopes the
+/// policy does not list are ignored. Each successive scope's values overlay
+/// the accumulated result according to the field's `MergeRule`.
+pub fn compute_effective_with(
+    policy: &MergePolicy,
+    scopes: &[(&str, Option<&Value>)],
+) -> EffectiveConfig {
+    let mut result = Map::new();
+    let mut sources: HashMap<String, String> = HashMap::new();
+    let mut all_paths: HashMap<String, Vec<(String, Value)>> = HashMap::new();
+
+    for scope_name in &policy.precedence {
+        let data = scopes
+            .iter()
+            .find(|(name, _)| name == scope_name)
+            .and_then(|(_, data)| *data);
+        if let Some(obj) = data.and_then(Value::as_object) {
+            merge_object(
+                &mut result,
+                obj,
+                scope_name,
+                "",
+                policy,
+                &mut sources,
+                &mut all_paths,
+            );
+        }
+    }
+
+    // Compute overrides: any path touched by more than one scope. Combined
+    // arrays are not overrides since every scope's entries survive; they are
+    // reported step by step instead.
+    let mut overrides = Vec::new();
+    let mut combined = Vec::new();
+    for (path, entries) in all_paths.iter().filter(|(_, e)| e.len() > 1) {
+        if policy.rule(path) == MergeRule::ArrayUnion {
+            combined.push(combine_steps(path, entries));
+            continue;
+        }
+        let (effective, lower) = entries.split_last().unwrap();
+        let overridden: Vec<String> = lower.iter().map(|(s, _)| s.clone()).collect();
+        let previous = &lower.last().unwrap().1;
+        let (added_elements, removed_elements) = match (effective.1.as_array(), previous.as_array())
+        {
+            (Some(new), Some(old)) => (
+                Some(elements_missing_from(new, old)),
+                Some(elements_missing_from(old, new)),
+            ),
+            _ => (None, None),
+        };
+        overrides.push(Override {
+            path: path.clone(),
+            effective_scope: effective.0.clone(),
+            overridden_scopes: overridden,
+            effective_value: effective.1.clone(),
+            added_elements,
+            removed_elements,
+        });
+    }
+
+    // Sort by path for deterministic output.
+    overrides.sort_by(|a, b| a.path.cmp(&b.path));
+    combined.sort_by(|a, b| a.path.cmp(&b.path));
+
+    EffectiveConfig {
+        settings: Value::Object(result),
+        sources,
+        overrides,
+        combined,
+    }
+}
+
+/// Recursively merges `source` into `result`, tracking source attribution and
+/// all path contributions for override detection.
+fn merge_object(
+    result: &mut Map<String, Value>,
+    source: &Map<String, Value>,
+    scope_name: &str,
+    prefix: &str,
+    policy: &MergePolicy,
+    sources: &mut HashMap<String, String>,
+    all_paths: &mut HashMap<String, Vec<(String, Value)>>,
+) {
+    for (key, value) in source {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+
+        // null in a higher scope removes the field (acts as delete).
+        if value.is_null() {
+            result.remove(key);
+            sources.insert(path.clone(), scope_name.to_string());
+            all_paths
+                .entry(path)
+                .or_default()
+                .push((scope_name.to_string(), value.clone()));
+            continue;
+        }
+
+        let rule = policy.rule(&path);
+
+        if let (MergeRule::DeepMerge, Some(obj)) = (rule, value.as_object()) {
+            // Deep merge: recursively merge keys from source into the existing
+            // object. The first scope to set the object is merged into an empty
+            // one too, so each key is attributed and later overrides detected.
+            let mut merged = match result.get(key) {
+                Some(Value::Object(existing)) => existing.clone(),
+                _ => Map::new(),
+            };
+            merge_object(
+                &mut merged,
+                obj,
+                scope_name,
+                &path,
+                policy,
+                sources,
+                all_paths,
+            );
+            result.insert(key.clone(), Value::Object(merged));
+        } else if let (MergeRule::ArrayUnion, Some(entries), Some(Value::Array(existing))) =
+            (rule, value.as_array(), result.get_mut(key))
+        {
+            for entry in entries {
+                if !existing.contains(entry) {
+                    existing.push(entry.clone());
+                }
+            }
+            sources.insert(path.clone(), scope_name.to_string());
+            all_paths
+                .entry(path)
+                .or_default()
+                .push((scope_name.to_string(), value.clone()));
+        } else {
+            // Replace semantics (scalar, array-replace, or first-time set).
+            result.insert(key.clone(), value.clone());
+            sources.insert(path.clone(), scope_name.to_string());
+            all_paths
+                .entry(path)
+                .or_default()
+                .push((scope_name.to_string(), value.clone()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn scalar_replace() {
+        let user = json!({ "model": "claude-3" });
+        let project = json!({ "model": "claude-4" });
+
+        let result = compute_effective(Some(&user), Some(&project), None, None);
+        assert_eq!(result.settings["model"], "claude-4");
+        assert_eq!(result.sources["model"], "project");
+        assert_eq!(result.overrides.len(), 1);
+        assert_eq!(result.overrides[0].path, "model");
+        assert_eq!(result.overrides[0].effective_scope, "project");
+        assert_eq!(result.overrides[0].overridden_scopes, vec!["user"]);
+    }
+
+    #[test]
+    fn deep_merge_permissions() {
+        let user = json!({
+            "permissions": {
+                "defaultMode": "reviewAll",
+                "allow": ["Read"]
+            }
+        });
+        let project = json!({
+            "permissions": {
+                "allow": ["Write"],
+                "deny": ["Bash"]
+            }
+        });
+
+        let result = compute_effective(Some(&user), Some(&project), None, None);
+        let perms = result.settings["permissions"].as_object().unwrap();
+
+        // defaultMode comes from user (not overridden by project).
+        assert_eq!(perms["defaultMode"], "reviewAll");
+        // allow rules from both scopes are combined.
+        assert_eq!(perms["allow"], json!(["Read", "Write"]));
+        // deny is new from project.
+        assert_eq!(perms["deny"], json!(["Bash"]));
+    }
+
+    #[test]
+    fn null_deletes_field() {
+        let user = json!({ "model": "claude-3", "language": "en" });
+        let project = json!({ "model": Value::Null });
+
+        let result = compute_effective(Some(&user), Some(&project), None, None);
+        assert!(result.settings.get("model").is_none());
+        assert_eq!(result.settings["language"], "en");
+    }
+
+    #[test]
+    fn managed_highest_precedence() {
+        let user = json!({ "model": "a" });
+        let project = json!({ "model": "b" });
+        let local = json!({ "model": "c" });
+        let managed = json!({ "model": "d" });
+
+        let result = compute_effective(
+            Some(&user),
+            Some(&project),
+            Some(&local),
+            Some(&managed),
+        );
+        assert_eq!(result.settings["model"], "d");
+        assert_eq!(result.sources["model"], "managed");
+    }
+
+    #[test]
+    fn env_deep_merge_with_null_delete() {
+        let user = json!({
+            "env": { "PATH": "/usr/bin", "HOME": "/home/user" }
+        });
+        let project = json!({
+            "env": { "PATH": "/project/bin", "EDITOR": "vim" }
+        });
+        let local = json!({
+            "env": { "HOME": Value::Null }
+        });
+
+        let result = compute_effective(Some(&user), Some(&project), Some(&local), None);
+        let env = result.settings["env"].as_object().unwrap();
+
+        assert_eq!(env["PATH"], "/project/bin");
+        assert_eq!(env["EDITOR"], "vim");
+        assert!(env.get("HOME").is_none());
+    }
+
+    #[test]
+    fn empty_scopes_produce_empty_config() {
+        let result = compute_effective(None, None, None, None);
+        assert_eq!(result.settings, json!({}));
+        assert!(result.sources.is_empty());
+        assert!(result.overrides.is_empty());
+    }
+
+    #[test]
+    fn hooks_deep_merge_at_event_level() {
+        let user = json!({
+            "hooks": {
+                "PreToolUse": [{ "matcher": "*", "hooks": [{ "type": "command", "command": "echo user" }] }]
+            }
+        });
+        let project = json!({
+            "hooks": {
+                "PostToolUse": [{ "matcher": "*", "hooks": [{ "type": "command", "command": "echo project" }] }]
+            }
+        });
+
+        let result = compute_effective(Some(&user), Some(&project), None, None);
+        let hooks = result.settings["hooks"].as_object().unwrap();
+
+        // Both event keys should be present (deep merge at hooks level).
+        assert!(hooks.contains_key("PreToolUse"));
+        assert!(hooks.contains_key("PostToolUse"));
+    }
+
+    #[test]
+    fn hooks_array_replace_at_group_level() {
+        let user = json!({
+            "hooks": {
+                "PreToolUse": [{ "matcher": "*", "hooks": [{ "type": "command", "command": "echo user" }] }]
+            }
+        });
+        let project = json!({
+            "hooks": {
+                "PreToolUse": [{ "matcher": "Bash", "hooks": [{ "type": "command", "command": "echo project" }] }]
+            }
+        });
+
+        let result = compute_effective(Some(&user), Some(&project), None, None);
+        let hooks = result.settings["hooks"].as_object().unwrap();
+        let pre_tool = hooks["PreToolUse"].as_array().unwrap();
+
+        // hooks.PreToolUse is not in DEEP_MERGE_FIELDS, so project replaces user's array.
+        assert_eq!(pre_tool.len(), 1);
+        assert_eq!(pre_tool[0]["matcher"], "Bash");
+    }
+
+    #[test]
+    fn managed_deny_rules_survive_lower_scopes() {
+        let user = json!({ "permissions": { "deny": ["WebFetch"], "allow": ["Bash"] } });
+        let local = json!({ "permissions": { "deny": ["Read(.env)", "WebFetch"] } });
+        let managed = json!({ "permissions": { "deny": ["Bash"] } });
+
+        let result = compute_effective(Some(&user), None, Some(&local), Some(&managed));
+        let perms = &result.settings["permissions"];
+        assert_eq!(perms["deny"], json!(["WebFetch", "Read(.env)", "Bash"]));
+        assert_eq!(perms["allow"], json!(["Bash"]));
+        assert_eq!(result.sources["permissions.deny"], "managed");
+        assert!(result.overrides.is_empty());
+
+        let deny = &result.combined[0];
+        assert_eq!(deny.path, "permissions.deny");
+        let added: Vec<_> = deny
+            .steps
+            .iter()
+            .map(|s| (s.scope.as_str(), s.added_elements.clone()))
+            .collect();
+        assert_eq!(
+            added,
+            [
+                ("user", vec![json!("WebFetch")]),
+                ("local", vec![json!("Read(.env)")]),
+                ("managed", vec![json!("Bash")]),
+            ]
+        );
+    }
+
+    #[test]
+    fn replaced_arrays_report_added_and_removed_elements() {
+        let user = json!({ "availableModels": ["opus", "sonnet"], "model": "opus" });
+        let project = json!({ "availableModels": ["sonnet", "haiku"], "model": "haiku" });
+
+        let result = compute_effective(Some(&user), Some(&project), None, None);
+        let models = &result.overrides[0];
+        assert_eq!(models.path, "availableModels");
+        assert_eq!(models.added_elements, Some(vec![json!("haiku")]));
+        assert_eq!(models.removed_elements, Some(vec![json!("opus")]));
+        assert_eq!(result.overrides[1].added_elements, None);
+    }
+
+    #[test]
+    fn precedence_comes_from_the_policy() {
+        let project = json!({ "model": "p" });
+        let local = json!({ "model": "l" });
+        let policy = MergePolicy {
+            precedence: ["user", "local", "project", "managed"]
+                .map(String::from)
+                .to_vec(),
+            ..MergePolicy::default()
+        };
+
+        let scopes = [("project", Some(&project)), ("local", Some(&local))];
+        let result = compute_effective_with(&policy, &scopes);
+        assert_eq!(result.settings["model"], "p");
+        assert_eq!(result.overrides[0].overridden_scopes, vec!["local"]);
+    }
+}
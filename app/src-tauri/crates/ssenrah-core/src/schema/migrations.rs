@@ -0,0 +1,184 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// A settings key Claude Code no longer reads, and how to rewrite it.
+pub struct Migration {
+    /// Deprecated top-level key.
+    pub from: &'static str,
+    /// Dot-path of the key that replaces it.
+    pub to: &'static str,
+    pub note: &'static str,
+    /// Converts the old value; `None` means the key can simply be dropped
+    /// because its value matches the new default.
+    convert: fn(&Value) -> Option<Value>,
+}
+
+/// Deprecated settings keys, in the order they are migrated.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        from: "allowedTools",
+        to: "permissions.allow",
+        note: "Tool allow lists moved under permissions.",
+        convert: |v| Some(v.clone()),
+    },
+    Migration {
+        from: "ignorePatterns",
+        to: "permissions.deny",
+        note: "Ignore patterns became Read deny rules.",
+        convert: ignore_patterns_to_rules,
+    },
+    Migration {
+        from: "includeCoAuthoredBy",
+        to: "attribution",
+        note: "Commit and PR attribution text replaced the co-author toggle.",
+        convert: co_authored_by_to_attribution,
+    },
+];
+
+fn ignore_patterns_to_rules(value: &Value) -> Option<Value> {
+    let patterns = value.as_array()?;
+    Some(Value::Array(
+        patterns
+            .iter()
+            .filter_map(|p| p.as_str())
+            .map(|p| Value::String(format!("Read({})", p)))
+            .collect(),
+    ))
+}
+
+fn co_authored_by_to_attribution(value: &Value) -> Option<Value> {
+    // `true` was the default, which attribution keeps when unset.
+    (value == &Value::Bool(false)).then(|| serde_json::json!({ "commit": "", "pr": "" }))
+}
+
+/// One rewrite reported by `migrate`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationChange {
+    pub from: String,
+    pub to: String,
+    pub old_value: Value,
+    /// Value merged into `to`, or `None` when the old key was just dropped.
+    pub new_value: Option<Value>,
+    /// `false` when `to` already holds a value that can't be combined with
+    /// the converted one; the deprecated key is then left in place.
+    pub applied: bool,
+    pub note: String,
+}
+
+/// Returns the migrations that apply to a settings object.
+pub fn pending(settings: &Value) -> Vec<&'static Migration> {
+    MIGRATIONS
+        .iter()
+        .filter(|m| settings.get(m.from).is_some())
+        .collect()
+}
+
+/// Merges `value` into the object at dot-path `path`, creating parents.
+/// Arrays are unioned and objects gain missing keys; any other existing
+/// value is a conflict and nothing is written.
+fn merge_at(root: &mut Map<String, Value>, path: &str, value: Value) -> bool {
+    let (parents, leaf) = path.rsplit_once('.').unwrap_or(("", path));
+    let mut target = root;
+    for key in parents.split('.').filter(|k| !k.is_empty()) {
+        let entry = target
+            .entry(key.to_string())
+            .or_insert_with(|| Value::Object(Map::new()));
+        let Some(next) = entry.as_object_mut() else {
+            return false;
+        };
+        target = next;
+    }
+
+    match (target.get_mut(leaf), value) {
+        (None, value) => {
+            target.insert(leaf.to_string(), value);
+            true
+        }
+        (Some(Value::Array(existing)), Value::Array(items)) => {
+            for item in items {
+                if !existing.contains(&item) {
+                    existing.push(item);
+                }
+            }
+            true
+        }
+        (Some(Value::Object(existing)), Value::Object(items)) => {
+            for (k, v) in items {
+                existing.entry(k).or_insert(v);
+            }
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Rewrites every deprecated key in `settings` and reports each change.
+pub fn migrate(settings: &mut Value) -> Vec<MigrationChange> {
+    let migrations = pending(settings);
+    let Some(obj) = settings.as_object_mut() else {
+        return Vec::new();
+    };
+
+    migrations
+        .into_iter()
+        .map(|m| {
+            let old_value = obj.get(m.from).cloned().unwrap_or(Value::Null);
+            let new_value = (m.convert)(&old_value);
+            let applied = match &new_value {
+                Some(v) => merge_at(obj, m.to, v.clone()),
+                None => true,
+            };
+            if applied {
+                obj.remove(m.from);
+            }
+            MigrationChange {
+                from: m.from.to_string(),
+                to: m.to.to_string(),
+                old_value,
+                new_value,
+                applied,
+                note: m.note.to_string(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn migrates_deprecated_keys() {
+        let mut settings = json!({
+            "allowedTools": ["Read", "Bash"],
+            "ignorePatterns": ["secrets/**"],
+            "includeCoAuthoredBy": false,
+            "permissions": { "allow": ["Read"] }
+        });
+        let changes = migrate(&mut settings);
+        assert_eq!(changes.len(), 3);
+        assert!(changes.iter().all(|c| c.applied));
+        assert_eq!(
+            settings,
+            json!({
+                "permissions": { "allow": ["Read", "Bash"], "deny": ["Read(secrets/**)"] },
+                "attribution": { "commit": "", "pr": "" }
+            })
+        );
+    }
+
+    #[test]
+    fn keeps_deprecated_key_on_conflict() {
+        let mut settings =
+            json!({ "allowedTools": ["Read"], "permissions": "bad", "includeCoAuthoredBy": true });
+        let changes = migrate(&mut settings);
+        assert!(!changes[0].applied);
+        assert!(changes[1].applied && changes[1].new_value.is_none());
+        assert_eq!(
+            settings,
+            json!({ "allowedTools": ["Read"], "permissions": "bad" })
+        );
+    }
+}
@@ -0,0 +1,23 @@
+pub mod agent;
+pub mod compat;
+pub mod diff;
+pub mod directories;
+pub mod docs;
+pub mod effective_cache;
+pub mod hook_lint;
+pub mod hooks;
+pub mod markdown;
+pub mod matrix;
+pub mod merge;
+pub mod migrations;
+pub mod model;
+pub mod models;
+pub mod occurrences;
+pub mod output_style;
+pub mod plugin;
+pub mod policy;
+pub mod references;
+pub mod sandbox;
+pub mod skill;
+pub mod three_way;
+pub mod tools;
@@ -0,0 +1,419 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::merge::{FieldPolicy, MergeRule};
+use crate::types::ValidationError;
+
+/// Typed model of `settings.json`.
+///
+/// Every struct keeps unrecognized keys in `extra` so newer Claude Code
+/// settings survive a round trip. Enumerated values (`defaultMode`,
+/// `forceLoginMethod`, ...) are kept as plain strings; allowed values are
+/// checked by the validator rather than rejected at deserialization.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Settings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<Permissions>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hooks: Option<Hooks>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable_all_hooks: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_managed_hooks_only: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_managed_mcp_servers_only: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enable_all_project_mcp_servers: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled_mcpjson_servers: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disabled_mcpjson_servers: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_mcp_servers: Option<Vec<McpServerMatcher>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub denied_mcp_servers: Option<Vec<McpServerMatcher>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sandbox: Option<Sandbox>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env: Option<Env>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key_helper: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub otel_headers_helper: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aws_auth_refresh: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aws_credential_export: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub available_models: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_style: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_line: Option<StatusLine>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_suggestion: Option<StatusLine>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub respect_gitignore: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefers_reduced_motion: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spinner_tips_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spinner_tips_override: Option<SpinnerTipsOverride>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spinner_verbs: Option<SpinnerVerbs>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub terminal_progress_bar_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub show_turn_duration: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub always_thinking_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attribution: Option<Attribution>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub company_announcements: Option<Vec<String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled_plugins: Option<BTreeMap<String, bool>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cleanup_period_days: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plans_directory: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub force_login_method: Option<String>,
+    #[serde(rename = "forceLoginOrgUUID", skip_serializing_if = "Option::is_none")]
+    pub force_login_org_uuid: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Permissions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ask: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deny: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub additional_directories: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disable_bypass_permissions_mode: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Hook groups keyed by event name (`PreToolUse`, `Stop`, ...).
+pub type Hooks = BTreeMap<String, Vec<HookGroup>>;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HookGroup {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matcher: Option<String>,
+    pub hooks: Vec<HookDefinition>,
+
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HookDefinition {
+    #[serde(rename = "type")]
+    pub hook_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Matches an MCP server by name, exact stdio command, or remote URL.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpServerMatcher {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_command: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_url: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Sandbox {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_allow_bash_if_sandboxed: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub excluded_commands: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_unsandboxed_commands: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network: Option<SandboxNetwork>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enable_weaker_nested_sandbox: Option<bool>,
+
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SandboxNetwork {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_domains: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_managed_domains_only: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_unix_sockets: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_all_unix_sockets: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_local_binding: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http_proxy_port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub socks_proxy_port: Option<u16>,
+
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Environment variables. A `null` value deletes a variable set by a lower
+/// scope, so values are optional.
+pub type Env = BTreeMap<String, Option<String>>;
+
+/// `statusLine` / `fileSuggestion`: `{ type: "command", command: "..." }`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StatusLine {
+    #[serde(rename = "type")]
+    pub line_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpinnerTipsOverride {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclude_default: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tips: Option<Vec<String>>,
+
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SpinnerVerbs {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verbs: Option<Vec<String>>,
+
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Attribution {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pr: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// Per-field merge rules of a typed settings struct, from which the default
+/// `MergePolicy` is built. Fields not listed use `MergeRule::Replace`.
+pub trait MergeFields {
+    /// Appends the rules for this struct's fields, with paths under `prefix`.
+    fn merge_fields(prefix: &str, fields: &mut Vec<FieldPolicy>);
+}
+
+fn push_rule(fields: &mut Vec<FieldPolicy>, prefix: &str, key: &str, rule: MergeRule) {
+    let path = if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", prefix, key)
+    };
+    fields.push(FieldPolicy { path, rule });
+}
+
+/// Deep-merges `key` and appends the rules of its struct `T`.
+fn push_nested<T: MergeFields>(fields: &mut Vec<FieldPolicy>, prefix: &str, key: &str) {
+    push_rule(fields, prefix, key, MergeRule::DeepMerge);
+    let path = fields.last().map(|f| f.path.clone()).unwrap_or_default();
+    T::merge_fields(&path, fields);
+}
+
+impl MergeFields for Settings {
+    fn merge_fields(prefix: &str, fields: &mut Vec<FieldPolicy>) {
+        push_nested::<Permissions>(fields, prefix, "permissions");
+        push_nested::<Sandbox>(fields, prefix, "sandbox");
+        // Maps and small objects whose keys each scope can set on its own.
+        for key in [
+            "hooks",
+            "env",
+            "attribution",
+            "spinnerTipsOverride",
+            "spinnerVerbs",
+            "statusLine",
+            "fileSuggestion",
+        ] {
+            push_rule(fields, prefix, key, MergeRule::DeepMerge);
+        }
+        for key in [
+            "availableModels",
+            "companyAnnouncements",
+            "enabledMcpjsonServers",
+            "disabledMcpjsonServers",
+            "allowedMcpServers",
+            "deniedMcpServers",
+        ] {
+            push_rule(fields, prefix, key, MergeRule::ArrayReplace);
+        }
+    }
+}
+
+impl MergeFields for Permissions {
+    /// Claude Code combines permission rules across scopes, so a managed
+    /// `deny` rule stays in force whatever other scopes allow.
+    fn merge_fields(prefix: &str, fields: &mut Vec<FieldPolicy>) {
+        for key in ["allow", "deny", "ask", "additionalDirectories"] {
+            push_rule(fields, prefix, key, MergeRule::ArrayUnion);
+        }
+    }
+}
+
+impl MergeFields for Sandbox {
+    fn merge_fields(prefix: &str, fields: &mut Vec<FieldPolicy>) {
+        push_rule(fields, prefix, "excludedCommands", MergeRule::ArrayReplace);
+        push_nested::<SandboxNetwork>(fields, prefix, "network");
+    }
+}
+
+impl MergeFields for SandboxNetwork {
+    fn merge_fields(prefix: &str, fields: &mut Vec<FieldPolicy>) {
+        for key in ["allowedDomains", "allowUnixSockets"] {
+            push_rule(fields, prefix, key, MergeRule::ArrayReplace);
+        }
+    }
+}
+
+/// Deserializes a raw settings value into the typed model.
+///
+/// On failure, returns a `ValidationError` whose `path` is the dot-separated
+/// location of the offending field (e.g. `permissions.allow.2`).
+pub fn parse_settings(value: &Value) -> Result<Settings, ValidationError> {
+    serde_path_to_error::deserialize(value).map_err(|e| {
+        let path = e.path().to_string();
+        ValidationError {
+            path: if path == "." { String::new() } else { path },
+            message: e.into_inner().to_string(),
+            code: "INVALID_TYPE".to_string(),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_known_fields_and_keeps_extras() {
+        let value = json!({
+            "model": "opus",
+            "permissions": { "allow": ["Read"], "futureKey": 1 },
+            "env": { "A": "1", "B": null },
+            "someNewSetting": true
+        });
+
+        let settings = parse_settings(&value).unwrap();
+        assert_eq!(settings.model.as_deref(), Some("opus"));
+        let perms = settings.permissions.as_ref().unwrap();
+        assert_eq!(perms.allow.as_ref().unwrap(), &vec!["Read".to_string()]);
+        assert_eq!(perms.extra["futureKey"], 1);
+        assert_eq!(settings.env.as_ref().unwrap()["B"], None);
+        assert_eq!(settings.extra["someNewSetting"], true);
+
+        // Round trip keeps every key.
+        assert_eq!(serde_json::to_value(&settings).unwrap(), value);
+    }
+
+    #[test]
+    fn merge_rules_name_typed_fields() {
+        let mut fields = Vec::new();
+        Settings::merge_fields("", &mut fields);
+
+        // Give every ruled path a value of the kind its rule expects; each
+        // must land in a typed field, not `extra`.
+        let mut value = json!({});
+        for field in &fields {
+            let sample = match field.rule {
+                MergeRule::DeepMerge => json!({}),
+                _ => json!([]),
+            };
+            let (parents, key) = field.path.rsplit_once('.').unwrap_or(("", &field.path));
+            let mut target = &mut value;
+            for part in parents.split('.').filter(|p| !p.is_empty()) {
+                target = &mut target[part];
+            }
+            if target.get(key).is_none() {
+                target[key] = sample;
+            }
+        }
+        // `type` is required in these.
+        value["statusLine"]["type"] = json!("command");
+        value["fileSuggestion"]["type"] = json!("command");
+
+        let settings = parse_settings(&value).unwrap();
+        assert!(settings.extra.is_empty());
+        assert!(settings.permissions.unwrap().extra.is_empty());
+        let sandbox = settings.sandbox.unwrap();
+        assert!(sandbox.extra.is_empty());
+        assert!(sandbox.network.unwrap().extra.is_empty());
+    }
+
+    #[test]
+    fn reports_path_of_type_error() {
+        let value = json!({ "permissions": { "allow": ["Read", 42] } });
+
+        let err = parse_settings(&value).unwrap_err();
+        assert_eq!(err.path, "permissions.allow[1]");
+        assert_eq!(err.code, "INVALID_TYPE");
+    }
+}
@@ -0,0 +1,210 @@
+//! Models Claude Code can be pointed at, and checks that the model settings
+//! name one of them.
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::schema::markdown::LintReport;
+use crate::schema::occurrences::lookup;
+use crate::similarity::edit_distance;
+
+/// Aliases Claude Code resolves itself, with what they select.
+pub const ALIASES: &[(&str, &str)] = &[
+    ("default", "Recommended model for the account"),
+    ("sonnet", "Latest Sonnet"),
+    ("opus", "Latest Opus"),
+    ("haiku", "Latest Haiku"),
+    ("sonnet[1m]", "Latest Sonnet with a 1M-token context window"),
+    ("opusplan", "Opus in plan mode, Sonnet otherwise"),
+];
+
+/// Full model names known at release time. Newer ones come from the live
+/// models API.
+pub const KNOWN_MODELS: &[&str] = &[
+    "claude-opus-4-1-20250805",
+    "claude-opus-4-20250514",
+    "claude-sonnet-4-5-20250929",
+    "claude-sonnet-4-20250514",
+    "claude-3-7-sonnet-20250219",
+    "claude-haiku-4-5-20251001",
+    "claude-3-5-haiku-20241022",
+];
+
+/// Settings that select a model.
+pub const MODEL_KEYS: &[&str] = &["model", "smallFastModel"];
+
+/// Suffix selecting the extended context window, accepted on any model.
+const LONG_CONTEXT_SUFFIX: &str = "[1m]";
+
+/// One selectable model.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelEntry {
+    pub id: String,
+    /// "availableModels", "alias", "known", or "api".
+    pub source: &'static str,
+    pub description: Option<String>,
+    /// False when `availableModels` is set and does not list this entry.
+    pub allowed: bool,
+}
+
+fn available_models(settings: &Value) -> Option<Vec<&str>> {
+    let list = lookup(settings, "availableModels")?.as_array()?;
+    Some(list.iter().filter_map(Value::as_str).collect())
+}
+
+/// Every model `settings` can select: its `availableModels` first, then the
+/// aliases, the known full names, and `live` entries from the models API,
+/// each listed once.
+pub fn catalog(settings: &Value, live: &[ModelEntry]) -> Vec<ModelEntry> {
+    let available = available_models(settings);
+    let allowed = |id: &str| available.as_ref().is_none_or(|list| list.contains(&id));
+
+    let mut entries: Vec<ModelEntry> = Vec::new();
+    let mut push = |entry: ModelEntry| {
+        if !entries.iter().any(|e| e.id == entry.id) {
+            entries.push(entry);
+        }
+    };
+    for id in available.iter().flatten() {
+        push(ModelEntry {
+            id: id.to_string(),
+            source: "availableModels",
+            description: None,
+            allowed: true,
+        });
+    }
+    for (id, description) in ALIASES {
+        push(ModelEntry {
+            id: id.to_string(),
+            source: "alias",
+            description: Some(description.to_string()),
+            allowed: allowed(id),
+        });
+    }
+    for id in KNOWN_MODELS {
+        push(ModelEntry {
+            id: id.to_string(),
+            source: "known",
+            description: None,
+            allowed: allowed(id),
+        });
+    }
+    for entry in live {
+        push(ModelEntry {
+            allowed: allowed(&entry.id),
+            ..entry.clone()
+        });
+    }
+    entries
+}
+
+/// Bedrock inference profiles and ARNs, and Vertex `name@version` ids,
+/// which depend on the provider account and cannot be checked here.
+fn is_provider_id(name: &str) -> bool {
+    name.starts_with("arn:") || name.contains("anthropic.") || name.contains('@')
+}
+
+/// Whether `name` resolves to an entry of `catalog`: an exact id, an id
+/// with the `[1m]` suffix, or a full name without its date
+/// (`claude-sonnet-4-5`).
+pub fn resolves(name: &str, catalog: &[ModelEntry]) -> bool {
+    let name = name.strip_suffix(LONG_CONTEXT_SUFFIX).unwrap_or(name);
+    let undated = format!("{}-2", name);
+    catalog
+        .iter()
+        .any(|e| e.id == name || e.id.starts_with(&undated))
+}
+
+/// The catalog id closest to `name`, if it is close enough to be a typo.
+pub fn closest<'a>(name: &str, catalog: &'a [ModelEntry]) -> Option<&'a str> {
+    catalog
+        .iter()
+        .map(|e| (edit_distance(&name.to_lowercase(), &e.id), e.id.as_str()))
+        .filter(|(distance, _)| *distance <= 3)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, id)| id)
+}
+
+/// Checks that `model` and `smallFastModel` name a model from
+/// [`catalog`]. An unknown name is a warning, since Claude Code accepts it
+/// and only fails once a request is sent; a name outside the settings' own
+/// `availableModels` is a warning too. Provider-specific ids (Bedrock,
+/// Vertex) are not checked.
+pub fn check_models(settings: &Value, live: &[ModelEntry]) -> LintReport {
+    let mut report = LintReport::default();
+    let catalog = catalog(settings, live);
+    let available = available_models(settings);
+
+    for key in MODEL_KEYS {
+        let Some(name) = lookup(settings, key).and_then(Value::as_str) else {
+            continue;
+        };
+        if is_provider_id(name) {
+            continue;
+        }
+        if !resolves(name, &catalog) {
+            let hint = match closest(name, &catalog) {
+                Some(id) => format!("; did you mean '{}'?", id),
+                None => String::new(),
+            };
+            report.warning(
+                key.to_string(),
+                format!("'{}' is not a known model or alias{}", name, hint),
+                "UNKNOWN_MODEL",
+            );
+        } else if available.as_ref().is_some_and(|list| !list.contains(&name)) {
+            report.warning(
+                key.to_string(),
+                format!("'{}' is not in availableModels", name),
+                "MODEL_NOT_AVAILABLE",
+            );
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn flags_unresolvable_model_names() {
+        let codes = |settings: Value| -> Vec<(String, String)> {
+            check_models(&settings, &[])
+                .warnings
+                .into_iter()
+                .map(|w| (w.path, w.code))
+                .collect()
+        };
+        assert!(
+            codes(json!({ "model": "opus", "smallFastModel": "claude-3-5-haiku-20241022" }))
+                .is_empty()
+        );
+        assert!(codes(json!({ "model": "claude-sonnet-4-5[1m]" })).is_empty());
+        assert!(codes(json!({ "model": "us.anthropic.claude-sonnet-4-20250514-v1:0" })).is_empty());
+
+        let report = check_models(&json!({ "model": "sonet" }), &[]);
+        assert_eq!(report.warnings[0].code, "UNKNOWN_MODEL");
+        assert!(report.warnings[0].message.contains("'sonnet'"));
+
+        assert_eq!(
+            codes(json!({ "availableModels": ["sonnet"], "smallFastModel": "haiku" })),
+            vec![(
+                "smallFastModel".to_string(),
+                "MODEL_NOT_AVAILABLE".to_string()
+            )]
+        );
+
+        let live = [ModelEntry {
+            id: "claude-future-9-20300101".to_string(),
+            source: "api",
+            description: None,
+            allowed: true,
+        }];
+        assert!(check_models(&json!({ "model": "claude-future-9" }), &live)
+            .warnings
+            .is_empty());
+    }
+}
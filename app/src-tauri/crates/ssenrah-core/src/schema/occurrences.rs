@@ -0,0 +1,128 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::schema::merge::{EffectiveConfig, MergePolicy, MergeRule};
+
+/// How the effective merge treats a scope's value for a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OccurrenceStatus {
+    /// This scope's value is the one Claude Code uses.
+    Effective,
+    /// A higher-precedence scope replaced the value.
+    Overridden,
+    /// The value is combined with other scopes': an object deep-merged by
+    /// key, or an array whose entries are added to theirs.
+    Merged,
+}
+
+/// One scope file that sets a key.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingOccurrence {
+    pub scope: String,
+    /// Settings file the value was read from.
+    pub file: String,
+    pub value: Value,
+    pub status: OccurrenceStatus,
+}
+
+/// Looks up a dot-separated key path in a settings object.
+pub fn lookup<'a>(value: &'a Value, key_path: &str) -> Option<&'a Value> {
+    key_path
+        .split('.')
+        .try_fold(value, |current, key| current.as_object()?.get(key))
+}
+
+/// Returns the scope that provides `key_path` in the merged result: the
+/// source of the key itself or of its nearest ancestor that was set whole.
+pub fn source_of(effective: &EffectiveConfig, key_path: &str) -> Option<String> {
+    let mut path = key_path;
+    loop {
+        if let Some(source) = effective.sources.get(path) {
+            return Some(source.clone());
+        }
+        path = &path[..path.rfind('.')?];
+    }
+}
+
+/// Classifies `scope`'s `value` at `key_path` against the merged result.
+///
+/// Deep-merged objects such as `env` and unioned arrays such as
+/// `permissions.allow` that survive the merge are reported as merged.
+/// Otherwise the nearest path (the key itself or an ancestor) in the
+/// merge's source map decides: the value is effective only if that path is
+/// attributed to `scope` and the key is still present, or if `value` is the
+/// `null` that deleted it.
+pub fn classify(
+    effective: &EffectiveConfig,
+    scope: &str,
+    key_path: &str,
+    value: &Value,
+) -> OccurrenceStatus {
+    let present = lookup(&effective.settings, key_path).is_some();
+    let merges = match MergePolicy::default().rule(key_path) {
+        MergeRule::DeepMerge => value.is_object(),
+        MergeRule::ArrayUnion => value.is_array(),
+        _ => false,
+    };
+    if present && merges {
+        return OccurrenceStatus::Merged;
+    }
+
+    let source = source_of(effective, key_path);
+    if source.is_some_and(|s| s == scope) && (present || value.is_null()) {
+        OccurrenceStatus::Effective
+    } else {
+        OccurrenceStatus::Overridden
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::merge::compute_effective;
+    use serde_json::json;
+
+    #[test]
+    fn classifies_each_scope_value() {
+        let user = json!({ "env": { "ANTHROPIC_MODEL": "a", "FOO": "1" }, "model": "x" });
+        let project = json!({ "env": { "ANTHROPIC_MODEL": "b" } });
+        let effective = compute_effective(Some(&user), Some(&project), None, None);
+        let status = |scope: &str, settings: &Value, key: &str| {
+            classify(&effective, scope, key, lookup(settings, key).unwrap())
+        };
+
+        assert_eq!(lookup(&user, "env.BAR"), None);
+        assert_eq!(
+            status("user", &user, "env.ANTHROPIC_MODEL"),
+            OccurrenceStatus::Overridden
+        );
+        assert_eq!(
+            status("project", &project, "env.ANTHROPIC_MODEL"),
+            OccurrenceStatus::Effective
+        );
+        assert_eq!(
+            status("user", &user, "env.FOO"),
+            OccurrenceStatus::Effective
+        );
+        assert_eq!(status("project", &project, "env"), OccurrenceStatus::Merged);
+        assert_eq!(status("user", &user, "model"), OccurrenceStatus::Effective);
+    }
+
+    #[test]
+    fn null_deletion_is_the_effective_occurrence() {
+        let user = json!({ "model": "x" });
+        let local = json!({ "model": null });
+        let effective = compute_effective(Some(&user), None, Some(&local), None);
+
+        assert_eq!(
+            classify(&effective, "user", "model", &json!("x")),
+            OccurrenceStatus::Overridden
+        );
+        assert_eq!(
+            classify(&effective, "local", "model", &Value::Null),
+            OccurrenceStatus::Effective
+        );
+    }
+}
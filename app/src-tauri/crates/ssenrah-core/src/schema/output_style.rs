@@ -0,0 +1,219 @@
+use serde_json::Value;
+
+use crate::schema::markdown::LintReport;
+
+/// Output styles that ship with Claude Code and need no file.
+pub const BUILTIN_STYLES: &[(&str, &str)] = &[
+    (
+        "default",
+        "Claude's standard software engineering behaviour",
+    ),
+    (
+        "Explanatory",
+        "Adds educational insights about implementation choices",
+    ),
+    (
+        "Learning",
+        "Collaborative mode that asks you to write small pieces of code",
+    ),
+];
+
+/// Checks an output style file's frontmatter and body.
+///
+/// `name`, `description`, and `keep-coding-instructions` are optional but
+/// must have the right type; a missing description is a warning since the
+/// `/output-style` menu shows it. The body is the style's system prompt and
+/// must not be empty.
+pub fn validate_output_style(frontmatter: &Value, body: &str) -> LintReport {
+    let mut report = LintReport::default();
+
+    let Some(obj) = frontmatter.as_object() else {
+        report.error(
+            "frontmatter".to_string(),
+            "Frontmatter must be an object".to_string(),
+            "INVALID_TYPE",
+        );
+        return report;
+    };
+
+    for key in ["name", "description"] {
+        if obj.get(key).is_some_and(|v| !v.is_string() && !v.is_null()) {
+            report.error(
+                format!("frontmatter.{}", key),
+                format!("'{}' must be a string", key),
+                "INVALID_TYPE",
+            );
+        }
+    }
+    if obj
+        .get("description")
+        .and_then(Value::as_str)
+        .is_none_or(|d| d.trim().is_empty())
+    {
+        report.warning(
+            "frontmatter.description".to_string(),
+            "Add a description so the style is recognizable in /output-style".to_string(),
+            "MISSING_FIELD",
+        );
+    }
+    if obj
+        .get("keep-coding-instructions")
+        .is_some_and(|v| !v.is_boolean())
+    {
+        report.error(
+            "frontmatter.keep-coding-instructions".to_string(),
+            "'keep-coding-instructions' must be true or false".to_string(),
+            "INVALID_TYPE",
+        );
+    }
+    if let Some(name) = obj.get("name").and_then(Value::as_str) {
+        if BUILTIN_STYLES
+            .iter()
+            .any(|(b, _)| b.eq_ignore_ascii_case(name))
+        {
+            report.warning(
+                "frontmatter.name".to_string(),
+                format!("'{}' shadows a built-in output style", name),
+                "NAME_COLLISION",
+            );
+        }
+    }
+    if body.trim().is_empty() {
+        report.error(
+            "body".to_string(),
+            "The style's instructions are empty".to_string(),
+            "MISSING_FIELD",
+        );
+    }
+
+    report
+}
+
+fn check_strings(report: &mut LintReport, path: &str, value: Option<&Value>) -> usize {
+    let Some(value) = value else {
+        return 0;
+    };
+    let Some(items) = value.as_array() else {
+        report.error(
+            path.to_string(),
+            "Expected a list of strings".to_string(),
+            "INVALID_TYPE",
+        );
+        return 0;
+    };
+    for (i, item) in items.iter().enumerate() {
+        if item.as_str().is_none_or(|s| s.trim().is_empty()) {
+            report.error(
+                format!("{}[{}]", path, i),
+                "Entries must be non-empty strings".to_string(),
+                "INVALID_FORMAT",
+            );
+        }
+    }
+    items.len()
+}
+
+/// Checks the spinner settings (`spinnerTipsEnabled`, `spinnerVerbs`,
+/// `spinnerTipsOverride`) in a settings object.
+pub fn validate_spinner(settings: &Value) -> LintReport {
+    let mut report = LintReport::default();
+
+    if settings
+        .get("spinnerTipsEnabled")
+        .is_some_and(|v| !v.is_boolean())
+    {
+        report.error(
+            "spinnerTipsEnabled".to_string(),
+            "Expected true or false".to_string(),
+            "INVALID_TYPE",
+        );
+    }
+
+    if let Some(verbs) = settings.get("spinnerVerbs") {
+        let mode = verbs.get("mode").and_then(Value::as_str);
+        if verbs.get("mode").is_some() && !matches!(mode, Some("append" | "replace")) {
+            report.error(
+                "spinnerVerbs.mode".to_string(),
+                "Expected \"append\" or \"replace\"".to_string(),
+                "INVALID_VALUE",
+            );
+        }
+        let count = check_strings(&mut report, "spinnerVerbs.verbs", verbs.get("verbs"));
+        if mode == Some("replace") && count == 0 {
+            report.warning(
+                "spinnerVerbs.verbs".to_string(),
+                "Replace mode with no verbs leaves the spinner without text".to_string(),
+                "EMPTY_LIST",
+            );
+        }
+    }
+
+    if let Some(tips) = settings.get("spinnerTipsOverride") {
+        if tips.get("excludeDefault").is_some_and(|v| !v.is_boolean()) {
+            report.error(
+                "spinnerTipsOverride.excludeDefault".to_string(),
+                "Expected true or false".to_string(),
+                "INVALID_TYPE",
+            );
+        }
+        let count = check_strings(&mut report, "spinnerTipsOverride.tips", tips.get("tips"));
+        if tips.get("excludeDefault") == Some(&Value::Bool(true)) && count == 0 {
+            report.warning(
+                "spinnerTipsOverride.tips".to_string(),
+                "Excluding the default tips with no custom tips shows no tips".to_string(),
+                "EMPTY_LIST",
+            );
+        }
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn validates_output_style_files() {
+        let report = validate_output_style(
+            &json!({ "name": "Terse", "description": "Short answers" }),
+            "Answer in one sentence.",
+        );
+        assert!(report.errors.is_empty() && report.warnings.is_empty());
+
+        let report = validate_output_style(
+            &json!({ "name": "learning", "keep-coding-instructions": "yes" }),
+            "  ",
+        );
+        let codes: Vec<&str> = report.errors.iter().map(|e| e.code.as_str()).collect();
+        assert_eq!(codes, ["INVALID_TYPE", "MISSING_FIELD"]);
+        assert_eq!(report.warnings.len(), 2);
+    }
+
+    #[test]
+    fn validates_spinner_settings() {
+        let report = validate_spinner(&json!({
+            "spinnerVerbs": { "mode": "append", "verbs": ["Pondering"] },
+            "spinnerTipsOverride": { "excludeDefault": false, "tips": ["Run tests"] },
+        }));
+        assert!(report.errors.is_empty() && report.warnings.is_empty());
+
+        let report = validate_spinner(&json!({
+            "spinnerTipsEnabled": "no",
+            "spinnerVerbs": { "mode": "prepend", "verbs": ["", 3] },
+            "spinnerTipsOverride": { "excludeDefault": true },
+        }));
+        let paths: Vec<&str> = report.errors.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "spinnerTipsEnabled",
+                "spinnerVerbs.mode",
+                "spinnerVerbs.verbs[0]",
+                "spinnerVerbs.verbs[1]",
+            ]
+        );
+        assert_eq!(report.warnings[0].path, "spinnerTipsOverride.tips");
+    }
+}
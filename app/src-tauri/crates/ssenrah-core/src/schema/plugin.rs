@@ -0,0 +1,167 @@
+use regex::Regex;
+use serde_json::Value;
+
+use crate::schema::markdown::LintReport;
+
+/// Manifest keys that point at component files: a `./`-relative path or a
+/// list of them.
+const PATH_LIST_KEYS: &[&str] = &["commands", "agents"];
+
+/// Manifest keys that hold either a `./`-relative path or an inline object.
+const PATH_OR_OBJECT_KEYS: &[&str] = &["hooks", "mcpServers"];
+
+/// Checks a plugin manifest (`.claude-plugin/plugin.json`).
+///
+/// `name` is required and must be kebab-case; `version` should be semver;
+/// component paths must be relative to the plugin root and start with `./`,
+/// and `hooks` / `mcpServers` may also be inline objects.
+pub fn validate_manifest(manifest: &Value) -> LintReport {
+    let mut report = LintReport::default();
+
+    let Some(obj) = manifest.as_object() else {
+        report.error(
+            "manifest".to_string(),
+            "Plugin manifest must be a JSON object".to_string(),
+            "INVALID_TYPE",
+        );
+        return report;
+    };
+
+    match obj.get("name").and_then(Value::as_str) {
+        Some(name) => {
+            let kebab = Regex::new(r"^[a-z0-9]+(-[a-z0-9]+)*$").unwrap();
+            if !kebab.is_match(name) {
+                report.error(
+                    "name".to_string(),
+                    format!("Plugin name '{}' must be kebab-case", name),
+                    "INVALID_FORMAT",
+                );
+            }
+        }
+        None => report.error(
+            "name".to_string(),
+            "Missing required field 'name'".to_string(),
+            "MISSING_FIELD",
+        ),
+    }
+
+    if let Some(version) = obj.get("version") {
+        let semver = Regex::new(r"^\d+\.\d+\.\d+([-+][0-9A-Za-z.+-]+)?$").unwrap();
+        if !version.as_str().is_some_and(|v| semver.is_match(v)) {
+            report.warning(
+                "version".to_string(),
+                "Version should be a semantic version such as 1.0.0".to_string(),
+                "INVALID_FORMAT",
+            );
+        }
+    }
+
+    for key in ["description", "homepage", "repository", "license"] {
+        if obj.get(key).is_some_and(|v| !v.is_string()) {
+            report.error(
+                key.to_string(),
+                format!("'{}' must be a string", key),
+                "INVALID_TYPE",
+            );
+        }
+    }
+    if obj
+        .get("author")
+        .is_some_and(|a| a.get("name").and_then(Value::as_str).is_none())
+    {
+        report.error(
+            "author".to_string(),
+            "'author' must be an object with a 'name'".to_string(),
+            "INVALID_TYPE",
+        );
+    }
+
+    for key in PATH_LIST_KEYS {
+        let paths: Vec<&Value> = match obj.get(*key) {
+            None => continue,
+            Some(Value::Array(items)) => items.iter().collect(),
+            Some(other) => vec![other],
+        };
+        for (i, path) in paths.into_iter().enumerate() {
+            check_path(&mut report, &format!("{}[{}]", key, i), path);
+        }
+    }
+    for key in PATH_OR_OBJECT_KEYS {
+        match obj.get(*key) {
+            None | Some(Value::Object(_)) => {}
+            Some(path) => check_path(&mut report, key, path),
+        }
+    }
+
+    report
+}
+
+fn check_path(report: &mut LintReport, field: &str, path: &Value) {
+    match path.as_str() {
+        Some(p) if p.starts_with("./") && !p.split('/').any(|c| c == "..") => {}
+        Some(p) => report.error(
+            field.to_string(),
+            format!(
+                "Path '{}' must be relative to the plugin root and start with './'",
+                p
+            ),
+            "INVALID_PATH",
+        ),
+        None => report.error(
+            field.to_string(),
+            "Expected a path string".to_string(),
+            "INVALID_TYPE",
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn accepts_valid_manifest() {
+        let report = validate_manifest(&json!({
+            "name": "review-tools",
+            "version": "1.2.0",
+            "description": "Code review helpers",
+            "author": { "name": "Dev" },
+            "commands": ["./extra/review.md"],
+            "hooks": "./config/hooks.json",
+            "mcpServers": { "github": { "command": "gh-mcp" } },
+        }));
+        assert!(report.errors.is_empty());
+        assert!(report.warnings.is_empty());
+    }
+
+    #[test]
+    fn reports_manifest_problems() {
+        let report = validate_manifest(&json!({
+            "name": "Review Tools",
+            "version": "latest",
+            "author": "Dev",
+            "agents": "../agents",
+            "hooks": 3,
+        }));
+        let codes: Vec<(&str, &str)> = report
+            .errors
+            .iter()
+            .map(|e| (e.path.as_str(), e.code.as_str()))
+            .collect();
+        assert_eq!(
+            codes,
+            vec![
+                ("name", "INVALID_FORMAT"),
+                ("author", "INVALID_TYPE"),
+                ("agents[0]", "INVALID_PATH"),
+                ("hooks", "INVALID_TYPE"),
+            ]
+        );
+        assert_eq!(report.warnings.len(), 1);
+        assert_eq!(
+            validate_manifest(&json!({})).errors[0].code,
+            "MISSING_FIELD"
+        );
+    }
+}
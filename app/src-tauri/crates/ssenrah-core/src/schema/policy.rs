@@ -0,0 +1,303 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::schema::merge;
+use crate::schema::occurrences::lookup;
+
+/// Why managed policy defeats a locally-set key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyStatus {
+    /// Managed settings set the same key, so the local value never applies.
+    Overridden,
+    /// A managed-only switch makes Claude Code ignore this key outside
+    /// managed settings.
+    Ignored,
+    /// The local value is disallowed by a managed restriction.
+    Forbidden,
+}
+
+/// One locally-set key that managed policy overrides or forbids.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyFinding {
+    /// "user", "project", or "local".
+    pub scope: String,
+    /// Dot-separated key path.
+    pub path: String,
+    pub local_value: Value,
+    /// The managed value or restriction responsible.
+    pub policy_value: Value,
+    /// Managed key that causes the finding.
+    pub policy_key: String,
+    pub status: PolicyStatus,
+    pub message: String,
+}
+
+/// Permission rule lists a managed `allowManagedPermissionRulesOnly` ignores.
+const PERMISSION_RULE_LISTS: &[&str] =
+    &["permissions.allow", "permissions.ask", "permissions.deny"];
+
+fn strings(value: Option<&Value>) -> Vec<&str> {
+    value
+        .and_then(|v| v.as_array())
+        .map(|a| a.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default()
+}
+
+/// `serverName` entries of a managed MCP allow/deny list.
+fn server_names(value: Option<&Value>) -> Vec<&str> {
+    value
+        .and_then(|v| v.as_array())
+        .map(|a| {
+            a.iter()
+                .filter_map(|e| e.get("serverName").and_then(|n| n.as_str()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Compares each local scope against managed settings and lists every key
+/// that managed policy overrides or forbids.
+///
+/// `scopes` are `(name, settings)` pairs in precedence order (user, project,
+/// local). Overrides come from the effective-config merge; restrictions
+/// (`allowManagedHooksOnly`, `allowManagedPermissionRulesOnly`,
+/// `disableBypassPermissionsMode`, managed deny rules, `availableModels`,
+/// and the MCP server allow/deny lists) are checked per scope. Findings are
+/// ordered by scope, then path.
+pub fn check_compliance(managed: &Value, scopes: &[(&str, Option<&Value>)]) -> Vec<PolicyFinding> {
+    let mut findings = Vec::new();
+    let scope_value = |name: &str| {
+        scopes
+            .iter()
+            .find(|(n, _)| *n == name)
+            .and_then(|(_, v)| *v)
+    };
+
+    let effective = merge::compute_effective(
+        scope_value("user"),
+        scope_value("project"),
+        scope_value("local"),
+        Some(managed),
+    );
+    for o in effective
+        .overrides
+        .iter()
+        .filter(|o| o.effective_scope == "managed")
+    {
+        for scope in &o.overridden_scopes {
+            let Some(local_value) = scope_value(scope).and_then(|v| lookup(v, &o.path)) else {
+                continue;
+            };
+            findings.push(PolicyFinding {
+                scope: scope.clone(),
+                path: o.path.clone(),
+                local_value: local_value.clone(),
+                policy_value: o.effective_value.clone(),
+                policy_key: o.path.clone(),
+                status: PolicyStatus::Overridden,
+                message: format!("Managed settings set {}, replacing this value", o.path),
+            });
+        }
+    }
+
+    let enabled = |key: &str| lookup(managed, key).and_then(|v| v.as_bool()) == Some(true);
+    let managed_deny = strings(lookup(managed, "permissions.deny"));
+    let available_models = lookup(managed, "availableModels").and_then(|v| v.as_array());
+    let allowed_servers = lookup(managed, "allowedMcpServers").map(|v| server_names(Some(v)));
+    let denied_servers = server_names(lookup(managed, "deniedMcpServers"));
+
+    for (scope, settings) in scopes {
+        let Some(settings) = settings else {
+            continue;
+        };
+        let mut push = |path: &str, local: &Value, policy_key: &str, status, message: String| {
+            let reported = findings
+                .iter()
+                .any(|f| f.scope == *scope && f.path == path && f.local_value == *local);
+            if !reported {
+                findings.push(PolicyFinding {
+                    scope: scope.to_string(),
+                    path: path.to_string(),
+                    local_value: local.clone(),
+                    policy_value: lookup(managed, policy_key).cloned().unwrap_or(Value::Null),
+                    policy_key: policy_key.to_string(),
+                    status,
+                    message,
+                });
+            }
+        };
+
+        if enabled("allowManagedHooksOnly") {
+            if let Some(hooks) =
+                lookup(settings, "hooks").filter(|h| h.as_object().is_some_and(|o| !o.is_empty()))
+            {
+                push(
+                    "hooks",
+                    hooks,
+                    "allowManagedHooksOnly",
+                    PolicyStatus::Ignored,
+                    "Only hooks from managed settings run".to_string(),
+                );
+            }
+        }
+
+        if enabled("allowManagedPermissionRulesOnly") {
+            for list in PERMISSION_RULE_LISTS {
+                if let Some(rules) =
+                    lookup(settings, list).filter(|r| r.as_array().is_some_and(|a| !a.is_empty()))
+                {
+                    push(
+                        list,
+                        rules,
+                        "allowManagedPermissionRulesOnly",
+                        PolicyStatus::Ignored,
+                        "Only permission rules from managed settings apply".to_string(),
+                    );
+                }
+            }
+        }
+
+        if lookup(managed, "permissions.disableBypassPermissionsMode").and_then(|v| v.as_str())
+            == Some("disable")
+        {
+            if let Some(mode) = lookup(settings, "permissions.defaultMode")
+                .filter(|m| m.as_str() == Some("bypassPermissions"))
+            {
+                push(
+                    "permissions.defaultMode",
+                    mode,
+                    "permissions.disableBypassPermissionsMode",
+                    PolicyStatus::Forbidden,
+                    "Managed settings disable bypassPermissions mode".to_string(),
+                );
+            }
+        }
+
+        for rule in strings(lookup(settings, "permissions.allow")) {
+            if managed_deny.contains(&rule) {
+                push(
+                    "permissions.allow",
+                    &Value::String(rule.to_string()),
+                    "permissions.deny",
+                    PolicyStatus::Forbidden,
+                    format!("Managed settings deny {}", rule),
+                );
+            }
+        }
+
+        if let (Some(models), Some(model)) = (available_models, lookup(settings, "model")) {
+            if !models.contains(model) {
+                push(
+                    "model",
+                    model,
+                    "availableModels",
+                    PolicyStatus::Forbidden,
+                    "Model is not in the managed availableModels list".to_string(),
+                );
+            }
+        }
+
+        for server in strings(lookup(settings, "enabledMcpjsonServers")) {
+            let (policy_key, message) = if denied_servers.contains(&server) {
+                (
+                    "deniedMcpServers",
+                    format!("MCP server '{}' is denied by managed settings", server),
+                )
+            } else if allowed_servers
+                .as_ref()
+                .is_some_and(|a| !a.contains(&server))
+            {
+                (
+                    "allowedMcpServers",
+                    format!("MCP server '{}' is not in the managed allowlist", server),
+                )
+            } else {
+                continue;
+            };
+            push(
+                "enabledMcpjsonServers",
+                &Value::String(server.to_string()),
+                policy_key,
+                PolicyStatus::Forbidden,
+                message,
+            );
+        }
+    }
+
+    let rank = |scope: &str| scopes.iter().position(|(n, _)| *n == scope);
+    findings.sort_by(|a, b| {
+        rank(&a.scope)
+            .cmp(&rank(&b.scope))
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn statuses(findings: &[PolicyFinding]) -> Vec<(&str, &str, PolicyStatus)> {
+        findings
+            .iter()
+            .map(|f| (f.scope.as_str(), f.path.as_str(), f.status))
+            .collect()
+    }
+
+    #[test]
+    fn reports_overrides_and_restrictions() {
+        let managed = json!({
+            "model": "sonnet",
+            "allowManagedHooksOnly": true,
+            "permissions": { "deny": ["Bash(curl:*)"], "disableBypassPermissionsMode": "disable" },
+            "deniedMcpServers": [{ "serverName": "filesystem" }]
+        });
+        let user = json!({
+            "model": "opus",
+            "hooks": { "Stop": [] },
+            "permissions": { "allow": ["Bash(curl:*)", "Read"], "defaultMode": "bypassPermissions" }
+        });
+        let project = json!({ "enabledMcpjsonServers": ["github", "filesystem"], "theme": "dark" });
+
+        let findings = check_compliance(
+            &managed,
+            &[
+                ("user", Some(&user)),
+                ("project", Some(&project)),
+                ("local", None),
+            ],
+        );
+        assert_eq!(
+            statuses(&findings),
+            [
+                ("user", "hooks", PolicyStatus::Ignored),
+                ("user", "model", PolicyStatus::Overridden),
+                ("user", "permissions.allow", PolicyStatus::Forbidden),
+                ("user", "permissions.defaultMode", PolicyStatus::Forbidden),
+                ("project", "enabledMcpjsonServers", PolicyStatus::Forbidden),
+            ]
+        );
+        assert_eq!(findings[2].local_value, json!("Bash(curl:*)"));
+        assert_eq!(findings[1].policy_value, json!("sonnet"));
+    }
+
+    #[test]
+    fn allowlists_apply_only_when_set() {
+        let user = json!({ "model": "opus", "enabledMcpjsonServers": ["github"] });
+        let scopes = [("user", Some(&user))];
+
+        assert!(check_compliance(&json!({}), &scopes).is_empty());
+
+        let managed = json!({ "availableModels": ["sonnet"], "allowedMcpServers": [] });
+        assert_eq!(
+            statuses(&check_compliance(&managed, &scopes)),
+            [
+                ("user", "enabledMcpjsonServers", PolicyStatus::Forbidden),
+                ("user", "model", PolicyStatus::Forbidden),
+            ]
+        );
+    }
+}
@@ -0,0 +1,64 @@
+use regex::Regex;
+use serde_json::Value;
+
+/// Finds string values in `value` that mention `name` as a whole word.
+///
+/// A word boundary is anything other than a letter, digit, `_`, or `-`, so
+/// `Task(reviewer)` and `/reviewer` match `reviewer` but `code-reviewer` does
+/// not. Returns `(path, value)` pairs with paths like `hooks.PreToolUse[0].matcher`.
+pub fn find_string_references(value: &Value, name: &str) -> Vec<(String, String)> {
+    let pattern = format!(
+        r"(^|[^A-Za-z0-9_-]){}($|[^A-Za-z0-9_-])",
+        regex::escape(name)
+    );
+    let Ok(re) = Regex::new(&pattern) else {
+        return vec![];
+    };
+    let mut out = Vec::new();
+    walk(value, String::new(), &re, &mut out);
+    out
+}
+
+fn walk(value: &Value, path: String, re: &Regex, out: &mut Vec<(String, String)>) {
+    match value {
+        Value::String(s) if re.is_match(s) => out.push((path, s.clone())),
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                walk(item, format!("{}[{}]", path, i), re, out);
+            }
+        }
+        Value::Object(map) => {
+            for (key, item) in map {
+                let child = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                walk(item, child, re, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn finds_whole_word_mentions_with_paths() {
+        let settings = json!({
+            "permissions": { "allow": ["Task(reviewer)", "Task(code-reviewer)"] },
+            "hooks": { "Stop": [{ "hooks": [{ "command": "run /reviewer now" }] }] },
+            "model": "reviewer-large"
+        });
+        let refs = find_string_references(&settings, "reviewer");
+        let mut paths: Vec<_> = refs.iter().map(|(p, _)| p.as_str()).collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec!["hooks.Stop[0].hooks[0].command", "permissions.allow[0]"]
+        );
+    }
+}
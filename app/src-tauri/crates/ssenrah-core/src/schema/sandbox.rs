@@ -0,0 +1,339 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::schema::merge::EffectiveConfig;
+use crate::schema::occurrences::{lookup, source_of};
+use crate::schema::tools::parse_permission_rule;
+
+/// Commands that hand out a general-purpose shell or interpreter; excluding
+/// one from the sandbox excludes almost everything.
+const BROAD_COMMANDS: &[&str] = &[
+    "*", "bash", "sh", "zsh", "fish", "env", "sudo", "python", "python3", "node", "ruby", "perl",
+    "npx", "eval", "exec", "xargs",
+];
+
+/// Unix sockets that give the holder control of the host.
+const PRIVILEGED_SOCKETS: &[&str] = &[
+    "/var/run/docker.sock",
+    "/run/docker.sock",
+    "/run/containerd/containerd.sock",
+    "/run/podman/podman.sock",
+];
+
+/// One observation about the effective sandbox policy.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SandboxFinding {
+    /// "warning" or "info".
+    pub severity: &'static str,
+    pub code: &'static str,
+    /// Dot-separated settings path of the entry.
+    pub path: String,
+    pub value: Value,
+    /// Scope that provides the entry in the merged settings.
+    pub scope: Option<String>,
+    pub message: String,
+}
+
+/// Which OS mechanism enforces the sandbox.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SandboxBackend {
+    /// "seatbelt", "bubblewrap", or "none".
+    pub name: &'static str,
+    pub supported: bool,
+    pub note: String,
+}
+
+/// Backend Claude Code uses on `os` (`std::env::consts::OS`).
+/// `bwrap_available` reports whether bubblewrap is on `PATH` (Linux only).
+pub fn backend_for(os: &str, bwrap_available: bool) -> SandboxBackend {
+    match os {
+        "macos" => SandboxBackend {
+            name: "seatbelt",
+            supported: true,
+            note: "macOS Seatbelt profiles via sandbox-exec (built in)".to_string(),
+        },
+        "linux" => SandboxBackend {
+            name: "bubblewrap",
+            supported: bwrap_available,
+            note: if bwrap_available {
+                "bubblewrap (bwrap) namespaces".to_string()
+            } else {
+                "bubblewrap (bwrap) is not installed; install it to enable the sandbox".to_string()
+            },
+        },
+        other => SandboxBackend {
+            name: "none",
+            supported: false,
+            note: format!(
+                "Sandboxing is not available on {}; run Claude Code under WSL2 on Windows",
+                other
+            ),
+        },
+    }
+}
+
+/// First word of a command pattern: `git push:*` and `git *` give `git`.
+fn command_head(pattern: &str) -> &str {
+    pattern
+        .split(|c: char| c.is_whitespace() || c == ':')
+        .next()
+        .unwrap_or_default()
+}
+
+/// Whether a domain pattern is a bare wildcard or covers a whole top-level
+/// domain (`*`, `*.com`, `*.co.uk`).
+fn is_broad_domain(domain: &str) -> bool {
+    let Some(rest) = domain.strip_prefix("*.") else {
+        return domain == "*";
+    };
+    match rest.split_once('.') {
+        None => true,
+        Some((second, tld)) => {
+            !tld.contains('.') && ["co", "com", "org", "net", "ac", "gov", "edu"].contains(&second)
+        }
+    }
+}
+
+/// Whether `pattern` (possibly `*.example.com`) matches `domain`.
+fn domain_matches(pattern: &str, domain: &str) -> bool {
+    match pattern.strip_prefix("*.") {
+        Some(suffix) => domain == suffix || domain.ends_with(&format!(".{}", suffix)),
+        None => pattern == "*" || pattern.eq_ignore_ascii_case(domain),
+    }
+}
+
+fn strings(value: Option<&Value>) -> Vec<&str> {
+    value
+        .and_then(|v| v.as_array())
+        .map(|a| a.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default()
+}
+
+/// Reviews the merged `sandbox` settings.
+///
+/// Flags excluded commands that amount to a shell, wildcard or TLD-wide
+/// allowed domains, privileged Unix sockets, settings that let commands
+/// leave the sandbox, and entries that contradict `permissions.deny` rules
+/// (`Bash(cmd:*)` against `excludedCommands`, `WebFetch(domain:x)` against
+/// `allowedDomains`).
+pub fn analyze(effective: &EffectiveConfig) -> Vec<SandboxFinding> {
+    let settings = &effective.settings;
+    let mut findings = Vec::new();
+    let mut push = |severity, code, path: &str, value: Value, message: String| {
+        findings.push(SandboxFinding {
+            severity,
+            code,
+            scope: source_of(effective, path),
+            path: path.to_string(),
+            value,
+            message,
+        });
+    };
+
+    let Some(sandbox) = lookup(settings, "sandbox").filter(|s| s.is_object()) else {
+        return Vec::new();
+    };
+    let enabled = sandbox.get("enabled").and_then(Value::as_bool) == Some(true);
+    if !enabled {
+        push(
+            "info",
+            "SANDBOX_DISABLED",
+            "sandbox.enabled",
+            sandbox.get("enabled").cloned().unwrap_or(Value::Null),
+            "The sandbox is not enabled, so none of these settings apply".to_string(),
+        );
+    }
+
+    let deny: Vec<(String, String)> = strings(lookup(settings, "permissions.deny"))
+        .into_iter()
+        .filter_map(parse_permission_rule)
+        .filter_map(|(tool, spec)| Some((tool, spec?)))
+        .collect();
+    let denied_commands: Vec<&str> = deny
+        .iter()
+        .filter(|(tool, _)| tool == "Bash")
+        .map(|(_, spec)| command_head(spec))
+        .collect();
+    let denied_domains: Vec<&str> = deny
+        .iter()
+        .filter(|(tool, _)| tool == "WebFetch")
+        .filter_map(|(_, spec)| spec.strip_prefix("domain:"))
+        .collect();
+
+    for command in strings(sandbox.get("excludedCommands")) {
+        let head = command_head(command);
+        let path = "sandbox.excludedCommands";
+        if BROAD_COMMANDS.contains(&head) {
+            push(
+                "warning",
+                "BROAD_EXCLUSION",
+                path,
+                command.into(),
+                format!(
+                    "'{}' can run any program, so excluding it bypasses the sandbox",
+                    command
+                ),
+            );
+        }
+        if denied_commands.contains(&head) {
+            push(
+                "warning",
+                "DENY_CONFLICT",
+                path,
+                command.into(),
+                format!(
+                    "'{}' is excluded from the sandbox but denied by a Bash permission rule",
+                    command
+                ),
+            );
+        }
+    }
+
+    let path = "sandbox.network.allowedDomains";
+    for domain in strings(lookup(sandbox, "network.allowedDomains")) {
+        if is_broad_domain(domain) {
+            push(
+                "warning",
+                "WILDCARD_DOMAIN",
+                path,
+                domain.into(),
+                format!(
+                    "'{}' allows sandboxed commands to reach a whole top-level domain",
+                    domain
+                ),
+            );
+        }
+        if let Some(denied) = denied_domains
+            .iter()
+            .find(|d| domain_matches(domain, d) || domain_matches(d, domain))
+        {
+            push(
+                "warning",
+                "DENY_CONFLICT",
+                path,
+                domain.into(),
+                format!(
+                    "'{}' is allowed for sandboxed commands but WebFetch(domain:{}) is denied",
+                    domain, denied
+                ),
+            );
+        }
+    }
+
+    let path = "sandbox.network.allowUnixSockets";
+    for socket in strings(lookup(sandbox, "network.allowUnixSockets")) {
+        if PRIVILEGED_SOCKETS.contains(&socket) {
+            push(
+                "warning",
+                "PRIVILEGED_SOCKET",
+                path,
+                socket.into(),
+                format!(
+                    "Access to {} gives sandboxed commands control of the host",
+                    socket
+                ),
+            );
+        }
+    }
+
+    let flag = |key: &str| lookup(sandbox, key).and_then(Value::as_bool) == Some(true);
+    if flag("network.allowAllUnixSockets") {
+        push(
+            "warning",
+            "ALL_UNIX_SOCKETS",
+            "sandbox.network.allowAllUnixSockets",
+            true.into(),
+            "Sandboxed commands may connect to any Unix socket".to_string(),
+        );
+    }
+    if flag("network.allowLocalBinding") {
+        push(
+            "info",
+            "LOCAL_BINDING",
+            "sandbox.network.allowLocalBinding",
+            true.into(),
+            "Sandboxed commands may listen on local ports".to_string(),
+        );
+    }
+    if flag("enableWeakerNestedSandbox") {
+        push(
+            "warning",
+            "WEAKER_SANDBOX",
+            "sandbox.enableWeakerNestedSandbox",
+            true.into(),
+            "The weaker nested sandbox is meant for Docker environments and isolates less"
+                .to_string(),
+        );
+    }
+    if flag("autoAllowBashIfSandboxed")
+        && sandbox.get("allowUnsandboxedCommands") != Some(&false.into())
+    {
+        push(
+            "info",
+            "UNSANDBOXED_FALLBACK",
+            "sandbox.allowUnsandboxedCommands",
+            sandbox
+                .get("allowUnsandboxedCommands")
+                .cloned()
+                .unwrap_or(Value::Null),
+            "Commands that fail in the sandbox may be retried outside it (with a prompt); set allowUnsandboxedCommands to false to forbid this".to_string(),
+        );
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::merge::compute_effective;
+    use serde_json::json;
+
+    #[test]
+    fn flags_broad_entries_and_deny_conflicts() {
+        let project = json!({
+            "permissions": { "deny": ["Bash(curl:*)", "WebFetch(domain:evil.example.com)"] },
+            "sandbox": {
+                "enabled": true,
+                "excludedCommands": ["docker", "bash", "curl"],
+                "network": {
+                    "allowedDomains": ["github.com", "*.com", "*.example.com"],
+                    "allowUnixSockets": ["/var/run/docker.sock"],
+                },
+            },
+        });
+        let effective = compute_effective(None, Some(&project), None, None);
+        let codes: Vec<(&str, Value)> = analyze(&effective)
+            .into_iter()
+            .map(|f| (f.code, f.value))
+            .collect();
+        assert_eq!(
+            codes,
+            vec![
+                ("BROAD_EXCLUSION", json!("bash")),
+                ("DENY_CONFLICT", json!("curl")),
+                ("WILDCARD_DOMAIN", json!("*.com")),
+                ("DENY_CONFLICT", json!("*.com")),
+                ("DENY_CONFLICT", json!("*.example.com")),
+                ("PRIVILEGED_SOCKET", json!("/var/run/docker.sock")),
+            ]
+        );
+        assert_eq!(analyze(&effective)[0].scope.as_deref(), Some("project"));
+    }
+
+    #[test]
+    fn reports_disabled_sandbox_and_backends() {
+        let user = json!({ "sandbox": { "excludedCommands": ["git"] } });
+        let effective = compute_effective(Some(&user), None, None, None);
+        let findings = analyze(&effective);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].code, "SANDBOX_DISABLED");
+        assert!(analyze(&compute_effective(None, None, None, None)).is_empty());
+
+        assert_eq!(backend_for("macos", false).name, "seatbelt");
+        assert!(!backend_for("linux", false).supported);
+        assert!(!backend_for("windows", true).supported);
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::BTreeSet;
+
+use regex::Regex;
+use serde_json::Value;
+
+use crate::schema::markdown::LintReport;
+use crate::schema::tools::{is_valid_tool_name, parse_permission_rule, tool_list};
+
+/// Claude Code truncates skill descriptions longer than this.
+pub const MAX_DESCRIPTION_CHARS: usize = 1024;
+
+/// Positional placeholders (`$1`..`$9`) referenced in a skill body.
+fn positional_placeholders(body: &str) -> BTreeSet<usize> {
+    let re = Regex::new(r"\$([1-9])\b").unwrap();
+    re.captures_iter(body)
+        .filter_map(|caps| caps[1].parse().ok())
+        .collect()
+}
+
+/// Parses an `argument-hint` such as `[file] [mode]` or `<pr-number>` into
+/// its argument count. Returns `None` if the hint is not a sequence of
+/// bracketed arguments.
+fn hint_arguments(hint: &str) -> Option<usize> {
+    let re = Regex::new(r"^\s*(?:(?:\[[^\[\]]+\]|<[^<>]+>)\s*)+$").unwrap();
+    if !re.is_match(hint) {
+        return None;
+    }
+    Some(hint.matches(['[', '<']).count())
+}
+
+/// Checks a skill's frontmatter and body.
+///
+/// `description` is required and should fit Claude Code's length limit;
+/// `allowed-tools` entries must follow the permission-rule grammar; and
+/// `argument-hint` must be a list of `[arg]` / `<arg>` entries consistent
+/// with the `$ARGUMENTS` / `$1`.. placeholders used in the body.
+pub fn validate_skill(frontmatter: &Value, body: &str) -> LintReport {
+    let mut report = LintReport::default();
+
+    if !frontmatter.is_object() {
+        report.error(
+            "frontmatter".to_string(),
+            "Frontmatter must be an object".to_string(),
+            "INVALID_TYPE",
+        );
+        return report;
+    }
+
+    match frontmatter.get("description").and_then(Value::as_str) {
+        Some(d) if !d.trim().is_empty() => {
+            let len = d.chars().count();
+            if len > MAX_DESCRIPTION_CHARS {
+                report.warning(
+                    "frontmatter.description".to_string(),
+                    format!(
+                        "Description is {} characters; Claude Code truncates it to {}",
+                        len, MAX_DESCRIPTION_CHARS
+                    ),
+                    "TOO_LONG",
+                );
+            }
+        }
+        _ => report.error(
+            "frontmatter.description".to_string(),
+            "Missing required frontmatter key 'description'".to_string(),
+            "MISSING_FIELD",
+        ),
+    }
+
+    if let Some(value) = frontmatter.get("allowed-tools").filter(|v| !v.is_null()) {
+        for rule in tool_list(value) {
+            match parse_permission_rule(&rule) {
+                None => report.error(
+                    "frontmatter.allowed-tools".to_string(),
+                    format!(
+                        "Invalid rule '{}'. Expected ToolName or ToolName(specifier).",
+                        rule
+                    ),
+                    "INVALID_FORMAT",
+                ),
+                Some((tool, _)) if !is_valid_tool_name(&tool) => report.warning(
+                    "frontmatter.allowed-tools".to_string(),
+                    format!("Unknown tool '{}'", tool),
+                    "UNKNOWN_TOOL",
+                ),
+                Some(_) => {}
+            }
+        }
+    }
+
+    let uses_arguments = body.contains("$ARGUMENTS");
+    let positional = positional_placeholders(body);
+    let hint = frontmatter
+        .get("argument-hint")
+        .and_then(Value::as_str)
+        .filter(|h| !h.trim().is_empty());
+
+    match hint {
+        Some(hint) => match hint_arguments(hint) {
+            None => report.warning(
+                "frontmatter.argument-hint".to_string(),
+                format!(
+                    "argument-hint '{}' should list arguments as [name] or <name>",
+                    hint
+                ),
+                "INVALID_FORMAT",
+            ),
+            Some(count) => {
+                for n in positional.iter().filter(|n| **n > count) {
+                    report.warning(
+                        "body".to_string(),
+                        format!(
+                            "${} is used but argument-hint declares only {} argument(s)",
+                            n, count
+                        ),
+                        "UNDECLARED_ARGUMENT",
+                    );
+                }
+                if !uses_arguments && positional.is_empty() {
+                    report.warning(
+                        "frontmatter.argument-hint".to_string(),
+                        "argument-hint is set but the body never uses $ARGUMENTS or $1..$9"
+                            .to_string(),
+                        "UNUSED_ARGUMENT_HINT",
+                    );
+                }
+            }
+        },
+        None if uses_arguments || !positional.is_empty() => report.warning(
+            "frontmatter.argument-hint".to_string(),
+            "The body uses argument placeholders; add an argument-hint so users know what to pass"
+                .to_string(),
+            "MISSING_ARGUMENT_HINT",
+        ),
+        None => {}
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn codes(report: &LintReport) -> Vec<&str> {
+        report
+            .errors
+            .iter()
+            .map(|e| e.code.as_str())
+            .chain(report.warnings.iter().map(|w| w.code.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn accepts_consistent_skill() {
+        let report = validate_skill(
+            &json!({
+                "description": "Review a pull request",
+                "allowed-tools": "Bash(gh pr diff:*, gh pr view:*), Read, mcp__github",
+                "argument-hint": "[pr-number] [focus]",
+            }),
+            "Review PR $1 focusing on $2.",
+        );
+        assert!(codes(&report).is_empty(), "{:?}", codes(&report));
+    }
+
+    #[test]
+    fn reports_grammar_and_placeholder_problems() {
+        let report = validate_skill(
+            &json!({
+                "allowed-tools": ["Bash(git", "Teleport"],
+                "argument-hint": "[file]",
+            }),
+            "Open $1 and $3",
+        );
+        assert_eq!(
+            codes(&report),
+            vec![
+                "MISSING_FIELD",
+                "INVALID_FORMAT",
+                "UNKNOWN_TOOL",
+                "UNDECLARED_ARGUMENT"
+            ]
+        );
+    }
+
+    #[test]
+    fn checks_hint_format_and_usage() {
+        assert_eq!(hint_arguments("[a] <b>"), Some(2));
+        assert_eq!(hint_arguments("file name"), None);
+
+        let report = validate_skill(&json!({ "description": "d" }), "Do $ARGUMENTS");
+        assert_eq!(codes(&report), vec!["MISSING_ARGUMENT_HINT"]);
+
+        let report = validate_skill(
+            &json!({ "description": "d", "argument-hint": "[x]" }),
+            "No placeholders",
+        );
+        assert_eq!(codes(&report), vec!["UNUSED_ARGUMENT_HINT"]);
+    }
+}
@@ -0,0 +1,258 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use super::merge::{is_array_union_field, is_deep_merge_field};
+
+/// Result of a structural three-way merge of two concurrent edits against a
+/// common base.
+#[derive(Debug, Serialize)]
+pub struct ThreeWayMerge {
+    /// The merged settings object. For conflicting paths, "ours" is kept so
+    /// the result is always a usable settings object.
+    pub merged: Value,
+    /// Paths where both sides changed the same value differently.
+    pub conflicts: Vec<MergeConflict>,
+}
+
+/// A single irreconcilable path. Missing values are reported as `None`.
+#[derive(Debug, Serialize)]
+pub struct MergeConflict {
+    /// Dot-separated JSON path of the field.
+    pub path: String,
+    pub base: Option<Value>,
+    pub ours: Option<Value>,
+    pub theirs: Option<Value>,
+}
+
+/// Performs a structural three-way merge of `ours` and `theirs` against their
+/// common ancestor `base`.
+///
+/// Deep-merge fields (and the root object) are merged key by key, as in
+/// `merge::compute_effective`. Permission arrays, which are unioned there,
+/// are merged entry by entry: an entry added on either side is kept, and one
+/// removed on one side stays removed, so concurrent rule edits never clash.
+/// Everything else — scalars, other arrays, and other objects — is treated
+/// as an atomic value. An atomic value changed on only one side takes that
+/// side's value; changed identically on both sides is accepted; changed
+/// differently is a conflict.
+pub fn merge_three_way(base: &Value, ours: &Value, theirs: &Value) -> ThreeWayMerge {
+    let mut conflicts = Vec::new();
+    let empty = Map::new();
+
+    let merged = merge_object(
+        base.as_object().unwrap_or(&empty),
+        ours.as_object().unwrap_or(&empty),
+        theirs.as_object().unwrap_or(&empty),
+        "",
+        &mut conflicts,
+    );
+
+    conflicts.sort_by(|a, b| a.path.cmp(&b.path));
+
+    ThreeWayMerge {
+        merged: Value::Object(merged),
+        conflicts,
+    }
+}
+
+/// Merges the keys of three objects, recursing into deep-merge fields.
+fn merge_object(
+    base: &Map<String, Value>,
+    ours: &Map<String, Value>,
+    theirs: &Map<String, Value>,
+    prefix: &str,
+    conflicts: &mut Vec<MergeConflict>,
+) -> Map<String, Value> {
+    let mut result = Map::new();
+
+    // Preserve "ours" key order, then append keys only "theirs" introduced.
+    let keys = ours
+        .keys()
+        .chain(theirs.keys().filter(|k| !ours.contains_key(*k)))
+        .chain(
+            base.keys()
+                .filter(|k| !ours.contains_key(*k) && !theirs.contains_key(*k)),
+        );
+
+    for key in keys {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+
+        let b = base.get(key);
+        let o = ours.get(key);
+        let t = theirs.get(key);
+
+        // Deep-merge fields recurse only when both sides still hold an object;
+        // deleting or retyping the whole field is handled as an atomic change.
+        if is_deep_merge_field(&path) {
+            if let (Some(Value::Object(o)), Some(Value::Object(t))) = (o, t) {
+                let empty = Map::new();
+                let b = b.and_then(|v| v.as_object()).unwrap_or(&empty);
+                let merged = merge_object(b, o, t, &path, conflicts);
+                result.insert(key.clone(), Value::Object(merged));
+                continue;
+            }
+        }
+
+        // Union arrays merge per entry while both sides still hold an array.
+        if is_array_union_field(&path) {
+            if let (Some(Value::Array(o)), Some(Value::Array(t))) = (o, t) {
+                let empty = Vec::new();
+                let b = b.and_then(|v| v.as_array()).unwrap_or(&empty);
+                result.insert(key.clone(), Value::Array(merge_entries(b, o, t)));
+                continue;
+            }
+        }
+
+        match merge_atomic(b, o, t) {
+            Some(Resolution::Keep(v)) => {
+                result.insert(key.clone(), v.clone());
+            }
+            Some(Resolution::Remove) => {}
+            None => {
+                conflicts.push(MergeConflict {
+                    path,
+                    base: b.cloned(),
+                    ours: o.cloned(),
+                    theirs: t.cloned(),
+                });
+                if let Some(v) = o {
+                    result.insert(key.clone(), v.clone());
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// Merges two edits of an array treated as a set. An entry survives if
+/// either side added it, or if it was in `base` and neither side removed
+/// it. Keeps "ours" order, then appends entries only "theirs" added.
+fn merge_entries(base: &[Value], ours: &[Value], theirs: &[Value]) -> Vec<Value> {
+    let mut merged: Vec<Value> = Vec::new();
+    for entry in ours.iter().chain(theirs) {
+        let kept = if base.contains(entry) {
+            ours.contains(entry) && theirs.contains(entry)
+        } else {
+            true
+        };
+        if kept && !merged.contains(entry) {
+            merged.push(entry.clone());
+        }
+    }
+    merged
+}
+
+enum Resolution<'a> {
+    Keep(&'a Value),
+    Remove,
+}
+
+/// Resolves an atomic value. Returns `None` when both sides diverged.
+fn merge_atomic<'a>(
+    base: Option<&'a Value>,
+    ours: Option<&'a Value>,
+    theirs: Option<&'a Value>,
+) -> Option<Resolution<'a>> {
+    let pick = if ours == theirs || theirs == base {
+        ours
+    } else if ours == base {
+        theirs
+    } else {
+        return None;
+    };
+
+    Some(match pick {
+        Some(v) => Resolution::Keep(v),
+        None => Resolution::Remove,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn non_overlapping_edits_merge_cleanly() {
+        let base = json!({ "model": "a", "language": "en" });
+        let ours = json!({ "model": "b", "language": "en" });
+        let theirs = json!({ "model": "a", "language": "ko" });
+
+        let result = merge_three_way(&base, &ours, &theirs);
+        assert!(result.conflicts.is_empty());
+        assert_eq!(result.merged, json!({ "model": "b", "language": "ko" }));
+    }
+
+    #[test]
+    fn divergent_scalar_is_conflict_and_keeps_ours() {
+        let base = json!({ "model": "a" });
+        let ours = json!({ "model": "b" });
+        let theirs = json!({ "model": "c" });
+
+        let result = merge_three_way(&base, &ours, &theirs);
+        assert_eq!(result.conflicts.len(), 1);
+        assert_eq!(result.conflicts[0].path, "model");
+        assert_eq!(result.conflicts[0].theirs, Some(json!("c")));
+        assert_eq!(result.merged["model"], "b");
+    }
+
+    #[test]
+    fn deep_merge_fields_merge_per_key() {
+        let base = json!({ "env": { "A": "1" } });
+        let ours = json!({ "env": { "A": "1", "B": "2" } });
+        let theirs = json!({ "env": { "A": "1", "C": "3" } });
+
+        let result = merge_three_way(&base, &ours, &theirs);
+        assert!(result.conflicts.is_empty());
+        assert_eq!(result.merged["env"], json!({ "A": "1", "B": "2", "C": "3" }));
+    }
+
+    #[test]
+    fn permission_arrays_merge_per_entry() {
+        let base = json!({ "permissions": { "allow": ["Read", "Grep"] } });
+        let ours = json!({ "permissions": { "allow": ["Read", "Grep", "Write"] } });
+        let theirs = json!({ "permissions": { "allow": ["Read", "Bash"] } });
+
+        let result = merge_three_way(&base, &ours, &theirs);
+        assert!(result.conflicts.is_empty());
+        assert_eq!(
+            result.merged["permissions"]["allow"],
+            json!(["Read", "Write", "Bash"])
+        );
+
+        // Other arrays are still atomic.
+        let base = json!({ "availableModels": ["a"] });
+        let ours = json!({ "availableModels": ["a", "b"] });
+        let theirs = json!({ "availableModels": ["a", "c"] });
+        let result = merge_three_way(&base, &ours, &theirs);
+        assert_eq!(result.conflicts.len(), 1);
+        assert_eq!(result.conflicts[0].path, "availableModels");
+    }
+
+    #[test]
+    fn one_sided_deletion_is_applied() {
+        let base = json!({ "model": "a", "language": "en" });
+        let ours = json!({ "model": "a", "language": "en" });
+        let theirs = json!({ "language": "en" });
+
+        let result = merge_three_way(&base, &ours, &theirs);
+        assert!(result.conflicts.is_empty());
+        assert!(result.merged.get("model").is_none());
+    }
+
+    #[test]
+    fn delete_versus_edit_is_conflict() {
+        let base = json!({ "model": "a" });
+        let ours = json!({ "model": "b" });
+        let theirs = json!({});
+
+        let result = merge_three_way(&base, &ours, &theirs);
+        assert_eq!(result.conflicts.len(), 1);
+        assert_eq!(result.conflicts[0].theirs, None);
+    }
+}
@@ -0,0 +1,308 @@
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::schema::occurrences::lookup;
+
+/// A built-in Claude Code tool.
+#[derive(Debug)]
+pub struct BuiltinTool {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// What goes between the parentheses of a permission rule for this
+    /// tool, or `None` if rules name the tool alone.
+    pub specifier: Option<&'static str>,
+}
+
+/// Built-in Claude Code tools accepted in agent `tools`, skill
+/// `allowed-tools`, and permission rules.
+pub const BUILTIN_TOOLS: &[BuiltinTool] = &[
+    BuiltinTool {
+        name: "Bash",
+        description: "Runs a shell command",
+        specifier: Some("command prefix, e.g. `npm run test:*` or `git diff`"),
+    },
+    BuiltinTool {
+        name: "BashOutput",
+        description: "Reads output from a background shell",
+        specifier: None,
+    },
+    BuiltinTool {
+        name: "Edit",
+        description: "Edits a file in place",
+        specifier: Some("gitignore-style path pattern, e.g. `./src/**`"),
+    },
+    BuiltinTool {
+        name: "ExitPlanMode",
+        description: "Leaves plan mode after presenting a plan",
+        specifier: None,
+    },
+    BuiltinTool {
+        name: "Glob",
+        description: "Finds files by name pattern",
+        specifier: None,
+    },
+    BuiltinTool {
+        name: "Grep",
+        description: "Searches file contents",
+        specifier: None,
+    },
+    BuiltinTool {
+        name: "KillShell",
+        description: "Stops a background shell",
+        specifier: None,
+    },
+    BuiltinTool {
+        name: "LS",
+        description: "Lists a directory",
+        specifier: None,
+    },
+    BuiltinTool {
+        name: "MCPSearch",
+        description: "Searches the tools of connected MCP servers",
+        specifier: None,
+    },
+    BuiltinTool {
+        name: "MultiEdit",
+        description: "Makes several edits to one file",
+        specifier: Some("gitignore-style path pattern, e.g. `./src/**`"),
+    },
+    BuiltinTool {
+        name: "NotebookEdit",
+        description: "Edits a Jupyter notebook cell",
+        specifier: Some("gitignore-style path pattern, e.g. `./notebooks/**`"),
+    },
+    BuiltinTool {
+        name: "NotebookRead",
+        description: "Reads a Jupyter notebook",
+        specifier: Some("gitignore-style path pattern, e.g. `./notebooks/**`"),
+    },
+    BuiltinTool {
+        name: "Read",
+        description: "Reads a file",
+        specifier: Some("gitignore-style path pattern, e.g. `./.env` or `~/.ssh/**`"),
+    },
+    BuiltinTool {
+        name: "Skill",
+        description: "Invokes a skill",
+        specifier: Some("skill name, e.g. `pdf`"),
+    },
+    BuiltinTool {
+        name: "SlashCommand",
+        description: "Runs a custom slash command",
+        specifier: Some("command, e.g. `/commit` or `/review-pr:*`"),
+    },
+    BuiltinTool {
+        name: "Task",
+        description: "Delegates to a subagent",
+        specifier: Some("subagent name, e.g. `Explore`"),
+    },
+    BuiltinTool {
+        name: "TodoWrite",
+        description: "Updates the session's todo list",
+        specifier: None,
+    },
+    BuiltinTool {
+        name: "WebFetch",
+        description: "Fetches a URL",
+        specifier: Some("`domain:` followed by a host, e.g. `domain:docs.rs`"),
+    },
+    BuiltinTool {
+        name: "WebSearch",
+        description: "Searches the web",
+        specifier: None,
+    },
+    BuiltinTool {
+        name: "Write",
+        description: "Creates or overwrites a file",
+        specifier: Some("gitignore-style path pattern, e.g. `./src/**`"),
+    },
+];
+
+/// Returns `true` for a built-in tool or an `mcp__server` /
+/// `mcp__server__tool` identifier.
+pub fn is_valid_tool_name(name: &str) -> bool {
+    if BUILTIN_TOOLS.iter().any(|t| t.name == name) {
+        return true;
+    }
+    Regex::new(r"^mcp__[A-Za-z0-9_-]+?(__[A-Za-z0-9_-]+)?$")
+        .map(|re| re.is_match(name))
+        .unwrap_or(false)
+}
+
+/// Splits a comma-separated tool list, ignoring commas inside a rule's
+/// parenthesized specifier such as `Bash(git diff:*, git log:*)`.
+pub fn split_tool_list(s: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+    for c in s.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                items.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    items.push(current);
+    items
+}
+
+/// Splits a permission rule into its tool name and optional specifier.
+///
+/// Format: `ToolName` or `ToolName(specifier)`, where MCP tool names may also
+/// contain digits and hyphens.
+pub fn parse_permission_rule(rule: &str) -> Option<(String, Option<String>)> {
+    let re = Regex::new(r"^([A-Za-z_][A-Za-z0-9_-]*)(?:\((.*)\))?$").ok()?;
+    let caps = re.captures(rule)?;
+    Some((
+        caps[1].to_string(),
+        caps.get(2).map(|m| m.as_str().to_string()),
+    ))
+}
+
+/// Reads a tool list written either as a comma-separated string or as an
+/// array of strings. Blank entries are dropped.
+pub fn tool_list(value: &serde_json::Value) -> Vec<String> {
+    let items: Vec<String> = match value {
+        serde_json::Value::String(s) => split_tool_list(s),
+        serde_json::Value::Array(arr) => arr
+            .iter()
+            .map(|v| match v {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            })
+            .collect(),
+        _ => vec![],
+    };
+    items
+        .into_iter()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// One entry of the tool catalog.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolInfo {
+    /// Name as written in permission rules and tool lists.
+    pub name: String,
+    pub description: String,
+    /// Grammar of the permission rule specifier; `None` if the tool takes
+    /// none. MCP tools never do.
+    pub specifier: Option<String>,
+    /// "builtin" or "mcp".
+    pub source: &'static str,
+    /// MCP server the tool belongs to.
+    pub server: Option<String>,
+}
+
+/// Permission rule lists that may name MCP tools.
+const RULE_LISTS: &[&str] = &["permissions.allow", "permissions.ask", "permissions.deny"];
+
+/// Lists every tool a rule, hook matcher, or agent can name: the built-in
+/// tools, then `mcp__<server>` for each server in `mcp_configs` (pairs of
+/// source and `{ mcpServers }` config), then any `mcp__<server>__<tool>`
+/// already named in the permission rules of `settings`. Which tools an MCP
+/// server offers is only known once it runs, so those rules are the only
+/// source of tool-level names.
+pub fn catalog(mcp_configs: &[(&str, &Value)], settings: &Value) -> Vec<ToolInfo> {
+    let mut tools: Vec<ToolInfo> = BUILTIN_TOOLS
+        .iter()
+        .map(|t| ToolInfo {
+            name: t.name.to_string(),
+            description: t.description.to_string(),
+            specifier: t.specifier.map(str::to_string),
+            source: "builtin",
+            server: None,
+        })
+        .collect();
+    let builtin = tools.len();
+
+    for (source, config) in mcp_configs {
+        let Some(servers) = config.get("mcpServers").and_then(Value::as_object) else {
+            continue;
+        };
+        for server in servers.keys() {
+            let name = format!("mcp__{}", server);
+            if tools.iter().all(|t| t.name != name) {
+                tools.push(ToolInfo {
+                    name,
+                    description: format!("Every tool of the {} MCP server '{}'", source, server),
+                    specifier: None,
+                    source: "mcp",
+                    server: Some(server.clone()),
+                });
+            }
+        }
+    }
+
+    let referenced = RULE_LISTS
+        .iter()
+        .filter_map(|list| lookup(settings, list).and_then(Value::as_array))
+        .flatten()
+        .filter_map(Value::as_str)
+        .filter_map(parse_permission_rule);
+    for (name, _) in referenced {
+        let Some((server, _)) = name
+            .strip_prefix("mcp__")
+            .and_then(|rest| rest.split_once("__"))
+        else {
+            continue;
+        };
+        if tools.iter().all(|t| t.name != name) {
+            tools.push(ToolInfo {
+                server: Some(server.to_string()),
+                description: "Named in a permission rule".to_string(),
+                specifier: None,
+                source: "mcp",
+                name,
+            });
+        }
+    }
+
+    tools[builtin..].sort_by(|a, b| a.name.cmp(&b.name));
+    tools
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn catalog_lists_builtin_then_mcp_tools() {
+        let user = json!({ "mcpServers": { "github": {}, "linear": {} } });
+        let project = json!({ "mcpServers": { "github": {} } });
+        let settings = json!({
+            "permissions": {
+                "allow": ["mcp__github__get_pr", "Read"],
+                "deny": ["mcp__github", "mcp__sentry__list_issues"]
+            }
+        });
+
+        let tools = catalog(&[("user", &user), ("project", &project)], &settings);
+        assert_eq!(tools.len(), BUILTIN_TOOLS.len() + 4);
+        assert_eq!(tools[0].name, "Bash");
+        assert!(tools[0].specifier.is_some());
+
+        let mcp: Vec<_> = tools[BUILTIN_TOOLS.len()..]
+            .iter()
+            .map(|t| (t.name.as_str(), t.server.as_deref()))
+            .collect();
+        assert_eq!(
+            mcp,
+            [
+                ("mcp__github", Some("github")),
+                ("mcp__github__get_pr", Some("github")),
+                ("mcp__linear", Some("linear")),
+                ("mcp__sentry__list_issues", Some("sentry")),
+            ]
+        );
+    }
+}
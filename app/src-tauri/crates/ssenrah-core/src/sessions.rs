@@ -0,0 +1,302 @@
+use std::collections::HashMap;
+use std::io::BufRead;
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// Entries returned per page by `read_page` when no page size is given.
+pub const DEFAULT_PAGE_SIZE: usize = 100;
+
+/// Longest title derived from a session's first prompt, in characters.
+const TITLE_CHARS: usize = 120;
+
+/// Metadata gathered from a transcript in a single streaming pass.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionMeta {
+    /// The `summary` entry Claude Code writes, or else the first user prompt.
+    pub title: Option<String>,
+    /// Number of `user` and `assistant` entries.
+    pub message_count: usize,
+    pub first_timestamp: Option<String>,
+    pub last_timestamp: Option<String>,
+    pub cwd: Option<String>,
+    pub git_branch: Option<String>,
+}
+
+/// One transcript line, with the fields a history viewer needs pulled out.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionEntry {
+    /// 1-based line number in the transcript file.
+    pub line: usize,
+    /// The entry's `type`: "user", "assistant", "summary", "system", ...
+    pub kind: String,
+    pub timestamp: Option<String>,
+    /// Plain text of the message, joined from its text blocks.
+    pub text: Option<String>,
+    /// The full parsed line.
+    pub raw: Value,
+}
+
+/// Returns the directory name Claude Code uses under `projects/` for a
+/// project root: every character that is not ASCII alphanumeric becomes `-`.
+pub fn project_dir_name(project_root: &str) -> String {
+    project_root
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+/// Returns `true` if `id` is safe to use as a transcript file stem.
+pub fn is_valid_session_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Extracts the plain text of a transcript entry's message. `content` may be
+/// a string or an array of blocks, of which only `text` blocks are kept.
+pub fn message_text(entry: &Value) -> Option<String> {
+    let content = entry.get("message")?.get("content")?;
+    match content {
+        Value::String(s) => Some(s.clone()),
+        Value::Array(blocks) => {
+            let text: Vec<&str> = blocks
+                .iter()
+                .filter(|b| b.get("type").and_then(|t| t.as_str()) == Some("text"))
+                .filter_map(|b| b.get("text").and_then(|t| t.as_str()))
+                .collect();
+            (!text.is_empty()).then(|| text.join("\n"))
+        }
+        _ => None,
+    }
+}
+
+fn str_field(entry: &Value, key: &str) -> Option<String> {
+    entry.get(key).and_then(|v| v.as_str()).map(str::to_string)
+}
+
+/// Reads a transcript line by line and collects its metadata. Lines that are
+/// not valid JSON (e.g. a partially written last line) are skipped.
+pub fn summarize<R: BufRead>(reader: R) -> SessionMeta {
+    let mut meta = SessionMeta::default();
+    let mut first_prompt: Option<String> = None;
+    let mut summary: Option<String> = None;
+
+    for line in reader.lines().map_while(Result::ok) {
+        let Ok(entry) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+        let kind = entry.get("type").and_then(|t| t.as_str()).unwrap_or("");
+
+        if kind == "summary" {
+            summary = summary.or_else(|| str_field(&entry, "summary"));
+            continue;
+        }
+        if let Some(ts) = str_field(&entry, "timestamp") {
+            meta.first_timestamp.get_or_insert_with(|| ts.clone());
+            meta.last_timestamp = Some(ts);
+        }
+        if meta.cwd.is_none() {
+            meta.cwd = str_field(&entry, "cwd");
+        }
+        if meta.git_branch.is_none() {
+            meta.git_branch = str_field(&entry, "gitBranch").filter(|b| !b.is_empty());
+        }
+        if kind == "user" || kind == "assistant" {
+            meta.message_count += 1;
+        }
+        if kind == "user" && first_prompt.is_none() {
+            first_prompt = message_text(&entry).filter(|t| !t.trim().is_empty());
+        }
+    }
+
+    meta.title = summary.or(first_prompt).map(|t| {
+        let t = t.trim();
+        let mut title: String = t.chars().take(TITLE_CHARS).collect();
+        if t.chars().count() > TITLE_CHARS {
+            title.push('…');
+        }
+        title
+    });
+    meta
+}
+
+/// Reads one page of entries from a transcript without loading the whole
+/// file: lines before the page are skipped unparsed. Returns the entries and
+/// whether more lines follow.
+pub fn read_page<R: BufRead>(
+    reader: R,
+    page: usize,
+    page_size: usize,
+) -> (Vec<SessionEntry>, bool) {
+    let mut lines = reader
+        .lines()
+        .map_while(Result::ok)
+        .enumerate()
+        .skip(page * page_size);
+
+    let entries = lines
+        .by_ref()
+        .take(page_size)
+        .filter_map(|(idx, line)| {
+            let raw = serde_json::from_str::<Value>(&line).ok()?;
+            Some(SessionEntry {
+                line: idx + 1,
+                kind: str_field(&raw, "type").unwrap_or_default(),
+                timestamp: str_field(&raw, "timestamp"),
+                text: message_text(&raw).or_else(|| str_field(&raw, "summary")),
+                raw,
+            })
+        })
+        .collect();
+
+    (entries, lines.next().is_some())
+}
+
+/// Whether a tool call recorded in a transcript ran or was turned down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolOutcome {
+    Approved,
+    Denied,
+}
+
+/// A tool call from a transcript and what became of it.
+#[derive(Debug)]
+pub struct ToolCall {
+    pub name: String,
+    pub input: Value,
+    pub outcome: ToolOutcome,
+}
+
+/// Content blocks of type `kind` in an entry's message.
+fn blocks<'a>(entry: &'a Value, kind: &'a str) -> impl Iterator<Item = &'a Value> {
+    entry
+        .get("message")
+        .and_then(|m| m.get("content"))
+        .and_then(|c| c.as_array())
+        .into_iter()
+        .flatten()
+        .filter(move |b| b.get("type").and_then(|t| t.as_str()) == Some(kind))
+}
+
+/// Whether a `tool_result` block reports that the user or a permission rule
+/// turned the call down, rather than the tool failing.
+fn is_denial(result: &Value) -> bool {
+    if result.get("is_error").and_then(|e| e.as_bool()) != Some(true) {
+        return false;
+    }
+    let text = match result.get("content") {
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(|i| i.get("text").and_then(|t| t.as_str()))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+    .to_lowercase();
+    ["doesn't want to proceed", "was rejected", "has been denied"]
+        .iter()
+        .any(|marker| text.contains(marker))
+}
+
+/// Pairs every `tool_use` block in a transcript with its `tool_result`, in
+/// call order. Calls without a result (the session was interrupted) are
+/// left out.
+pub fn tool_calls<R: BufRead>(reader: R) -> Vec<ToolCall> {
+    let mut uses: Vec<(String, String, Value)> = Vec::new();
+    let mut outcomes: HashMap<String, ToolOutcome> = HashMap::new();
+
+    for line in reader.lines().map_while(Result::ok) {
+        let Ok(entry) = serde_json::from_str::<Value>(&line) else {
+            continue;
+        };
+        for block in blocks(&entry, "tool_use") {
+            let (Some(id), Some(name)) = (str_field(block, "id"), str_field(block, "name")) else {
+                continue;
+            };
+            let input = block.get("input").cloned().unwrap_or(Value::Null);
+            uses.push((id, name, input));
+        }
+        for block in blocks(&entry, "tool_result") {
+            if let Some(id) = str_field(block, "tool_use_id") {
+                let outcome = if is_denial(block) {
+                    ToolOutcome::Denied
+                } else {
+                    ToolOutcome::Approved
+                };
+                outcomes.insert(id, outcome);
+            }
+        }
+    }
+
+    uses.into_iter()
+        .filter_map(|(id, name, input)| {
+            let outcome = *outcomes.get(&id)?;
+            Some(ToolCall {
+                name,
+                input,
+                outcome,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRANSCRIPT: &str = r#"{"type":"user","timestamp":"2025-01-01T00:00:00Z","cwd":"/w/app","gitBranch":"main","message":{"role":"user","content":"Fix the build"}}
+{"type":"assistant","timestamp":"2025-01-01T00:00:05Z","message":{"role":"assistant","content":[{"type":"text","text":"Done."},{"type":"tool_use","name":"Bash"}]}}
+not json
+{"type":"user","timestamp":"2025-01-01T00:01:00Z","message":{"role":"user","content":"Thanks"}}
+"#;
+
+    #[test]
+    fn encodes_project_dir_names() {
+        assert_eq!(project_dir_name("/home/me/my.app"), "-home-me-my-app");
+        assert!(is_valid_session_id("3f2a-11"));
+        assert!(!is_valid_session_id("../x"));
+    }
+
+    #[test]
+    fn summarizes_a_transcript() {
+        let meta = summarize(TRANSCRIPT.as_bytes());
+        assert_eq!(meta.title.as_deref(), Some("Fix the build"));
+        assert_eq!(meta.message_count, 3);
+        assert_eq!(
+            meta.first_timestamp.as_deref(),
+            Some("2025-01-01T00:00:00Z")
+        );
+        assert_eq!(meta.last_timestamp.as_deref(), Some("2025-01-01T00:01:00Z"));
+        assert_eq!(meta.git_branch.as_deref(), Some("main"));
+    }
+
+    #[test]
+    fn pages_through_entries() {
+        let (first, more) = read_page(TRANSCRIPT.as_bytes(), 0, 2);
+        assert!(more);
+        assert_eq!(first[1].text.as_deref(), Some("Done."));
+
+        let (second, more) = read_page(TRANSCRIPT.as_bytes(), 1, 2);
+        assert!(!more);
+        // The malformed line is skipped but keeps its line number slot.
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].line, 4);
+    }
+
+    #[test]
+    fn pairs_tool_calls_with_results() {
+        let transcript = r#"{"type":"assistant","message":{"content":[{"type":"tool_use","id":"a","name":"Bash","input":{"command":"npm test"}},{"type":"tool_use","id":"b","name":"Bash","input":{"command":"rm -rf dist"}}]}}
+{"type":"user","message":{"content":[{"type":"tool_result","tool_use_id":"a","content":"ok"},{"type":"tool_result","tool_use_id":"b","is_error":true,"content":"The user doesn't want to proceed with this tool use."}]}}
+{"type":"assistant","message":{"content":[{"type":"tool_use","id":"c","name":"Read","input":{}}]}}
+"#;
+        let calls = tool_calls(transcript.as_bytes());
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].outcome, ToolOutcome::Approved);
+        assert_eq!(calls[1].outcome, ToolOutcome::Denied);
+    }
+}
@@ -0,0 +1,451 @@
+//! Single artifacts (an agent, a skill, a permission set, an MCP server) as
+//! self-contained snippets to paste into chats, issues, or gists.
+
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::args;
+use crate::errors::IpcError;
+use crate::frontmatter::parse_frontmatter;
+use crate::schema::markdown::LintReport;
+use crate::schema::tools::{is_valid_tool_name, parse_permission_rule};
+use crate::schema::{agent, skill};
+use crate::support::{self, REDACTED};
+
+/// Artifacts that can be shared one at a time.
+pub const ARTIFACT_KINDS: &[&str] = &["agent", "skill", "permissions", "mcp_server"];
+
+/// Snippet formats: a markdown message with install instructions, or the
+/// bare JSON.
+pub const SNIPPET_FORMATS: &[&str] = &["markdown", "json"];
+
+/// Permission lists copied into a permissions snippet.
+pub const PERMISSION_LISTS: &[&str] = &["allow", "ask", "deny"];
+
+/// A rendered snippet.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactSnippet {
+    pub kind: String,
+    pub name: String,
+    pub format: String,
+    pub content: String,
+    /// Values replaced with `[redacted]`, as JSON paths.
+    pub redacted: Vec<String>,
+    /// Files of the artifact the snippet does not carry (a skill's extra
+    /// files).
+    pub omitted: Vec<String>,
+}
+
+/// A code fence longer than any backtick run in `content`.
+fn fence(content: &str) -> String {
+    let longest = content.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    "`".repeat((longest + 1).max(3))
+}
+
+fn fenced(language: &str, content: &str) -> String {
+    let fence = fence(content);
+    let newline = if content.ends_with('\n') { "" } else { "\n" };
+    format!("{}{}\n{}{}{}\n", fence, language, content, newline, fence)
+}
+
+/// `word` quoted for a POSIX shell, unless it needs no quoting.
+fn shell_quote(word: &str) -> String {
+    let plain = !word.is_empty()
+        && word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/'));
+    if plain {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', r"'\''"))
+    }
+}
+
+/// Paths under `path` of the leaves that differ between `before` and
+/// `after`, two values of the same shape.
+fn changed_leaves(before: &Value, after: &Value, path: &str, out: &mut Vec<String>) {
+    match (before, after) {
+        (Value::Object(before), Value::Object(after)) => {
+            for (key, value) in before {
+                if let Some(other) = after.get(key) {
+                    changed_leaves(value, other, &format!("{}.{}", path, key), out);
+                }
+            }
+        }
+        (Value::Array(before), Value::Array(after)) => {
+            for (i, (value, other)) in before.iter().zip(after).enumerate() {
+                changed_leaves(value, other, &format!("{}[{}]", path, i), out);
+            }
+        }
+        _ if before != after => out.push(path.to_string()),
+        _ => {}
+    }
+}
+
+/// Paths under `path` of the strings in `value` that hold a redacted secret.
+fn redacted_leaves(value: &Value, path: &str, out: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                redacted_leaves(value, &format!("{}.{}", path, key), out);
+            }
+        }
+        Value::Array(items) => {
+            for (i, value) in items.iter().enumerate() {
+                redacted_leaves(value, &format!("{}[{}]", path, i), out);
+            }
+        }
+        Value::String(s) if s.contains(REDACTED) => out.push(path.to_string()),
+        _ => {}
+    }
+}
+
+/// Masks the secrets of an MCP server with the support bundle rules (see
+/// `support::redact`). Returns the paths replaced, under `prefix`.
+pub fn redact_server(server: &mut Value, prefix: &str) -> Vec<String> {
+    let masked = support::redact(server);
+    let mut redacted = Vec::new();
+    changed_leaves(server, &masked, prefix, &mut redacted);
+    *server = masked;
+    redacted
+}
+
+/// Renders a markdown file artifact (an agent or a skill's `SKILL.md`).
+/// `install` is where the recipient saves it, e.g.
+/// `~/.claude/agents/reviewer.md`.
+pub fn render_document(
+    kind: &str,
+    name: &str,
+    install: &str,
+    document: &str,
+    description: Option<&str>,
+    format: &str,
+) -> Result<ArtifactSnippet, IpcError> {
+    let content = match args::one_of("format", format, SNIPPET_FORMATS)? {
+        "json" => serde_json::to_string_pretty(&json!({
+            "kind": kind,
+            "name": name,
+            "path": install,
+            "content": document,
+        }))
+        .unwrap_or_default(),
+        _ => {
+            let label = if kind == "agent" { "agent" } else { "skill" };
+            let mut text = format!("**Claude Code {}: `{}`**", label, name);
+            if let Some(description) = description.filter(|d| !d.trim().is_empty()) {
+                text.push_str(&format!(" — {}", description.trim()));
+            }
+            text.push_str(&format!("\n\nSave as `{}`:\n\n", install));
+            text.push_str(&fenced("markdown", document));
+            text
+        }
+    };
+    Ok(ArtifactSnippet {
+        kind: kind.to_string(),
+        name: name.to_string(),
+        format: format.to_string(),
+        content,
+        redacted: Vec::new(),
+        omitted: Vec::new(),
+    })
+}
+
+/// Renders a settings fragment (`{"permissions": ...}` or
+/// `{"mcpServers": ...}`). `target` names the file the recipient merges it
+/// into; MCP servers also get a `claude mcp add-json` command line.
+pub fn render_config(
+    kind: &str,
+    name: &str,
+    fragment: &Value,
+    target: &str,
+    format: &str,
+) -> Result<ArtifactSnippet, IpcError> {
+    let pretty = serde_json::to_string_pretty(fragment).unwrap_or_default();
+    let content = match args::one_of("format", format, SNIPPET_FORMATS)? {
+        "json" => pretty,
+        _ => {
+            let mut text = if kind == "mcp_server" {
+                format!("**Claude Code MCP server: `{}`**", name)
+            } else {
+                format!("**Claude Code permissions: {}**", name)
+            };
+            text.push_str(&format!("\n\nMerge into `{}`:\n\n", target));
+            text.push_str(&fenced("json", &pretty));
+            if let Some(server) = fragment["mcpServers"].get(name) {
+                let compact = serde_json::to_string(server).unwrap_or_default();
+                text.push_str("\nOr add it with the CLI:\n\n");
+                text.push_str(&fenced(
+                    "sh",
+                    &format!(
+                        "claude mcp add-json {} {}",
+                        shell_quote(name),
+                        shell_quote(&compact)
+                    ),
+                ));
+            }
+            text
+        }
+    };
+    Ok(ArtifactSnippet {
+        kind: kind.to_string(),
+        name: name.to_string(),
+        format: format.to_string(),
+        content,
+        redacted: Vec::new(),
+        omitted: Vec::new(),
+    })
+}
+
+/// An artifact read back from a snippet.
+#[derive(Debug)]
+pub struct SharedArtifact {
+    pub name: String,
+    /// The markdown file of an agent or skill.
+    pub document: Option<String>,
+    /// The `permissions` object, or the configuration of an MCP server.
+    pub config: Option<Value>,
+    pub report: LintReport,
+}
+
+fn unparsable(message: impl Into<String>) -> IpcError {
+    args::invalid("content", message.into())
+}
+
+/// The body of the first fenced code block in `text`, or `text` itself when
+/// it has none.
+fn unfence(text: &str) -> &str {
+    let Some(start) = text.find("```") else {
+        return text;
+    };
+    let fence_len = text[start..].chars().take_while(|c| *c == '`').count();
+    let fence = &text[start..start + fence_len];
+    let Some(body_start) = text[start..].find('\n').map(|i| start + i + 1) else {
+        return text;
+    };
+    let body = &text[body_start..];
+    let end = body
+        .match_indices(fence)
+        .map(|(i, _)| i)
+        .find(|&i| i == 0 || body[..i].ends_with('\n'))
+        .unwrap_or(body.len());
+    &body[..end]
+}
+
+/// Reads an agent or skill from a pasted markdown file, a markdown snippet
+/// from [`render_document`], or its JSON form.
+fn parse_document(kind: &str, text: &str) -> Result<SharedArtifact, IpcError> {
+    let (document, name_hint) = match serde_json::from_str::<Value>(text.trim()) {
+        Ok(json) => {
+            let document = json["content"]
+                .as_str()
+                .ok_or_else(|| unparsable("The JSON has no \"content\" string"))?;
+            (
+                document.to_string(),
+                json["name"].as_str().map(str::to_string),
+            )
+        }
+        Err(_) => (unfence(text).to_string(), None),
+    };
+    let (frontmatter, body) = parse_frontmatter(&document);
+    let name = frontmatter["name"]
+        .as_str()
+        .map(str::to_string)
+        .or(name_hint)
+        .ok_or_else(|| unparsable(format!("The {} has no name in its frontmatter", kind)))?;
+    args::file_name("name", &name)?;
+    let report = if kind == "agent" {
+        agent::validate_agent(&frontmatter, &body)
+    } else {
+        skill::validate_skill(&frontmatter, &body)
+    };
+    Ok(SharedArtifact {
+        name,
+        document: Some(document),
+        config: None,
+        report,
+    })
+}
+
+/// Reads one MCP server from `{"mcpServers": {name: config}}` or
+/// `{name: config}`.
+fn parse_server(json: &Value) -> Result<SharedArtifact, IpcError> {
+    let servers = json
+        .get("mcpServers")
+        .unwrap_or(json)
+        .as_object()
+        .ok_or_else(|| unparsable("Expected an object of MCP servers"))?;
+    let mut entries = servers.iter();
+    let (Some((name, config)), None) = (entries.next(), entries.next()) else {
+        return Err(unparsable(format!(
+            "Expected exactly one MCP server, found {}",
+            servers.len()
+        )));
+    };
+
+    let mut report = LintReport::default();
+    if !config.is_object() {
+        report.error(
+            name.clone(),
+            "The server configuration must be an object".to_string(),
+            "INVALID_TYPE",
+        );
+    } else if config.get("command").is_none() && config.get("url").is_none() {
+        report.error(
+            name.clone(),
+            "The server needs a \"command\" or a \"url\"".to_string(),
+            "MISSING_FIELD",
+        );
+    }
+    let mut redacted = Vec::new();
+    redacted_leaves(config, name, &mut redacted);
+    for path in redacted {
+        report.warning(
+            path,
+            "This value was redacted when shared; fill it in before use".to_string(),
+            "REDACTED_VALUE",
+        );
+    }
+    Ok(SharedArtifact {
+        name: name.clone(),
+        document: None,
+        config: Some(config.clone()),
+        report,
+    })
+}
+
+/// Reads permission rules from `{"permissions": {...}}` or a bare
+/// `{"allow": [...], ...}`, keeping only the `allow`/`ask`/`deny` lists.
+fn parse_permissions(json: &Value) -> Result<SharedArtifact, IpcError> {
+    let source = json.get("permissions").unwrap_or(json);
+    let mut permissions = serde_json::Map::new();
+    let mut report = LintReport::default();
+    for list in PERMISSION_LISTS {
+        let Some(rules) = source.get(*list).and_then(Value::as_array) else {
+            continue;
+        };
+        for (i, rule) in rules.iter().enumerate() {
+            let path = format!("permissions.{}[{}]", list, i);
+            match rule.as_str().map(parse_permission_rule) {
+                Some(Some((tool, _))) if !is_valid_tool_name(&tool) => report.warning(
+                    path,
+                    format!("'{}' is not a known tool", tool),
+                    "UNKNOWN_TOOL",
+                ),
+                Some(Some(_)) => {}
+                _ => report.error(path, format!("Invalid rule {}", rule), "INVALID_RULE"),
+            }
+        }
+        permissions.insert(list.to_string(), Value::Array(rules.clone()));
+    }
+    if permissions.is_empty() {
+        return Err(unparsable("No allow, ask, or deny rules found"));
+    }
+    Ok(SharedArtifact {
+        name: "permissions".to_string(),
+        document: None,
+        config: Some(Value::Object(permissions)),
+        report,
+    })
+}
+
+/// Reads an artifact of `kind` from pasted text: a bare file or JSON, or a
+/// snippet rendered by this module, in which case the first fenced block
+/// is used.
+pub fn parse_snippet(kind: &str, text: &str) -> Result<SharedArtifact, IpcError> {
+    match args::one_of("kind", kind, ARTIFACT_KINDS)? {
+        "agent" | "skill" => parse_document(kind, text),
+        kind => {
+            let json: Value = serde_json::from_str(unfence(text).trim())
+                .map_err(|e| unparsable(format!("Not valid JSON: {}", e)))?;
+            if kind == "mcp_server" {
+                parse_server(&json)
+            } else {
+                parse_permissions(&json)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_snippets() {
+        let agent = "---\nname: reviewer\n---\n\nUse ```diff``` blocks.\n";
+        let snippet = render_document(
+            "agent",
+            "reviewer",
+            ".claude/agents/reviewer.md",
+            agent,
+            Some("Reviews diffs"),
+            "markdown",
+        )
+        .unwrap();
+        assert!(snippet
+            .content
+            .starts_with("**Claude Code agent: `reviewer`** — Reviews diffs"));
+        assert!(snippet
+            .content
+            .contains("\n````markdown\n---\nname: reviewer"));
+
+        let mut server = json!({
+            "command": "npx",
+            "args": ["-y", "github-mcp"],
+            "env": { "GITHUB_TOKEN": "ghp_secret", "LOG_LEVEL": "info" }
+        });
+        let redacted = redact_server(&mut server, "mcpServers.github");
+        assert_eq!(
+            redacted,
+            vec![
+                "mcpServers.github.args[1]",
+                "mcpServers.github.env.GITHUB_TOKEN",
+                "mcpServers.github.env.LOG_LEVEL",
+            ]
+        );
+        assert_eq!(server["args"][0], "-y");
+        assert!(redact_server(&mut server, "mcpServers.github").is_empty());
+
+        let fragment = json!({ "mcpServers": { "github": server } });
+        let snippet =
+            render_config("mcp_server", "github", &fragment, ".mcp.json", "markdown").unwrap();
+        assert!(snippet.content.contains("claude mcp add-json github '{"));
+        assert!(!snippet.content.contains("ghp_secret"));
+
+        let json = render_config("mcp_server", "github", &fragment, ".mcp.json", "json").unwrap();
+        assert_eq!(
+            serde_json::from_str::<Value>(&json.content).unwrap(),
+            fragment
+        );
+        assert!(render_config("permissions", "user", &fragment, "", "yaml").is_err());
+    }
+
+    #[test]
+    fn parses_snippets_back() {
+        let agent =
+            "---\nname: reviewer\ndescription: Reviews diffs\n---\n\nUse ```diff``` blocks.\n";
+        for format in SNIPPET_FORMATS {
+            let snippet = render_document("agent", "reviewer", "", agent, None, format).unwrap();
+            let parsed = parse_snippet("agent", &snippet.content).unwrap();
+            assert_eq!(parsed.name, "reviewer");
+            assert_eq!(parsed.document.as_deref(), Some(agent));
+        }
+
+        let fragment = json!({ "mcpServers": { "github": {
+            "command": "npx", "env": { "GITHUB_TOKEN": REDACTED }
+        } } });
+        let snippet = render_config("mcp_server", "github", &fragment, "", "markdown").unwrap();
+        let parsed = parse_snippet("mcp_server", &snippet.content).unwrap();
+        assert_eq!(parsed.name, "github");
+        assert_eq!(parsed.report.warnings[0].code, "REDACTED_VALUE");
+        assert!(parse_snippet("mcp_server", r#"{ "a": {}, "b": {} }"#).is_err());
+
+        let parsed = parse_snippet(
+            "permissions",
+            r#"{ "allow": ["Bash(npm test:*)", "bad rule!"] }"#,
+        )
+        .unwrap();
+        assert_eq!(parsed.report.errors[0].path, "permissions.allow[1]");
+    }
+}
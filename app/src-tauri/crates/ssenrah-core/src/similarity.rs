@@ -0,0 +1,202 @@
+//! Near-duplicate detection for agents and skills, so importing or copying
+//! one does not leave a directory full of `reviewer (1).md` clones.
+
+use std::collections::HashSet;
+
+use serde::Serialize;
+use xxhash_rust::xxh3::xxh3_64;
+
+/// Body similarity at or above which two artifacts count as near-duplicates.
+pub const SIMILAR_BODY: f64 = 0.7;
+
+/// Words per shingle when comparing bodies.
+const SHINGLE_WORDS: usize = 3;
+
+/// Levenshtein distance between `a` and `b`, in characters.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = (diagonal + usize::from(ca != *cb))
+                .min(row[j] + 1)
+                .min(above + 1);
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+/// Lowercase words of `text`, without markdown punctuation.
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Hash of `text` that ignores case, whitespace, and markdown punctuation.
+pub fn normalized_hash(text: &str) -> u64 {
+    xxh3_64(words(text).join(" ").as_bytes())
+}
+
+/// Jaccard similarity of the word shingles of `a` and `b`, from 0 to 1.
+pub fn similarity(a: &str, b: &str) -> f64 {
+    let shingles = |text: &str| -> HashSet<String> {
+        let words = words(text);
+        if words.len() < SHINGLE_WORDS {
+            return words.into_iter().collect();
+        }
+        words.windows(SHINGLE_WORDS).map(|w| w.join(" ")).collect()
+    };
+    let (a, b) = (shingles(a), shingles(b));
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    a.intersection(&b).count() as f64 / a.union(&b).count() as f64
+}
+
+/// `name` without the suffixes copies tend to get: `reviewer (1)`,
+/// `reviewer-copy`, `reviewer_2`, and `.md`.
+pub fn base_name(name: &str) -> String {
+    let mut name = name.trim().trim_end_matches(".md").to_lowercase();
+    loop {
+        let trimmed = name
+            .trim_end_matches(|c: char| c.is_ascii_digit() || matches!(c, '(' | ')'))
+            .trim_end();
+        let trimmed = ["copy", "-", "_", " "]
+            .iter()
+            .fold(trimmed, |t, suffix| t.strip_suffix(suffix).unwrap_or(t));
+        if trimmed.len() == name.len() || trimmed.is_empty() {
+            return name;
+        }
+        name = trimmed.to_string();
+    }
+}
+
+/// An existing agent or skill to compare against.
+#[derive(Debug, Clone)]
+pub struct Artifact {
+    pub scope: String,
+    pub name: String,
+    pub path: String,
+    pub description: Option<String>,
+    pub body: String,
+}
+
+/// An existing artifact that resembles the one being added.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateMatch {
+    pub scope: String,
+    pub name: String,
+    pub path: String,
+    /// Any of `same_name`, `similar_name`, `same_description`,
+    /// `identical_body`, and `similar_body`.
+    pub reasons: Vec<&'static str>,
+    /// Body similarity, from 0 to 1.
+    pub similarity: f64,
+}
+
+/// Existing artifacts that look like `candidate`, most similar first.
+///
+/// Names match when they are equal once copy suffixes are removed, or
+/// within two edits of each other; descriptions when they are equal
+/// ignoring case and punctuation; bodies when they hash the same after
+/// normalization or share at least `SIMILAR_BODY` of their word shingles.
+pub fn find_duplicates(candidate: &Artifact, existing: &[Artifact]) -> Vec<DuplicateMatch> {
+    let name = base_name(&candidate.name);
+    let description = candidate
+        .description
+        .as_deref()
+        .filter(|d| !d.trim().is_empty())
+        .map(normalized_hash);
+    let body_hash = normalized_hash(&candidate.body);
+
+    let mut matches: Vec<DuplicateMatch> = existing
+        .iter()
+        .filter_map(|other| {
+            let mut reasons = Vec::new();
+            let other_name = base_name(&other.name);
+            if other_name == name {
+                reasons.push("same_name");
+            } else if name.chars().count() >= 4 && edit_distance(&name, &other_name) <= 2 {
+                reasons.push("similar_name");
+            }
+            if description.is_some()
+                && description == other.description.as_deref().map(normalized_hash)
+            {
+                reasons.push("same_description");
+            }
+            let similarity = similarity(&candidate.body, &other.body);
+            if normalized_hash(&other.body) == body_hash {
+                reasons.push("identical_body");
+            } else if similarity >= SIMILAR_BODY {
+                reasons.push("similar_body");
+            }
+            (!reasons.is_empty()).then(|| DuplicateMatch {
+                scope: other.scope.clone(),
+                name: other.name.clone(),
+                path: other.path.clone(),
+                reasons,
+                similarity,
+            })
+        })
+        .collect();
+    matches.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn artifact(name: &str, description: &str, body: &str) -> Artifact {
+        Artifact {
+            scope: "user".to_string(),
+            name: name.to_string(),
+            path: format!("/agents/{}.md", name),
+            description: Some(description.to_string()),
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn finds_near_duplicates() {
+        assert_eq!(base_name("Reviewer (1).md"), "reviewer");
+        assert_eq!(base_name("reviewer-copy-2"), "reviewer");
+        assert_eq!(base_name("gpt4"), "gpt");
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+
+        let body = "Review the diff for bugs, missing tests, and unclear names.\n\nBe concise.";
+        let existing = [
+            artifact("reviewer", "Reviews diffs", body),
+            artifact(
+                "code-reviewr",
+                "Something else",
+                "Unrelated prompt about writing docs.",
+            ),
+            artifact("planner", "Plans work", "Break the task into small steps."),
+        ];
+
+        let copy = artifact(
+            "reviewer (1)",
+            "reviews diffs.",
+            "Review the diff for bugs, missing tests, and unclear names. Be concise!",
+        );
+        let found = find_duplicates(&copy, &existing);
+        assert_eq!(found.len(), 1);
+        assert_eq!(
+            found[0].reasons,
+            vec!["same_name", "same_description", "identical_body"]
+        );
+
+        let renamed = artifact("code-reviewer", "", "Write release notes.");
+        let found = find_duplicates(&renamed, &existing);
+        assert_eq!(found[0].name, "code-reviewr");
+        assert_eq!(found[0].reasons, vec!["similar_name"]);
+    }
+}
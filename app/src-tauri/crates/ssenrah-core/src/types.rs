@@ -0,0 +1,98 @@
+use serde::de::value::StrDeserializer;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::errors::IpcError;
+use crate::platform::paths;
+use crate::platform::proxy::DetectedProxy;
+
+/// Scope from which a settings file can be read.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigScope {
+    User,
+    Project,
+    Local,
+    Managed,
+}
+
+/// Scopes that the GUI is allowed to write to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WritableScope {
+    User,
+    Project,
+    Local,
+}
+
+/// A scope as sent by the frontend, which in a monorepo may address a
+/// package: `"project:packages/api"` deserializes to the project scope of
+/// `<projectRoot>/packages/api` (see `paths::resolve_scope_address`).
+#[derive(Debug, Clone)]
+pub struct ScopeAddress<S> {
+    pub scope: S,
+    address: String,
+}
+
+impl<S> ScopeAddress<S> {
+    /// The project root the address refers to.
+    pub fn project_root(&self, project_root: &Option<String>) -> Result<Option<String>, IpcError> {
+        paths::resolve_scope_address(&self.address, project_root).map(|(_, root)| root)
+    }
+}
+
+impl<'de, S: Deserialize<'de>> Deserialize<'de> for ScopeAddress<S> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let address = String::deserialize(deserializer)?;
+        let scope = address.split_once(':').map_or(address.as_str(), |(s, _)| s);
+        let scope = S::deserialize(StrDeserializer::<D::Error>::new(scope))?;
+        Ok(ScopeAddress { scope, address })
+    }
+}
+
+/// Information about the host platform, resolved at runtime.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlatformInfo {
+    pub os: String,
+    pub is_wsl: bool,
+    pub shell: String,
+    pub claude_code_installed: bool,
+    pub claude_code_path: Option<String>,
+    /// Raw `claude --version` output.
+    pub claude_code_version: Option<String>,
+    pub config_dir: String,
+    pub managed_settings_dir: Option<String>,
+    /// Where ssenrah keeps its own state; see `paths::resolve_app_dir`.
+    pub app_dir: String,
+    /// Whether ssenrah runs in portable mode, with its state beside the
+    /// executable.
+    pub portable: bool,
+    /// Proxies configured in the environment or system settings, with
+    /// credentials redacted.
+    pub proxies: Vec<DetectedProxy>,
+}
+
+/// Information about the currently-opened project.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectInfo {
+    pub project_root: Option<String>,
+    pub claude_dir_exists: bool,
+    pub git_root: Option<String>,
+}
+
+/// A single validation error, surfaced to the frontend.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidationError {
+    pub path: String,
+    pub message: String,
+    pub code: String,
+}
+
+/// A single validation warning, surfaced to the frontend.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationWarning {
+    pub path: String,
+    pub message: String,
+    pub code: String,
+}
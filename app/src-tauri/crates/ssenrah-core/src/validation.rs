@@ -0,0 +1,111 @@
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::platform::{detect, paths};
+use crate::policy;
+use crate::schema::compat::{self, Version};
+use crate::schema::directories;
+use crate::schema::{model, models};
+use crate::types::{ValidationError, ValidationWarning};
+
+/// Result of validating a settings object.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationResult {
+    pub valid: bool,
+    pub errors: Vec<ValidationError>,
+    pub warnings: Vec<ValidationWarning>,
+}
+
+/// Validates a settings object against the typed settings model.
+///
+/// Unknown keys are accepted. When the installed Claude Code version is
+/// known, keys introduced in a later release produce an `UNSUPPORTED_BY_CLI`
+/// warning, since the CLI would silently ignore them. Violations of the
+/// admin policy file are errors when it is enforced and warnings otherwise.
+/// `permissions.additionalDirectories` entries are checked on disk, with
+/// relative entries resolved against `project_root` (see
+/// `schema::directories`). `model` and `smallFastModel` must name a known
+/// model or alias (see `schema::models`).
+pub fn validate_settings(
+    settings: &serde_json::Value,
+    project_root: &Option<String>,
+) -> ValidationResult {
+    // Basic structural check: settings must be an object
+    if !settings.is_object() {
+        return ValidationResult {
+            valid: false,
+            errors: vec![ValidationError {
+                path: "".to_string(),
+                message: "Settings must be a JSON object".to_string(),
+                code: "INVALID_TYPE".to_string(),
+            }],
+            warnings: vec![],
+        };
+    }
+
+    let mut errors = Vec::new();
+    if let Err(e) = model::parse_settings(settings) {
+        errors.push(e);
+    }
+
+    let mut warnings = Vec::new();
+    if let Some(installed) = detect::cached_claude_code_version()
+        .as_deref()
+        .and_then(Version::parse)
+    {
+        for (key, min) in compat::unsupported_keys(settings, installed) {
+            warnings.push(ValidationWarning {
+                message: format!(
+                    "'{}' requires Claude Code {} or newer; the installed CLI is {} and will ignore it",
+                    key, min, installed
+                ),
+                path: key,
+                code: "UNSUPPORTED_BY_CLI".to_string(),
+            });
+        }
+    }
+
+    // Without a home directory `~` cannot be expanded, so the check is skipped.
+    if let Ok(home) = paths::home_dir() {
+        let dirs = directories::check_additional_directories(
+            settings,
+            project_root.as_deref().map(Path::new),
+            &home,
+        );
+        errors.extend(dirs.errors);
+        warnings.extend(dirs.warnings);
+    }
+
+    warnings.extend(models::check_models(settings, &[]).warnings);
+
+    match policy::load() {
+        Ok(Some(policy)) if policy.enforce => {
+            errors.extend(policy::check_settings(&policy, settings));
+        }
+        Ok(Some(policy)) => {
+            warnings.extend(
+                policy::check_settings(&policy, settings)
+                    .into_iter()
+                    .map(|e| ValidationWarning {
+                        path: e.path,
+                        message: e.message,
+                        code: e.code,
+                    }),
+            );
+        }
+        Ok(None) => {}
+        Err(e) => warnings.push(ValidationWarning {
+            path: String::new(),
+            message: format!("The settings policy could not be read: {}", e),
+            code: "POLICY_UNREADABLE".to_string(),
+        }),
+    }
+
+    ValidationResult {
+        valid: errors.is_empty(),
+        errors,
+        warnings,
+    }
+}
@@ -1,253 +1,107 @@
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::Path;
 
 use serde_json::json;
 use tauri::State;
 
+use crate::args;
+use crate::commands::references::find_name_references;
+use crate::audit;
 use crate::errors::IpcError;
+use crate::frontmatter::{
+    parse_frontmatter, patch_frontmatter, replace_body, serialize_frontmatter,
+};
 use crate::io::atomic::atomic_write;
-use crate::platform::paths;
+use crate::io::blocking;
+use crate::io::self_write;
+use crate::io::text::{self, TextFormat};
+use crate::io::trash::{self, TrashItem};
+use crate::logging;
+use crate::platform::paths::resolve_agents_dir;
 use crate::AppState;
 
-/// Resolves the agents directory for a given scope.
-///
-/// - "user"    -> {configDir}/agents/
-/// - "project" -> {projectRoot}/.claude/agents/
-fn resolve_agents_dir(scope: &str, project_root: &Option<String>) -> Result<PathBuf, IpcError> {
-    match scope {
-        "user" => {
-            let config_dir = paths::resolve_config_dir();
-            Ok(config_dir.join("agents"))
-        }
-        "project" => {
-            let root = project_root.as_ref().ok_or_else(|| IpcError::NoProject {
-                message: "No project is open. Open a project before accessing project agents."
-                    .to_string(),
-            })?;
-            Ok(PathBuf::from(root).join(".claude").join("agents"))
-        }
-        _ => Err(IpcError::PlatformError {
-            message: format!("Unknown agent scope: {}", scope),
-        }),
-    }
-}
-
-/// Parse YAML frontmatter from a markdown file.
-///
-/// Splits the content on "---" markers. The text between the first and second
-/// "---" lines is treated as YAML frontmatter (parsed into a JSON value via
-/// simple key: value line parsing). Everything after the second "---" is the body.
-fn parse_frontmatter(content: &str) -> (serde_json::Value, String) {
-    let trimmed = content.trim_start();
-    if !trimmed.starts_with("---") {
-        return (json!({}), content.to_string());
-    }
-
-    // Find the closing ---
-    let after_first = &trimmed[3..];
-    let after_first = after_first.trim_start_matches(['\r', '\n']);
-
-    if let Some(end_idx) = after_first.find("\n---") {
-        let yaml_part = &after_first[..end_idx];
-        let body_start = end_idx + 4; // skip "\n---"
-        let body = if body_start < after_first.len() {
-            after_first[body_start..]
-                .trim_start_matches(['\r', '\n'])
-                .to_string()
-        } else {
-            String::new()
-        };
-
-        let frontmatter = parse_yaml_simple(yaml_part);
-        (frontmatter, body)
-    } else {
-        // No closing ---, treat entire content as body
-        (json!({}), content.to_string())
-    }
-}
-
-/// Simple YAML-like parser for frontmatter key: value pairs.
-///
-/// Handles strings, booleans, numbers, and bracket-delimited arrays.
-fn parse_yaml_simple(yaml: &str) -> serde_json::Value {
-    let mut map = serde_json::Map::new();
-
-    for line in yaml.lines() {
-        let line = line.trim();
-        if line.is_empty() || line.starts_with('#') {
-            continue;
-        }
-
-        if let Some((key, value)) = line.split_once(':') {
-            let key = key.trim().to_string();
-            let value = value.trim();
-
-            if value.is_empty() {
-                map.insert(key, json!(null));
-                continue;
-            }
-
-            // Array: [item1, item2, ...]
-            if value.starts_with('[') && value.ends_with(']') {
-                let inner = &value[1..value.len() - 1];
-                let items: Vec<serde_json::Value> = inner
-                    .split(',')
-                    .map(|s| {
-                        let s = s.trim().trim_matches('"').trim_matches('\'');
-                        json!(s)
-                    })
-                    .collect();
-                map.insert(key, json!(items));
-                continue;
-            }
-
-            // Boolean
-            if value == "true" {
-                map.insert(key, json!(true));
-                continue;
-            }
-            if value == "false" {
-                map.insert(key, json!(false));
-                continue;
-            }
-
-            // Number (integer)
-            if let Ok(n) = value.parse::<i64>() {
-                map.insert(key, json!(n));
-                continue;
-            }
-
-            // Number (float)
-            if let Ok(n) = value.parse::<f64>() {
-                map.insert(key, json!(n));
-                continue;
-            }
-
-            // String (strip optional quotes)
-            let s = value.trim_matches('"').trim_matches('\'');
-            map.insert(key, json!(s));
-        }
-    }
-
-    serde_json::Value::Object(map)
-}
-
-/// Serialize a JSON value as YAML-like frontmatter lines.
-fn serialize_frontmatter(frontmatter: &serde_json::Value) -> String {
-    let mut lines = Vec::new();
-    if let Some(obj) = frontmatter.as_object() {
-        for (key, value) in obj {
-            match value {
-                serde_json::Value::String(s) => lines.push(format!("{}: {}", key, s)),
-                serde_json::Value::Bool(b) => lines.push(format!("{}: {}", key, b)),
-                serde_json::Value::Number(n) => lines.push(format!("{}: {}", key, n)),
-                serde_json::Value::Array(arr) => {
-                    let items: Vec<String> = arr
-                        .iter()
-                        .map(|v| match v {
-                            serde_json::Value::String(s) => s.clone(),
-                            other => other.to_string(),
-                        })
-                        .collect();
-                    lines.push(format!("{}: [{}]", key, items.join(", ")));
-                }
-                serde_json::Value::Null => {}
-                _ => {
-                    lines.push(format!(
-                        "{}: {}",
-                        key,
-                        serde_json::to_string(value).unwrap_or_default()
-                    ));
-                }
-            }
-        }
-    }
-    lines.join("\n")
-}
-
 /// IPC command: lists agent .md files from one or both scopes.
 ///
 /// Returns an array of `{ filename, scope, frontmatter, bodyPreview }`.
 #[tauri::command]
-pub fn list_agents(
+pub async fn list_agents(
     scope: Option<String>,
-    state: State<AppState>,
+    window: tauri::Window,
+    state: State<'_, AppState>,
 ) -> Result<Vec<serde_json::Value>, IpcError> {
-    let project_root = state
-        .project_root
-        .lock()
-        .map_err(|e| IpcError::PlatformError {
-            message: format!("Failed to acquire state lock: {}", e),
-        })?;
+    logging::async_command("list_agents", async move {
+        let project_root = state.project_root(&window);
+        blocking::run("list_agents", move || {
+            let scopes: Vec<&str> = match scope.as_deref() {
+                Some(s) => vec![s],
+                None => vec!["user", "project"],
+            };
+
+            let mut agents = Vec::new();
+
+            for s in scopes {
+                let dir = match resolve_agents_dir(s, &project_root) {
+                    Ok(d) => d,
+                    Err(_) => continue, // Skip scopes that can't be resolved (e.g. no project open)
+                };
+
+                if !dir.exists() {
+                    continue;
+                }
 
-    let scopes: Vec<&str> = match scope.as_deref() {
-        Some(s) => vec![s],
-        None => vec!["user", "project"],
-    };
+                let entries = fs::read_dir(&dir).map_err(|e| IpcError::PlatformError {
+                    message: format!("Failed to read agents directory: {}", e),
+                })?;
 
-    let mut agents = Vec::new();
+                for entry in entries {
+                    let entry = entry.map_err(|e| IpcError::PlatformError {
+                        message: format!("Failed to read directory entry: {}", e),
+                    })?;
 
-    for s in scopes {
-        let dir = match resolve_agents_dir(s, &project_root) {
-            Ok(d) => d,
-            Err(_) => continue, // Skip scopes that can't be resolved (e.g. no project open)
-        };
+                    let path = entry.path();
+                    if path.extension().and_then(|e| e.to_str()) != Some("md") {
+                        continue;
+                    }
 
-        if !dir.exists() {
-            continue;
-        }
+                    let filename = entry.file_name().to_string_lossy().to_string();
+                    let content = fs::read_to_string(&path).unwrap_or_default();
+                    let (frontmatter, body) = parse_frontmatter(&content);
 
-        let entries = fs::read_dir(&dir).map_err(|e| IpcError::PlatformError {
-            message: format!("Failed to read agents directory: {}", e),
-        })?;
+                    let preview: String = body.chars().take(200).collect();
 
-        for entry in entries {
-            let entry = entry.map_err(|e| IpcError::PlatformError {
-                message: format!("Failed to read directory entry: {}", e),
-            })?;
-
-            let path = entry.path();
-            if path.extension().and_then(|e| e.to_str()) != Some("md") {
-                continue;
+                    agents.push(json!({
+                        "filename": filename,
+                        "scope": s,
+                        "frontmatter": frontmatter,
+                        "bodyPreview": preview,
+                    }));
+                }
             }
 
-            let filename = entry.file_name().to_string_lossy().to_string();
-            let content = fs::read_to_string(&path).unwrap_or_default();
-            let (frontmatter, body) = parse_frontmatter(&content);
-
-            let preview: String = body.chars().take(200).collect();
-
-            agents.push(json!({
-                "filename": filename,
-                "scope": s,
-                "frontmatter": frontmatter,
-                "bodyPreview": preview,
-            }));
-        }
-    }
-
-    Ok(agents)
+            Ok(agents)
+        })
+        .await
+    })
+    .await
 }
 
-/// IPC command: reads a single agent .md file and returns its frontmatter and body.
+/// IPC command: reads a single agent .md file and returns its frontmatter,
+/// body, and `format` (line endings and BOM, which writes keep).
 #[tauri::command]
 pub fn read_agent(
     scope: String,
     filename: String,
+    window: tauri::Window,
     state: State<AppState>,
 ) -> Result<serde_json::Value, IpcError> {
-    let project_root = state
-        .project_root
-        .lock()
-        .map_err(|e| IpcError::PlatformError {
-            message: format!("Failed to acquire state lock: {}", e),
-        })?;
+    args::file_name("filename", &filename)?;
+    let project_root = state.project_root(&window);
 
     let dir = resolve_agents_dir(&scope, &project_root)?;
     let path = dir.join(&filename);
     let path_str = path.display().to_string();
 
-    let content = fs::read_to_string(&path).map_err(|e| match e.kind() {
+    let (content, format) = text::read_text(&path).map_err(|e| match e.kind() {
         std::io::ErrorKind::NotFound => IpcError::NotFound {
             path: path_str.clone(),
         },
@@ -264,24 +118,28 @@ pub fn read_agent(
     Ok(json!({
         "frontmatter": frontmatter,
         "body": body,
+        "format": format,
     }))
 }
 
 /// IPC command: writes an agent .md file with YAML frontmatter and markdown body.
+///
+/// An existing file keeps its line endings and BOM unless `format`
+/// overrides them.
 #[tauri::command]
 pub fn write_agent(
     scope: String,
     filename: String,
     frontmatter: serde_json::Value,
     body: String,
+    format: Option<TextFormat>,
+    window: tauri::Window,
     state: State<AppState>,
 ) -> Result<(), IpcError> {
-    let project_root = state
-        .project_root
-        .lock()
-        .map_err(|e| IpcError::PlatformError {
-            message: format!("Failed to acquire state lock: {}", e),
-        })?;
+    state.ensure_writable()?;
+    args::file_name("filename", &filename)?;
+    args::max_bytes("body", body.len(), args::MAX_CONTENT_BYTES)?;
+    let project_root = state.project_root(&window);
 
     let dir = resolve_agents_dir(&scope, &project_root)?;
 
@@ -304,7 +162,7 @@ pub fn write_agent(
         format!("---\n{}\n---\n\n{}", fm, body)
     };
 
-    atomic_write(Path::new(&path), content.as_bytes()).map_err(|_| IpcError::WriteFailed {
+    text::write_text(&path, &content, format).map_err(|_| IpcError::WriteFailed {
         path: path_str,
         message: "Atomic write failed".to_string(),
     })?;
@@ -312,19 +170,75 @@ pub fn write_agent(
     Ok(())
 }
 
+/// Reads an existing agent file for an in-place edit, without its BOM.
+fn read_agent_file(path: &Path) -> Result<String, IpcError> {
+    let path_str = path.display().to_string();
+    let (content, _) = text::read_text(path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => IpcError::NotFound { path: path_str },
+        std::io::ErrorKind::PermissionDenied => IpcError::PermissionDenied { path: path_str },
+        _ => IpcError::PlatformError {
+            message: format!("Failed to read {}: {}", path_str, e),
+        },
+    })?;
+    Ok(content)
+}
+
+/// IPC command: replaces the markdown body of an existing agent file.
+///
+/// Unlike `write_agent`, the frontmatter is not re-serialized: everything
+/// before the body, including comments, key order, and line endings, is
+/// kept byte-for-byte.
+#[tauri::command]
+pub fn write_agent_body(
+    scope: String,
+    filename: String,
+    body: String,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<(), IpcError> {
+    state.ensure_writable()?;
+    let project_root = state.project_root(&window);
+
+    let path = resolve_agents_dir(&scope, &project_root)?.join(agent_filename(&filename)?);
+    let content = read_agent_file(&path)?;
+    text::write_text(&path, &replace_body(&content, &body), None)
+}
+
+/// IPC command: updates frontmatter keys of an existing agent file.
+///
+/// Each key in `patch` replaces its line in the frontmatter or is appended;
+/// a `null` value removes the key. Other keys, comments, and the body are
+/// left exactly as they are on disk.
+#[tauri::command]
+pub fn write_agent_frontmatter(
+    scope: String,
+    filename: String,
+    patch: serde_json::Map<String, serde_json::Value>,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<(), IpcError> {
+    state.ensure_writable()?;
+    let project_root = state.project_root(&window);
+
+    let path = resolve_agents_dir(&scope, &project_root)?.join(agent_filename(&filename)?);
+    let content = read_agent_file(&path)?;
+    text::write_text(&path, &patch_frontmatter(&content, &patch), None)
+}
+
 /// IPC command: deletes an agent .md file.
+///
+/// The file is moved to ssenrah's trash rather than removed; the returned
+/// item can be passed to `restore_deleted` within the retention period.
 #[tauri::command]
 pub fn delete_agent(
     scope: String,
     filename: String,
+    window: tauri::Window,
     state: State<AppState>,
-) -> Result<(), IpcError> {
-    let project_root = state
-        .project_root
-        .lock()
-        .map_err(|e| IpcError::PlatformError {
-            message: format!("Failed to acquire state lock: {}", e),
-        })?;
+) -> Result<TrashItem, IpcError> {
+    state.ensure_writable()?;
+    args::file_name("filename", &filename)?;
+    let project_root = state.project_root(&window);
 
     let dir = resolve_agents_dir(&scope, &project_root)?;
     let path = dir.join(&filename);
@@ -334,10 +248,126 @@ pub fn delete_agent(
         return Err(IpcError::NotFound { path: path_str });
     }
 
-    fs::remove_file(&path).map_err(|e| IpcError::WriteFailed {
-        path: path_str,
-        message: format!("Failed to delete agent file: {}", e),
+    let hash_before = audit::file_hash(&path);
+    let item = trash::move_to_trash("agent", &scope, &path)?;
+    self_write::tracker().register(&path);
+    audit::record_delete(&path, hash_before);
+
+    Ok(item)
+}
+
+/// Returns `name` with a `.md` extension, rejecting anything that is not a
+/// plain file name.
+pub(crate) fn agent_filename(name: &str) -> Result<String, IpcError> {
+    args::file_name("filename", name)?;
+    if name.ends_with(".md") {
+        Ok(name.to_string())
+    } else {
+        Ok(format!("{}.md", name))
+    }
+}
+
+/// IPC command: renames an agent .md file within its scope.
+///
+/// The file is moved with a single rename, so it is never missing or
+/// duplicated. Returns `{ path, references }`, where `references` lists
+/// settings entries and agent/skill frontmatter that still mention the old
+/// name and may need updating.
+#[tauri::command]
+pub fn rename_agent(
+    scope: String,
+    old: String,
+    new: String,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<serde_json::Value, IpcError> {
+    state.ensure_writable()?;
+    let project_root = state.project_root(&window);
+
+    let dir = resolve_agents_dir(&scope, &project_root)?;
+    let old = agent_filename(args::file_name("old", &old)?)?;
+    let new = agent_filename(args::file_name("new", &new)?)?;
+    let source = dir.join(&old);
+    let target = dir.join(&new);
+
+    if !source.exists() {
+        return Err(IpcError::NotFound {
+            path: source.display().to_string(),
+        });
+    }
+    if target.exists() {
+        return Err(IpcError::Conflict {
+            path: target.display().to_string(),
+            message: "An agent with that filename already exists".to_string(),
+        });
+    }
+
+    fs::rename(&source, &target).map_err(|e| IpcError::WriteFailed {
+        path: target.display().to_string(),
+        message: format!("Failed to rename agent: {}", e),
     })?;
+    self_write::tracker().register(&source);
+    self_write::tracker().register(&target);
+    audit::record_rename(&source, &target);
 
-    Ok(())
+    let old_name = old.trim_end_matches(".md");
+    Ok(json!({
+        "path": target.display().to_string(),
+        "references": find_name_references(old_name, &project_root),
+    }))
+}
+
+/// IPC command: copies an agent .md file to another scope.
+///
+/// The file is copied byte-for-byte, so frontmatter and body are preserved.
+/// Fails if the target scope already has an agent with that filename unless
+/// `overwrite` is true. Returns `{ path }` of the new file.
+#[tauri::command]
+pub fn copy_agent(
+    from_scope: String,
+    to_scope: String,
+    filename: String,
+    overwrite: Option<bool>,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<serde_json::Value, IpcError> {
+    state.ensure_writable()?;
+    let project_root = state.project_root(&window);
+
+    if from_scope == to_scope {
+        return Err(IpcError::InvalidArgument {
+            argument: "toScope".to_string(),
+            message: "Source and target scope are the same".to_string(),
+        });
+    }
+
+    let filename = agent_filename(&filename)?;
+    let source = resolve_agents_dir(&from_scope, &project_root)?.join(&filename);
+    let target_dir = resolve_agents_dir(&to_scope, &project_root)?;
+    let target = target_dir.join(&filename);
+    let target_str = target.display().to_string();
+
+    let content = fs::read(&source).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => IpcError::NotFound {
+            path: source.display().to_string(),
+        },
+        _ => IpcError::PlatformError {
+            message: format!("Failed to read {}: {}", source.display(), e),
+        },
+    })?;
+
+    if target.exists() && !overwrite.unwrap_or(false) {
+        return Err(IpcError::Conflict {
+            path: target_str,
+            message: format!("Agent {} already exists in {} scope", filename, to_scope),
+        });
+    }
+
+    fs::create_dir_all(&target_dir).map_err(|e| IpcError::WriteFailed {
+        path: target_dir.display().to_string(),
+        message: format!("Failed to create agents directory: {}", e),
+    })?;
+    atomic_write(&target, &content)?;
+
+    Ok(json!({ "path": target_str }))
 }
@@ -0,0 +1,22 @@
+use crate::audit::{self, AuditFilter, AuditRecord};
+use crate::errors::IpcError;
+
+/// Records returned when the caller does not set a limit.
+const DEFAULT_AUDIT_LIMIT: usize = 1000;
+
+/// IPC command: reads the audit trail of changes made through the app.
+///
+/// Every file ssenrah writes, deletes, or renames is recorded in
+/// `{configDir}/ssenrah/audit.jsonl` with the OS user, the IPC command, the
+/// bytes written, and SHA-256 hashes before and after. Returns up to `limit`
+/// (default 1000) of the most recent records matching `filter`, oldest first.
+#[tauri::command]
+pub fn read_audit_log(
+    filter: Option<AuditFilter>,
+    limit: Option<usize>,
+) -> Result<Vec<AuditRecord>, IpcError> {
+    audit::read_records(
+        &filter.unwrap_or_default(),
+        limit.unwrap_or(DEFAULT_AUDIT_LIMIT),
+    )
+}
@@ -0,0 +1,373 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::args;
+use crate::commands::fixes::make_executable;
+use crate::commands::statusline::run_command;
+use crate::config::{read_json_file, read_scope};
+use crate::errors::IpcError;
+use crate::io::atomic::atomic_write;
+use crate::io::blocking;
+use crate::logging;
+use crate::platform::auth::{self, CredentialSource};
+use crate::platform::paths::{self, resolve_mcp_path};
+use crate::schema::merge::{self, EffectiveConfig};
+use crate::schema::occurrences::{lookup, source_of};
+use crate::statusline::check_command;
+use crate::support::{self, REDACTED};
+use crate::types::{ConfigScope, ValidationError};
+use crate::AppState;
+
+/// The claude.ai account from `/login`, as recorded in `~/.claude.json`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthAccount {
+    pub email: Option<String>,
+    pub organization: Option<String>,
+}
+
+/// Result of `get_auth_status`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthStatus {
+    /// Kind of the source Claude Code would use, or `null` when none is set.
+    pub active: Option<&'static str>,
+    /// Every source in precedence order.
+    pub sources: Vec<CredentialSource>,
+    pub account: Option<AuthAccount>,
+}
+
+/// An apiKeyHelper script still running after this long is killed.
+const HELPER_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// An apiKeyHelper script on disk.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HelperScript {
+    pub path: String,
+    /// `null` when the file does not exist yet.
+    pub content: Option<String>,
+    /// Shebang and permission problems.
+    pub warnings: Vec<String>,
+}
+
+/// Result of `test_api_key_helper`. The key itself is never returned.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HelperTestResult {
+    pub command: String,
+    /// The helper exited with status 0 within the timeout.
+    pub ok: bool,
+    /// The output looks like a single API key.
+    pub plausible: bool,
+    /// Masked output, see `platform::auth::mask`.
+    pub hint: Option<String>,
+    pub exit_code: Option<i32>,
+    pub duration_ms: f64,
+    pub timed_out: bool,
+    /// Error output with secrets masked, including any copy of the key.
+    pub stderr: String,
+    /// Problems with the command path.
+    pub warnings: Vec<String>,
+}
+
+/// Resolves a helper script path the way the `apiKeyHelper` setting names
+/// it: `~/` is the home directory, relative paths start at `cwd`.
+fn resolve_script_path(path: &str, cwd: &Path) -> Result<PathBuf, IpcError> {
+    match path.strip_prefix("~/") {
+        Some(rest) => Ok(paths::home_dir()?.join(rest)),
+        None => Ok(cwd.join(path)),
+    }
+}
+
+/// Resolves where `write_api_key_helper_script` may write: `path` relative
+/// to the config dir, never outside it.
+fn helper_script_target(config_dir: &Path, path: &str) -> Result<PathBuf, IpcError> {
+    Ok(config_dir.join(args::relative_path("path", path)?))
+}
+
+/// Masks secrets in a helper's error output, including any copy of what it
+/// printed on stdout.
+fn mask_stderr(stderr: &str, key: &str) -> String {
+    let stderr = if key.is_empty() {
+        stderr.to_string()
+    } else {
+        stderr.replace(key, REDACTED)
+    };
+    support::redact_text(&stderr)
+}
+
+fn working_dir(project_root: &Option<String>) -> Result<PathBuf, IpcError> {
+    match project_root {
+        Some(root) => Ok(PathBuf::from(root)),
+        None => paths::home_dir(),
+    }
+}
+
+fn describe_script(path: &Path) -> Result<HelperScript, IpcError> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => Some(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+            return Err(IpcError::PermissionDenied {
+                path: path.display().to_string(),
+            })
+        }
+        Err(e) => {
+            return Err(IpcError::PlatformError {
+                message: format!("Failed to read {}: {}", path.display(), e),
+            })
+        }
+    };
+    let warnings = match &content {
+        Some(content) => {
+            #[cfg(unix)]
+            let mode = {
+                use std::os::unix::fs::PermissionsExt;
+                fs::metadata(path).ok().map(|m| m.permissions().mode())
+            };
+            #[cfg(not(unix))]
+            let mode = None;
+            auth::check_helper_script(content, mode)
+        }
+        None => Vec::new(),
+    };
+    Ok(HelperScript {
+        path: path.display().to_string(),
+        content,
+        warnings,
+    })
+}
+
+/// IPC command: reads an apiKeyHelper script and checks its shebang and
+/// permissions. A missing file returns `content: null`.
+#[tauri::command]
+pub fn read_api_key_helper_script(
+    path: String,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<HelperScript, IpcError> {
+    let project_root = state.project_root(&window);
+    describe_script(&resolve_script_path(&path, &working_dir(&project_root)?)?)
+}
+
+/// IPC command: creates or replaces an apiKeyHelper script and marks it
+/// executable. `path` is relative to the config dir (`~/.claude/`), so only
+/// scripts inside it can be written. Returns the script with any remaining
+/// warnings. Does not change the `apiKeyHelper` setting itself.
+#[tauri::command]
+pub fn write_api_key_helper_script(
+    path: String,
+    content: String,
+    state: State<AppState>,
+) -> Result<HelperScript, IpcError> {
+    state.ensure_writable()?;
+    let path = helper_script_target(&paths::resolve_config_dir()?, &path)?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| IpcError::WriteFailed {
+            path: parent.display().to_string(),
+            message: format!("Failed to create directory: {}", e),
+        })?;
+    }
+    atomic_write(&path, content.as_bytes())?;
+    make_executable(&path)?;
+    describe_script(&path)
+}
+
+/// IPC command: runs an apiKeyHelper command and reports whether it prints
+/// something shaped like an API key.
+///
+/// Uses `command`, or the effective `apiKeyHelper` setting when it is
+/// omitted. The command runs through the user's shell in the project root
+/// (or home directory) and is killed after 10 seconds. Only a masked hint
+/// of the output is returned.
+#[tauri::command]
+pub async fn test_api_key_helper(
+    command: Option<String>,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<HelperTestResult, IpcError> {
+    logging::async_command("test_api_key_helper", async move {
+        let project_root = state.project_root(&window);
+        blocking::run("test_api_key_helper", move || {
+            let command = match command {
+                Some(command) => command,
+                None => {
+                    let effective = effective_settings(&project_root)?;
+                    lookup(&effective.settings, "apiKeyHelper")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or_default()
+                        .to_string()
+                }
+            };
+            if command.trim().is_empty() {
+                return Err(IpcError::ValidationError {
+                    errors: vec![ValidationError {
+                        path: "apiKeyHelper".to_string(),
+                        message: "No apiKeyHelper is configured".to_string(),
+                        code: "MISSING_API_KEY_HELPER".to_string(),
+                    }],
+                });
+            }
+
+            let cwd = working_dir(&project_root)?;
+            let warnings = check_command(&command, &paths::home_dir()?, &cwd);
+            let run = run_command(&command, &cwd, &[], HELPER_TIMEOUT)?;
+            let output = run.stdout.trim();
+
+            Ok(HelperTestResult {
+                ok: run.exit_code == Some(0) && !run.timed_out,
+                plausible: auth::looks_like_key(output),
+                hint: (!output.is_empty()).then(|| auth::mask(output)),
+                exit_code: run.exit_code,
+                duration_ms: run.duration_ms,
+                timed_out: run.timed_out,
+                stderr: mask_stderr(&run.stderr, output),
+                warnings,
+                command,
+            })
+        })
+        .await
+    })
+    .await
+}
+
+fn effective_settings(project_root: &Option<String>) -> Result<EffectiveConfig, IpcError> {
+    let user = read_scope(&ConfigScope::User, project_root)?;
+    let project = read_scope(&ConfigScope::Project, project_root)?;
+    let local = read_scope(&ConfigScope::Local, project_root)?;
+    let managed = read_scope(&ConfigScope::Managed, project_root)?;
+    Ok(merge::compute_effective(
+        user.as_ref(),
+        project.as_ref(),
+        local.as_ref(),
+        managed.as_ref(),
+    ))
+}
+
+/// IPC command: reports which credentials Claude Code would authenticate
+/// with, without revealing them.
+///
+/// Checks, in precedence order: Bedrock/Vertex switches, `ANTHROPIC_AUTH_TOKEN`,
+/// `ANTHROPIC_API_KEY` (settings `env` first, then ssenrah's environment),
+/// `apiKeyHelper`, and the `/login` credentials in the keychain or
+/// `.credentials.json`. Secrets are reduced to their last four characters.
+#[tauri::command]
+pub fn get_auth_status(
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<AuthStatus, IpcError> {
+    let project_root = state.project_root(&window);
+    let effective = effective_settings(&project_root)?;
+
+    // Settings `env` is applied on top of the inherited environment.
+    let env_var = |name: &str| -> Option<(String, String)> {
+        let key = format!("env.{}", name);
+        if let Some(value) = lookup(&effective.settings, &key).and_then(|v| v.as_str()) {
+            let scope = source_of(&effective, &key).unwrap_or_default();
+            return Some((value.to_string(), format!("settings env ({})", scope)));
+        }
+        std::env::var(name)
+            .ok()
+            .map(|value| (value, "process env".to_string()))
+    };
+    let non_empty = |found: Option<(String, String)>| found.filter(|(v, _)| !v.trim().is_empty());
+
+    let flag = |kind: &'static str, name: &str| match env_var(name) {
+        Some((value, origin)) if auth::is_truthy(&value) => CredentialSource {
+            kind,
+            present: true,
+            origin: Some(origin),
+            hint: Some(name.to_string()),
+        },
+        _ => CredentialSource::absent(kind),
+    };
+    let secret = |kind: &'static str, name: &str| match non_empty(env_var(name)) {
+        Some((value, origin)) => CredentialSource {
+            kind,
+            present: true,
+            origin: Some(origin),
+            hint: Some(auth::mask(&value)),
+        },
+        None => CredentialSource::absent(kind),
+    };
+
+    let helper = match lookup(&effective.settings, "apiKeyHelper").and_then(|v| v.as_str()) {
+        Some(command) if !command.trim().is_empty() => CredentialSource {
+            kind: "apiKeyHelper",
+            present: true,
+            origin: source_of(&effective, "apiKeyHelper"),
+            // The helper is a command, not a secret, but it may embed one.
+            hint: None,
+        },
+        _ => CredentialSource::absent("apiKeyHelper"),
+    };
+    let oauth = match auth::oauth_credentials(&paths::resolve_config_dir()?) {
+        Some(origin) => CredentialSource {
+            kind: "oauth",
+            present: true,
+            origin: Some(origin),
+            hint: None,
+        },
+        None => CredentialSource::absent("oauth"),
+    };
+
+    let sources = vec![
+        flag("bedrock", "CLAUDE_CODE_USE_BEDROCK"),
+        flag("vertex", "CLAUDE_CODE_USE_VERTEX"),
+        secret("authToken", "ANTHROPIC_AUTH_TOKEN"),
+        secret("apiKey", "ANTHROPIC_API_KEY"),
+        helper,
+        oauth,
+    ];
+
+    let account = resolve_mcp_path("user", &project_root)
+        .ok()
+        .and_then(|path| read_json_file(&path).ok().flatten())
+        .and_then(|json| {
+            let account = json.get("oauthAccount")?;
+            let field = |key: &str| account.get(key).and_then(|v| v.as_str()).map(String::from);
+            Some(AuthAccount {
+                email: field("emailAddress"),
+                organization: field("organizationName"),
+            })
+        });
+
+    Ok(AuthStatus {
+        active: auth::active(&sources),
+        sources,
+        account,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_helper_scripts_only_inside_the_config_dir() {
+        let config_dir = Path::new("/home/me/.claude");
+        assert_eq!(
+            helper_script_target(config_dir, "bin/api-key.sh").unwrap(),
+            config_dir.join("bin/api-key.sh")
+        );
+        for path in ["../.bashrc", "bin/../../.bashrc", "/home/me/.bashrc", ""] {
+            assert!(helper_script_target(config_dir, path).is_err(), "{}", path);
+        }
+    }
+
+    #[test]
+    fn masks_the_key_in_stderr() {
+        let key = "abcdefghijklmnopqrstuvwxyz012345";
+        let stderr = format!("+ echo {}\n+ ANTHROPIC_API_KEY=secret\n", key);
+        let masked = mask_stderr(&stderr, key);
+        assert!(!masked.contains(key) && !masked.contains("secret"));
+        let plain = "curl: (6) no host\n";
+        assert_eq!(mask_stderr(plain, ""), plain);
+    }
+}
@@ -0,0 +1,231 @@
+use serde::Serialize;
+use serde_json::Value;
+use tauri::State;
+
+use crate::args;
+use crate::commands::agents::agent_filename;
+use crate::commands::skills::resolve_skills_dir;
+use crate::errors::IpcError;
+use crate::frontmatter::{parse_frontmatter, serialize_frontmatter};
+use crate::io::journal::Transaction;
+use crate::io::trash::{self, TrashItem};
+use crate::platform::paths::resolve_agents_dir;
+use crate::AppState;
+
+/// An item a bulk operation left alone, and why.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkSkip {
+    pub name: String,
+    pub reason: String,
+}
+
+/// Aggregated result of a bulk operation.
+///
+/// Items are checked first; those that cannot be processed are listed in
+/// `skipped` and the rest are changed in one journaled transaction, so
+/// either all of `changed` happened or none did.
+#[derive(Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkResult {
+    /// Paths written or deleted.
+    pub changed: Vec<String>,
+    pub skipped: Vec<BulkSkip>,
+    /// Trash entries for deleted items, restorable with `restore_deleted`.
+    pub trashed: Vec<TrashItem>,
+}
+
+impl BulkResult {
+    fn skip(&mut self, name: &str, reason: impl Into<String>) {
+        self.skipped.push(BulkSkip {
+            name: name.to_string(),
+            reason: reason.into(),
+        });
+    }
+}
+
+/// Commits `txn`, discarding the trash copies taken for it if that fails.
+fn commit(txn: Transaction, result: BulkResult) -> Result<BulkResult, IpcError> {
+    if result.changed.is_empty() {
+        return Ok(result);
+    }
+    if let Err(e) = txn.commit() {
+        for item in &result.trashed {
+            trash::discard(&item.id);
+        }
+        return Err(e);
+    }
+    Ok(result)
+}
+
+/// IPC command: deletes several agent .md files of one scope at once.
+///
+/// Each file is copied to ssenrah's trash before the deletions are applied
+/// together. Missing files are skipped.
+#[tauri::command]
+pub fn bulk_delete_agents(
+    scope: String,
+    filenames: Vec<String>,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<BulkResult, IpcError> {
+    state.ensure_writable()?;
+    let project_root = state.project_root(&window);
+    let dir = resolve_agents_dir(&scope, &project_root)?;
+
+    let mut result = BulkResult::default();
+    let mut txn = Transaction::begin("bulk-delete-agents")?;
+    for name in &filenames {
+        let path = match agent_filename(name) {
+            Ok(filename) => dir.join(filename),
+            Err(e) => {
+                result.skip(name, e.to_string());
+                continue;
+            }
+        };
+        if !path.is_file() {
+            result.skip(name, "Agent not found");
+            continue;
+        }
+        txn.remove(&path)?;
+        match trash::copy_to_trash("agent", &scope, &path) {
+            Ok(item) => result.trashed.push(item),
+            Err(e) => {
+                for item in &result.trashed {
+                    trash::discard(&item.id);
+                }
+                return Err(e);
+            }
+        }
+        result.changed.push(path.display().to_string());
+    }
+    commit(txn, result)
+}
+
+/// IPC command: copies several skills from one scope to another at once.
+///
+/// Skills that already exist in the target scope are skipped unless
+/// `overwrite` is set.
+#[tauri::command]
+pub fn bulk_copy_skills(
+    from_scope: String,
+    to_scope: String,
+    directories: Vec<String>,
+    overwrite: Option<bool>,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<BulkResult, IpcError> {
+    state.ensure_writable()?;
+    let project_root = state.project_root(&window);
+
+    if from_scope == to_scope {
+        return Err(IpcError::InvalidArgument {
+            argument: "toScope".to_string(),
+            message: "Source and target scope are the same".to_string(),
+        });
+    }
+    let source_base = resolve_skills_dir(&from_scope, &project_root)?;
+    let target_base = resolve_skills_dir(&to_scope, &project_root)?;
+
+    let mut result = BulkResult::default();
+    let mut txn = Transaction::begin("bulk-copy-skills")?;
+    for directory in &directories {
+        if let Err(e) = args::file_name("directories", directory) {
+            result.skip(directory, e.to_string());
+            continue;
+        }
+        let source = source_base.join(directory);
+        let target = target_base.join(directory);
+        if !source.exists() {
+            result.skip(directory, "Skill not found");
+            continue;
+        }
+        if target.exists() && !overwrite.unwrap_or(false) {
+            result.skip(directory, format!("Already exists in {} scope", to_scope));
+            continue;
+        }
+        txn.copy(&target, &source)?;
+        result.changed.push(target.display().to_string());
+    }
+    commit(txn, result)
+}
+
+/// Applies `patch` to `frontmatter` as a JSON merge patch of its top-level
+/// keys: `null` removes a key, any other value sets it.
+fn apply_patch(frontmatter: &mut Value, patch: &serde_json::Map<String, Value>) {
+    if !frontmatter.is_object() {
+        *frontmatter = Value::Object(Default::default());
+    }
+    let Some(map) = frontmatter.as_object_mut() else {
+        return;
+    };
+    for (key, value) in patch {
+        if value.is_null() {
+            map.remove(key);
+        } else {
+            map.insert(key.clone(), value.clone());
+        }
+    }
+}
+
+/// IPC command: sets or removes frontmatter keys on several agents of one
+/// scope at once, e.g. `{ "model": "sonnet", "color": null }`.
+///
+/// Bodies are left untouched. Missing files are skipped.
+#[tauri::command]
+pub fn bulk_update_frontmatter(
+    scope: String,
+    filenames: Vec<String>,
+    patch: serde_json::Map<String, Value>,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<BulkResult, IpcError> {
+    state.ensure_writable()?;
+    let project_root = state.project_root(&window);
+    let dir = resolve_agents_dir(&scope, &project_root)?;
+
+    let mut result = BulkResult::default();
+    let mut txn = Transaction::begin("bulk-update-frontmatter")?;
+    for name in &filenames {
+        let path = match agent_filename(name) {
+            Ok(filename) => dir.join(filename),
+            Err(e) => {
+                result.skip(name, e.to_string());
+                continue;
+            }
+        };
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            result.skip(name, "Agent not found or unreadable");
+            continue;
+        };
+
+        let (mut frontmatter, body) = parse_frontmatter(&content);
+        apply_patch(&mut frontmatter, &patch);
+        let fm = serialize_frontmatter(&frontmatter);
+        let updated = if fm.is_empty() {
+            body
+        } else {
+            format!("---\n{}\n---\n\n{}", fm, body)
+        };
+        txn.write(&path, updated.as_bytes())?;
+        result.changed.push(path.display().to_string());
+    }
+    commit(txn, result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn patch_sets_and_removes_keys() {
+        let mut frontmatter = json!({ "name": "reviewer", "color": "red" });
+        let patch = json!({ "model": "sonnet", "color": null });
+        apply_patch(&mut frontmatter, patch.as_object().unwrap());
+        assert_eq!(
+            frontmatter,
+            json!({ "name": "reviewer", "model": "sonnet" })
+        );
+    }
+}
@@ -0,0 +1,70 @@
+use std::path::Path;
+
+use tauri::State;
+
+use crate::bundle::{self, BundleInclude, ExportBundleResult, ImportBundleResult, ImportStrategy};
+use crate::errors::IpcError;
+use crate::io::blocking;
+use crate::logging;
+use crate::AppState;
+
+/// IPC command: exports configuration into a single `.tar.gz` bundle.
+///
+/// `scopes` selects from "user", "project", "project_root", and "local";
+/// `include` selects artifact kinds. The archive contains `manifest.json`
+/// plus one entry per file under `{scope}/...`. For the user scope only the
+/// `mcpServers` section of ~/.claude.json is exported (as `user/mcp.json`).
+#[tauri::command]
+pub async fn export_config_bundle(
+    scopes: Vec<String>,
+    include: Option<BundleInclude>,
+    destination: String,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<ExportBundleResult, IpcError> {
+    logging::async_command("export_config_bundle", async move {
+        let project_root = state.project_root(&window);
+        blocking::run("export_config_bundle", move || {
+            bundle::export(
+                &scopes,
+                &include.unwrap_or_default(),
+                destination,
+                &project_root,
+            )
+        })
+        .await
+    })
+    .await
+}
+
+/// IPC command: restores a bundle created by `export_config_bundle`.
+///
+/// `strategy` decides what happens to files that already exist:
+/// - `overwrite`: replace them
+/// - `merge`: settings and MCP JSON are merged (bundle wins per field, using
+///   the same semantics as the effective-config merge); differing markdown is
+///   left alone and reported as a conflict
+/// - `skip_existing`: leave them untouched
+///
+/// Only scopes listed in `scopes` (default: all in the bundle) are restored.
+/// Every file gets an entry in the per-file report. All files are written in
+/// one journaled transaction, so a failure leaves the configuration as it
+/// was.
+#[tauri::command]
+pub async fn import_config_bundle(
+    path: String,
+    strategy: ImportStrategy,
+    scopes: Option<Vec<String>>,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<ImportBundleResult, IpcError> {
+    logging::async_command("import_config_bundle", async move {
+        state.ensure_writable()?;
+        let project_root = state.project_root(&window);
+        blocking::run("import_config_bundle", move || {
+            bundle::import(Path::new(&path), strategy, scopes.as_deref(), &project_root)
+        })
+        .await
+    })
+    .await
+}
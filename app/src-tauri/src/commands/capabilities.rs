@@ -0,0 +1,90 @@
+use std::path::PathBuf;
+use std::sync::atomic::Ordering;
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::errors::IpcError;
+use crate::platform::detect;
+use crate::platform::paths;
+use crate::AppState;
+
+/// Access to one configuration scope's directory.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScopeCapability {
+    /// "user", "project", or "managed".
+    pub scope: String,
+    pub path: String,
+    pub exists: bool,
+    pub can_read: bool,
+    /// `true` if the directory (or, when missing, its nearest existing
+    /// ancestor) accepts new files.
+    pub can_write: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Capabilities {
+    /// App-level read-only switch; see `set_read_only`.
+    pub read_only: bool,
+    pub scopes: Vec<ScopeCapability>,
+}
+
+fn scope_capability(scope: &str, dir: PathBuf) -> ScopeCapability {
+    let access = detect::probe_dir_access(&dir);
+    ScopeCapability {
+        scope: scope.to_string(),
+        path: dir.display().to_string(),
+        exists: access.exists,
+        can_read: access.readable,
+        can_write: access.writable,
+    }
+}
+
+/// IPC command: reports what the app can do on this machine.
+///
+/// Probes the user config dir, the open project's `.claude/` dir, and the
+/// managed-settings dir for read and write access, so the frontend can
+/// disable editors for scopes it could not save. The project scope is
+/// omitted when no project is open, the managed scope on platforms without
+/// one.
+#[tauri::command]
+pub fn get_capabilities(
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<Capabilities, IpcError> {
+    let project_root = state.project_root(&window);
+
+    let mut scopes = vec![scope_capability("user", paths::resolve_config_dir()?)];
+    if let Some(root) = project_root.as_ref() {
+        scopes.push(scope_capability(
+            "project",
+            PathBuf::from(root).join(".claude"),
+        ));
+    }
+    if let Some(dir) = paths::resolve_managed_settings_dir() {
+        scopes.push(scope_capability("managed", dir));
+    }
+
+    Ok(Capabilities {
+        read_only: state.read_only.load(Ordering::SeqCst),
+        scopes,
+    })
+}
+
+/// IPC command: turns the app-level read-only mode on or off.
+///
+/// While it is on, every command that writes, deletes, or renames files
+/// fails with `IpcError::ReadOnly`, which makes it safe to browse someone
+/// else's checkout. Reads, validation, and exports to a new file still work.
+#[tauri::command]
+pub fn set_read_only(
+    enabled: bool,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<Capabilities, IpcError> {
+    state.read_only.store(enabled, Ordering::SeqCst);
+    tracing::info!(enabled, "read-only mode changed");
+    get_capabilities(window, state)
+}
@@ -0,0 +1,124 @@
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+use tauri::State;
+
+use crate::claude_json::{self, ClaudeJsonSection, ProjectEntry, ProjectTrust};
+use crate::errors::IpcError;
+use crate::io::backup;
+use crate::io::blocking;
+use crate::io::lock::lock_path;
+use crate::logging;
+use crate::AppState;
+
+/// Result of `set_project_trust`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrustChange {
+    pub trust: ProjectTrust,
+    /// Backup of ~/.claude.json taken before the change, if there was a file.
+    pub backup_path: Option<String>,
+}
+
+/// IPC command: reads one section of ~/.claude.json.
+///
+/// `section` is one of `claude_json::SECTIONS`: "mcpServers", "projects"
+/// (per-directory trust, allowed tools, and history), "onboarding",
+/// "account", or "other". The result is `{ section, value }` with known
+/// fields type-checked, or `null` when the file does not exist.
+#[tauri::command]
+pub async fn read_claude_json(section: String) -> Result<Option<ClaudeJsonSection>, IpcError> {
+    logging::async_command("read_claude_json", async move {
+        blocking::run("read_claude_json", move || {
+            claude_json::read_section(&claude_json::claude_json_path()?, &section)
+        })
+        .await
+    })
+    .await
+}
+
+/// IPC command: patches the ~/.claude.json `projects` entry for `dir`.
+///
+/// Keys in `patch` are set, or removed when `null`; e.g.
+/// `{ "hasTrustDialogAccepted": null }` resets the trust prompt. The rest of
+/// the file is left untouched. Returns the updated entry.
+#[tauri::command]
+pub async fn update_claude_json_project(
+    dir: String,
+    patch: Map<String, Value>,
+    state: State<'_, AppState>,
+) -> Result<ProjectEntry, IpcError> {
+    logging::async_command("update_claude_json_project", async move {
+        state.ensure_writable()?;
+        blocking::run("update_claude_json_project", move || {
+            claude_json::update_project_entry(&claude_json::claude_json_path()?, &dir, &patch)
+        })
+        .await
+    })
+    .await
+}
+
+/// IPC command: removes the ~/.claude.json `projects` entry for `dir`, so
+/// Claude Code treats the directory as never opened.
+#[tauri::command]
+pub async fn remove_claude_json_project(
+    dir: String,
+    state: State<'_, AppState>,
+) -> Result<(), IpcError> {
+    logging::async_command("remove_claude_json_project", async move {
+        state.ensure_writable()?;
+        blocking::run("remove_claude_json_project", move || {
+            claude_json::remove_project_entry(&claude_json::claude_json_path()?, &dir)
+        })
+        .await
+    })
+    .await
+}
+
+/// IPC command: reports whether Claude Code trusts the directory `path`,
+/// either directly or through a trusted parent directory.
+#[tauri::command]
+pub async fn get_project_trust(path: String) -> Result<ProjectTrust, IpcError> {
+    logging::async_command("get_project_trust", async move {
+        blocking::run("get_project_trust", move || {
+            claude_json::project_trust(&claude_json::claude_json_path()?, Path::new(&path))
+        })
+        .await
+    })
+    .await
+}
+
+/// IPC command: marks the directory `path` as trusted or untrusted in
+/// ~/.claude.json, so Claude Code skips or shows its trust prompt there.
+///
+/// `confirmed` must be `true`: trusting a directory lets its settings run
+/// hooks and commands, so the frontend sets it only after the user agreed.
+/// ~/.claude.json is backed up to ssenrah's backups directory first.
+#[tauri::command]
+pub async fn set_project_trust(
+    path: String,
+    trusted: bool,
+    confirmed: bool,
+    state: State<'_, AppState>,
+) -> Result<TrustChange, IpcError> {
+    logging::async_command("set_project_trust", async move {
+        state.ensure_writable()?;
+        if !confirmed {
+            return Err(IpcError::ConfirmationRequired {
+                message:
+                    "Changing project trust affects what Claude Code runs; confirm the change first"
+                        .to_string(),
+            });
+        }
+        blocking::run("set_project_trust", move || {
+            let file = claude_json::claude_json_path()?;
+            let _lock = lock_path(&file);
+            let backup_path = backup::backup_file("trust", &file)?.map(|p| p.display().to_string());
+            let trust = claude_json::set_project_trust(&file, Path::new(&path), trusted)?;
+            Ok(TrustChange { trust, backup_path })
+        })
+        .await
+    })
+    .await
+}
@@ -0,0 +1,168 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::commands::skills::resolve_skills_dir;
+use crate::config::read_json_file;
+use crate::errors::IpcError;
+use crate::frontmatter::parse_frontmatter;
+use crate::platform::paths::{resolve_agents_dir, resolve_mcp_path};
+use crate::AppState;
+
+/// Scopes from lowest to highest precedence; a later scope shadows an
+/// earlier one when both define the same name.
+const SCOPES_BY_PRECEDENCE: &[&str] = &["user", "project"];
+
+/// Where one definition of a name lives.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Definition {
+    pub scope: String,
+    pub path: String,
+}
+
+/// A name defined in more than one scope.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigConflict {
+    /// "agent", "skill", or "mcp_server".
+    pub kind: String,
+    pub name: String,
+    /// The definition Claude Code uses.
+    pub effective: Definition,
+    /// Definitions hidden by `effective`, highest precedence first.
+    pub shadowed: Vec<Definition>,
+}
+
+type DefinitionsByName = BTreeMap<String, Vec<Definition>>;
+
+/// Agents are identified by their frontmatter `name`, falling back to the
+/// filename without `.md`.
+fn collect_agents(project_root: &Option<String>) -> DefinitionsByName {
+    let mut names = DefinitionsByName::new();
+    for scope in SCOPES_BY_PRECEDENCE {
+        let Ok(dir) = resolve_agents_dir(scope, project_root) else {
+            continue;
+        };
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("md") {
+                continue;
+            }
+            let content = fs::read_to_string(&path).unwrap_or_default();
+            let (frontmatter, _) = parse_frontmatter(&content);
+            let name = frontmatter
+                .get("name")
+                .and_then(|v| v.as_str())
+                .map(String::from)
+                .unwrap_or_else(|| {
+                    path.file_stem()
+                        .map(|s| s.to_string_lossy().to_string())
+                        .unwrap_or_default()
+                });
+            push(&mut names, name, scope, path);
+        }
+    }
+    names
+}
+
+/// Skills are identified by their directory name or single-file stem.
+fn collect_skills(project_root: &Option<String>) -> DefinitionsByName {
+    let mut names = DefinitionsByName::new();
+    for scope in SCOPES_BY_PRECEDENCE {
+        let Ok(dir) = resolve_skills_dir(scope, project_root) else {
+            continue;
+        };
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let is_skill = if path.is_dir() {
+                path.join("SKILL.md").exists()
+            } else {
+                path.extension().and_then(|e| e.to_str()) == Some("md")
+            };
+            if !is_skill {
+                continue;
+            }
+            let name = entry
+                .file_name()
+                .to_string_lossy()
+                .trim_end_matches(".md")
+                .to_string();
+            push(&mut names, name, scope, path);
+        }
+    }
+    names
+}
+
+/// MCP servers are identified by their key under `mcpServers`.
+fn collect_mcp_servers(project_root: &Option<String>) -> DefinitionsByName {
+    let mut names = DefinitionsByName::new();
+    for scope in SCOPES_BY_PRECEDENCE {
+        let Ok(path) = resolve_mcp_path(scope, project_root) else {
+            continue;
+        };
+        let Ok(Some(config)) = read_json_file(&path) else {
+            continue;
+        };
+        if let Some(servers) = config.get("mcpServers").and_then(|v| v.as_object()) {
+            for name in servers.keys() {
+                push(&mut names, name.clone(), scope, path.clone());
+            }
+        }
+    }
+    names
+}
+
+fn push(names: &mut DefinitionsByName, name: String, scope: &str, path: PathBuf) {
+    names.entry(name).or_default().push(Definition {
+        scope: scope.to_string(),
+        path: path.display().to_string(),
+    });
+}
+
+fn conflicts(kind: &str, names: DefinitionsByName, out: &mut Vec<ConfigConflict>) {
+    for (name, mut definitions) in names {
+        if definitions.len() < 2 {
+            continue;
+        }
+        // Collected lowest precedence first; the last one wins.
+        definitions.reverse();
+        let effective = definitions.remove(0);
+        out.push(ConfigConflict {
+            kind: kind.to_string(),
+            name,
+            effective,
+            shadowed: definitions,
+        });
+    }
+}
+
+/// IPC command: reports agents, skills, and MCP servers whose names are
+/// defined more than once across the user and project scopes.
+///
+/// For each duplicate the project definition is reported as effective and the
+/// user definition as shadowed, matching Claude Code's precedence. Duplicate
+/// agent names within one scope are reported the same way.
+#[tauri::command]
+pub fn detect_config_conflicts(
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<Vec<ConfigConflict>, IpcError> {
+    let project_root = state.project_root(&window);
+
+    let mut out = Vec::new();
+    conflicts("agent", collect_agents(&project_root), &mut out);
+    conflicts("skill", collect_skills(&project_root), &mut out);
+    conflicts("mcp_server", collect_mcp_servers(&project_root), &mut out);
+
+    Ok(out)
+}
@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Emitter, Manager, State};
+use url::Url;
+
+use crate::commands::windows::open_window;
+use crate::deep_link::{self, ArtifactLink};
+use crate::errors::IpcError;
+
+/// Artifacts waiting to be shown, keyed by window label. A window opened
+/// for a link has not loaded yet when the link arrives, so the link is
+/// parked here and the `deep_link` event only says there is one to take.
+pub struct DeepLinkState {
+    pub pending: Mutex<HashMap<String, ArtifactLink>>,
+}
+
+fn show(app: &AppHandle, label: &str) {
+    if let Some(window) = app.get_webview_window(label) {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+}
+
+fn dispatch(app: &AppHandle, url: &Url) -> Result<(), IpcError> {
+    let link = deep_link::parse(url).map_err(|message| IpcError::PlatformError { message })?;
+    let label = match link.project {
+        Some(project) => open_window(app, project)?.label,
+        None => "main".to_string(),
+    };
+    show(app, &label);
+    if let Some(artifact) = link.artifact {
+        app.state::<DeepLinkState>()
+            .pending
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(label.clone(), artifact);
+        let _ = app.emit_to(label.as_str(), "deep_link", ());
+    }
+    Ok(())
+}
+
+/// Handles URLs from the deep link plugin, including those forwarded from
+/// a second instance. Bad links are logged and dropped.
+pub(crate) fn handle_urls(app: &AppHandle, urls: &[Url]) {
+    for url in urls {
+        tracing::info!(url = %url, "deep link received");
+        if let Err(e) = dispatch(app, url) {
+            tracing::warn!(url = %url, error = %e, "ignored deep link");
+        }
+    }
+}
+
+/// IPC command: returns and clears the artifact a deep link asked this
+/// window to show. Called on load and on each `deep_link` event.
+#[tauri::command]
+pub fn take_pending_deep_link(
+    window: tauri::Window,
+    state: State<'_, DeepLinkState>,
+) -> Result<Option<ArtifactLink>, IpcError> {
+    Ok(state
+        .pending
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(window.label()))
+}
@@ -0,0 +1,26 @@
+use tauri::State;
+
+use crate::doctor::{self, DoctorReport};
+use crate::errors::IpcError;
+use crate::io::blocking;
+use crate::logging;
+use crate::AppState;
+
+/// IPC command: runs an end-to-end configuration health check.
+///
+/// Covers config dir writability, JSON syntax of every settings and MCP file,
+/// the lockfile, file watching, managed settings access, MCP server commands
+/// on PATH, hook script permissions, and `.gitignore` coverage of local
+/// files. Each finding carries a category, a stable code, a status, and a
+/// suggested fix. Passing checks are included so the UI can show them.
+#[tauri::command]
+pub async fn run_doctor(
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<DoctorReport, IpcError> {
+    logging::async_command("run_doctor", async move {
+        let project_root = state.project_root(&window);
+        blocking::run("run_doctor", move || Ok(doctor::run(&project_root, true))).await
+    })
+    .await
+}
@@ -0,0 +1,54 @@
+use tauri::State;
+
+use crate::drift::{self, Baseline, DriftReport};
+use crate::errors::IpcError;
+use crate::io::blocking;
+use crate::logging;
+use crate::AppState;
+
+/// IPC command: pins the window's current effective config as baseline
+/// `name` (letters, digits, `-`, `_`, `.`), replacing an existing one.
+///
+/// With `alert`, the window's file watcher emits a `config_drift` event
+/// carrying a `DriftReport` whenever a settings change makes the effective
+/// config differ from the baseline.
+#[tauri::command]
+pub async fn pin_baseline(
+    name: String,
+    alert: Option<bool>,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<Baseline, IpcError> {
+    logging::async_command("pin_baseline", async move {
+        let project_root = state.project_root(&window);
+        blocking::run("pin_baseline", move || {
+            drift::pin(&name, alert.unwrap_or(false), &project_root)
+        })
+        .await
+    })
+    .await
+}
+
+/// IPC command: reports the settings keys that changed between baseline
+/// `name` and the window's current effective config.
+#[tauri::command]
+pub async fn check_drift(
+    name: String,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<DriftReport, IpcError> {
+    logging::async_command("check_drift", async move {
+        let project_root = state.project_root(&window);
+        blocking::run("check_drift", move || drift::check(&name, &project_root)).await
+    })
+    .await
+}
+
+/// IPC command: lists pinned baselines by name.
+#[tauri::command]
+pub async fn list_baselines() -> Result<Vec<Baseline>, IpcError> {
+    logging::async_command("list_baselines", async move {
+        blocking::run("list_baselines", drift::list).await
+    })
+    .await
+}
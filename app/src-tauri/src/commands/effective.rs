@@ -1,65 +1,167 @@
+use std::time::Instant;
+
+use serde::Serialize;
 use tauri::State;
 
+use crate::config::{self, read_scope, EditPreview};
 use crate::errors::IpcError;
+use crate::io::blocking;
+use crate::logging;
+use crate::platform::env::{self, ShellExport};
 use crate::platform::paths;
-use crate::schema::merge;
+use crate::schema::effective_cache;
+use crate::schema::hooks::EffectiveHooks;
+use crate::schema::matrix::MatrixRow;
+use crate::schema::merge::{self, MergePolicy};
+use crate::schema::occurrences::{self, SettingOccurrence};
 use crate::types::ConfigScope;
 use crate::AppState;
 
-/// Reads a settings file for the given scope, returning `None` if the file
-/// does not exist. Parse errors and permission errors are propagated.
-fn read_scope(
-    scope: &ConfigScope,
-    project_root: &Option<String>,
-) -> Result<Option<serde_json::Value>, IpcError> {
-    let path = match paths::resolve_settings_path(scope, project_root) {
-        Ok(p) => p,
-        // If there is no project open, project/local scopes simply return None.
-        Err(IpcError::NoProject { .. }) => return Ok(None),
-        // Managed settings dir not supported on this platform -> None.
-        Err(IpcError::PlatformError { .. }) => return Ok(None),
-        Err(e) => return Err(e),
-    };
-
-    let path_str = path.to_string_lossy().to_string();
-
-    match std::fs::read_to_string(&path) {
-        Ok(contents) => {
-            let value: serde_json::Value =
-                serde_json::from_str(&contents).map_err(|e| IpcError::ParseError {
-                    path: path_str.clone(),
-                    message: e.to_string(),
-                })?;
-            Ok(Some(value))
-        }
-        Err(e) => match e.kind() {
-            std::io::ErrorKind::NotFound => Ok(None),
-            std::io::ErrorKind::PermissionDenied => Err(IpcError::PermissionDenied {
-                path: path_str,
-            }),
-            _ => Err(IpcError::PlatformError {
-                message: format!("Failed to read {}: {}", path_str, e),
-            }),
-        },
-    }
-}
-
 /// IPC command: computes the effective (merged) configuration from all 4 scopes.
 ///
 /// Returns a JSON object with:
 /// - `settings`: the merged configuration
 /// - `sources`: map of JSON path -> scope name
 /// - `overrides`: list of fields where higher scopes override lower scopes
+/// - `cache`: "hit" if the result was reused because no settings file
+///   changed since the last call, "miss" if it was recomputed
+/// - `computeMs`: time spent producing the result
 #[tauri::command]
-pub fn compute_effective_config(
-    state: State<AppState>,
+pub async fn compute_effective_config(
+    window: tauri::Window,
+    state: State<'_, AppState>,
 ) -> Result<serde_json::Value, IpcError> {
-    let project_root = state
-        .project_root
-        .lock()
-        .map_err(|e| IpcError::PlatformError {
-            message: format!("Failed to acquire state lock: {}", e),
-        })?;
+    logging::async_command("compute_effective_config", async move {
+        let project_root = state.project_root(&window);
+        blocking::run("compute_effective_config", move || {
+            let start = Instant::now();
+            let scopes = [
+                ConfigScope::User,
+                ConfigScope::Project,
+                ConfigScope::Local,
+                ConfigScope::Managed,
+            ];
+            let key = effective_cache::key(
+                &scopes
+                    .iter()
+                    .filter_map(|s| paths::resolve_settings_path(s, &project_root).ok())
+                    .collect::<Vec<_>>(),
+            );
+
+            let (mut value, cache) = match effective_cache::get(&key) {
+                Some(value) => (value, "hit"),
+                None => {
+                    let effective = config::compute_effective(&project_root)?;
+
+                    // Serialize the EffectiveConfig struct to a JSON Value for the frontend.
+                    let value =
+                        serde_json::to_value(&effective).map_err(|e| IpcError::PlatformError {
+                            message: format!("Failed to serialize effective config: {}", e),
+                        })?;
+                    effective_cache::put(key, value.clone());
+                    (value, "miss")
+                }
+            };
+
+            if let Some(obj) = value.as_object_mut() {
+                obj.insert("cache".into(), cache.into());
+                obj.insert(
+                    "computeMs".into(),
+                    (start.elapsed().as_secs_f64() * 1000.0).into(),
+                );
+            }
+            Ok(value)
+        })
+        .await
+    })
+    .await
+}
+
+/// IPC command: returns one row per settings key set in any scope, with the
+/// key's value in user, project, local, and managed settings, its effective
+/// value, the scope that provides it, and the merge rule applied.
+///
+/// Keys inside deep-merged objects such as `env` get a row each; any other
+/// value, including arrays, is one row.
+#[tauri::command]
+pub async fn compute_scope_matrix(
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<Vec<MatrixRow>, IpcError> {
+    logging::async_command("compute_scope_matrix", async move {
+        let project_root = state.project_root(&window);
+        blocking::run("compute_scope_matrix", move || {
+            config::compute_scope_matrix(&project_root)
+        })
+        .await
+    })
+    .await
+}
+
+/// IPC command: lists the hooks of every scope as Claude Code runs them.
+///
+/// The settings merge lets a higher scope's hook list for an event replace a
+/// lower one's, but at runtime every scope's hook groups run. Each hook is
+/// annotated with its scope and whether it runs (`disableAllHooks` and
+/// managed `allowManagedHooksOnly` can stop it), and hooks registered twice
+/// or matchers defined differently by several scopes are flagged.
+#[tauri::command]
+pub async fn compute_effective_hooks(
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<EffectiveHooks, IpcError> {
+    logging::async_command("compute_effective_hooks", async move {
+        let project_root = state.project_root(&window);
+        blocking::run("compute_effective_hooks", move || {
+            config::compute_effective_hooks(&project_root)
+        })
+        .await
+    })
+    .await
+}
+
+/// IPC command: previews the effective config as it would be if `proposed`
+/// were saved to `scope`, before anything is written.
+///
+/// Returns `effective` (same shape as `compute_effective_config` without the
+/// cache fields) and `changes`, the effective keys that would be added,
+/// removed, or changed compared with the current effective config.
+#[tauri::command]
+pub async fn preview_effective_with_edit(
+    scope: ConfigScope,
+    proposed: serde_json::Value,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<EditPreview, IpcError> {
+    logging::async_command("preview_effective_with_edit", async move {
+        let project_root = state.project_root(&window);
+        blocking::run("preview_effective_with_edit", move || {
+            config::preview_effective_with_edit(&scope, &proposed, &project_root)
+        })
+        .await
+    })
+    .await
+}
+
+/// IPC command: returns the scope precedence and per-field merge rules the
+/// effective config is computed with, so the UI can explain the result.
+#[tauri::command]
+pub fn get_merge_policy() -> Result<MergePolicy, IpcError> {
+    Ok(MergePolicy::default())
+}
+
+/// IPC command: reports every scope that sets a dot-separated key path (for
+/// example `env.ANTHROPIC_MODEL`), with the value in each scope and whether
+/// the effective merge keeps it, overrides it, or deep-merges it.
+///
+/// Occurrences are listed from lowest to highest precedence.
+#[tauri::command]
+pub fn find_setting_occurrences(
+    key_path: String,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<Vec<SettingOccurrence>, IpcError> {
+    let project_root = state.project_root(&window);
 
     let user = read_scope(&ConfigScope::User, &project_root)?;
     let project = read_scope(&ConfigScope::Project, &project_root)?;
@@ -73,8 +175,109 @@ pub fn compute_effective_config(
         managed.as_ref(),
     );
 
-    // Serialize the EffectiveConfig struct to a JSON Value for the frontend.
-    serde_json::to_value(&effective).map_err(|e| IpcError::PlatformError {
-        message: format!("Failed to serialize effective config: {}", e),
-    })
+    let scopes = [
+        ("user", ConfigScope::User, user),
+        ("project", ConfigScope::Project, project),
+        ("local", ConfigScope::Local, local),
+        ("managed", ConfigScope::Managed, managed),
+    ];
+
+    let mut found = Vec::new();
+    for (name, scope, settings) in &scopes {
+        let Some(value) = settings
+            .as_ref()
+            .and_then(|s| occurrences::lookup(s, &key_path))
+        else {
+            continue;
+        };
+        let file = paths::resolve_settings_path(scope, &project_root)?;
+        found.push(SettingOccurrence {
+            scope: name.to_string(),
+            file: file.display().to_string(),
+            value: value.clone(),
+            status: occurrences::classify(&effective, name, &key_path, value),
+        });
+    }
+
+    Ok(found)
+}
+
+/// One variable from the merged `env` settings block.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnvVarReport {
+    pub name: String,
+    pub value: String,
+    /// Scope that provides the effective value.
+    pub scope: Option<String>,
+    /// The value should be masked in the UI (API keys, tokens, proxy URLs).
+    pub sensitive: bool,
+    /// The value is an empty string, which unsets nothing but still shadows
+    /// the inherited value.
+    pub empty: bool,
+    /// Value of the variable in ssenrah's own process environment.
+    pub process_value: Option<String>,
+    /// Exports of the variable found in shell init files.
+    pub shell_exports: Vec<ShellExport>,
+    /// Claude Code will replace a different inherited value with this one.
+    pub overrides_inherited: bool,
+}
+
+/// IPC command: inspects the merged `env` block of the effective config.
+///
+/// Each variable is compared with the process environment and with exports
+/// in the user's shell init files (`~/.bashrc`, `~/.zshrc`, ...). Settings
+/// `env` wins over inherited values when Claude Code starts, so variables
+/// whose inherited value differs are flagged as overriding it. Empty values
+/// and well-known credential or proxy variables are flagged as well.
+#[tauri::command]
+pub fn inspect_env_settings(
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<Vec<EnvVarReport>, IpcError> {
+    let project_root = state.project_root(&window);
+
+    let user = read_scope(&ConfigScope::User, &project_root)?;
+    let project = read_scope(&ConfigScope::Project, &project_root)?;
+    let local = read_scope(&ConfigScope::Local, &project_root)?;
+    let managed = read_scope(&ConfigScope::Managed, &project_root)?;
+
+    let effective = merge::compute_effective(
+        user.as_ref(),
+        project.as_ref(),
+        local.as_ref(),
+        managed.as_ref(),
+    );
+
+    let Some(vars) = effective.settings.get("env").and_then(|e| e.as_object()) else {
+        return Ok(Vec::new());
+    };
+
+    let home = paths::home_dir()?;
+    let reports = vars
+        .iter()
+        .map(|(name, value)| {
+            let value = match value {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            let process_value = std::env::var(name).ok();
+            let shell_exports = env::shell_exports(&home, name);
+            let overrides_inherited = process_value.as_ref().is_some_and(|v| *v != value)
+                || shell_exports.iter().any(|e| e.value != value);
+
+            EnvVarReport {
+                scope: occurrences::source_of(&effective, &format!("env.{}", name)),
+                sensitive: env::is_sensitive(name),
+                empty: value.is_empty(),
+                name: name.clone(),
+                value,
+                process_value,
+                shell_exports,
+                overrides_inherited,
+            }
+        })
+        .collect();
+
+    Ok(reports)
 }
@@ -0,0 +1,62 @@
+use tauri::State;
+
+use crate::commands::memory::resolve_memory_path;
+use crate::commands::skills::resolve_skills_dir;
+use crate::errors::IpcError;
+use crate::io::stat::{self, FileStat};
+use crate::platform::paths::{self, resolve_agents_dir, resolve_mcp_path};
+use crate::types::ConfigScope;
+use crate::AppState;
+
+/// IPC command: reports existence, size, modification time, permissions,
+/// owner, and symlink status of a config artifact.
+///
+/// `kind` and the scopes it accepts:
+/// - `settings`: user, project, local, managed
+/// - `mcp`: user (~/.claude.json), project, managed
+/// - `memory`: user, project, project_root, local
+/// - `agents`, `skills`: user, project (the directory)
+///
+/// A missing file is not an error; it reports `exists: false`.
+#[tauri::command]
+pub fn stat_config_file(
+    kind: String,
+    scope: String,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<FileStat, IpcError> {
+    let project_root = state.project_root(&window);
+
+    let path = match kind.as_str() {
+        "settings" => {
+            let config_scope = match scope.as_str() {
+                "user" => ConfigScope::User,
+                "project" => ConfigScope::Project,
+                "local" => ConfigScope::Local,
+                "managed" => ConfigScope::Managed,
+                other => {
+                    return Err(IpcError::InvalidArgument {
+                        argument: "scope".to_string(),
+                        message: format!("Unknown settings scope: {}", other),
+                    })
+                }
+            };
+            paths::resolve_settings_path(&config_scope, &project_root)?
+        }
+        "mcp" => resolve_mcp_path(&scope, &project_root)?,
+        "memory" => resolve_memory_path(&scope, &project_root)?,
+        "agents" => resolve_agents_dir(&scope, &project_root)?,
+        "skills" => resolve_skills_dir(&scope, &project_root)?,
+        other => {
+            return Err(IpcError::InvalidArgument {
+                argument: "kind".to_string(),
+                message: format!(
+                    "Unknown config kind '{}'. Expected settings, mcp, memory, agents, or skills",
+                    other
+                ),
+            })
+        }
+    };
+
+    Ok(stat::stat(&path))
+}
@@ -0,0 +1,334 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::errors::IpcError;
+use crate::fixes::{self, FIX_IDS};
+use crate::git::check_local_gitignore;
+use crate::io::atomic::atomic_write;
+use crate::io::backup;
+use crate::io::lock::lock_path;
+use crate::platform::paths::{self, resolve_mcp_path};
+use crate::schema::hooks;
+use crate::schema::migrations;
+use crate::types::ConfigScope;
+use crate::AppState;
+
+/// Outcome of `apply_fix`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FixResult {
+    pub fix_id: String,
+    /// The file or directory the fix touched.
+    pub path: String,
+    /// `false` when there was nothing to fix.
+    pub changed: bool,
+    pub message: String,
+    pub backup_path: Option<String>,
+}
+
+/// Backs up a file before a fix changes its content.
+fn backup_before_fix(fix_id: &str, path: &Path) -> Result<Option<String>, IpcError> {
+    let dir = backup::backup_file(&format!("fix-{}", fix_id), path)?;
+    Ok(dir.map(|d| d.display().to_string()))
+}
+
+fn require_target(target: Option<String>, fix_id: &str) -> Result<String, IpcError> {
+    target
+        .filter(|t| !t.is_empty())
+        .ok_or_else(|| IpcError::InvalidArgument {
+            argument: "target".to_string(),
+            message: format!("Fix '{}' requires a target", fix_id),
+        })
+}
+
+fn require_project(project_root: &Option<String>) -> Result<PathBuf, IpcError> {
+    project_root
+        .as_ref()
+        .map(PathBuf::from)
+        .ok_or_else(|| IpcError::NoProject {
+            message: "No project is open. Open a project before applying this fix.".to_string(),
+        })
+}
+
+/// Config files `remove_trailing_commas` may rewrite.
+fn json_config_files(project_root: &Option<String>) -> Vec<PathBuf> {
+    let settings = [ConfigScope::User, ConfigScope::Project, ConfigScope::Local]
+        .iter()
+        .filter_map(|s| paths::resolve_settings_path(s, project_root).ok());
+    let mcp = ["user", "project"]
+        .iter()
+        .filter_map(|s| resolve_mcp_path(s, project_root).ok());
+    settings.chain(mcp).collect()
+}
+
+/// Directories `create_directory` may create.
+fn known_directories(project_root: &Option<String>) -> Result<Vec<PathBuf>, IpcError> {
+    let config_dir = paths::resolve_config_dir()?;
+    let mut dirs = vec![
+        config_dir.join("agents"),
+        config_dir.join("skills"),
+        config_dir.join("commands"),
+        config_dir,
+    ];
+    if let Some(root) = project_root {
+        let claude = PathBuf::from(root).join(".claude");
+        dirs.extend([
+            claude.join("agents"),
+            claude.join("skills"),
+            claude.join("commands"),
+            claude,
+        ]);
+    }
+    Ok(dirs)
+}
+
+/// Hook scripts referenced from any settings scope.
+fn hook_scripts(project_root: &Option<String>) -> Vec<PathBuf> {
+    let root = project_root.as_ref().map(PathBuf::from);
+    let Ok(home) = paths::home_dir() else {
+        return Vec::new();
+    };
+    [
+        ConfigScope::User,
+        ConfigScope::Project,
+        ConfigScope::Local,
+        ConfigScope::Managed,
+    ]
+    .iter()
+    .filter_map(|s| paths::resolve_settings_path(s, project_root).ok())
+    .filter_map(|p| fs::read_to_string(p).ok())
+    .filter_map(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+    .flat_map(|settings| hooks::command_hooks(&settings))
+    .filter_map(|(_, command)| hooks::script_path(&command, root.as_deref(), &home))
+    .collect()
+}
+
+/// Resolves `target` against `allowed`, rejecting anything else.
+fn allowed_target(target: &str, allowed: &[PathBuf]) -> Result<PathBuf, IpcError> {
+    let target = PathBuf::from(target);
+    allowed
+        .iter()
+        .find(|p| **p == target)
+        .cloned()
+        .ok_or_else(|| IpcError::InvalidArgument {
+            argument: "target".to_string(),
+            message: format!("{} is not a valid target for this fix", target.display()),
+        })
+}
+
+fn read_text(path: &Path) -> Result<String, IpcError> {
+    fs::read_to_string(path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => IpcError::NotFound {
+            path: path.display().to_string(),
+        },
+        std::io::ErrorKind::PermissionDenied => IpcError::PermissionDenied {
+            path: path.display().to_string(),
+        },
+        _ => IpcError::PlatformError {
+            message: format!("Failed to read {}: {}", path.display(), e),
+        },
+    })
+}
+
+/// IPC command: applies an automated remediation for a doctor or validator
+/// finding.
+///
+/// | `fix_id`                 | `target`                                   |
+/// |--------------------------|--------------------------------------------|
+/// | `add_gitignore_entries`  | unused; the open project's `.gitignore`    |
+/// | `rename_deprecated_keys` | settings scope: user, project, or local    |
+/// | `chmod_hook_script`      | path of a script referenced by a hook      |
+/// | `remove_trailing_commas` | path of a settings or MCP config file      |
+/// | `create_directory`       | config dir, `.claude/`, or its subfolders  |
+///
+/// Targets are checked against the files ssenrah manages, so a fix can't be
+/// pointed elsewhere. Files are backed up before their content changes and
+/// rewritten with `atomic_write`. Doctor findings that one of these fixes
+/// resolves carry its `fixId` and `fixTarget`.
+#[tauri::command]
+pub fn apply_fix(
+    fix_id: String,
+    target: Option<String>,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<FixResult, IpcError> {
+    state.ensure_writable()?;
+    let project_root = state.project_root(&window);
+
+    let result =
+        |path: &Path, changed: bool, message: String, backup_path: Option<String>| FixResult {
+            fix_id: fix_id.clone(),
+            path: path.display().to_string(),
+            changed,
+            message,
+            backup_path,
+        };
+
+    match fix_id.as_str() {
+        "add_gitignore_entries" => {
+            let root = require_project(&project_root)?;
+            let gitignore = root.join(".gitignore");
+            let check = check_local_gitignore(&root, false)?;
+            if check.missing.is_empty() {
+                return Ok(result(
+                    &gitignore,
+                    false,
+                    "Nothing to add".to_string(),
+                    None,
+                ));
+            }
+            let backup_path = backup_before_fix(&fix_id, &gitignore)?;
+            let check = check_local_gitignore(&root, true)?;
+            Ok(result(
+                &gitignore,
+                true,
+                format!("Added {}", check.added.join(", ")),
+                backup_path,
+            ))
+        }
+        "rename_deprecated_keys" => {
+            let scope: ConfigScope = match require_target(target, &fix_id)?.as_str() {
+                "user" => ConfigScope::User,
+                "project" => ConfigScope::Project,
+                "local" => ConfigScope::Local,
+                other => {
+                    return Err(IpcError::InvalidArgument {
+                        argument: "target".to_string(),
+                        message: format!("Settings scope '{}' is not writable", other),
+                    })
+                }
+            };
+            let path = paths::resolve_settings_path(&scope, &project_root)?;
+            let _lock = lock_path(&path);
+            let mut settings: serde_json::Value = serde_json::from_str(&read_text(&path)?)
+                .map_err(|e| IpcError::ParseError {
+                    path: path.display().to_string(),
+                    message: e.to_string(),
+                })?;
+
+            let changes = migrations::migrate(&mut settings);
+            let moved: Vec<String> = changes
+                .iter()
+                .filter(|c| c.applied)
+                .map(|c| format!("{} -> {}", c.from, c.to))
+                .collect();
+            if moved.is_empty() {
+                return Ok(result(
+                    &path,
+                    false,
+                    "No deprecated keys to move".to_string(),
+                    None,
+                ));
+            }
+            let backup_path = backup_before_fix(&fix_id, &path)?;
+            let content =
+                serde_json::to_vec_pretty(&settings).map_err(|e| IpcError::WriteFailed {
+                    path: path.display().to_string(),
+                    message: format!("Failed to serialize settings: {}", e),
+                })?;
+            atomic_write(&path, &content)?;
+
+            Ok(result(
+                &path,
+                true,
+                format!("Moved {}", moved.join(", ")),
+                backup_path,
+            ))
+        }
+        "chmod_hook_script" => {
+            let path = allowed_target(
+                &require_target(target, &fix_id)?,
+                &hook_scripts(&project_root),
+            )?;
+            make_executable(&path)?;
+            Ok(result(
+                &path,
+                true,
+                "Made the script executable".to_string(),
+                None,
+            ))
+        }
+        "remove_trailing_commas" => {
+            let path = allowed_target(
+                &require_target(target, &fix_id)?,
+                &json_config_files(&project_root),
+            )?;
+            let _lock = lock_path(&path);
+            let text = read_text(&path)?;
+            let Some(fixed) = fixes::strip_trailing_commas(&text) else {
+                return Ok(result(
+                    &path,
+                    false,
+                    "No trailing commas found".to_string(),
+                    None,
+                ));
+            };
+            if let Err(e) = serde_json::from_str::<serde_json::Value>(&fixed) {
+                return Err(IpcError::ParseError {
+                    path: path.display().to_string(),
+                    message: format!("Still invalid after removing trailing commas: {}", e),
+                });
+            }
+            let backup_path = backup_before_fix(&fix_id, &path)?;
+            atomic_write(&path, fixed.as_bytes())?;
+            Ok(result(
+                &path,
+                true,
+                "Removed trailing commas".to_string(),
+                backup_path,
+            ))
+        }
+        "create_directory" => {
+            let path = allowed_target(
+                &require_target(target, &fix_id)?,
+                &known_directories(&project_root)?,
+            )?;
+            if path.is_dir() {
+                return Ok(result(
+                    &path,
+                    false,
+                    "Directory already exists".to_string(),
+                    None,
+                ));
+            }
+            fs::create_dir_all(&path).map_err(|e| IpcError::WriteFailed {
+                path: path.display().to_string(),
+                message: format!("Failed to create directory: {}", e),
+            })?;
+            Ok(result(&path, true, "Created directory".to_string(), None))
+        }
+        _ => Err(IpcError::InvalidArgument {
+            argument: "fixId".to_string(),
+            message: format!(
+                "Unknown fix '{}'. Expected one of: {}",
+                fix_id,
+                FIX_IDS.join(", ")
+            ),
+        }),
+    }
+}
+
+/// Adds execute permission wherever read permission is set (like `chmod +x`
+/// under a typical umask).
+pub(crate) fn make_executable(path: &Path) -> Result<(), IpcError> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let write_failed = |e: std::io::Error| IpcError::WriteFailed {
+            path: path.display().to_string(),
+            message: format!("Failed to change permissions: {}", e),
+        };
+        let mut perms = fs::metadata(path).map_err(write_failed)?.permissions();
+        let mode = perms.mode();
+        perms.set_mode(mode | ((mode & 0o444) >> 2));
+        fs::set_permissions(path, perms).map_err(write_failed)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        Ok(())
+    }
+}
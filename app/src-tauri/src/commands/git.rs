@@ -0,0 +1,406 @@
+use std::collections::HashSet;
+use std::path::{Component, Path, PathBuf};
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::errors::IpcError;
+use crate::git::{check_local_gitignore, find_git_root, git_spec, run_git, GitignoreCheck};
+use crate::AppState;
+
+/// Shared project config paths, relative to the project root.
+const SHARED_CONFIG_PATHS: &[&str] = &[
+    ".claude/settings.json",
+    ".claude/.mcp.json",
+    ".claude/agents",
+    ".claude/commands",
+];
+
+/// Personal config paths that should never be committed.
+const LOCAL_CONFIG_PATHS: &[&str] = &[".claude/settings.local.json", ".claude/CLAUDE.local.md"];
+
+/// Version-control status of one config file or directory.
+///
+/// For directories, each flag is set if it applies to any file inside.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitFileStatus {
+    /// Path relative to the project root.
+    pub path: String,
+    pub exists: bool,
+    pub tracked: bool,
+    /// Has changes in the index.
+    pub staged: bool,
+    /// Has unstaged changes in the working tree.
+    pub modified: bool,
+    pub untracked: bool,
+    pub ignored: bool,
+}
+
+/// Version-control status of the open project's config files.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigGitStatus {
+    /// `None` when the project is not inside a git repository.
+    pub git_root: Option<String>,
+    pub files: Vec<GitFileStatus>,
+    pub local_files: Vec<GitFileStatus>,
+    /// Local-only files that are tracked, staged, or not gitignored.
+    pub warnings: Vec<String>,
+}
+
+/// Entry `entry` (from git output) belongs to pathspec `spec`.
+fn covers(spec: &str, entry: &str) -> bool {
+    let entry = entry.trim_end_matches('/');
+    entry == spec
+        || entry.starts_with(&format!("{}/", spec))
+        // An untracked or ignored directory is reported as a single entry.
+        || spec.starts_with(&format!("{}/", entry))
+}
+
+/// Builds the status of each `specs` entry (git-root-relative) from one
+/// `git status`, `git ls-files`, and `git check-ignore` run.
+fn file_statuses(
+    git_root: &Path,
+    project_root: &Path,
+    rel_paths: &[&str],
+    specs: &[String],
+) -> Result<Vec<GitFileStatus>, IpcError> {
+    let mut args = vec!["ls-files", "-z", "--"];
+    args.extend(specs.iter().map(String::as_str));
+    let tracked_out = run_git(git_root, &args, &[])?;
+    let tracked: Vec<&str> = tracked_out.split('\0').filter(|s| !s.is_empty()).collect();
+
+    let mut args = vec![
+        "status",
+        "--porcelain=v1",
+        "-z",
+        "--ignored",
+        "--untracked-files=all",
+        "--",
+    ];
+    args.extend(specs.iter().map(String::as_str));
+    let status_out = run_git(git_root, &args, &[])?;
+
+    // Each entry is "XY path"; renames and copies are followed by the
+    // original path as a separate entry.
+    let mut entries = Vec::new();
+    let mut parts = status_out.split('\0').filter(|s| !s.is_empty());
+    while let Some(part) = parts.next() {
+        if part.len() < 4 {
+            continue;
+        }
+        let (x, y) = (part.as_bytes()[0], part.as_bytes()[1]);
+        if x == b'R' || x == b'C' {
+            parts.next();
+        }
+        entries.push((x, y, &part[3..]));
+    }
+
+    let mut args = vec!["check-ignore", "--"];
+    args.extend(specs.iter().map(String::as_str));
+    let ignored_out = run_git(git_root, &args, &[1])?;
+    let ignored: HashSet<&str> = ignored_out.lines().collect();
+
+    Ok(rel_paths
+        .iter()
+        .zip(specs)
+        .map(|(rel, spec)| {
+            let mut status = GitFileStatus {
+                path: rel.to_string(),
+                exists: project_root.join(rel).exists(),
+                tracked: tracked.iter().any(|t| covers(spec, t)),
+                staged: false,
+                modified: false,
+                untracked: false,
+                ignored: ignored.contains(spec.as_str()),
+            };
+            for (x, y, _) in entries.iter().filter(|(_, _, p)| covers(spec, p)) {
+                match (x, y) {
+                    (b'?', b'?') => status.untracked = true,
+                    (b'!', b'!') => status.ignored = true,
+                    _ => {
+                        status.staged |= *x != b' ';
+                        status.modified |= *y != b' ';
+                    }
+                }
+            }
+            status
+        })
+        .collect())
+}
+
+/// IPC command: reports the git status of the open project's config files.
+///
+/// Shared files (`.claude/settings.json`, `.mcp.json`, agents, commands) are
+/// reported as tracked / staged / modified / untracked / ignored. Local-only
+/// files (`settings.local.json`, `CLAUDE.local.md`) get a warning when they
+/// are tracked, staged, or would be picked up by `git add`.
+#[tauri::command]
+pub fn get_config_git_status(
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<ConfigGitStatus, IpcError> {
+    let project_root = state.project_root(&window);
+
+    let root = project_root.as_ref().ok_or_else(|| IpcError::NoProject {
+        message: "No project is open. Open a project to view git status.".to_string(),
+    })?;
+    let root = PathBuf::from(root);
+
+    let git_root = match find_git_root(&root) {
+        Some(git_root) => PathBuf::from(git_root),
+        None => {
+            return Ok(ConfigGitStatus {
+                git_root: None,
+                files: vec![],
+                local_files: vec![],
+                warnings: vec![],
+            })
+        }
+    };
+
+    let to_specs = |rels: &[&str]| -> Vec<String> {
+        rels.iter()
+            .map(|rel| git_spec(&root, &git_root, rel))
+            .collect()
+    };
+
+    let files = file_statuses(
+        &git_root,
+        &root,
+        SHARED_CONFIG_PATHS,
+        &to_specs(SHARED_CONFIG_PATHS),
+    )?;
+    let local_files = file_statuses(
+        &git_root,
+        &root,
+        LOCAL_CONFIG_PATHS,
+        &to_specs(LOCAL_CONFIG_PATHS),
+    )?;
+
+    let mut warnings = Vec::new();
+    for file in &local_files {
+        if file.tracked {
+            warnings.push(format!(
+                "{} is tracked by git; remove it with `git rm --cached`",
+                file.path
+            ));
+        } else if file.staged {
+            warnings.push(format!("{} is staged for commit", file.path));
+        } else if file.exists && !file.ignored {
+            warnings.push(format!(
+                "{} is not gitignored and may be committed",
+                file.path
+            ));
+        }
+    }
+
+    Ok(ConfigGitStatus {
+        git_root: Some(git_root.display().to_string()),
+        files,
+        local_files,
+        warnings,
+    })
+}
+
+/// Diff of one config path against the last commit.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigDiff {
+    /// Path relative to the project root.
+    pub path: String,
+    pub tracked: bool,
+    /// Unified diff; untracked files are diffed against an empty file.
+    pub diff: String,
+}
+
+/// A commit created from config changes.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigCommit {
+    pub commit: String,
+    /// Committed paths, relative to the git root.
+    pub files: Vec<String>,
+}
+
+/// Resolves the open project and the git repository containing it.
+fn project_repo(project_root: &Option<String>) -> Result<(PathBuf, PathBuf), IpcError> {
+    let root = project_root.as_ref().ok_or_else(|| IpcError::NoProject {
+        message: "No project is open. Open a project to use git.".to_string(),
+    })?;
+    let git_root = find_git_root(Path::new(root)).ok_or_else(|| IpcError::PlatformError {
+        message: format!("{} is not inside a git repository", root),
+    })?;
+    Ok((PathBuf::from(root), PathBuf::from(git_root)))
+}
+
+/// Normalizes `path` (absolute, or relative to the project root) and checks
+/// that it is under `.claude/` or is the project-root CLAUDE.md. Returns the
+/// project-relative path, forward-slash separated.
+fn config_rel_path(root: &Path, path: &str) -> Result<String, IpcError> {
+    let invalid = || IpcError::InvalidArgument {
+        argument: "paths".to_string(),
+        message: format!(
+            "{} is not a project config path (.claude/ or CLAUDE.md)",
+            path
+        ),
+    };
+
+    let p = Path::new(path);
+    let rel = if p.is_absolute() {
+        p.strip_prefix(root).map_err(|_| invalid())?
+    } else {
+        p
+    };
+
+    let mut parts = Vec::new();
+    for component in rel.components() {
+        match component {
+            Component::Normal(s) => parts.push(s.to_string_lossy().to_string()),
+            Component::CurDir => {}
+            _ => return Err(invalid()),
+        }
+    }
+
+    let allowed = parts == ["CLAUDE.md"] || parts.first().is_some_and(|p| p == ".claude");
+    if !allowed {
+        return Err(invalid());
+    }
+    Ok(parts.join("/"))
+}
+
+/// IPC command: diffs a project config path against `HEAD`.
+///
+/// `path` is absolute or relative to the project root and must be under
+/// `.claude/` or be the root CLAUDE.md. Staged and unstaged changes are both
+/// included.
+#[tauri::command]
+pub fn diff_config_against_head(
+    path: String,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<ConfigDiff, IpcError> {
+    let project_root = state.project_root(&window);
+    let (root, git_root) = project_repo(&project_root)?;
+
+    let rel = config_rel_path(&root, &path)?;
+    let spec = git_spec(&root, &git_root, &rel);
+
+    let tracked = !run_git(&git_root, &["ls-files", "--", &spec], &[])?
+        .trim()
+        .is_empty();
+    let has_head = !run_git(
+        &git_root,
+        &["rev-parse", "--verify", "--quiet", "HEAD"],
+        &[1],
+    )?
+    .trim()
+    .is_empty();
+
+    let diff = if tracked && has_head {
+        run_git(&git_root, &["diff", "--no-color", "HEAD", "--", &spec], &[])?
+    } else if root.join(&rel).is_file() {
+        run_git(
+            &git_root,
+            &["diff", "--no-color", "--no-index", "--", "/dev/null", &spec],
+            &[1],
+        )?
+    } else {
+        String::new()
+    };
+
+    Ok(ConfigDiff {
+        path: rel,
+        tracked,
+        diff,
+    })
+}
+
+/// IPC command: commits changes to the given project config paths.
+///
+/// Every path must be under `.claude/` or be the root CLAUDE.md. The paths
+/// are staged (including deletions) and committed on their own; anything else
+/// already staged stays staged.
+#[tauri::command]
+pub fn commit_config_changes(
+    paths: Vec<String>,
+    message: String,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<ConfigCommit, IpcError> {
+    state.ensure_writable()?;
+    let project_root = state.project_root(&window);
+    let (root, git_root) = project_repo(&project_root)?;
+
+    if message.trim().is_empty() {
+        return Err(IpcError::InvalidArgument {
+            argument: "message".to_string(),
+            message: "Commit message cannot be empty".to_string(),
+        });
+    }
+    if paths.is_empty() {
+        return Err(IpcError::InvalidArgument {
+            argument: "paths".to_string(),
+            message: "No paths given to commit".to_string(),
+        });
+    }
+
+    let specs = paths
+        .iter()
+        .map(|p| config_rel_path(&root, p).map(|rel| git_spec(&root, &git_root, &rel)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut args = vec!["add", "-A", "--"];
+    args.extend(specs.iter().map(String::as_str));
+    run_git(&git_root, &args, &[])?;
+
+    let mut args = vec!["diff", "--cached", "--name-only", "-z", "--"];
+    args.extend(specs.iter().map(String::as_str));
+    let files: Vec<String> = run_git(&git_root, &args, &[])?
+        .split('\0')
+        .filter(|s| !s.is_empty())
+        .map(String::from)
+        .collect();
+    if files.is_empty() {
+        return Err(IpcError::InvalidArgument {
+            argument: "paths".to_string(),
+            message: "No changes to commit in the selected paths".to_string(),
+        });
+    }
+
+    let mut args = vec!["commit", "--quiet", "-m", message.as_str(), "--"];
+    args.extend(specs.iter().map(String::as_str));
+    run_git(&git_root, &args, &[])?;
+
+    let commit = run_git(&git_root, &["rev-parse", "HEAD"], &[])?
+        .trim()
+        .to_string();
+
+    Ok(ConfigCommit { commit, files })
+}
+
+/// IPC command: checks that local-only config files are gitignored and, when
+/// `apply` is true, appends the missing patterns to the project-root
+/// `.gitignore` under an explanatory comment.
+///
+/// Inside a git repository coverage is checked with `git check-ignore`, so
+/// broader existing rules count; otherwise `.gitignore` is searched for the
+/// exact pattern lines.
+#[tauri::command]
+pub fn ensure_local_files_gitignored(
+    apply: Option<bool>,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<GitignoreCheck, IpcError> {
+    if apply.unwrap_or(false) {
+        state.ensure_writable()?;
+    }
+    let project_root = state.project_root(&window);
+
+    let root = project_root.as_ref().ok_or_else(|| IpcError::NoProject {
+        message: "No project is open. Open a project to manage .gitignore.".to_string(),
+    })?;
+
+    check_local_gitignore(Path::new(root), apply.unwrap_or(false))
+}
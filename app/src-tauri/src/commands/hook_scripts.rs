@@ -0,0 +1,216 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::commands::fixes::make_executable;
+use crate::config;
+use crate::errors::IpcError;
+use crate::io::atomic::atomic_write;
+use crate::io::blocking;
+use crate::logging;
+use crate::platform::{detect, paths};
+use crate::schema::hook_lint::{self, HookCommandLint};
+use crate::schema::hooks;
+use crate::AppState;
+
+/// A hook that runs a script.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HookScriptUse {
+    pub scope: String,
+    pub event: String,
+    pub matcher: Option<String>,
+    /// Location in the scope's settings, e.g. `hooks.Stop[0].hooks[1]`.
+    pub path: String,
+    pub command: String,
+    /// False when `disableAllHooks` or `allowManagedHooksOnly` keeps the
+    /// hook from running.
+    pub runs: bool,
+}
+
+/// A script file run by one or more hooks.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HookScript {
+    pub path: String,
+    pub exists: bool,
+    pub executable: bool,
+    pub hooks: Vec<HookScriptUse>,
+}
+
+/// A hook script's content, as returned by `read_hook_script` and
+/// `write_hook_script`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HookScriptFile {
+    pub path: String,
+    /// `null` when the file does not exist yet.
+    pub content: Option<String>,
+    pub executable: bool,
+    /// Shebang and permission problems.
+    pub warnings: Vec<String>,
+}
+
+/// Scripts run by the effective hooks, keyed by path.
+fn hook_scripts(project_root: &Option<String>) -> Result<Vec<HookScript>, IpcError> {
+    let effective = config::compute_effective_hooks(project_root)?;
+    let root = project_root.as_ref().map(PathBuf::from);
+    let home = paths::home_dir()?;
+
+    let mut scripts: BTreeMap<PathBuf, Vec<HookScriptUse>> = BTreeMap::new();
+    for hook in effective.hooks {
+        let Some(command) = hook.hook.get("command").and_then(|c| c.as_str()) else {
+            continue;
+        };
+        let Some(script) = hooks::script_path(command, root.as_deref(), &home) else {
+            continue;
+        };
+        scripts.entry(script).or_default().push(HookScriptUse {
+            command: command.to_string(),
+            scope: hook.scope,
+            event: hook.event,
+            matcher: hook.matcher,
+            path: hook.path,
+            runs: hook.runs,
+        });
+    }
+
+    Ok(scripts
+        .into_iter()
+        .map(|(path, uses)| HookScript {
+            exists: path.is_file(),
+            executable: path.is_file() && detect::is_executable(&path),
+            path: path.display().to_string(),
+            hooks: uses,
+        })
+        .collect())
+}
+
+/// Resolves `path` to a script some hook runs, rejecting anything else so
+/// these commands cannot read or write arbitrary files.
+fn referenced_script(path: &str, project_root: &Option<String>) -> Result<PathBuf, IpcError> {
+    hook_scripts(project_root)?
+        .into_iter()
+        .find(|s| s.path == path)
+        .map(|s| PathBuf::from(s.path))
+        .ok_or_else(|| IpcError::InvalidArgument {
+            argument: "path".to_string(),
+            message: format!("{} is not run by any hook", path),
+        })
+}
+
+fn describe_script(path: &Path) -> Result<HookScriptFile, IpcError> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => Some(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+            return Err(IpcError::PermissionDenied {
+                path: path.display().to_string(),
+            })
+        }
+        Err(e) => {
+            return Err(IpcError::PlatformError {
+                message: format!("Failed to read {}: {}", path.display(), e),
+            })
+        }
+    };
+    let warnings = match &content {
+        Some(content) => {
+            #[cfg(unix)]
+            let mode = {
+                use std::os::unix::fs::PermissionsExt;
+                fs::metadata(path).ok().map(|m| m.permissions().mode())
+            };
+            #[cfg(not(unix))]
+            let mode = None;
+            hooks::check_script(content, mode)
+        }
+        None => Vec::new(),
+    };
+    Ok(HookScriptFile {
+        path: path.display().to_string(),
+        executable: content.is_some() && detect::is_executable(path),
+        content,
+        warnings,
+    })
+}
+
+/// IPC command: lists the script files the effective hooks run, each with
+/// the hooks that run it.
+///
+/// A hook runs a script when the first word of its command is a path
+/// (`$CLAUDE_PROJECT_DIR` and `~` are expanded, relative paths start at the
+/// project root). Commands that run a program from PATH are left out.
+#[tauri::command]
+pub async fn list_hook_scripts(
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<Vec<HookScript>, IpcError> {
+    logging::async_command("list_hook_scripts", async move {
+        let project_root = state.project_root(&window);
+        blocking::run("list_hook_scripts", move || hook_scripts(&project_root)).await
+    })
+    .await
+}
+
+/// IPC command: reads a hook script and checks its shebang and
+/// permissions. `path` must be one returned by `list_hook_scripts`; a
+/// missing file returns `content: null`.
+#[tauri::command]
+pub fn read_hook_script(
+    path: String,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<HookScriptFile, IpcError> {
+    let project_root = state.project_root(&window);
+    describe_script(&referenced_script(&path, &project_root)?)
+}
+
+/// IPC command: creates or replaces a hook script. `path` must be one
+/// returned by `list_hook_scripts`.
+///
+/// With `executable` unset, a new script is made executable and an existing
+/// one keeps its executable bit; `true` or `false` sets it explicitly.
+#[tauri::command]
+pub fn write_hook_script(
+    path: String,
+    content: String,
+    executable: Option<bool>,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<HookScriptFile, IpcError> {
+    state.ensure_writable()?;
+    let project_root = state.project_root(&window);
+    let path = referenced_script(&path, &project_root)?;
+    let executable = executable.unwrap_or_else(|| !path.is_file() || detect::is_executable(&path));
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| IpcError::WriteFailed {
+            path: parent.display().to_string(),
+            message: format!("Failed to create directory: {}", e),
+        })?;
+    }
+    // The replacement file gets default permissions, so the bit is set
+    // again afterwards.
+    atomic_write(&path, content.as_bytes())?;
+    if executable {
+        make_executable(&path)?;
+    }
+    tracing::info!(path = %path.display(), executable, "wrote hook script");
+    describe_script(&path)
+}
+
+/// IPC command: lints a hook command before it is saved.
+///
+/// `event` is the hook event the command is registered for; checks that
+/// depend on it (exit codes, tool input fields) are skipped without one.
+/// See `schema::hook_lint::lint_command` for what is flagged.
+#[tauri::command]
+pub fn lint_hook_command(command: String, event: Option<String>) -> Vec<HookCommandLint> {
+    hook_lint::lint_command(&command, event.as_deref(), |program| {
+        detect::find_on_path(program).is_some()
+    })
+}
@@ -0,0 +1,234 @@
+use std::io::{BufRead, BufReader, Read};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use serde::{Deserialize, Serialize};
+use tauri::{Emitter, State};
+
+use crate::errors::IpcError;
+use crate::platform::detect;
+use crate::schema::agent::PERMISSION_MODES;
+use crate::sessions;
+use crate::AppState;
+
+/// Options for `launch_claude_code`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchOptions {
+    /// Passed as `--model`.
+    pub model: Option<String>,
+    /// Passed as `--permission-mode`.
+    pub permission_mode: Option<String>,
+    /// Session id passed as `--resume`.
+    pub resume: Option<String>,
+    /// Runs non-interactively with `--print <prompt>`.
+    pub prompt: Option<String>,
+}
+
+/// Result of `launch_claude_code`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchedProcess {
+    pub pid: u32,
+    pub command: String,
+}
+
+/// Payload of `claude_output` events.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OutputLine {
+    pid: u32,
+    /// "stdout" or "stderr".
+    stream: &'static str,
+    line: String,
+}
+
+/// Payload of the `claude_exit` event.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProcessExit {
+    pid: u32,
+    code: Option<i32>,
+}
+
+/// Quotes `arg` for display and for the login shell: single quotes for POSIX
+/// shells, double quotes in the style of cmd.exe on Windows.
+fn shell_quote(arg: &str, windows: bool) -> String {
+    if windows {
+        format!("\"{}\"", arg.replace('"', "\"\""))
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+/// Builds the `claude` argument list from the launch options.
+fn claude_args(options: &LaunchOptions) -> Result<Vec<String>, IpcError> {
+    let mut args = Vec::new();
+
+    if let Some(model) = options.model.as_deref().filter(|m| !m.is_empty()) {
+        args.extend(["--model".to_string(), model.to_string()]);
+    }
+    if let Some(mode) = options.permission_mode.as_deref() {
+        crate::args::one_of("permissionMode", mode, PERMISSION_MODES)?;
+        args.extend(["--permission-mode".to_string(), mode.to_string()]);
+    }
+    if let Some(id) = options.resume.as_deref() {
+        if !sessions::is_valid_session_id(id) {
+            return Err(crate::args::invalid(
+                "resume",
+                format!("Invalid session id: {}", id),
+            ));
+        }
+        args.extend(["--resume".to_string(), id.to_string()]);
+    }
+    if let Some(prompt) = options.prompt.as_deref() {
+        args.extend(["--print".to_string(), prompt.to_string()]);
+    }
+
+    Ok(args)
+}
+
+/// Forwards each line of `reader` as a `claude_output` event.
+fn forward_lines<R: Read + Send + 'static>(
+    app: tauri::AppHandle,
+    pid: u32,
+    stream: &'static str,
+    reader: R,
+) {
+    std::thread::spawn(move || {
+        for line in BufReader::new(reader).lines().map_while(Result::ok) {
+            let _ = app.emit("claude_output", OutputLine { pid, stream, line });
+        }
+    });
+}
+
+/// IPC command: starts Claude Code in the open project's root.
+///
+/// The binary found by `detect_claude_code` is run through the login shell
+/// (`$SHELL -lc`) so the user's PATH and profile apply. On Windows it is
+/// started directly with each argument passed separately, since `cmd.exe`
+/// would parse the prompt again and run anything after a `&` in it.
+/// `model`, `permissionMode`, and `resume` map to the matching CLI flags;
+/// `prompt` runs a one-shot `--print` session, since stdin is not attached.
+///
+/// Returns the spawned PID. Output is streamed line by line as
+/// `claude_output` events (`{ pid, stream, line }`), followed by a single
+/// `claude_exit` event (`{ pid, code }`) when the process ends.
+#[tauri::command]
+pub fn launch_claude_code(
+    options: Option<LaunchOptions>,
+    app: tauri::AppHandle,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<LaunchedProcess, IpcError> {
+    let project_root = state.project_root(&window);
+    let root = project_root.clone().ok_or_else(|| IpcError::NoProject {
+        message: "No project is open. Open a project before launching Claude Code.".to_string(),
+    })?;
+    drop(project_root);
+
+    let (installed, binary) = detect::detect_claude_code();
+    let binary = binary
+        .filter(|_| installed)
+        .ok_or_else(|| IpcError::NotFound {
+            path: "claude".to_string(),
+        })?;
+    // `where` may list several matches, one per line.
+    let binary = binary.lines().next().unwrap_or_default().trim().to_string();
+
+    let args = claude_args(&options.unwrap_or_default())?;
+    let windows = cfg!(target_os = "windows");
+    let command_line = std::iter::once(binary.as_str())
+        .chain(args.iter().map(String::as_str))
+        .map(|a| shell_quote(a, windows))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let (program, mut command) = if windows {
+        let mut command = Command::new(&binary);
+        command.args(&args);
+        (binary, command)
+    } else {
+        let shell = detect::detect_shell();
+        let mut command = Command::new(&shell);
+        command.args(["-lc", &command_line]);
+        (shell, command)
+    };
+
+    let mut child = command
+        .current_dir(Path::new(&root))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| IpcError::PlatformError {
+            message: format!("Failed to start {}: {}", program, e),
+        })?;
+    let pid = child.id();
+
+    if let Some(stdout) = child.stdout.take() {
+        forward_lines(app.clone(), pid, "stdout", stdout);
+    }
+    if let Some(stderr) = child.stderr.take() {
+        forward_lines(app.clone(), pid, "stderr", stderr);
+    }
+    std::thread::spawn(move || {
+        let code = child.wait().ok().and_then(|s| s.code());
+        let _ = app.emit("claude_exit", ProcessExit { pid, code });
+    });
+
+    Ok(LaunchedProcess {
+        pid,
+        command: command_line,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_arguments_for_each_shell() {
+        assert_eq!(shell_quote("claude", false), "'claude'");
+        assert_eq!(shell_quote("it's; rm -rf ~", false), r"'it'\''s; rm -rf ~'");
+        assert_eq!(
+            shell_quote(r#"say "hi" & exit"#, true),
+            r#""say ""hi"" & exit""#
+        );
+    }
+
+    #[test]
+    fn builds_claude_arguments() {
+        let options = LaunchOptions {
+            model: Some("opus".to_string()),
+            permission_mode: Some("plan".to_string()),
+            resume: Some("0b1c-2d3e".to_string()),
+            prompt: Some("fix the build".to_string()),
+        };
+        assert_eq!(
+            claude_args(&options).unwrap(),
+            [
+                "--model",
+                "opus",
+                "--permission-mode",
+                "plan",
+                "--resume",
+                "0b1c-2d3e",
+                "--print",
+                "fix the build",
+            ]
+        );
+        assert!(claude_args(&LaunchOptions::default()).unwrap().is_empty());
+
+        let bad_mode = LaunchOptions {
+            permission_mode: Some("yolo".to_string()),
+            ..LaunchOptions::default()
+        };
+        assert!(claude_args(&bad_mode).is_err());
+        let bad_resume = LaunchOptions {
+            resume: Some("../other".to_string()),
+            ..LaunchOptions::default()
+        };
+        assert!(claude_args(&bad_resume).is_err());
+    }
+}
@@ -0,0 +1,87 @@
+use std::fs;
+
+use serde_json::Value;
+
+use crate::errors::IpcError;
+use crate::logging;
+
+/// Entries returned when the caller does not set a limit.
+const DEFAULT_LOG_LIMIT: usize = 500;
+
+/// IPC command: returns the most recent app log entries, oldest first.
+///
+/// `level` ("trace", "debug", "info", "warn", "error") is the minimum
+/// severity to include (default "info"); `limit` caps the number of entries
+/// (default 500). Entries are read from the newest daily log files first.
+#[tauri::command]
+pub fn read_app_logs(level: Option<String>, limit: Option<usize>) -> Result<Vec<Value>, IpcError> {
+    let level = level.unwrap_or_else(|| "info".to_string());
+    if logging::parse_level(&level).is_none() {
+        return Err(IpcError::InvalidArgument {
+            argument: "level".to_string(),
+            message: format!(
+                "Unknown log level '{}'. Expected one of: {}",
+                level,
+                logging::LEVELS.join(", ")
+            ),
+        });
+    }
+    let limit = limit.unwrap_or(DEFAULT_LOG_LIMIT);
+
+    let dir = logging::logs_dir()?;
+    let mut files: Vec<_> = match fs::read_dir(&dir) {
+        Ok(entries) => entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| {
+                p.file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n.starts_with("ssenrah.") && n.ends_with(".log"))
+            })
+            .collect(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(IpcError::PlatformError {
+                message: format!("Failed to read {}: {}", dir.display(), e),
+            })
+        }
+    };
+    // File names embed the date, so they sort chronologically.
+    files.sort();
+
+    let mut entries = Vec::new();
+    for file in files.iter().rev() {
+        if entries.len() >= limit {
+            break;
+        }
+        let Ok(content) = fs::read_to_string(file) else {
+            continue;
+        };
+        let mut older = logging::select_entries(content.lines(), &level, limit - entries.len());
+        older.append(&mut entries);
+        entries = older;
+    }
+
+    Ok(entries)
+}
+
+/// IPC command: changes the minimum level written to the app log.
+#[tauri::command]
+pub fn set_log_level(level: String) -> Result<(), IpcError> {
+    let filter = logging::parse_level(&level).ok_or_else(|| IpcError::InvalidArgument {
+        argument: "level".to_string(),
+        message: format!(
+            "Unknown log level '{}'. Expected one of: {}, OFF",
+            level,
+            logging::LEVELS.join(", ")
+        ),
+    })?;
+
+    if !logging::set_level(filter) {
+        return Err(IpcError::PlatformError {
+            message: "Logging is not initialized".to_string(),
+        });
+    }
+    tracing::info!(level = %filter, "log level changed");
+    Ok(())
+}
@@ -1,7 +1,21 @@
 use std::fs;
 
+use serde::Serialize;
+use tauri::State;
+
+use crate::audit;
+use crate::config::read_scope;
 use crate::errors::IpcError;
-use crate::platform::paths;
+use crate::io::backup;
+use crate::io::self_write;
+use crate::mdm::{self, ManagedProfile, PROFILE_FORMATS};
+use crate::platform::elevate;
+use crate::platform::paths::{self, resolve_mcp_path};
+use crate::policy::{self as admin_policy, PolicyFile};
+use crate::schema::model;
+use crate::schema::policy::{self, PolicyFinding};
+use crate::types::{ConfigScope, ValidationError};
+use crate::AppState;
 
 /// IPC command: reads the managed-settings.json file.
 ///
@@ -11,7 +25,7 @@ use crate::platform::paths;
 /// - `Err(...)` on I/O or parse failure
 #[tauri::command]
 pub fn read_managed_settings() -> Result<Option<serde_json::Value>, IpcError> {
-    let dir = paths::resolve_managed_settings_dir().ok_or_else(|| IpcError::PlatformError {
+    let dir = paths::resolve_managed_settings_dir().ok_or_else(|| IpcError::Unsupported {
         message: "Managed settings directory is not supported on this platform.".to_string(),
     })?;
 
@@ -38,3 +52,255 @@ pub fn read_managed_settings() -> Result<Option<serde_json::Value>, IpcError> {
         },
     }
 }
+
+/// Result of `write_managed_settings`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManagedWriteResult {
+    pub path: String,
+    /// Backup of the previous file, if there was one.
+    pub backup_path: Option<String>,
+}
+
+/// IPC command: writes managed-settings.json (`file: "settings"`),
+/// managed-mcp.json (`file: "mcp"`), or the ssenrah settings policy
+/// (`file: "policy"`, see `policy::PolicyFile`) with administrator rights.
+///
+/// Those files live in a system directory, so the content is written to a
+/// temp file and copied into place through the platform's elevation prompt
+/// (see `platform::elevate`). `confirmed` must be `true`: the frontend sets
+/// it only after the user has explicitly agreed to a machine-wide change.
+/// The content is validated first and the current file is backed up to
+/// ssenrah's backups directory.
+#[tauri::command]
+pub fn write_managed_settings(
+    file: String,
+    content: serde_json::Value,
+    confirmed: bool,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<ManagedWriteResult, IpcError> {
+    state.ensure_writable()?;
+    let project_root = state.project_root(&window);
+
+    if !confirmed {
+        return Err(IpcError::ConfirmationRequired {
+            message:
+                "Managed settings apply to every user on this machine; confirm the write first"
+                    .to_string(),
+        });
+    }
+
+    let path = match file.as_str() {
+        "settings" => {
+            model::parse_settings(&content)
+                .map_err(|e| IpcError::ValidationError { errors: vec![e] })?;
+            paths::resolve_settings_path(&ConfigScope::Managed, &project_root)?
+        }
+        "mcp" => {
+            if !content.get("mcpServers").is_some_and(|s| s.is_object()) {
+                return Err(IpcError::ValidationError {
+                    errors: vec![ValidationError {
+                        path: "mcpServers".to_string(),
+                        message: "Managed MCP config must contain an mcpServers object".to_string(),
+                        code: "MISSING_MCP_SERVERS".to_string(),
+                    }],
+                });
+            }
+            resolve_mcp_path("managed", &project_root)?
+        }
+        "policy" => {
+            serde_json::from_value::<PolicyFile>(content.clone()).map_err(|e| {
+                IpcError::ValidationError {
+                    errors: vec![ValidationError {
+                        path: String::new(),
+                        message: format!("Invalid policy: {}", e),
+                        code: "INVALID_POLICY".to_string(),
+                    }],
+                }
+            })?;
+            admin_policy::policy_path().ok_or_else(|| IpcError::Unsupported {
+                message: "Managed settings are not supported on this platform".to_string(),
+            })?
+        }
+        other => {
+            return Err(IpcError::InvalidArgument {
+                argument: "file".to_string(),
+                message: format!(
+                    "Unknown managed file '{}'. Expected settings, mcp, or policy",
+                    other
+                ),
+            })
+        }
+    };
+    let path_str = path.display().to_string();
+
+    let bytes = serde_json::to_vec_pretty(&content).map_err(|e| IpcError::WriteFailed {
+        path: path_str.clone(),
+        message: format!("Failed to serialize: {}", e),
+    })?;
+    if !elevate::is_supported() {
+        return Err(IpcError::Unsupported {
+            message: "Writing managed files is not supported on this platform".to_string(),
+        });
+    }
+    let before = fs::read(&path).ok();
+    let backup_path = backup::backup_file("managed", &path)?.map(|p| p.display().to_string());
+
+    let staging_dir = paths::resolve_app_dir()?;
+    fs::create_dir_all(&staging_dir).map_err(|e| IpcError::WriteFailed {
+        path: staging_dir.display().to_string(),
+        message: format!("Failed to create directory: {}", e),
+    })?;
+    let staged = staging_dir.join(format!("managed-{}-{}.json", file, std::process::id()));
+    fs::write(&staged, &bytes).map_err(|e| IpcError::WriteFailed {
+        path: staged.display().to_string(),
+        message: format!("Failed to stage file: {}", e),
+    })?;
+    let copied = elevate::elevated_copy(&staged, &path);
+    let _ = fs::remove_file(&staged);
+    copied.map_err(|message| IpcError::WriteFailed {
+        path: path_str.clone(),
+        message,
+    })?;
+
+    self_write::tracker().register(&path);
+    audit::record_write(&path, before.as_deref(), &bytes);
+    Ok(ManagedWriteResult {
+        path: path_str,
+        backup_path,
+    })
+}
+
+/// IPC command: builds deployable artifacts that install `settings` as
+/// managed policy.
+///
+/// `format` is one of:
+/// - `macos_mobileconfig`: a configuration profile for MDM upload
+/// - `linux`: managed-settings.json plus an install.sh for /etc/claude-code
+/// - `windows`: managed-settings.json plus an install.ps1
+///
+/// `organization` fills the profile's PayloadOrganization (macOS only).
+/// The settings are validated first; nothing is written to disk.
+#[tauri::command]
+pub fn generate_managed_profile(
+    settings: serde_json::Value,
+    format: String,
+    organization: Option<String>,
+) -> Result<ManagedProfile, IpcError> {
+    model::parse_settings(&settings).map_err(|e| IpcError::ValidationError { errors: vec![e] })?;
+    if !settings.is_object() {
+        return Err(IpcError::InvalidArgument {
+            argument: "settings".to_string(),
+            message: "Managed settings must be a JSON object".to_string(),
+        });
+    }
+
+    mdm::generate(
+        &settings,
+        &format,
+        organization.as_deref().unwrap_or("Claude Code"),
+    )
+    .ok_or_else(|| IpcError::InvalidArgument {
+        argument: "format".to_string(),
+        message: format!(
+            "Unknown profile format '{}'. Expected one of: {}",
+            format,
+            PROFILE_FORMATS.join(", ")
+        ),
+    })
+}
+
+/// Result of `check_policy_compliance`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyReport {
+    /// `false` if there is no managed-settings.json, in which case there are
+    /// no findings.
+    pub has_policy: bool,
+    pub findings: Vec<PolicyFinding>,
+}
+
+/// IPC command: lists every user, project, or local setting that managed
+/// policy overrides or forbids.
+///
+/// Explains why a setting "doesn't work" from the user's side and shows
+/// admins where machines drift from policy. See
+/// `schema::policy::check_compliance` for the rules checked.
+#[tauri::command]
+pub fn check_policy_compliance(
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<PolicyReport, IpcError> {
+    let project_root = state.project_root(&window);
+
+    let Some(managed) = read_scope(&ConfigScope::Managed, &project_root)? else {
+        return Ok(PolicyReport {
+            has_policy: false,
+            findings: vec![],
+        });
+    };
+    let user = read_scope(&ConfigScope::User, &project_root)?;
+    let project = read_scope(&ConfigScope::Project, &project_root)?;
+    let local = read_scope(&ConfigScope::Local, &project_root)?;
+
+    let findings = policy::check_compliance(
+        &managed,
+        &[
+            ("user", user.as_ref()),
+            ("project", project.as_ref()),
+            ("local", local.as_ref()),
+        ],
+    );
+    Ok(PolicyReport {
+        has_policy: true,
+        findings,
+    })
+}
+
+/// IPC command: reads the ssenrah settings policy, or `None` if the admin
+/// has not written one.
+#[tauri::command]
+pub fn read_settings_policy() -> Result<Option<PolicyFile>, IpcError> {
+    admin_policy::load()
+}
+
+/// Result of `check_settings_against_policy`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PolicyCheck {
+    /// `false` if there is no policy file, in which case there are no
+    /// violations.
+    pub has_policy: bool,
+    /// Writes that violate the policy are refused.
+    pub enforced: bool,
+    pub violations: Vec<ValidationError>,
+}
+
+/// IPC command: checks a scope's settings file against the ssenrah
+/// settings policy.
+///
+/// The same check runs in `validate_settings` and, when the policy is
+/// enforced, before `write_settings`; this command reports on what is
+/// already on disk.
+#[tauri::command]
+pub fn check_settings_against_policy(
+    scope: ConfigScope,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<PolicyCheck, IpcError> {
+    let project_root = state.project_root(&window);
+    let Some(policy) = admin_policy::load()? else {
+        return Ok(PolicyCheck {
+            has_policy: false,
+            enforced: false,
+            violations: vec![],
+        });
+    };
+    let settings = read_scope(&scope, &project_root)?.unwrap_or_else(|| serde_json::json!({}));
+    Ok(PolicyCheck {
+        has_policy: true,
+        enforced: policy.enforce,
+        violations: admin_policy::check_settings(&policy, &settings),
+    })
+}
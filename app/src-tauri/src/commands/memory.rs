@@ -1,10 +1,20 @@
 use std::fs;
+use std::io::{Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
 
+use serde::Serialize;
 use tauri::State;
 
+use crate::args;
 use crate::errors::IpcError;
-use crate::io::atomic::atomic_write;
+use crate::io::blocking;
+use crate::io::text::{self, TextFormat};
+use crate::io::walk::DEFAULT_MAX_DEPTH;
+use crate::logging;
+use crate::memory::chunk::{self, MemoryChunk, DEFAULT_MAX_INLINE_BYTES, MAX_CHUNK_BYTES};
+use crate::memory::discover::{self, MemoryDiscovery};
+use crate::memory::effective::{self, EffectiveMemory};
+use crate::memory::imports::{self, MemoryNode};
 use crate::platform::paths;
 use crate::AppState;
 
@@ -14,10 +24,13 @@ use crate::AppState;
 /// - "project"      -> {projectRoot}/.claude/CLAUDE.md
 /// - "project_root" -> {projectRoot}/CLAUDE.md
 /// - "local"        -> {projectRoot}/.claude/CLAUDE.local.md
-fn resolve_memory_path(scope: &str, project_root: &Option<String>) -> Result<PathBuf, IpcError> {
+pub(crate) fn resolve_memory_path(
+    scope: &str,
+    project_root: &Option<String>,
+) -> Result<PathBuf, IpcError> {
     match scope {
         "user" => {
-            let config_dir = paths::resolve_config_dir();
+            let config_dir = paths::resolve_config_dir()?;
             Ok(config_dir.join("CLAUDE.md"))
         }
         "project" => {
@@ -41,70 +54,224 @@ fn resolve_memory_path(scope: &str, project_root: &Option<String>) -> Result<Pat
             })?;
             Ok(PathBuf::from(root).join(".claude").join("CLAUDE.local.md"))
         }
-        _ => Err(IpcError::PlatformError {
+        _ => Err(IpcError::InvalidArgument {
+            argument: "scope".to_string(),
             message: format!("Unknown memory scope: {}", scope),
         }),
     }
 }
 
+/// A memory file's size and, if it is small enough, its content.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryFile {
+    pub path: String,
+    pub size_bytes: u64,
+    /// The whole file, or `None` if it is larger than the inline limit; page
+    /// through it with `read_memory_chunk` instead.
+    pub content: Option<String>,
+    /// Line endings and BOM on disk; `content` never includes the BOM.
+    pub format: TextFormat,
+}
+
+fn read_error(path: &Path, e: std::io::Error) -> IpcError {
+    match e.kind() {
+        std::io::ErrorKind::NotFound => IpcError::NotFound {
+            path: path.display().to_string(),
+        },
+        std::io::ErrorKind::PermissionDenied => IpcError::PermissionDenied {
+            path: path.display().to_string(),
+        },
+        _ => IpcError::PlatformError {
+            message: format!("Failed to read {}: {}", path.display(), e),
+        },
+    }
+}
+
 /// IPC command: reads a memory file (CLAUDE.md) for the given scope.
 ///
-/// Returns `Ok(Some(content))` if the file exists, `Ok(None)` if it does not.
+/// Returns `Ok(None)` if the file does not exist. Files larger than
+/// `max_inline_bytes` (default 1 MiB) come back with their size but no
+/// content, so a pasted-in multi-megabyte document cannot stall the IPC
+/// channel or the editor. `format` reports the file's line endings and BOM,
+/// which `write_memory` keeps.
 #[tauri::command]
-pub fn read_memory(scope: String, state: State<AppState>) -> Result<Option<String>, IpcError> {
-    let project_root = state
-        .project_root
-        .lock()
-        .map_err(|e| IpcError::PlatformError {
-            message: format!("Failed to acquire state lock: {}", e),
-        })?;
-
-    let path = resolve_memory_path(&scope, &project_root)?;
+pub async fn read_memory(
+    scope: String,
+    max_inline_bytes: Option<u64>,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<Option<MemoryFile>, IpcError> {
+    logging::async_command("read_memory", async move {
+        let project_root = state.project_root(&window);
+        blocking::run("read_memory", move || {
+            let path = resolve_memory_path(&scope, &project_root)?;
+            let size_bytes = match fs::metadata(&path) {
+                Ok(meta) => meta.len(),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+                Err(e) => return Err(read_error(&path, e)),
+            };
+            let (content, format) = if size_bytes
+                <= max_inline_bytes.unwrap_or(DEFAULT_MAX_INLINE_BYTES)
+            {
+                let (content, format) = text::read_text(&path).map_err(|e| read_error(&path, e))?;
+                (Some(content), format)
+            } else {
+                (None, TextFormat::of_file(&path))
+            };
 
-    match fs::read_to_string(&path) {
-        Ok(content) => Ok(Some(content)),
-        Err(e) => match e.kind() {
-            std::io::ErrorKind::NotFound => Ok(None),
-            std::io::ErrorKind::PermissionDenied => Err(IpcError::PermissionDenied {
+            Ok(Some(MemoryFile {
                 path: path.display().to_string(),
-            }),
-            _ => Err(IpcError::PlatformError {
-                message: format!("Failed to read {}: {}", path.display(), e),
-            }),
-        },
-    }
+                size_bytes,
+                content,
+                format,
+            }))
+        })
+        .await
+    })
+    .await
+}
+
+/// IPC command: reads up to `len` bytes (at most 4 MiB) of a memory file
+/// starting at byte `offset`.
+///
+/// Pages are cut on UTF-8 character boundaries; continue from the returned
+/// `nextOffset` until `eof`. Used for files too large for `read_memory`.
+#[tauri::command]
+pub async fn read_memory_chunk(
+    scope: String,
+    offset: u64,
+    len: u64,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<MemoryChunk, IpcError> {
+    logging::async_command("read_memory_chunk", async move {
+        let project_root = state.project_root(&window);
+        blocking::run("read_memory_chunk", move || {
+            let path = resolve_memory_path(&scope, &project_root)?;
+            let mut file = fs::File::open(&path).map_err(|e| read_error(&path, e))?;
+            let size_bytes = file.metadata().map_err(|e| read_error(&path, e))?.len();
+            let offset = offset.min(size_bytes);
+
+            let mut bytes = Vec::new();
+            file.seek(SeekFrom::Start(offset))
+                .and_then(|_| file.take(len.min(MAX_CHUNK_BYTES)).read_to_end(&mut bytes))
+                .map_err(|e| read_error(&path, e))?;
+
+            Ok(chunk::decode(&bytes, offset, size_bytes))
+        })
+        .await
+    })
+    .await
 }
 
 /// IPC command: writes a memory file (CLAUDE.md) for the given scope.
 ///
-/// Creates parent directories if needed and performs an atomic write.
+/// Creates parent directories if needed and performs an atomic write. The
+/// file keeps its line endings and BOM unless `format` overrides them.
 #[tauri::command]
-pub fn write_memory(
+pub async fn write_memory(
     scope: String,
     content: String,
-    state: State<AppState>,
+    format: Option<TextFormat>,
+    window: tauri::Window,
+    state: State<'_, AppState>,
 ) -> Result<(), IpcError> {
-    let project_root = state
-        .project_root
-        .lock()
-        .map_err(|e| IpcError::PlatformError {
-            message: format!("Failed to acquire state lock: {}", e),
-        })?;
+    logging::async_command("write_memory", async move {
+        state.ensure_writable()?;
+        args::max_bytes("content", content.len(), args::MAX_CONTENT_BYTES)?;
+        let project_root = state.project_root(&window);
+        blocking::run("write_memory", move || {
+            let path = resolve_memory_path(&scope, &project_root)?;
+            let path_str = path.display().to_string();
 
-    let path = resolve_memory_path(&scope, &project_root)?;
-    let path_str = path.display().to_string();
-
-    // Ensure parent directory exists
-    if let Some(parent) = path.parent() {
-        if !parent.exists() {
-            fs::create_dir_all(parent).map_err(|e| IpcError::WriteFailed {
-                path: path_str.clone(),
-                message: format!("Failed to create parent directory: {}", e),
+            // Ensure parent directory exists
+            if let Some(parent) = path.parent() {
+                if !parent.exists() {
+                    fs::create_dir_all(parent).map_err(|e| IpcError::WriteFailed {
+                        path: path_str.clone(),
+                        message: format!("Failed to create parent directory: {}", e),
+                    })?;
+                }
+            }
+
+            text::write_text(&path, &content, format)?;
+
+            Ok(())
+        })
+        .await
+    })
+    .await
+}
+
+/// IPC command: lists every CLAUDE.md and CLAUDE.local.md in the open
+/// project's tree, with a preview of each, so nested memory in a monorepo
+/// can be managed from one place.
+///
+/// The walk skips gitignored, hidden, and dependency directories and goes
+/// at most `max_depth` (default 8) levels deep; see `memory::discover`.
+#[tauri::command]
+pub async fn discover_memory_files(
+    max_depth: Option<usize>,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<MemoryDiscovery, IpcError> {
+    logging::async_command("discover_memory_files", async move {
+        let project_root = state.project_root(&window);
+        blocking::run("discover_memory_files", move || {
+            let root = project_root.ok_or_else(|| IpcError::NoProject {
+                message: "No project is open. Open a project to find its memory files.".to_string(),
             })?;
-        }
+            Ok(discover::discover(
+                Path::new(&root),
+                max_depth.unwrap_or(DEFAULT_MAX_DEPTH),
+            ))
+        })
+        .await
+    })
+    .await
+}
+
+/// IPC command: resolves the `@path` import tree of a memory file.
+///
+/// Imports are followed recursively up to five levels deep. Each
+/// node reports its size and whether it is missing, unreadable, part of a
+/// cycle, or too deeply nested, so the editor can show what the model sees.
+#[tauri::command]
+pub fn resolve_memory_graph(
+    scope: String,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<MemoryNode, IpcError> {
+    let project_root = state.project_root(&window);
+
+    let path = resolve_memory_path(&scope, &project_root)?;
+    if !path.exists() {
+        return Err(IpcError::NotFound {
+            path: path.display().to_string(),
+        });
     }
 
-    atomic_write(Path::new(&path), content.as_bytes())?;
+    Ok(imports::build_graph(&path, &paths::home_dir()?))
+}
+
+/// IPC command: concatenates every memory file in Claude Code's load order
+/// (user, project root, project, local) with their imports, and estimates how
+/// many tokens each file contributes. Scopes whose file is missing, and the
+/// project scopes when no project is open, are skipped.
+#[tauri::command]
+pub fn compute_effective_memory(
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<EffectiveMemory, IpcError> {
+    let project_root = state.project_root(&window);
+
+    let mut sources = Vec::new();
+    for scope in ["user", "project_root", "project", "local"] {
+        if let Ok(path) = resolve_memory_path(scope, &project_root) {
+            sources.push((scope, path));
+        }
+    }
 
-    Ok(())
+    Ok(effective::combine(&sources, &paths::home_dir()?))
 }
@@ -0,0 +1,45 @@
+use std::path::PathBuf;
+
+use crate::errors::IpcError;
+use crate::metrics::{self, UsageMetrics};
+use crate::platform::paths;
+
+/// IPC command: returns per-command invocation counts, error kinds, and
+/// durations counted in this session, for the diagnostics panel.
+///
+/// Counting only happens while the `telemetryOptIn` preference is on, and
+/// nothing leaves the machine; `enabled` reports the current state.
+#[tauri::command]
+pub fn get_usage_metrics() -> UsageMetrics {
+    metrics::snapshot()
+}
+
+/// IPC command: clears the usage metrics counted so far.
+#[tauri::command]
+pub fn reset_usage_metrics() {
+    metrics::reset();
+}
+
+/// IPC command: writes an anonymized usage report (command names, counts,
+/// error kinds, durations, app version, OS; no paths or settings) to attach
+/// to a bug report, and returns its path.
+///
+/// Written to `destination` if given, otherwise to
+/// `{configDir}/ssenrah/reports/usage-{unixMillis}.json`.
+#[tauri::command]
+pub fn export_usage_report(destination: Option<String>) -> Result<String, IpcError> {
+    let snapshot = metrics::snapshot();
+    let path = match destination {
+        Some(destination) => PathBuf::from(destination),
+        None => {
+            let dir = paths::resolve_app_dir()?.join("reports");
+            std::fs::create_dir_all(&dir).map_err(|e| IpcError::WriteFailed {
+                path: dir.display().to_string(),
+                message: e.to_string(),
+            })?;
+            dir.join(format!("usage-{}.json", snapshot.since))
+        }
+    };
+    metrics::export_report(&snapshot, &path)?;
+    Ok(path.display().to_string())
+}
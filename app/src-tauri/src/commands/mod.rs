@@ -1,11 +1,49 @@
 pub mod agents;
+pub mod audit;
+pub mod auth;
+pub mod bulk;
+pub mod bundle;
+pub mod capabilities;
+pub mod claude_json;
+pub mod conflicts;
+pub mod deep_link;
+pub mod doctor;
+pub mod drift;
 pub mod effective;
+pub mod files;
+pub mod fixes;
+pub mod git;
+pub mod hook_scripts;
+pub mod launch;
+pub mod logs;
 pub mod managed;
 pub mod mcp;
 pub mod memory;
+pub mod metrics;
+pub mod models;
+pub mod onboarding;
+pub mod output_styles;
 pub mod platform;
+pub mod plugins;
+pub mod preferences;
+pub mod profiles;
 pub mod project;
+pub mod references;
+pub mod registry;
+pub mod sandbox;
+pub mod scaffold;
+pub mod search;
+pub mod sessions;
 pub mod settings;
+pub mod share;
 pub mod skills;
+pub mod snapshots;
+pub mod statusline;
+pub mod support;
+pub mod sync;
+pub mod team_template;
+pub mod templates;
+pub mod trash;
 pub mod validation;
 pub mod watcher;
+pub mod windows;
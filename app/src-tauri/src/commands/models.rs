@@ -0,0 +1,114 @@
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::State;
+
+use crate::config;
+use crate::errors::IpcError;
+use crate::io::blocking;
+use crate::logging;
+use crate::schema::models::{self, ModelEntry};
+use crate::schema::occurrences::lookup;
+use crate::AppState;
+
+const DEFAULT_API_URL: &str = "https://api.anthropic.com";
+
+const API_VERSION: &str = "2023-06-01";
+
+const API_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Result of `list_available_models`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelCatalog {
+    pub models: Vec<ModelEntry>,
+    /// Whether the models API was queried successfully.
+    pub live: bool,
+    /// Why the models API could not be queried, when it was asked for.
+    pub live_error: Option<String>,
+}
+
+/// An environment variable as Claude Code would see it: settings `env`
+/// first, then ssenrah's own environment.
+fn env_var(settings: &Value, name: &str) -> Option<String> {
+    lookup(settings, &format!("env.{}", name))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .or_else(|| std::env::var(name).ok())
+        .filter(|value| !value.trim().is_empty())
+}
+
+/// Lists the models the API key in `settings` can use.
+fn fetch_models(settings: &Value) -> Result<Vec<ModelEntry>, String> {
+    let key = env_var(settings, "ANTHROPIC_API_KEY")
+        .ok_or("ANTHROPIC_API_KEY is not set; the models API needs an API key")?;
+    let base = env_var(settings, "ANTHROPIC_BASE_URL").unwrap_or(DEFAULT_API_URL.to_string());
+    let url = format!("{}/v1/models?limit=1000", base.trim_end_matches('/'));
+
+    let response = ureq::AgentBuilder::new()
+        .timeout(API_TIMEOUT)
+        .build()
+        .get(&url)
+        .set("x-api-key", &key)
+        .set("anthropic-version", API_VERSION)
+        .call()
+        .map_err(|e| e.to_string())?;
+    let body: Value = serde_json::from_str(&response.into_string().map_err(|e| e.to_string())?)
+        .map_err(|e| format!("Unexpected models API response: {}", e))?;
+
+    Ok(body["data"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|model| {
+            Some(ModelEntry {
+                id: model["id"].as_str()?.to_string(),
+                source: "api",
+                description: model["display_name"].as_str().map(str::to_string),
+                allowed: true,
+            })
+        })
+        .collect())
+}
+
+/// IPC command: lists the models Claude Code can be pointed at in the
+/// current project.
+///
+/// Combines the effective `availableModels`, Claude Code's aliases, the full
+/// model names known to this release, and, with `live`, the models the
+/// configured `ANTHROPIC_API_KEY` can use according to the models API.
+/// Entries outside `availableModels` are marked `allowed: false`. A failed
+/// live query is reported in `liveError` rather than failing the command.
+#[tauri::command]
+pub async fn list_available_models(
+    live: Option<bool>,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<ModelCatalog, IpcError> {
+    logging::async_command("list_available_models", async move {
+        let project_root = state.project_root(&window);
+        blocking::run("list_available_models", move || {
+            let settings = config::compute_effective(&project_root)?.settings;
+            let (api_models, live_error) = if live.unwrap_or(false) {
+                match fetch_models(&settings) {
+                    Ok(found) => (Some(found), None),
+                    Err(e) => {
+                        tracing::warn!(error = %e, "models API query failed");
+                        (None, Some(e))
+                    }
+                }
+            } else {
+                (None, None)
+            };
+
+            Ok(ModelCatalog {
+                models: models::catalog(&settings, api_models.as_deref().unwrap_or_default()),
+                live: api_models.is_some(),
+                live_error,
+            })
+        })
+        .await
+    })
+    .await
+}
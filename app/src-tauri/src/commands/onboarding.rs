@@ -0,0 +1,65 @@
+use std::fs;
+
+use tauri::State;
+
+use crate::args;
+use crate::config::read_scope;
+use crate::errors::IpcError;
+use crate::onboarding::{self, Facts, OnboardingStatus, PersistedState, STEPS};
+use crate::platform::{detect, paths};
+use crate::types::ConfigScope;
+use crate::AppState;
+
+fn detect_facts(project_root: Option<String>, persisted: &PersistedState) -> Facts {
+    Facts {
+        config_dir_exists: paths::resolve_config_dir().is_ok_and(|dir| dir.is_dir()),
+        claude_code_installed: detect::detect_claude_code().0,
+        settings_valid: read_scope(&ConfigScope::User, &project_root).is_ok(),
+        project_opened: project_root.is_some() || !persisted.recent_projects.is_empty(),
+    }
+}
+
+/// IPC command: returns the first-run onboarding progress.
+///
+/// Each step in `onboarding::STEPS` is satisfied when detected on this
+/// machine (config directory exists, `claude` is on PATH, user settings
+/// parse, a project was opened before) or completed when the user finished
+/// or skipped it. `current` is the first step that is neither.
+#[tauri::command]
+pub fn get_onboarding_status(
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<OnboardingStatus, IpcError> {
+    let persisted = PersistedState::load_from(&onboarding::state_path()?);
+    let facts = detect_facts(state.project_root(&window), &persisted);
+    Ok(onboarding::status(&facts, &persisted))
+}
+
+/// IPC command: marks an onboarding step as done and returns the new status.
+///
+/// Completing `config_dir` creates the Claude Code config directory when it
+/// is missing. Other steps are only recorded, which is how the user skips
+/// them. Progress is saved in `{configDir}/ssenrah/state.json`.
+#[tauri::command]
+pub fn complete_onboarding_step(
+    step: String,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<OnboardingStatus, IpcError> {
+    args::one_of("step", &step, STEPS)?;
+
+    if step == "config_dir" {
+        let config_dir = paths::resolve_config_dir()?;
+        if !config_dir.is_dir() {
+            state.ensure_writable()?;
+            fs::create_dir_all(&config_dir).map_err(|e| IpcError::WriteFailed {
+                path: config_dir.display().to_string(),
+                message: format!("Failed to create directory: {}", e),
+            })?;
+        }
+    }
+
+    let persisted = onboarding::update(|s| s.mark_complete(&step))?;
+    let facts = detect_facts(state.project_root(&window), &persisted);
+    Ok(onboarding::status(&facts, &persisted))
+}
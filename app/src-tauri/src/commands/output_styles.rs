@@ -0,0 +1,254 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::json;
+use tauri::State;
+
+use crate::args;
+use crate::audit;
+use crate::config::read_scope;
+use crate::errors::IpcError;
+use crate::frontmatter::{parse_frontmatter, serialize_frontmatter};
+use crate::io::atomic::atomic_write;
+use crate::io::self_write;
+use crate::io::trash::{self, TrashItem};
+use crate::platform::paths;
+use crate::schema::output_style::BUILTIN_STYLES;
+use crate::types::ConfigScope;
+use crate::AppState;
+
+/// An output style available to `/output-style`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputStyleInfo {
+    /// The name selected with the `outputStyle` setting.
+    pub name: String,
+    pub description: Option<String>,
+    /// "builtin", "user", or "project".
+    pub scope: String,
+    /// File name inside the scope's `output-styles/` directory.
+    pub filename: Option<String>,
+    /// Frontmatter of the style file, for previews.
+    pub frontmatter: Option<serde_json::Value>,
+    /// The effective `outputStyle` setting selects this style.
+    pub active: bool,
+}
+
+/// Resolves the output styles directory for a scope.
+///
+/// - "user"    -> {configDir}/output-styles/
+/// - "project" -> {projectRoot}/.claude/output-styles/
+fn resolve_output_styles_dir(
+    scope: &str,
+    project_root: &Option<String>,
+) -> Result<PathBuf, IpcError> {
+    match scope {
+        "user" => Ok(paths::resolve_config_dir()?.join("output-styles")),
+        "project" => {
+            let root = project_root.as_ref().ok_or_else(|| IpcError::NoProject {
+                message:
+                    "No project is open. Open a project before accessing project output styles."
+                        .to_string(),
+            })?;
+            Ok(PathBuf::from(root).join(".claude").join("output-styles"))
+        }
+        _ => Err(IpcError::InvalidArgument {
+            argument: "scope".to_string(),
+            message: format!("Unknown output style scope: {}", scope),
+        }),
+    }
+}
+
+/// Resolves a style file, rejecting anything that is not a plain `.md`
+/// file name.
+fn resolve_style_file(dir: &Path, filename: &str) -> Result<PathBuf, IpcError> {
+    args::file_name("filename", filename)?;
+    if !filename.ends_with(".md") {
+        return Err(args::invalid(
+            "filename",
+            format!("Invalid output style filename: {}", filename),
+        ));
+    }
+    Ok(dir.join(filename))
+}
+
+/// The `outputStyle` setting from the highest-precedence scope that sets it.
+fn active_style(project_root: &Option<String>) -> String {
+    [
+        ConfigScope::Managed,
+        ConfigScope::Local,
+        ConfigScope::Project,
+        ConfigScope::User,
+    ]
+    .iter()
+    .find_map(|scope| {
+        let settings = read_scope(scope, project_root).ok().flatten()?;
+        settings.get("outputStyle")?.as_str().map(str::to_string)
+    })
+    .unwrap_or_else(|| "default".to_string())
+}
+
+/// IPC command: lists the built-in output styles and the style files in
+/// the user and project `output-styles/` directories.
+///
+/// A style's name is its frontmatter `name`, or the file name without
+/// `.md`. The style selected by the effective `outputStyle` setting is
+/// marked `active`.
+#[tauri::command]
+pub fn list_output_styles(
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<Vec<OutputStyleInfo>, IpcError> {
+    let project_root = state.project_root(&window);
+    let active = active_style(&project_root);
+
+    let mut styles: Vec<OutputStyleInfo> = BUILTIN_STYLES
+        .iter()
+        .map(|(name, description)| OutputStyleInfo {
+            name: name.to_string(),
+            description: Some(description.to_string()),
+            scope: "builtin".to_string(),
+            filename: None,
+            frontmatter: None,
+            active: *name == active,
+        })
+        .collect();
+
+    for scope in ["user", "project"] {
+        let Ok(dir) = resolve_output_styles_dir(scope, &project_root) else {
+            continue;
+        };
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut found: Vec<OutputStyleInfo> = entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("md"))
+            .map(|path| {
+                let filename = path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                let content = fs::read_to_string(&path).unwrap_or_default();
+                let (frontmatter, _) = parse_frontmatter(&content);
+                let field = |key: &str| frontmatter.get(key).and_then(|v| v.as_str());
+                let name = field("name")
+                    .map(str::to_string)
+                    .unwrap_or_else(|| filename.trim_end_matches(".md").to_string());
+                OutputStyleInfo {
+                    description: field("description").map(str::to_string),
+                    active: name == active,
+                    name,
+                    scope: scope.to_string(),
+                    filename: Some(filename),
+                    frontmatter: Some(frontmatter),
+                }
+            })
+            .collect();
+        found.sort_by(|a, b| a.name.cmp(&b.name));
+        styles.extend(found);
+    }
+
+    Ok(styles)
+}
+
+/// IPC command: reads an output style file and returns its frontmatter and
+/// body.
+#[tauri::command]
+pub fn read_output_style(
+    scope: String,
+    filename: String,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<serde_json::Value, IpcError> {
+    let project_root = state.project_root(&window);
+
+    let dir = resolve_output_styles_dir(&scope, &project_root)?;
+    let path = resolve_style_file(&dir, &filename)?;
+    let path_str = path.display().to_string();
+
+    let content = fs::read_to_string(&path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => IpcError::NotFound {
+            path: path_str.clone(),
+        },
+        std::io::ErrorKind::PermissionDenied => IpcError::PermissionDenied {
+            path: path_str.clone(),
+        },
+        _ => IpcError::PlatformError {
+            message: format!("Failed to read {}: {}", path_str, e),
+        },
+    })?;
+
+    let (frontmatter, body) = parse_frontmatter(&content);
+
+    Ok(json!({
+        "frontmatter": frontmatter,
+        "body": body,
+    }))
+}
+
+/// IPC command: writes an output style file with YAML frontmatter and a
+/// markdown body (the style's system prompt).
+#[tauri::command]
+pub fn write_output_style(
+    scope: String,
+    filename: String,
+    frontmatter: serde_json::Value,
+    body: String,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<(), IpcError> {
+    state.ensure_writable()?;
+    args::max_bytes("body", body.len(), args::MAX_CONTENT_BYTES)?;
+    let project_root = state.project_root(&window);
+
+    let dir = resolve_output_styles_dir(&scope, &project_root)?;
+    let path = resolve_style_file(&dir, &filename)?;
+
+    fs::create_dir_all(&dir).map_err(|e| IpcError::WriteFailed {
+        path: dir.display().to_string(),
+        message: format!("Failed to create output styles directory: {}", e),
+    })?;
+
+    let fm = serialize_frontmatter(&frontmatter);
+    let content = if fm.is_empty() {
+        body
+    } else {
+        format!("---\n{}\n---\n\n{}", fm, body)
+    };
+
+    atomic_write(&path, content.as_bytes())
+}
+
+/// IPC command: deletes an output style file.
+///
+/// The file is moved to ssenrah's trash rather than removed; the returned
+/// item can be passed to `restore_deleted` within the retention period.
+#[tauri::command]
+pub fn delete_output_style(
+    scope: String,
+    filename: String,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<TrashItem, IpcError> {
+    state.ensure_writable()?;
+    let project_root = state.project_root(&window);
+
+    let dir = resolve_output_styles_dir(&scope, &project_root)?;
+    let path = resolve_style_file(&dir, &filename)?;
+
+    if !path.exists() {
+        return Err(IpcError::NotFound {
+            path: path.display().to_string(),
+        });
+    }
+
+    let hash_before = audit::file_hash(&path);
+    let item = trash::move_to_trash("output-style", &scope, &path)?;
+    self_write::tracker().register(&path);
+    audit::record_delete(&path, hash_before);
+
+    Ok(item)
+}
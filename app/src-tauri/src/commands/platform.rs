@@ -1,6 +1,18 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::Emitter;
+
 use crate::errors::IpcError;
+use crate::io::blocking;
+use crate::logging;
 use crate::platform::detect;
 use crate::platform::paths;
+use crate::platform::portable;
+use crate::platform::proxy::{self, DetectedProxy};
+use crate::schema::compat::{self, FeatureSupport, Version};
 use crate::types::PlatformInfo;
 
 /// IPC command: returns information about the host platform.
@@ -10,7 +22,11 @@ pub fn get_platform_info() -> Result<PlatformInfo, IpcError> {
     let is_wsl = detect::is_wsl();
     let shell = detect::detect_shell();
     let (claude_code_installed, claude_code_path) = detect::detect_claude_code();
-    let config_dir = paths::resolve_config_dir()
+    let claude_code_version = claude_code_path
+        .as_deref()
+        .and_then(|p| p.lines().next())
+        .and_then(|p| detect::detect_claude_code_version(p.trim()));
+    let config_dir = paths::resolve_config_dir()?
         .to_string_lossy()
         .to_string();
     let managed_settings_dir = paths::resolve_managed_settings_dir()
@@ -22,7 +38,209 @@ pub fn get_platform_info() -> Result<PlatformInfo, IpcError> {
         shell,
         claude_code_installed,
         claude_code_path,
+        claude_code_version,
         config_dir,
         managed_settings_dir,
+        app_dir: paths::resolve_app_dir()?.to_string_lossy().to_string(),
+        portable: portable::root().is_some(),
+        proxies: proxy::detect()
+            .iter()
+            .map(DetectedProxy::redacted)
+            .collect(),
+    })
+}
+
+/// IPC command: points ssenrah at a different Claude Code config directory
+/// at runtime (e.g. to inspect a test profile), or clears the override when
+/// `path` is `None` or empty.
+///
+/// Every subsequent path resolution respects the override. Emits a
+/// `config_dir_changed` event carrying the new `configDir` so the frontend
+/// can re-subscribe the watcher and reload open panels.
+#[tauri::command]
+pub fn set_config_dir_override(
+    path: Option<String>,
+    app: tauri::AppHandle,
+) -> Result<PlatformInfo, IpcError> {
+    let dir = path.filter(|p| !p.is_empty()).map(PathBuf::from);
+
+    if let Some(ref dir) = dir {
+        if !dir.is_dir() {
+            return Err(IpcError::NotFound {
+                path: dir.display().to_string(),
+            });
+        }
+    }
+
+    paths::set_config_dir_override(dir);
+
+    let config_dir = paths::resolve_config_dir()?.to_string_lossy().to_string();
+    let _ = app.emit(
+        "config_dir_changed",
+        serde_json::json!({ "configDir": config_dir }),
+    );
+
+    get_platform_info()
+}
+
+/// IPC command: uses `path` as the home directory instead of the one the OS
+/// reports, or clears the override when `path` is `None` or empty.
+///
+/// For portable installs and hosts with no home directory. `~/.claude.json`
+/// and `~` expansion follow the override, and so does the config dir unless
+/// `CLAUDE_CONFIG_DIR` or a config dir override is set. Emits
+/// `config_dir_changed` like `set_config_dir_override`.
+#[tauri::command]
+pub fn set_home_override(
+    path: Option<String>,
+    app: tauri::AppHandle,
+) -> Result<PlatformInfo, IpcError> {
+    let dir = path.filter(|p| !p.is_empty()).map(PathBuf::from);
+
+    if let Some(ref dir) = dir {
+        if !dir.is_dir() {
+            return Err(IpcError::NotFound {
+                path: dir.display().to_string(),
+            });
+        }
+    }
+
+    paths::set_home_override(dir);
+
+    let config_dir = paths::resolve_config_dir()?.to_string_lossy().to_string();
+    let _ = app.emit(
+        "config_dir_changed",
+        serde_json::json!({ "configDir": config_dir }),
+    );
+
+    get_platform_info()
+}
+
+/// Installed Claude Code version and the versioned settings it supports.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeCodeInfo {
+    pub installed: bool,
+    pub path: Option<String>,
+    /// Raw `claude --version` output.
+    pub version_output: Option<String>,
+    /// Parsed `major.minor.patch`, if the output contained one.
+    pub version: Option<String>,
+    /// Support for each settings key that needs a minimum CLI version.
+    /// Empty when the version is unknown.
+    pub features: Vec<FeatureSupport>,
+}
+
+/// IPC command: runs `claude --version` and reports which versioned settings
+/// keys the installed CLI understands.
+///
+/// The detected version is cached, and `validate_settings` uses it to warn
+/// about keys the installed CLI would silently ignore.
+#[tauri::command]
+pub fn get_claude_code_info() -> Result<ClaudeCodeInfo, IpcError> {
+    let (installed, path) = detect::detect_claude_code();
+    let path = path.and_then(|p| p.lines().next().map(|l| l.trim().to_string()));
+    let version_output = path
+        .as_deref()
+        .and_then(detect::detect_claude_code_version);
+    let version = version_output.as_deref().and_then(Version::parse);
+
+    Ok(ClaudeCodeInfo {
+        installed,
+        path,
+        version_output,
+        version: version.map(|v| v.to_string()),
+        features: version.map(compat::feature_matrix).unwrap_or_default(),
+    })
+}
+
+/// Host used to check that Claude Code can reach the API through a proxy.
+const CONNECTIVITY_URL: &str = "https://api.anthropic.com/";
+
+/// Result of reaching the Anthropic API through a proxy.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectivityResult {
+    pub ok: bool,
+    /// HTTP status of the response, if one arrived.
+    pub status: Option<u16>,
+    pub duration_ms: f64,
+    pub error: Option<String>,
+}
+
+/// Suggested settings `env` entries for a detected proxy.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxySuggestion {
+    /// The proxy the suggestion is based on, credentials redacted.
+    pub proxy: Option<DetectedProxy>,
+    /// `HTTPS_PROXY` / `HTTP_PROXY` / `NO_PROXY` entries to merge into `env`.
+    pub env: BTreeMap<String, String>,
+    pub connectivity: Option<ConnectivityResult>,
+}
+
+fn check_connectivity(proxy_url: &str) -> ConnectivityResult {
+    let start = Instant::now();
+    let (status, error) = match ureq::Proxy::new(proxy_url) {
+        Ok(proxy) => match ureq::AgentBuilder::new()
+            .proxy(proxy)
+            .timeout(Duration::from_secs(10))
+            .build()
+            .get(CONNECTIVITY_URL)
+            .call()
+        {
+            Ok(response) => (Some(response.status()), None),
+            // Any HTTP response means the proxy forwarded the request.
+            Err(ureq::Error::Status(code, _)) => (Some(code), None),
+            Err(e) => (None, Some(e.to_string())),
+        },
+        Err(e) => (None, Some(e.to_string())),
+    };
+    ConnectivityResult {
+        ok: status.is_some(),
+        status,
+        duration_ms: start.elapsed().as_secs_f64() * 1000.0,
+        error,
+    }
+}
+
+/// IPC command: suggests settings `env` entries for the proxy configured on
+/// this machine.
+///
+/// Environment variables take precedence over the system proxy (macOS
+/// System Settings, Windows WinINET). With `test_connectivity`, a request
+/// to api.anthropic.com is sent through the suggested `HTTPS_PROXY` to
+/// confirm the proxy works. Returns empty `env` when no proxy is found.
+#[tauri::command]
+pub async fn suggest_proxy_env_settings(
+    test_connectivity: Option<bool>,
+) -> Result<ProxySuggestion, IpcError> {
+    logging::async_command("suggest_proxy_env_settings", async move {
+        blocking::run("suggest_proxy_env_settings", move || {
+            let Some(detected) = proxy::detect().into_iter().next() else {
+                return Ok(ProxySuggestion {
+                    proxy: None,
+                    env: BTreeMap::new(),
+                    connectivity: None,
+                });
+            };
+
+            let proxy_url = detected
+                .https_proxy
+                .as_ref()
+                .or(detected.http_proxy.as_ref());
+            let connectivity = match proxy_url {
+                Some(url) if test_connectivity.unwrap_or(false) => Some(check_connectivity(url)),
+                _ => None,
+            };
+
+            Ok(ProxySuggestion {
+                env: detected.env_entries(),
+                proxy: Some(detected.redacted()),
+                connectivity,
+            })
+        })
+        .await
     })
+    .await
 }
@@ -0,0 +1,413 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::State;
+
+use crate::config::read_scope;
+use crate::errors::IpcError;
+use crate::io::atomic::atomic_write;
+use crate::io::lock::lock_path;
+use crate::platform::paths;
+use crate::schema::markdown::LintReport;
+use crate::schema::plugin;
+use crate::types::{ConfigScope, WritableScope};
+use crate::validation::ValidationResult;
+use crate::AppState;
+
+/// Plugin manifest location inside a plugin's install directory.
+const MANIFEST_PATH: &str = ".claude-plugin/plugin.json";
+
+/// An installed Claude Code plugin.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginInfo {
+    /// `plugin@marketplace`, the key used by `enabledPlugins`.
+    pub id: String,
+    pub name: String,
+    pub marketplace: Option<String>,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    pub install_path: String,
+    /// Effective `enabledPlugins` value, or `None` if no scope sets it.
+    pub enabled: Option<bool>,
+    /// Highest-precedence scope that sets `enabled`.
+    pub enabled_scope: Option<String>,
+}
+
+/// What a plugin contributes, as found on disk.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginComponents {
+    /// Slash command names, without the leading `/`.
+    pub commands: Vec<String>,
+    pub agents: Vec<String>,
+    pub skills: Vec<String>,
+    /// Hook events with at least one handler (`PreToolUse`, `Stop`, ...).
+    pub hooks: Vec<String>,
+    pub mcp_servers: Vec<String>,
+}
+
+/// Result of `inspect_plugin`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginDetails {
+    #[serde(flatten)]
+    pub info: PluginInfo,
+    pub manifest: Option<Value>,
+    pub components: PluginComponents,
+    pub validation: ValidationResult,
+}
+
+/// Resolves `{configDir}/plugins/`.
+fn plugins_dir() -> Result<PathBuf, IpcError> {
+    Ok(paths::resolve_config_dir()?.join("plugins"))
+}
+
+/// Reads `installed_plugins.json` as `(id, installPath, version)` triples.
+///
+/// Both the original layout (`plugins.{id}` is an object) and the current
+/// one (`plugins.{id}` is a list of per-scope installs) are accepted; the
+/// first install of a plugin wins.
+fn installed_plugins() -> Result<Vec<(String, PathBuf, Option<String>)>, IpcError> {
+    let path = plugins_dir()?.join("installed_plugins.json");
+    let index = match fs::read_to_string(&path) {
+        Ok(text) => serde_json::from_str::<Value>(&text).map_err(|e| IpcError::ParseError {
+            path: path.display().to_string(),
+            message: e.to_string(),
+        })?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(IpcError::PlatformError {
+                message: format!("Failed to read {}: {}", path.display(), e),
+            })
+        }
+    };
+
+    let Some(plugins) = index.get("plugins").and_then(Value::as_object) else {
+        return Ok(Vec::new());
+    };
+    Ok(plugins
+        .iter()
+        .filter_map(|(id, entry)| {
+            let install = match entry {
+                Value::Array(installs) => installs.first()?,
+                other => other,
+            };
+            let install_path = install.get("installPath")?.as_str()?;
+            let version = install
+                .get("version")
+                .and_then(Value::as_str)
+                .map(str::to_string);
+            Some((id.clone(), PathBuf::from(install_path), version))
+        })
+        .collect())
+}
+
+fn read_manifest(install_path: &Path) -> Option<Value> {
+    fs::read_to_string(install_path.join(MANIFEST_PATH))
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+}
+
+/// `enabledPlugins` from every readable scope, highest precedence first.
+fn enablement(project_root: &Option<String>) -> Vec<(&'static str, Value)> {
+    [
+        ("managed", ConfigScope::Managed),
+        ("local", ConfigScope::Local),
+        ("project", ConfigScope::Project),
+        ("user", ConfigScope::User),
+    ]
+    .into_iter()
+    .filter_map(|(name, scope)| {
+        let settings = read_scope(&scope, project_root).ok().flatten()?;
+        Some((name, settings.get("enabledPlugins")?.clone()))
+    })
+    .collect()
+}
+
+fn plugin_info(
+    id: &str,
+    install_path: &Path,
+    version: Option<String>,
+    manifest: Option<&Value>,
+    enabled_by: &[(&'static str, Value)],
+) -> PluginInfo {
+    let (name, marketplace) = match id.split_once('@') {
+        Some((name, marketplace)) => (name.to_string(), Some(marketplace.to_string())),
+        None => (id.to_string(), None),
+    };
+    let field = |key: &str| {
+        manifest
+            .and_then(|m| m.get(key))
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    };
+    let (enabled_scope, enabled) = enabled_by
+        .iter()
+        .find_map(|(scope, map)| Some((scope.to_string(), map.get(id)?.as_bool()?)))
+        .unzip();
+
+    PluginInfo {
+        id: id.to_string(),
+        name,
+        marketplace,
+        version: version.or_else(|| field("version")),
+        description: field("description"),
+        install_path: install_path.display().to_string(),
+        enabled,
+        enabled_scope,
+    }
+}
+
+/// IPC command: lists the Claude Code plugins installed under
+/// `{configDir}/plugins/`, with their effective enablement.
+///
+/// Enablement comes from `enabledPlugins` in managed, local, project, and
+/// user settings, in that order of precedence.
+#[tauri::command]
+pub fn list_plugins(
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<Vec<PluginInfo>, IpcError> {
+    let project_root = state.project_root(&window);
+    let enabled_by = enablement(&project_root);
+
+    let mut plugins: Vec<PluginInfo> = installed_plugins()?
+        .into_iter()
+        .map(|(id, install_path, version)| {
+            let manifest = read_manifest(&install_path);
+            plugin_info(&id, &install_path, version, manifest.as_ref(), &enabled_by)
+        })
+        .collect();
+    plugins.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(plugins)
+}
+
+/// IPC command: sets `enabledPlugins.{pluginId}` in the given settings scope.
+///
+/// Other settings are left untouched. The plugin does not have to be
+/// installed, so a project can enable plugins its members install later.
+#[tauri::command]
+pub fn set_plugin_enabled(
+    plugin_id: String,
+    enabled: bool,
+    scope: WritableScope,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<(), IpcError> {
+    state.ensure_writable()?;
+    let project_root = state.project_root(&window);
+
+    let config_scope = match scope {
+        WritableScope::User => ConfigScope::User,
+        WritableScope::Project => ConfigScope::Project,
+        WritableScope::Local => ConfigScope::Local,
+    };
+    let path = paths::resolve_settings_path(&config_scope, &project_root)?;
+    let path_str = path.display().to_string();
+    let _lock = lock_path(&path);
+
+    let mut settings = match read_scope(&config_scope, &project_root)? {
+        Some(Value::Object(m)) => m,
+        Some(_) => {
+            return Err(IpcError::ParseError {
+                path: path_str,
+                message: "Settings file is not a JSON object".to_string(),
+            })
+        }
+        None => serde_json::Map::new(),
+    };
+    let plugins = settings
+        .entry("enabledPlugins")
+        .or_insert_with(|| Value::Object(serde_json::Map::new()));
+    if !plugins.is_object() {
+        *plugins = Value::Object(serde_json::Map::new());
+    }
+    plugins[&plugin_id] = Value::Bool(enabled);
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| IpcError::WriteFailed {
+            path: path_str.clone(),
+            message: format!("Failed to create parent directory: {}", e),
+        })?;
+    }
+    let content = serde_json::to_vec_pretty(&settings).map_err(|e| IpcError::WriteFailed {
+        path: path_str,
+        message: format!("Failed to serialize settings: {}", e),
+    })?;
+    atomic_write(&path, &content)
+}
+
+/// Resolves a manifest path (`./commands/x.md`) inside the plugin root.
+fn plugin_path(root: &Path, path: &str) -> Option<PathBuf> {
+    let relative = Path::new(path);
+    relative
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+        .then(|| root.join(relative))
+}
+
+/// Default component location plus any extra paths from the manifest.
+fn component_paths(root: &Path, manifest: Option<&Value>, key: &str) -> Vec<PathBuf> {
+    let mut found = vec![root.join(key)];
+    let extra: Vec<&Value> = match manifest.and_then(|m| m.get(key)) {
+        Some(Value::Array(items)) => items.iter().collect(),
+        Some(other) => vec![other],
+        None => Vec::new(),
+    };
+    found.extend(
+        extra
+            .into_iter()
+            .filter_map(Value::as_str)
+            .filter_map(|p| plugin_path(root, p)),
+    );
+    found
+}
+
+/// Names of the `.md` files at `paths` (files, or directories searched
+/// recursively, as Claude Code namespaces nested commands).
+fn markdown_names(paths: &[PathBuf]) -> Vec<String> {
+    fn walk(dir: &Path, out: &mut BTreeSet<String>) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+            if path.is_dir() {
+                walk(&path, out);
+            } else if path.extension().and_then(|e| e.to_str()) == Some("md") {
+                out.extend(path.file_stem().map(|s| s.to_string_lossy().to_string()));
+            }
+        }
+    }
+
+    let mut names = BTreeSet::new();
+    for path in paths {
+        if path.is_dir() {
+            walk(path, &mut names);
+        } else if path.is_file() {
+            names.extend(path.file_stem().map(|s| s.to_string_lossy().to_string()));
+        }
+    }
+    names.into_iter().collect()
+}
+
+/// Keys of the object under `key` in `inline` (a manifest value), or in the
+/// JSON file it or `default_file` points at.
+fn object_keys(root: &Path, inline: Option<&Value>, default_file: &str, key: &str) -> Vec<String> {
+    let mut sources = vec![root.join(default_file)];
+    let mut names = BTreeSet::new();
+    match inline {
+        Some(Value::Object(obj)) => {
+            let block = obj.get(key).and_then(Value::as_object).unwrap_or(obj);
+            names.extend(block.keys().cloned());
+        }
+        Some(Value::String(path)) => sources.extend(plugin_path(root, path)),
+        _ => {}
+    }
+    for source in sources {
+        let Some(value) = fs::read_to_string(&source)
+            .ok()
+            .and_then(|text| serde_json::from_str::<Value>(&text).ok())
+        else {
+            continue;
+        };
+        let block = value.get(key).unwrap_or(&value);
+        names.extend(
+            block
+                .as_object()
+                .into_iter()
+                .flat_map(|o| o.keys().cloned()),
+        );
+    }
+    names.into_iter().collect()
+}
+
+fn plugin_components(root: &Path, manifest: Option<&Value>) -> PluginComponents {
+    let skills = fs::read_dir(root.join("skills"))
+        .ok()
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().join("SKILL.md").is_file())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    PluginComponents {
+        commands: markdown_names(&component_paths(root, manifest, "commands")),
+        agents: markdown_names(&component_paths(root, manifest, "agents")),
+        skills,
+        hooks: object_keys(
+            root,
+            manifest.and_then(|m| m.get("hooks")),
+            "hooks/hooks.json",
+            "hooks",
+        ),
+        mcp_servers: object_keys(
+            root,
+            manifest.and_then(|m| m.get("mcpServers")),
+            ".mcp.json",
+            "mcpServers",
+        ),
+    }
+}
+
+/// IPC command: inspects an installed plugin: its manifest, the commands,
+/// agents, skills, hooks, and MCP servers it provides, and manifest
+/// validation results.
+///
+/// Components are found in the default directories (`commands/`,
+/// `agents/`, `skills/`, `hooks/hooks.json`, `.mcp.json`) plus any extra
+/// paths or inline objects declared in the manifest.
+#[tauri::command]
+pub fn inspect_plugin(
+    plugin_id: String,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<PluginDetails, IpcError> {
+    let project_root = state.project_root(&window);
+
+    let (id, install_path, version) = installed_plugins()?
+        .into_iter()
+        .find(|(id, ..)| *id == plugin_id)
+        .ok_or_else(|| IpcError::NotFound {
+            path: plugins_dir()
+                .map(|dir| dir.join("installed_plugins.json").display().to_string())
+                .unwrap_or_default(),
+        })?;
+
+    let manifest = read_manifest(&install_path);
+    let report = match &manifest {
+        Some(m) => plugin::validate_manifest(m),
+        None => {
+            let mut report = LintReport::default();
+            report.warning(
+                MANIFEST_PATH.to_string(),
+                "Plugin has no readable manifest".to_string(),
+                "MISSING_MANIFEST",
+            );
+            report
+        }
+    };
+
+    Ok(PluginDetails {
+        info: plugin_info(
+            &id,
+            &install_path,
+            version,
+            manifest.as_ref(),
+            &enablement(&project_root),
+        ),
+        components: plugin_components(&install_path, manifest.as_ref()),
+        manifest,
+        validation: ValidationResult {
+            valid: report.errors.is_empty(),
+            errors: report.errors,
+            warnings: report.warnings,
+        },
+    })
+}
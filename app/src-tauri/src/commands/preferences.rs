@@ -0,0 +1,49 @@
+use crate::errors::IpcError;
+use crate::io::lock::lock_path;
+use crate::metrics;
+use crate::preferences::{self, AppPreferences, CURRENT_VERSION};
+
+/// IPC command: returns ssenrah's own preferences (theme, backup retention,
+/// watcher debounce, telemetry opt-in).
+///
+/// Read from `{configDir}/ssenrah/preferences.json`, migrated to the current
+/// layout version, with defaults for anything missing.
+#[tauri::command]
+pub fn read_app_preferences() -> Result<AppPreferences, IpcError> {
+    preferences::load_from(&preferences::preferences_path()?)
+}
+
+/// IPC command: validates and saves ssenrah's preferences, returning what
+/// was written.
+///
+/// Fails with `validation_error` for out-of-range values, and refuses to
+/// overwrite a file written by a newer ssenrah. These preferences never
+/// touch Claude Code's settings files, so read-only mode does not apply.
+#[tauri::command]
+pub fn write_app_preferences(preferences: AppPreferences) -> Result<AppPreferences, IpcError> {
+    let errors = preferences.validate();
+    if !errors.is_empty() {
+        return Err(IpcError::ValidationError { errors });
+    }
+
+    let path = preferences::preferences_path()?;
+    let _lock = lock_path(&path);
+    let existing = preferences::load_from(&path)?;
+    if existing.version > CURRENT_VERSION {
+        return Err(IpcError::Unsupported {
+            message: format!(
+                "{} was written by a newer version of ssenrah (layout {})",
+                path.display(),
+                existing.version
+            ),
+        });
+    }
+
+    let preferences = AppPreferences {
+        version: CURRENT_VERSION,
+        ..preferences
+    };
+    preferences::save_to(&path, &preferences)?;
+    metrics::set_enabled(preferences.telemetry_opt_in);
+    Ok(preferences)
+}
@@ -0,0 +1,286 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::args;
+use crate::audit;
+use crate::errors::IpcError;
+use crate::io::backup;
+use crate::io::copy::{copy_path, remove_path};
+use crate::io::journal::Transaction;
+use crate::platform::paths;
+use crate::AppState;
+
+/// User-scope items captured in a profile, relative to the config dir.
+const PROFILE_ITEMS: &[&str] = &["settings.json", "CLAUDE.md", "agents", "commands"];
+
+/// Marker file recording which profile was last activated.
+const ACTIVE_MARKER: &str = ".active";
+
+/// A saved configuration profile.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileInfo {
+    pub name: String,
+    pub path: String,
+    /// Which of the profile items the snapshot contains.
+    pub items: Vec<String>,
+    pub active: bool,
+}
+
+/// Result of switching to a profile.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivateProfileResult {
+    pub profile: ProfileInfo,
+    /// Where the previous user configuration was backed up.
+    pub backup_path: String,
+}
+
+/// Resolves `{configDir}/profiles/`.
+fn profiles_dir() -> Result<PathBuf, IpcError> {
+    Ok(paths::resolve_config_dir()?.join("profiles"))
+}
+
+/// Validates a profile name and resolves its directory.
+///
+/// Names are limited to letters, digits, `-`, `_`, and `.` (not leading) so
+/// they are safe as a single path component on every platform.
+fn resolve_profile_dir(name: &str) -> Result<PathBuf, IpcError> {
+    Ok(profiles_dir()?.join(args::identifier("name", name)?))
+}
+
+fn active_profile() -> Option<String> {
+    fs::read_to_string(profiles_dir().ok()?.join(ACTIVE_MARKER))
+        .ok()
+        .map(|s| s.trim().to_string())
+}
+
+fn profile_info(name: &str, dir: &Path) -> ProfileInfo {
+    let items = PROFILE_ITEMS
+        .iter()
+        .filter(|item| dir.join(item).exists())
+        .map(|item| item.to_string())
+        .collect();
+
+    ProfileInfo {
+        name: name.to_string(),
+        path: dir.display().to_string(),
+        items,
+        active: active_profile().as_deref() == Some(name),
+    }
+}
+
+/// IPC command: lists saved profiles under `{configDir}/profiles/`.
+#[tauri::command]
+pub fn list_profiles() -> Result<Vec<ProfileInfo>, IpcError> {
+    let dir = profiles_dir()?;
+    if !dir.exists() {
+        return Ok(vec![]);
+    }
+
+    let entries = fs::read_dir(&dir).map_err(|e| IpcError::PlatformError {
+        message: format!("Failed to read profiles directory: {}", e),
+    })?;
+
+    let mut profiles = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if path.is_dir() && !name.starts_with('.') {
+            profiles.push(profile_info(&name, &path));
+        }
+    }
+    profiles.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(profiles)
+}
+
+/// IPC command: snapshots the current user-scope settings.json, CLAUDE.md,
+/// agents, and commands into `{configDir}/profiles/{name}/`.
+///
+/// Fails if the profile already exists unless `overwrite` is true.
+#[tauri::command]
+pub fn create_profile(
+    name: String,
+    overwrite: Option<bool>,
+    state: State<AppState>,
+) -> Result<ProfileInfo, IpcError> {
+    state.ensure_writable()?;
+    let dir = resolve_profile_dir(&name)?;
+    let path_str = dir.display().to_string();
+
+    if dir.exists() {
+        if !overwrite.unwrap_or(false) {
+            return Err(IpcError::Conflict {
+                path: path_str,
+                message: format!("Profile '{}' already exists", name),
+            });
+        }
+        remove_path(&dir).map_err(|e| IpcError::WriteFailed {
+            path: path_str.clone(),
+            message: format!("Failed to replace profile: {}", e),
+        })?;
+    }
+
+    fs::create_dir_all(&dir).map_err(|e| IpcError::WriteFailed {
+        path: path_str.clone(),
+        message: format!("Failed to create profile directory: {}", e),
+    })?;
+
+    let config_dir = paths::resolve_config_dir()?;
+    for item in PROFILE_ITEMS {
+        let src = config_dir.join(item);
+        if src.exists() {
+            copy_path(&src, &dir.join(item)).map_err(|e| IpcError::WriteFailed {
+                path: path_str.clone(),
+                message: format!("Failed to snapshot {}: {}", item, e),
+            })?;
+        }
+    }
+
+    Ok(profile_info(&name, &dir))
+}
+
+/// IPC command: swaps a saved profile into the user config dir.
+///
+/// The current items are backed up first. All items are swapped in one
+/// journaled transaction: each is staged next to its target and moved into
+/// place with a rename, so Claude Code never sees a half-copied directory
+/// and a crash midway is rolled back on the next start. Items absent from
+/// the profile are removed, making the user scope match the snapshot
+/// exactly.
+#[tauri::command]
+pub fn activate_profile(
+    name: String,
+    state: State<AppState>,
+) -> Result<ActivateProfileResult, IpcError> {
+    state.ensure_writable()?;
+    let dir = resolve_profile_dir(&name)?;
+    if !dir.is_dir() {
+        return Err(IpcError::NotFound {
+            path: dir.display().to_string(),
+        });
+    }
+
+    let config_dir = paths::resolve_config_dir()?;
+    let backup_dir = backup::backup_items(
+        &format!("profile-{}", name),
+        &config_dir,
+        PROFILE_ITEMS,
+    )?;
+
+    let txn = Transaction::begin(&format!("profile-{}", name))?;
+    swap_in(txn, &dir, &config_dir)?;
+
+    let _ = fs::write(profiles_dir()?.join(ACTIVE_MARKER), &name);
+
+    Ok(ActivateProfileResult {
+        profile: profile_info(&name, &dir),
+        backup_path: backup_dir.display().to_string(),
+    })
+}
+
+/// Replaces the items in `config_dir` with those of the profile in `dir`,
+/// removing items the profile lacks. If any item fails to stage, `txn` is
+/// dropped uncommitted and `config_dir` is left as it was.
+fn swap_in(mut txn: Transaction, dir: &Path, config_dir: &Path) -> Result<(), IpcError> {
+    for item in PROFILE_ITEMS {
+        let live = config_dir.join(item);
+        let source = dir.join(item);
+        if source.exists() {
+            txn.copy(&live, &source)?;
+        } else if live.exists() {
+            txn.remove(&live)?;
+        }
+    }
+    txn.commit()
+}
+
+/// IPC command: deletes a saved profile. The live configuration is untouched.
+#[tauri::command]
+pub fn delete_profile(name: String, state: State<AppState>) -> Result<(), IpcError> {
+    state.ensure_writable()?;
+    let dir = resolve_profile_dir(&name)?;
+    let path_str = dir.display().to_string();
+
+    if !dir.exists() {
+        return Err(IpcError::NotFound { path: path_str });
+    }
+
+    fs::remove_dir_all(&dir).map_err(|e| IpcError::WriteFailed {
+        path: path_str,
+        message: format!("Failed to delete profile: {}", e),
+    })?;
+    audit::record_delete(&dir, None);
+
+    if active_profile().as_deref() == Some(name.as_str()) {
+        let _ = fs::remove_file(profiles_dir()?.join(ACTIVE_MARKER));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    /// A profile with new settings and agents, and a live config whose
+    /// CLAUDE.md the profile lacks.
+    fn fixture(root: &Path) -> (PathBuf, PathBuf) {
+        let (profile, config) = (root.join("profile"), root.join("config"));
+        write(&profile.join("settings.json"), r#"{"model":"opus"}"#);
+        write(&profile.join("agents/reviewer.md"), "reviewer");
+        write(&config.join("settings.json"), "{}");
+        write(&config.join("CLAUDE.md"), "memory");
+        write(&config.join("agents/old.md"), "old");
+        (profile, config)
+    }
+
+    #[test]
+    fn activation_makes_the_config_match_the_profile() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (profile, config) = fixture(tmp.path());
+
+        let txn = Transaction::begin_in(tmp.path().join("journal"), "profile-test");
+        swap_in(txn, &profile, &config).unwrap();
+
+        let settings = fs::read_to_string(config.join("settings.json")).unwrap();
+        assert_eq!(settings, r#"{"model":"opus"}"#);
+        assert!(config.join("agents/reviewer.md").exists());
+        assert!(!config.join("agents/old.md").exists());
+        assert!(!config.join("CLAUDE.md").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn failed_activation_leaves_the_config_untouched() {
+        let tmp = tempfile::tempdir().unwrap();
+        let (profile, config) = fixture(tmp.path());
+        // A dangling symlink cannot be copied, so staging `commands` fails
+        // after the other items were staged.
+        fs::create_dir_all(profile.join("commands")).unwrap();
+        std::os::unix::fs::symlink("/nonexistent", profile.join("commands/gone.md")).unwrap();
+
+        let txn = Transaction::begin_in(tmp.path().join("journal"), "profile-test");
+        assert!(swap_in(txn, &profile, &config).is_err());
+
+        let settings = fs::read_to_string(config.join("settings.json")).unwrap();
+        assert_eq!(settings, "{}");
+        assert!(config.join("CLAUDE.md").exists());
+        assert!(config.join("agents/old.md").exists());
+        let mut names: Vec<_> = fs::read_dir(&config)
+            .unwrap()
+            .map(|e| e.unwrap().file_name())
+            .collect();
+        names.sort();
+        assert_eq!(names, ["CLAUDE.md", "agents", "settings.json"]);
+    }
+}
@@ -1,20 +1,27 @@
 use std::path::Path;
 
-use tauri::State;
+use tauri::{Manager, State};
 
+use crate::commands::watcher::WatcherState;
+use crate::commands::windows::{forget_label, restore_geometry};
 use crate::errors::IpcError;
+use crate::git::find_git_root;
+use crate::io::blocking;
+use crate::io::walk::DEFAULT_MAX_DEPTH;
+use crate::logging;
+use crate::onboarding;
+use crate::packages::{self, NestedClaudeDir};
+use crate::tray;
 use crate::types::ProjectInfo;
 use crate::AppState;
 
 /// IPC command: returns the current project info from managed state.
 #[tauri::command]
-pub fn get_project_info(state: State<AppState>) -> Result<ProjectInfo, IpcError> {
-    let project_root = state
-        .project_root
-        .lock()
-        .map_err(|e| IpcError::PlatformError {
-            message: format!("Failed to acquire state lock: {}", e),
-        })?;
+pub fn get_project_info(
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<ProjectInfo, IpcError> {
+    let project_root = state.project_root(&window);
 
     match project_root.as_ref() {
         Some(root) => {
@@ -36,38 +43,94 @@ pub fn get_project_info(state: State<AppState>) -> Result<ProjectInfo, IpcError>
     }
 }
 
-/// IPC command: opens a project directory.
+/// IPC command: last-resort recovery when commands keep failing after an
+/// internal panic.
 ///
-/// Validates that the path exists and is a directory, then stores it in
-/// AppState. Returns updated ProjectInfo.
+/// Locks left poisoned by the panic are cleared (commands already recover
+/// the data behind them, so this only silences the poison flag), and the
+/// calling window's project binding and file watcher are dropped as if the
+/// window had just opened. Other windows keep their projects. Returns the
+/// now-empty ProjectInfo; the frontend reopens the project from there.
 #[tauri::command]
-pub fn open_project(path: String, state: State<AppState>) -> Result<ProjectInfo, IpcError> {
+pub fn reset_app_state(
+    window: tauri::Window,
+    state: State<AppState>,
+    watcher_state: State<WatcherState>,
+) -> Result<ProjectInfo, IpcError> {
+    reset_window_state(&state, &watcher_state, window.label());
+    tracing::warn!(window = window.label(), "app state reset");
+    get_project_info(window, state)
+}
+
+fn reset_window_state(state: &AppState, watcher_state: &WatcherState, label: &str) {
+    state.projects.clear_poison();
+    watcher_state.watchers.clear_poison();
+    forget_label(state, watcher_state, label);
+}
+
+/// IPC command: lists the `.claude` directories of packages inside the open
+/// project, e.g. `packages/api/.claude` in a monorepo.
+///
+/// Each entry carries the scope address (`project:packages/api`) that the
+/// agents, skills, and settings commands accept to manage it. Returns an
+/// empty list when no project is open.
+#[tauri::command]
+pub async fn list_nested_claude_dirs(
+    max_depth: Option<usize>,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<Vec<NestedClaudeDir>, IpcError> {
+    logging::async_command("list_nested_claude_dirs", async move {
+        let project_root = state.project_root(&window);
+        blocking::run("list_nested_claude_dirs", move || {
+            Ok(project_root
+                .map(|root| {
+                    packages::find_nested_claude_dirs(
+                        Path::new(&root),
+                        max_depth.unwrap_or(DEFAULT_MAX_DEPTH),
+                    )
+                })
+                .unwrap_or_default())
+        })
+        .await
+    })
+    .await
+}
+
+/// IPC command: opens a project directory in the calling window.
+///
+/// Validates that the path exists and is a directory, then binds it to the
+/// window in AppState and restores the window's size and position saved for
+/// that project. Returns updated ProjectInfo.
+#[tauri::command]
+pub fn open_project(
+    path: String,
+    window: tauri::WebviewWindow,
+    state: State<AppState>,
+) -> Result<ProjectInfo, IpcError> {
     let root_path = Path::new(&path);
 
     // Validate the path exists and is a directory
     if !root_path.exists() {
-        return Err(IpcError::NotFound {
-            path: path.clone(),
-        });
+        return Err(IpcError::NotFound { path: path.clone() });
     }
 
     if !root_path.is_dir() {
-        return Err(IpcError::NotFound {
-            path: path.clone(),
-        });
+        return Err(IpcError::NotFound { path: path.clone() });
     }
 
     let claude_dir_exists = root_path.join(".claude").is_dir();
     let git_root = find_git_root(root_path);
 
     // Store the project root in managed state
-    let mut project_root = state
-        .project_root
-        .lock()
-        .map_err(|e| IpcError::PlatformError {
-            message: format!("Failed to acquire state lock: {}", e),
-        })?;
-    *project_root = Some(path.clone());
+    state.set_project_root(window.label(), Some(path.clone()));
+    restore_geometry(&window, Some(&path));
+
+    // Remembered for onboarding; failing to persist it is not an error.
+    if let Err(e) = onboarding::update(|s| s.record_project(&path)) {
+        tracing::warn!(error = %e, "failed to record opened project");
+    }
+    tray::refresh(window.app_handle());
 
     Ok(ProjectInfo {
         project_root: Some(path),
@@ -76,16 +139,44 @@ pub fn open_project(path: String, state: State<AppState>) -> Result<ProjectInfo,
     })
 }
 
-/// Walks up from the given path looking for a `.git` directory.
-/// Returns the path containing `.git`, or None.
-fn find_git_root(start: &Path) -> Option<String> {
-    let mut current = start.to_path_buf();
-    loop {
-        if current.join(".git").exists() {
-            return Some(current.to_string_lossy().to_string());
-        }
-        if !current.pop() {
-            return None;
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Mutex;
+
+    #[test]
+    fn reset_clears_poison_and_only_the_calling_window() {
+        let projects = HashMap::from([
+            ("main".to_string(), "/work/a".to_string()),
+            ("other".to_string(), "/work/b".to_string()),
+        ]);
+        let state = AppState {
+            projects: Mutex::new(projects),
+            read_only: AtomicBool::new(false),
+        };
+        let watcher_state = WatcherState {
+            watchers: Mutex::new(HashMap::new()),
+        };
+        std::thread::scope(|s| {
+            let projects = s.spawn(|| {
+                let _guard = state.projects.lock().unwrap();
+                panic!("command panicked");
+            });
+            let watchers = s.spawn(|| {
+                let _guard = watcher_state.watchers.lock().unwrap();
+                panic!("command panicked");
+            });
+            assert!(projects.join().is_err() && watchers.join().is_err());
+        });
+        assert!(state.projects.is_poisoned() && watcher_state.watchers.is_poisoned());
+
+        reset_window_state(&state, &watcher_state, "main");
+
+        assert!(!state.projects.is_poisoned() && !watcher_state.watchers.is_poisoned());
+        let projects = state.projects.lock().unwrap();
+        assert_eq!(projects.get("other").map(String::as_str), Some("/work/b"));
+        assert!(!projects.contains_key("main"));
     }
 }
@@ -0,0 +1,81 @@
+use std::fs;
+use std::path::Path;
+
+use serde_json::json;
+
+use crate::commands::skills::resolve_skills_dir;
+use crate::frontmatter::parse_frontmatter;
+use crate::platform::paths::{self, resolve_agents_dir};
+use crate::schema::references::find_string_references;
+use crate::types::ConfigScope;
+
+/// Collects mentions of `name` in settings files and in agent/skill
+/// frontmatter across every scope, for reporting after a rename.
+///
+/// Returns `{ file, path, value }` objects; `path` is a field path within the
+/// settings object or `frontmatter.<key>` for markdown files.
+pub(crate) fn find_name_references(
+    name: &str,
+    project_root: &Option<String>,
+) -> Vec<serde_json::Value> {
+    let mut refs = Vec::new();
+
+    for scope in [ConfigScope::User, ConfigScope::Project, ConfigScope::Local] {
+        let Ok(path) = paths::resolve_settings_path(&scope, project_root) else {
+            continue;
+        };
+        let Some(settings) = fs::read_to_string(&path)
+            .ok()
+            .and_then(|s| serde_json::from_str::<serde_json::Value>(&s).ok())
+        else {
+            continue;
+        };
+        push_refs(&mut refs, &path, &settings, name, "");
+    }
+
+    for scope in ["user", "project"] {
+        let dirs = [
+            resolve_agents_dir(scope, project_root),
+            resolve_skills_dir(scope, project_root),
+        ];
+        for dir in dirs.into_iter().flatten() {
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let file = if path.is_dir() {
+                    path.join("SKILL.md")
+                } else {
+                    path
+                };
+                if file.extension().and_then(|e| e.to_str()) != Some("md") {
+                    continue;
+                }
+                let Ok(content) = fs::read_to_string(&file) else {
+                    continue;
+                };
+                let (frontmatter, _) = parse_frontmatter(&content);
+                push_refs(&mut refs, &file, &frontmatter, name, "frontmatter.");
+            }
+        }
+    }
+
+    refs
+}
+
+fn push_refs(
+    refs: &mut Vec<serde_json::Value>,
+    file: &Path,
+    value: &serde_json::Value,
+    name: &str,
+    prefix: &str,
+) {
+    for (path, value) in find_string_references(value, name) {
+        refs.push(json!({
+            "file": file.display().to_string(),
+            "path": format!("{}{}", prefix, path),
+            "value": value,
+        }));
+    }
+}
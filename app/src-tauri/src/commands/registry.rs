@@ -0,0 +1,340 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::Serialize;
+use serde_json::json;
+use tauri::State;
+
+use crate::args;
+use crate::audit;
+use crate::commands::agents::agent_filename;
+use crate::commands::skills::resolve_skills_dir;
+use crate::config::read_json_file;
+use crate::errors::IpcError;
+use crate::io::atomic::atomic_write;
+use crate::io::blocking;
+use crate::io::lock::lock_path;
+use crate::logging;
+use crate::platform::paths::{resolve_agents_dir, resolve_mcp_path};
+use crate::registry::{self, RegistryItem};
+use crate::AppState;
+
+/// Checksums of items the user has previewed, by item id. Installing
+/// requires a preview of the same payload first.
+static PREVIEWED: Mutex<Option<HashMap<String, String>>> = Mutex::new(None);
+
+/// An item's payload, shown before installing it.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegistryPreview {
+    pub item: RegistryItem,
+    /// The file that will be written (markdown, or the MCP server JSON).
+    pub content: String,
+    /// Where the item will be written for each scope it can be installed to.
+    pub targets: Vec<String>,
+}
+
+fn find_item(id: &str) -> Result<RegistryItem, IpcError> {
+    registry::fetch_index()?
+        .items
+        .into_iter()
+        .find(|i| i.id == id)
+        .ok_or_else(|| IpcError::NotFound {
+            path: format!("{}#{}", registry::index_url(), id),
+        })
+}
+
+/// Scopes a registry item can be installed to.
+const INSTALL_SCOPES: &[&str] = &["user", "project"];
+
+/// Where `item` is installed for `scope`: the agent file, the skill
+/// directory, or the MCP config the server is added to.
+fn install_target(
+    item: &RegistryItem,
+    scope: &str,
+    project_root: &Option<String>,
+) -> Result<PathBuf, IpcError> {
+    match item.kind.as_str() {
+        "agent" => Ok(resolve_agents_dir(scope, project_root)?.join(agent_filename(&item.name)?)),
+        "skill" => {
+            Ok(resolve_skills_dir(scope, project_root)?.join(args::file_name("name", &item.name)?))
+        }
+        "mcp" => {
+            if !INSTALL_SCOPES.contains(&scope) {
+                return Err(IpcError::InvalidArgument {
+                    argument: "scope".to_string(),
+                    message: format!("MCP source '{}' is not writable", scope),
+                });
+            }
+            resolve_mcp_path(scope, project_root)
+        }
+        other => Err(IpcError::Unsupported {
+            message: format!("Unsupported registry item kind '{}'", other),
+        }),
+    }
+}
+
+/// Downloads an item's payload and checks it against the index checksum.
+fn fetch_payload(item: &RegistryItem) -> Result<String, IpcError> {
+    let bytes = registry::fetch(&item.url, registry::MAX_ITEM_BYTES)?;
+    registry::verify(item, &bytes)?;
+    String::from_utf8(bytes).map_err(|_| IpcError::ParseError {
+        path: item.url.clone(),
+        message: "Registry item is not UTF-8 text".to_string(),
+    })
+}
+
+/// IPC command: searches the community registry for agents, skills, and MCP
+/// server configs.
+///
+/// The index is fetched from `SSENRAH_REGISTRY_URL` (or the default
+/// registry) on every call. Items whose name matches come first, then tag
+/// and description matches.
+#[tauri::command]
+pub async fn search_registry(query: String) -> Result<Vec<RegistryItem>, IpcError> {
+    logging::async_command("search_registry", async move {
+        blocking::run("search_registry", move || {
+            Ok(registry::search(&registry::fetch_index()?, &query))
+        })
+        .await
+    })
+    .await
+}
+
+/// IPC command: downloads a registry item and returns its content and
+/// license for review.
+///
+/// The payload must match the checksum in the index. Nothing is written;
+/// `install_registry_item` only accepts items previewed this way. Targets
+/// are listed for each scope available to the window; the project scope is
+/// left out when no project is open.
+#[tauri::command]
+pub async fn preview_registry_item(
+    id: String,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<RegistryPreview, IpcError> {
+    logging::async_command("preview_registry_item", async move {
+        let project_root = state.project_root(&window);
+        blocking::run("preview_registry_item", move || {
+            let item = find_item(&id)?;
+            let content = fetch_payload(&item)?;
+            let mut targets = Vec::new();
+            for scope in INSTALL_SCOPES {
+                let target = match install_target(&item, scope, &project_root) {
+                    Ok(target) => target,
+                    Err(IpcError::NoProject { .. }) => continue,
+                    Err(e) => return Err(e),
+                };
+                targets.push(match item.kind.as_str() {
+                    "skill" => target.join("SKILL.md").display().to_string(),
+                    "mcp" => format!("{} (mcpServers.{})", target.display(), item.name),
+                    _ => target.display().to_string(),
+                });
+            }
+
+            PREVIEWED
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .get_or_insert_with(HashMap::new)
+                .insert(item.id.clone(), audit::hash(content.as_bytes()));
+            Ok(RegistryPreview {
+                item,
+                content,
+                targets,
+            })
+        })
+        .await
+    })
+    .await
+}
+
+/// IPC command: installs a previewed registry item into `scope`.
+///
+/// The payload is downloaded again and must match both the index checksum
+/// and what was previewed. Agents and skills fail if one with the same name
+/// already exists; MCP servers are added to the `scope` MCP config ("user"
+/// or "project") and fail if a server with that name is configured.
+/// Returns `{ path }`.
+#[tauri::command]
+pub async fn install_registry_item(
+    id: String,
+    scope: String,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<serde_json::Value, IpcError> {
+    logging::async_command("install_registry_item", async move {
+        state.ensure_writable()?;
+        let project_root = state.project_root(&window);
+        blocking::run("install_registry_item", move || {
+            let previewed = PREVIEWED
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .as_ref()
+                .and_then(|p| p.get(&id).cloned());
+            let Some(previewed) = previewed else {
+                return Err(IpcError::InvalidArgument {
+                    argument: "id".to_string(),
+                    message: format!("Preview registry item '{}' before installing it", id),
+                });
+            };
+
+            let item = find_item(&id)?;
+            let content = fetch_payload(&item)?;
+            if audit::hash(content.as_bytes()) != previewed {
+                return Err(IpcError::Conflict {
+                    path: id.clone(),
+                    message: format!(
+                        "Registry item '{}' changed since it was previewed; preview it again",
+                        id
+                    ),
+                });
+            }
+
+            let target = install_target(&item, &scope, &project_root)?;
+            let path = match item.kind.as_str() {
+                "skill" => {
+                    let path = target.join("SKILL.md");
+                    write_new(&target, &path, &content)?;
+                    path
+                }
+                "mcp" => {
+                    install_mcp_server(&item, &content, &target)?;
+                    target
+                }
+                _ => {
+                    write_new(&target, &target, &content)?;
+                    target
+                }
+            };
+
+            Ok(json!({ "path": path.display().to_string() }))
+        })
+        .await
+    })
+    .await
+}
+
+/// Writes `content` to `path` unless `target` (the file or skill directory)
+/// already exists.
+fn write_new(target: &Path, path: &Path, content: &str) -> Result<(), IpcError> {
+    if target.exists() {
+        return Err(IpcError::Conflict {
+            path: target.display().to_string(),
+            message: "An item with that name already exists".to_string(),
+        });
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| IpcError::WriteFailed {
+            path: parent.display().to_string(),
+            message: format!("Failed to create parent directory: {}", e),
+        })?;
+    }
+    atomic_write(path, content.as_bytes())
+}
+
+/// Adds the server to `mcpServers` of the MCP config at `path`, keeping
+/// every other key. A config that is not a JSON object is left alone.
+fn install_mcp_server(item: &RegistryItem, content: &str, path: &Path) -> Result<(), IpcError> {
+    let server: serde_json::Value =
+        serde_json::from_str(content).map_err(|e| IpcError::ParseError {
+            path: item.url.clone(),
+            message: e.to_string(),
+        })?;
+
+    let path_str = path.display().to_string();
+    let _lock = lock_path(path);
+    let mut config = match read_json_file(path)? {
+        Some(serde_json::Value::Object(m)) => m,
+        None => serde_json::Map::new(),
+        Some(_) => {
+            return Err(IpcError::ParseError {
+                path: path_str,
+                message: "MCP config is not a JSON object".to_string(),
+            })
+        }
+    };
+    let servers = config
+        .entry("mcpServers")
+        .or_insert_with(|| json!({}))
+        .as_object_mut()
+        .ok_or_else(|| IpcError::ParseError {
+            path: path_str.clone(),
+            message: "mcpServers is not an object".to_string(),
+        })?;
+    if servers.contains_key(&item.name) {
+        return Err(IpcError::Conflict {
+            path: path_str,
+            message: format!("An MCP server named '{}' already exists", item.name),
+        });
+    }
+    servers.insert(item.name.clone(), server);
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| IpcError::WriteFailed {
+            path: path_str.clone(),
+            message: format!("Failed to create parent directory: {}", e),
+        })?;
+    }
+    let bytes = serde_json::to_vec_pretty(&config).map_err(|e| IpcError::WriteFailed {
+        path: path_str,
+        message: format!("Failed to serialize config: {}", e),
+    })?;
+    atomic_write(path, &bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(kind: &str, name: &str) -> RegistryItem {
+        serde_json::from_value(json!({
+            "id": format!("{}-{}", kind, name),
+            "kind": kind,
+            "name": name,
+            "url": "https://example.com/item",
+            "sha256": "",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn skill_targets_match_the_skills_dir() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let root = Some(dir.display().to_string());
+
+        let target = install_target(&item("skill", "review"), "project", &root).unwrap();
+        assert_eq!(
+            target,
+            resolve_skills_dir("project", &root).unwrap().join("review")
+        );
+        assert!(matches!(
+            install_target(&item("skill", "review"), "project", &None),
+            Err(IpcError::NoProject { .. })
+        ));
+        assert!(install_target(&item("mcp", "db"), "local", &root).is_err());
+    }
+
+    #[test]
+    fn refuses_an_mcp_config_that_is_not_an_object() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let path = dir.join(".mcp.json");
+        fs::write(&path, "[1, 2]").unwrap();
+
+        let server = r#"{ "command": "db-server" }"#;
+        let err = install_mcp_server(&item("mcp", "db"), server, &path).unwrap_err();
+        assert!(matches!(err, IpcError::ParseError { .. }));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "[1, 2]");
+
+        fs::write(&path, r#"{ "other": true }"#).unwrap();
+        install_mcp_server(&item("mcp", "db"), server, &path).unwrap();
+        let config: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(config["other"], true);
+        assert_eq!(config["mcpServers"]["db"]["command"], "db-server");
+    }
+}
@@ -0,0 +1,67 @@
+use serde::Serialize;
+use tauri::State;
+
+use crate::config::read_scope;
+use crate::errors::IpcError;
+use crate::schema::merge;
+use crate::schema::occurrences::lookup;
+use crate::schema::sandbox::{self, SandboxBackend, SandboxFinding};
+use crate::types::ConfigScope;
+use crate::AppState;
+
+/// Result of `analyze_sandbox_config`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SandboxAnalysis {
+    /// The merged `sandbox.enabled` is true.
+    pub enabled: bool,
+    /// The merged `sandbox` object, or `null` when no scope sets it.
+    pub sandbox: serde_json::Value,
+    pub backend: SandboxBackend,
+    pub findings: Vec<SandboxFinding>,
+}
+
+fn on_path(binary: &str) -> bool {
+    std::env::var_os("PATH")
+        .map(|paths| std::env::split_paths(&paths).any(|dir| dir.join(binary).is_file()))
+        .unwrap_or(false)
+}
+
+/// IPC command: reviews the effective sandbox policy.
+///
+/// Merges `sandbox` from all scopes and reports overly broad entries
+/// (shell-like `excludedCommands`, wildcard `allowedDomains`, privileged
+/// Unix sockets), entries that contradict `permissions.deny`, and which OS
+/// sandbox backend applies on this machine. Each finding names the scope
+/// that provides it. See `schema::sandbox::analyze` for the rules.
+#[tauri::command]
+pub fn analyze_sandbox_config(
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<SandboxAnalysis, IpcError> {
+    let project_root = state.project_root(&window);
+
+    let user = read_scope(&ConfigScope::User, &project_root)?;
+    let project = read_scope(&ConfigScope::Project, &project_root)?;
+    let local = read_scope(&ConfigScope::Local, &project_root)?;
+    let managed = read_scope(&ConfigScope::Managed, &project_root)?;
+
+    let effective = merge::compute_effective(
+        user.as_ref(),
+        project.as_ref(),
+        local.as_ref(),
+        managed.as_ref(),
+    );
+
+    let os = std::env::consts::OS;
+    let sandbox_value = lookup(&effective.settings, "sandbox")
+        .cloned()
+        .unwrap_or(serde_json::Value::Null);
+
+    Ok(SandboxAnalysis {
+        enabled: lookup(&sandbox_value, "enabled").and_then(|v| v.as_bool()) == Some(true),
+        backend: sandbox::backend_for(os, os == "linux" && on_path("bwrap")),
+        findings: sandbox::analyze(&effective),
+        sandbox: sandbox_value,
+    })
+}
@@ -0,0 +1,196 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::State;
+
+use crate::commands::team_template::{apply_template, ApplyTemplateResult};
+use crate::errors::IpcError;
+use crate::git::{check_local_gitignore, GitignoreCheck};
+use crate::io::blocking;
+use crate::io::journal::Transaction;
+use crate::logging;
+use crate::platform::paths;
+use crate::scaffold::{self, Item, ScaffoldReport, USER_MEMORY_SKELETON};
+use crate::AppState;
+
+/// Options for `initialize_claude_config`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializeConfigOptions {
+    /// Starter settings template, one of `scaffold::SETTINGS_TEMPLATES`.
+    /// Defaults to "minimal".
+    #[serde(default)]
+    pub template: Option<String>,
+    /// Also create a CLAUDE.md skeleton.
+    #[serde(default)]
+    pub claude_md: bool,
+}
+
+/// Options for `scaffold_project_claude`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScaffoldProjectOptions {
+    /// Starter settings template, one of `scaffold::SETTINGS_TEMPLATES`.
+    /// Defaults to "minimal".
+    #[serde(default)]
+    pub settings_template: Option<String>,
+    /// Team template to apply first: a directory or git URL, as for
+    /// `apply_project_template`.
+    #[serde(default)]
+    pub team_template: Option<String>,
+    /// Extra `{{name}}` variables for the team template.
+    #[serde(default)]
+    pub variables: Option<BTreeMap<String, String>>,
+    /// Add the local-file patterns to `.gitignore`. Defaults to true.
+    #[serde(default)]
+    pub gitignore: Option<bool>,
+}
+
+/// Result of `scaffold_project_claude`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectScaffoldReport {
+    /// Starter files and directories created or found in place.
+    #[serde(flatten)]
+    pub files: ScaffoldReport,
+    /// What the team template wrote and skipped, when one was given.
+    pub template: Option<ApplyTemplateResult>,
+    /// `.gitignore` patterns checked and added, unless turned off.
+    pub gitignore: Option<GitignoreCheck>,
+}
+
+/// IPC command: sets up the user config dir (`~/.claude/`, or wherever
+/// `CLAUDE_CONFIG_DIR` points) for first use.
+///
+/// Creates `settings.json` from the chosen starter template, empty `agents/`
+/// and `commands/` directories, and, if asked, a CLAUDE.md skeleton, all in
+/// one transaction. Anything already there is left alone and reported under
+/// `existing`.
+#[tauri::command]
+pub async fn initialize_claude_config(
+    options: Option<InitializeConfigOptions>,
+    state: State<'_, AppState>,
+) -> Result<ScaffoldReport, IpcError> {
+    logging::async_command("initialize_claude_config", async move {
+        state.ensure_writable()?;
+        let options = options.unwrap_or_default();
+        blocking::run("initialize_claude_config", move || {
+            let template = options.template.as_deref().unwrap_or("minimal");
+            let settings = scaffold::starter_settings(template)?;
+
+            let dir = paths::resolve_config_dir()?;
+            let dir_existed = dir.is_dir();
+            let mut items = vec![
+                Item::json(dir.join("settings.json"), &settings),
+                Item::Dir(dir.join("agents")),
+                Item::Dir(dir.join("commands")),
+            ];
+            if options.claude_md {
+                items.push(Item::File(
+                    dir.join("CLAUDE.md"),
+                    USER_MEMORY_SKELETON.as_bytes().to_vec(),
+                ));
+            }
+
+            let mut txn = Transaction::begin("initialize-config")?;
+            let mut report = scaffold::stage(&mut txn, &items)?;
+            txn.commit()?;
+
+            // Staging creates the directory itself, outside the transaction.
+            let display = dir.display().to_string();
+            if dir_existed {
+                report.existing.insert(0, display);
+            } else {
+                report.created.insert(0, display);
+            }
+            tracing::info!(
+                template,
+                created = report.created.len(),
+                "initialized config dir"
+            );
+            Ok(report)
+        })
+        .await
+    })
+    .await
+}
+
+/// IPC command: sets up the open project's `.claude/` directory.
+///
+/// Applies the team template first when one is given, so its files win,
+/// then creates whatever is still missing of `settings.json` (from the
+/// starter template), the project MCP file, `agents/`, and `commands/` in
+/// one transaction, and finally adds the local-file patterns
+/// (`settings.local.json`, ...) to `.gitignore`. Existing files are never
+/// replaced. Supersedes `ensure_claude_dir`, which only creates the
+/// directory.
+#[tauri::command]
+pub async fn scaffold_project_claude(
+    options: Option<ScaffoldProjectOptions>,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<ProjectScaffoldReport, IpcError> {
+    logging::async_command("scaffold_project_claude", async move {
+        state.ensure_writable()?;
+        let project_root = state.project_root(&window);
+        let options = options.unwrap_or_default();
+        blocking::run("scaffold_project_claude", move || {
+            let root = project_root.as_ref().ok_or_else(|| IpcError::NoProject {
+                message: "No project is open. Open a project before setting up .claude."
+                    .to_string(),
+            })?;
+            let root = Path::new(root);
+            let claude_dir = root.join(".claude");
+            let settings_template = options.settings_template.as_deref().unwrap_or("minimal");
+            let settings = scaffold::starter_settings(settings_template)?;
+            let dir_existed = claude_dir.is_dir();
+
+            let template = match &options.team_template {
+                Some(source) => Some(apply_template(
+                    source,
+                    root,
+                    &claude_dir,
+                    options.variables,
+                    Some(false),
+                )?),
+                None => None,
+            };
+
+            let items = vec![
+                Item::json(claude_dir.join("settings.json"), &settings),
+                Item::json(
+                    paths::resolve_mcp_path("project", &project_root)?,
+                    &json!({ "mcpServers": {} }),
+                ),
+                Item::Dir(claude_dir.join("agents")),
+                Item::Dir(claude_dir.join("commands")),
+            ];
+            let mut txn = Transaction::begin("scaffold-project")?;
+            let mut files = scaffold::stage(&mut txn, &items)?;
+            txn.commit()?;
+
+            let display = claude_dir.display().to_string();
+            if dir_existed {
+                files.existing.insert(0, display);
+            } else {
+                files.created.insert(0, display);
+            }
+
+            let gitignore = if options.gitignore.unwrap_or(true) {
+                Some(check_local_gitignore(root, true)?)
+            } else {
+                None
+            };
+
+            Ok(ProjectScaffoldReport {
+                files,
+                template,
+                gitignore,
+            })
+        })
+        .await
+    })
+    .await
+}
@@ -0,0 +1,256 @@
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+use tauri::State;
+
+use crate::commands::skills::resolve_skills_dir;
+use crate::config::read_json_file;
+use crate::errors::IpcError;
+use crate::io::blocking;
+use crate::io::copy::collect_files;
+use crate::logging;
+use crate::platform::paths::{self, resolve_agents_dir, resolve_mcp_path};
+use crate::query::{self, ArtifactMatch, FieldCondition};
+use crate::search::{json_hits, text_hits, Matcher, SearchHit};
+use crate::types::ConfigScope;
+use crate::{args, AppState};
+
+/// Hits returned when the caller does not set a limit.
+const DEFAULT_SEARCH_LIMIT: usize = 200;
+
+/// Options for `search_config`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchOptions {
+    #[serde(default)]
+    pub case_sensitive: bool,
+    /// Artifact kinds to search: "settings", "mcp", "agents", "skills",
+    /// "memory". All when omitted.
+    pub kinds: Option<Vec<String>>,
+    pub limit: Option<usize>,
+}
+
+struct Search<'a> {
+    matcher: Matcher,
+    hits: Vec<SearchHit>,
+    kinds: Option<&'a [String]>,
+}
+
+impl Search<'_> {
+    fn wants(&self, kind: &str) -> bool {
+        self.kinds.is_none_or(|k| k.iter().any(|k| k == kind))
+    }
+
+    fn json(&mut self, kind: &str, scope: &str, file: &Path, value: &serde_json::Value) {
+        for (path, snippet, score) in json_hits(&self.matcher, value) {
+            self.hits.push(SearchHit {
+                kind: kind.to_string(),
+                scope: scope.to_string(),
+                file: file.display().to_string(),
+                path: Some(path),
+                line: None,
+                snippet,
+                score,
+            });
+        }
+    }
+
+    fn text(&mut self, kind: &str, scope: &str, file: &Path) {
+        let Ok(content) = fs::read_to_string(file) else {
+            return;
+        };
+        for (line, snippet, score) in text_hits(&self.matcher, &content) {
+            self.hits.push(SearchHit {
+                kind: kind.to_string(),
+                scope: scope.to_string(),
+                file: file.display().to_string(),
+                path: None,
+                line: Some(line),
+                snippet,
+                score,
+            });
+        }
+    }
+}
+
+/// IPC command: full-text searches settings, MCP server definitions, agents,
+/// skills, and memory files across scopes.
+///
+/// Matches in names (frontmatter `name`/`description`, JSON keys such as MCP
+/// server names) and whole-word matches rank above plain substring matches.
+/// Hits are returned best first with their file, field path or line, and a
+/// snippet; `~/.claude.json` is searched only within `mcpServers`.
+#[tauri::command]
+pub async fn search_config(
+    query: String,
+    options: Option<SearchOptions>,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<Vec<SearchHit>, IpcError> {
+    logging::async_command("search_config", async move {
+        let project_root = state.project_root(&window);
+        blocking::run("search_config", move || {
+            let options = options.unwrap_or_default();
+            if query.trim().is_empty() {
+                return Ok(vec![]);
+            }
+
+            let mut search = Search {
+                matcher: Matcher::new(&query, options.case_sensitive),
+                hits: Vec::new(),
+                kinds: options.kinds.as_deref(),
+            };
+
+            if search.wants("settings") {
+                let scopes = [
+                    ("user", ConfigScope::User),
+                    ("project", ConfigScope::Project),
+                    ("local", ConfigScope::Local),
+                    ("managed", ConfigScope::Managed),
+                ];
+                for (name, scope) in scopes {
+                    let Ok(path) = paths::resolve_settings_path(&scope, &project_root) else {
+                        continue;
+                    };
+                    if let Ok(Some(value)) = read_json_file(&path) {
+                        search.json("settings", name, &path, &value);
+                    }
+                }
+            }
+
+            if search.wants("mcp") {
+                for scope in ["user", "project", "managed"] {
+                    let Ok(path) = resolve_mcp_path(scope, &project_root) else {
+                        continue;
+                    };
+                    let Ok(Some(value)) = read_json_file(&path) else {
+                        continue;
+                    };
+                    let servers = serde_json::json!({
+                        "mcpServers": value.get("mcpServers").cloned().unwrap_or_default()
+                    });
+                    search.json("mcp", scope, &path, &servers);
+                }
+            }
+
+            for scope in ["user", "project"] {
+                if search.wants("agents") {
+                    if let Ok(dir) = resolve_agents_dir(scope, &project_root) {
+                        let mut files = Vec::new();
+                        collect_files(&dir, &dir, &mut files);
+                        for rel in files
+                            .iter()
+                            .filter(|f| f.extension().is_some_and(|e| e == "md"))
+                        {
+                            search.text("agents", scope, &dir.join(rel));
+                        }
+                    }
+                }
+                if search.wants("skills") {
+                    if let Ok(dir) = resolve_skills_dir(scope, &project_root) {
+                        let mut files = Vec::new();
+                        collect_files(&dir, &dir, &mut files);
+                        for rel in files
+                            .iter()
+                            .filter(|f| f.extension().is_some_and(|e| e == "md"))
+                        {
+                            search.text("skills", scope, &dir.join(rel));
+                        }
+                    }
+                }
+            }
+
+            if search.wants("memory") {
+                let memory_files = [
+                    (
+                        "user",
+                        paths::resolve_config_dir()
+                            .ok()
+                            .map(|dir| dir.join("CLAUDE.md")),
+                    ),
+                    (
+                        "project_root",
+                        project_root
+                            .as_ref()
+                            .map(|r| Path::new(r).join("CLAUDE.md")),
+                    ),
+                    (
+                        "project",
+                        project_root
+                            .as_ref()
+                            .map(|r| Path::new(r).join(".claude").join("CLAUDE.md")),
+                    ),
+                    (
+                        "local",
+                        project_root
+                            .as_ref()
+                            .map(|r| Path::new(r).join(".claude").join("CLAUDE.local.md")),
+                    ),
+                ];
+                for (scope, path) in memory_files {
+                    if let Some(path) = path {
+                        search.text("memory", scope, &path);
+                    }
+                }
+            }
+
+            let mut hits = search.hits;
+            hits.sort_by(|a, b| {
+                b.score
+                    .cmp(&a.score)
+                    .then_with(|| a.file.cmp(&b.file))
+                    .then_with(|| a.line.cmp(&b.line))
+            });
+            hits.truncate(options.limit.unwrap_or(DEFAULT_SEARCH_LIMIT));
+
+            Ok(hits)
+        })
+        .await
+    })
+    .await
+}
+
+/// IPC command: lists the agents or skills whose frontmatter meets every
+/// condition of `filter`, e.g. agents with `model` equal to `haiku` or
+/// skills with `description` missing.
+///
+/// `kind` is "agent" or "skill". Both scopes are searched unless `scope`
+/// is given. Results are ordered by scope, then name.
+#[tauri::command]
+pub async fn query_artifacts(
+    kind: String,
+    filter: Vec<FieldCondition>,
+    scope: Option<String>,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<Vec<ArtifactMatch>, IpcError> {
+    logging::async_command("query_artifacts", async move {
+        let project_root = state.project_root(&window);
+        blocking::run("query_artifacts", move || {
+            let kind = args::one_of("kind", &kind, &["agent", "skill"])?;
+            query::validate(&filter)?;
+            let scopes: Vec<&str> = match scope.as_deref() {
+                Some(s) => vec![args::one_of("scope", s, &["user", "project"])?],
+                None => vec!["user", "project"],
+            };
+
+            let mut found = Vec::new();
+            for scope in scopes {
+                let dir = if kind == "agent" {
+                    resolve_agents_dir(scope, &project_root)
+                } else {
+                    resolve_skills_dir(scope, &project_root)
+                };
+                // A project scope without an open project has nothing to match.
+                if let Ok(dir) = dir {
+                    query::query_dir(kind, scope, &dir, &filter, &mut found);
+                }
+            }
+            found.sort_by(|a, b| (&a.scope, &a.name).cmp(&(&b.scope, &b.name)));
+            Ok(found)
+        })
+        .await
+    })
+    .await
+}
@@ -0,0 +1,247 @@
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use tauri::State;
+
+use crate::audit;
+use crate::config;
+use crate::errors::IpcError;
+use crate::io::blocking;
+use crate::logging;
+use crate::platform::paths;
+use crate::rule_suggestions::{self, RuleSuggestion};
+use crate::sessions::{self, SessionEntry, SessionMeta};
+use crate::AppState;
+
+/// A transcript listed by `list_sessions`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionSummary {
+    pub id: String,
+    pub path: String,
+    pub size_bytes: u64,
+    #[serde(flatten)]
+    pub meta: SessionMeta,
+}
+
+/// One page of a transcript returned by `read_session`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionPage {
+    pub id: String,
+    pub page: usize,
+    pub page_size: usize,
+    pub entries: Vec<SessionEntry>,
+    pub has_more: bool,
+}
+
+/// Directory holding per-project transcript folders: `{configDir}/projects/`.
+fn projects_dir() -> Result<PathBuf, IpcError> {
+    Ok(paths::resolve_config_dir()?.join("projects"))
+}
+
+fn open(path: &Path) -> Result<BufReader<File>, IpcError> {
+    File::open(path)
+        .map(BufReader::new)
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => IpcError::NotFound {
+                path: path.display().to_string(),
+            },
+            std::io::ErrorKind::PermissionDenied => IpcError::PermissionDenied {
+                path: path.display().to_string(),
+            },
+            _ => IpcError::PlatformError {
+                message: format!("Failed to read {}: {}", path.display(), e),
+            },
+        })
+}
+
+/// Finds the transcript file for a session id in any project folder.
+fn find_session(id: &str) -> Result<PathBuf, IpcError> {
+    let projects = projects_dir()?;
+    let not_found = || IpcError::NotFound {
+        path: projects
+            .join(format!("*/{}.jsonl", id))
+            .display()
+            .to_string(),
+    };
+    if !sessions::is_valid_session_id(id) {
+        return Err(not_found());
+    }
+
+    fs::read_dir(&projects)
+        .ok()
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .map(|e| e.path().join(format!("{}.jsonl", id)))
+        .find(|p| p.is_file())
+        .ok_or_else(not_found)
+}
+
+/// IPC command: lists the Claude Code session transcripts recorded for a
+/// project, newest first.
+///
+/// `project` is a project root path; when omitted, the open project is used.
+/// Transcripts live in `{configDir}/projects/{encodedRoot}/{sessionId}.jsonl`.
+/// Each file is streamed once to collect its title, message count, and
+/// timestamps.
+#[tauri::command]
+pub async fn list_sessions(
+    project: Option<String>,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<Vec<SessionSummary>, IpcError> {
+    logging::async_command("list_sessions", async move {
+        let root = match project {
+            Some(p) => p,
+            None => state
+                .project_root(&window)
+                .ok_or_else(|| IpcError::NoProject {
+                    message: "No project is open. Open a project before listing sessions."
+                        .to_string(),
+                })?,
+        };
+
+        blocking::run("list_sessions", move || {
+            let dir = projects_dir()?.join(sessions::project_dir_name(&root));
+            let entries = match fs::read_dir(&dir) {
+                Ok(entries) => entries,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+                Err(e) => {
+                    return Err(IpcError::PlatformError {
+                        message: format!("Failed to read {}: {}", dir.display(), e),
+                    })
+                }
+            };
+
+            let mut summaries = Vec::new();
+            for entry in entries.filter_map(|e| e.ok()) {
+                let path = entry.path();
+                if path.extension().and_then(|e| e.to_str()) != Some("jsonl") {
+                    continue;
+                }
+                let Some(id) = path
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .map(str::to_string)
+                else {
+                    continue;
+                };
+                // A transcript being rotated or locked away shouldn't hide the rest.
+                let Ok(reader) = open(&path) else {
+                    continue;
+                };
+                let size_bytes = entry.metadata().map(|m| m.len()).unwrap_or(0);
+                let meta = sessions::summarize(reader);
+
+                summaries.push(SessionSummary {
+                    id,
+                    path: path.display().to_string(),
+                    size_bytes,
+                    meta,
+                });
+            }
+
+            summaries.sort_by(|a, b| b.meta.last_timestamp.cmp(&a.meta.last_timestamp));
+            Ok(summaries)
+        })
+        .await
+    })
+    .await
+}
+
+/// IPC command: reads one page of a session transcript.
+///
+/// Pages are zero-based and hold `page_size` lines (default 100). Only the
+/// requested lines are parsed, so large transcripts can be browsed
+/// incrementally.
+#[tauri::command]
+pub fn read_session(
+    id: String,
+    page: Option<usize>,
+    page_size: Option<usize>,
+) -> Result<SessionPage, IpcError> {
+    let path = find_session(&id)?;
+    let page = page.unwrap_or(0);
+    let page_size = page_size
+        .filter(|&n| n > 0)
+        .unwrap_or(sessions::DEFAULT_PAGE_SIZE);
+
+    let (entries, has_more) = sessions::read_page(open(&path)?, page, page_size);
+
+    Ok(SessionPage {
+        id,
+        page,
+        page_size,
+        entries,
+        has_more,
+    })
+}
+
+/// IPC command: deletes a session transcript, along with the sibling
+/// `{sessionId}/` directory Claude Code uses for subagent transcripts and
+/// tool output, if present.
+#[tauri::command]
+pub fn delete_session(id: String, state: State<AppState>) -> Result<(), IpcError> {
+    state.ensure_writable()?;
+    let path = find_session(&id)?;
+
+    let hash_before = audit::file_hash(&path);
+    fs::remove_file(&path).map_err(|e| IpcError::WriteFailed {
+        path: path.display().to_string(),
+        message: format!("Failed to delete session: {}", e),
+    })?;
+    audit::record_delete(&path, hash_before);
+
+    let extra = path.with_extension("");
+    if extra.is_dir() {
+        fs::remove_dir_all(&extra).map_err(|e| IpcError::WriteFailed {
+            path: extra.display().to_string(),
+            message: format!("Failed to delete session data: {}", e),
+        })?;
+        audit::record_delete(&extra, None);
+    }
+
+    Ok(())
+}
+
+/// IPC command: proposes permission rules from the tool calls made in the
+/// given sessions.
+///
+/// Calls the user approved at least `rule_suggestions::MIN_APPROVALS` times
+/// and never denied are proposed for `allow`, calls that were only ever
+/// denied for `deny`, and calls answered both ways for `ask`. Rules already
+/// present in the effective `permissions` lists are left out. Bash calls are
+/// generalized to a command prefix (`Bash(npm test:*)`) and WebFetch calls to
+/// their domain.
+#[tauri::command]
+pub async fn suggest_permission_rules(
+    session_ids: Vec<String>,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<Vec<RuleSuggestion>, IpcError> {
+    logging::async_command("suggest_permission_rules", async move {
+        let project_root = state.project_root(&window);
+        blocking::run("suggest_permission_rules", move || {
+            let calls = session_ids
+                .iter()
+                .map(|id| Ok(sessions::tool_calls(open(&find_session(id)?)?)))
+                .collect::<Result<Vec<_>, IpcError>>()?;
+
+            let effective = config::compute_effective(&project_root)?;
+            let existing: Vec<String> = ["allow", "ask", "deny"]
+                .iter()
+                .filter_map(|list| effective.settings["permissions"][list].as_array())
+                .flatten()
+                .filter_map(|rule| rule.as_str().map(str::to_string))
+                .collect();
+
+            Ok(rule_suggestions::suggest(&calls, &existing))
+        })
+        .await
+    })
+    .await
+}
@@ -1,12 +1,22 @@
 use std::fs;
-use std::path::Path;
 
+use serde::Serialize;
 use tauri::State;
 
+use crate::config::{self, read_scope};
 use crate::errors::IpcError;
 use crate::io::atomic::atomic_write;
+use crate::io::backup;
+use crate::io::blocking;
+use crate::io::lock::lock_path;
+use crate::io::text::TextFormat;
+use crate::logging;
 use crate::platform::paths;
-use crate::types::{ConfigScope, WritableScope};
+use crate::schema::migrations::{self, MigrationChange};
+use crate::schema::model;
+use crate::schema::output_style;
+use crate::schema::three_way::{self, ThreeWayMerge};
+use crate::types::{ConfigScope, ScopeAddress, WritableScope};
 use crate::AppState;
 
 /// IPC command: reads a settings file for the given scope.
@@ -15,88 +25,265 @@ use crate::AppState;
 /// - `Ok(Some(value))` if the file exists and parses successfully
 /// - `Ok(None)` if the file does not exist
 /// - `Err(IpcError::ParseError)` if the file exists but is not valid JSON
+///
+/// A known field with the wrong type does not fail the read; `validate_settings`
+/// reports it and `write_settings` rejects it.
 /// - `Err(IpcError::PermissionDenied)` if the file cannot be read
+///
+/// `scope` may address a monorepo package, e.g. `"project:packages/api"`.
 #[tauri::command]
-pub fn read_settings(
-    scope: ConfigScope,
-    state: State<AppState>,
+pub async fn read_settings(
+    scope: ScopeAddress<ConfigScope>,
+    window: tauri::Window,
+    state: State<'_, AppState>,
 ) -> Result<Option<serde_json::Value>, IpcError> {
-    let project_root = state
-        .project_root
-        .lock()
-        .map_err(|e| IpcError::PlatformError {
-            message: format!("Failed to acquire state lock: {}", e),
-        })?;
+    logging::async_command("read_settings", async move {
+        let project_root = scope.project_root(&state.project_root(&window))?;
+        blocking::run("read_settings", move || {
+            config::read_settings(&scope.scope, &project_root)
+        })
+        .await
+    })
+    .await
+}
+
+/// IPC command: reports the line endings and BOM of a scope's settings file,
+/// or `None` if it does not exist. `write_settings` keeps them by default.
+#[tauri::command]
+pub fn read_settings_format(
+    scope: ScopeAddress<ConfigScope>,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<Option<TextFormat>, IpcError> {
+    let project_root = scope.project_root(&state.project_root(&window))?;
+    config::settings_format(&scope.scope, &project_root)
+}
 
-    let path = paths::resolve_settings_path(&scope, &project_root)?;
-    let path_str = path.to_string_lossy().to_string();
-
-    match std::fs::read_to_string(&path) {
-        Ok(contents) => {
-            let value: serde_json::Value =
-                serde_json::from_str(&contents).map_err(|e| IpcError::ParseError {
-                    path: path_str.clone(),
-                    message: e.to_string(),
-                })?;
-            Ok(Some(value))
+/// IPC command: writes a settings JSON object for the given writable scope.
+///
+/// Type-checks the value against the typed settings model, resolves the target
+/// path, ensures the parent directory exists, serializes the value to pretty
+/// JSON, and performs an atomic write (temp file + rename). The original value
+/// is written as-is so key order and unknown keys are preserved. The file's
+/// line endings and BOM are kept unless `format` is given. `scope` may
+/// address a monorepo package, e.g. `"project:packages/api"`.
+#[tauri::command]
+pub async fn write_settings(
+    scope: ScopeAddress<WritableScope>,
+    settings: serde_json::Value,
+    format: Option<TextFormat>,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<(), IpcError> {
+    logging::async_command("write_settings", async move {
+        state.ensure_writable()?;
+        let project_root = scope.project_root(&state.project_root(&window))?;
+        blocking::run("write_settings", move || {
+            config::write_settings(&scope.scope, &settings, format, &project_root)
+        })
+        .await
+    })
+    .await
+}
+
+/// IPC command: three-way merges two conflicting edits of a settings object.
+///
+/// `base` is the last version both sides agreed on, `ours` the unsaved GUI
+/// state, and `theirs` the version written externally. Returns the merged
+/// object plus every path that could not be reconciled automatically, so the
+/// frontend can ask the user to resolve only those fields.
+#[tauri::command]
+pub fn merge_settings_conflict(
+    base: serde_json::Value,
+    ours: serde_json::Value,
+    theirs: serde_json::Value,
+) -> Result<ThreeWayMerge, IpcError> {
+    for (name, value) in [("base", &base), ("ours", &ours), ("theirs", &theirs)] {
+        if !value.is_object() && !value.is_null() {
+            return Err(IpcError::InvalidArgument {
+                argument: name.to_string(),
+                message: format!("Settings '{}' must be a JSON object", name),
+            });
         }
-        Err(e) => match e.kind() {
-            std::io::ErrorKind::NotFound => Ok(None),
-            std::io::ErrorKind::PermissionDenied => Err(IpcError::PermissionDenied {
+    }
+
+    Ok(three_way::merge_three_way(&base, &ours, &theirs))
+}
+
+/// Result of `migrate_settings`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationReport {
+    pub path: String,
+    pub changes: Vec<MigrationChange>,
+    /// The migrated settings (also returned on a dry run).
+    pub settings: Option<serde_json::Value>,
+    /// `true` if the file was rewritten.
+    pub written: bool,
+    pub backup_path: Option<String>,
+}
+
+/// IPC command: rewrites deprecated keys in a writable scope's settings file
+/// to their current equivalents (see `schema::migrations::MIGRATIONS`).
+///
+/// With `dry_run` the file is left untouched and the report shows what would
+/// change. Otherwise the original file is backed up and the migrated
+/// settings are written atomically. Changes that conflict with an existing
+/// value are reported with `applied: false` and leave the old key in place.
+#[tauri::command]
+pub fn migrate_settings(
+    scope: WritableScope,
+    dry_run: Option<bool>,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<MigrationReport, IpcError> {
+    if !dry_run.unwrap_or(false) {
+        state.ensure_writable()?;
+    }
+    let project_root = state.project_root(&window);
+
+    let config_scope = match scope {
+        WritableScope::User => ConfigScope::User,
+        WritableScope::Project => ConfigScope::Project,
+        WritableScope::Local => ConfigScope::Local,
+    };
+    let path = paths::resolve_settings_path(&config_scope, &project_root)?;
+    let path_str = path.display().to_string();
+    let _lock = lock_path(&path);
+
+    let contents = match fs::read_to_string(&path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(MigrationReport {
                 path: path_str,
-            }),
-            _ => Err(IpcError::PlatformError {
+                changes: vec![],
+                settings: None,
+                written: false,
+                backup_path: None,
+            })
+        }
+        Err(e) => {
+            return Err(IpcError::PlatformError {
                 message: format!("Failed to read {}: {}", path_str, e),
-            }),
-        },
+            })
+        }
+    };
+    let mut settings: serde_json::Value =
+        serde_json::from_str(&contents).map_err(|e| IpcError::ParseError {
+            path: path_str.clone(),
+            message: e.to_string(),
+        })?;
+
+    let changes = migrations::migrate(&mut settings);
+    let mut written = false;
+    let mut backup_path = None;
+
+    if !dry_run.unwrap_or(false) && changes.iter().any(|c| c.applied) {
+        backup_path = backup::backup_file("migrate", &path)?.map(|p| p.display().to_string());
+        let content = serde_json::to_vec_pretty(&settings).map_err(|e| IpcError::WriteFailed {
+            path: path_str.clone(),
+            message: format!("Failed to serialize settings: {}", e),
+        })?;
+        atomic_write(&path, &content)?;
+        written = true;
     }
+
+    Ok(MigrationReport {
+        path: path_str,
+        changes,
+        settings: Some(settings),
+        written,
+        backup_path,
+    })
 }
 
-/// IPC command: writes a settings JSON object for the given writable scope.
+/// Settings keys managed by the spinner commands.
+const SPINNER_KEYS: &[&str] = &["spinnerTipsEnabled", "spinnerVerbs", "spinnerTipsOverride"];
+
+/// IPC command: reads the spinner settings (`spinnerTipsEnabled`,
+/// `spinnerVerbs`, `spinnerTipsOverride`) of one scope. Keys the scope does
+/// not set are omitted.
+#[tauri::command]
+pub fn read_spinner_settings(
+    scope: ConfigScope,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<serde_json::Value, IpcError> {
+    let project_root = state.project_root(&window);
+
+    let settings = read_scope(&scope, &project_root)?.unwrap_or_default();
+    let spinner: serde_json::Map<String, serde_json::Value> = SPINNER_KEYS
+        .iter()
+        .filter_map(|key| Some((key.to_string(), settings.get(*key)?.clone())))
+        .collect();
+    Ok(serde_json::Value::Object(spinner))
+}
+
+/// IPC command: updates the spinner settings of a writable scope.
 ///
-/// Resolves the target path, ensures the parent directory exists, serializes
-/// the value to pretty JSON, and performs an atomic write (temp file + rename).
+/// Only the spinner keys present in `spinner` change; a `null` value
+/// removes the key. Other settings are left untouched. Fails with
+/// `ValidationError` if the values are malformed (unknown `spinnerVerbs`
+/// mode, empty verbs or tips).
 #[tauri::command]
-pub fn write_settings(
+pub fn write_spinner_settings(
     scope: WritableScope,
-    settings: serde_json::Value,
+    spinner: serde_json::Value,
+    window: tauri::Window,
     state: State<AppState>,
 ) -> Result<(), IpcError> {
-    let project_root = state
-        .project_root
-        .lock()
-        .map_err(|e| IpcError::PlatformError {
-            message: format!("Failed to acquire state lock: {}", e),
-        })?;
+    state.ensure_writable()?;
+    let project_root = state.project_root(&window);
+
+    let report = output_style::validate_spinner(&spinner);
+    if !report.errors.is_empty() {
+        return Err(IpcError::ValidationError {
+            errors: report.errors,
+        });
+    }
 
-    // Map WritableScope to ConfigScope for path resolution
     let config_scope = match scope {
         WritableScope::User => ConfigScope::User,
         WritableScope::Project => ConfigScope::Project,
         WritableScope::Local => ConfigScope::Local,
     };
-
     let path = paths::resolve_settings_path(&config_scope, &project_root)?;
     let path_str = path.display().to_string();
+    let _lock = lock_path(&path);
 
-    // Ensure parent directory exists
-    if let Some(parent) = path.parent() {
-        if !parent.exists() {
-            fs::create_dir_all(parent).map_err(|e| IpcError::WriteFailed {
-                path: path_str.clone(),
-                message: format!("Failed to create parent directory: {}", e),
-            })?;
+    let mut settings = match read_scope(&config_scope, &project_root)? {
+        Some(serde_json::Value::Object(m)) => m,
+        Some(_) => {
+            return Err(IpcError::ParseError {
+                path: path_str,
+                message: "Settings file is not a JSON object".to_string(),
+            })
+        }
+        None => serde_json::Map::new(),
+    };
+    for key in SPINNER_KEYS {
+        match spinner.get(*key) {
+            None => {}
+            Some(serde_json::Value::Null) => {
+                settings.remove(*key);
+            }
+            Some(value) => {
+                settings.insert(key.to_string(), value.clone());
+            }
         }
     }
+    let settings = serde_json::Value::Object(settings);
+    model::parse_settings(&settings).map_err(|e| IpcError::ValidationError { errors: vec![e] })?;
 
-    // Serialize to pretty JSON
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| IpcError::WriteFailed {
+            path: path_str.clone(),
+            message: format!("Failed to create parent directory: {}", e),
+        })?;
+    }
     let content = serde_json::to_vec_pretty(&settings).map_err(|e| IpcError::WriteFailed {
-        path: path_str.clone(),
+        path: path_str,
         message: format!("Failed to serialize settings: {}", e),
     })?;
-
-    // Atomic write
-    atomic_write(Path::new(&path), &content)?;
-
-    Ok(())
+    atomic_write(&path, &content)
 }
@@ -0,0 +1,593 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use tauri::State;
+
+use crate::args;
+use crate::commands::skills::resolve_skills_dir;
+use crate::config::{self, read_scope};
+use crate::errors::IpcError;
+use crate::frontmatter::parse_frontmatter;
+use crate::io::blocking;
+use crate::io::text;
+use crate::io::walk::relative_slash_path;
+use crate::logging;
+use crate::platform::paths::{self, resolve_agents_dir};
+use crate::registry;
+use crate::share::{self, ArtifactSnippet, SharedArtifact, ARTIFACT_KINDS, PERMISSION_LISTS};
+use crate::similarity::{self, Artifact, DuplicateMatch};
+use crate::types::{ConfigScope, ValidationError, ValidationWarning, WritableScope};
+use crate::AppState;
+
+/// Result of `import_artifact`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactImport {
+    pub kind: String,
+    pub name: String,
+    /// The file written to.
+    pub path: String,
+    /// An artifact of that name already exists there.
+    pub exists: bool,
+    /// What is written: the markdown file, the server configuration, or the
+    /// permission rules not yet present.
+    pub content: Value,
+    pub errors: Vec<ValidationError>,
+    pub warnings: Vec<ValidationWarning>,
+    /// False for a dry run.
+    pub written: bool,
+    /// Agents or skills in either scope that look like this one.
+    pub duplicates: Vec<DuplicateMatch>,
+}
+
+/// Reads a markdown artifact without its BOM.
+fn read_document(path: &Path) -> Result<String, IpcError> {
+    let path_str = path.display().to_string();
+    text::read_text(path)
+        .map(|(content, _)| content)
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => IpcError::NotFound { path: path_str },
+            std::io::ErrorKind::PermissionDenied => IpcError::PermissionDenied { path: path_str },
+            _ => IpcError::PlatformError {
+                message: format!("Failed to read {}: {}", path_str, e),
+            },
+        })
+}
+
+/// Files under a skill directory other than SKILL.md, relative to it.
+fn extra_files(skill_dir: &Path, dir: &Path, found: &mut Vec<String>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            extra_files(skill_dir, &path, found);
+        } else if path != skill_dir.join("SKILL.md") {
+            found.push(relative_slash_path(skill_dir, &path));
+        }
+    }
+}
+
+/// Where a file in `dir` (`agents`, `commands`) of `scope` is installed.
+fn install_path(scope: &str, dir: &str, file: &str) -> String {
+    let base = if scope == "user" {
+        "~/.claude"
+    } else {
+        ".claude"
+    };
+    format!("{}/{}/{}", base, dir, file)
+}
+
+fn required_name(name: Option<String>) -> Result<String, IpcError> {
+    let name = name.unwrap_or_default();
+    args::file_name("name", &name)?;
+    Ok(name)
+}
+
+/// Reads an agent or skill file into an `Artifact`; `None` when it is
+/// missing or unreadable.
+fn load_artifact(scope: &str, name: String, path: PathBuf) -> Option<Artifact> {
+    let document = read_document(&path).ok()?;
+    let (frontmatter, body) = parse_frontmatter(&document);
+    Some(Artifact {
+        scope: scope.to_string(),
+        name: frontmatter["name"]
+            .as_str()
+            .map(str::to_string)
+            .unwrap_or(name),
+        path: path.display().to_string(),
+        description: frontmatter["description"].as_str().map(str::to_string),
+        body,
+    })
+}
+
+/// The agents or skills of the user scope and, with a project open, the
+/// project scope.
+fn existing_artifacts(kind: &str, project_root: &Option<String>) -> Vec<Artifact> {
+    let scopes: &[&str] = if project_root.is_some() {
+        &["user", "project"]
+    } else {
+        &["user"]
+    };
+    let mut artifacts = Vec::new();
+    for scope in scopes {
+        let dir = if kind == "agent" {
+            resolve_agents_dir(scope, project_root)
+        } else {
+            resolve_skills_dir(scope, project_root)
+        };
+        let Ok(entries) = dir.and_then(|dir| {
+            fs::read_dir(&dir).map_err(|e| IpcError::PlatformError {
+                message: format!("Failed to read {}: {}", dir.display(), e),
+            })
+        }) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            let found = if path.is_dir() && kind == "skill" {
+                load_artifact(scope, name, path.join("SKILL.md"))
+            } else if name.ends_with(".md") {
+                load_artifact(scope, name.trim_end_matches(".md").to_string(), path)
+            } else {
+                None
+            };
+            artifacts.extend(found);
+        }
+    }
+    artifacts
+}
+
+/// Existing agents or skills resembling `document`, other than the file
+/// at `exclude`.
+fn duplicates_of(
+    kind: &str,
+    name: &str,
+    document: &str,
+    exclude: Option<&Path>,
+    project_root: &Option<String>,
+) -> Vec<DuplicateMatch> {
+    let (frontmatter, body) = parse_frontmatter(document);
+    let candidate = Artifact {
+        scope: String::new(),
+        name: name.to_string(),
+        path: String::new(),
+        description: frontmatter["description"].as_str().map(str::to_string),
+        body,
+    };
+    let exclude = exclude.map(|p| p.display().to_string());
+    let existing: Vec<Artifact> = existing_artifacts(kind, project_root)
+        .into_iter()
+        .filter(|a| Some(&a.path) != exclude.as_ref())
+        .collect();
+    similarity::find_duplicates(&candidate, &existing)
+}
+
+fn export(
+    kind: &str,
+    scope: &str,
+    name: Option<String>,
+    format: &str,
+    project_root: &Option<String>,
+) -> Result<ArtifactSnippet, IpcError> {
+    match args::one_of("kind", kind, ARTIFACT_KINDS)? {
+        "agent" => {
+            let name = required_name(name)?;
+            let file = if name.ends_with(".md") {
+                name
+            } else {
+                format!("{}.md", name)
+            };
+            let document = read_document(&resolve_agents_dir(scope, project_root)?.join(&file))?;
+            let (frontmatter, _) = parse_frontmatter(&document);
+            share::render_document(
+                kind,
+                file.trim_end_matches(".md"),
+                &install_path(scope, "agents", &file),
+                &document,
+                frontmatter["description"].as_str(),
+                format,
+            )
+        }
+        "skill" => {
+            let name = required_name(name)?;
+            let base = resolve_skills_dir(scope, project_root)?;
+            let (path, install, mut omitted) = if name.ends_with(".md") {
+                (base.join(&name), name.clone(), Vec::new())
+            } else {
+                let mut omitted = Vec::new();
+                extra_files(&base.join(&name), &base.join(&name), &mut omitted);
+                omitted.sort();
+                let path = base.join(&name).join("SKILL.md");
+                (path, format!("{}/SKILL.md", name), omitted)
+            };
+            let document = read_document(&path)?;
+            let (frontmatter, _) = parse_frontmatter(&document);
+            let mut snippet = share::render_document(
+                kind,
+                name.trim_end_matches(".md"),
+                &install_path(scope, "commands", &install),
+                &document,
+                frontmatter["description"].as_str(),
+                format,
+            )?;
+            snippet.omitted.append(&mut omitted);
+            Ok(snippet)
+        }
+        "permissions" => {
+            let config_scope =
+                match args::one_of("scope", scope, &["user", "project", "local", "managed"])? {
+                    "user" => ConfigScope::User,
+                    "project" => ConfigScope::Project,
+                    "local" => ConfigScope::Local,
+                    _ => ConfigScope::Managed,
+                };
+            let lists: Vec<&str> = match &name {
+                Some(list) => vec![args::one_of("name", list, PERMISSION_LISTS)?],
+                None => PERMISSION_LISTS.to_vec(),
+            };
+            let settings = read_scope(&config_scope, project_root)?.unwrap_or_default();
+            let mut permissions = Map::new();
+            for list in lists {
+                if let Some(rules) = settings["permissions"][list].as_array() {
+                    if !rules.is_empty() {
+                        permissions.insert(list.to_string(), Value::Array(rules.clone()));
+                    }
+                }
+            }
+            if permissions.is_empty() {
+                return Err(IpcError::InvalidArgument {
+                    argument: "scope".to_string(),
+                    message: format!("The {} settings have no permission rules to share", scope),
+                });
+            }
+            let target = if scope == "user" {
+                "~/.claude/settings.json"
+            } else {
+                ".claude/settings.json"
+            };
+            share::render_config(
+                kind,
+                &format!("{} settings", scope),
+                &json!({ "permissions": permissions }),
+                target,
+                format,
+            )
+        }
+        _ => {
+            let name = name.unwrap_or_default();
+            args::non_empty("name", &name)?;
+            let config = config::read_mcp_config(scope, project_root)?.unwrap_or_default();
+            let mut server =
+                config["mcpServers"]
+                    .get(&name)
+                    .cloned()
+                    .ok_or_else(|| IpcError::NotFound {
+                        path: format!("mcpServers.{}", name),
+                    })?;
+            let redacted = share::redact_server(&mut server, &format!("mcpServers.{}", name));
+            let target = if scope == "user" {
+                "~/.claude.json"
+            } else {
+                ".claude/.mcp.json"
+            };
+            let mut snippet = share::render_config(
+                kind,
+                &name,
+                &json!({ "mcpServers": { name.clone(): server } }),
+                target,
+                format,
+            )?;
+            snippet.redacted = redacted;
+            Ok(snippet)
+        }
+    }
+}
+
+/// IPC command: renders one artifact as a snippet to paste into a chat,
+/// issue, or gist.
+///
+/// `kind` is one of `share::ARTIFACT_KINDS`:
+/// - `agent`: `name` is the agent file, with or without `.md`
+/// - `skill`: `name` is the skill directory or single `.md` file; other
+///   files of a skill directory are listed under `omitted`
+/// - `permissions`: the `allow`/`ask`/`deny` rules of a settings scope, or
+///   only the list `name` names
+/// - `mcp_server`: `name` is a server of the MCP source `scope`; secrets
+///   are replaced with `[redacted]` by the support bundle rules
+///
+/// `format` is "markdown" (the default: a fenced block with where to save
+/// it) or "json".
+#[tauri::command]
+pub async fn export_artifact(
+    kind: String,
+    scope: String,
+    name: Option<String>,
+    format: Option<String>,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<ArtifactSnippet, IpcError> {
+    logging::async_command("export_artifact", async move {
+        let project_root = state.project_root(&window);
+        blocking::run("export_artifact", move || {
+            let format = format.as_deref().unwrap_or("markdown");
+            export(&kind, &scope, name, format, &project_root)
+        })
+        .await
+    })
+    .await
+}
+
+/// The pasted text, or the file at `content_or_url` when it is an https://
+/// URL.
+fn import_source(content_or_url: &str) -> Result<String, IpcError> {
+    let trimmed = content_or_url.trim();
+    if !trimmed.starts_with("https://") || trimmed.contains(char::is_whitespace) {
+        args::max_bytes("content", content_or_url.len(), args::MAX_CONTENT_BYTES)?;
+        return Ok(content_or_url.to_string());
+    }
+    let bytes = registry::fetch(trimmed, args::MAX_CONTENT_BYTES as u64)?;
+    String::from_utf8(bytes).map_err(|_| IpcError::ParseError {
+        path: trimmed.to_string(),
+        message: "The file is not UTF-8 text".to_string(),
+    })
+}
+
+fn writable_scope(scope: &str) -> Result<(ConfigScope, WritableScope), IpcError> {
+    Ok(
+        match args::one_of("scope", scope, &["user", "project", "local"])? {
+            "user" => (ConfigScope::User, WritableScope::User),
+            "project" => (ConfigScope::Project, WritableScope::Project),
+            _ => (ConfigScope::Local, WritableScope::Local),
+        },
+    )
+}
+
+fn create_dir(dir: &Path) -> Result<(), IpcError> {
+    fs::create_dir_all(dir).map_err(|e| IpcError::WriteFailed {
+        path: dir.display().to_string(),
+        message: format!("Failed to create directory: {}", e),
+    })
+}
+
+fn import(
+    kind: &str,
+    scope: &str,
+    artifact: SharedArtifact,
+    write: bool,
+    overwrite: bool,
+    project_root: &Option<String>,
+) -> Result<ArtifactImport, IpcError> {
+    let SharedArtifact {
+        name,
+        document,
+        config,
+        report,
+    } = artifact;
+    let mut result = ArtifactImport {
+        kind: kind.to_string(),
+        name: name.clone(),
+        path: String::new(),
+        exists: false,
+        content: Value::Null,
+        errors: report.errors,
+        warnings: report.warnings,
+        written: false,
+        duplicates: Vec::new(),
+    };
+
+    match kind {
+        "agent" | "skill" => {
+            let document = document.unwrap_or_default();
+            let path = if kind == "agent" {
+                resolve_agents_dir(scope, project_root)?.join(format!("{}.md", name))
+            } else {
+                resolve_skills_dir(scope, project_root)?
+                    .join(&name)
+                    .join("SKILL.md")
+            };
+            result.path = path.display().to_string();
+            result.exists = path.exists();
+            result.content = Value::String(document.clone());
+            result.duplicates = duplicates_of(kind, &name, &document, None, project_root);
+            if !write || !result.errors.is_empty() {
+                return Ok(result);
+            }
+            if result.exists && !overwrite {
+                return Err(IpcError::Conflict {
+                    path: result.path,
+                    message: format!("A {} named '{}' already exists", kind, name),
+                });
+            }
+            if let Some(parent) = path.parent() {
+                create_dir(parent)?;
+            }
+            text::write_text(&path, &document, None)?;
+        }
+        "mcp_server" => {
+            let server = config.unwrap_or_default();
+            let mut mcp = config::read_mcp_config(scope, project_root)?
+                .unwrap_or_else(|| json!({ "mcpServers": {} }));
+            result.path = paths::resolve_mcp_path(scope, project_root)?
+                .display()
+                .to_string();
+            result.exists = mcp["mcpServers"].get(&name).is_some();
+            result.content = server.clone();
+            if !write || !result.errors.is_empty() {
+                return Ok(result);
+            }
+            if result.exists && !overwrite {
+                return Err(IpcError::Conflict {
+                    path: result.path,
+                    message: format!("An MCP server named '{}' already exists", name),
+                });
+            }
+            if !mcp["mcpServers"].is_object() {
+                mcp["mcpServers"] = json!({});
+            }
+            mcp["mcpServers"][&name] = server;
+            config::write_mcp_config(scope, &mcp, project_root)?;
+        }
+        _ => {
+            let (config_scope, writable) = writable_scope(scope)?;
+            let mut settings =
+                config::read_settings(&config_scope, project_root)?.unwrap_or_else(|| json!({}));
+            result.path = paths::resolve_settings_path(&config_scope, project_root)?
+                .display()
+                .to_string();
+
+            // Only rules the scope does not have yet are added.
+            let incoming = config.unwrap_or_default();
+            let mut added = Map::new();
+            for list in PERMISSION_LISTS {
+                let present = settings["permissions"][*list]
+                    .as_array()
+                    .cloned()
+                    .unwrap_or_default();
+                let new: Vec<Value> = incoming[*list]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter(|rule| !present.contains(rule))
+                    .cloned()
+                    .collect();
+                if !new.is_empty() {
+                    added.insert(list.to_string(), Value::Array(new));
+                }
+            }
+            result.exists = added.is_empty();
+            result.content = Value::Object(added.clone());
+            if !write || !result.errors.is_empty() || added.is_empty() {
+                return Ok(result);
+            }
+            if !settings["permissions"].is_object() {
+                settings["permissions"] = json!({});
+            }
+            for (list, rules) in added {
+                let entry = &mut settings["permissions"][&list];
+                if !entry.is_array() {
+                    *entry = json!([]);
+                }
+                if let (Some(entry), Value::Array(rules)) = (entry.as_array_mut(), rules) {
+                    entry.extend(rules);
+                }
+            }
+            config::write_settings(&writable, &settings, None, project_root)?;
+        }
+    }
+
+    tracing::info!(kind, name = %result.name, path = %result.path, "imported artifact");
+    result.written = true;
+    Ok(result)
+}
+
+/// IPC command: imports one shared artifact into `scope`.
+///
+/// `content_or_url` is the pasted text (a SKILL.md or agent file, MCP or
+/// permissions JSON, or a snippet from `export_artifact`) or an https:// URL
+/// of the raw file. The artifact is validated and, unless `dry_run` is set,
+/// written: agents and skills as files named after their frontmatter
+/// `name`, MCP servers into the scope's MCP config, and permission rules
+/// appended to the scope's lists, skipping rules already there. Nothing is
+/// written when validation finds errors; an existing agent, skill, or
+/// server is a conflict unless `overwrite` is set.
+///
+/// Agents and skills come back with `duplicates`: existing ones in either
+/// scope with the same or a similar name, the same description, or a
+/// similar body, so the caller can offer to replace one or rename.
+#[tauri::command]
+pub async fn import_artifact(
+    kind: String,
+    scope: String,
+    content_or_url: String,
+    dry_run: Option<bool>,
+    overwrite: Option<bool>,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<ArtifactImport, IpcError> {
+    logging::async_command("import_artifact", async move {
+        let write = !dry_run.unwrap_or(false);
+        if write {
+            state.ensure_writable()?;
+        }
+        let project_root = state.project_root(&window);
+        blocking::run("import_artifact", move || {
+            let text = import_source(&content_or_url)?;
+            let artifact = share::parse_snippet(&kind, &text)?;
+            import(
+                &kind,
+                &scope,
+                artifact,
+                write,
+                overwrite.unwrap_or(false),
+                &project_root,
+            )
+        })
+        .await
+    })
+    .await
+}
+
+/// IPC command: finds existing agents or skills that look like the one
+/// about to be imported or copied, in both the user and project scopes.
+///
+/// Pass the artifact either as `content` (a pasted file or snippet) or as
+/// `scope` and `name` of an existing one about to be copied, which is left
+/// out of the results. Matches are ordered by body similarity; see
+/// `similarity::find_duplicates` for what counts as a match.
+#[tauri::command]
+pub async fn find_duplicate_artifacts(
+    kind: String,
+    content: Option<String>,
+    scope: Option<String>,
+    name: Option<String>,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<Vec<DuplicateMatch>, IpcError> {
+    logging::async_command("find_duplicate_artifacts", async move {
+        let project_root = state.project_root(&window);
+        blocking::run("find_duplicate_artifacts", move || {
+            let kind = args::one_of("kind", &kind, &["agent", "skill"])?;
+            if let Some(content) = content {
+                args::max_bytes("content", content.len(), args::MAX_CONTENT_BYTES)?;
+                let artifact = share::parse_snippet(kind, &content)?;
+                let document = artifact.document.unwrap_or_default();
+                return Ok(duplicates_of(
+                    kind,
+                    &artifact.name,
+                    &document,
+                    None,
+                    &project_root,
+                ));
+            }
+
+            let scope = scope.unwrap_or_default();
+            let name = required_name(name)?;
+            let stem = name.trim_end_matches(".md");
+            let path = if kind == "agent" {
+                resolve_agents_dir(&scope, &project_root)?.join(format!("{}.md", stem))
+            } else {
+                let base = resolve_skills_dir(&scope, &project_root)?;
+                if name.ends_with(".md") {
+                    base.join(&name)
+                } else {
+                    base.join(&name).join("SKILL.md")
+                }
+            };
+            let document = read_document(&path)?;
+            Ok(duplicates_of(
+                kind,
+                stem,
+                &document,
+                Some(&path),
+                &project_root,
+            ))
+        })
+        .await
+    })
+    .await
+}
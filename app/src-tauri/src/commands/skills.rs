@@ -1,11 +1,25 @@
 use std::fs;
+use std::io::Read;
 use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
 
+use base64::Engine;
 use serde_json::json;
 use tauri::State;
 
+use crate::args;
+use crate::audit;
+use crate::commands::references::find_name_references;
 use crate::errors::IpcError;
+use crate::frontmatter::{parse_frontmatter, serialize_frontmatter};
 use crate::io::atomic::atomic_write;
+use crate::io::blocking;
+use crate::io::copy::{copy_path, remove_path};
+use crate::io::mime;
+use crate::io::self_write;
+use crate::io::text::{self, TextFormat};
+use crate::io::trash::{self, TrashItem};
+use crate::logging;
 use crate::platform::paths;
 use crate::AppState;
 
@@ -13,143 +27,99 @@ use crate::AppState;
 ///
 /// - "user"    -> {configDir}/commands/
 /// - "project" -> {projectRoot}/.claude/commands/
-fn resolve_skills_dir(scope: &str, project_root: &Option<String>) -> Result<PathBuf, IpcError> {
+/// - "project:<package>" -> {projectRoot}/<package>/.claude/commands/
+pub(crate) fn resolve_skills_dir(
+    scope: &str,
+    project_root: &Option<String>,
+) -> Result<PathBuf, IpcError> {
+    let (scope, project_root) = paths::resolve_scope_address(scope, project_root)?;
     match scope {
         "user" => {
-            let config_dir = paths::resolve_config_dir();
+            let config_dir = paths::resolve_config_dir()?;
             Ok(config_dir.join("commands"))
         }
         "project" => {
-            let root = project_root.as_ref().ok_or_else(|| IpcError::NoProject {
+            let root = project_root.ok_or_else(|| IpcError::NoProject {
                 message: "No project is open. Open a project before accessing project skills."
                     .to_string(),
             })?;
             Ok(PathBuf::from(root).join(".claude").join("commands"))
         }
-        _ => Err(IpcError::PlatformError {
+        _ => Err(IpcError::InvalidArgument {
+            argument: "scope".to_string(),
             message: format!("Unknown skill scope: {}", scope),
         }),
     }
 }
 
-/// Parse YAML frontmatter from a markdown file.
-fn parse_frontmatter(content: &str) -> (serde_json::Value, String) {
-    let trimmed = content.trim_start();
-    if !trimmed.starts_with("---") {
-        return (json!({}), content.to_string());
-    }
+/// Largest skill file returned by `read_skill_file_binary` unless the caller
+/// asks for a different limit.
+const MAX_BINARY_READ_BYTES: u64 = 10 * 1024 * 1024;
 
-    let after_first = &trimmed[3..];
-    let after_first = after_first.trim_start_matches(['\r', '\n']);
-
-    if let Some(end_idx) = after_first.find("\n---") {
-        let yaml_part = &after_first[..end_idx];
-        let body_start = end_idx + 4;
-        let body = if body_start < after_first.len() {
-            after_first[body_start..]
-                .trim_start_matches(['\r', '\n'])
-                .to_string()
-        } else {
-            String::new()
-        };
-
-        let frontmatter = parse_yaml_simple(yaml_part);
-        (frontmatter, body)
-    } else {
-        (json!({}), content.to_string())
-    }
+/// Resolves a file inside a skill directory.
+///
+/// `directory` must be a single path component and `filename` a relative
+/// path without `..`, so the result cannot escape the skill directory.
+pub(crate) fn resolve_skill_file(
+    base_dir: &Path,
+    directory: &str,
+    filename: &str,
+) -> Result<PathBuf, IpcError> {
+    args::file_name("directory", directory)?;
+    args::relative_path("filename", filename)?;
+    Ok(base_dir.join(directory).join(filename))
 }
 
-/// Simple YAML-like parser for key: value lines.
-fn parse_yaml_simple(yaml: &str) -> serde_json::Value {
-    let mut map = serde_json::Map::new();
-
-    for line in yaml.lines() {
-        let line = line.trim();
-        if line.is_empty() || line.starts_with('#') {
-            continue;
-        }
-
-        if let Some((key, value)) = line.split_once(':') {
-            let key = key.trim().to_string();
-            let value = value.trim();
-
-            if value.is_empty() {
-                map.insert(key, json!(null));
-                continue;
-            }
-
-            if value.starts_with('[') && value.ends_with(']') {
-                let inner = &value[1..value.len() - 1];
-                let items: Vec<serde_json::Value> = inner
-                    .split(',')
-                    .map(|s| {
-                        let s = s.trim().trim_matches('"').trim_matches('\'');
-                        json!(s)
-                    })
-                    .collect();
-                map.insert(key, json!(items));
-                continue;
-            }
-
-            if value == "true" {
-                map.insert(key, json!(true));
-                continue;
-            }
-            if value == "false" {
-                map.insert(key, json!(false));
-                continue;
-            }
-
-            if let Ok(n) = value.parse::<i64>() {
-                map.insert(key, json!(n));
-                continue;
-            }
-
-            if let Ok(n) = value.parse::<f64>() {
-                map.insert(key, json!(n));
-                continue;
-            }
-
-            let s = value.trim_matches('"').trim_matches('\'');
-            map.insert(key, json!(s));
-        }
-    }
-
-    serde_json::Value::Object(map)
-}
+/// Builds the recursive file tree of a skill directory.
+///
+/// Paths are relative to the skill directory, forward-slash separated.
+/// Directories come before files; both are sorted by name.
+fn skill_file_tree(skill_dir: &Path, dir: &Path) -> Vec<serde_json::Value> {
+    let mut entries: Vec<_> = match fs::read_dir(dir) {
+        Ok(entries) => entries.flatten().collect(),
+        Err(_) => return vec![],
+    };
+    entries.sort_by_key(|e| (!e.path().is_dir(), e.file_name()));
 
-/// Serialize a JSON value as YAML-like frontmatter lines.
-fn serialize_frontmatter(frontmatter: &serde_json::Value) -> String {
-    let mut lines = Vec::new();
-    if let Some(obj) = frontmatter.as_object() {
-        for (key, value) in obj {
-            match value {
-                serde_json::Value::String(s) => lines.push(format!("{}: {}", key, s)),
-                serde_json::Value::Bool(b) => lines.push(format!("{}: {}", key, b)),
-                serde_json::Value::Number(n) => lines.push(format!("{}: {}", key, n)),
-                serde_json::Value::Array(arr) => {
-                    let items: Vec<String> = arr
-                        .iter()
-                        .map(|v| match v {
-                            serde_json::Value::String(s) => s.clone(),
-                            other => other.to_string(),
-                        })
-                        .collect();
-                    lines.push(format!("{}: [{}]", key, items.join(", ")));
-                }
-                serde_json::Value::Null => {}
-                _ => {
-                    lines.push(format!(
-                        "{}: {}",
-                        key,
-                        serde_json::to_string(value).unwrap_or_default()
-                    ));
-                }
+    entries
+        .into_iter()
+        .map(|entry| {
+            let path = entry.path();
+            let metadata = entry.metadata().ok();
+            let modified = metadata
+                .as_ref()
+                .and_then(|m| m.modified().ok())
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_millis() as u64);
+            let rel = path
+                .strip_prefix(skill_dir)
+                .unwrap_or(&path)
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+                .join("/");
+
+            if path.is_dir() {
+                json!({
+                    "name": entry.file_name().to_string_lossy(),
+                    "path": rel,
+                    "isDir": true,
+                    "size": null,
+                    "modified": modified,
+                    "children": skill_file_tree(skill_dir, &path),
+                })
+            } else {
+                json!({
+                    "name": entry.file_name().to_string_lossy(),
+                    "path": rel,
+                    "isDir": false,
+                    "size": metadata.map(|m| m.len()),
+                    "modified": modified,
+                    "children": [],
+                })
             }
-        }
-    }
-    lines.join("\n")
+        })
+        .collect()
 }
 
 /// IPC command: lists skill directories from one or both scopes.
@@ -157,98 +127,97 @@ fn serialize_frontmatter(frontmatter: &serde_json::Value) -> String {
 /// Each skill is a directory containing a SKILL.md file. Returns an array of
 /// `{ directory, scope, frontmatter, bodyPreview }`.
 #[tauri::command]
-pub fn list_skills(
+pub async fn list_skills(
     scope: Option<String>,
-    state: State<AppState>,
+    window: tauri::Window,
+    state: State<'_, AppState>,
 ) -> Result<Vec<serde_json::Value>, IpcError> {
-    let project_root = state
-        .project_root
-        .lock()
-        .map_err(|e| IpcError::PlatformError {
-            message: format!("Failed to acquire state lock: {}", e),
-        })?;
-
-    let scopes: Vec<&str> = match scope.as_deref() {
-        Some(s) => vec![s],
-        None => vec!["user", "project"],
-    };
-
-    let mut skills = Vec::new();
-
-    for s in scopes {
-        let dir = match resolve_skills_dir(s, &project_root) {
-            Ok(d) => d,
-            Err(_) => continue,
-        };
-
-        if !dir.exists() {
-            continue;
-        }
-
-        let entries = fs::read_dir(&dir).map_err(|e| IpcError::PlatformError {
-            message: format!("Failed to read skills directory: {}", e),
-        })?;
-
-        for entry in entries {
-            let entry = entry.map_err(|e| IpcError::PlatformError {
-                message: format!("Failed to read directory entry: {}", e),
-            })?;
+    logging::async_command("list_skills", async move {
+        let project_root = state.project_root(&window);
+        blocking::run("list_skills", move || {
+            let scopes: Vec<&str> = match scope.as_deref() {
+                Some(s) => vec![s],
+                None => vec!["user", "project"],
+            };
+
+            let mut skills = Vec::new();
+
+            for s in scopes {
+                let dir = match resolve_skills_dir(s, &project_root) {
+                    Ok(d) => d,
+                    Err(_) => continue,
+                };
+
+                if !dir.exists() {
+                    continue;
+                }
 
-            let path = entry.path();
-            if !path.is_dir() {
-                // Skills can also be single .md files in the commands directory
-                if path.extension().and_then(|e| e.to_str()) == Some("md") {
-                    let filename = entry.file_name().to_string_lossy().to_string();
-                    let content = fs::read_to_string(&path).unwrap_or_default();
+                let entries = fs::read_dir(&dir).map_err(|e| IpcError::PlatformError {
+                    message: format!("Failed to read skills directory: {}", e),
+                })?;
+
+                for entry in entries {
+                    let entry = entry.map_err(|e| IpcError::PlatformError {
+                        message: format!("Failed to read directory entry: {}", e),
+                    })?;
+
+                    let path = entry.path();
+                    if !path.is_dir() {
+                        // Skills can also be single .md files in the commands directory
+                        if path.extension().and_then(|e| e.to_str()) == Some("md") {
+                            let filename = entry.file_name().to_string_lossy().to_string();
+                            let content = fs::read_to_string(&path).unwrap_or_default();
+                            let (frontmatter, body) = parse_frontmatter(&content);
+                            let preview: String = body.chars().take(200).collect();
+
+                            skills.push(json!({
+                                "directory": filename,
+                                "scope": s,
+                                "frontmatter": frontmatter,
+                                "bodyPreview": preview,
+                            }));
+                        }
+                        continue;
+                    }
+
+                    let dir_name = entry.file_name().to_string_lossy().to_string();
+                    let skill_md = path.join("SKILL.md");
+
+                    if !skill_md.exists() {
+                        continue;
+                    }
+
+                    let content = fs::read_to_string(&skill_md).unwrap_or_default();
                     let (frontmatter, body) = parse_frontmatter(&content);
                     let preview: String = body.chars().take(200).collect();
 
                     skills.push(json!({
-                        "directory": filename,
+                        "directory": dir_name,
                         "scope": s,
                         "frontmatter": frontmatter,
                         "bodyPreview": preview,
                     }));
                 }
-                continue;
             }
 
-            let dir_name = entry.file_name().to_string_lossy().to_string();
-            let skill_md = path.join("SKILL.md");
-
-            if !skill_md.exists() {
-                continue;
-            }
-
-            let content = fs::read_to_string(&skill_md).unwrap_or_default();
-            let (frontmatter, body) = parse_frontmatter(&content);
-            let preview: String = body.chars().take(200).collect();
-
-            skills.push(json!({
-                "directory": dir_name,
-                "scope": s,
-                "frontmatter": frontmatter,
-                "bodyPreview": preview,
-            }));
-        }
-    }
-
-    Ok(skills)
+            Ok(skills)
+        })
+        .await
+    })
+    .await
 }
 
-/// IPC command: reads a single skill's SKILL.md and returns its frontmatter and body.
+/// IPC command: reads a single skill's SKILL.md and returns its frontmatter,
+/// body, and `format` (line endings and BOM, which writes keep).
 #[tauri::command]
 pub fn read_skill(
     scope: String,
     directory: String,
+    window: tauri::Window,
     state: State<AppState>,
 ) -> Result<serde_json::Value, IpcError> {
-    let project_root = state
-        .project_root
-        .lock()
-        .map_err(|e| IpcError::PlatformError {
-            message: format!("Failed to acquire state lock: {}", e),
-        })?;
+    args::file_name("directory", &directory)?;
+    let project_root = state.project_root(&window);
 
     let dir = resolve_skills_dir(&scope, &project_root)?;
 
@@ -261,7 +230,7 @@ pub fn read_skill(
 
     let path_str = skill_path.display().to_string();
 
-    let content = fs::read_to_string(&skill_path).map_err(|e| match e.kind() {
+    let (content, format) = text::read_text(&skill_path).map_err(|e| match e.kind() {
         std::io::ErrorKind::NotFound => IpcError::NotFound {
             path: path_str.clone(),
         },
@@ -278,24 +247,28 @@ pub fn read_skill(
     Ok(json!({
         "frontmatter": frontmatter,
         "body": body,
+        "format": format,
     }))
 }
 
 /// IPC command: writes a skill's SKILL.md with YAML frontmatter and markdown body.
+///
+/// An existing file keeps its line endings and BOM unless `format`
+/// overrides them.
 #[tauri::command]
 pub fn write_skill(
     scope: String,
     directory: String,
     frontmatter: serde_json::Value,
     body: String,
+    format: Option<TextFormat>,
+    window: tauri::Window,
     state: State<AppState>,
 ) -> Result<(), IpcError> {
-    let project_root = state
-        .project_root
-        .lock()
-        .map_err(|e| IpcError::PlatformError {
-            message: format!("Failed to acquire state lock: {}", e),
-        })?;
+    state.ensure_writable()?;
+    args::file_name("directory", &directory)?;
+    args::max_bytes("body", body.len(), args::MAX_CONTENT_BYTES)?;
+    let project_root = state.project_root(&window);
 
     let base_dir = resolve_skills_dir(&scope, &project_root)?;
 
@@ -324,37 +297,33 @@ pub fn write_skill(
         format!("---\n{}\n---\n\n{}", fm, body)
     };
 
-    atomic_write(Path::new(&skill_path), content.as_bytes()).map_err(|_| {
-        IpcError::WriteFailed {
-            path: path_str,
-            message: "Atomic write failed".to_string(),
-        }
+    text::write_text(&skill_path, &content, format).map_err(|_| IpcError::WriteFailed {
+        path: path_str,
+        message: "Atomic write failed".to_string(),
     })?;
 
     Ok(())
 }
 
 /// IPC command: deletes a skill directory (or single .md file).
+///
+/// The skill is moved to ssenrah's trash rather than removed; the returned
+/// item can be passed to `restore_deleted` within the retention period.
 #[tauri::command]
 pub fn delete_skill(
     scope: String,
     directory: String,
+    window: tauri::Window,
     state: State<AppState>,
-) -> Result<(), IpcError> {
-    let project_root = state
-        .project_root
-        .lock()
-        .map_err(|e| IpcError::PlatformError {
-            message: format!("Failed to acquire state lock: {}", e),
-        })?;
+) -> Result<TrashItem, IpcError> {
+    state.ensure_writable()?;
+    args::file_name("directory", &directory)?;
+    let project_root = state.project_root(&window);
 
     let base_dir = resolve_skills_dir(&scope, &project_root)?;
 
-    let path = if directory.ends_with(".md") {
-        base_dir.join(&directory)
-    } else {
-        base_dir.join(&directory)
-    };
+    // Single-file skills and skill directories both live directly under base_dir.
+    let path = base_dir.join(&directory);
 
     let path_str = path.display().to_string();
 
@@ -362,19 +331,12 @@ pub fn delete_skill(
         return Err(IpcError::NotFound { path: path_str });
     }
 
-    if path.is_dir() {
-        fs::remove_dir_all(&path).map_err(|e| IpcError::WriteFailed {
-            path: path_str,
-            message: format!("Failed to delete skill directory: {}", e),
-        })?;
-    } else {
-        fs::remove_file(&path).map_err(|e| IpcError::WriteFailed {
-            path: path_str,
-            message: format!("Failed to delete skill file: {}", e),
-        })?;
-    }
+    let hash_before = audit::file_hash(&path);
+    let item = trash::move_to_trash("skill", &scope, &path)?;
+    self_write::tracker().register(&path);
+    audit::record_delete(&path, hash_before);
 
-    Ok(())
+    Ok(item)
 }
 
 /// IPC command: reads an arbitrary file within a skill directory.
@@ -383,17 +345,13 @@ pub fn read_skill_file(
     scope: String,
     directory: String,
     filename: String,
+    window: tauri::Window,
     state: State<AppState>,
 ) -> Result<String, IpcError> {
-    let project_root = state
-        .project_root
-        .lock()
-        .map_err(|e| IpcError::PlatformError {
-            message: format!("Failed to acquire state lock: {}", e),
-        })?;
+    let project_root = state.project_root(&window);
 
     let base_dir = resolve_skills_dir(&scope, &project_root)?;
-    let path = base_dir.join(&directory).join(&filename);
+    let path = resolve_skill_file(&base_dir, &directory, &filename)?;
     let path_str = path.display().to_string();
 
     fs::read_to_string(&path).map_err(|e| match e.kind() {
@@ -410,39 +368,369 @@ pub fn read_skill_file(
 }
 
 /// IPC command: writes an arbitrary file within a skill directory.
+///
+/// An existing file keeps its line endings and BOM unless `format`
+/// overrides them.
 #[tauri::command]
 pub fn write_skill_file(
     scope: String,
     directory: String,
     filename: String,
     content: String,
+    format: Option<TextFormat>,
+    window: tauri::Window,
     state: State<AppState>,
 ) -> Result<(), IpcError> {
-    let project_root = state
-        .project_root
-        .lock()
-        .map_err(|e| IpcError::PlatformError {
-            message: format!("Failed to acquire state lock: {}", e),
-        })?;
+    state.ensure_writable()?;
+    args::max_bytes("content", content.len(), args::MAX_CONTENT_BYTES)?;
+    let project_root = state.project_root(&window);
 
     let base_dir = resolve_skills_dir(&scope, &project_root)?;
-    let skill_dir = base_dir.join(&directory);
+    let path = resolve_skill_file(&base_dir, &directory, &filename)?;
+    let path_str = path.display().to_string();
 
-    // Ensure skill directory exists
-    if !skill_dir.exists() {
-        fs::create_dir_all(&skill_dir).map_err(|e| IpcError::WriteFailed {
+    // Nested resource files may live in subdirectories
+    if let Some(parent) = path.parent() {
+        if !parent.exists() {
+            fs::create_dir_all(parent).map_err(|e| IpcError::WriteFailed {
+                path: path_str.clone(),
+                message: format!("Failed to create parent directory: {}", e),
+            })?;
+        }
+    }
+
+    text::write_text(&path, &content, format).map_err(|_| IpcError::WriteFailed {
+        path: path_str,
+        message: "Atomic write failed".to_string(),
+    })?;
+
+    Ok(())
+}
+
+/// IPC command: lists every file in a skill directory as a recursive tree.
+///
+/// Returns an array of `{ name, path, isDir, size, modified, children }`,
+/// where `path` is relative to the skill directory and `modified` is in Unix
+/// milliseconds.
+#[tauri::command]
+pub fn list_skill_files(
+    scope: String,
+    directory: String,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<Vec<serde_json::Value>, IpcError> {
+    let project_root = state.project_root(&window);
+
+    let base_dir = resolve_skills_dir(&scope, &project_root)?;
+    let skill_dir = base_dir.join(args::file_name("directory", &directory)?);
+
+    if !skill_dir.is_dir() {
+        return Err(IpcError::NotFound {
             path: skill_dir.display().to_string(),
-            message: format!("Failed to create skill directory: {}", e),
-        })?;
+        });
     }
 
-    let path = skill_dir.join(&filename);
+    Ok(skill_file_tree(&skill_dir, &skill_dir))
+}
+
+/// IPC command: deletes a file or subdirectory within a skill directory.
+///
+/// SKILL.md itself cannot be deleted this way; use `delete_skill` instead.
+#[tauri::command]
+pub fn delete_skill_file(
+    scope: String,
+    directory: String,
+    filename: String,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<(), IpcError> {
+    state.ensure_writable()?;
+    let project_root = state.project_root(&window);
+
+    let base_dir = resolve_skills_dir(&scope, &project_root)?;
+    let path = resolve_skill_file(&base_dir, &directory, &filename)?;
     let path_str = path.display().to_string();
 
-    atomic_write(Path::new(&path), content.as_bytes()).map_err(|_| IpcError::WriteFailed {
+    if path == base_dir.join(&directory).join("SKILL.md") {
+        return Err(IpcError::WriteFailed {
+            path: path_str,
+            message: "SKILL.md cannot be deleted on its own; delete the skill instead".to_string(),
+        });
+    }
+
+    if !path.exists() {
+        return Err(IpcError::NotFound { path: path_str });
+    }
+
+    remove_path(&path).map_err(|e| IpcError::WriteFailed {
         path: path_str,
-        message: "Atomic write failed".to_string(),
+        message: format!("Failed to delete skill file: {}", e),
+    })?;
+
+    Ok(())
+}
+
+/// IPC command: renames or moves a file within a skill directory.
+///
+/// Fails if the destination already exists. SKILL.md cannot be renamed.
+#[tauri::command]
+pub fn rename_skill_file(
+    scope: String,
+    directory: String,
+    from: String,
+    to: String,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<(), IpcError> {
+    state.ensure_writable()?;
+    let project_root = state.project_root(&window);
+
+    let base_dir = resolve_skills_dir(&scope, &project_root)?;
+    let source = resolve_skill_file(&base_dir, &directory, &from)?;
+    let target = resolve_skill_file(&base_dir, &directory, &to)?;
+    let source_str = source.display().to_string();
+    let target_str = target.display().to_string();
+
+    let skill_md = base_dir.join(&directory).join("SKILL.md");
+    if source == skill_md || target == skill_md {
+        return Err(IpcError::WriteFailed {
+            path: source_str,
+            message: "SKILL.md cannot be renamed".to_string(),
+        });
+    }
+
+    if !source.exists() {
+        return Err(IpcError::NotFound { path: source_str });
+    }
+    if target.exists() {
+        return Err(IpcError::Conflict {
+            path: target_str,
+            message: "A file with that name already exists".to_string(),
+        });
+    }
+
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).map_err(|e| IpcError::WriteFailed {
+            path: target_str.clone(),
+            message: format!("Failed to create parent directory: {}", e),
+        })?;
+    }
+
+    fs::rename(&source, &target).map_err(|e| IpcError::WriteFailed {
+        path: target_str,
+        message: format!("Failed to rename skill file: {}", e),
     })?;
+    self_write::tracker().register(&source);
+    self_write::tracker().register(&target);
+    audit::record_rename(&source, &target);
 
     Ok(())
 }
+
+/// IPC command: reads any file within a skill directory as base64.
+///
+/// Returns `{ size, mimeType, truncated, content }`. The MIME type is sniffed
+/// from the leading bytes (then the extension). Files larger than `maxBytes`
+/// (default 10 MiB) are not read: `content` is null and `truncated` is true.
+#[tauri::command]
+pub fn read_skill_file_binary(
+    scope: String,
+    directory: String,
+    filename: String,
+    max_bytes: Option<u64>,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<serde_json::Value, IpcError> {
+    let project_root = state.project_root(&window);
+
+    let base_dir = resolve_skills_dir(&scope, &project_root)?;
+    let path = resolve_skill_file(&base_dir, &directory, &filename)?;
+    let path_str = path.display().to_string();
+
+    let map_err = |e: std::io::Error| match e.kind() {
+        std::io::ErrorKind::NotFound => IpcError::NotFound {
+            path: path_str.clone(),
+        },
+        std::io::ErrorKind::PermissionDenied => IpcError::PermissionDenied {
+            path: path_str.clone(),
+        },
+        _ => IpcError::PlatformError {
+            message: format!("Failed to read {}: {}", path_str, e),
+        },
+    };
+
+    let size = fs::metadata(&path).map_err(map_err)?.len();
+    let limit = max_bytes.unwrap_or(MAX_BINARY_READ_BYTES);
+
+    if size > limit {
+        let mut head = Vec::new();
+        let file = fs::File::open(&path).map_err(map_err)?;
+        file.take(512).read_to_end(&mut head).map_err(map_err)?;
+        return Ok(json!({
+            "size": size,
+            "mimeType": mime::sniff(&path, &head),
+            "truncated": true,
+            "content": null,
+        }));
+    }
+
+    let bytes = fs::read(&path).map_err(map_err)?;
+
+    Ok(json!({
+        "size": size,
+        "mimeType": mime::sniff(&path, &bytes[..bytes.len().min(512)]),
+        "truncated": false,
+        "content": base64::engine::general_purpose::STANDARD.encode(&bytes),
+    }))
+}
+
+/// IPC command: writes base64-encoded bytes to a file within a skill directory.
+#[tauri::command]
+pub fn write_skill_file_binary(
+    scope: String,
+    directory: String,
+    filename: String,
+    content: String,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<(), IpcError> {
+    state.ensure_writable()?;
+    let project_root = state.project_root(&window);
+
+    let base_dir = resolve_skills_dir(&scope, &project_root)?;
+    let path = resolve_skill_file(&base_dir, &directory, &filename)?;
+    let path_str = path.display().to_string();
+
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(content.trim())
+        .map_err(|e| args::invalid("content", format!("Content is not valid base64: {}", e)))?;
+    args::max_bytes("content", bytes.len(), args::MAX_CONTENT_BYTES)?;
+
+    if let Some(parent) = path.parent() {
+        if !parent.exists() {
+            fs::create_dir_all(parent).map_err(|e| IpcError::WriteFailed {
+                path: path_str.clone(),
+                message: format!("Failed to create parent directory: {}", e),
+            })?;
+        }
+    }
+
+    atomic_write(Path::new(&path), &bytes)
+}
+
+/// IPC command: renames a skill within its scope.
+///
+/// Skill directories and single-file `.md` skills are both moved with one
+/// rename; a single-file skill keeps its `.md` extension. Returns
+/// `{ path, references }`, where `references` lists settings entries and
+/// agent/skill frontmatter that still mention the old name.
+#[tauri::command]
+pub fn rename_skill(
+    scope: String,
+    old: String,
+    new: String,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<serde_json::Value, IpcError> {
+    state.ensure_writable()?;
+    let project_root = state.project_root(&window);
+
+    let base_dir = resolve_skills_dir(&scope, &project_root)?;
+
+    // A skill directory holds SKILL.md; a single-file skill is `{name}.md`.
+    let single_file = old.ends_with(".md");
+    let new = match (single_file, new.ends_with(".md")) {
+        (true, false) => format!("{}.md", new),
+        (false, true) => new.trim_end_matches(".md").to_string(),
+        _ => new,
+    };
+    args::file_name("old", &old)?;
+    args::file_name("new", &new)?;
+    let source = base_dir.join(&old);
+    let target = base_dir.join(&new);
+
+    if !source.exists() {
+        return Err(IpcError::NotFound {
+            path: source.display().to_string(),
+        });
+    }
+    if target.exists() {
+        return Err(IpcError::Conflict {
+            path: target.display().to_string(),
+            message: "A skill with that name already exists".to_string(),
+        });
+    }
+
+    fs::rename(&source, &target).map_err(|e| IpcError::WriteFailed {
+        path: target.display().to_string(),
+        message: format!("Failed to rename skill: {}", e),
+    })?;
+    self_write::tracker().register(&source);
+    self_write::tracker().register(&target);
+    audit::record_rename(&source, &target);
+
+    let old_name = old.trim_end_matches(".md");
+    Ok(json!({
+        "path": target.display().to_string(),
+        "references": find_name_references(old_name, &project_root),
+    }))
+}
+
+/// IPC command: copies a skill to another scope.
+///
+/// Skill directories are copied recursively, including nested resource
+/// files; single-file skills are copied as-is. Fails if the target scope
+/// already has a skill with that name unless `overwrite` is true, in which
+/// case the existing skill is replaced entirely. Returns `{ path }`.
+#[tauri::command]
+pub fn copy_skill(
+    from_scope: String,
+    to_scope: String,
+    directory: String,
+    overwrite: Option<bool>,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<serde_json::Value, IpcError> {
+    state.ensure_writable()?;
+    let project_root = state.project_root(&window);
+
+    if from_scope == to_scope {
+        return Err(IpcError::InvalidArgument {
+            argument: "toScope".to_string(),
+            message: "Source and target scope are the same".to_string(),
+        });
+    }
+
+    let source_base = resolve_skills_dir(&from_scope, &project_root)?;
+    let target_base = resolve_skills_dir(&to_scope, &project_root)?;
+    args::file_name("directory", &directory)?;
+    let source = source_base.join(&directory);
+    let target = target_base.join(&directory);
+    let target_str = target.display().to_string();
+
+    if !source.exists() {
+        return Err(IpcError::NotFound {
+            path: source.display().to_string(),
+        });
+    }
+
+    if target.exists() {
+        if !overwrite.unwrap_or(false) {
+            return Err(IpcError::Conflict {
+                path: target_str,
+                message: format!("Skill {} already exists in {} scope", directory, to_scope),
+            });
+        }
+        remove_path(&target).map_err(|e| IpcError::WriteFailed {
+            path: target_str.clone(),
+            message: format!("Failed to replace existing skill: {}", e),
+        })?;
+    }
+
+    copy_path(&source, &target).map_err(|e| IpcError::WriteFailed {
+        path: target_str.clone(),
+        message: format!("Failed to copy skill: {}", e),
+    })?;
+
+    Ok(json!({ "path": target_str }))
+}
@@ -0,0 +1,124 @@
+use std::time::Duration;
+
+use tauri::{AppHandle, Manager, State};
+
+use crate::clock;
+use crate::errors::IpcError;
+use crate::io::blocking;
+use crate::io::snapshot::{self, Snapshot};
+use crate::logging;
+use crate::preferences;
+use crate::AppState;
+
+/// How often the scheduler checks whether a snapshot is due.
+const SCHEDULER_TICK: Duration = Duration::from_secs(15 * 60);
+
+/// Every project open in some window, each once.
+fn open_project_roots(state: &AppState) -> Vec<String> {
+    let mut roots: Vec<String> = state
+        .projects
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .values()
+        .cloned()
+        .collect();
+    roots.sort();
+    roots.dedup();
+    roots
+}
+
+/// Takes a scheduled snapshot if the last one is older than the
+/// `snapshotIntervalHours` preference.
+fn snapshot_if_due(app: &AppHandle) {
+    let hours = preferences::preferences_path()
+        .and_then(|path| preferences::load_from(&path))
+        .map(|p| p.snapshot_interval_hours)
+        .unwrap_or_default();
+    if hours == 0 {
+        return;
+    }
+    let now = clock::now_millis();
+    let last = snapshot::list().map(|snapshots| {
+        snapshots
+            .into_iter()
+            .find(|s| s.trigger == "scheduled")
+            .map_or(0, |s| s.created_at)
+    });
+    if last.is_ok_and(|last| now.saturating_sub(last) < u64::from(hours) * 60 * 60 * 1000) {
+        return;
+    }
+
+    let roots = open_project_roots(&app.state::<AppState>());
+    let taken = snapshot::snapshot_targets(&roots)
+        .and_then(|targets| snapshot::take("scheduled", &targets));
+    match taken {
+        Ok(Some(taken)) => {
+            tracing::info!(id = %taken.id, files = taken.files.len(), "took scheduled config snapshot")
+        }
+        Ok(None) => {}
+        Err(e) => tracing::warn!(error = %e, "scheduled config snapshot failed"),
+    }
+}
+
+/// Starts the background task behind scheduled snapshots. It wakes every
+/// `SCHEDULER_TICK`, so files changed by other tools while ssenrah runs are
+/// captured even though no ssenrah command touched them.
+pub fn spawn_scheduler(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut ticks = tokio::time::interval(SCHEDULER_TICK);
+        loop {
+            ticks.tick().await;
+            let app = app.clone();
+            let _ = tauri::async_runtime::spawn_blocking(move || snapshot_if_due(&app)).await;
+        }
+    });
+}
+
+/// IPC command: lists config snapshots, newest first.
+///
+/// Each snapshot records the user settings and memory plus the settings,
+/// memory, and `.mcp.json` of every project open at the time, with the
+/// content hash of each file.
+#[tauri::command]
+pub async fn list_snapshots() -> Result<Vec<Snapshot>, IpcError> {
+    logging::async_command("list_snapshots", async move {
+        blocking::run("list_snapshots", snapshot::list).await
+    })
+    .await
+}
+
+/// IPC command: snapshots the config files now. Returns `null` when nothing
+/// changed since the latest snapshot.
+#[tauri::command]
+pub async fn take_snapshot(state: State<'_, AppState>) -> Result<Option<Snapshot>, IpcError> {
+    logging::async_command("take_snapshot", async move {
+        let roots = open_project_roots(&state);
+        blocking::run("take_snapshot", move || {
+            snapshot::take("manual", &snapshot::snapshot_targets(&roots)?)
+        })
+        .await
+    })
+    .await
+}
+
+/// IPC command: writes the files of snapshot `id` back, or only `paths`
+/// when given, and returns the restored paths.
+///
+/// The current content is snapshotted first, so a restore shows up in
+/// `list_snapshots` and can be undone the same way.
+#[tauri::command]
+pub async fn restore_snapshot(
+    id: String,
+    paths: Option<Vec<String>>,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, IpcError> {
+    logging::async_command("restore_snapshot", async move {
+        state.ensure_writable()?;
+        blocking::run("restore_snapshot", move || {
+            snapshot::restore(&id, paths.as_deref())
+        })
+        .await
+    })
+    .await
+}
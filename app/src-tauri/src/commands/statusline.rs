@@ -0,0 +1,223 @@
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{self, Receiver};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::State;
+
+use crate::errors::IpcError;
+use crate::io::blocking;
+use crate::logging;
+use crate::platform::{detect, paths};
+use crate::statusline;
+use crate::AppState;
+
+/// A preview command still running after this long is killed.
+const PREVIEW_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long to wait for output after the command exits. Background
+/// processes it started may hold its pipes open much longer.
+const OUTPUT_GRACE: Duration = Duration::from_millis(200);
+
+/// Result of `render_statusline_preview`.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatuslinePreview {
+    /// The line Claude Code would show: the first line of stdout.
+    pub output: String,
+    pub stdout: String,
+    pub stderr: String,
+    pub exit_code: Option<i32>,
+    pub duration_ms: f64,
+    pub timed_out: bool,
+    /// Problems with the configured command path.
+    pub warnings: Vec<String>,
+}
+
+/// Reads `pipe` to the end on its own thread; the text arrives on the
+/// returned channel.
+fn read_pipe<R: Read + Send + 'static>(pipe: Option<R>) -> Receiver<String> {
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut bytes = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut bytes);
+        }
+        let _ = sender.send(String::from_utf8_lossy(&bytes).to_string());
+    });
+    receiver
+}
+
+/// The flag that makes `shell` run a command string: `/C` for cmd.exe,
+/// `-Command` for PowerShell, and `-c` for everything else, including Git
+/// Bash on Windows.
+fn command_flag(shell: &str) -> &'static str {
+    let name = shell.rsplit(['/', '\\']).next().unwrap_or(shell);
+    let name = name.to_ascii_lowercase();
+    match name.strip_suffix(".exe").unwrap_or(&name) {
+        "cmd" => "/C",
+        "powershell" | "pwsh" => "-Command",
+        _ => "-c",
+    }
+}
+
+/// Kills the command and, on Unix, every process it started: it runs in its
+/// own process group.
+fn kill_tree(child: &mut Child) {
+    #[cfg(unix)]
+    // SAFETY: kill(2) with a negative pid only signals that process group.
+    unsafe {
+        libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL);
+    }
+    let _ = child.kill();
+}
+
+/// Runs `command` through the user's shell with `input` on stdin, the way
+/// Claude Code runs a statusLine command, killing it after `timeout`.
+pub(crate) fn run_command(
+    command: &str,
+    cwd: &Path,
+    input: &[u8],
+    timeout: Duration,
+) -> Result<StatuslinePreview, IpcError> {
+    let shell = detect::detect_shell();
+    let mut process = Command::new(&shell);
+    process.args([command_flag(&shell), command]);
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut process, 0);
+
+    let start = Instant::now();
+    let mut child = process
+        .current_dir(cwd)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| IpcError::PlatformError {
+            message: format!("Failed to start {}: {}", shell, e),
+        })?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // The command may exit without reading its input.
+        let _ = stdin.write_all(input);
+    }
+    let stdout = read_pipe(child.stdout.take());
+    let stderr = read_pipe(child.stderr.take());
+
+    let mut timed_out = false;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break Some(status),
+            Ok(None) if start.elapsed() >= timeout => {
+                kill_tree(&mut child);
+                timed_out = true;
+                break child.wait().ok();
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(10)),
+            Err(_) => break None,
+        }
+    };
+    let duration_ms = start.elapsed().as_secs_f64() * 1000.0;
+    let stdout = stdout.recv_timeout(OUTPUT_GRACE).unwrap_or_default();
+
+    Ok(StatuslinePreview {
+        output: statusline::first_line(&stdout).to_string(),
+        stderr: stderr.recv_timeout(OUTPUT_GRACE).unwrap_or_default(),
+        stdout,
+        exit_code: status.and_then(|s| s.code()),
+        duration_ms,
+        timed_out,
+        warnings: Vec::new(),
+    })
+}
+
+/// IPC command: renders a statusLine setting with synthetic session data.
+///
+/// For `{ type: "command", command }` the command is run through the user's
+/// shell in the project root (or home directory) with the session JSON on
+/// stdin, as Claude Code does, and killed after 5 seconds. For
+/// `{ template }` the `{{dot.path}}` placeholders are filled from the
+/// session JSON instead. `sample_context` overrides fields of the default
+/// session data (model, workspace, cost, ...).
+///
+/// Returns the rendered line, the raw output, exit code, timing, and
+/// warnings about the command path (missing, not a file, not executable).
+#[tauri::command]
+pub async fn render_statusline_preview(
+    statusline_config: Value,
+    sample_context: Option<Value>,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<StatuslinePreview, IpcError> {
+    logging::async_command("render_statusline_preview", async move {
+        let project_root = state.project_root(&window);
+        blocking::run("render_statusline_preview", move || {
+            let home = paths::home_dir()?;
+            let cwd = project_root
+                .map(PathBuf::from)
+                .unwrap_or_else(|| home.clone());
+            let context =
+                statusline::sample_context(&cwd.display().to_string(), sample_context.as_ref());
+
+            if let Some(template) = statusline_config.get("template").and_then(Value::as_str) {
+                let start = Instant::now();
+                let output = statusline::render_template(template, &context);
+                return Ok(StatuslinePreview {
+                    output: statusline::first_line(&output).to_string(),
+                    stdout: output,
+                    duration_ms: start.elapsed().as_secs_f64() * 1000.0,
+                    ..Default::default()
+                });
+            }
+
+            let command = statusline_config
+                .get("command")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            let warnings = statusline::check_command(command, &home, &cwd);
+            if command.trim().is_empty() {
+                return Ok(StatuslinePreview {
+                    warnings,
+                    ..Default::default()
+                });
+            }
+
+            let input = serde_json::to_vec(&context).unwrap_or_default();
+            let mut preview = run_command(command, &cwd, &input, PREVIEW_TIMEOUT)?;
+            preview.warnings = warnings;
+            Ok(preview)
+        })
+        .await
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_the_flag_of_the_detected_shell() {
+        assert_eq!(command_flag(r"C:\Windows\System32\cmd.exe"), "/C");
+        assert_eq!(command_flag("pwsh"), "-Command");
+        assert_eq!(command_flag(r"C:\Program Files\Git\bin\bash.exe"), "-c");
+        assert_eq!(command_flag("/bin/zsh"), "-c");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn times_out_even_when_background_processes_hold_the_pipes() {
+        let start = Instant::now();
+        let run = run_command(
+            "sleep 30 & echo started; sleep 30",
+            Path::new("/"),
+            &[],
+            Duration::from_millis(300),
+        )
+        .unwrap();
+        assert!(run.timed_out);
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,84 @@
+use std::path::PathBuf;
+
+use serde_json::json;
+use tauri::State;
+
+use crate::clock;
+use crate::commands::platform::get_platform_info;
+use crate::commands::watcher::WatcherState;
+use crate::errors::IpcError;
+use crate::io::blocking;
+use crate::logging;
+use crate::metrics;
+use crate::platform::paths;
+use crate::support::{self, SupportBundleResult};
+use crate::watcher::debounce::{self, WatchMode};
+use crate::AppState;
+
+/// IPC command: writes a zip to attach to bug reports and returns its
+/// path and entries.
+///
+/// The bundle holds platform info, the lockfile state, settings and MCP
+/// config of every scope with their validation results, the newest app
+/// logs, the file watcher status, and usage metrics when they are enabled.
+/// Secrets are masked in Rust before anything is written (see
+/// `support::redact`). Written to `destination` if given, otherwise to
+/// `{configDir}/ssenrah/reports/support-{unixMillis}.zip`.
+#[tauri::command]
+pub async fn generate_support_bundle(
+    destination: Option<String>,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+    watcher_state: State<'_, WatcherState>,
+) -> Result<SupportBundleResult, IpcError> {
+    logging::async_command("generate_support_bundle", async move {
+        let project_root = state.project_root(&window);
+        let subscriptions: Vec<_> = watcher_state
+            .watchers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(|(label, watcher)| {
+                let mode = match watcher.mode() {
+                    WatchMode::Notify => "notify",
+                    WatchMode::Poll => "poll",
+                };
+                json!({ "window": label, "mode": mode })
+            })
+            .collect();
+        let watcher = json!({
+            "paused": debounce::is_paused(),
+            "subscriptions": subscriptions,
+        });
+        let usage = metrics::snapshot();
+
+        blocking::run("generate_support_bundle", move || {
+            let destination = match destination {
+                Some(destination) => PathBuf::from(destination),
+                None => {
+                    let millis = clock::now_millis();
+                    paths::resolve_app_dir()?
+                        .join("reports")
+                        .join(format!("support-{}.zip", millis))
+                }
+            };
+            let mut extra = vec![
+                (
+                    "platform.json".to_string(),
+                    json!({
+                        "appVersion": env!("CARGO_PKG_VERSION"),
+                        "arch": std::env::consts::ARCH,
+                        "platform": get_platform_info()?,
+                    }),
+                ),
+                ("watcher.json".to_string(), watcher),
+            ];
+            if usage.enabled {
+                extra.push(("metrics.json".to_string(), json!(usage)));
+            }
+            support::generate(&destination, &project_root, &logging::logs_dir()?, extra)
+        })
+        .await
+    })
+    .await
+}
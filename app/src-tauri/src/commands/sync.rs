@@ -0,0 +1,299 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::errors::IpcError;
+use crate::io::backup;
+use crate::io::copy::{collect_files, copy_path, remove_path};
+use crate::platform::paths;
+use crate::AppState;
+
+/// User-scope items kept in the dotfiles repository, relative to the config dir.
+const SYNC_ITEMS: &[&str] = &["agents", "commands", "CLAUDE.md"];
+
+/// How live items are tied to the repository copy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncMode {
+    /// Replace the live item with a link into the repository (symlink on Unix,
+    /// junction for directories on Windows; Windows files fall back to mirror).
+    Link,
+    /// Keep independent copies and copy the newer side over the older one.
+    Mirror,
+}
+
+/// Relationship between a live item and its repository copy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncState {
+    /// The live item is a link to the repository copy.
+    Linked,
+    /// The live item is a link to somewhere else; sync leaves it alone.
+    LinkedElsewhere,
+    /// Both copies exist with identical contents.
+    InSync,
+    /// Both copies exist and differ.
+    Drifted,
+    LiveOnly,
+    RepoOnly,
+    Missing,
+}
+
+/// Sync status of one item.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncItemStatus {
+    pub item: String,
+    pub live_path: String,
+    pub repo_path: String,
+    pub state: SyncState,
+    /// For drifted items, which side was modified last: "live" or "repo".
+    pub newer: Option<String>,
+}
+
+/// Result of a sync run.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncReport {
+    /// Status of every item after the sync.
+    pub items: Vec<SyncItemStatus>,
+    /// Where the live items were backed up before being touched.
+    pub backup_path: String,
+}
+
+fn resolve_repo_dir(repo_dir: &str) -> Result<PathBuf, IpcError> {
+    let repo = PathBuf::from(repo_dir);
+    if !repo.is_dir() {
+        return Err(IpcError::NotFound {
+            path: repo_dir.to_string(),
+        });
+    }
+
+    let config_dir = paths::resolve_config_dir()?;
+    if let (Ok(a), Ok(b)) = (repo.canonicalize(), config_dir.canonicalize()) {
+        if a == b {
+            return Err(IpcError::InvalidArgument {
+                argument: "repoDir".to_string(),
+                message: "The dotfiles repository cannot be the config directory itself"
+                    .to_string(),
+            });
+        }
+    }
+
+    Ok(repo)
+}
+
+/// Returns the link target if `path` is a symlink or junction.
+fn link_target(path: &Path) -> Option<PathBuf> {
+    fs::read_link(path).ok()
+}
+
+fn points_to(live: &Path, repo_item: &Path) -> bool {
+    match (live.canonicalize(), repo_item.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// Sorted relative file list of a file or directory tree.
+fn tree_files(path: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    if path.is_file() {
+        files.push(PathBuf::new());
+    } else {
+        collect_files(path, path, &mut files);
+    }
+    files.sort();
+    files
+}
+
+fn trees_equal(a: &Path, b: &Path) -> bool {
+    if a.is_dir() != b.is_dir() {
+        return false;
+    }
+    let files = tree_files(a);
+    if files != tree_files(b) {
+        return false;
+    }
+    files
+        .iter()
+        .all(|rel| match (fs::read(a.join(rel)), fs::read(b.join(rel))) {
+            (Ok(x), Ok(y)) => x == y,
+            _ => false,
+        })
+}
+
+/// Most recent modification time of any file in the tree.
+fn tree_modified(path: &Path) -> Option<SystemTime> {
+    tree_files(path)
+        .iter()
+        .filter_map(|rel| fs::metadata(path.join(rel)).and_then(|m| m.modified()).ok())
+        .max()
+}
+
+fn item_status(item: &str, config_dir: &Path, repo: &Path) -> SyncItemStatus {
+    let live = config_dir.join(item);
+    let repo_item = repo.join(item);
+
+    let mut newer = None;
+    let state = if link_target(&live).is_some() {
+        if points_to(&live, &repo_item) {
+            SyncState::Linked
+        } else {
+            SyncState::LinkedElsewhere
+        }
+    } else {
+        match (live.exists(), repo_item.exists()) {
+            (false, false) => SyncState::Missing,
+            (true, false) => SyncState::LiveOnly,
+            (false, true) => SyncState::RepoOnly,
+            (true, true) if trees_equal(&live, &repo_item) => SyncState::InSync,
+            (true, true) => {
+                let side = if tree_modified(&live) > tree_modified(&repo_item) {
+                    "live"
+                } else {
+                    "repo"
+                };
+                newer = Some(side.to_string());
+                SyncState::Drifted
+            }
+        }
+    };
+
+    SyncItemStatus {
+        item: item.to_string(),
+        live_path: live.display().to_string(),
+        repo_path: repo_item.display().to_string(),
+        state,
+        newer,
+    }
+}
+
+/// Replaces `dst` with a copy of `src`, so files deleted on one side do not
+/// linger on the other.
+fn mirror(src: &Path, dst: &Path) -> io::Result<()> {
+    remove_path(dst)?;
+    copy_path(src, dst)
+}
+
+/// Links `link` to `target`. Returns `Ok(false)` when the platform cannot
+/// link this kind of item and the caller should mirror instead.
+#[cfg(unix)]
+fn create_link(target: &Path, link: &Path) -> io::Result<bool> {
+    std::os::unix::fs::symlink(target, link).map(|_| true)
+}
+
+/// Links `link` to `target`. Returns `Ok(false)` when the platform cannot
+/// link this kind of item and the caller should mirror instead.
+///
+/// Directories use a junction, which needs no elevation; file symlinks do, so
+/// files are mirrored.
+#[cfg(windows)]
+fn create_link(target: &Path, link: &Path) -> io::Result<bool> {
+    if !target.is_dir() {
+        return Ok(false);
+    }
+    let output = std::process::Command::new("cmd")
+        .args(["/C", "mklink", "/J"])
+        .arg(link)
+        .arg(target)
+        .output()?;
+    if output.status.success() {
+        Ok(true)
+    } else {
+        Err(io::Error::other(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ))
+    }
+}
+
+fn link_or_mirror(repo_item: &Path, live: &Path, mode: SyncMode) -> io::Result<()> {
+    remove_path(live)?;
+    if mode == SyncMode::Link && create_link(repo_item, live)? {
+        return Ok(());
+    }
+    copy_path(repo_item, live)
+}
+
+fn sync_item(status: &SyncItemStatus, mode: SyncMode) -> io::Result<()> {
+    let live = Path::new(&status.live_path);
+    let repo_item = Path::new(&status.repo_path);
+
+    match status.state {
+        SyncState::Linked | SyncState::LinkedElsewhere | SyncState::Missing => Ok(()),
+        SyncState::InSync if mode == SyncMode::Mirror => Ok(()),
+        SyncState::InSync | SyncState::RepoOnly => link_or_mirror(repo_item, live, mode),
+        SyncState::LiveOnly => {
+            copy_path(live, repo_item)?;
+            if mode == SyncMode::Link {
+                link_or_mirror(repo_item, live, mode)?;
+            }
+            Ok(())
+        }
+        SyncState::Drifted => {
+            if status.newer.as_deref() == Some("live") {
+                mirror(live, repo_item)?;
+                if mode == SyncMode::Mirror {
+                    return Ok(());
+                }
+            }
+            link_or_mirror(repo_item, live, mode)
+        }
+    }
+}
+
+/// IPC command: reports how the user-scope agents, commands, and CLAUDE.md
+/// relate to their copies in a dotfiles repository directory.
+#[tauri::command]
+pub fn sync_status(repo_dir: String) -> Result<Vec<SyncItemStatus>, IpcError> {
+    let repo = resolve_repo_dir(&repo_dir)?;
+    let config_dir = paths::resolve_config_dir()?;
+
+    Ok(SYNC_ITEMS
+        .iter()
+        .map(|item| item_status(item, &config_dir, &repo))
+        .collect())
+}
+
+/// IPC command: brings the user-scope agents, commands, and CLAUDE.md in line
+/// with a dotfiles repository directory.
+///
+/// Items only present live are moved into the repository; items only in the
+/// repository are installed. When both copies differ, the more recently
+/// modified side wins. In `link` mode (the default) the live item is then
+/// replaced with a link into the repository. Live items are backed up first.
+#[tauri::command]
+pub fn sync_now(
+    repo_dir: String,
+    mode: Option<SyncMode>,
+    state: State<AppState>,
+) -> Result<SyncReport, IpcError> {
+    state.ensure_writable()?;
+    let repo = resolve_repo_dir(&repo_dir)?;
+    let mode = mode.unwrap_or(SyncMode::Link);
+    let config_dir = paths::resolve_config_dir()?;
+
+    let backup_dir = backup::backup_items("sync", &config_dir, SYNC_ITEMS)?;
+
+    for item in SYNC_ITEMS {
+        let status = item_status(item, &config_dir, &repo);
+        sync_item(&status, mode).map_err(|e| IpcError::WriteFailed {
+            path: status.live_path.clone(),
+            message: format!("Failed to sync {}: {}", item, e),
+        })?;
+    }
+
+    let items = SYNC_ITEMS
+        .iter()
+        .map(|item| item_status(item, &config_dir, &repo))
+        .collect();
+
+    Ok(SyncReport {
+        items,
+        backup_path: backup_dir.display().to_string(),
+    })
+}
@@ -0,0 +1,521 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::args;
+use crate::clock;
+use crate::errors::IpcError;
+use crate::git::run_git;
+use crate::io::atomic::atomic_write;
+use crate::io::blocking;
+use crate::io::copy::{collect_files, remove_path};
+use crate::io::journal::Transaction;
+use crate::logging;
+use crate::platform::paths;
+use crate::AppState;
+
+/// Project-scope items (relative to `.claude/`) that make up a team template.
+const TEMPLATE_ITEMS: &[&str] = &["settings.json", ".mcp.json", "agents", "skills", "commands"];
+
+/// Manifest file at the root of a template directory.
+const TEMPLATE_MANIFEST: &str = "ssenrah-template.json";
+
+/// Subdirectory of a template holding the `.claude/` contents.
+const TEMPLATE_FILES_DIR: &str = "files";
+
+/// Manifest describing a team template.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateManifest {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub created_at: u64,
+    /// Paths relative to `.claude/`, forward-slash separated.
+    #[serde(default)]
+    pub files: Vec<String>,
+}
+
+/// Result of publishing a template to a git repository.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublishTemplateResult {
+    pub manifest: TemplateManifest,
+    /// The commit pushed, or `None` if the repository already held this
+    /// template.
+    pub commit: Option<String>,
+}
+
+/// Result of applying a template to the open project.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyTemplateResult {
+    pub manifest: TemplateManifest,
+    /// Files written into `.claude/`.
+    pub written: Vec<String>,
+    /// Files left alone because they already existed.
+    pub skipped: Vec<String>,
+}
+
+fn project_claude_dir(project_root: &Option<String>) -> Result<(PathBuf, PathBuf), IpcError> {
+    let root = project_root.as_ref().ok_or_else(|| IpcError::NoProject {
+        message: "No project is open. Open a project before using team templates.".to_string(),
+    })?;
+    let root = PathBuf::from(root);
+    let claude_dir = root.join(".claude");
+    Ok((root, claude_dir))
+}
+
+/// Variables available to `{{name}}` placeholders, built from the project.
+/// User-supplied variables take precedence.
+fn template_variables(
+    project_root: &Path,
+    extra: Option<BTreeMap<String, String>>,
+) -> BTreeMap<String, String> {
+    let mut vars = BTreeMap::new();
+    vars.insert(
+        "projectName".to_string(),
+        project_root
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default(),
+    );
+    vars.insert(
+        "projectRoot".to_string(),
+        project_root.display().to_string(),
+    );
+    // Empty when there is no home directory; exports then keep such paths.
+    vars.insert(
+        "userHome".to_string(),
+        paths::home_dir()
+            .map(|home| home.display().to_string())
+            .unwrap_or_default(),
+    );
+    vars.extend(extra.unwrap_or_default());
+    vars
+}
+
+/// Replaces `{{name}}` placeholders with their values.
+fn substitute(text: &str, vars: &BTreeMap<String, String>) -> String {
+    let mut out = text.to_string();
+    for (name, value) in vars {
+        out = out.replace(&format!("{{{{{}}}}}", name), value);
+    }
+    out
+}
+
+fn write_file(path: &Path, bytes: &[u8]) -> Result<(), IpcError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| IpcError::WriteFailed {
+            path: path.display().to_string(),
+            message: format!("Failed to create parent directory: {}", e),
+        })?;
+    }
+    atomic_write(path, bytes)
+}
+
+/// IPC command: exports the open project's `.claude/` settings.json,
+/// .mcp.json, agents, skills, and commands as a template directory.
+///
+/// Absolute occurrences of the project root and home directory in text files
+/// are replaced with `{{projectRoot}}` / `{{userHome}}` so the template can
+/// be applied to other checkouts. The destination must not exist or be empty.
+#[tauri::command]
+pub fn export_project_template(
+    destination: String,
+    name: String,
+    description: Option<String>,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<TemplateManifest, IpcError> {
+    let project_root = state.project_root(&window);
+    let (root, claude_dir) = project_claude_dir(&project_root)?;
+
+    let dest = PathBuf::from(&destination);
+    if dest.exists() && fs::read_dir(&dest).map_or(true, |mut d| d.next().is_some()) {
+        return Err(IpcError::WriteFailed {
+            path: destination,
+            message: "Template destination must be an empty directory".to_string(),
+        });
+    }
+    export_template(&root, &claude_dir, &dest, name, description)
+}
+
+/// Writes the template of the project at `root` into `dest`; see
+/// `export_project_template`.
+fn export_template(
+    root: &Path,
+    claude_dir: &Path,
+    dest: &Path,
+    name: String,
+    description: Option<String>,
+) -> Result<TemplateManifest, IpcError> {
+    let vars = template_variables(root, None);
+    let mut files = Vec::new();
+    for item in TEMPLATE_ITEMS {
+        collect_files(claude_dir, &claude_dir.join(item), &mut files);
+    }
+    files.sort();
+
+    let mut manifest_files = Vec::new();
+    for rel in files {
+        let bytes = fs::read(claude_dir.join(&rel)).map_err(|e| IpcError::PlatformError {
+            message: format!("Failed to read {}: {}", rel.display(), e),
+        })?;
+
+        // Generalize machine-specific paths in text files.
+        let bytes = match String::from_utf8(bytes) {
+            Ok(text) => {
+                let mut text = text.replace(&vars["projectRoot"], "{{projectRoot}}");
+                if !vars["userHome"].is_empty() {
+                    text = text.replace(&vars["userHome"], "{{userHome}}");
+                }
+                text.into_bytes()
+            }
+            Err(e) => e.into_bytes(),
+        };
+
+        write_file(&dest.join(TEMPLATE_FILES_DIR).join(&rel), &bytes)?;
+        manifest_files.push(
+            rel.components()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+                .join("/"),
+        );
+    }
+
+    let manifest = TemplateManifest {
+        name,
+        description,
+        created_at: clock::now_secs(),
+        files: manifest_files,
+    };
+    let manifest_bytes =
+        serde_json::to_vec_pretty(&manifest).map_err(|e| IpcError::WriteFailed {
+            path: dest.display().to_string(),
+            message: format!("Failed to serialize template manifest: {}", e),
+        })?;
+    write_file(&dest.join(TEMPLATE_MANIFEST), &manifest_bytes)?;
+
+    Ok(manifest)
+}
+
+/// Returns `true` for sources that should be fetched with `git clone`:
+/// `https://`, `ssh://`, and scp-style `git@host:path` URLs.
+fn is_git_url(source: &str) -> bool {
+    source.starts_with("https://") || source.starts_with("ssh://") || source.starts_with("git@")
+}
+
+/// Returns `true` for `http://` and `git://` URLs, which are refused: anyone
+/// on the network could swap the template in transit.
+fn is_plaintext_url(source: &str) -> bool {
+    source.starts_with("http://") || source.starts_with("git://")
+}
+
+/// Shallow-clones a template repository into a temporary directory.
+fn clone_template(url: &str) -> Result<PathBuf, IpcError> {
+    let millis = clock::now_millis();
+    let dir = std::env::temp_dir().join(format!("ssenrah-template-{}", millis));
+
+    let output = Command::new("git")
+        .args(["clone", "--depth", "1", "--quiet", "--", url])
+        .arg(&dir)
+        // Fail instead of waiting for credentials no one can type.
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()
+        .map_err(|e| IpcError::PlatformError {
+            message: format!("Failed to run git: {}", e),
+        })?;
+
+    if !output.status.success() {
+        let _ = remove_path(&dir);
+        return Err(IpcError::PlatformError {
+            message: format!(
+                "git clone failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        });
+    }
+
+    Ok(dir)
+}
+
+/// IPC command: publishes the open project's template (see
+/// `export_project_template`) to the git repository `remote`, an `https://`
+/// or `ssh://` URL.
+///
+/// The repository is shallow-cloned, its default branch's files are replaced
+/// with the template, and the result is committed and pushed with the user's
+/// own git identity and credentials. Nothing is pushed if the template is
+/// unchanged.
+#[tauri::command]
+pub async fn publish_project_template(
+    remote: String,
+    name: String,
+    description: Option<String>,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<PublishTemplateResult, IpcError> {
+    logging::async_command("publish_project_template", async move {
+        let project_root = state.project_root(&window);
+        let (root, claude_dir) = project_claude_dir(&project_root)?;
+        if !is_git_url(&remote) {
+            return Err(args::invalid(
+                "remote",
+                "Use an https:// or ssh:// repository URL",
+            ));
+        }
+        blocking::run("publish_project_template", move || {
+            let dir = clone_template(&remote)?;
+            let result = publish_from_clone(&dir, &root, &claude_dir, name, description);
+            let _ = remove_path(&dir);
+            result
+        })
+        .await
+    })
+    .await
+}
+
+/// Replaces the files of the clone at `dir` with the project's template,
+/// then commits and pushes them.
+fn publish_from_clone(
+    dir: &Path,
+    root: &Path,
+    claude_dir: &Path,
+    name: String,
+    description: Option<String>,
+) -> Result<PublishTemplateResult, IpcError> {
+    let entries = fs::read_dir(dir).map_err(|e| IpcError::PlatformError {
+        message: format!("Failed to read {}: {}", dir.display(), e),
+    })?;
+    for entry in entries.flatten() {
+        if entry.file_name() != ".git" {
+            remove_path(&entry.path()).map_err(|e| IpcError::PlatformError {
+                message: format!("Failed to clear {}: {}", entry.path().display(), e),
+            })?;
+        }
+    }
+
+    let manifest = export_template(root, claude_dir, dir, name, description)?;
+    run_git(dir, &["add", "-A"], &[])?;
+    if run_git(dir, &["status", "--porcelain"], &[])?
+        .trim()
+        .is_empty()
+    {
+        return Ok(PublishTemplateResult {
+            manifest,
+            commit: None,
+        });
+    }
+    let message = format!("Update team template {}", manifest.name);
+    run_git(dir, &["commit", "--quiet", "-m", &message], &[])?;
+    run_git(dir, &["push", "--quiet", "origin", "HEAD"], &[])?;
+    let commit = run_git(dir, &["rev-parse", "HEAD"], &[])?
+        .trim()
+        .to_string();
+    Ok(PublishTemplateResult {
+        manifest,
+        commit: Some(commit),
+    })
+}
+
+/// IPC command: applies a team template to the open project's `.claude/`.
+///
+/// `source` is a local template directory or an `https://` or `ssh://` git
+/// URL (shallow-cloned into a temp dir); plaintext `http://` and `git://`
+/// URLs are refused, as is a template containing symbolic links.
+/// `{{projectName}}`, `{{projectRoot}}`, `{{userHome}}`, and any entries in
+/// `variables` are substituted in text files. Existing files are kept unless
+/// `overwrite` is true. All files are written in one journaled
+/// transaction, so a failure leaves the project untouched.
+#[tauri::command]
+pub fn apply_project_template(
+    source: String,
+    variables: Option<BTreeMap<String, String>>,
+    overwrite: Option<bool>,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<ApplyTemplateResult, IpcError> {
+    state.ensure_writable()?;
+    let project_root = state.project_root(&window);
+    let (root, claude_dir) = project_claude_dir(&project_root)?;
+    apply_template(&source, &root, &claude_dir, variables, overwrite)
+}
+
+/// Applies the template at `source` (a directory or git URL) to
+/// `claude_dir`; see `apply_project_template`.
+pub(crate) fn apply_template(
+    source: &str,
+    root: &Path,
+    claude_dir: &Path,
+    variables: Option<BTreeMap<String, String>>,
+    overwrite: Option<bool>,
+) -> Result<ApplyTemplateResult, IpcError> {
+    if is_plaintext_url(source) {
+        return Err(args::invalid(
+            "source",
+            "Plaintext http:// and git:// URLs are not allowed; use https:// or ssh://",
+        ));
+    }
+    let cloned = is_git_url(source) && !Path::new(source).exists();
+    let template_dir = if cloned {
+        clone_template(source)?
+    } else {
+        PathBuf::from(source)
+    };
+
+    let result = apply_from_dir(&template_dir, root, claude_dir, variables, overwrite);
+
+    if cloned {
+        let _ = remove_path(&template_dir);
+    }
+    result
+}
+
+fn apply_from_dir(
+    template_dir: &Path,
+    root: &Path,
+    claude_dir: &Path,
+    variables: Option<BTreeMap<String, String>>,
+    overwrite: Option<bool>,
+) -> Result<ApplyTemplateResult, IpcError> {
+    let manifest_path = template_dir.join(TEMPLATE_MANIFEST);
+    let manifest_str = manifest_path.display().to_string();
+    let manifest_text = fs::read_to_string(&manifest_path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => IpcError::NotFound {
+            path: manifest_str.clone(),
+        },
+        _ => IpcError::PlatformError {
+            message: format!("Failed to read {}: {}", manifest_str, e),
+        },
+    })?;
+    let manifest: TemplateManifest =
+        serde_json::from_str(&manifest_text).map_err(|e| IpcError::ParseError {
+            path: manifest_str.clone(),
+            message: e.to_string(),
+        })?;
+
+    let files_dir = template_dir.join(TEMPLATE_FILES_DIR);
+    let mut files = Vec::new();
+    template_files(&files_dir, &files_dir, &mut files)?;
+    files.sort();
+
+    let vars = template_variables(root, variables);
+    let overwrite = overwrite.unwrap_or(false);
+    let mut written = Vec::new();
+    let mut skipped = Vec::new();
+    let mut txn = Transaction::begin("apply-template")?;
+
+    for rel in files {
+        // Only apply the items a template may contain.
+        let top = rel
+            .components()
+            .next()
+            .map(|c| c.as_os_str().to_string_lossy().to_string())
+            .unwrap_or_default();
+        if !TEMPLATE_ITEMS.contains(&top.as_str()) {
+            continue;
+        }
+
+        let target = claude_dir.join(&rel);
+        let target_str = target.display().to_string();
+        if target.exists() && !overwrite {
+            skipped.push(target_str);
+            continue;
+        }
+
+        let bytes = fs::read(files_dir.join(&rel)).map_err(|e| IpcError::PlatformError {
+            message: format!("Failed to read template file {}: {}", rel.display(), e),
+        })?;
+        let bytes = match String::from_utf8(bytes) {
+            Ok(text) => substitute(&text, &vars).into_bytes(),
+            Err(e) => e.into_bytes(),
+        };
+
+        txn.write(&target, &bytes)?;
+        written.push(target_str);
+    }
+    txn.commit()?;
+
+    Ok(ApplyTemplateResult {
+        manifest,
+        written,
+        skipped,
+    })
+}
+
+/// Collects the files under `path`, relative to `base`. A template comes from
+/// someone else, so a symbolic link, which could point anywhere on this
+/// machine, fails the apply instead of being followed.
+fn template_files(base: &Path, path: &Path, out: &mut Vec<PathBuf>) -> Result<(), IpcError> {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return Ok(());
+    };
+    if metadata.file_type().is_symlink() {
+        return Err(args::invalid(
+            "source",
+            format!(
+                "Templates cannot contain symbolic links: {}",
+                path.display()
+            ),
+        ));
+    }
+    if metadata.is_dir() {
+        for entry in fs::read_dir(path).into_iter().flatten().flatten() {
+            template_files(base, &entry.path(), out)?;
+        }
+    } else if let Ok(rel) = path.strip_prefix(base) {
+        out.push(rel.to_path_buf());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_only_secure_git_urls() {
+        assert!(is_git_url("https://github.com/team/template.git"));
+        assert!(is_git_url("ssh://git@github.com/team/template.git"));
+        assert!(is_git_url("git@github.com:team/template.git"));
+        assert!(!is_git_url("http://example.com/template.git"));
+        assert!(!is_git_url("/home/me/template.git"));
+
+        let root = std::env::temp_dir();
+        let err = apply_template(
+            "http://example.com/template.git",
+            &root,
+            &root.join(".claude"),
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert!(
+            matches!(err, IpcError::InvalidArgument { ref argument, .. } if argument == "source")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn refuses_templates_with_symlinks() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let agents = dir.join("agents");
+        fs::create_dir_all(&agents).unwrap();
+        fs::write(agents.join("reviewer.md"), "---\nname: reviewer\n---\n").unwrap();
+
+        let mut files = Vec::new();
+        template_files(dir, dir, &mut files).unwrap();
+        assert_eq!(files, vec![PathBuf::from("agents/reviewer.md")]);
+
+        std::os::unix::fs::symlink("/etc/passwd", agents.join("passwd.md")).unwrap();
+        let err = template_files(dir, dir, &mut Vec::new()).unwrap_err();
+        assert!(matches!(err, IpcError::InvalidArgument { .. }));
+    }
+}
@@ -0,0 +1,85 @@
+use std::collections::BTreeMap;
+use std::fs;
+
+use serde_json::json;
+use tauri::State;
+
+use crate::args;
+use crate::commands::agents::agent_filename;
+use crate::commands::skills::resolve_skills_dir;
+use crate::errors::IpcError;
+use crate::io::atomic::atomic_write;
+use crate::platform::paths::resolve_agents_dir;
+use crate::templates::{self, Template};
+use crate::AppState;
+
+fn check_kind(kind: &str) -> Result<(), IpcError> {
+    args::one_of("kind", kind, &["agent", "skill"]).map(|_| ())
+}
+
+/// IPC command: lists templates for new agents or skills (`kind` is "agent"
+/// or "skill").
+///
+/// Embedded templates come first, followed by user-defined ones from
+/// `{configDir}/ssenrah/templates/agents/` and `.../skills/`.
+#[tauri::command]
+pub fn list_templates(kind: String) -> Result<Vec<Template>, IpcError> {
+    check_kind(&kind)?;
+    templates::list(&kind)
+}
+
+/// IPC command: creates an agent or skill from a template.
+///
+/// `{{name}}` in the template is replaced with `name`, and any other
+/// `{{variable}}` with the matching entry of `variables`. Agents are written
+/// to `{name}.md`, skills to `{name}/SKILL.md`. Fails if an agent or skill
+/// with that name already exists. Returns `{ path }`.
+#[tauri::command]
+pub fn create_from_template(
+    kind: String,
+    template_id: String,
+    name: String,
+    scope: String,
+    variables: Option<BTreeMap<String, String>>,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<serde_json::Value, IpcError> {
+    state.ensure_writable()?;
+    check_kind(&kind)?;
+    let project_root = state.project_root(&window);
+
+    let template = templates::find(&kind, &template_id)?.ok_or_else(|| IpcError::NotFound {
+        path: templates::user_templates_dir()
+            .map(|dir| dir.join(&template_id).display().to_string())
+            .unwrap_or_else(|_| template_id.clone()),
+    })?;
+
+    let (path, target) = if kind == "agent" {
+        let path = resolve_agents_dir(&scope, &project_root)?.join(agent_filename(&name)?);
+        (path.clone(), path)
+    } else {
+        let base_dir = resolve_skills_dir(&scope, &project_root)?;
+        let dir = base_dir.join(args::file_name("name", &name)?);
+        (dir.join("SKILL.md"), dir)
+    };
+    if target.exists() {
+        return Err(IpcError::Conflict {
+            path: target.display().to_string(),
+            message: format!("An {} with that name already exists", kind),
+        });
+    }
+
+    let mut vars = variables.unwrap_or_default();
+    vars.insert("name".to_string(), name.trim_end_matches(".md").to_string());
+    let content = templates::render(&template.content, &vars);
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| IpcError::WriteFailed {
+            path: parent.display().to_string(),
+            message: format!("Failed to create {} directory: {}", kind, e),
+        })?;
+    }
+    atomic_write(&path, content.as_bytes())?;
+
+    Ok(json!({ "path": path.display().to_string() }))
+}
@@ -0,0 +1,29 @@
+use std::path::Path;
+
+use tauri::State;
+
+use crate::audit;
+use crate::errors::IpcError;
+use crate::io::self_write;
+use crate::io::trash::{self, TrashItem};
+use crate::AppState;
+
+/// IPC command: lists deleted agents and skills that can still be restored,
+/// newest first. Items older than 30 days are purged for good.
+#[tauri::command]
+pub fn list_deleted() -> Result<Vec<TrashItem>, IpcError> {
+    trash::list()
+}
+
+/// IPC command: moves a deleted agent or skill back to where it was.
+///
+/// Fails if a file with the same name has been created there since.
+#[tauri::command]
+pub fn restore_deleted(item_id: String, state: State<AppState>) -> Result<TrashItem, IpcError> {
+    state.ensure_writable()?;
+    let item = trash::restore(&item_id)?;
+    let original = Path::new(&item.original_path);
+    self_write::tracker().register(original);
+    audit::record_rename(&trash::trash_dir()?.join(&item.id), original);
+    Ok(item)
+}
@@ -1,17 +1,23 @@
+use std::path::Path;
+
 use regex::Regex;
 use serde::Serialize;
+use tauri::State;
 
+use crate::config;
 use crate::errors::IpcError;
-use crate::types::{ConfigScope, ValidationError, ValidationWarning};
-
-/// Result of validating a settings object.
-#[derive(Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct ValidationResult {
-    pub valid: bool,
-    pub errors: Vec<ValidationError>,
-    pub warnings: Vec<ValidationWarning>,
-}
+use crate::frontmatter::parse_frontmatter;
+use crate::platform::paths::resolve_agents_dir;
+use crate::schema::agent;
+use crate::schema::docs::{self, SettingDoc};
+use crate::schema::markdown::{self, MarkdownKind};
+use crate::schema::output_style;
+use crate::schema::plugin;
+use crate::schema::skill;
+use crate::schema::tools::ToolInfo;
+use crate::types::ConfigScope;
+use crate::validation::{self, ValidationResult};
+use crate::AppState;
 
 /// Result of validating a permission rule string.
 #[derive(Serialize)]
@@ -33,32 +39,22 @@ pub struct HookMatcherResult {
 
 /// IPC command: validates a settings JSON object for a given scope.
 ///
-/// Currently performs basic structural checks. Returns valid: true with
-/// empty errors/warnings. Full validation will be added later.
+/// Checks that the value is an object and that every known field matches the
+/// typed settings model. Unknown keys are accepted. When the installed Claude
+/// Code version is known, keys introduced in a later release produce an
+/// `UNSUPPORTED_BY_CLI` warning, since the CLI would silently ignore them.
+/// `permissions.additionalDirectories` entries must be existing directories
+/// that are not the filesystem root, the home directory, or a system
+/// directory; relative entries resolve against the window's project.
 #[tauri::command]
 pub fn validate_settings(
     settings: serde_json::Value,
     _scope: ConfigScope,
+    window: tauri::Window,
+    state: State<AppState>,
 ) -> Result<ValidationResult, IpcError> {
-    // Basic structural check: settings must be an object
-    if !settings.is_object() {
-        return Ok(ValidationResult {
-            valid: false,
-            errors: vec![ValidationError {
-                path: "".to_string(),
-                message: "Settings must be a JSON object".to_string(),
-                code: "INVALID_TYPE".to_string(),
-            }],
-            warnings: vec![],
-        });
-    }
-
-    // Placeholder: full validation comes later
-    Ok(ValidationResult {
-        valid: true,
-        errors: vec![],
-        warnings: vec![],
-    })
+    let project_root = state.project_root(&window);
+    Ok(validation::validate_settings(&settings, &project_root))
 }
 
 /// IPC command: validates a permission rule string.
@@ -94,6 +90,21 @@ pub fn validate_permission_rule(rule: String) -> Result<PermissionRuleResult, Ip
     }
 }
 
+/// IPC command: lists the tools that permission rules, hook matchers, and
+/// agent `tools` can name.
+///
+/// Built-in tools come with the grammar of their rule specifier. MCP tools
+/// are `mcp__<server>` for every configured server, plus any
+/// `mcp__<server>__<tool>` already named in the effective permission rules.
+#[tauri::command]
+pub fn list_known_tools(
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<Vec<ToolInfo>, IpcError> {
+    let project_root = state.project_root(&window);
+    config::list_known_tools(&project_root)
+}
+
 /// IPC command: validates a hook matcher pattern by compiling it as a regex.
 #[tauri::command]
 pub fn validate_hook_matcher(pattern: String) -> Result<HookMatcherResult, IpcError> {
@@ -108,3 +119,160 @@ pub fn validate_hook_matcher(pattern: String) -> Result<HookMatcherResult, IpcEr
         }),
     }
 }
+
+/// IPC command: lints a memory, agent, or skill markdown file.
+///
+/// `kind` is "memory", "agent", or "skill". Reports binary content, missing
+/// required frontmatter keys, oversized files, duplicate headings, and — when
+/// `path` (the file's location) is given — relative links that do not resolve.
+#[tauri::command]
+pub fn lint_markdown(
+    content: String,
+    kind: String,
+    path: Option<String>,
+) -> Result<ValidationResult, IpcError> {
+    let kind = MarkdownKind::parse(&kind).ok_or_else(|| IpcError::InvalidArgument {
+        argument: "kind".to_string(),
+        message: format!("Unknown markdown kind: {}", kind),
+    })?;
+
+    let (frontmatter, body) = parse_frontmatter(&content);
+    let base_dir = path.as_deref().and_then(|p| Path::new(p).parent());
+    let report = markdown::lint(kind, &content, &frontmatter, &body, base_dir);
+
+    Ok(ValidationResult {
+        valid: report.errors.is_empty(),
+        errors: report.errors,
+        warnings: report.warnings,
+    })
+}
+
+/// IPC command: validates an agent's frontmatter and body before it is saved.
+///
+/// Enforces the agent file contract (required name and description, known
+/// tools, recognized model alias). When another agent in either scope already
+/// uses the same `name`, a warning is added; `scope` and `filename` identify
+/// the file being edited so it is not reported against itself.
+#[tauri::command]
+pub fn validate_agent(
+    frontmatter: serde_json::Value,
+    body: String,
+    scope: Option<String>,
+    filename: Option<String>,
+    window: tauri::Window,
+    state: State<AppState>,
+) -> Result<ValidationResult, IpcError> {
+    let project_root = state.project_root(&window);
+
+    let mut report = agent::validate_agent(&frontmatter, &body);
+
+    if let Some(name) = frontmatter.get("name").and_then(|v| v.as_str()) {
+        for s in ["user", "project"] {
+            let Ok(dir) = resolve_agents_dir(s, &project_root) else {
+                continue;
+            };
+            let Ok(entries) = std::fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let other = entry.file_name().to_string_lossy().to_string();
+                if !other.ends_with(".md")
+                    || (scope.as_deref() == Some(s) && filename.as_deref() == Some(other.as_str()))
+                {
+                    continue;
+                }
+                let content = std::fs::read_to_string(entry.path()).unwrap_or_default();
+                let (other_fm, _) = parse_frontmatter(&content);
+                if other_fm.get("name").and_then(|v| v.as_str()) == Some(name) {
+                    report.warning(
+                        "frontmatter.name".to_string(),
+                        format!(
+                            "Agent name '{}' is also used by {} agent {}",
+                            name, s, other
+                        ),
+                        "NAME_COLLISION",
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(ValidationResult {
+        valid: report.errors.is_empty(),
+        errors: report.errors,
+        warnings: report.warnings,
+    })
+}
+
+/// IPC command: validates a skill's frontmatter and body before it is saved.
+///
+/// Checks the description, `allowed-tools` rule grammar, `argument-hint`
+/// format, and that body placeholders agree with the declared hint.
+#[tauri::command]
+pub fn validate_skill(
+    frontmatter: serde_json::Value,
+    body: String,
+) -> Result<ValidationResult, IpcError> {
+    let report = skill::validate_skill(&frontmatter, &body);
+
+    Ok(ValidationResult {
+        valid: report.errors.is_empty(),
+        errors: report.errors,
+        warnings: report.warnings,
+    })
+}
+
+/// IPC command: validates an output style file's frontmatter and body.
+///
+/// Checks field types, warns about a missing description or a name that
+/// shadows a built-in style, and rejects an empty body.
+#[tauri::command]
+pub fn validate_output_style(
+    frontmatter: serde_json::Value,
+    body: String,
+) -> Result<ValidationResult, IpcError> {
+    let report = output_style::validate_output_style(&frontmatter, &body);
+
+    Ok(ValidationResult {
+        valid: report.errors.is_empty(),
+        errors: report.errors,
+        warnings: report.warnings,
+    })
+}
+
+/// IPC command: validates spinner settings (`spinnerTipsEnabled`,
+/// `spinnerVerbs`, `spinnerTipsOverride`) before they are saved.
+#[tauri::command]
+pub fn validate_spinner_settings(spinner: serde_json::Value) -> Result<ValidationResult, IpcError> {
+    let report = output_style::validate_spinner(&spinner);
+
+    Ok(ValidationResult {
+        valid: report.errors.is_empty(),
+        errors: report.errors,
+        warnings: report.warnings,
+    })
+}
+
+/// IPC command: validates a plugin manifest (`.claude-plugin/plugin.json`).
+///
+/// Requires a kebab-case `name`, checks that `version` is semver, and that
+/// component paths are `./`-relative to the plugin root.
+#[tauri::command]
+pub fn validate_plugin_manifest(manifest: serde_json::Value) -> Result<ValidationResult, IpcError> {
+    let report = plugin::validate_manifest(&manifest);
+
+    Ok(ValidationResult {
+        valid: report.errors.is_empty(),
+        errors: report.errors,
+        warnings: report.warnings,
+    })
+}
+
+/// IPC command: returns inline help for a settings key and the keys nested
+/// under it (description, type, default, allowed values, example, and the
+/// minimum CLI version where relevant). Returns every documented key when
+/// `key_path` is omitted.
+#[tauri::command]
+pub fn get_setting_docs(key_path: Option<String>) -> Result<Vec<SettingDoc>, IpcError> {
+    Ok(docs::lookup(key_path.as_deref().unwrap_or("")))
+}
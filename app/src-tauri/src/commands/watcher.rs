@@ -1,62 +1,163 @@
+use crate::args;
 use crate::errors::IpcError;
-use crate::platform::paths;
-use crate::watcher::debounce::DebouncedWatcher;
+use crate::platform::{detect, paths};
+use crate::watcher::debounce::{
+    DebouncedWatcher, WatchFilter, WatchMode, WATCH_KINDS, WATCH_MODES, WATCH_SCOPES,
+};
+use crate::watcher::scope::ScopeRoots;
 use crate::AppState;
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Mutex;
-use tauri::State;
+use tauri::{Manager, State};
 
+/// Active watchers, keyed by the label of the window that subscribed.
 pub struct WatcherState {
-    pub watcher: Mutex<Option<DebouncedWatcher>>,
+    pub watchers: Mutex<HashMap<String, DebouncedWatcher>>,
 }
 
+/// IPC command: starts watching config files and emitting
+/// `file_changes_batch` events.
+///
+/// `scopes` ("user", "project", "local", "managed") and `kinds` ("settings",
+/// "mcp", "agents", "skills", "memory") narrow the subscription; omitting
+/// either subscribes to everything. Changes are coalesced into at most one
+/// event per interval listing each changed path once; `overflow` is set when
+/// too many paths changed to list. Each change carries a `fileKind` tag so
+/// the frontend can refresh only the affected panel. With `include_content`,
+/// events for known files also carry a `contentHash` and the re-parsed
+/// `content` (size-limited) so simple refreshes need no extra round trip.
+///
+/// `mode` picks how changes are detected: "notify" uses OS notifications,
+/// "poll" rescans the watched files every few seconds comparing size,
+/// mtime, and a content hash, and "auto" (the default) polls only when a
+/// watched path is on a network filesystem or a WSL-mounted Windows drive,
+/// where notifications are unreliable.
+/// Each window has its own subscription covering the project opened in it,
+/// and events go only to that window. Calling this again replaces the
+/// window's previous subscription.
 #[tauri::command]
 pub fn subscribe_file_changes(
-    app: tauri::AppHandle,
+    scopes: Option<Vec<String>>,
+    kinds: Option<Vec<String>>,
+    include_content: Option<bool>,
+    mode: Option<String>,
+    window: tauri::Window,
     state: State<AppState>,
     watcher_state: State<WatcherState>,
 ) -> Result<(), IpcError> {
-    let mut watcher_guard = watcher_state.watcher.lock().unwrap();
+    check_known("scope", &scopes, WATCH_SCOPES)?;
+    check_known("kind", &kinds, WATCH_KINDS)?;
+    let mode = mode.unwrap_or_else(|| "auto".to_string());
+    check_known("mode", &Some(vec![mode.clone()]), WATCH_MODES)?;
+    let filter = WatchFilter { scopes, kinds };
 
-    let mut watcher = DebouncedWatcher::new(app).map_err(|e| IpcError::PlatformError {
-        message: format!("Failed to create file watcher: {}", e),
-    })?;
+    let mut watchers = watcher_state
+        .watchers
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let project_root = state.project_root(&window);
+
+    // Without a config directory only the project and managed scopes are
+    // watched.
+    let config_dir = paths::resolve_config_dir().ok();
+    let roots = ScopeRoots::new(
+        config_dir.clone(),
+        paths::home_dir().ok().map(|home| home.join(".claude.json")),
+        project_root.as_ref().map(std::path::PathBuf::from),
+        paths::resolve_managed_settings_dir(),
+    );
 
-    // Watch user config dir
-    let config_dir = paths::resolve_config_dir();
-    if config_dir.exists() {
-        let _ = watcher.watch(&config_dir);
+    // (path, recursive) pairs to watch.
+    let mut targets: Vec<(PathBuf, bool)> = Vec::new();
+
+    // Watch user config dir. The directory (not settings.json itself) is
+    // watched so that atomic temp-file + rename replacements of
+    // settings.json and CLAUDE.md are still reported.
+    if let Some(config_dir) = config_dir.filter(|d| filter.wants_scope("user") && d.exists()) {
+        targets.push((config_dir, false));
+    }
+
+    // Watch the managed-settings dir so policy pushed by MDM shows up without
+    // a restart. Skipped when the directory is absent or not readable.
+    if let Some(managed_dir) = paths::resolve_managed_settings_dir() {
+        if filter.wants_scope("managed") && std::fs::read_dir(&managed_dir).is_ok() {
+            targets.push((managed_dir, false));
+        }
     }
 
     // Watch user .claude.json
-    let home = paths::home_dir();
-    let claude_json = home.join(".claude.json");
-    if claude_json.exists() {
-        let _ = watcher.watch(&claude_json);
+    if let Ok(home) = paths::home_dir() {
+        let claude_json = home.join(".claude.json");
+        if filter.wants_scope("user") && claude_json.exists() {
+            targets.push((claude_json, false));
+        }
     }
 
     // Watch project paths if project is open
-    let project_root = state.project_root.lock().unwrap();
-    if let Some(ref root) = *project_root {
+    let wants_project = filter.wants_scope("project") || filter.wants_scope("local");
+    if let Some(root) = project_root.as_ref().filter(|_| wants_project) {
         let claude_dir = std::path::Path::new(root).join(".claude");
         if claude_dir.exists() {
-            let _ = watcher.watch_recursive(&claude_dir);
+            targets.push((claude_dir, true));
         }
         // Watch project root CLAUDE.md
         let project_claude_md = std::path::Path::new(root).join("CLAUDE.md");
         if project_claude_md.exists() {
-            let _ = watcher.watch(&project_claude_md);
+            targets.push((project_claude_md, false));
         }
     }
 
-    *watcher_guard = Some(watcher);
+    let watch_mode = match mode.as_str() {
+        "poll" => WatchMode::Poll,
+        "notify" => WatchMode::Notify,
+        _ if targets.iter().any(|(path, _)| detect::is_network_fs(path)) => WatchMode::Poll,
+        _ => WatchMode::Notify,
+    };
+    let mut watcher = DebouncedWatcher::new(
+        window.app_handle().clone(),
+        window.label().to_string(),
+        roots,
+        filter,
+        include_content.unwrap_or(false),
+        watch_mode,
+    )
+    .map_err(|e| IpcError::PlatformError {
+        message: format!("Failed to create file watcher: {}", e),
+    })?;
+    for (path, recursive) in &targets {
+        let _ = if *recursive {
+            watcher.watch_recursive(path)
+        } else {
+            watcher.watch(path)
+        };
+    }
+
+    watchers.insert(window.label().to_string(), watcher);
     Ok(())
 }
 
 #[tauri::command]
 pub fn unsubscribe_file_changes(
+    window: tauri::Window,
     watcher_state: State<WatcherState>,
 ) -> Result<(), IpcError> {
-    let mut watcher_guard = watcher_state.watcher.lock().unwrap();
-    *watcher_guard = None; // Drop watcher, stops watching
+    let mut watchers = watcher_state
+        .watchers
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    watchers.remove(window.label()); // Drop watcher, stops watching
+    Ok(())
+}
+
+/// Rejects filter values the watcher does not know how to classify.
+fn check_known(
+    what: &str,
+    values: &Option<Vec<String>>,
+    known: &[&str],
+) -> Result<(), IpcError> {
+    for value in values.iter().flatten() {
+        args::one_of(what, value, known)?;
+    }
     Ok(())
 }
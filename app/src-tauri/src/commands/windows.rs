@@ -0,0 +1,161 @@
+use std::path::Path;
+
+use serde::Serialize;
+use tauri::{Manager, PhysicalPosition, PhysicalSize, WebviewUrl, WebviewWindowBuilder};
+
+use crate::audit;
+use crate::commands::watcher::WatcherState;
+use crate::errors::IpcError;
+use crate::onboarding::{self, PersistedState};
+use crate::tray;
+use crate::window_state::{self, WindowGeometry};
+use crate::AppState;
+
+/// A window opened by `open_project_window`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectWindow {
+    /// Window label, `project-{hash}`; one window per project.
+    pub label: String,
+    pub project_root: String,
+}
+
+/// Remembers `window`'s size and position under the project open in it.
+pub(crate) fn save_geometry(window: &tauri::Window) {
+    let (Ok(position), Ok(size), Ok(maximized)) = (
+        window.outer_position(),
+        window.inner_size(),
+        window.is_maximized(),
+    ) else {
+        return;
+    };
+    let geometry = WindowGeometry {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+        maximized,
+    };
+    let key =
+        window_state::geometry_key(window.state::<AppState>().project_root(window).as_deref());
+    if let Err(e) = onboarding::update(|s| {
+        s.window_geometry.insert(key, geometry);
+    }) {
+        tracing::warn!(error = %e, "failed to save window geometry");
+    }
+}
+
+/// Moves and resizes `window` to the geometry last saved for `project_root`.
+/// Geometry that would put the title bar off every monitor is ignored.
+pub(crate) fn restore_geometry(window: &tauri::WebviewWindow, project_root: Option<&str>) {
+    let Ok(path) = onboarding::state_path() else {
+        return;
+    };
+    let state = PersistedState::load_from(&path);
+    let Some(geometry) = state
+        .window_geometry
+        .get(&window_state::geometry_key(project_root))
+    else {
+        return;
+    };
+    let monitors: Vec<_> = window
+        .available_monitors()
+        .unwrap_or_default()
+        .iter()
+        .map(|m| {
+            let (position, size) = (m.position(), m.size());
+            (position.x, position.y, size.width, size.height)
+        })
+        .collect();
+    if !window_state::is_reachable(geometry, &monitors) {
+        return;
+    }
+    let _ = window.set_size(PhysicalSize::new(geometry.width, geometry.height));
+    let _ = window.set_position(PhysicalPosition::new(geometry.x, geometry.y));
+    if geometry.maximized {
+        let _ = window.maximize();
+    }
+}
+
+/// Drops the project binding and watcher of a closed window.
+pub(crate) fn forget_window(window: &tauri::Window) {
+    forget_label(
+        &window.state::<AppState>(),
+        &window.state::<WatcherState>(),
+        window.label(),
+    );
+}
+
+/// Drops the project binding and watcher of the window labelled `label`.
+pub(crate) fn forget_label(state: &AppState, watcher_state: &WatcherState, label: &str) {
+    state.set_project_root(label, None);
+    watcher_state
+        .watchers
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .remove(label);
+}
+
+/// Opens or focuses the window for `path`; see `open_project_window`.
+pub(crate) fn open_window(app: &tauri::AppHandle, path: String) -> Result<ProjectWindow, IpcError> {
+    let state = app.state::<AppState>();
+    let root = Path::new(&path);
+    if !root.is_dir() {
+        return Err(IpcError::NotFound { path });
+    }
+
+    let label = format!("project-{}", &audit::hash(path.as_bytes())[..12]);
+    if let Some(existing) = app.get_webview_window(&label) {
+        let _ = existing.unminimize();
+        let _ = existing.set_focus();
+        return Ok(ProjectWindow {
+            label,
+            project_root: path,
+        });
+    }
+
+    // Bound before the webview loads so its first `get_project_info` sees it.
+    state.set_project_root(&label, Some(path.clone()));
+    let name = root
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.clone());
+    let window = WebviewWindowBuilder::new(app, &label, WebviewUrl::App("index.html".into()))
+        .title(format!("ssenrah — {}", name))
+        .inner_size(1200.0, 800.0)
+        .min_inner_size(900.0, 600.0)
+        .build()
+        .map_err(|e| {
+            state.set_project_root(&label, None);
+            IpcError::PlatformError {
+                message: format!("Failed to open a window for {}: {}", path, e),
+            }
+        })?;
+    restore_geometry(&window, Some(&path));
+
+    if let Err(e) = onboarding::update(|s| s.record_project(&path)) {
+        tracing::warn!(error = %e, "failed to record opened project");
+    }
+    tray::refresh(app);
+
+    Ok(ProjectWindow {
+        label,
+        project_root: path,
+    })
+}
+
+/// IPC command: opens `path` in an additional window so two projects can be
+/// compared side by side.
+///
+/// The new window is bound to the project: every command invoked from it
+/// resolves the project scope against `path`, and its file watcher reports
+/// only to it. Opening a project that already has a window focuses that
+/// window instead. Size and position are restored from the last time the
+/// project's window was closed.
+#[tauri::command]
+pub fn open_project_window(
+    path: String,
+    app: tauri::AppHandle,
+) -> Result<ProjectWindow, IpcError> {
+    open_window(&app, path)
+}
@@ -0,0 +1,164 @@
+use std::path::Path;
+
+use percent_encoding::percent_decode_str;
+use serde::Serialize;
+use url::Url;
+
+/// URL scheme registered for deep links.
+pub const SCHEME: &str = "ssenrah";
+
+/// Artifact kinds a deep link can point at, and whether they take a name.
+const KINDS: &[(&str, bool)] = &[
+    ("agent", true),
+    ("skill", true),
+    ("output-style", true),
+    ("settings", false),
+    ("mcp", false),
+    ("memory", false),
+];
+
+const SCOPES: &[&str] = &["user", "project", "local"];
+
+/// The config artifact a deep link asks the UI to show.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactLink {
+    pub kind: String,
+    pub scope: String,
+    /// File name for agents, skills and output styles.
+    pub name: Option<String>,
+}
+
+/// A parsed `ssenrah://` URL.
+///
+/// `ssenrah://open?project=/path` opens a project.
+/// `ssenrah://agent/user/reviewer.md` shows an artifact; adding
+/// `?project=/path` opens that project first and shows it there.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeepLink {
+    pub project: Option<String>,
+    pub artifact: Option<ArtifactLink>,
+}
+
+fn decode(segment: &str) -> Result<String, String> {
+    percent_decode_str(segment)
+        .decode_utf8()
+        .map(|s| s.into_owned())
+        .map_err(|_| format!("'{}' is not valid UTF-8", segment))
+}
+
+/// Names are single file names: anything that could walk out of the scope
+/// directory is rejected here rather than trusted to the UI.
+fn check_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\', '\0']) {
+        return Err(format!("'{}' is not a valid name", name));
+    }
+    Ok(())
+}
+
+/// Parses a deep link. Links only select what to show; nothing is written,
+/// so a link from an untrusted page can at most open a window.
+pub fn parse(url: &Url) -> Result<DeepLink, String> {
+    if url.scheme() != SCHEME {
+        return Err(format!("Unsupported scheme '{}'", url.scheme()));
+    }
+    let project = url
+        .query_pairs()
+        .find(|(key, _)| key == "project")
+        .map(|(_, value)| value.into_owned());
+    if let Some(project) = &project {
+        if !Path::new(project).is_absolute() {
+            return Err(format!("Project path '{}' must be absolute", project));
+        }
+    }
+
+    let action = url.host_str().unwrap_or_default();
+    let segments: Vec<String> = url
+        .path_segments()
+        .into_iter()
+        .flatten()
+        .filter(|s| !s.is_empty())
+        .map(decode)
+        .collect::<Result<_, _>>()?;
+
+    if action == "open" {
+        if project.is_none() || !segments.is_empty() {
+            return Err("Expected ssenrah://open?project=/path".to_string());
+        }
+        return Ok(DeepLink {
+            project,
+            artifact: None,
+        });
+    }
+
+    let Some(&(kind, named)) = KINDS.iter().find(|(k, _)| *k == action) else {
+        return Err(format!("Unknown deep link target '{}'", action));
+    };
+    let (scope, name) = match (segments.as_slice(), named) {
+        ([scope, name], true) => (scope, Some(name.clone())),
+        ([scope], false) => (scope, None),
+        _ if named => {
+            return Err(format!("Expected ssenrah://{}/{{scope}}/{{name}}", kind));
+        }
+        _ => return Err(format!("Expected ssenrah://{}/{{scope}}", kind)),
+    };
+    if !SCOPES.contains(&scope.as_str()) {
+        return Err(format!("Unknown scope '{}'", scope));
+    }
+    if let Some(name) = &name {
+        check_name(name)?;
+    }
+    Ok(DeepLink {
+        project,
+        artifact: Some(ArtifactLink {
+            kind: kind.to_string(),
+            scope: scope.clone(),
+            name,
+        }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_str(s: &str) -> Result<DeepLink, String> {
+        parse(&Url::parse(s).unwrap())
+    }
+
+    #[test]
+    fn parses_open_and_artifact_links() {
+        let open = parse_str("ssenrah://open?project=/home/me/app").unwrap();
+        assert_eq!(open.project.as_deref(), Some("/home/me/app"));
+        assert!(open.artifact.is_none());
+
+        let agent = parse_str("ssenrah://agent/user/code%20reviewer.md").unwrap();
+        assert!(agent.project.is_none());
+        assert_eq!(
+            agent.artifact,
+            Some(ArtifactLink {
+                kind: "agent".to_string(),
+                scope: "user".to_string(),
+                name: Some("code reviewer.md".to_string()),
+            })
+        );
+
+        let settings = parse_str("ssenrah://settings/local?project=%2Fsrv%2Fapi").unwrap();
+        assert_eq!(settings.project.as_deref(), Some("/srv/api"));
+        assert_eq!(settings.artifact.unwrap().name, None);
+    }
+
+    #[test]
+    fn rejects_malformed_links() {
+        assert!(parse_str("https://agent/user/a.md").is_err());
+        assert!(parse_str("ssenrah://open").is_err());
+        assert!(parse_str("ssenrah://open?project=relative/dir").is_err());
+        assert!(parse_str("ssenrah://hooks/user").is_err());
+        assert!(parse_str("ssenrah://agent/managed/a.md").is_err());
+        assert!(parse_str("ssenrah://agent/user").is_err());
+        assert!(parse_str("ssenrah://agent/user/..").is_err());
+        assert!(parse_str("ssenrah://skill/user/a%2F..%2Fb").is_err());
+        assert!(parse_str("ssenrah://memory/user/extra").is_err());
+    }
+}
@@ -0,0 +1,43 @@
+use std::time::{Duration, Instant};
+
+use crate::audit;
+use crate::errors::IpcError;
+
+/// Longest a filesystem-heavy command may run before it fails with
+/// `IpcError::Timeout`.
+pub const IO_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Runs `work` on the blocking thread pool and waits at most `IO_TIMEOUT`.
+///
+/// Async commands use this for their file IO so a slow or hung filesystem
+/// (e.g. a network-mounted home directory) ties up a pool thread instead of
+/// the IPC threads. A timed-out `work` cannot be cancelled and runs to
+/// completion in the background; only the command's answer is given up.
+/// `command` names the operation in the timeout error, the log, and the
+/// audit trail.
+pub async fn run<T, F>(command: &'static str, work: F) -> Result<T, IpcError>
+where
+    F: FnOnce() -> Result<T, IpcError> + Send + 'static,
+    T: Send + 'static,
+{
+    let start = Instant::now();
+    let task = tauri::async_runtime::spawn_blocking(move || audit::with_command(command, work));
+    let result = match tokio::time::timeout(IO_TIMEOUT, task).await {
+        Ok(Ok(result)) => result,
+        Ok(Err(e)) => Err(IpcError::PlatformError {
+            message: format!("{} failed: {}", command, e),
+        }),
+        Err(_) => Err(IpcError::Timeout {
+            operation: command.to_string(),
+            seconds: IO_TIMEOUT.as_secs(),
+        }),
+    };
+    crate::metrics::record_io(command, start.elapsed(), result.as_ref().err());
+    tracing::debug!(
+        command,
+        duration_ms = start.elapsed().as_secs_f64() * 1000.0,
+        ok = result.is_ok(),
+        "blocking io"
+    );
+    result
+}
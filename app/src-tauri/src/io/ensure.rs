@@ -5,8 +5,9 @@ use std::path::Path;
 use tauri::State;
 
 #[tauri::command]
-pub fn ensure_claude_dir(state: State<AppState>) -> Result<(), IpcError> {
-    let project_root = state.project_root.lock().unwrap();
+pub fn ensure_claude_dir(window: tauri::Window, state: State<AppState>) -> Result<(), IpcError> {
+    state.ensure_writable()?;
+    let project_root = state.project_root(&window);
     let root = project_root.as_ref().ok_or_else(|| IpcError::NoProject {
         message: "No project open".to_string(),
     })?;
@@ -1,2 +1,6 @@
-pub mod atomic;
+//! Tauri-side IO helpers, alongside the shared ones from `ssenrah_core::io`.
+
+pub use ssenrah_core::io::*;
+
+pub mod blocking;
 pub mod ensure;
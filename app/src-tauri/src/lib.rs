@@ -1,20 +1,74 @@
 mod commands;
-mod errors;
+mod deep_link;
 mod io;
-mod lockfile;
-mod platform;
-mod schema;
-mod types;
+mod logging;
+mod mdm;
+mod metrics;
+mod onboarding;
+mod preferences;
+mod registry;
+mod search;
+mod statusline;
+mod templates;
+mod tray;
 mod watcher;
+mod window_state;
 
+use ssenrah_core::{
+    args, audit, bundle, claude_json, clock, config, doctor, drift, errors, fixes, frontmatter,
+    git, lockfile, memory, packages, platform, policy, query, rule_suggestions, scaffold, schema,
+    sessions, share, similarity, support, types, validation,
+};
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
 
+use tauri::Manager;
+use tauri_plugin_deep_link::DeepLinkExt;
+
 pub use errors::IpcError;
 pub use types::*;
 
 /// Managed application state shared across IPC commands.
 pub struct AppState {
-    pub project_root: Mutex<Option<String>>,
+    /// Project opened in each window, keyed by window label. Read through
+    /// `project_root`, which clones the path so no lock is held while a
+    /// command runs.
+    pub projects: Mutex<HashMap<String, String>>,
+    /// When set, every command that changes files fails with
+    /// `IpcError::ReadOnly`.
+    pub read_only: AtomicBool,
+}
+
+impl AppState {
+    /// The project opened in `window`, if any.
+    pub fn project_root(&self, window: &tauri::Window) -> Option<String> {
+        self.projects
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(window.label())
+            .cloned()
+    }
+
+    /// Binds `root` to the window labelled `label`, or unbinds it.
+    pub fn set_project_root(&self, label: &str, root: Option<String>) {
+        let mut projects = self.projects.lock().unwrap_or_else(|e| e.into_inner());
+        match root {
+            Some(root) => projects.insert(label.to_string(), root),
+            None => projects.remove(label),
+        };
+    }
+
+    /// Called first by every mutating command.
+    pub fn ensure_writable(&self) -> Result<(), IpcError> {
+        if self.read_only.load(Ordering::SeqCst) {
+            return Err(IpcError::ReadOnly {
+                message: "ssenrah is in read-only mode. Turn it off to make changes.".to_string(),
+            });
+        }
+        Ok(())
+    }
 }
 
 /// Entry point called from main.rs.
@@ -22,48 +76,246 @@ pub struct AppState {
 /// Builds the Tauri application with plugins and IPC command handlers.
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // Decided before logging starts, since portable logs live beside the exe.
+    let portable_root = platform::portable::init(std::env::args());
+    logging::init();
+    if let Some(root) = portable_root {
+        tracing::info!(root = %root.display(), "running in portable mode");
+    }
+
     tauri::Builder::default()
+        // Must come first: a second launch hands its arguments, including
+        // any deep link, to this instance and exits.
+        .plugin(tauri_plugin_single_instance::init(|app, _argv, _cwd| {
+            if let Some(main) = app.get_webview_window("main") {
+                let _ = main.show();
+                let _ = main.set_focus();
+            }
+        }))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
+        .plugin(tauri_plugin_notification::init())
         .manage(AppState {
-            project_root: Mutex::new(None),
+            projects: Mutex::new(HashMap::new()),
+            read_only: AtomicBool::new(false),
+        })
+        .manage(commands::deep_link::DeepLinkState {
+            pending: Mutex::new(HashMap::new()),
         })
         .manage(commands::watcher::WatcherState {
-            watcher: Mutex::new(None),
+            watchers: Mutex::new(HashMap::new()),
         })
-        .invoke_handler(tauri::generate_handler![
+        .invoke_handler(logging::instrument(tauri::generate_handler![
             commands::platform::get_platform_info,
+            commands::platform::set_config_dir_override,
+            commands::platform::set_home_override,
+            commands::platform::get_claude_code_info,
+            commands::platform::suggest_proxy_env_settings,
+            commands::windows::open_project_window,
+            commands::deep_link::take_pending_deep_link,
+            commands::preferences::read_app_preferences,
+            commands::preferences::write_app_preferences,
+            commands::onboarding::get_onboarding_status,
+            commands::onboarding::complete_onboarding_step,
+            commands::auth::get_auth_status,
+            commands::auth::read_api_key_helper_script,
+            commands::auth::write_api_key_helper_script,
+            commands::auth::test_api_key_helper,
+            commands::plugins::list_plugins,
+            commands::plugins::set_plugin_enabled,
+            commands::plugins::inspect_plugin,
+            commands::capabilities::get_capabilities,
+            commands::capabilities::set_read_only,
+            commands::doctor::run_doctor,
+            commands::fixes::apply_fix,
             commands::project::get_project_info,
             commands::project::open_project,
+            commands::project::reset_app_state,
+            commands::project::list_nested_claude_dirs,
             commands::settings::read_settings,
+            commands::settings::read_settings_format,
             commands::settings::write_settings,
+            commands::settings::merge_settings_conflict,
+            commands::settings::migrate_settings,
+            commands::settings::read_spinner_settings,
+            commands::settings::write_spinner_settings,
+            commands::share::export_artifact,
+            commands::share::import_artifact,
+            commands::share::find_duplicate_artifacts,
             commands::validation::validate_settings,
             commands::validation::validate_permission_rule,
+            commands::validation::list_known_tools,
             commands::validation::validate_hook_matcher,
+            commands::validation::lint_markdown,
+            commands::validation::validate_agent,
+            commands::validation::validate_skill,
+            commands::validation::validate_plugin_manifest,
+            commands::validation::validate_output_style,
+            commands::validation::validate_spinner_settings,
+            commands::validation::get_setting_docs,
             commands::mcp::read_mcp_config,
             commands::mcp::write_mcp_config,
             commands::mcp::read_managed_mcp,
+            commands::claude_json::read_claude_json,
+            commands::claude_json::update_claude_json_project,
+            commands::claude_json::remove_claude_json_project,
+            commands::claude_json::get_project_trust,
+            commands::claude_json::set_project_trust,
             commands::managed::read_managed_settings,
+            commands::managed::check_policy_compliance,
+            commands::managed::read_settings_policy,
+            commands::managed::check_settings_against_policy,
+            commands::sandbox::analyze_sandbox_config,
+            commands::scaffold::initialize_claude_config,
+            commands::scaffold::scaffold_project_claude,
+            commands::managed::write_managed_settings,
+            commands::managed::generate_managed_profile,
+            commands::files::stat_config_file,
+            commands::bulk::bulk_delete_agents,
+            commands::bulk::bulk_copy_skills,
+            commands::bulk::bulk_update_frontmatter,
+            commands::trash::list_deleted,
+            commands::trash::restore_deleted,
+            commands::snapshots::list_snapshots,
+            commands::snapshots::take_snapshot,
+            commands::snapshots::restore_snapshot,
             commands::memory::read_memory,
+            commands::memory::read_memory_chunk,
             commands::memory::write_memory,
+            commands::memory::discover_memory_files,
+            commands::memory::resolve_memory_graph,
+            commands::memory::compute_effective_memory,
+            commands::output_styles::list_output_styles,
+            commands::output_styles::read_output_style,
+            commands::output_styles::write_output_style,
+            commands::output_styles::delete_output_style,
             commands::agents::list_agents,
             commands::agents::read_agent,
             commands::agents::write_agent,
+            commands::agents::write_agent_body,
+            commands::agents::write_agent_frontmatter,
             commands::agents::delete_agent,
+            commands::agents::rename_agent,
+            commands::agents::copy_agent,
+            commands::conflicts::detect_config_conflicts,
+            commands::search::search_config,
+            commands::search::query_artifacts,
+            commands::sessions::list_sessions,
+            commands::sessions::read_session,
+            commands::sessions::delete_session,
+            commands::sessions::suggest_permission_rules,
             commands::effective::compute_effective_config,
+            commands::effective::compute_effective_hooks,
+            commands::effective::preview_effective_with_edit,
+            commands::drift::pin_baseline,
+            commands::drift::check_drift,
+            commands::drift::list_baselines,
+            commands::effective::compute_scope_matrix,
+            commands::effective::find_setting_occurrences,
+            commands::effective::get_merge_policy,
+            commands::effective::inspect_env_settings,
             commands::skills::list_skills,
             commands::skills::read_skill,
             commands::skills::write_skill,
             commands::skills::delete_skill,
             commands::skills::read_skill_file,
             commands::skills::write_skill_file,
+            commands::skills::list_skill_files,
+            commands::skills::delete_skill_file,
+            commands::skills::rename_skill_file,
+            commands::skills::read_skill_file_binary,
+            commands::skills::write_skill_file_binary,
+            commands::skills::rename_skill,
+            commands::skills::copy_skill,
+            commands::statusline::render_statusline_preview,
+            commands::support::generate_support_bundle,
+            commands::profiles::list_profiles,
+            commands::profiles::create_profile,
+            commands::profiles::activate_profile,
+            commands::profiles::delete_profile,
+            commands::bundle::export_config_bundle,
+            commands::bundle::import_config_bundle,
+            commands::registry::search_registry,
+            commands::registry::preview_registry_item,
+            commands::registry::install_registry_item,
+            commands::team_template::export_project_template,
+            commands::team_template::apply_project_template,
+            commands::team_template::publish_project_template,
+            commands::templates::list_templates,
+            commands::templates::create_from_template,
+            commands::sync::sync_status,
+            commands::sync::sync_now,
+            commands::git::get_config_git_status,
+            commands::git::diff_config_against_head,
+            commands::git::commit_config_changes,
+            commands::git::ensure_local_files_gitignored,
+            commands::hook_scripts::lint_hook_command,
+            commands::hook_scripts::list_hook_scripts,
+            commands::hook_scripts::read_hook_script,
+            commands::hook_scripts::write_hook_script,
+            commands::launch::launch_claude_code,
             io::ensure::ensure_claude_dir,
             commands::watcher::subscribe_file_changes,
             commands::watcher::unsubscribe_file_changes,
-        ])
-        .setup(|_app| {
-            lockfile::acquire_lock().map_err(|e| Box::new(std::io::Error::new(std::io::ErrorKind::Other, e)))?;
+            commands::logs::read_app_logs,
+            commands::logs::set_log_level,
+            commands::metrics::get_usage_metrics,
+            commands::metrics::reset_usage_metrics,
+            commands::metrics::export_usage_report,
+            commands::models::list_available_models,
+            commands::audit::read_audit_log,
+        ]))
+        .on_window_event(|window, event| match event {
+            tauri::WindowEvent::CloseRequested { api, .. } => {
+                commands::windows::save_geometry(window);
+                // The main window hides to the tray; Quit in the tray menu exits.
+                if window.label() == "main" {
+                    api.prevent_close();
+                    let _ = window.hide();
+                }
+            }
+            tauri::WindowEvent::Destroyed => commands::windows::forget_window(window),
+            _ => {}
+        })
+        .setup(|app| {
+            lockfile::acquire_lock().map_err(|e| {
+                tracing::error!(error = %e, "failed to acquire lockfile");
+                Box::new(std::io::Error::other(e))
+            })?;
+            match io::journal::recover() {
+                Ok(outcomes) => {
+                    for outcome in outcomes {
+                        tracing::warn!(transaction = %outcome, "recovered interrupted transaction");
+                    }
+                }
+                Err(e) => tracing::warn!(error = %e, "could not check for interrupted transactions"),
+            }
+            metrics::set_enabled(
+                preferences::preferences_path()
+                    .and_then(|path| preferences::load_from(&path))
+                    .is_ok_and(|p| p.telemetry_opt_in),
+            );
+            tray::init(app.handle())?;
+            commands::snapshots::spawn_scheduler(app.handle());
+            if let Some(main) = app.get_webview_window("main") {
+                commands::windows::restore_geometry(&main, None);
+            }
+
+            // Installers register the scheme; dev builds and AppImages don't.
+            #[cfg(any(target_os = "linux", all(debug_assertions, windows)))]
+            if let Err(e) = app.deep_link().register_all() {
+                tracing::warn!(error = %e, "failed to register deep link scheme");
+            }
+            let handle = app.handle().clone();
+            app.deep_link().on_open_url(move |event| {
+                commands::deep_link::handle_urls(&handle, &event.urls());
+            });
+            // A link that launched the app.
+            if let Ok(Some(urls)) = app.deep_link().get_current() {
+                commands::deep_link::handle_urls(app.handle(), &urls);
+            }
             Ok(())
         })
         .build(tauri::generate_context!())
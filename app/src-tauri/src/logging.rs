@@ -0,0 +1,272 @@
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, reload, Registry};
+
+use crate::errors::{self, IpcError};
+use crate::platform::paths;
+
+/// Log levels from most to least verbose, as written in the `level` field.
+pub const LEVELS: &[&str] = &["TRACE", "DEBUG", "INFO", "WARN", "ERROR"];
+
+/// Commands declared `async`. Their body runs after the IPC handler has
+/// returned, so [`instrument`] leaves them to [`async_command`].
+pub const ASYNC_COMMANDS: &[&str] = &[
+    "check_drift",
+    "compute_effective_config",
+    "compute_effective_hooks",
+    "compute_scope_matrix",
+    "discover_memory_files",
+    "export_artifact",
+    "export_config_bundle",
+    "find_duplicate_artifacts",
+    "generate_support_bundle",
+    "get_project_trust",
+    "import_artifact",
+    "import_config_bundle",
+    "initialize_claude_config",
+    "install_registry_item",
+    "list_agents",
+    "list_available_models",
+    "list_baselines",
+    "list_hook_scripts",
+    "list_nested_claude_dirs",
+    "list_sessions",
+    "list_skills",
+    "list_snapshots",
+    "pin_baseline",
+    "preview_effective_with_edit",
+    "preview_registry_item",
+    "publish_project_template",
+    "query_artifacts",
+    "read_claude_json",
+    "read_mcp_config",
+    "read_memory",
+    "read_memory_chunk",
+    "read_settings",
+    "remove_claude_json_project",
+    "render_statusline_preview",
+    "restore_snapshot",
+    "run_doctor",
+    "scaffold_project_claude",
+    "search_config",
+    "search_registry",
+    "set_project_trust",
+    "suggest_permission_rules",
+    "suggest_proxy_env_settings",
+    "take_snapshot",
+    "test_api_key_helper",
+    "update_claude_json_project",
+    "write_mcp_config",
+    "write_memory",
+    "write_settings",
+];
+
+/// Daily log files kept before the oldest is deleted.
+const MAX_LOG_FILES: usize = 7;
+
+/// Handle for changing the level filter at runtime.
+static LEVEL_HANDLE: OnceLock<reload::Handle<LevelFilter, Registry>> = OnceLock::new();
+
+/// Directory holding ssenrah's logs: `{configDir}/ssenrah/logs/`.
+pub fn logs_dir() -> Result<PathBuf, IpcError> {
+    Ok(paths::resolve_app_dir()?.join("logs"))
+}
+
+/// Installs the global subscriber: JSON lines written to a daily rolling
+/// file (`ssenrah.YYYY-MM-DD.log`) at INFO level. Logging is best-effort;
+/// if the log directory cannot be created the app runs without a log file.
+pub fn init() {
+    let Ok(dir) = logs_dir() else {
+        return;
+    };
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix("ssenrah")
+        .filename_suffix("log")
+        .max_log_files(MAX_LOG_FILES)
+        .build(dir);
+    let Ok(appender) = appender else {
+        return;
+    };
+
+    let (filter, handle) = reload::Layer::new(LevelFilter::INFO);
+    let layer = fmt::layer()
+        .json()
+        .flatten_event(true)
+        .with_current_span(false)
+        .with_span_list(false)
+        .with_writer(appender);
+
+    if tracing_subscriber::registry()
+        .with(filter)
+        .with(layer)
+        .try_init()
+        .is_ok()
+    {
+        let _ = LEVEL_HANDLE.set(handle);
+    }
+}
+
+/// Parses a level name case-insensitively.
+pub fn parse_level(level: &str) -> Option<LevelFilter> {
+    match level.to_ascii_uppercase().as_str() {
+        "TRACE" => Some(LevelFilter::TRACE),
+        "DEBUG" => Some(LevelFilter::DEBUG),
+        "INFO" => Some(LevelFilter::INFO),
+        "WARN" => Some(LevelFilter::WARN),
+        "ERROR" => Some(LevelFilter::ERROR),
+        "OFF" => Some(LevelFilter::OFF),
+        _ => None,
+    }
+}
+
+/// Changes the level written to the log file. Returns `false` if logging
+/// was not initialized.
+pub fn set_level(level: LevelFilter) -> bool {
+    LEVEL_HANDLE
+        .get()
+        .is_some_and(|handle| handle.modify(|filter| *filter = level).is_ok())
+}
+
+/// Logs one IPC command invocation with its duration and, if it failed, the
+/// error kind, and counts it in the opt-in usage metrics.
+pub fn record_command(command: &str, elapsed: Duration, error: Option<&str>) {
+    crate::metrics::record_call(command, elapsed, error);
+    let duration_ms = elapsed.as_secs_f64() * 1000.0;
+    match error {
+        Some(kind) => tracing::warn!(command, duration_ms, error = kind, "ipc command failed"),
+        None => tracing::info!(command, duration_ms, "ipc command"),
+    }
+}
+
+/// Wraps the IPC handler so every command invocation is logged with its
+/// name, how long the handler took, and whether it failed. The command name
+/// is also attached to any file changes it makes in the audit trail.
+///
+/// Sync commands run inside the handler and their error is serialized
+/// there, so their outcome is known when it returns. Async commands only
+/// queue their body; it records its own outcome through [`async_command`].
+pub fn instrument<F>(handler: F) -> impl Fn(tauri::ipc::Invoke) -> bool + Send + Sync + 'static
+where
+    F: Fn(tauri::ipc::Invoke) -> bool + Send + Sync + 'static,
+{
+    move |invoke| {
+        let command = invoke.message.command().to_string();
+        if ASYNC_COMMANDS.contains(&command.as_str()) {
+            return handler(invoke);
+        }
+        let start = Instant::now();
+        errors::take_serialized_kind();
+        let handled = crate::audit::with_command(&command, || handler(invoke));
+        let error = errors::take_serialized_kind();
+        record_command(&command, start.elapsed(), error);
+        handled
+    }
+}
+
+/// Runs the whole body of async command `command`, then logs and counts its
+/// outcome like [`instrument`] does for sync commands, including errors
+/// returned before its IO starts.
+pub async fn async_command<T, F>(command: &'static str, body: F) -> Result<T, IpcError>
+where
+    F: Future<Output = Result<T, IpcError>>,
+{
+    let start = Instant::now();
+    let result = body.await;
+    record_command(
+        command,
+        start.elapsed(),
+        result.as_ref().err().map(IpcError::kind),
+    );
+    result
+}
+
+/// Index of `level` in [`LEVELS`], i.e. its severity rank.
+fn rank(level: &str) -> Option<usize> {
+    LEVELS.iter().position(|l| l.eq_ignore_ascii_case(level))
+}
+
+/// Selects up to `limit` of the most recent entries at or above `min_level`
+/// from JSON log lines ordered oldest first. Malformed lines are skipped.
+pub fn select_entries<'a>(
+    lines: impl DoubleEndedIterator<Item = &'a str>,
+    min_level: &str,
+    limit: usize,
+) -> Vec<Value> {
+    let min = rank(min_level).unwrap_or(0);
+    let mut entries: Vec<Value> = lines
+        .rev()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter(|entry| {
+            entry
+                .get("level")
+                .and_then(|l| l.as_str())
+                .and_then(rank)
+                .is_some_and(|r| r >= min)
+        })
+        .take(limit)
+        .collect();
+    entries.reverse();
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn async_commands_are_listed_and_record_their_outcome() {
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src/commands");
+        let mut found = Vec::new();
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let source = std::fs::read_to_string(entry.unwrap().path()).unwrap();
+            for rest in source.split("pub async fn ").skip(1) {
+                let name = &rest[..rest.find('(').unwrap()];
+                let body = &rest[..rest.find("\n}").unwrap()];
+                let wrapped = format!("logging::async_command(\"{}\", async move {{", name);
+                assert!(
+                    body.contains(&wrapped),
+                    "{} does not record its outcome",
+                    name
+                );
+                found.push(name.to_string());
+            }
+        }
+        found.sort();
+        assert_eq!(found, ASYNC_COMMANDS);
+    }
+
+    #[test]
+    fn selects_recent_entries_by_level() {
+        let log = [
+            r#"{"level":"INFO","message":"a"}"#,
+            r#"{"level":"ERROR","message":"b"}"#,
+            "garbage",
+            r#"{"level":"DEBUG","message":"c"}"#,
+            r#"{"level":"WARN","message":"d"}"#,
+        ];
+        let messages = |entries: Vec<Value>| -> Vec<String> {
+            entries
+                .iter()
+                .map(|e| e["message"].as_str().unwrap().to_string())
+                .collect()
+        };
+        assert_eq!(
+            messages(select_entries(log.into_iter(), "warn", 10)),
+            ["b", "d"]
+        );
+        assert_eq!(
+            messages(select_entries(log.into_iter(), "TRACE", 2)),
+            ["c", "d"]
+        );
+        assert!(parse_level("Debug").is_some() && parse_level("loud").is_none());
+    }
+}
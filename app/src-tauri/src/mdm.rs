@@ -0,0 +1,251 @@
+use serde::Serialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+/// Formats understood by `generate_managed_profile`.
+pub const PROFILE_FORMATS: &[&str] = &["macos_mobileconfig", "linux", "windows"];
+
+/// Preference domain Claude Code reads managed settings from on macOS.
+const MACOS_DOMAIN: &str = "com.anthropic.claudecode";
+
+/// Where Claude Code looks for managed settings on Windows.
+const WINDOWS_DIR: &str = r"C:\Program Files\ClaudeCode";
+
+/// One file of a generated deployment artifact.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeneratedFile {
+    pub name: String,
+    pub content: String,
+}
+
+/// Deployable files plus how to roll them out.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManagedProfile {
+    pub format: String,
+    pub files: Vec<GeneratedFile>,
+    pub instructions: String,
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// A stable UUID derived from `seed`, so regenerating a profile from the
+/// same settings yields the same identifiers and MDM treats it as an update.
+fn stable_uuid(seed: &str) -> String {
+    let hex: String = Sha256::digest(seed.as_bytes())
+        .iter()
+        .take(16)
+        .map(|b| format!("{:02X}", b))
+        .collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+/// Appends `value` as a property-list element. `null` has no plist
+/// equivalent; callers skip it.
+fn write_plist(out: &mut String, value: &Value, indent: usize) {
+    let pad = "\t".repeat(indent);
+    match value {
+        Value::Null => {}
+        Value::Bool(b) => out.push_str(&format!("{}<{}/>\n", pad, b)),
+        Value::Number(n) if n.is_i64() || n.is_u64() => {
+            out.push_str(&format!("{}<integer>{}</integer>\n", pad, n))
+        }
+        Value::Number(n) => out.push_str(&format!("{}<real>{}</real>\n", pad, n)),
+        Value::String(s) => out.push_str(&format!("{}<string>{}</string>\n", pad, escape_xml(s))),
+        Value::Array(items) => {
+            out.push_str(&format!("{}<array>\n", pad));
+            for item in items.iter().filter(|v| !v.is_null()) {
+                write_plist(out, item, indent + 1);
+            }
+            out.push_str(&format!("{}</array>\n", pad));
+        }
+        Value::Object(map) => {
+            out.push_str(&format!("{}<dict>\n", pad));
+            for (key, item) in map.iter().filter(|(_, v)| !v.is_null()) {
+                out.push_str(&format!("{}\t<key>{}</key>\n", pad, escape_xml(key)));
+                write_plist(out, item, indent + 1);
+            }
+            out.push_str(&format!("{}</dict>\n", pad));
+        }
+    }
+}
+
+/// A configuration profile with one payload for the Claude Code preference
+/// domain whose keys are the settings.
+fn mobileconfig(settings: &Value, organization: &str) -> String {
+    let seed = serde_json::to_string(settings).unwrap_or_default();
+    let mut payload = serde_json::Map::new();
+    payload.insert("PayloadType".into(), Value::from(MACOS_DOMAIN));
+    payload.insert(
+        "PayloadIdentifier".into(),
+        Value::from(format!("{}.settings", MACOS_DOMAIN)),
+    );
+    payload.insert(
+        "PayloadUUID".into(),
+        Value::from(stable_uuid(&format!("payload:{}", seed))),
+    );
+    payload.insert("PayloadVersion".into(), Value::from(1));
+    payload.insert(
+        "PayloadDisplayName".into(),
+        Value::from("Claude Code Settings"),
+    );
+    if let Some(map) = settings.as_object() {
+        payload.extend(map.clone());
+    }
+
+    let mut profile = serde_json::Map::new();
+    profile.insert(
+        "PayloadContent".into(),
+        Value::Array(vec![Value::Object(payload)]),
+    );
+    profile.insert(
+        "PayloadDisplayName".into(),
+        Value::from("Claude Code Managed Settings"),
+    );
+    profile.insert(
+        "PayloadIdentifier".into(),
+        Value::from(format!("{}.profile", MACOS_DOMAIN)),
+    );
+    profile.insert("PayloadOrganization".into(), Value::from(organization));
+    profile.insert("PayloadScope".into(), Value::from("System"));
+    profile.insert("PayloadType".into(), Value::from("Configuration"));
+    profile.insert(
+        "PayloadUUID".into(),
+        Value::from(stable_uuid(&format!("profile:{}", seed))),
+    );
+    profile.insert("PayloadVersion".into(), Value::from(1));
+
+    let mut out = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \
+         \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n\
+         <plist version=\"1.0\">\n",
+    );
+    write_plist(&mut out, &Value::Object(profile), 0);
+    out.push_str("</plist>\n");
+    out
+}
+
+/// Builds the deployable files for `format` from an already-validated
+/// settings object. Returns `None` for an unknown format.
+pub fn generate(settings: &Value, format: &str, organization: &str) -> Option<ManagedProfile> {
+    let json = serde_json::to_string_pretty(settings).unwrap_or_default() + "\n";
+    let (files, instructions) = match format {
+        "macos_mobileconfig" => (
+            vec![GeneratedFile {
+                name: "claude-code.mobileconfig".to_string(),
+                content: mobileconfig(settings, organization),
+            }],
+            format!(
+                "Upload claude-code.mobileconfig to your MDM (Jamf, Kandji, Intune, ...) and \
+                 scope it to the target Macs. It sets the {} preference domain system-wide.",
+                MACOS_DOMAIN
+            ),
+        ),
+        "linux" => (
+            vec![
+                GeneratedFile {
+                    name: "managed-settings.json".to_string(),
+                    content: json,
+                },
+                GeneratedFile {
+                    name: "install.sh".to_string(),
+                    content: "#!/bin/sh\n\
+                              # Installs Claude Code managed settings. Run as root.\n\
+                              set -eu\n\
+                              install -d -m 755 /etc/claude-code\n\
+                              install -m 644 \"$(dirname \"$0\")/managed-settings.json\" \
+                              /etc/claude-code/managed-settings.json\n"
+                        .to_string(),
+                },
+            ],
+            "Copy both files to each machine (or package them with your configuration \
+             management tool) and run install.sh as root."
+                .to_string(),
+        ),
+        "windows" => (
+            vec![
+                GeneratedFile {
+                    name: "managed-settings.json".to_string(),
+                    content: json,
+                },
+                GeneratedFile {
+                    name: "install.ps1".to_string(),
+                    content: format!(
+                        "# Installs Claude Code managed settings. Run as Administrator.\r\n\
+                         $ErrorActionPreference = 'Stop'\r\n\
+                         $target = '{}'\r\n\
+                         New-Item -ItemType Directory -Force -Path $target | Out-Null\r\n\
+                         Copy-Item -Force (Join-Path $PSScriptRoot 'managed-settings.json') \
+                         (Join-Path $target 'managed-settings.json')\r\n",
+                        WINDOWS_DIR
+                    ),
+                },
+            ],
+            "Deploy both files with Intune, Group Policy, or SCCM and run install.ps1 \
+             with administrator rights."
+                .to_string(),
+        ),
+        _ => return None,
+    };
+    Some(ManagedProfile {
+        format: format.to_string(),
+        files,
+        instructions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn writes_settings_as_plist() {
+        let settings = json!({
+            "model": "a < b",
+            "cleanupPeriodDays": 30,
+            "permissions": { "deny": ["Read(./.env)"], "ask": null },
+            "includeCoAuthoredBy": false
+        });
+        let profile = generate(&settings, "macos_mobileconfig", "Acme").unwrap();
+        let plist = &profile.files[0].content;
+
+        assert!(plist.contains("\t\t\t<key>model</key>\n\t\t\t<string>a &lt; b</string>\n"));
+        assert!(plist.contains("<key>cleanupPeriodDays</key>\n\t\t\t<integer>30</integer>"));
+        assert!(plist.contains("<array>\n\t\t\t\t\t<string>Read(./.env)</string>"));
+        assert!(plist.contains("<false/>"));
+        assert!(!plist.contains("<key>ask</key>"));
+        assert_eq!(
+            plist,
+            &generate(&settings, "macos_mobileconfig", "Acme")
+                .unwrap()
+                .files[0]
+                .content
+        );
+    }
+
+    #[test]
+    fn generates_each_format() {
+        let settings = json!({ "model": "sonnet" });
+        for format in PROFILE_FORMATS {
+            let profile = generate(&settings, format, "Acme").unwrap();
+            assert!(!profile.files.is_empty());
+        }
+        assert!(generate(&settings, "solaris", "Acme").is_none());
+        assert_eq!(stable_uuid("x").len(), 36);
+    }
+}
@@ -0,0 +1,195 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::clock::now_millis;
+use crate::errors::IpcError;
+
+/// Set from the `telemetryOptIn` preference. Nothing is counted while off.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Usage of one IPC command since metrics were enabled or reset.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandMetrics {
+    pub command: String,
+    pub calls: u64,
+    /// Failures by `IpcError` kind.
+    pub errors: BTreeMap<String, u64>,
+    /// Time in the handler. Async commands leave it once their work is
+    /// queued, so most of their time shows under `io_ms` instead.
+    pub total_ms: f64,
+    pub max_ms: f64,
+    /// Time spent in blocking IO (see `io::blocking::run`), counted apart
+    /// from the handler time.
+    pub io_ms: f64,
+    pub max_io_ms: f64,
+}
+
+/// Result of `get_usage_metrics`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageMetrics {
+    pub enabled: bool,
+    /// Milliseconds since the Unix epoch when counting started.
+    pub since: u64,
+    /// Sorted by command name.
+    pub commands: Vec<CommandMetrics>,
+}
+
+#[derive(Default)]
+struct Store {
+    since: u64,
+    commands: HashMap<String, CommandMetrics>,
+}
+
+fn store() -> &'static Mutex<Store> {
+    static STORE: OnceLock<Mutex<Store>> = OnceLock::new();
+    STORE.get_or_init(|| {
+        Mutex::new(Store {
+            since: now_millis(),
+            ..Store::default()
+        })
+    })
+}
+
+/// Turns counting on or off. Turning it off drops what was counted.
+pub fn set_enabled(enabled: bool) {
+    if ENABLED.swap(enabled, Ordering::SeqCst) != enabled {
+        reset();
+    }
+}
+
+/// Forgets everything counted so far.
+pub fn reset() {
+    let mut store = store().lock().unwrap_or_else(|e| e.into_inner());
+    store.commands.clear();
+    store.since = now_millis();
+}
+
+fn update(command: &str, f: impl FnOnce(&mut CommandMetrics)) {
+    if !ENABLED.load(Ordering::SeqCst) {
+        return;
+    }
+    let mut store = store().lock().unwrap_or_else(|e| e.into_inner());
+    let entry = store
+        .commands
+        .entry(command.to_string())
+        .or_insert_with(|| CommandMetrics {
+            command: command.to_string(),
+            ..CommandMetrics::default()
+        });
+    f(entry);
+}
+
+fn millis(elapsed: Duration) -> f64 {
+    elapsed.as_secs_f64() * 1000.0
+}
+
+fn add_error(entry: &mut CommandMetrics, kind: &str) {
+    *entry.errors.entry(kind.to_string()).or_default() += 1;
+}
+
+/// Counts one invocation of `command`, the time its handler took, and the
+/// error kind if it failed there.
+pub fn record_call(command: &str, elapsed: Duration, error: Option<&str>) {
+    update(command, |entry| {
+        let ms = millis(elapsed);
+        entry.calls += 1;
+        entry.total_ms += ms;
+        entry.max_ms = entry.max_ms.max(ms);
+        if let Some(kind) = error {
+            add_error(entry, kind);
+        }
+    });
+}
+
+/// Adds the blocking IO a command waited for (see `io::blocking::run`) and,
+/// if it failed, the error kind.
+pub fn record_io(command: &str, elapsed: Duration, error: Option<&IpcError>) {
+    update(command, |entry| {
+        let ms = millis(elapsed);
+        entry.io_ms += ms;
+        entry.max_io_ms = entry.max_io_ms.max(ms);
+        if let Some(e) = error {
+            add_error(entry, e.kind());
+        }
+    });
+}
+
+/// The counts so far.
+pub fn snapshot() -> UsageMetrics {
+    let store = store().lock().unwrap_or_else(|e| e.into_inner());
+    let mut commands: Vec<CommandMetrics> = store.commands.values().cloned().collect();
+    commands.sort_by(|a, b| a.command.cmp(&b.command));
+    UsageMetrics {
+        enabled: ENABLED.load(Ordering::SeqCst),
+        since: store.since,
+        commands,
+    }
+}
+
+/// Writes `metrics` to `path` as a report to attach to bug reports. Only
+/// command names, counts, error kinds, durations, the app version, and the
+/// OS are included: no paths, settings, or error messages.
+pub fn export_report(metrics: &UsageMetrics, path: &Path) -> Result<(), IpcError> {
+    let report = serde_json::json!({
+        "appVersion": env!("CARGO_PKG_VERSION"),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "since": metrics.since,
+        "exportedAt": now_millis(),
+        "commands": metrics.commands,
+    });
+    let text = serde_json::to_string_pretty(&report).unwrap_or_default();
+    crate::io::atomic::atomic_write(path, text.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_only_while_enabled() {
+        record_call("read_settings", Duration::from_millis(5), None);
+        assert!(snapshot().commands.is_empty());
+
+        set_enabled(true);
+        record_call("read_settings", Duration::from_millis(5), None);
+        record_call(
+            "read_settings",
+            Duration::from_millis(1),
+            Some("parse_error"),
+        );
+        let missing = IpcError::NotFound {
+            path: "/home/me/.claude/settings.json".to_string(),
+        };
+        record_io("read_settings", Duration::from_millis(20), Some(&missing));
+        let metrics = snapshot();
+        let read = metrics
+            .commands
+            .iter()
+            .find(|c| c.command == "read_settings")
+            .unwrap();
+        assert_eq!(read.calls, 2);
+        assert_eq!(read.errors["not_found"], 1);
+        assert_eq!(read.errors["parse_error"], 1);
+        assert_eq!(read.total_ms, 6.0);
+        assert_eq!(read.max_ms, 5.0);
+        assert_eq!(read.io_ms, 20.0);
+
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let path = dir.join("report.json");
+        export_report(&metrics, &path).unwrap();
+        let report = std::fs::read_to_string(&path).unwrap();
+        assert!(report.contains("not_found") && !report.contains("/home/me"));
+
+        set_enabled(false);
+        assert!(snapshot().commands.is_empty());
+    }
+}
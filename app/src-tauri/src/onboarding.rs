@@ -0,0 +1,183 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::IpcError;
+use crate::io::lock::lock_path;
+use crate::platform::paths;
+use crate::window_state::WindowGeometry;
+
+/// Onboarding steps, in the order the first-run flow walks through them.
+pub const STEPS: &[&str] = &["config_dir", "claude_code", "settings", "project"];
+
+/// How many recently opened projects are remembered.
+const MAX_RECENT_PROJECTS: usize = 10;
+
+/// ssenrah's own persisted state: `{configDir}/ssenrah/state.json`.
+pub fn state_path() -> Result<PathBuf, IpcError> {
+    Ok(paths::resolve_app_dir()?.join("state.json"))
+}
+
+/// Contents of `state.json`. Unknown keys are kept so older builds don't
+/// drop state written by newer ones.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PersistedState {
+    /// Steps the user finished or skipped.
+    #[serde(default)]
+    pub completed_steps: Vec<String>,
+    /// Most recently opened project roots, newest first.
+    #[serde(default)]
+    pub recent_projects: Vec<String>,
+    /// Last window size and position per project, see
+    /// `window_state::geometry_key`.
+    #[serde(default)]
+    pub window_geometry: BTreeMap<String, WindowGeometry>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl PersistedState {
+    /// Reads the state file. A missing or unreadable file is a fresh start.
+    pub fn load_from(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<(), IpcError> {
+        let write_failed = |e: std::io::Error| IpcError::WriteFailed {
+            path: path.display().to_string(),
+            message: e.to_string(),
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(write_failed)?;
+        }
+        let json = serde_json::to_string_pretty(self).unwrap_or_default();
+        fs::write(path, json).map_err(write_failed)
+    }
+
+    pub fn mark_complete(&mut self, step: &str) {
+        if !self.completed_steps.iter().any(|s| s == step) {
+            self.completed_steps.push(step.to_string());
+        }
+    }
+
+    /// Moves `root` to the front of the recent projects list.
+    pub fn record_project(&mut self, root: &str) {
+        self.recent_projects.retain(|p| p != root);
+        self.recent_projects.insert(0, root.to_string());
+        self.recent_projects.truncate(MAX_RECENT_PROJECTS);
+    }
+}
+
+/// Loads `state.json`, applies `change`, and writes it back while holding
+/// the file's lock.
+pub fn update(change: impl FnOnce(&mut PersistedState)) -> Result<PersistedState, IpcError> {
+    let path = state_path()?;
+    let _lock = lock_path(&path);
+    let mut state = PersistedState::load_from(&path);
+    change(&mut state);
+    state.save_to(&path)?;
+    Ok(state)
+}
+
+/// What ssenrah detected about this machine.
+#[derive(Debug, Default)]
+pub struct Facts {
+    pub config_dir_exists: bool,
+    pub claude_code_installed: bool,
+    /// The user settings file is absent or parses cleanly.
+    pub settings_valid: bool,
+    pub project_opened: bool,
+}
+
+/// One step of the first-run flow.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnboardingStep {
+    pub id: &'static str,
+    /// Detected as satisfied on this machine.
+    pub satisfied: bool,
+    /// The user finished or skipped the step.
+    pub completed: bool,
+}
+
+/// Result of `get_onboarding_status`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnboardingStatus {
+    pub steps: Vec<OnboardingStep>,
+    /// First step neither satisfied nor completed, `null` when done.
+    pub current: Option<&'static str>,
+    pub finished: bool,
+}
+
+/// Combines detected facts with persisted progress.
+pub fn status(facts: &Facts, state: &PersistedState) -> OnboardingStatus {
+    let steps: Vec<OnboardingStep> = STEPS
+        .iter()
+        .map(|&id| OnboardingStep {
+            id,
+            satisfied: match id {
+                "config_dir" => facts.config_dir_exists,
+                "claude_code" => facts.claude_code_installed,
+                "settings" => facts.settings_valid,
+                _ => facts.project_opened,
+            },
+            completed: state.completed_steps.iter().any(|s| s == id),
+        })
+        .collect();
+    let current = steps
+        .iter()
+        .find(|s| !s.satisfied && !s.completed)
+        .map(|s| s.id);
+    OnboardingStatus {
+        finished: current.is_none(),
+        current,
+        steps,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn current_step_is_first_unfinished() {
+        let mut state = PersistedState::default();
+        let facts = Facts {
+            config_dir_exists: true,
+            settings_valid: true,
+            ..Default::default()
+        };
+        assert_eq!(status(&facts, &state).current, Some("claude_code"));
+
+        state.mark_complete("claude_code");
+        assert_eq!(status(&facts, &state).current, Some("project"));
+
+        state.mark_complete("project");
+        let done = status(&facts, &state);
+        assert!(done.finished && done.current.is_none());
+    }
+
+    #[test]
+    fn persists_recent_projects_and_unknown_keys() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let path = dir.join("state.json");
+        fs::write(&path, r#"{"recentProjects":["/a"],"theme":"dark"}"#).unwrap();
+
+        let mut state = PersistedState::load_from(&path);
+        state.record_project("/b");
+        state.record_project("/a");
+        state.save_to(&path).unwrap();
+
+        let reloaded = PersistedState::load_from(&path);
+        assert_eq!(reloaded.recent_projects, vec!["/a", "/b"]);
+        assert_eq!(reloaded.extra.get("theme").unwrap(), "dark");
+    }
+}
@@ -1,2 +0,0 @@
-pub mod detect;
-pub mod paths;
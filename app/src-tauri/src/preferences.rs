@@ -0,0 +1,188 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::errors::IpcError;
+use crate::platform::paths;
+use crate::types::ValidationError;
+
+/// Layout version written to `preferences.json`.
+pub const CURRENT_VERSION: u64 = 1;
+
+/// Upgrades, indexed by the version they start from: `MIGRATIONS[0]`
+/// turns a version 0 file into version 1.
+const MIGRATIONS: &[fn(&mut Map<String, Value>)] = &[
+    // Files without a `version` predate versioning and already use the
+    // version 1 layout.
+    |_| {},
+];
+
+/// ssenrah's own preferences: `{configDir}/ssenrah/preferences.json`.
+/// Kept apart from Claude Code's settings files.
+pub fn preferences_path() -> Result<PathBuf, IpcError> {
+    Ok(paths::resolve_app_dir()?.join("preferences.json"))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    System,
+    Light,
+    Dark,
+}
+
+/// Preferences for ssenrah itself. Missing keys take their defaults.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AppPreferences {
+    pub version: u64,
+    pub theme: Theme,
+    /// Days to keep backups and deleted items.
+    pub backup_retention_days: u32,
+    /// Hours between scheduled config snapshots; 0 turns them off.
+    pub snapshot_interval_hours: u32,
+    /// How long the file watcher collects changes before emitting a batch.
+    pub watcher_debounce_ms: u64,
+    pub telemetry_opt_in: bool,
+    /// Keys this build does not know, kept when writing.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl Default for AppPreferences {
+    fn default() -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            theme: Theme::System,
+            backup_retention_days: 30,
+            snapshot_interval_hours: 24,
+            watcher_debounce_ms: 200,
+            telemetry_opt_in: false,
+            extra: Map::new(),
+        }
+    }
+}
+
+impl AppPreferences {
+    /// Range problems, reported the way settings validation reports them.
+    pub fn validate(&self) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+        let mut out_of_range = |path: &str, message: &str| {
+            errors.push(ValidationError {
+                path: path.to_string(),
+                message: message.to_string(),
+                code: "OUT_OF_RANGE".to_string(),
+            })
+        };
+        if !(1..=365).contains(&self.backup_retention_days) {
+            out_of_range(
+                "backupRetentionDays",
+                "Backup retention must be between 1 and 365 days",
+            );
+        }
+        if self.snapshot_interval_hours > 24 * 30 {
+            out_of_range(
+                "snapshotIntervalHours",
+                "Snapshot interval must be at most 720 hours (0 turns snapshots off)",
+            );
+        }
+        if !(50..=10_000).contains(&self.watcher_debounce_ms) {
+            out_of_range(
+                "watcherDebounceMs",
+                "Watcher debounce must be between 50 and 10000 ms",
+            );
+        }
+        errors
+    }
+}
+
+/// Brings a parsed preferences object up to `CURRENT_VERSION`. Versions
+/// newer than this build are left alone.
+pub fn migrate(mut value: Map<String, Value>) -> Map<String, Value> {
+    let version = value.get("version").and_then(Value::as_u64).unwrap_or(0);
+    if version >= CURRENT_VERSION {
+        return value;
+    }
+    for step in &MIGRATIONS[version as usize..] {
+        step(&mut value);
+    }
+    value.insert("version".to_string(), Value::from(CURRENT_VERSION));
+    value
+}
+
+/// Reads and migrates the preferences file. A missing file gives the
+/// defaults.
+pub fn load_from(path: &Path) -> Result<AppPreferences, IpcError> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(AppPreferences::default()),
+        Err(e) => {
+            return Err(IpcError::PlatformError {
+                message: format!("Failed to read {}: {}", path.display(), e),
+            })
+        }
+    };
+    let parse_error = |message: String| IpcError::ParseError {
+        path: path.display().to_string(),
+        message,
+    };
+    let object = match serde_json::from_str(&text).map_err(|e| parse_error(e.to_string()))? {
+        Value::Object(object) => object,
+        _ => return Err(parse_error("Preferences must be a JSON object".to_string())),
+    };
+    serde_json::from_value(Value::Object(migrate(object))).map_err(|e| parse_error(e.to_string()))
+}
+
+pub fn save_to(path: &Path, preferences: &AppPreferences) -> Result<(), IpcError> {
+    let write_failed = |e: std::io::Error| IpcError::WriteFailed {
+        path: path.display().to_string(),
+        message: e.to_string(),
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(write_failed)?;
+    }
+    let json = serde_json::to_string_pretty(preferences).unwrap_or_default();
+    fs::write(path, json).map_err(write_failed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_defaults_and_stamps_version() {
+        let migrated = migrate(
+            serde_json::json!({ "theme": "dark" })
+                .as_object()
+                .unwrap()
+                .clone(),
+        );
+        assert_eq!(migrated["version"], CURRENT_VERSION);
+
+        let prefs: AppPreferences = serde_json::from_value(Value::Object(migrated)).unwrap();
+        assert_eq!(prefs.theme, Theme::Dark);
+        assert_eq!(prefs.backup_retention_days, 30);
+        assert!(prefs.validate().is_empty());
+    }
+
+    #[test]
+    fn round_trips_unknown_keys_and_rejects_bad_ranges() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let path = dir.join("preferences.json");
+        assert_eq!(load_from(&path).unwrap().theme, Theme::System);
+
+        fs::write(
+            &path,
+            r#"{"version":1,"watcherDebounceMs":0,"layout":"wide"}"#,
+        )
+        .unwrap();
+        let prefs = load_from(&path).unwrap();
+        assert_eq!(prefs.validate()[0].path, "watcherDebounceMs");
+
+        save_to(&path, &prefs).unwrap();
+        assert_eq!(load_from(&path).unwrap().extra["layout"], "wide");
+    }
+}
@@ -0,0 +1,172 @@
+use std::io::Read;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::audit;
+use crate::errors::IpcError;
+
+/// Index used when `SSENRAH_REGISTRY_URL` is not set.
+pub const DEFAULT_INDEX_URL: &str =
+    "https://raw.githubusercontent.com/HyperBlaze456/ssenrah-registry/main/index.json";
+
+/// Registry requests give up after this long, well inside the IPC timeout.
+const FETCH_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Largest index accepted, in bytes.
+const MAX_INDEX_BYTES: u64 = 8 * 1024 * 1024;
+
+/// Largest item payload accepted, in bytes.
+pub const MAX_ITEM_BYTES: u64 = 1024 * 1024;
+
+/// The registry index: every item that can be installed.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RegistryIndex {
+    #[serde(default)]
+    pub items: Vec<RegistryItem>,
+}
+
+/// A community-shared agent, skill, or MCP server config.
+///
+/// Agents and skills are a single markdown file written as `{name}.md` or
+/// `{name}/SKILL.md`; MCP items are the JSON config of one server, added to
+/// `mcpServers.{name}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegistryItem {
+    pub id: String,
+    /// "agent", "skill", or "mcp".
+    pub kind: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub author: Option<String>,
+    #[serde(default)]
+    pub license: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// HTTPS URL of the payload.
+    pub url: String,
+    /// Hex-encoded SHA-256 of the payload.
+    pub sha256: String,
+}
+
+/// URL of the registry index, overridable with `SSENRAH_REGISTRY_URL`.
+pub fn index_url() -> String {
+    std::env::var("SSENRAH_REGISTRY_URL").unwrap_or_else(|_| DEFAULT_INDEX_URL.to_string())
+}
+
+/// Downloads `url` (HTTPS only), reading at most `limit` bytes.
+pub fn fetch(url: &str, limit: u64) -> Result<Vec<u8>, IpcError> {
+    let failed = |message: String| IpcError::PlatformError {
+        message: format!("Registry request to {} failed: {}", url, message),
+    };
+    if !url.starts_with("https://") {
+        return Err(failed("only https:// URLs are allowed".to_string()));
+    }
+
+    let agent = ureq::AgentBuilder::new().timeout(FETCH_TIMEOUT).build();
+    let response = agent.get(url).call().map_err(|e| failed(e.to_string()))?;
+    let mut bytes = Vec::new();
+    response
+        .into_reader()
+        .take(limit + 1)
+        .read_to_end(&mut bytes)
+        .map_err(|e| failed(e.to_string()))?;
+    if bytes.len() as u64 > limit {
+        return Err(failed(format!("response is larger than {} bytes", limit)));
+    }
+    Ok(bytes)
+}
+
+/// Fetches and parses the registry index.
+pub fn fetch_index() -> Result<RegistryIndex, IpcError> {
+    let url = index_url();
+    let bytes = fetch(&url, MAX_INDEX_BYTES)?;
+    serde_json::from_slice(&bytes).map_err(|e| IpcError::ParseError {
+        path: url,
+        message: e.to_string(),
+    })
+}
+
+/// Items whose name, description, or tags contain `query` (case-insensitive),
+/// name matches first. An empty query returns every item.
+pub fn search(index: &RegistryIndex, query: &str) -> Vec<RegistryItem> {
+    let query = query.trim().to_lowercase();
+    let mut hits: Vec<(u32, &RegistryItem)> = index
+        .items
+        .iter()
+        .filter_map(|item| {
+            let score = if item.name.to_lowercase().contains(&query) {
+                3
+            } else if item.tags.iter().any(|t| t.to_lowercase().contains(&query)) {
+                2
+            } else if item.description.to_lowercase().contains(&query) {
+                1
+            } else {
+                return None;
+            };
+            Some((score, item))
+        })
+        .collect();
+    hits.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.name.cmp(&b.1.name)));
+    hits.into_iter().map(|(_, item)| item.clone()).collect()
+}
+
+/// Checks a downloaded payload against the checksum published in the index.
+pub fn verify(item: &RegistryItem, bytes: &[u8]) -> Result<(), IpcError> {
+    let actual = audit::hash(bytes);
+    if actual.eq_ignore_ascii_case(item.sha256.trim()) {
+        Ok(())
+    } else {
+        Err(IpcError::PlatformError {
+            message: format!(
+                "Checksum mismatch for registry item '{}': expected {}, got {}",
+                item.id, item.sha256, actual
+            ),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(id: &str, name: &str, tags: &[&str], description: &str) -> RegistryItem {
+        RegistryItem {
+            id: id.to_string(),
+            kind: "agent".to_string(),
+            name: name.to_string(),
+            description: description.to_string(),
+            author: None,
+            license: None,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            url: format!("https://example.com/{}.md", id),
+            sha256: audit::hash(id.as_bytes()),
+        }
+    }
+
+    #[test]
+    fn searches_names_before_tags_and_descriptions() {
+        let index = RegistryIndex {
+            items: vec![
+                item("a", "security-auditor", &[], "Finds review issues"),
+                item("b", "pr-helper", &["Review"], ""),
+                item("c", "code-review", &[], ""),
+            ],
+        };
+        let ids: Vec<String> = search(&index, "review").into_iter().map(|i| i.id).collect();
+        assert_eq!(ids, ["c", "b", "a"]);
+        assert_eq!(search(&index, "").len(), 3);
+        assert!(search(&index, "deploy").is_empty());
+    }
+
+    #[test]
+    fn verifies_checksums_and_rejects_plain_http() {
+        let item = item("payload", "x", &[], "");
+        assert!(verify(&item, b"payload").is_ok());
+        assert!(verify(&item, b"tampered").is_err());
+        assert!(fetch("http://example.com/index.json", MAX_INDEX_BYTES).is_err());
+    }
+}
@@ -0,0 +1,185 @@
+use serde::Serialize;
+use serde_json::Value;
+
+/// Longest snippet returned for a hit, in characters.
+const SNIPPET_CHARS: usize = 160;
+
+/// A single search match.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchHit {
+    /// "settings", "mcp", "agents", "skills", or "memory".
+    pub kind: String,
+    pub scope: String,
+    pub file: String,
+    /// Field path for JSON files, e.g. `permissions.allow[2]`.
+    pub path: Option<String>,
+    /// 1-based line number for markdown files.
+    pub line: Option<usize>,
+    pub snippet: String,
+    pub score: u32,
+}
+
+/// Case-(in)sensitive substring matcher with simple relevance scoring.
+pub struct Matcher {
+    needle: String,
+    case_sensitive: bool,
+}
+
+impl Matcher {
+    pub fn new(query: &str, case_sensitive: bool) -> Self {
+        let needle = if case_sensitive {
+            query.to_string()
+        } else {
+            query.to_lowercase()
+        };
+        Self {
+            needle,
+            case_sensitive,
+        }
+    }
+
+    /// Returns the character offset of the first match.
+    fn find(&self, text: &str) -> Option<usize> {
+        let haystack = if self.case_sensitive {
+            text.to_string()
+        } else {
+            text.to_lowercase()
+        };
+        let byte = haystack.find(&self.needle)?;
+        Some(haystack[..byte].chars().count())
+    }
+
+    /// Scores a match in `text`: 1 for a substring match, 3 when the query
+    /// appears as a whole word. Returns `None` if `text` does not match.
+    fn score(&self, text: &str) -> Option<(u32, usize)> {
+        let start = self.find(text)?;
+        let chars: Vec<char> = text.chars().collect();
+        let end = start + self.needle.chars().count();
+        let is_word = |c: Option<&char>| c.is_some_and(|c| c.is_alphanumeric() || *c == '_');
+        let whole_word =
+            !is_word(start.checked_sub(1).and_then(|i| chars.get(i))) && !is_word(chars.get(end));
+        Some((if whole_word { 3 } else { 1 }, start))
+    }
+}
+
+/// Cuts a window of at most `SNIPPET_CHARS` around the match at `start`.
+fn snippet(text: &str, start: usize) -> String {
+    let chars: Vec<char> = text.trim_end().chars().collect();
+    let from = start.saturating_sub(SNIPPET_CHARS / 3).min(chars.len());
+    let to = (from + SNIPPET_CHARS).min(chars.len());
+    let mut out: String = chars[from..to].iter().collect();
+    if from > 0 {
+        out.insert(0, '…');
+    }
+    if to < chars.len() {
+        out.push('…');
+    }
+    out.trim().to_string()
+}
+
+/// Finds matching lines in a markdown file. Lines in the frontmatter naming
+/// the file (`name:` / `description:`) get a relevance boost.
+pub fn text_hits(matcher: &Matcher, content: &str) -> Vec<(usize, String, u32)> {
+    let mut hits = Vec::new();
+    let mut in_frontmatter = false;
+
+    for (idx, line) in content.lines().enumerate() {
+        if line.trim() == "---" && (idx == 0 || in_frontmatter) {
+            in_frontmatter = idx == 0;
+            continue;
+        }
+        let Some((mut score, start)) = matcher.score(line) else {
+            continue;
+        };
+        let key = line.trim_start();
+        if in_frontmatter && (key.starts_with("name:") || key.starts_with("description:")) {
+            score += 5;
+        }
+        hits.push((idx + 1, snippet(line, start), score));
+    }
+
+    hits
+}
+
+/// Finds object keys and string values matching the query in a JSON value.
+/// Matching keys (such as an MCP server name) score higher than values.
+pub fn json_hits(matcher: &Matcher, value: &Value) -> Vec<(String, String, u32)> {
+    let mut hits = Vec::new();
+    walk(matcher, value, String::new(), &mut hits);
+    hits
+}
+
+fn walk(matcher: &Matcher, value: &Value, path: String, hits: &mut Vec<(String, String, u32)>) {
+    match value {
+        Value::String(s) => {
+            if let Some((score, start)) = matcher.score(s) {
+                hits.push((path, snippet(s, start), score));
+            }
+        }
+        Value::Number(_) | Value::Bool(_) => {
+            let s = value.to_string();
+            if let Some((score, start)) = matcher.score(&s) {
+                hits.push((path, snippet(&s, start), score));
+            }
+        }
+        Value::Array(items) => {
+            for (i, item) in items.iter().enumerate() {
+                walk(matcher, item, format!("{}[{}]", path, i), hits);
+            }
+        }
+        Value::Object(map) => {
+            for (key, item) in map {
+                let child = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                if let Some((score, start)) = matcher.score(key) {
+                    hits.push((child.clone(), snippet(key, start), score + 2));
+                }
+                walk(matcher, item, child, hits);
+            }
+        }
+        Value::Null => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn scores_whole_words_and_frontmatter_names() {
+        let matcher = Matcher::new("Review", false);
+        let content = "---\nname: review\ndescription: x\n---\nPreviewing changes\nreview the diff";
+        let hits = text_hits(&matcher, content);
+        let lines: Vec<_> = hits
+            .iter()
+            .map(|(line, _, score)| (*line, *score))
+            .collect();
+        assert_eq!(lines, vec![(2, 8), (5, 1), (6, 3)]);
+    }
+
+    #[test]
+    fn matches_json_keys_and_values() {
+        let matcher = Matcher::new("github", true);
+        let value = json!({ "mcpServers": { "github": { "args": ["--repo", "x/github-cli"] } } });
+        let hits = json_hits(&matcher, &value);
+        let paths: Vec<_> = hits.iter().map(|(p, _, s)| (p.as_str(), *s)).collect();
+        assert_eq!(
+            paths,
+            vec![("mcpServers.github", 5), ("mcpServers.github.args[1]", 3)]
+        );
+    }
+
+    #[test]
+    fn trims_long_snippets_around_the_match() {
+        let line = format!("{}needle{}", "a ".repeat(100), " b".repeat(100));
+        let s = snippet(&line, 200);
+        assert!(s.starts_with('…') && s.ends_with('…'));
+        assert!(s.contains("needle"));
+        assert!(s.chars().count() <= SNIPPET_CHARS + 2);
+    }
+}
@@ -0,0 +1,162 @@
+use std::path::{Path, PathBuf};
+
+use serde_json::{json, Value};
+
+/// Session data piped to a statusLine command, in the shape Claude Code
+/// sends. Fields in `overrides` replace the synthetic defaults.
+pub fn sample_context(cwd: &str, overrides: Option<&Value>) -> Value {
+    let mut context = json!({
+        "hook_event_name": "Status",
+        "session_id": "00000000-0000-4000-8000-000000000000",
+        "transcript_path": "",
+        "cwd": cwd,
+        "model": { "id": "claude-sonnet-4-5", "display_name": "Sonnet 4.5" },
+        "workspace": { "current_dir": cwd, "project_dir": cwd },
+        "version": "2.0.0",
+        "output_style": { "name": "default" },
+        "cost": {
+            "total_cost_usd": 0.0123,
+            "total_duration_ms": 45000,
+            "total_api_duration_ms": 2300,
+            "total_lines_added": 156,
+            "total_lines_removed": 23
+        }
+    });
+    if let Some(overrides) = overrides {
+        merge_into(&mut context, overrides);
+    }
+    context
+}
+
+fn merge_into(target: &mut Value, overrides: &Value) {
+    match (target, overrides) {
+        (Value::Object(t), Value::Object(o)) => {
+            for (key, value) in o {
+                match t.get_mut(key) {
+                    Some(existing) => merge_into(existing, value),
+                    None => {
+                        t.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (target, value) => *target = value.clone(),
+    }
+}
+
+/// Replaces `{{dot.path}}` placeholders with values from `context`; missing
+/// fields render as empty strings.
+pub fn render_template(template: &str, context: &Value) -> String {
+    let mut out = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start..].find("}}") else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        let path = rest[start + 2..start + len].trim();
+        let value = path
+            .split('.')
+            .try_fold(context, |v, key| v.get(key))
+            .map(|v| match v {
+                Value::String(s) => s.clone(),
+                Value::Null => String::new(),
+                other => other.to_string(),
+            })
+            .unwrap_or_default();
+        out.push_str(&value);
+        rest = &rest[start + len + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Claude Code shows only the first line of the command's output.
+pub fn first_line(output: &str) -> &str {
+    output.lines().next().unwrap_or_default()
+}
+
+/// The program a statusLine command runs, when it names a script or binary
+/// by path (`~/.claude/statusline.sh`, `./bin/status`). Bare names resolved
+/// through `PATH` and shell builtins return `None`.
+pub fn command_path(command: &str, home: &Path, cwd: &Path) -> Option<PathBuf> {
+    let program = command.split_whitespace().next()?;
+    let program = program.trim_matches(|c| c == '"' || c == '\'');
+    if let Some(rest) = program.strip_prefix("~/") {
+        Some(home.join(rest))
+    } else if program.contains('/') || program.contains('\\') {
+        Some(cwd.join(program))
+    } else {
+        None
+    }
+}
+
+/// Problems with the program a statusLine command points at.
+pub fn check_command(command: &str, home: &Path, cwd: &Path) -> Vec<String> {
+    if command.trim().is_empty() {
+        return vec!["statusLine.command is empty".to_string()];
+    }
+    let Some(path) = command_path(command, home, cwd) else {
+        return Vec::new();
+    };
+    let Ok(meta) = std::fs::metadata(&path) else {
+        return vec![format!("{} does not exist", path.display())];
+    };
+    if !meta.is_file() {
+        return vec![format!("{} is not a file", path.display())];
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if meta.permissions().mode() & 0o111 == 0 {
+            return vec![format!(
+                "{} is not executable (run chmod +x)",
+                path.display()
+            )];
+        }
+    }
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_context_and_renders_templates() {
+        let context = sample_context(
+            "/work/app",
+            Some(&json!({ "model": { "display_name": "Opus" } })),
+        );
+        assert_eq!(context["model"]["id"], "claude-sonnet-4-5");
+        assert_eq!(
+            render_template(
+                "[{{ model.display_name }}] {{workspace.current_dir}} ${{cost.total_cost_usd}}{{missing}}",
+                &context
+            ),
+            "[Opus] /work/app $0.0123"
+        );
+        assert_eq!(first_line("one\ntwo"), "one");
+    }
+
+    #[test]
+    fn checks_command_paths() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        std::fs::write(dir.join("status.sh"), "#!/bin/sh\necho hi\n").unwrap();
+
+        assert!(check_command("echo hi", dir, dir).is_empty());
+        assert_eq!(check_command("~/missing.sh", dir, dir).len(), 1);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(check_command("~/status.sh", dir, dir).len(), 1);
+            std::fs::set_permissions(
+                dir.join("status.sh"),
+                std::fs::Permissions::from_mode(0o755),
+            )
+            .unwrap();
+            assert!(check_command("~/status.sh --short", dir, dir).is_empty());
+        }
+    }
+}
@@ -0,0 +1,232 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::errors::IpcError;
+use crate::platform::paths;
+
+/// A starting point for a new agent or skill.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Template {
+    /// `builtin:{slug}` for embedded templates, `user:{stem}` for files in
+    /// the user template directory.
+    pub id: String,
+    /// "agent" or "skill".
+    pub kind: String,
+    pub title: String,
+    pub description: String,
+    pub builtin: bool,
+    /// Markdown with `{{variable}}` placeholders; `{{name}}` is always set.
+    pub content: String,
+}
+
+/// Embedded templates: (kind, slug, title, description, content).
+const BUILTIN: &[(&str, &str, &str, &str, &str)] = &[
+    (
+        "agent",
+        "code-reviewer",
+        "Code reviewer",
+        "Reviews changes for bugs, readability, and missing tests",
+        "---\n\
+name: {{name}}\n\
+description: Reviews code changes for correctness, readability, and test coverage. Use after writing or modifying code.\n\
+tools: Read, Grep, Glob, Bash\n\
+model: sonnet\n\
+---\n\
+\n\
+You are a senior engineer reviewing a change.\n\
+\n\
+1. Run `git diff` to see what changed.\n\
+2. Read the surrounding code before judging a hunk.\n\
+3. Report problems grouped as **must fix**, **should fix**, and **nits**,\n\
+   each with the file, line, and a concrete suggestion.\n\
+\n\
+Look for logic errors, unhandled errors, security issues, and changes that\n\
+lack tests. Do not rewrite code that is merely different from your taste.\n",
+    ),
+    (
+        "agent",
+        "test-writer",
+        "Test writer",
+        "Writes focused tests for new or changed code",
+        "---\n\
+name: {{name}}\n\
+description: Writes and runs tests for new or changed code. Use when a change lacks coverage.\n\
+tools: Read, Grep, Glob, Edit, Write, Bash\n\
+---\n\
+\n\
+You write tests that match the project's existing test style.\n\
+\n\
+1. Find how and where the project already tests similar code.\n\
+2. Cover the main behaviour, edge cases, and error paths of the change.\n\
+3. Run the test suite and fix any test you wrote that fails.\n\
+\n\
+Prefer a few precise tests over many shallow ones.\n",
+    ),
+    (
+        "agent",
+        "debugger",
+        "Debugger",
+        "Finds the root cause of failures and proposes a minimal fix",
+        "---\n\
+name: {{name}}\n\
+description: Investigates errors, test failures, and unexpected behaviour. Use when something is broken.\n\
+tools: Read, Grep, Glob, Bash\n\
+---\n\
+\n\
+You are debugging a failure.\n\
+\n\
+1. Reproduce it and capture the exact error.\n\
+2. Form a hypothesis, then confirm it by reading code or adding logging.\n\
+3. Explain the root cause and propose the smallest fix that addresses it.\n",
+    ),
+    (
+        "skill",
+        "commit-message",
+        "Commit message",
+        "Drafts a commit message from the staged diff",
+        "---\n\
+name: {{name}}\n\
+description: Writes a commit message for the staged changes. Use when committing.\n\
+allowed-tools: Bash(git diff:*), Bash(git log:*)\n\
+---\n\
+\n\
+# Commit message\n\
+\n\
+1. Run `git diff --staged` to see the change and `git log -5 --oneline` to\n\
+   match the repository's style.\n\
+2. Write a subject line under 72 characters in the imperative mood.\n\
+3. Add a short body explaining why the change was made if it is not obvious.\n",
+    ),
+    (
+        "skill",
+        "pr-description",
+        "Pull request description",
+        "Summarizes a branch as a pull request description",
+        "---\n\
+name: {{name}}\n\
+description: Drafts a pull request description for the current branch.\n\
+allowed-tools: Bash(git diff:*), Bash(git log:*)\n\
+---\n\
+\n\
+# Pull request description\n\
+\n\
+1. Compare the branch with its base using `git log` and `git diff`.\n\
+2. Open with one or two sentences on what the change does and why.\n\
+3. List how it was tested and anything left out of scope.\n",
+    ),
+];
+
+/// Directory holding user-defined templates: `{configDir}/ssenrah/templates/`,
+/// with `agents/*.md` and `skills/*.md` inside.
+pub fn user_templates_dir() -> Result<PathBuf, IpcError> {
+    Ok(paths::resolve_app_dir()?.join("templates"))
+}
+
+/// Lists the embedded templates of `kind` followed by the user's own.
+pub fn list(kind: &str) -> Result<Vec<Template>, IpcError> {
+    Ok(list_in(&user_templates_dir()?, kind))
+}
+
+/// Looks up a template by id.
+pub fn find(kind: &str, id: &str) -> Result<Option<Template>, IpcError> {
+    Ok(list(kind)?.into_iter().find(|t| t.id == id))
+}
+
+fn list_in(dir: &Path, kind: &str) -> Vec<Template> {
+    let mut templates: Vec<Template> = BUILTIN
+        .iter()
+        .filter(|(k, ..)| *k == kind)
+        .map(|(k, slug, title, description, content)| Template {
+            id: format!("builtin:{}", slug),
+            kind: k.to_string(),
+            title: title.to_string(),
+            description: description.to_string(),
+            builtin: true,
+            content: content.to_string(),
+        })
+        .collect();
+
+    let Ok(entries) = fs::read_dir(dir.join(format!("{}s", kind))) else {
+        return templates;
+    };
+    let mut user: Vec<Template> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("md"))
+        .filter_map(|path| {
+            let stem = path.file_stem()?.to_string_lossy().to_string();
+            let content = fs::read_to_string(&path).ok()?;
+            Some(Template {
+                id: format!("user:{}", stem),
+                kind: kind.to_string(),
+                description: frontmatter_description(&content).unwrap_or_default(),
+                title: stem,
+                builtin: false,
+                content,
+            })
+        })
+        .collect();
+    user.sort_by(|a, b| a.id.cmp(&b.id));
+    templates.extend(user);
+    templates
+}
+
+/// The `description:` line of a template's frontmatter, ignoring placeholders.
+fn frontmatter_description(content: &str) -> Option<String> {
+    let rest = content.strip_prefix("---")?;
+    let end = rest.find("\n---")?;
+    rest[..end].lines().find_map(|line| {
+        line.strip_prefix("description:")
+            .map(|v| v.trim().trim_matches('"').to_string())
+            .filter(|v| !v.contains("{{"))
+    })
+}
+
+/// Replaces `{{variable}}` placeholders. Unknown placeholders are left as is.
+pub fn render(content: &str, vars: &BTreeMap<String, String>) -> String {
+    let mut out = content.to_string();
+    for (name, value) in vars {
+        out = out.replace(&format!("{{{{{}}}}}", name), value);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_builtin_and_user_templates() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        fs::create_dir_all(dir.join("agents")).unwrap();
+        fs::write(
+            dir.join("agents/triage.md"),
+            "---\nname: {{name}}\ndescription: Sorts incoming issues\n---\n\nTriage.\n",
+        )
+        .unwrap();
+
+        let agents = list_in(dir, "agent");
+        assert!(agents.iter().any(|t| t.id == "builtin:code-reviewer"));
+        let user = agents.iter().find(|t| t.id == "user:triage").unwrap();
+        assert!(!user.builtin);
+        assert_eq!(user.description, "Sorts incoming issues");
+        assert!(list_in(dir, "skill").iter().all(|t| t.builtin));
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let vars = BTreeMap::from([("name".to_string(), "reviewer".to_string())]);
+        assert_eq!(
+            render("name: {{name}}\n{{other}}", &vars),
+            "name: reviewer\n{{other}}"
+        );
+        for (_, _, _, _, content) in BUILTIN {
+            assert!(content.starts_with("---\nname: {{name}}\n"));
+        }
+    }
+}
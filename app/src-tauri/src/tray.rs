@@ -0,0 +1,187 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tauri::menu::{IsMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu};
+use tauri::tray::TrayIconBuilder;
+use tauri::{AppHandle, Emitter, Manager, Wry};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::commands::windows::open_window;
+use crate::onboarding::{self, PersistedState};
+use crate::platform::paths;
+use crate::watcher::batch::Batch;
+use crate::watcher::debounce;
+
+const TRAY_ID: &str = "main";
+
+/// Recent projects listed in the tray menu.
+const MAX_TRAY_PROJECTS: usize = 5;
+
+/// Minimum gap between change notifications, so a checkout rewriting many
+/// files over several batches produces one notification.
+const NOTIFY_INTERVAL: Duration = Duration::from_secs(30);
+
+static LAST_NOTIFIED: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// `~/...` form of `path` for menu labels.
+fn display_path(path: &str) -> String {
+    let Ok(home) = paths::home_dir() else {
+        return path.to_string();
+    };
+    match std::path::Path::new(path).strip_prefix(&home) {
+        Ok(rest) => format!("~/{}", rest.display()),
+        Err(_) => path.to_string(),
+    }
+}
+
+fn build_menu(app: &AppHandle) -> tauri::Result<Menu<Wry>> {
+    // Without an app directory there are no recent projects to list.
+    let recent = onboarding::state_path()
+        .map(|path| PersistedState::load_from(&path).recent_projects)
+        .unwrap_or_default();
+    let mut projects = recent
+        .iter()
+        .take(MAX_TRAY_PROJECTS)
+        .map(|p| {
+            MenuItem::with_id(
+                app,
+                format!("recent:{}", p),
+                display_path(p),
+                true,
+                None::<&str>,
+            )
+        })
+        .collect::<tauri::Result<Vec<_>>>()?;
+    if projects.is_empty() {
+        projects.push(MenuItem::with_id(
+            app,
+            "recent-none",
+            "No recent projects",
+            false,
+            None::<&str>,
+        )?);
+    }
+    let project_items: Vec<&dyn IsMenuItem<Wry>> = projects
+        .iter()
+        .map(|item| item as &dyn IsMenuItem<Wry>)
+        .collect();
+
+    let watching_label = if debounce::is_paused() {
+        "Resume watching"
+    } else {
+        "Pause watching"
+    };
+    Menu::with_items(
+        app,
+        &[
+            &MenuItem::with_id(app, "show", "Show ssenrah", true, None::<&str>)?,
+            &Submenu::with_items(app, "Recent projects", true, &project_items)?,
+            &MenuItem::with_id(
+                app,
+                "open-user-settings",
+                "Open user settings",
+                true,
+                None::<&str>,
+            )?,
+            &PredefinedMenuItem::separator(app)?,
+            &MenuItem::with_id(app, "toggle-watching", watching_label, true, None::<&str>)?,
+            &PredefinedMenuItem::separator(app)?,
+            &MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?,
+        ],
+    )
+}
+
+fn show_main(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+}
+
+fn handle_menu_event(app: &AppHandle, id: &str) {
+    match id {
+        "show" => show_main(app),
+        "open-user-settings" => {
+            show_main(app);
+            let _ = app.emit_to(
+                "main",
+                "tray_action",
+                serde_json::json!({ "action": "open_user_settings" }),
+            );
+        }
+        "toggle-watching" => {
+            debounce::set_paused(!debounce::is_paused());
+            tracing::info!(
+                paused = debounce::is_paused(),
+                "file watching toggled from tray"
+            );
+            refresh(app);
+        }
+        "quit" => app.exit(0),
+        _ => {
+            if let Some(path) = id.strip_prefix("recent:") {
+                if let Err(e) = open_window(app, path.to_string()) {
+                    tracing::warn!(error = %e, path, "failed to open recent project");
+                }
+            }
+        }
+    }
+}
+
+/// Creates the tray icon. Called once from `setup`.
+pub fn init(app: &AppHandle) -> tauri::Result<()> {
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("ssenrah")
+        .menu(&build_menu(app)?)
+        .show_menu_on_left_click(true)
+        .on_menu_event(|app, event| handle_menu_event(app, event.id().as_ref()));
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder.build(app)?;
+    Ok(())
+}
+
+/// Rebuilds the tray menu after the recent projects or watching state
+/// changed.
+pub fn refresh(app: &AppHandle) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    match build_menu(app) {
+        Ok(menu) => {
+            let _ = tray.set_menu(Some(menu));
+        }
+        Err(e) => tracing::warn!(error = %e, "failed to rebuild tray menu"),
+    }
+}
+
+/// Shows a desktop notification for changes made outside ssenrah when the
+/// window they are reported to is hidden or minimized. Self-writes never
+/// reach a batch, so every change here is external.
+pub fn notify_if_hidden(app: &AppHandle, target: &str, batch: &Batch) {
+    let Some(window) = app.get_webview_window(target) else {
+        return;
+    };
+    let visible = window.is_visible().unwrap_or(true) && !window.is_minimized().unwrap_or(false);
+    if visible {
+        return;
+    }
+    {
+        let mut last = LAST_NOTIFIED.lock().unwrap_or_else(|e| e.into_inner());
+        if last.is_some_and(|t| t.elapsed() < NOTIFY_INTERVAL) {
+            return;
+        }
+        *last = Some(Instant::now());
+    }
+    if let Err(e) = app
+        .notification()
+        .builder()
+        .title("Claude Code config changed")
+        .body(batch.summary())
+        .show()
+    {
+        tracing::warn!(error = %e, "failed to show change notification");
+    }
+}
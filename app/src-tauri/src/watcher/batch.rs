@@ -0,0 +1,160 @@
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// How often pending changes are flushed as one `file_changes_batch` event.
+pub const BATCH_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Most distinct paths listed in one batch. Beyond this the batch is marked
+/// `overflow` and the frontend should reload everything instead.
+pub const MAX_BATCH_PATHS: usize = 200;
+
+/// Payload of a `file_changes_batch` event.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Batch {
+    pub changes: Vec<Map<String, Value>>,
+    /// `true` if more than `MAX_BATCH_PATHS` paths changed and some were
+    /// left out.
+    pub overflow: bool,
+    /// Distinct paths that changed, including any left out.
+    pub total_paths: usize,
+}
+
+/// Change events collected between flushes, deduplicated by path.
+#[derive(Debug, Default)]
+pub struct PendingBatch {
+    changes: Vec<Map<String, Value>>,
+    index: HashMap<String, usize>,
+    /// Paths that did not fit once the batch was full.
+    dropped: HashSet<String>,
+}
+
+impl PendingBatch {
+    /// Adds one change event payload (with at least `path` and `kind`).
+    ///
+    /// A path already in the batch keeps one entry reflecting its latest
+    /// state, except that a file created and then modified within the
+    /// interval is still reported as created.
+    pub fn push(&mut self, change: Map<String, Value>) {
+        let path = change
+            .get("path")
+            .and_then(|p| p.as_str())
+            .unwrap_or_default()
+            .to_string();
+
+        if let Some(&i) = self.index.get(&path) {
+            let was_created =
+                self.changes[i].get("kind").and_then(|k| k.as_str()) == Some("created");
+            let modified = change.get("kind").and_then(|k| k.as_str()) == Some("modified");
+            self.changes[i] = change;
+            if was_created && modified {
+                self.changes[i].insert("kind".into(), "created".into());
+            }
+        } else if self.changes.len() < MAX_BATCH_PATHS {
+            self.index.insert(path, self.changes.len());
+            self.changes.push(change);
+        } else {
+            self.dropped.insert(path);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Takes the pending changes, leaving the batch empty. Returns `None` if
+    /// nothing changed since the last call.
+    pub fn take(&mut self) -> Option<Batch> {
+        if self.is_empty() {
+            return None;
+        }
+        let pending = std::mem::take(self);
+        Some(Batch {
+            total_paths: pending.changes.len() + pending.dropped.len(),
+            overflow: !pending.dropped.is_empty(),
+            changes: pending.changes,
+        })
+    }
+}
+
+impl Batch {
+    /// One-line description for a desktop notification, e.g.
+    /// "settings.json and 2 other files changed".
+    pub fn summary(&self) -> String {
+        let first = self
+            .changes
+            .first()
+            .and_then(|c| c.get("path"))
+            .and_then(|p| p.as_str())
+            .map(|p| {
+                std::path::Path::new(p)
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_else(|| p.to_string())
+            })
+            .unwrap_or_default();
+        match self.total_paths {
+            0 | 1 => format!("{} changed", first),
+            2 => format!("{} and 1 other file changed", first),
+            n => format!("{} and {} other files changed", first, n - 1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(path: &str, kind: &str) -> Map<String, Value> {
+        let mut m = Map::new();
+        m.insert("path".into(), path.into());
+        m.insert("kind".into(), kind.into());
+        m
+    }
+
+    #[test]
+    fn deduplicates_paths_keeping_latest_state() {
+        let mut batch = PendingBatch::default();
+        assert!(batch.take().is_none());
+
+        batch.push(change("/a", "created"));
+        batch.push(change("/a", "modified"));
+        batch.push(change("/b", "modified"));
+        batch.push(change("/b", "deleted"));
+
+        let taken = batch.take().unwrap();
+        let kinds: Vec<_> = taken
+            .changes
+            .iter()
+            .map(|c| c["kind"].as_str().unwrap())
+            .collect();
+        assert_eq!(kinds, ["created", "deleted"]);
+        assert!(!taken.overflow);
+        assert_eq!(taken.total_paths, 2);
+        assert!(batch.take().is_none());
+    }
+
+    #[test]
+    fn marks_overflow_past_the_limit() {
+        let mut batch = PendingBatch::default();
+        for i in 0..MAX_BATCH_PATHS + 5 {
+            batch.push(change(&format!("/f{}", i), "modified"));
+            batch.push(change(&format!("/f{}", i), "modified"));
+        }
+        let taken = batch.take().unwrap();
+        assert_eq!(taken.changes.len(), MAX_BATCH_PATHS);
+        assert!(taken.overflow);
+        assert_eq!(taken.total_paths, MAX_BATCH_PATHS + 5);
+        assert_eq!(taken.summary(), "f0 and 204 other files changed");
+    }
+
+    #[test]
+    fn summarizes_single_change() {
+        let mut batch = PendingBatch::default();
+        batch.push(change("/home/me/.claude/settings.json", "modified"));
+        assert_eq!(batch.take().unwrap().summary(), "settings.json changed");
+    }
+}
@@ -1,58 +1,354 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+
 use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::drift::DriftAlerts;
+use crate::io::self_write;
+use crate::schema::effective_cache;
+use crate::watcher::batch::{PendingBatch, BATCH_INTERVAL};
+use crate::watcher::payload;
+use crate::watcher::poll::{self, PollRoot, POLL_INTERVAL};
+use crate::watcher::scope::ScopeRoots;
 use tauri::AppHandle;
 use tauri::Emitter;
 
+/// While set, every watcher keeps collecting changes but holds them back;
+/// the collected batch is delivered on the first flush after resuming.
+static PAUSED: AtomicBool = AtomicBool::new(false);
+
+/// Pauses or resumes event delivery for all watchers.
+pub fn set_paused(paused: bool) {
+    PAUSED.store(paused, Ordering::SeqCst);
+}
+
+pub fn is_paused() -> bool {
+    PAUSED.load(Ordering::SeqCst)
+}
+
+/// Scopes a subscriber can ask for.
+pub const WATCH_SCOPES: &[&str] = &["user", "project", "local", "managed"];
+
+/// Watch modes a subscriber can ask for. "auto" picks polling for paths on
+/// network or WSL-mounted filesystems and native events otherwise.
+pub const WATCH_MODES: &[&str] = &["auto", "notify", "poll"];
+
+/// How changes are detected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchMode {
+    /// OS change notifications via `notify`.
+    Notify,
+    /// Rescan watched paths every `POLL_INTERVAL` and diff size, mtime, and
+    /// content hash. For filesystems where notifications are unreliable.
+    Poll,
+}
+
+/// File kinds a subscriber can ask for.
+pub const WATCH_KINDS: &[&str] = &["settings", "mcp", "agents", "skills", "memory"];
+
+/// Restricts which change events are emitted. `None` means "everything".
+#[derive(Debug, Clone, Default)]
+pub struct WatchFilter {
+    pub scopes: Option<Vec<String>>,
+    pub kinds: Option<Vec<String>>,
+}
+
+impl WatchFilter {
+    /// Returns `true` if events for this scope should be emitted.
+    pub fn wants_scope(&self, scope: &str) -> bool {
+        self.scopes
+            .as_ref()
+            .is_none_or(|s| s.iter().any(|x| x == scope))
+    }
+
+    /// Returns `true` if events for this file kind should be emitted.
+    /// Files of unknown kind are only emitted when no kind filter is set.
+    pub fn wants_kind(&self, kind: Option<&str>) -> bool {
+        match (&self.kinds, kind) {
+            (None, _) => true,
+            (Some(kinds), Some(k)) => kinds.iter().any(|x| x == k),
+            (Some(_), None) => false,
+        }
+    }
+}
+
+/// Classifies raw changes and queues the ones that pass the filter.
+struct ChangeSink {
+    roots: ScopeRoots,
+    filter: WatchFilter,
+    pending: Arc<Mutex<PendingBatch>>,
+}
+
+impl ChangeSink {
+    fn record(&self, path: &Path, kind: &'static str) {
+        let file_kind = detect_file_kind(path);
+        // Our own writes change the effective config too, so invalidate
+        // before they are filtered out.
+        if file_kind == Some("settings") {
+            effective_cache::invalidate();
+        }
+        if self_write::tracker().is_self_write(path) {
+            return;
+        }
+        let scope = self.roots.detect_scope(path);
+        if !self.filter.wants_scope(scope) || !self.filter.wants_kind(file_kind) {
+            return;
+        }
+        let mut event_payload = serde_json::Map::new();
+        event_payload.insert("path".into(), path.display().to_string().into());
+        event_payload.insert("kind".into(), kind.into());
+        event_payload.insert("scope".into(), scope.into());
+        event_payload.insert("fileKind".into(), file_kind.into());
+
+        self.pending
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(event_payload);
+    }
+}
+
+enum Backend {
+    Notify(RecommendedWatcher),
+    /// Paths rescanned by the polling thread, which stops once this is
+    /// dropped.
+    Poll(Arc<Mutex<Vec<PollRoot>>>),
+}
+
 pub struct DebouncedWatcher {
-    watcher: RecommendedWatcher,
+    backend: Backend,
 }
 
 impl DebouncedWatcher {
-    pub fn new(app: AppHandle) -> Result<Self, notify::Error> {
-        let watcher =
-            notify::recommended_watcher(move |res: Result<Event, notify::Error>| {
-                if let Ok(event) = res {
-                    let kind = match event.kind {
-                        EventKind::Create(_) => "created",
-                        EventKind::Modify(_) => "modified",
-                        EventKind::Remove(_) => "deleted",
-                        _ => return,
-                    };
-
-                    for path in &event.paths {
-                        let scope = detect_scope(path);
-                        let _ = app.emit(
-                            "file_change",
-                            serde_json::json!({
-                                "path": path.display().to_string(),
-                                "kind": kind,
-                                "scope": scope,
-                            }),
-                        );
-                    }
-                }
-            })?;
+    /// Creates a watcher emitting `file_changes_batch` events to the window
+    /// labelled `target` for changes that pass `filter`.
+    ///
+    /// Each change's scope is classified against `roots` by path prefix.
+    /// Changes are collected into a [`PendingBatch`] and flushed at most
+    /// once per `BATCH_INTERVAL` as a `batch::Batch`, so
+    /// a git checkout rewriting hundreds of files produces a handful of
+    /// events. With `include_content`, created/modified changes for known
+    /// file kinds also carry the re-read content (see
+    /// `payload::attach_content`), read once per flush.
+    ///
+    /// In `WatchMode::Poll` a background thread rescans the watched paths
+    /// instead and reports differences through the same batches.
+    pub fn new(
+        app: AppHandle,
+        target: String,
+        roots: ScopeRoots,
+        filter: WatchFilter,
+        include_content: bool,
+        mode: WatchMode,
+    ) -> Result<Self, notify::Error> {
+        let pending = Arc::new(Mutex::new(PendingBatch::default()));
+        spawn_flusher(
+            app,
+            target,
+            Arc::downgrade(&pending),
+            include_content,
+            roots.user_claude_json.clone(),
+            roots.project_root.clone(),
+        );
+        let sink = ChangeSink {
+            roots,
+            filter,
+            pending,
+        };
+
+        let backend = match mode {
+            WatchMode::Notify => {
+                let watcher =
+                    notify::recommended_watcher(move |res: Result<Event, notify::Error>| {
+                        if let Ok(event) = res {
+                            let kind = match event.kind {
+                                EventKind::Create(_) => "created",
+                                EventKind::Modify(_) => "modified",
+                                EventKind::Remove(_) => "deleted",
+                                _ => return,
+                            };
+                            for path in &event.paths {
+                                sink.record(path, kind);
+                            }
+                        }
+                    })?;
+                Backend::Notify(watcher)
+            }
+            WatchMode::Poll => {
+                let targets = Arc::new(Mutex::new(Vec::new()));
+                spawn_poller(sink, Arc::downgrade(&targets));
+                Backend::Poll(targets)
+            }
+        };
 
-        Ok(Self { watcher })
+        Ok(Self { backend })
     }
 
-    pub fn watch(&mut self, path: &std::path::Path) -> Result<(), notify::Error> {
-        self.watcher.watch(path, RecursiveMode::NonRecursive)
+    fn add(&mut self, path: &Path, recursive: bool) -> Result<(), notify::Error> {
+        match &mut self.backend {
+            Backend::Notify(watcher) => {
+                let mode = if recursive {
+                    RecursiveMode::Recursive
+                } else {
+                    RecursiveMode::NonRecursive
+                };
+                watcher.watch(path, mode)
+            }
+            Backend::Poll(targets) => {
+                targets
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .push(PollRoot {
+                        path: path.to_path_buf(),
+                        recursive,
+                    });
+                Ok(())
+            }
+        }
     }
 
-    pub fn watch_recursive(&mut self, path: &std::path::Path) -> Result<(), notify::Error> {
-        self.watcher.watch(path, RecursiveMode::Recursive)
+    /// How this watcher detects changes.
+    pub fn mode(&self) -> WatchMode {
+        match self.backend {
+            Backend::Notify(_) => WatchMode::Notify,
+            Backend::Poll(_) => WatchMode::Poll,
+        }
     }
+
+    pub fn watch(&mut self, path: &Path) -> Result<(), notify::Error> {
+        self.add(path, false)
+    }
+
+    pub fn watch_recursive(&mut self, path: &Path) -> Result<(), notify::Error> {
+        self.add(path, true)
+    }
+}
+
+/// Rescans `targets` every `POLL_INTERVAL` and records the differences
+/// until the watcher owning them is dropped. A scan after the target list
+/// changed only sets the baseline.
+fn spawn_poller(sink: ChangeSink, targets: Weak<Mutex<Vec<PollRoot>>>) {
+    std::thread::spawn(move || {
+        let mut previous: Option<(usize, poll::Snapshot)> = None;
+        loop {
+            std::thread::sleep(POLL_INTERVAL);
+            let Some(targets) = targets.upgrade() else {
+                return;
+            };
+            let roots = targets.lock().unwrap_or_else(|e| e.into_inner()).clone();
+            drop(targets);
+
+            let snapshot = poll::scan(&roots);
+            if let Some((count, old)) = &previous {
+                if *count == roots.len() {
+                    for (path, kind) in poll::diff(old, &snapshot) {
+                        sink.record(&path, kind);
+                    }
+                }
+            }
+            previous = Some((roots.len(), snapshot));
+        }
+    });
 }
 
-fn detect_scope(path: &std::path::Path) -> &'static str {
-    let path_str = path.display().to_string();
-    if path_str.contains("settings.local.json") || path_str.contains("CLAUDE.local.md") {
-        "local"
-    } else if path_str.contains(".claude/") || path_str.contains(".claude\\") {
-        "project"
-    } else if path_str.contains("managed") {
-        "managed"
+/// Flushes `pending` every `BATCH_INTERVAL` until the watcher owning it is
+/// dropped. Changes to `claude_json` (`~/.claude.json`) are tagged with the
+/// sections that changed since the previous flush. When settings changed,
+/// drift from alerting baselines pinned for `project_root` is reported as a
+/// `config_drift` event.
+fn spawn_flusher(
+    app: AppHandle,
+    target: String,
+    pending: Weak<Mutex<PendingBatch>>,
+    include_content: bool,
+    claude_json: Option<PathBuf>,
+    project_root: Option<PathBuf>,
+) {
+    let mut sections = claude_json
+        .as_deref()
+        .map(payload::ClaudeJsonSections::new)
+        .unwrap_or_default();
+    let mut drift_alerts = DriftAlerts::new(project_root);
+    std::thread::spawn(move || loop {
+        std::thread::sleep(BATCH_INTERVAL);
+        let Some(pending) = pending.upgrade() else {
+            return;
+        };
+        if is_paused() {
+            continue;
+        }
+        let batch = pending.lock().unwrap_or_else(|e| e.into_inner()).take();
+        let Some(mut batch) = batch else {
+            continue;
+        };
+
+        for change in &mut batch.changes {
+            let path = change
+                .get("path")
+                .and_then(|v| v.as_str())
+                .map(PathBuf::from);
+            let user = change.get("scope").and_then(|v| v.as_str()) == Some("user");
+            if let Some(path) =
+                path.filter(|p| user && p.file_name() == Some(".claude.json".as_ref()))
+            {
+                sections.attach(change, &path);
+            }
+        }
+
+        if include_content {
+            for change in &mut batch.changes {
+                let field =
+                    |name: &str| change.get(name).and_then(|v| v.as_str()).map(String::from);
+                let (path, file_kind, kind) = (field("path"), field("fileKind"), field("kind"));
+                if let (Some(path), Some(fk)) = (path, file_kind) {
+                    if kind.as_deref() != Some("deleted") {
+                        payload::attach_content(change, Path::new(&path), &fk);
+                    }
+                }
+            }
+        }
+
+        let settings_changed = batch
+            .changes
+            .iter()
+            .any(|c| c.get("fileKind").and_then(|v| v.as_str()) == Some("settings"));
+        let drift = if settings_changed {
+            drift_alerts.check()
+        } else {
+            Vec::new()
+        };
+        crate::tray::notify_if_hidden(&app, &target, &batch);
+        let _ = app.emit_to(target.as_str(), "file_changes_batch", batch);
+        for report in drift {
+            let _ = app.emit_to(target.as_str(), "config_drift", report);
+        }
+    });
+}
+
+/// Classifies a changed path into the panel that displays it, or `None` for
+/// files ssenrah does not manage (temp files, caches, ...).
+fn detect_file_kind(path: &Path) -> Option<&'static str> {
+    let file_name = path.file_name()?.to_str()?;
+
+    match file_name {
+        "settings.json" | "settings.local.json" | "managed-settings.json" => {
+            return Some("settings")
+        }
+        ".mcp.json" | ".claude.json" | "managed-mcp.json" => return Some("mcp"),
+        "CLAUDE.md" | "CLAUDE.local.md" => return Some("memory"),
+        _ => {}
+    }
+
+    if file_name.ends_with(".ssenrah-tmp") {
+        return None;
+    }
+
+    let in_dir = |name: &str| path.components().any(|c| c.as_os_str() == name);
+    if in_dir("agents") {
+        Some("agents")
+    } else if in_dir("commands") || in_dir("skills") {
+        Some("skills")
     } else {
-        "user"
+        None
     }
 }
@@ -1 +1,5 @@
+pub mod batch;
 pub mod debounce;
+pub mod payload;
+pub mod poll;
+pub mod scope;
@@ -0,0 +1,160 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use serde_json::{Map, Value};
+use xxhash_rust::xxh3::{xxh3_64, Xxh3};
+
+use crate::claude_json;
+
+/// Largest file whose content is inlined into a change event. Bigger files
+/// only carry their hash and the frontend falls back to a read command.
+pub const MAX_INLINE_BYTES: u64 = 256 * 1024;
+
+/// Adds `contentHash` and, for files up to `MAX_INLINE_BYTES`, a parsed
+/// `content` field to a change event payload.
+///
+/// JSON files are parsed (`~/.claude.json` is reduced to its `mcpServers`
+/// section, matching `read_mcp_config`); markdown files are inlined as text.
+/// Files that cannot be read or parsed get `content: null` and, if the parse
+/// failed, a `contentError` message. Larger files are hashed in chunks
+/// rather than read whole.
+pub fn attach_content(payload: &mut Map<String, Value>, path: &Path, file_kind: &str) {
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return;
+    };
+    if metadata.len() > MAX_INLINE_BYTES {
+        let Ok(hash) = file_hash(path) else {
+            return;
+        };
+        payload.insert("contentHash".to_string(), Value::String(hash));
+        payload.insert("contentTruncated".to_string(), Value::Bool(true));
+        payload.insert("content".to_string(), Value::Null);
+        return;
+    }
+
+    let bytes = match std::fs::read(path) {
+        Ok(b) => b,
+        Err(_) => return,
+    };
+    // The file may have grown since its size was checked.
+    let too_large = bytes.len() as u64 > MAX_INLINE_BYTES;
+
+    payload.insert(
+        "contentHash".to_string(),
+        Value::String(content_hash(&bytes)),
+    );
+    payload.insert("contentTruncated".to_string(), Value::Bool(too_large));
+
+    let content = if too_large {
+        Value::Null
+    } else {
+        let text = String::from_utf8_lossy(&bytes);
+        match file_kind {
+            "settings" | "mcp" => match serde_json::from_str::<Value>(&text) {
+                Ok(value) => {
+                    if path.file_name().is_some_and(|n| n == ".claude.json") {
+                        let servers = value.get("mcpServers").cloned().unwrap_or(Value::Null);
+                        serde_json::json!({ "mcpServers": servers })
+                    } else {
+                        value
+                    }
+                }
+                Err(e) => {
+                    payload.insert("contentError".to_string(), Value::String(e.to_string()));
+                    Value::Null
+                }
+            },
+            _ => Value::String(text.into_owned()),
+        }
+    };
+
+    payload.insert("content".to_string(), content);
+}
+
+/// Section hashes of `~/.claude.json` as last seen by a watcher, so its
+/// change events can say which sections (see `claude_json::SECTIONS`)
+/// changed rather than only that the file did.
+#[derive(Debug, Default)]
+pub struct ClaudeJsonSections(Option<BTreeMap<&'static str, u64>>);
+
+impl ClaudeJsonSections {
+    /// Starts from the current content of `path`.
+    pub fn new(path: &Path) -> Self {
+        Self(read_section_hashes(path))
+    }
+
+    /// Adds a `sections` list to a change event for `~/.claude.json` at
+    /// `path`. A deleted file changes every section it had; a file that
+    /// cannot be parsed (e.g. mid-write) gets `sections: null` and the last
+    /// good state is kept for the next comparison.
+    pub fn attach(&mut self, payload: &mut Map<String, Value>, path: &Path) {
+        let current = if path.exists() {
+            read_section_hashes(path)
+        } else {
+            Some(BTreeMap::new())
+        };
+        let Some(current) = current else {
+            payload.insert("sections".to_string(), Value::Null);
+            return;
+        };
+        let previous = self.0.take().unwrap_or_default();
+        let changed = claude_json::changed_sections(&previous, &current);
+        payload.insert("sections".to_string(), serde_json::json!(changed));
+        self.0 = Some(current);
+    }
+}
+
+fn read_section_hashes(path: &Path) -> Option<BTreeMap<&'static str, u64>> {
+    let text = std::fs::read_to_string(path).ok()?;
+    let json = serde_json::from_str(crate::io::text::strip_bom(&text)).ok()?;
+    Some(claude_json::section_hashes(&json))
+}
+
+/// Hex-encoded 64-bit hash of the raw file bytes, used by the frontend to
+/// skip refreshes when content did not actually change.
+pub fn content_hash(bytes: &[u8]) -> String {
+    format!("{:016x}", xxh3_64(bytes))
+}
+
+/// `content_hash` of the file at `path`, read in chunks.
+fn file_hash(path: &Path) -> std::io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Xxh3::new();
+    let mut buffer = vec![0; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            return Ok(format!("{:016x}", hasher.digest()));
+        }
+        hasher.update(&buffer[..read]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hashes_large_files_without_inlining_them() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+
+        let large = dir.join("CLAUDE.md");
+        let bytes = "# Notes\n".repeat(MAX_INLINE_BYTES as usize / 4);
+        std::fs::write(&large, &bytes).unwrap();
+        let mut payload = Map::new();
+        attach_content(&mut payload, &large, "memory");
+        assert_eq!(payload["contentHash"], content_hash(bytes.as_bytes()));
+        assert_eq!(payload["contentTruncated"], true);
+        assert!(payload["content"].is_null());
+
+        let small = dir.join("settings.json");
+        std::fs::write(&small, r#"{ "model": "opus" }"#).unwrap();
+        let mut payload = Map::new();
+        attach_content(&mut payload, &small, "settings");
+        assert_eq!(payload["contentTruncated"], false);
+        assert_eq!(payload["content"]["model"], "opus");
+    }
+}
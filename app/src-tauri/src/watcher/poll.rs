@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use xxhash_rust::xxh3::xxh3_64;
+
+/// How often the polling fallback rescans watched paths.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Files larger than this are compared by size and mtime only.
+const MAX_HASHED_BYTES: u64 = 4 * 1024 * 1024;
+
+/// What the poller remembers about one file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileStamp {
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+    /// xxh3 of the content; `None` for files over `MAX_HASHED_BYTES` or
+    /// that could not be read.
+    pub hash: Option<u64>,
+}
+
+/// Every file under the watched paths, keyed by path.
+pub type Snapshot = HashMap<PathBuf, FileStamp>;
+
+/// A path to poll: a file, or a directory scanned one level deep or
+/// recursively.
+#[derive(Debug, Clone)]
+pub struct PollRoot {
+    pub path: PathBuf,
+    pub recursive: bool,
+}
+
+fn stamp(path: &Path, meta: &fs::Metadata) -> FileStamp {
+    let hash = (meta.len() <= MAX_HASHED_BYTES)
+        .then(|| fs::read(path).ok().map(|bytes| xxh3_64(&bytes)))
+        .flatten();
+    FileStamp {
+        size: meta.len(),
+        modified: meta.modified().ok(),
+        hash,
+    }
+}
+
+fn scan_into(path: &Path, recursive: bool, depth: usize, out: &mut Snapshot) {
+    let Ok(meta) = fs::metadata(path) else {
+        return;
+    };
+    if meta.is_file() {
+        out.insert(path.to_path_buf(), stamp(path, &meta));
+    } else if meta.is_dir() && (depth == 0 || recursive) {
+        if let Ok(entries) = fs::read_dir(path) {
+            for entry in entries.flatten() {
+                scan_into(&entry.path(), recursive, depth + 1, out);
+            }
+        }
+    }
+}
+
+/// Stamps every file under `roots`. Unreadable paths are skipped.
+pub fn scan(roots: &[PollRoot]) -> Snapshot {
+    let mut out = Snapshot::new();
+    for root in roots {
+        scan_into(&root.path, root.recursive, 0, &mut out);
+    }
+    out
+}
+
+/// Lists the changes between two snapshots as `(path, kind)` pairs, where
+/// kind is "created", "modified", or "deleted", sorted by path.
+pub fn diff(old: &Snapshot, new: &Snapshot) -> Vec<(PathBuf, &'static str)> {
+    let mut changes: Vec<(PathBuf, &'static str)> = new
+        .iter()
+        .filter_map(|(path, stamp)| match old.get(path) {
+            None => Some((path.clone(), "created")),
+            Some(previous) if previous != stamp => Some((path.clone(), "modified")),
+            Some(_) => None,
+        })
+        .chain(
+            old.keys()
+                .filter(|path| !new.contains_key(*path))
+                .map(|path| (path.clone(), "deleted")),
+        )
+        .collect();
+    changes.sort();
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_created_modified_and_deleted_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        fs::create_dir_all(dir.join("agents")).unwrap();
+        fs::write(dir.join("settings.json"), "{}").unwrap();
+        fs::write(dir.join("agents/a.md"), "a").unwrap();
+
+        let shallow = [PollRoot {
+            path: dir.to_path_buf(),
+            recursive: false,
+        }];
+        let deep = [PollRoot {
+            path: dir.to_path_buf(),
+            recursive: true,
+        }];
+        assert_eq!(scan(&shallow).len(), 1);
+        let before = scan(&deep);
+        assert_eq!(before.len(), 2);
+
+        // Same size: only the content hash tells the write apart.
+        fs::write(dir.join("settings.json"), "[]").unwrap();
+        fs::remove_file(dir.join("agents/a.md")).unwrap();
+        fs::write(dir.join("agents/b.md"), "b").unwrap();
+
+        let kinds: Vec<_> = diff(&before, &scan(&deep))
+            .into_iter()
+            .map(|(p, k)| (p.strip_prefix(dir).unwrap().to_path_buf(), k))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                (PathBuf::from("agents/a.md"), "deleted"),
+                (PathBuf::from("agents/b.md"), "created"),
+                (PathBuf::from("settings.json"), "modified"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,148 @@
+use std::path::{Path, PathBuf};
+
+/// Resolved root directories used to classify changed paths into scopes.
+///
+/// Built once per subscription from the same path resolution the IPC
+/// commands use, so events agree with where reads and writes happen even
+/// when `CLAUDE_CONFIG_DIR` is set or a project directory name happens to
+/// contain words like "managed".
+#[derive(Debug, Clone, Default)]
+pub struct ScopeRoots {
+    pub config_dir: Option<PathBuf>,
+    pub user_claude_json: Option<PathBuf>,
+    pub project_root: Option<PathBuf>,
+    pub managed_dir: Option<PathBuf>,
+}
+
+impl ScopeRoots {
+    /// Canonicalizes every root so prefix comparison is not fooled by
+    /// symlinks (e.g. /var -> /private/var on macOS) or relative paths.
+    pub fn new(
+        config_dir: Option<PathBuf>,
+        user_claude_json: Option<PathBuf>,
+        project_root: Option<PathBuf>,
+        managed_dir: Option<PathBuf>,
+    ) -> Self {
+        Self {
+            config_dir: config_dir.map(|p| canonicalize_lenient(&p)),
+            user_claude_json: user_claude_json.map(|p| canonicalize_lenient(&p)),
+            project_root: project_root.map(|p| canonicalize_lenient(&p)),
+            managed_dir: managed_dir.map(|p| canonicalize_lenient(&p)),
+        }
+    }
+
+    /// Classifies a changed path as "user", "project", "local", or "managed".
+    ///
+    /// The most specific (longest) matching root wins, so a config dir nested
+    /// inside an open project is still reported as user scope. Paths outside
+    /// every root fall back to "user".
+    pub fn detect_scope(&self, path: &Path) -> &'static str {
+        let path = canonicalize_lenient(path);
+
+        let mut candidates: Vec<(&Path, &'static str)> = Vec::new();
+        if let Some(dir) = &self.managed_dir {
+            candidates.push((dir, "managed"));
+        }
+        if let Some(dir) = &self.config_dir {
+            candidates.push((dir, "user"));
+        }
+        if let Some(file) = &self.user_claude_json {
+            candidates.push((file, "user"));
+        }
+        if let Some(root) = &self.project_root {
+            candidates.push((root, "project"));
+        }
+
+        let best = candidates
+            .into_iter()
+            .filter(|(root, _)| path.starts_with(root))
+            .max_by_key(|(root, _)| root.components().count())
+            .map(|(_, scope)| scope)
+            .unwrap_or("user");
+
+        if best == "project" && is_local_file(&path) {
+            "local"
+        } else {
+            best
+        }
+    }
+}
+
+/// Files that belong to the personal (gitignored) local scope.
+fn is_local_file(path: &Path) -> bool {
+    matches!(
+        path.file_name().and_then(|n| n.to_str()),
+        Some("settings.local.json") | Some("CLAUDE.local.md")
+    )
+}
+
+/// Canonicalizes a path, falling back to canonicalizing its parent (the file
+/// itself may already be deleted) and finally to the path as given.
+fn canonicalize_lenient(path: &Path) -> PathBuf {
+    if let Ok(p) = path.canonicalize() {
+        return p;
+    }
+    if let (Some(parent), Some(name)) = (path.parent(), path.file_name()) {
+        if let Ok(p) = parent.canonicalize() {
+            return p.join(name);
+        }
+    }
+    path.to_path_buf()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roots() -> ScopeRoots {
+        ScopeRoots::new(
+            Some(PathBuf::from("/nonexistent/home/.claude")),
+            Some(PathBuf::from("/nonexistent/home/.claude.json")),
+            Some(PathBuf::from("/nonexistent/work/managed-api")),
+            Some(PathBuf::from("/nonexistent/etc/claude-code")),
+        )
+    }
+
+    #[test]
+    fn project_named_managed_is_project() {
+        let scope = roots().detect_scope(Path::new(
+            "/nonexistent/work/managed-api/.claude/settings.json",
+        ));
+        assert_eq!(scope, "project");
+    }
+
+    #[test]
+    fn local_files_in_project_are_local() {
+        let r = roots();
+        let base = "/nonexistent/work/managed-api";
+        assert_eq!(
+            r.detect_scope(Path::new(&format!("{}/.claude/settings.local.json", base))),
+            "local"
+        );
+        assert_eq!(
+            r.detect_scope(Path::new(&format!("{}/.claude/CLAUDE.local.md", base))),
+            "local"
+        );
+    }
+
+    #[test]
+    fn user_config_dir_is_user_even_inside_project() {
+        let r = ScopeRoots::new(
+            Some(PathBuf::from("/nonexistent/home/.claude")),
+            None,
+            Some(PathBuf::from("/nonexistent/home")),
+            None,
+        );
+        assert_eq!(
+            r.detect_scope(Path::new("/nonexistent/home/.claude/settings.json")),
+            "user"
+        );
+    }
+
+    #[test]
+    fn managed_dir_is_managed() {
+        let scope =
+            roots().detect_scope(Path::new("/nonexistent/etc/claude-code/managed-settings.json"));
+        assert_eq!(scope, "managed");
+    }
+}
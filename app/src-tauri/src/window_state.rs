@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+/// Size and position of a window, in physical pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowGeometry {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub maximized: bool,
+}
+
+/// A monitor's area: (x, y, width, height) in physical pixels.
+pub type MonitorArea = (i32, i32, u32, u32);
+
+/// Key under which a window's geometry is remembered: its project root, or
+/// the empty string for a window with no project.
+pub fn geometry_key(project_root: Option<&str>) -> String {
+    project_root.unwrap_or_default().to_string()
+}
+
+/// Whether enough of the window's title bar lands on one of `monitors` to
+/// grab it. Geometry saved on a since-disconnected display fails this and
+/// is restored at the default position instead.
+pub fn is_reachable(geometry: &WindowGeometry, monitors: &[MonitorArea]) -> bool {
+    // A point inside the title bar, a little in from the left edge.
+    let (x, y) = (
+        i64::from(geometry.x) + 40.min(i64::from(geometry.width) / 2),
+        i64::from(geometry.y) + 10,
+    );
+    monitors.iter().any(|&(mx, my, mw, mh)| {
+        let (mx, my) = (i64::from(mx), i64::from(my));
+        x >= mx && x < mx + i64::from(mw) && y >= my && y < my + i64::from(mh)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_geometry_off_every_monitor() {
+        let monitors = [(0, 0, 1920, 1080), (1920, 0, 2560, 1440)];
+        let geometry = |x, y| WindowGeometry {
+            x,
+            y,
+            width: 1200,
+            height: 800,
+            maximized: false,
+        };
+        assert!(is_reachable(&geometry(100, 100), &monitors));
+        assert!(is_reachable(&geometry(3000, 200), &monitors));
+        assert!(!is_reachable(&geometry(5000, 100), &monitors));
+        assert!(!is_reachable(&geometry(100, -400), &monitors));
+        assert_eq!(geometry_key(None), "");
+    }
+}
@@ -0,0 +1,45 @@
+import { useEffect, useState } from "react";
+import { AlertTriangle, Info } from "lucide-react";
+import type { EffectiveHooks } from "@/lib/ipc/effective";
+import { computeEffectiveHooks } from "@/lib/ipc/effective";
+
+interface HookCompositionProps {
+  /** Changes whenever the hooks being edited change, to trigger a reload. */
+  hooks: unknown;
+}
+
+/** Summarizes how the hooks of all scopes combine when Claude Code runs. */
+export function HookComposition({ hooks }: HookCompositionProps) {
+  const [composed, setComposed] = useState<EffectiveHooks | null>(null);
+
+  useEffect(() => {
+    computeEffectiveHooks()
+      .then(setComposed)
+      .catch(() => setComposed(null));
+  }, [hooks]);
+
+  if (!composed || composed.hooks.length === 0) return null;
+
+  const running = composed.hooks.filter((h) => h.runs).length;
+  const scopes = [...new Set(composed.hooks.map((h) => h.scope))];
+
+  return (
+    <div className="space-y-2 rounded-md border border-border/60 p-3 text-xs">
+      <p className="flex items-center gap-1.5 text-muted-foreground">
+        <Info className="h-3.5 w-3.5" />
+        At runtime {running} of {composed.hooks.length} hooks run, from {scopes.join(", ")}.
+        Hooks from every scope run; a higher scope does not replace a lower one's.
+      </p>
+      {composed.restriction && <p className="text-muted-foreground">{composed.restriction}.</p>}
+      {composed.findings.map((finding) => (
+        <div key={`${finding.kind}:${finding.locations.join(",")}`} className="flex items-start gap-1.5">
+          <AlertTriangle className="mt-0.5 h-3.5 w-3.5 shrink-0 text-yellow-500" />
+          <div>
+            <p>{finding.message}</p>
+            <p className="font-mono text-muted-foreground">{finding.locations.join("  ")}</p>
+          </div>
+        </div>
+      ))}
+    </div>
+  );
+}
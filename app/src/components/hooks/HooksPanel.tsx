@@ -1,6 +1,7 @@
 import { useSettingsStore } from "@/lib/store/settings";
 import { useUiStore } from "@/lib/store/ui";
 import { HookEditor } from "@/components/shared/HookEditor";
+import { HookComposition } from "./HookComposition";
 import { EmptyState } from "@/components/shared/EmptyState";
 import { ErrorBanner } from "@/components/shared/ErrorBanner";
 import { Switch } from "@/components/ui/switch";
@@ -62,6 +63,8 @@ export function HooksPanel() {
 
       <Separator />
 
+      <HookComposition hooks={hooks} />
+
       {/* Hook editor */}
       {disableAll ? (
         <p className="text-sm text-muted-foreground py-4 text-center">
@@ -5,6 +5,7 @@ import { ErrorBanner } from "@/components/shared/ErrorBanner";
 import { Tabs, TabsContent, TabsList, TabsTrigger } from "@/components/ui/tabs";
 import { MarkdownEditor } from "@/components/shared/MarkdownEditor";
 import { Badge } from "@/components/ui/badge";
+import { NestedMemoryList } from "./NestedMemoryList";
 import type { MemoryScope } from "@/types";
 
 const SCOPES: { value: MemoryScope; label: string; description: string }[] = [
@@ -28,6 +29,7 @@ function ScopeEditor({ scope }: { scope: MemoryScope }) {
   const content = useMemoryStore((s) => s.getForScope(scope));
   const status = useMemoryStore((s) => s.status[scope]);
   const isDirty = useMemoryStore((s) => s.isDirty(scope));
+  const oversizedBytes = useMemoryStore((s) => s.oversized[scope]);
   const update = useMemoryStore((s) => s.update);
   const load = useMemoryStore((s) => s.load);
 
@@ -59,10 +61,16 @@ function ScopeEditor({ scope }: { scope: MemoryScope }) {
           <span className="text-xs text-muted-foreground">Loading...</span>
         )}
       </div>
+      {oversizedBytes !== undefined && (
+        <p className="text-xs text-orange-400">
+          This file is {(oversizedBytes / (1024 * 1024)).toFixed(1)} MB, too large to edit here.
+          Showing the beginning read-only.
+        </p>
+      )}
       <MarkdownEditor
         value={content ?? ""}
         onChange={handleChange}
-        disabled={isLoading}
+        disabled={isLoading || oversizedBytes !== undefined}
         placeholder={
           content === null
             ? "No memory file exists at this scope yet. Start typing to create one."
@@ -99,6 +107,7 @@ export function MemoryPanel() {
               {s.label}
             </TabsTrigger>
           ))}
+          {projectRoot && <TabsTrigger value="nested">Nested</TabsTrigger>}
         </TabsList>
 
         {SCOPES.map((s) => (
@@ -107,6 +116,15 @@ export function MemoryPanel() {
             <ScopeEditor scope={s.value} />
           </TabsContent>
         ))}
+
+        {projectRoot && (
+          <TabsContent value="nested" className="space-y-3 pt-2">
+            <p className="text-xs text-muted-foreground">
+              CLAUDE.md files in subdirectories, which Claude reads when it works in them
+            </p>
+            <NestedMemoryList projectRoot={projectRoot} />
+          </TabsContent>
+        )}
       </Tabs>
     </div>
   );
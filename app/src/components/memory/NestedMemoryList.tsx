@@ -0,0 +1,57 @@
+import { useCallback, useEffect, useState } from "react";
+import { discoverMemoryFiles, type MemoryDiscovery } from "@/lib/ipc/memory";
+import { ErrorBanner } from "@/components/shared/ErrorBanner";
+import { Badge } from "@/components/ui/badge";
+
+/** Every CLAUDE.md and CLAUDE.local.md in the project tree. */
+export function NestedMemoryList({ projectRoot }: { projectRoot: string }) {
+  const [discovery, setDiscovery] = useState<MemoryDiscovery | null>(null);
+  const [error, setError] = useState<unknown>(null);
+
+  const load = useCallback(() => {
+    setError(null);
+    discoverMemoryFiles().then(setDiscovery).catch(setError);
+  }, []);
+
+  useEffect(() => {
+    load();
+  }, [load, projectRoot]);
+
+  if (error) return <ErrorBanner error={error} onRetry={load} />;
+  if (!discovery) return <p className="text-xs text-muted-foreground">Searching...</p>;
+  if (discovery.files.length === 0) {
+    return <p className="text-xs text-muted-foreground">No memory files found in this project.</p>;
+  }
+
+  return (
+    <div className="space-y-2">
+      {discovery.files.map((file) => (
+        <div key={file.path} className="rounded border border-border bg-muted/30 px-3 py-2">
+          <div className="flex items-center gap-2">
+            <span className="flex-1 truncate font-mono text-sm" title={file.path}>
+              {file.relativePath}
+            </span>
+            {file.local && (
+              <Badge variant="outline" className="text-[10px]">
+                local
+              </Badge>
+            )}
+            <span className="text-xs text-muted-foreground">
+              {(file.sizeBytes / 1024).toFixed(1)} KB
+            </span>
+          </div>
+          {file.preview && (
+            <p className="mt-1 line-clamp-2 whitespace-pre-wrap text-xs text-muted-foreground">
+              {file.preview}
+            </p>
+          )}
+        </div>
+      ))}
+      {discovery.truncated && (
+        <p className="text-xs text-orange-400">
+          Stopped after {discovery.files.length} files; there may be more.
+        </p>
+      )}
+    </div>
+  );
+}
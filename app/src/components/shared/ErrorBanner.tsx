@@ -26,6 +26,7 @@ export function ErrorBanner({
       <AlertTitle>Error</AlertTitle>
       <AlertDescription>
         <p className="mb-2">{message}</p>
+        {error.suggestion && <p className="mb-2 text-sm">{error.suggestion}</p>}
         <div className="flex gap-2">
           {onRetry && (
             <Button variant="outline" size="sm" onClick={onRetry}>
@@ -69,5 +70,16 @@ function getErrorMessage(error: IpcError): string {
       return error.message;
     case "platform_error":
       return error.message;
+    case "read_only":
+      return error.message;
+    case "timeout":
+      return `Timed out after ${error.seconds}s (${error.operation}). The file system may be slow or unreachable.`;
+    case "conflict":
+      return `Conflict at ${error.path}: ${error.message}`;
+    case "locked":
+      return `${error.path} is in use: ${error.message}`;
+    case "invalid_argument":
+    case "unsupported":
+      return error.message;
   }
 }
@@ -1,9 +1,9 @@
-import { useState } from "react";
+import { useEffect, useState } from "react";
 import { Input } from "@/components/ui/input";
 import { Button } from "@/components/ui/button";
 import { Select } from "@/components/ui/select";
 import { X, Plus, GripVertical } from "lucide-react";
-import { KNOWN_TOOLS } from "@/lib/schemas/permissions";
+import { listKnownTools, type ToolInfo } from "@/lib/ipc/validation";
 import { cn } from "@/lib/utils";
 
 interface RuleBuilderProps {
@@ -27,10 +27,19 @@ export function RuleBuilder({
 }: RuleBuilderProps) {
   const [tool, setTool] = useState("");
   const [specifier, setSpecifier] = useState("");
+  const [tools, setTools] = useState<ToolInfo[]>([]);
+
+  useEffect(() => {
+    listKnownTools().then(setTools).catch(() => setTools([]));
+  }, []);
+
+  const selected = tools.find((t) => t.name === tool);
+
+  const takesSpecifier = selected === undefined || selected.specifier !== null;
 
   const handleAdd = () => {
     if (!tool) return;
-    const rule = specifier ? `${tool}(${specifier})` : tool;
+    const rule = specifier && takesSpecifier ? `${tool}(${specifier})` : tool;
     onChange([...rules, rule]);
     setTool("");
     setSpecifier("");
@@ -70,16 +79,17 @@ export function RuleBuilder({
             className="w-40"
           >
             <option value="">Select tool...</option>
-            {KNOWN_TOOLS.map((t) => (
-              <option key={t} value={t}>
-                {t}
+            {tools.map((t) => (
+              <option key={t.name} value={t.name} title={t.description}>
+                {t.name}
               </option>
             ))}
           </Select>
           <Input
             value={specifier}
             onChange={(e) => setSpecifier(e.target.value)}
-            placeholder="specifier (optional)"
+            placeholder={selected?.specifier ?? "specifier (optional)"}
+            disabled={!takesSpecifier}
             className="flex-1 font-mono text-sm"
           />
           <Button
@@ -1,6 +1,7 @@
 import { OverrideIndicator } from "./OverrideIndicator";
 import type { EffectiveConfig } from "@/lib/ipc/effective";
 import type { ConfigScope } from "@/types";
+import { useEffectiveStore } from "@/lib/store/effective";
 import { Card, CardContent, CardHeader, CardTitle } from "@/components/ui/card";
 
 interface EffectiveDiffProps {
@@ -15,14 +16,61 @@ function formatValue(value: unknown): string {
   return JSON.stringify(value, null, 2);
 }
 
+function ElementChanges({ added, removed }: { added?: unknown[]; removed?: unknown[] }) {
+  if (!added?.length && !removed?.length) return null;
+  return (
+    <div className="flex flex-wrap gap-1 text-xs font-mono">
+      {added?.map((v) => (
+        <span key={`+${JSON.stringify(v)}`} className="rounded bg-green-500/10 px-1.5 text-green-600">
+          + {formatValue(v)}
+        </span>
+      ))}
+      {removed?.map((v) => (
+        <span key={`-${JSON.stringify(v)}`} className="rounded bg-red-500/10 px-1.5 text-red-600">
+          − {formatValue(v)}
+        </span>
+      ))}
+    </div>
+  );
+}
+
 export function EffectiveDiff({ config }: EffectiveDiffProps) {
   const overrides = config.overrides;
+  const policy = useEffectiveStore((s) => s.policy);
+  const combined = policy?.fields.filter((f) => f.rule === "array_union").map((f) => f.path);
+  const precedence = policy && (
+    <p className="text-xs text-muted-foreground">
+      Precedence, lowest first: {policy.precedence.join(" → ")}.
+      {combined && combined.length > 0 && ` Entries of ${combined.join(", ")} are combined across scopes.`}
+    </p>
+  );
 
-  if (overrides.length === 0) {
+  const combinedCards = config.combined.map((array) => (
+    <Card key={array.path} className="border-border/60">
+      <CardHeader className="py-3 px-4">
+        <CardTitle className="text-sm">
+          <code className="text-xs font-mono">{array.path}</code>
+        </CardTitle>
+      </CardHeader>
+      <CardContent className="space-y-1.5 px-4 pb-3 pt-0">
+        {array.steps.map((step) => (
+          <div key={step.scope} className="flex items-start gap-2">
+            <span className="w-16 shrink-0 text-xs text-muted-foreground">{step.scope}</span>
+            <ElementChanges added={step.addedElements} removed={step.removedElements} />
+          </div>
+        ))}
+      </CardContent>
+    </Card>
+  ));
+
+  if (overrides.length === 0 && combinedCards.length === 0) {
     return (
-      <p className="text-sm text-muted-foreground py-6 text-center">
-        No overrides detected. Each field is set by a single scope.
-      </p>
+      <div className="space-y-3 py-6 text-center">
+        <p className="text-sm text-muted-foreground">
+          No overrides detected. Each field is set by a single scope.
+        </p>
+        {precedence}
+      </div>
     );
   }
 
@@ -32,6 +80,7 @@ export function EffectiveDiff({ config }: EffectiveDiffProps) {
         {overrides.length} field{overrides.length !== 1 ? "s" : ""} overridden
         by higher-precedence scopes.
       </p>
+      {precedence}
       {overrides.map((override) => (
         <Card key={override.path} className="border-border/60">
           <CardHeader className="py-3 px-4">
@@ -47,9 +96,13 @@ export function EffectiveDiff({ config }: EffectiveDiffProps) {
             <pre className="overflow-auto rounded border border-border/40 bg-muted/20 p-2 text-xs font-mono leading-relaxed text-foreground/80">
               {formatValue(override.effectiveValue)}
             </pre>
+            <div className="mt-2">
+              <ElementChanges added={override.addedElements} removed={override.removedElements} />
+            </div>
           </CardContent>
         </Card>
       ))}
+      {combinedCards}
     </div>
   );
 }
@@ -1,15 +1,105 @@
 import { listen } from "@tauri-apps/api/event";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
+import type { ClaudeJsonSectionName } from "@/lib/ipc/claude-json";
+import type { DriftReport } from "@/lib/ipc/effective";
+
+export type WatchScope = "user" | "project" | "local" | "managed";
+
+export type WatchFileKind = "settings" | "mcp" | "agents" | "skills" | "memory";
 
 export interface FileChangeEvent {
   path: string;
   kind: "created" | "modified" | "deleted";
   scope: string;
+  fileKind: WatchFileKind | null;
+  /** Present only when subscribed with `includeContent`. */
+  contentHash?: string;
+  contentTruncated?: boolean;
+  content?: unknown;
+  contentError?: string;
+  /**
+   * For user `~/.claude.json` only: the sections that changed, or `null`
+   * when the file could not be parsed.
+   */
+  sections?: ClaudeJsonSectionName[] | null;
+}
+
+export interface FileChangesBatchEvent {
+  /** Each changed path once, in its latest state. */
+  changes: FileChangeEvent[];
+  /** More paths changed than fit in one batch; reload everything. */
+  overflow: boolean;
+  totalPaths: number;
+}
+
+/**
+ * Fired at most once per watcher interval with the coalesced changes.
+ * Each window only receives changes for its own subscription.
+ */
+export function onFileChangesBatch(
+  callback: (event: FileChangesBatchEvent) => void,
+): Promise<() => void> {
+  return getCurrentWebviewWindow().listen<FileChangesBatchEvent>(
+    "file_changes_batch",
+    (event) => {
+      callback(event.payload);
+    },
+  );
 }
 
+/** Calls `callback` for every change in each batch. */
 export function onFileChange(
   callback: (event: FileChangeEvent) => void,
 ): Promise<() => void> {
-  return listen<FileChangeEvent>("file_change", (event) => {
+  return onFileChangesBatch((batch) => {
+    batch.changes.forEach(callback);
+  });
+}
+
+export interface ConfigDirChangedEvent {
+  configDir: string;
+}
+
+export interface TrayActionEvent {
+  action: "open_user_settings";
+}
+
+/** Fired on the main window when a tray menu item asks it to navigate. */
+export function onTrayAction(
+  callback: (event: TrayActionEvent) => void,
+): Promise<() => void> {
+  return listen<TrayActionEvent>("tray_action", (event) => {
+    callback(event.payload);
+  });
+}
+
+/**
+ * Fired when a deep link targets this window; fetch it with
+ * `takePendingDeepLink`.
+ */
+export function onDeepLink(callback: () => void): Promise<() => void> {
+  return getCurrentWebviewWindow().listen("deep_link", () => {
+    callback();
+  });
+}
+
+/**
+ * Fired on a watching window when a settings change makes its effective
+ * config drift from a baseline pinned with `alert`. Each drift is reported once.
+ */
+export function onConfigDrift(
+  callback: (report: DriftReport) => void,
+): Promise<() => void> {
+  return getCurrentWebviewWindow().listen<DriftReport>("config_drift", (event) => {
+    callback(event.payload);
+  });
+}
+
+/** Fired after `set_config_dir_override`; re-subscribe and reload panels. */
+export function onConfigDirChanged(
+  callback: (event: ConfigDirChangedEvent) => void,
+): Promise<() => void> {
+  return listen<ConfigDirChangedEvent>("config_dir_changed", (event) => {
     callback(event.payload);
   });
 }
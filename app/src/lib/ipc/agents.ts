@@ -1,4 +1,5 @@
 import { invoke } from "./invoke";
+import type { TextFormat } from "@/types";
 
 export interface AgentEntry {
   filename: string;
@@ -14,7 +15,7 @@ export async function listAgents(scope?: string): Promise<AgentEntry[]> {
 export async function readAgent(
   scope: string,
   filename: string,
-): Promise<{ frontmatter: Record<string, unknown>; body: string }> {
+): Promise<{ frontmatter: Record<string, unknown>; body: string; format: TextFormat }> {
   return invoke("read_agent", { scope, filename });
 }
 
@@ -23,8 +24,26 @@ export async function writeAgent(
   filename: string,
   frontmatter: Record<string, unknown>,
   body: string,
+  format?: TextFormat,
 ): Promise<void> {
-  return invoke<void>("write_agent", { scope, filename, frontmatter, body });
+  return invoke<void>("write_agent", { scope, filename, frontmatter, body, format });
+}
+
+/** Replaces an agent's body, leaving its frontmatter untouched on disk. */
+export async function writeAgentBody(scope: string, filename: string, body: string): Promise<void> {
+  return invoke<void>("write_agent_body", { scope, filename, body });
+}
+
+/**
+ * Updates the given frontmatter keys (null removes a key), leaving other
+ * keys, comments, and the body untouched on disk.
+ */
+export async function writeAgentFrontmatter(
+  scope: string,
+  filename: string,
+  patch: Record<string, unknown>,
+): Promise<void> {
+  return invoke<void>("write_agent_frontmatter", { scope, filename, patch });
 }
 
 export async function deleteAgent(scope: string, filename: string): Promise<void> {
@@ -0,0 +1,91 @@
+import { invoke } from "./invoke";
+
+/** Groups of top-level keys in ~/.claude.json. */
+export type ClaudeJsonSectionName =
+  | "mcpServers"
+  | "projects"
+  | "onboarding"
+  | "account"
+  | "other";
+
+/** Per-directory state under `projects`; unknown keys are passed through. */
+export interface ClaudeJsonProject {
+  hasTrustDialogAccepted?: boolean;
+  allowedTools?: string[];
+  history?: unknown[];
+  mcpServers?: Record<string, unknown>;
+  enabledMcpjsonServers?: string[];
+  disabledMcpjsonServers?: string[];
+  hasClaudeMdExternalIncludesApproved?: boolean;
+  projectOnboardingSeenCount?: number;
+  [key: string]: unknown;
+}
+
+export type ClaudeJsonSection =
+  | { section: "mcpServers"; value: Record<string, unknown> }
+  | { section: "projects"; value: Record<string, ClaudeJsonProject> }
+  | {
+      section: "onboarding";
+      value: {
+        hasCompletedOnboarding: boolean | null;
+        lastOnboardingVersion: string | null;
+        lastReleaseNotesSeen: string | null;
+        numStartups: number | null;
+        firstStartTime: string | null;
+        tipsHistory: Record<string, unknown> | null;
+      };
+    }
+  | {
+      section: "account";
+      value: { oauthAccount: Record<string, unknown> | null; userID: string | null };
+    }
+  | { section: "other"; value: Record<string, unknown> };
+
+/** Reads one section of ~/.claude.json, or `null` if the file does not exist. */
+export async function readClaudeJson(
+  section: ClaudeJsonSectionName,
+): Promise<ClaudeJsonSection | null> {
+  return invoke<ClaudeJsonSection | null>("read_claude_json", { section });
+}
+
+/** Sets keys of one `projects` entry; `null` values remove the key. */
+export async function updateClaudeJsonProject(
+  dir: string,
+  patch: Record<string, unknown>,
+): Promise<ClaudeJsonProject> {
+  return invoke<ClaudeJsonProject>("update_claude_json_project", { dir, patch });
+}
+
+export async function removeClaudeJsonProject(dir: string): Promise<void> {
+  return invoke<void>("remove_claude_json_project", { dir });
+}
+
+export interface ProjectTrust {
+  path: string;
+  /** The `projects` key recorded for the directory itself, if any. */
+  key: string | null;
+  trusted: boolean;
+  /** Trusted parent directory the trust is inherited from. */
+  inheritedFrom: string | null;
+}
+
+export interface TrustChange {
+  trust: ProjectTrust;
+  backupPath: string | null;
+}
+
+export async function getProjectTrust(path: string): Promise<ProjectTrust> {
+  return invoke<ProjectTrust>("get_project_trust", { path });
+}
+
+/**
+ * Trusts or distrusts `path` for Claude Code. Pass `confirmed: true` only
+ * after the user agreed; ~/.claude.json is backed up first.
+ */
+export async function setProjectTrust(
+  path: string,
+  trusted: boolean,
+  confirmed: boolean,
+): Promise<TrustChange> {
+  return invoke<TrustChange>("set_project_trust", { path, trusted, confirmed });
+}
@@ -5,6 +5,10 @@ export interface EffectiveConfig {
   settings: Record<string, unknown>;
   sources: Record<string, ConfigScope>;
   overrides: Override[];
+  combined: CombinedArray[];
+  /** "hit" when nothing changed since the previous call. */
+  cache: "hit" | "miss";
+  computeMs: number;
 }
 
 export interface Override {
@@ -12,8 +16,189 @@ export interface Override {
   effectiveScope: ConfigScope;
   overriddenScopes: ConfigScope[];
   effectiveValue: unknown;
+  /** Array fields only: entries the winning scope added / dropped. */
+  addedElements?: unknown[];
+  removedElements?: unknown[];
+}
+
+/** An array such as `permissions.allow` whose entries come from several scopes. */
+export interface CombinedArray {
+  path: string;
+  steps: { scope: ConfigScope; addedElements: unknown[]; removedElements: unknown[] }[];
+  effectiveValue: unknown[];
+}
+
+export type MergeRule = "replace" | "array_replace" | "array_union" | "deep_merge";
+
+export interface MergePolicy {
+  /** Scopes from lowest to highest precedence. */
+  precedence: ConfigScope[];
+  /** Fields not listed use "replace". */
+  fields: { path: string; rule: MergeRule }[];
+}
+
+/** One settings key across scopes; a missing column means the scope does not set it. */
+export interface ScopeMatrixRow {
+  path: string;
+  user?: unknown;
+  project?: unknown;
+  local?: unknown;
+  managed?: unknown;
+  /** Missing when a scope deleted the key with `null`. */
+  effective?: unknown;
+  source: ConfigScope | null;
+  rule: MergeRule;
+}
+
+/** A hook as Claude Code runs it: every scope's hook groups run. */
+export interface ComposedHook {
+  scope: ConfigScope;
+  event: string;
+  /** `null` matches every tool. */
+  matcher: string | null;
+  path: string;
+  hook: Record<string, unknown>;
+  runs: boolean;
+}
+
+export interface HookFinding {
+  kind: "duplicate" | "conflict";
+  event: string;
+  matcher: string | null;
+  locations: string[];
+  message: string;
+}
+
+export interface EffectiveHooks {
+  hooks: ComposedHook[];
+  findings: HookFinding[];
+  restriction: string | null;
+}
+
+/** One effective settings key that a pending edit would change. */
+export interface SettingChange {
+  path: string;
+  kind: "added" | "removed" | "changed";
+  left?: unknown;
+  right?: unknown;
+}
+
+export interface EditPreview {
+  effective: Omit<EffectiveConfig, "cache" | "computeMs">;
+  changes: SettingChange[];
 }
 
 export async function computeEffectiveConfig(): Promise<EffectiveConfig> {
   return invoke<EffectiveConfig>("compute_effective_config");
 }
+
+export async function getMergePolicy(): Promise<MergePolicy> {
+  return invoke<MergePolicy>("get_merge_policy");
+}
+
+export async function computeScopeMatrix(): Promise<ScopeMatrixRow[]> {
+  return invoke<ScopeMatrixRow[]>("compute_scope_matrix");
+}
+
+export async function computeEffectiveHooks(): Promise<EffectiveHooks> {
+  return invoke<EffectiveHooks>("compute_effective_hooks");
+}
+
+/** Effective settings pinned under a name, to detect drift against. */
+export interface Baseline {
+  name: string;
+  pinnedAt: number;
+  projectRoot: string | null;
+  hash: string;
+  /** Emit `config_drift` events when the effective config drifts. */
+  alert: boolean;
+  settings: Record<string, unknown>;
+}
+
+export interface DriftReport {
+  name: string;
+  pinnedAt: number;
+  baselineHash: string;
+  currentHash: string;
+  drifted: boolean;
+  changes: SettingChange[];
+}
+
+export async function pinBaseline(name: string, alert?: boolean): Promise<Baseline> {
+  return invoke<Baseline>("pin_baseline", { name, alert });
+}
+
+export async function checkDrift(name: string): Promise<DriftReport> {
+  return invoke<DriftReport>("check_drift", { name });
+}
+
+export async function listBaselines(): Promise<Baseline[]> {
+  return invoke<Baseline[]>("list_baselines");
+}
+
+/** What the effective config would become if `proposed` were saved to `scope`. */
+export async function previewEffectiveWithEdit(
+  scope: ConfigScope,
+  proposed: Record<string, unknown>,
+): Promise<EditPreview> {
+  return invoke<EditPreview>("preview_effective_with_edit", { scope, proposed });
+}
+
+/** A script file run by one or more hooks. */
+export interface HookScript {
+  path: string;
+  exists: boolean;
+  executable: boolean;
+  hooks: {
+    scope: ConfigScope;
+    event: string;
+    matcher: string | null;
+    path: string;
+    command: string;
+    runs: boolean;
+  }[];
+}
+
+export interface HookScriptFile {
+  path: string;
+  /** `null` when the file does not exist yet. */
+  content: string | null;
+  executable: boolean;
+  warnings: string[];
+}
+
+export async function listHookScripts(): Promise<HookScript[]> {
+  return invoke<HookScript[]>("list_hook_scripts");
+}
+
+export async function readHookScript(path: string): Promise<HookScriptFile> {
+  return invoke<HookScriptFile>("read_hook_script", { path });
+}
+
+/**
+ * Writes a hook script listed by `listHookScripts`. Without `executable`, a
+ * new script is made executable and an existing one keeps its bit.
+ */
+export async function writeHookScript(
+  path: string,
+  content: string,
+  executable?: boolean,
+): Promise<HookScriptFile> {
+  return invoke<HookScriptFile>("write_hook_script", { path, content, executable });
+}
+
+export interface HookCommandLint {
+  code:
+    | "UNQUOTED_VARIABLE"
+    | "JQ_NOT_INSTALLED"
+    | "NON_BLOCKING_EXIT"
+    | "MISSING_EXIT_CODE"
+    | "FIELD_NOT_PROVIDED"
+    | "STDIN_READ_TWICE";
+  message: string;
+}
+
+/** Lints a hook command; `event` enables the event-specific checks. */
+export async function lintHookCommand(command: string, event?: string): Promise<HookCommandLint[]> {
+  return invoke<HookCommandLint[]>("lint_hook_command", { command, event });
+}
@@ -10,6 +10,11 @@ export async function invoke<T>(cmd: string, args?: Record<string, unknown>): Pr
       throw error as IpcError;
     }
     // Unknown error
-    throw { kind: "platform_error", message: String(error) } as IpcError;
+    throw {
+      kind: "platform_error",
+      message: String(error),
+      retryable: false,
+      suggestion: null,
+    } as IpcError;
   }
 }
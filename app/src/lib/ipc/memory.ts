@@ -1,9 +1,61 @@
 import { invoke } from "./invoke";
+import type { TextFormat } from "@/types";
 
-export async function readMemory(scope: string): Promise<string | null> {
-  return invoke<string | null>("read_memory", { scope });
+export interface MemoryFile {
+  path: string;
+  sizeBytes: number;
+  /** `null` when the file is over the inline limit; page it with `readMemoryChunk`. */
+  content: string | null;
+  format: TextFormat;
 }
 
-export async function writeMemory(scope: string, content: string): Promise<void> {
-  return invoke<void>("write_memory", { scope, content });
+export interface MemoryChunk {
+  content: string;
+  offset: number;
+  nextOffset: number;
+  sizeBytes: number;
+  eof: boolean;
+}
+
+export async function readMemory(
+  scope: string,
+  maxInlineBytes?: number,
+): Promise<MemoryFile | null> {
+  return invoke<MemoryFile | null>("read_memory", { scope, maxInlineBytes });
+}
+
+export async function readMemoryChunk(
+  scope: string,
+  offset: number,
+  len: number,
+): Promise<MemoryChunk> {
+  return invoke<MemoryChunk>("read_memory_chunk", { scope, offset, len });
+}
+
+export interface NestedMemory {
+  path: string;
+  /** Relative to the project root, forward-slash separated. */
+  relativePath: string;
+  /** `true` for CLAUDE.local.md. */
+  local: boolean;
+  sizeBytes: number;
+  preview: string | null;
+}
+
+export interface MemoryDiscovery {
+  files: NestedMemory[];
+  /** The walk stopped at its file limit. */
+  truncated: boolean;
+}
+
+export async function discoverMemoryFiles(maxDepth?: number): Promise<MemoryDiscovery> {
+  return invoke<MemoryDiscovery>("discover_memory_files", { maxDepth });
+}
+
+export async function writeMemory(
+  scope: string,
+  content: string,
+  format?: TextFormat,
+): Promise<void> {
+  return invoke<void>("write_memory", { scope, content, format });
 }
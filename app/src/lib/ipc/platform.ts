@@ -1,5 +1,10 @@
 import { invoke } from "./invoke";
-import type { PlatformInfo, ProjectInfo } from "@/types";
+import type {
+  ArtifactLink,
+  PlatformInfo,
+  ProjectInfo,
+  ProjectWindow,
+} from "@/types";
 
 export async function getPlatformInfo(): Promise<PlatformInfo> {
   return invoke<PlatformInfo>("get_platform_info");
@@ -12,3 +17,50 @@ export async function getProjectInfo(): Promise<ProjectInfo> {
 export async function openProject(path: string): Promise<ProjectInfo> {
   return invoke<ProjectInfo>("open_project", { path });
 }
+
+/**
+ * Last-resort recovery: clears poisoned state locks and unbinds this
+ * window's project and file watcher. Reopen the project afterwards.
+ */
+export async function resetAppState(): Promise<ProjectInfo> {
+  return invoke<ProjectInfo>("reset_app_state");
+}
+
+/** A package's own `.claude` directory inside the open project. */
+export interface NestedClaudeDir {
+  /** Directory holding `.claude`, relative to the project root. */
+  package: string;
+  path: string;
+  /** Scope address (`project:<package>`) for the agents, skills, and settings commands. */
+  scope: string;
+  agents: number;
+  skills: number;
+  hasSettings: boolean;
+  hasLocalSettings: boolean;
+}
+
+export async function listNestedClaudeDirs(maxDepth?: number): Promise<NestedClaudeDir[]> {
+  return invoke<NestedClaudeDir[]>("list_nested_claude_dirs", { maxDepth });
+}
+
+/** Takes the artifact a deep link asked this window to show, if any. */
+export async function takePendingDeepLink(): Promise<ArtifactLink | null> {
+  return invoke<ArtifactLink | null>("take_pending_deep_link");
+}
+
+/** A zip of diagnostics, with secrets masked, to attach to bug reports. */
+export interface SupportBundleResult {
+  path: string;
+  /** Entries in the archive, in order. */
+  files: string[];
+}
+
+/** Writes a support bundle to `destination`, or under the app's reports directory. */
+export async function generateSupportBundle(destination?: string): Promise<SupportBundleResult> {
+  return invoke<SupportBundleResult>("generate_support_bundle", { destination });
+}
+
+/** Opens `path` in a separate window, or focuses the window already showing it. */
+export async function openProjectWindow(path: string): Promise<ProjectWindow> {
+  return invoke<ProjectWindow>("open_project_window", { path });
+}
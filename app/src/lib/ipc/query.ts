@@ -0,0 +1,32 @@
+import { invoke } from "./invoke";
+
+export type QueryOp = "eq" | "ne" | "contains" | "exists" | "missing";
+
+/** One condition on a frontmatter field; `value` is needed by eq, ne, and contains. */
+export interface FieldCondition {
+  field: string;
+  op: QueryOp;
+  value?: string | number | boolean;
+}
+
+/** An agent or skill whose frontmatter met every condition. */
+export interface ArtifactMatch {
+  scope: string;
+  /** Agent filename or skill directory. */
+  name: string;
+  path: string;
+  frontmatter: Record<string, unknown>;
+  bodyPreview: string;
+}
+
+/**
+ * Lists the agents or skills whose frontmatter meets every condition of
+ * `filter`, in both scopes unless `scope` is given.
+ */
+export async function queryArtifacts(
+  kind: "agent" | "skill",
+  filter: FieldCondition[],
+  scope?: string,
+): Promise<ArtifactMatch[]> {
+  return invoke<ArtifactMatch[]>("query_artifacts", { kind, filter, scope: scope ?? null });
+}
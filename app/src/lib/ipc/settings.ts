@@ -1,14 +1,83 @@
 import { invoke } from "./invoke";
-import type { ConfigScope, WritableScope, Settings } from "@/types";
+import type { ConfigScope, WritableScope, Settings, TextFormat } from "@/types";
 
 export async function readSettings(scope: ConfigScope): Promise<Settings | null> {
   return invoke<Settings | null>("read_settings", { scope });
 }
 
-export async function writeSettings(scope: WritableScope, settings: Settings): Promise<void> {
-  return invoke<void>("write_settings", { scope, settings });
+export async function readSettingsFormat(scope: ConfigScope): Promise<TextFormat | null> {
+  return invoke<TextFormat | null>("read_settings_format", { scope });
+}
+
+export async function writeSettings(
+  scope: WritableScope,
+  settings: Settings,
+  format?: TextFormat,
+): Promise<void> {
+  return invoke<void>("write_settings", { scope, settings, format });
 }
 
 export async function ensureClaudeDir(): Promise<void> {
   return invoke<void>("ensure_claude_dir");
 }
+
+/** Paths a scaffold command created, and those it found already in place. */
+export interface ScaffoldReport {
+  created: string[];
+  existing: string[];
+}
+
+export type SettingsTemplate = "minimal" | "cautious" | "accept_edits";
+
+/**
+ * Sets up the user config dir: starter settings.json, empty agents/ and
+ * commands/, and optionally a CLAUDE.md skeleton. Existing files are kept.
+ */
+export async function initializeClaudeConfig(options?: {
+  template?: SettingsTemplate;
+  claudeMd?: boolean;
+}): Promise<ScaffoldReport> {
+  return invoke<ScaffoldReport>("initialize_claude_config", { options });
+}
+
+export interface ProjectScaffoldReport extends ScaffoldReport {
+  /** Files the team template wrote and skipped, when one was applied. */
+  template: { written: string[]; skipped: string[] } | null;
+  /** `.gitignore` patterns found missing and those added. */
+  gitignore: { path: string; missing: string[]; added: string[] } | null;
+}
+
+/**
+ * Sets up the open project's `.claude/`: applies a team template if given,
+ * creates missing starter files, and updates `.gitignore` for local files.
+ */
+export async function scaffoldProjectClaude(options?: {
+  settingsTemplate?: SettingsTemplate;
+  teamTemplate?: string;
+  variables?: Record<string, string>;
+  gitignore?: boolean;
+}): Promise<ProjectScaffoldReport> {
+  return invoke<ProjectScaffoldReport>("scaffold_project_claude", { options });
+}
+
+export interface ModelEntry {
+  id: string;
+  source: "availableModels" | "alias" | "known" | "api";
+  description: string | null;
+  /** False when `availableModels` is set and does not list this model. */
+  allowed: boolean;
+}
+
+export interface ModelCatalog {
+  models: ModelEntry[];
+  live: boolean;
+  liveError: string | null;
+}
+
+/**
+ * Lists selectable models: `availableModels`, aliases, known model names,
+ * and with `live`, the models API for the configured API key.
+ */
+export async function listAvailableModels(live?: boolean): Promise<ModelCatalog> {
+  return invoke<ModelCatalog>("list_available_models", { live });
+}
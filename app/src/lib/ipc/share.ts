@@ -0,0 +1,91 @@
+import { invoke } from "./invoke";
+
+export type ArtifactKind = "agent" | "skill" | "permissions" | "mcp_server";
+
+export type SnippetFormat = "markdown" | "json";
+
+/** One artifact rendered for pasting into a chat, issue, or gist. */
+export interface ArtifactSnippet {
+  kind: ArtifactKind;
+  name: string;
+  format: SnippetFormat;
+  content: string;
+  /** Secret values replaced with `[redacted]`, as JSON paths. */
+  redacted: string[];
+  /** Skill files the snippet does not carry. */
+  omitted: string[];
+}
+
+/**
+ * Renders an agent, skill, permission set, or MCP server as a snippet.
+ * For permissions, `name` optionally picks one of allow/ask/deny.
+ */
+export async function exportArtifact(
+  kind: ArtifactKind,
+  scope: string,
+  name?: string,
+  format?: SnippetFormat,
+): Promise<ArtifactSnippet> {
+  return invoke<ArtifactSnippet>("export_artifact", { kind, scope, name, format });
+}
+
+export type DuplicateReason =
+  | "same_name"
+  | "similar_name"
+  | "same_description"
+  | "identical_body"
+  | "similar_body";
+
+/** An existing agent or skill resembling the one being imported or copied. */
+export interface DuplicateMatch {
+  scope: string;
+  name: string;
+  path: string;
+  reasons: DuplicateReason[];
+  /** Body similarity, from 0 to 1. */
+  similarity: number;
+}
+
+/** What `importArtifact` wrote, or would write on a dry run. */
+export interface ArtifactImport {
+  kind: ArtifactKind;
+  name: string;
+  path: string;
+  exists: boolean;
+  content: unknown;
+  errors: { path: string; message: string; code: string }[];
+  warnings: { path: string; message: string; code: string }[];
+  written: boolean;
+  /** Existing agents or skills that look like this one. */
+  duplicates: DuplicateMatch[];
+}
+
+/**
+ * Imports a pasted artifact (or the raw file at an https:// URL) into
+ * `scope`. With `dryRun`, only validates and reports what would be written.
+ */
+export async function importArtifact(
+  kind: ArtifactKind,
+  scope: string,
+  contentOrUrl: string,
+  options?: { dryRun?: boolean; overwrite?: boolean },
+): Promise<ArtifactImport> {
+  return invoke<ArtifactImport>("import_artifact", {
+    kind,
+    scope,
+    contentOrUrl,
+    dryRun: options?.dryRun,
+    overwrite: options?.overwrite,
+  });
+}
+
+/**
+ * Finds existing agents or skills that look like `source`: pasted content,
+ * or an existing artifact about to be copied (left out of the results).
+ */
+export async function findDuplicateArtifacts(
+  kind: "agent" | "skill",
+  source: { content: string } | { scope: string; name: string },
+): Promise<DuplicateMatch[]> {
+  return invoke<DuplicateMatch[]>("find_duplicate_artifacts", { kind, ...source });
+}
@@ -1,4 +1,5 @@
 import { invoke } from "./invoke";
+import type { TextFormat } from "@/types";
 
 export interface SkillEntry {
   directory: string;
@@ -14,7 +15,7 @@ export async function listSkills(scope?: string): Promise<SkillEntry[]> {
 export async function readSkill(
   scope: string,
   directory: string,
-): Promise<{ frontmatter: Record<string, unknown>; body: string }> {
+): Promise<{ frontmatter: Record<string, unknown>; body: string; format: TextFormat }> {
   return invoke("read_skill", { scope, directory });
 }
 
@@ -23,8 +24,9 @@ export async function writeSkill(
   directory: string,
   frontmatter: Record<string, unknown>,
   body: string,
+  format?: TextFormat,
 ): Promise<void> {
-  return invoke<void>("write_skill", { scope, directory, frontmatter, body });
+  return invoke<void>("write_skill", { scope, directory, frontmatter, body, format });
 }
 
 export async function deleteSkill(scope: string, directory: string): Promise<void> {
@@ -44,6 +46,7 @@ export async function writeSkillFile(
   directory: string,
   filename: string,
   content: string,
+  format?: TextFormat,
 ): Promise<void> {
-  return invoke<void>("write_skill_file", { scope, directory, filename, content });
+  return invoke<void>("write_skill_file", { scope, directory, filename, content, format });
 }
@@ -14,6 +14,15 @@ export interface PermissionRuleResult {
   error: string | null;
 }
 
+export interface ToolInfo {
+  name: string;
+  description: string;
+  /** Grammar of the permission rule specifier; null if the tool takes none. */
+  specifier: string | null;
+  source: "builtin" | "mcp";
+  server: string | null;
+}
+
 export interface HookMatcherResult {
   valid: boolean;
   error: string | null;
@@ -27,6 +36,10 @@ export async function validatePermissionRule(rule: string): Promise<PermissionRu
   return invoke<PermissionRuleResult>("validate_permission_rule", { rule });
 }
 
+export async function listKnownTools(): Promise<ToolInfo[]> {
+  return invoke<ToolInfo[]>("list_known_tools");
+}
+
 export async function validateHookMatcher(pattern: string): Promise<HookMatcherResult> {
   return invoke<HookMatcherResult>("validate_hook_matcher", { pattern });
 }
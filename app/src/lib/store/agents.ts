@@ -4,6 +4,8 @@ import {
   listAgents,
   readAgent,
   writeAgent,
+  writeAgentBody,
+  writeAgentFrontmatter,
   deleteAgent,
   type AgentEntry,
 } from "../ipc/agents";
@@ -35,6 +37,21 @@ interface AgentsStore {
   removeAgent: (scope: string, filename: string) => Promise<void>;
 }
 
+/** Frontmatter keys that differ between two versions; removed keys map to null. */
+function frontmatterPatch(
+  before: Record<string, unknown>,
+  after: Record<string, unknown>,
+): Record<string, unknown> {
+  const patch: Record<string, unknown> = {};
+  for (const key of Object.keys(before)) {
+    if (!(key in after)) patch[key] = null;
+  }
+  for (const [key, value] of Object.entries(after)) {
+    if (JSON.stringify(before[key]) !== JSON.stringify(value)) patch[key] = value;
+  }
+  return patch;
+}
+
 export const useAgentsStore = create<AgentsStore>((set, get) => ({
   entries: [],
   selected: null,
@@ -68,7 +85,22 @@ export const useAgentsStore = create<AgentsStore>((set, get) => ({
   },
 
   saveAgent: async (scope, filename, frontmatter, body) => {
-    await writeAgent(scope, filename, frontmatter, body);
+    const { selected, selectedKey } = get();
+    const loaded =
+      selected && selectedKey?.scope === scope && selectedKey?.filename === filename
+        ? selected
+        : null;
+    // Edit the loaded file in place where possible so untouched parts keep
+    // their comments and formatting.
+    const patch = loaded ? frontmatterPatch(loaded.frontmatter, frontmatter) : null;
+    if (loaded && patch && Object.keys(patch).length === 0) {
+      if (body !== loaded.body) await writeAgentBody(scope, filename, body);
+    } else if (loaded && patch && body === loaded.body) {
+      await writeAgentFrontmatter(scope, filename, patch);
+    } else {
+      await writeAgent(scope, filename, frontmatter, body);
+    }
+    if (loaded) set({ selected: { frontmatter, body } });
     // Reload list to reflect changes
     await get().loadList();
   },
@@ -1,21 +1,27 @@
 import { create } from "zustand";
-import type { EffectiveConfig } from "@/lib/ipc/effective";
-import { computeEffectiveConfig } from "@/lib/ipc/effective";
+import type { EffectiveConfig, MergePolicy } from "@/lib/ipc/effective";
+import { computeEffectiveConfig, getMergePolicy } from "@/lib/ipc/effective";
 
 interface EffectiveStore {
   config: EffectiveConfig | null;
+  /** Precedence and merge rules the config was computed with. */
+  policy: MergePolicy | null;
   loading: boolean;
   recompute: () => Promise<void>;
 }
 
-export const useEffectiveStore = create<EffectiveStore>((set) => ({
+export const useEffectiveStore = create<EffectiveStore>((set, get) => ({
   config: null,
+  policy: null,
   loading: false,
   recompute: async () => {
     set({ loading: true });
     try {
-      const config = await computeEffectiveConfig();
-      set({ config, loading: false });
+      const [config, policy] = await Promise.all([
+        computeEffectiveConfig(),
+        get().policy ?? getMergePolicy(),
+      ]);
+      set({ config, policy, loading: false });
     } catch {
       set({ loading: false });
     }
@@ -1,10 +1,13 @@
 import { create } from "zustand";
 import type { LoadStatus, MemoryScope } from "@/types";
-import { readMemory, writeMemory } from "../ipc/memory";
+import { readMemory, readMemoryChunk, writeMemory } from "../ipc/memory";
 import { createDebouncedSaver } from "../config/debounce";
 
 const MEMORY_SCOPES: MemoryScope[] = ["user", "project", "project_root", "local"];
 
+/** Bytes of an oversized memory file shown as a read-only preview. */
+const PREVIEW_BYTES = 256 * 1024;
+
 interface MemoryStore {
   user: string | null | undefined;
   project: string | null | undefined;
@@ -13,6 +16,8 @@ interface MemoryStore {
 
   dirtyScopes: Set<MemoryScope>;
   status: Record<MemoryScope, LoadStatus>;
+  /** Size in bytes of files too large to edit; their content is a preview. */
+  oversized: Partial<Record<MemoryScope, number>>;
 
   load: (scope: MemoryScope) => Promise<void>;
   loadAll: () => Promise<void>;
@@ -45,16 +50,26 @@ export const useMemoryStore = create<MemoryStore>((set, get) => ({
     local: { state: "idle" },
   },
 
+  oversized: {},
+
   load: async (scope) => {
     set((s) => ({
       status: { ...s.status, [scope]: { state: "loading" } },
     }));
     try {
-      const content = await readMemory(scope);
+      const file = await readMemory(scope);
+      let content = file?.content ?? null;
+      if (file && file.content === null) {
+        content = (await readMemoryChunk(scope, 0, PREVIEW_BYTES)).content;
+      }
       const key = scopeKey(scope);
       set((s) => ({
         [key]: content,
         status: { ...s.status, [scope]: { state: "loaded" } },
+        oversized: {
+          ...s.oversized,
+          [scope]: file && file.content === null ? file.sizeBytes : undefined,
+        },
       }));
     } catch (error) {
       set((s) => ({
@@ -74,6 +89,7 @@ export const useMemoryStore = create<MemoryStore>((set, get) => ({
   },
 
   update: (scope, content) => {
+    if (get().oversized[scope] !== undefined) return;
     const key = scopeKey(scope);
     const newDirty = new Set(get().dirtyScopes);
     newDirty.add(scope);
@@ -90,6 +106,8 @@ export const useMemoryStore = create<MemoryStore>((set, get) => ({
     const state = get();
     const content = state[scopeKey(scope)];
     if (content === null || content === undefined) return;
+    // Never overwrite a large file with its truncated preview.
+    if (state.oversized[scope] !== undefined) return;
 
     try {
       await writeMemory(scope, content);
@@ -20,15 +20,25 @@ export type LoadStatus =
   | { state: "loaded" }
   | { state: "error"; error: IpcError };
 
-// IPC error from Rust backend
-export type IpcError =
+// IPC error from Rust backend. `retryable` is set when repeating the same
+// call may succeed; `suggestion` says what the user can do about it.
+export type IpcError = IpcErrorDetail & { retryable: boolean; suggestion: string | null };
+
+export type IpcErrorDetail =
   | { kind: "not_found"; path: string }
   | { kind: "permission_denied"; path: string }
   | { kind: "parse_error"; path: string; message: string }
   | { kind: "write_failed"; path: string; message: string }
   | { kind: "validation_error"; errors: ValidationError[] }
   | { kind: "no_project"; message: string }
-  | { kind: "platform_error"; message: string };
+  | { kind: "platform_error"; message: string }
+  | { kind: "read_only"; message: string }
+  | { kind: "timeout"; operation: string; seconds: number }
+  | { kind: "conflict"; path: string; message: string }
+  | { kind: "locked"; path: string; message: string }
+  | { kind: "invalid_argument"; argument: string; message: string }
+  | { kind: "confirmation_required"; message: string }
+  | { kind: "unsupported"; message: string };
 
 export interface ValidationError {
   path: string;
@@ -42,14 +52,33 @@ export interface ValidationWarning {
   code: string;
 }
 
+/** Line endings and BOM of a file on disk; writes keep them unless overridden. */
+export interface TextFormat {
+  lineEnding: "lf" | "crlf";
+  bom: boolean;
+}
+
 export interface PlatformInfo {
   os: "macos" | "linux" | "windows";
   isWsl: boolean;
   shell: string;
   claudeCodeInstalled: boolean;
   claudeCodePath: string | null;
+  claudeCodeVersion: string | null;
   configDir: string;
   managedSettingsDir: string | null;
+  /** Where ssenrah keeps its own state. */
+  appDir: string;
+  /** Running with state beside the executable (`portable.flag` or `--portable`). */
+  portable: boolean;
+  proxies: DetectedProxy[];
+}
+
+export interface DetectedProxy {
+  source: "env" | "macos" | "windows";
+  httpProxy: string | null;
+  httpsProxy: string | null;
+  noProxy: string | null;
 }
 
 export interface ProjectInfo {
@@ -58,6 +87,20 @@ export interface ProjectInfo {
   gitRoot: string | null;
 }
 
+export interface ProjectWindow {
+  /** Tauri window label, `project-{hash}`. */
+  label: string;
+  projectRoot: string;
+}
+
+/** Artifact a `ssenrah://` deep link asked a window to show. */
+export interface ArtifactLink {
+  kind: "agent" | "skill" | "output-style" | "settings" | "mcp" | "memory";
+  scope: "user" | "project" | "local";
+  /** File name for agents, skills and output styles. */
+  name: string | null;
+}
+
 export interface ConflictInfo {
   id: string;
   scope: ConfigScope;